@@ -2,6 +2,7 @@
 //!
 //! This build script handles platform-specific configuration:
 //! - Windows: Embeds the application manifest for long path support (>260 chars)
+//! - All platforms: Records build metadata for `rustdupe version --json`
 //!
 //! # Windows Long Path Support
 //!
@@ -13,12 +14,30 @@
 //! which, combined with the Windows 10 v1607+ registry setting, enables
 //! paths up to 32,767 characters.
 //!
+//! # Build Metadata
+//!
+//! The following variables are passed to the compiler and read by the
+//! `version` module with `option_env!`:
+//!
+//! - `RUSTDUPE_GIT_COMMIT`: `git rev-parse HEAD`, empty outside a git checkout
+//! - `RUSTDUPE_BUILD_TIMESTAMP`: seconds since the Unix epoch, taken from
+//!   `SOURCE_DATE_EPOCH` when set so reproducible builds stay reproducible
+//! - `RUSTDUPE_BUILD_TARGET` / `RUSTDUPE_BUILD_PROFILE`: target triple and
+//!   Cargo profile
+//! - `RUSTDUPE_FEATURES`: comma-separated enabled Cargo features
+//!
 //! # Usage
 //!
 //! This script runs automatically during `cargo build`. No manual intervention
-//! is required. On non-Windows platforms, the script does nothing.
+//! is required. Build metadata is emitted on every platform; the manifest is
+//! only embedded on Windows.
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 fn main() {
+    emit_build_metadata();
+
     // Only compile and embed the manifest on Windows
     #[cfg(windows)]
     {
@@ -38,3 +57,53 @@ fn main() {
         // The build script exits successfully without embedding any resources
     }
 }
+
+/// Pass build metadata to the compiler as environment variables.
+fn emit_build_metadata() {
+    let commit = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=RUSTDUPE_GIT_COMMIT={}", commit);
+
+    let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs())
+        });
+    println!("cargo:rustc-env=RUSTDUPE_BUILD_TIMESTAMP={}", timestamp);
+
+    for (key, name) in [
+        ("TARGET", "RUSTDUPE_BUILD_TARGET"),
+        ("PROFILE", "RUSTDUPE_BUILD_PROFILE"),
+    ] {
+        let value = std::env::var(key).unwrap_or_default();
+        println!("cargo:rustc-env={}={}", name, value);
+    }
+
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|f| f.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=RUSTDUPE_FEATURES={}", features.join(","));
+
+    // Refresh the commit when HEAD moves and the build date when the sources
+    // change; without `src` here Cargo would keep the first build's date
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src");
+    for path in [".git/HEAD", ".git/refs/heads"] {
+        if std::path::Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
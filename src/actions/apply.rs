@@ -0,0 +1,750 @@
+//! Apply deletion decisions from an edited report (`rustdupe apply`).
+//!
+//! Reports are often reviewed away from the machine that produced them: a
+//! CSV is opened in a spreadsheet, someone marks the copies to remove, and
+//! the file comes back later. [`ApplyPlan`] reads such a report, and
+//! [`ApplyPlan::verify`] checks every marked file against what the report
+//! recorded before anything is deleted.
+//!
+//! # Input Formats
+//!
+//! - **CSV/TSV** as written by `--output csv` (or `tsv`), plus a decision
+//!   column named `delete`, `decision` or `action`. `delete`, `yes`, `y`,
+//!   `true`, `1` and `x` mark a file for deletion; an empty cell, `keep`,
+//!   `no`, `n`, `false` and `0` keep it.
+//! - **JSON** as written by `--output json`, where each group may carry a
+//!   `delete` array listing the paths to remove from that group.
+//!
+//! # Verification
+//!
+//! A marked file is only scheduled if it still exists with the recorded
+//! size and, for CSV input, the recorded modification time. In paranoid
+//! mode its content is also rehashed and compared with the group hash;
+//! similar-match groups share no content hash, so their files are skipped.
+//! Groups where every file is marked, or where no kept file is left on
+//! disk, are skipped entirely so a stale report can never remove the last
+//! copy.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::actions::apply::ApplyPlan;
+//! use std::path::Path;
+//!
+//! let plan = ApplyPlan::load(Path::new("reviewed.csv")).unwrap();
+//! let outcome = plan.verify(None);
+//! for skipped in &outcome.skipped {
+//!     eprintln!("Skipping {}: {}", skipped.path.display(), skipped.reason);
+//! }
+//! println!("{} file(s) ready to delete", outcome.scheduled.len());
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::DateTime;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::scanner::{hex_to_hash, Hash, Hasher};
+
+/// Column names accepted as the decision column, in order of preference.
+pub const DECISION_COLUMNS: [&str; 3] = ["delete", "decision", "action"];
+
+/// Errors that can occur while reading a decision file.
+#[derive(Debug, Error)]
+pub enum ApplyError {
+    /// The input file could not be read.
+    #[error("failed to read {path}: {source}")]
+    Io {
+        /// Path of the input file
+        path: PathBuf,
+        /// Underlying I/O error
+        #[source]
+        source: io::Error,
+    },
+
+    /// The CSV input is malformed.
+    #[error("invalid CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    /// The JSON input is malformed.
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// A required CSV column is missing.
+    #[error("missing required column: {0}")]
+    MissingColumn(&'static str),
+
+    /// A CSV row or JSON group holds an invalid value.
+    #[error("entry {entry}: {message}")]
+    InvalidEntry {
+        /// CSV line number or 1-based JSON group number
+        entry: usize,
+        /// What was wrong with the entry
+        message: String,
+    },
+}
+
+/// One file listed in a decision file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplyEntry {
+    /// Group the file belongs to (a [group ID](crate::duplicates::group_ids)
+    /// or, in older reports, a group number)
+    pub group_id: String,
+    /// Which group of the decision file the entry came from, counting from 0
+    ///
+    /// Survivors are checked per group occurrence, so two groups that share
+    /// an ID in an older report never vouch for each other. CSV rows of one
+    /// group must be consecutive; a group split up by reordering the rows
+    /// counts as several groups.
+    pub group: usize,
+    /// Path of the file
+    pub path: PathBuf,
+    /// Size recorded in the report
+    pub size: u64,
+    /// Modification time recorded in the report, if any
+    pub modified: Option<SystemTime>,
+    /// Group content hash recorded in the report, if any
+    pub hash: Option<Hash>,
+    /// Whether the file is marked for deletion
+    pub delete: bool,
+}
+
+/// Why a marked file was not scheduled for deletion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The file no longer exists
+    Missing,
+    /// The file could not be inspected
+    Unreadable(String),
+    /// The file size differs from the report
+    SizeChanged {
+        /// Size recorded in the report
+        expected: u64,
+        /// Size on disk
+        actual: u64,
+    },
+    /// The modification time differs from the report
+    ModifiedChanged,
+    /// The content no longer matches the group hash (paranoid mode)
+    HashChanged,
+    /// No other file of the group would survive
+    NoSurvivor,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing => write!(f, "file no longer exists"),
+            Self::Unreadable(e) => write!(f, "cannot inspect file: {}", e),
+            Self::SizeChanged { expected, actual } => {
+                write!(f, "size changed ({} -> {} bytes)", expected, actual)
+            }
+            Self::ModifiedChanged => write!(f, "modified since the report was written"),
+            Self::HashChanged => write!(f, "content no longer matches the group hash"),
+            Self::NoSurvivor => write!(f, "no kept copy of this group remains"),
+        }
+    }
+}
+
+/// A marked file that will not be deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFile {
+    /// Path of the file
+    pub path: PathBuf,
+    /// Why it was skipped
+    pub reason: SkipReason,
+}
+
+/// Result of verifying an [`ApplyPlan`] against the filesystem.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplyOutcome {
+    /// Files that are marked and unchanged, in report order
+    pub scheduled: Vec<PathBuf>,
+    /// Marked files that were skipped, in report order
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// Deletion decisions read from an edited report.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplyPlan {
+    /// Every file listed in the report, in report order
+    pub entries: Vec<ApplyEntry>,
+}
+
+/// JSON report envelope, reduced to the fields `apply` needs.
+#[derive(Debug, Deserialize)]
+struct JsonDecisionDocument {
+    data: JsonDecisionData,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonDecisionData {
+    duplicates: Vec<JsonDecisionGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonDecisionGroup {
+    #[serde(default)]
+    group_id: String,
+    hash: String,
+    size: u64,
+    files: Vec<String>,
+    #[serde(default)]
+    delete: Vec<String>,
+}
+
+impl ApplyPlan {
+    /// Read a decision file, detecting JSON, TSV or CSV from its content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self, ApplyError> {
+        let content = fs::read_to_string(path).map_err(|source| ApplyError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        // Spreadsheets may prepend a byte order mark
+        let content = content.trim_start_matches('\u{feff}');
+
+        if content.trim_start().starts_with('{') {
+            return Self::from_json(content.as_bytes());
+        }
+        let header = content.lines().next().unwrap_or_default();
+        let delimiter = if header.contains('\t') && !header.contains(',') {
+            b'\t'
+        } else {
+            b','
+        };
+        Self::from_csv(content.as_bytes(), delimiter)
+    }
+
+    /// Parse a CSV report with a decision column.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required column is missing or a row holds an
+    /// invalid value.
+    pub fn from_csv<R: Read>(reader: R, delimiter: u8) -> Result<Self, ApplyError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(reader);
+
+        let headers = reader.headers()?.clone();
+        let column = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+        let group_col = column("group_id").ok_or(ApplyError::MissingColumn("group_id"))?;
+        let path_col = column("path").ok_or(ApplyError::MissingColumn("path"))?;
+        let size_col = column("size").ok_or(ApplyError::MissingColumn("size"))?;
+        let decision_col = DECISION_COLUMNS
+            .iter()
+            .find_map(|name| column(name))
+            .ok_or(ApplyError::MissingColumn("delete"))?;
+        let modified_col = column("modified");
+        let hash_col = column("hash");
+
+        let mut entries: Vec<ApplyEntry> = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            let entry = record.position().map_or(0, |p| p.line() as usize);
+            let invalid = |message: String| ApplyError::InvalidEntry { entry, message };
+            let field = |col: usize| record.get(col).unwrap_or_default();
+
+            let path = field(path_col);
+            if path.is_empty() {
+                return Err(invalid("empty path".to_string()));
+            }
+            let group_id = field(group_col).to_string();
+            if group_id.is_empty() {
+                return Err(invalid("empty group_id".to_string()));
+            }
+            let size = field(size_col)
+                .parse()
+                .map_err(|_| invalid(format!("invalid size '{}'", field(size_col))))?;
+            let delete = parse_decision(field(decision_col))
+                .ok_or_else(|| invalid(format!("invalid decision '{}'", field(decision_col))))?;
+            let modified = match modified_col.map(field).filter(|s| !s.is_empty()) {
+                Some(value) => Some(
+                    DateTime::parse_from_rfc3339(value)
+                        .map(SystemTime::from)
+                        .map_err(|_| invalid(format!("invalid modified time '{}'", value)))?,
+                ),
+                None => None,
+            };
+            let hash = match hash_col.map(field).filter(|s| !s.is_empty()) {
+                Some(value) => Some(
+                    hex_to_hash(value)
+                        .ok_or_else(|| invalid(format!("invalid hash '{}'", value)))?,
+                ),
+                None => None,
+            };
+
+            let group = match entries.last() {
+                Some(last) if last.group_id == group_id => last.group,
+                Some(last) => last.group + 1,
+                None => 0,
+            };
+            entries.push(ApplyEntry {
+                group_id,
+                group,
+                path: PathBuf::from(path),
+                size,
+                modified,
+                hash,
+                delete,
+            });
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Parse a JSON report whose groups list the paths to delete in `delete`.
+    ///
+    /// JSON reports do not record modification times, so only sizes (and
+    /// hashes in paranoid mode) are verified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is malformed, a hash is invalid, or
+    /// a `delete` entry is not one of the group's files.
+    pub fn from_json<R: Read>(reader: R) -> Result<Self, ApplyError> {
+        let document: JsonDecisionDocument = serde_json::from_reader(reader)?;
+
+        let mut entries = Vec::new();
+        for (idx, group) in document.data.duplicates.into_iter().enumerate() {
+            let invalid = |message: String| ApplyError::InvalidEntry {
+                entry: idx + 1,
+                message,
+            };
+            // Reports written before group IDs existed fall back to positions
+            let group_id = if group.group_id.is_empty() {
+                (idx + 1).to_string()
+            } else {
+                group.group_id
+            };
+            let hash = hex_to_hash(&group.hash)
+                .ok_or_else(|| invalid(format!("invalid hash '{}'", group.hash)))?;
+            if let Some(unknown) = group.delete.iter().find(|p| !group.files.contains(*p)) {
+                return Err(invalid(format!(
+                    "'{}' is not a file of this group",
+                    unknown
+                )));
+            }
+
+            for path in &group.files {
+                entries.push(ApplyEntry {
+                    group_id: group_id.clone(),
+                    group: idx,
+                    path: PathBuf::from(path),
+                    size: group.size,
+                    modified: None,
+                    hash: Some(hash),
+                    delete: group.delete.contains(path),
+                });
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Number of files marked for deletion.
+    #[must_use]
+    pub fn marked_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.delete).count()
+    }
+
+    /// Check every marked file against the filesystem.
+    ///
+    /// With a `hasher` (paranoid mode), marked files are also rehashed and
+    /// compared with the recorded group hash.
+    ///
+    /// Marked files are only scheduled if an unmarked file of their group
+    /// passes the same checks, so a kept copy that changed since the report
+    /// does not count as a survivor.
+    #[must_use]
+    pub fn verify(&self, hasher: Option<&Hasher>) -> ApplyOutcome {
+        let mut survivors: HashMap<usize, bool> = HashMap::new();
+        for entry in self.entries.iter().filter(|e| !e.delete) {
+            let survives = survivors.entry(entry.group).or_default();
+            if !*survives {
+                *survives = check_unchanged(entry, hasher).is_ok();
+            }
+        }
+
+        let mut outcome = ApplyOutcome::default();
+        for entry in self.entries.iter().filter(|e| e.delete) {
+            let check = if survivors.get(&entry.group) == Some(&true) {
+                check_unchanged(entry, hasher)
+            } else {
+                Err(SkipReason::NoSurvivor)
+            };
+            match check {
+                Ok(()) => outcome.scheduled.push(entry.path.clone()),
+                Err(reason) => outcome.skipped.push(SkippedFile {
+                    path: entry.path.clone(),
+                    reason,
+                }),
+            }
+        }
+        outcome
+    }
+}
+
+/// Parse a decision cell; `None` if the value is not recognized.
+///
+/// # Example
+///
+/// ```
+/// use rustdupe::actions::apply::parse_decision;
+///
+/// assert_eq!(parse_decision("Delete"), Some(true));
+/// assert_eq!(parse_decision(""), Some(false));
+/// assert_eq!(parse_decision("maybe"), None);
+/// ```
+#[must_use]
+pub fn parse_decision(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "delete" | "del" | "d" | "yes" | "y" | "true" | "1" | "x" => Some(true),
+        "" | "keep" | "k" | "no" | "n" | "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Compare a file on disk with what the report recorded.
+fn check_unchanged(entry: &ApplyEntry, hasher: Option<&Hasher>) -> Result<(), SkipReason> {
+    let metadata = fs::metadata(&entry.path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => SkipReason::Missing,
+        _ => SkipReason::Unreadable(e.to_string()),
+    })?;
+    if !metadata.is_file() {
+        return Err(SkipReason::Missing);
+    }
+    if metadata.len() != entry.size {
+        return Err(SkipReason::SizeChanged {
+            expected: entry.size,
+            actual: metadata.len(),
+        });
+    }
+    if let Some(expected) = entry.modified {
+        match metadata.modified() {
+            Ok(actual) if actual == expected => {}
+            Ok(_) => return Err(SkipReason::ModifiedChanged),
+            Err(e) => return Err(SkipReason::Unreadable(e.to_string())),
+        }
+    }
+    if let (Some(hasher), Some(expected)) = (hasher, entry.hash) {
+        let actual = hasher
+            .full_hash(&entry.path)
+            .map_err(|e| SkipReason::Unreadable(e.to_string()))?;
+        if actual != expected {
+            return Err(SkipReason::HashChanged);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn rfc3339(path: &Path) -> String {
+        let modified: DateTime<Utc> = fs::metadata(path).unwrap().modified().unwrap().into();
+        modified.to_rfc3339()
+    }
+
+    #[test]
+    fn test_parse_decision_values() {
+        for value in ["delete", "YES", " x ", "1", "true"] {
+            assert_eq!(parse_decision(value), Some(true), "{value}");
+        }
+        for value in ["", "keep", "No", "0"] {
+            assert_eq!(parse_decision(value), Some(false), "{value}");
+        }
+        assert_eq!(parse_decision("later"), None);
+    }
+
+    #[test]
+    fn test_csv_schedules_only_marked_and_unchanged_files() {
+        let dir = TempDir::new().unwrap();
+        let keep = dir.path().join("keep.txt");
+        let marked = dir.path().join("marked.txt");
+        let unmarked = dir.path().join("unmarked.txt");
+        let changed = dir.path().join("changed.txt");
+        let missing = dir.path().join("missing.txt");
+        for path in [&keep, &marked, &unmarked, &changed] {
+            fs::write(path, b"content").unwrap();
+        }
+
+        let hash = "00".repeat(32);
+        let mut csv = String::from("group_id,hash,path,size,modified,role,delete\n");
+        for (path, role, decision) in [
+            (&keep, "original", ""),
+            (&marked, "duplicate", "delete"),
+            (&unmarked, "duplicate", "keep"),
+            (&changed, "duplicate", "x"),
+        ] {
+            csv.push_str(&format!(
+                "1,{},{},7,{},{},{}\n",
+                hash,
+                path.display(),
+                rfc3339(path),
+                role,
+                decision
+            ));
+        }
+        csv.push_str(&format!(
+            "1,{},{},7,,duplicate,yes\n",
+            hash,
+            missing.display()
+        ));
+        // Modified after the report was written
+        fs::write(&changed, b"changed!").unwrap();
+
+        let plan = ApplyPlan::from_csv(csv.as_bytes(), b',').unwrap();
+        assert_eq!(plan.entries.len(), 5);
+        assert_eq!(plan.marked_count(), 3);
+
+        let outcome = plan.verify(None);
+        assert_eq!(outcome.scheduled, vec![marked]);
+        assert_eq!(
+            outcome.skipped,
+            vec![
+                SkippedFile {
+                    path: changed,
+                    reason: SkipReason::SizeChanged {
+                        expected: 7,
+                        actual: 8
+                    },
+                },
+                SkippedFile {
+                    path: missing,
+                    reason: SkipReason::Missing,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_group_without_survivor_is_skipped() {
+        let dir = TempDir::new().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+        fs::write(&a, b"same").unwrap();
+        fs::write(&b, b"same").unwrap();
+
+        let csv = format!(
+            "group_id\tpath\tsize\tdecision\n1\t{}\t4\tdelete\n1\t{}\t4\tdelete\n",
+            a.display(),
+            b.display()
+        );
+        let path = dir.path().join("decisions.tsv");
+        fs::write(&path, csv).unwrap();
+
+        let outcome = ApplyPlan::load(&path).unwrap().verify(None);
+        assert!(outcome.scheduled.is_empty());
+        assert_eq!(outcome.skipped.len(), 2);
+        assert!(outcome
+            .skipped
+            .iter()
+            .all(|s| s.reason == SkipReason::NoSurvivor));
+    }
+
+    #[test]
+    fn test_csv_group_ids_keep_groups_apart() {
+        let dir = TempDir::new().unwrap();
+        let paths: Vec<PathBuf> = ["a1", "a2", "b1", "b2"]
+            .iter()
+            .map(|name| dir.path().join(name))
+            .collect();
+        for path in &paths {
+            fs::write(path, b"same").unwrap();
+        }
+
+        // Group aaaa keeps a1; every file of group bbbb is marked
+        let mut csv = String::from("group_id,path,size,delete\n");
+        for (id, path, decision) in [
+            ("aaaaaaaaaaaa", &paths[0], ""),
+            ("aaaaaaaaaaaa", &paths[1], "x"),
+            ("bbbbbbbbbbbb", &paths[2], "x"),
+            ("bbbbbbbbbbbb", &paths[3], "x"),
+        ] {
+            csv.push_str(&format!("{},{},4,{}\n", id, path.display(), decision));
+        }
+
+        let outcome = ApplyPlan::from_csv(csv.as_bytes(), b',')
+            .unwrap()
+            .verify(None);
+        assert_eq!(outcome.scheduled, vec![paths[1].clone()]);
+        assert_eq!(outcome.skipped.len(), 2);
+        assert!(outcome
+            .skipped
+            .iter()
+            .all(|s| s.reason == SkipReason::NoSurvivor));
+    }
+
+    #[test]
+    fn test_groups_sharing_an_id_need_their_own_survivor() {
+        let dir = TempDir::new().unwrap();
+        let paths: Vec<PathBuf> = ["a1", "a2", "b1", "b2", "c1"]
+            .iter()
+            .map(|name| dir.path().join(name))
+            .collect();
+        for path in &paths {
+            fs::write(path, b"same").unwrap();
+        }
+
+        // Older reports could give two groups with one hash the same ID
+        let mut csv = String::from("group_id,path,size,delete\n");
+        for (id, path, decision) in [
+            ("070707070707", &paths[0], ""),
+            ("070707070707", &paths[1], "x"),
+            ("080808080808", &paths[4], ""),
+            ("070707070707", &paths[2], "x"),
+            ("070707070707", &paths[3], "x"),
+        ] {
+            csv.push_str(&format!("{},{},4,{}\n", id, path.display(), decision));
+        }
+        let outcome = ApplyPlan::from_csv(csv.as_bytes(), b',')
+            .unwrap()
+            .verify(None);
+        assert_eq!(outcome.scheduled, vec![paths[1].clone()]);
+        assert_eq!(outcome.skipped.len(), 2);
+        assert!(outcome
+            .skipped
+            .iter()
+            .all(|s| s.reason == SkipReason::NoSurvivor));
+
+        let group = |files: &[PathBuf], delete: &[PathBuf]| {
+            let names = |paths: &[PathBuf]| -> Vec<String> {
+                paths
+                    .iter()
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .collect()
+            };
+            serde_json::json!({
+                "group_id": "070707070707",
+                "hash": "07".repeat(32),
+                "size": 4,
+                "files": names(files),
+                "delete": names(delete),
+            })
+        };
+        let document = serde_json::json!({
+            "data": {
+                "duplicates": [
+                    group(&paths[0..2], &paths[1..2]),
+                    group(&paths[2..4], &paths[2..4]),
+                ],
+            },
+        });
+        let outcome = ApplyPlan::from_json(document.to_string().as_bytes())
+            .unwrap()
+            .verify(None);
+        assert_eq!(outcome.scheduled, vec![paths[1].clone()]);
+        assert_eq!(outcome.skipped.len(), 2);
+    }
+
+    #[test]
+    fn test_csv_errors() {
+        let missing = ApplyPlan::from_csv("group_id,path,size\n1,/a,1\n".as_bytes(), b',');
+        assert!(matches!(missing, Err(ApplyError::MissingColumn("delete"))));
+
+        let invalid =
+            ApplyPlan::from_csv("group_id,path,size,delete\n1,/a,1,maybe\n".as_bytes(), b',');
+        assert!(matches!(
+            invalid,
+            Err(ApplyError::InvalidEntry { entry: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_json_decisions_with_paranoid_hash() {
+        let dir = TempDir::new().unwrap();
+        let a = dir.path().join("a.bin");
+        let b = dir.path().join("b.bin");
+        fs::write(&a, b"payload").unwrap();
+        fs::write(&b, b"payload").unwrap();
+        let hasher = Hasher::new();
+        let hash = crate::scanner::hash_to_hex(&hasher.full_hash(&a).unwrap());
+
+        let document = serde_json::json!({
+            "schema_version": "2.0",
+            "data": {
+                "duplicates": [{
+                    "hash": hash,
+                    "size": 7,
+                    "files": [a.to_string_lossy(), b.to_string_lossy()],
+                    "delete": [b.to_string_lossy()],
+                }],
+            },
+        });
+        let path = dir.path().join("reviewed.json");
+        fs::write(&path, document.to_string()).unwrap();
+
+        let plan = ApplyPlan::load(&path).unwrap();
+        assert_eq!(plan.marked_count(), 1);
+        assert_eq!(plan.verify(Some(&hasher)).scheduled, vec![b.clone()]);
+
+        // Same size, different content: only paranoid mode notices
+        fs::write(&b, b"PAYLOAD").unwrap();
+        assert_eq!(plan.verify(None).scheduled, vec![b.clone()]);
+        assert_eq!(
+            plan.verify(Some(&hasher)).skipped,
+            vec![SkippedFile {
+                path: b,
+                reason: SkipReason::HashChanged,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_changed_survivor_does_not_count() {
+        let dir = TempDir::new().unwrap();
+        let keep = dir.path().join("keep.txt");
+        let marked = dir.path().join("marked.txt");
+        fs::write(&keep, b"content").unwrap();
+        fs::write(&marked, b"content").unwrap();
+        let hasher = Hasher::new();
+        let hash = crate::scanner::hash_to_hex(&hasher.full_hash(&keep).unwrap());
+
+        let mut csv = String::from("group_id,hash,path,size,delete\n");
+        for (path, decision) in [(&keep, ""), (&marked, "x")] {
+            csv.push_str(&format!("1,{},{},7,{}\n", hash, path.display(), decision));
+        }
+        let plan = ApplyPlan::from_csv(csv.as_bytes(), b',').unwrap();
+        assert_eq!(plan.verify(None).scheduled, vec![marked.clone()]);
+
+        // The kept copy was edited in place: only paranoid mode notices
+        fs::write(&keep, b"CONTENT").unwrap();
+        assert_eq!(plan.verify(None).scheduled, vec![marked.clone()]);
+        let outcome = plan.verify(Some(&hasher));
+        assert!(outcome.scheduled.is_empty());
+        assert_eq!(
+            outcome.skipped,
+            vec![SkippedFile {
+                path: marked.clone(),
+                reason: SkipReason::NoSurvivor,
+            }]
+        );
+
+        // The kept copy was replaced with a different size
+        fs::write(&keep, b"replaced").unwrap();
+        let outcome = plan.verify(None);
+        assert!(outcome.scheduled.is_empty());
+        assert_eq!(
+            outcome.skipped,
+            vec![SkippedFile {
+                path: marked,
+                reason: SkipReason::NoSurvivor,
+            }]
+        );
+    }
+}
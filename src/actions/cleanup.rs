@@ -0,0 +1,270 @@
+//! Removal of directories left empty after deleting duplicates.
+//!
+//! # Overview
+//!
+//! After a batch delete, the folders that held the deleted files are often
+//! left empty. This module walks those folders bottom-up and removes the ones
+//! that became empty:
+//! - Scan roots themselves are never removed, nor anything outside them
+//! - Reference directories are excluded via [`candidate_dirs`]
+//! - Only empty directories are removed (`fs::remove_dir`), so a folder that
+//!   gains a file between the check and the removal is left alone
+//!
+//! [`find_empty_dirs`] performs the same walk without touching the
+//! filesystem, for dry-run reporting.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::actions::cleanup::{candidate_dirs, remove_empty_dirs};
+//! use std::path::PathBuf;
+//!
+//! let deleted = vec![PathBuf::from("/photos/old/copy.jpg")];
+//! let roots = vec![PathBuf::from("/photos")];
+//! let candidates = candidate_dirs(&deleted, &[]);
+//! for dir in remove_empty_dirs(&candidates, &roots) {
+//!     println!("Removed empty directory: {}", dir.display());
+//! }
+//! ```
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Get the parent directories of `deleted` files as cleanup candidates.
+///
+/// Directories inside (or equal to) any `protected` path, such as reference
+/// directories, are left out.
+#[must_use]
+pub fn candidate_dirs(deleted: &[PathBuf], protected: &[PathBuf]) -> Vec<PathBuf> {
+    deleted
+        .iter()
+        .filter_map(|path| path.parent())
+        .filter(|dir| !protected.iter().any(|p| dir.starts_with(p)))
+        .map(Path::to_path_buf)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Expand `candidates` to every directory that may become empty, deepest first.
+///
+/// Each candidate contributes itself and its ancestors up to, but excluding,
+/// the scan root that contains it. Candidates outside every root are ignored.
+#[must_use]
+pub fn cleanup_order(candidates: &[PathBuf], roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut dirs = BTreeSet::new();
+    for candidate in candidates {
+        let mut dir = Some(candidate.as_path());
+        while let Some(d) = dir {
+            if !is_below_root(d, roots) {
+                break;
+            }
+            dirs.insert(d.to_path_buf());
+            dir = d.parent();
+        }
+    }
+
+    let mut ordered: Vec<PathBuf> = dirs.into_iter().collect();
+    ordered.sort_by_key(|d| std::cmp::Reverse(d.components().count()));
+    ordered
+}
+
+/// Remove directories under `roots` that are empty after a deletion.
+///
+/// Walks each candidate and its ancestors bottom-up, stopping at the scan
+/// roots. Failures (non-empty, permission denied, already gone) are logged
+/// and skipped.
+///
+/// # Returns
+///
+/// The directories that were removed, deepest first.
+pub fn remove_empty_dirs(candidates: &[PathBuf], roots: &[PathBuf]) -> Vec<PathBuf> {
+    sweep(candidates, roots, &[], true)
+}
+
+/// Report the directories [`remove_empty_dirs`] would remove once the
+/// `pending` paths are deleted, without touching the filesystem.
+#[must_use]
+pub fn find_empty_dirs(
+    candidates: &[PathBuf],
+    roots: &[PathBuf],
+    pending: &[PathBuf],
+) -> Vec<PathBuf> {
+    sweep(candidates, roots, pending, false)
+}
+
+fn sweep(
+    candidates: &[PathBuf],
+    roots: &[PathBuf],
+    pending: &[PathBuf],
+    remove: bool,
+) -> Vec<PathBuf> {
+    let mut gone: HashSet<PathBuf> = pending.iter().cloned().collect();
+    let mut removed = Vec::new();
+
+    for dir in cleanup_order(candidates, roots) {
+        // Never follow or remove symlinked directories
+        match fs::symlink_metadata(&dir) {
+            Ok(meta) if meta.is_dir() => {}
+            _ => continue,
+        }
+        if !is_empty_except(&dir, &gone) {
+            continue;
+        }
+
+        if remove {
+            if let Err(e) = fs::remove_dir(&dir) {
+                log::debug!("Skipping empty directory {}: {}", dir.display(), e);
+                continue;
+            }
+            log::debug!("Removed empty directory: {}", dir.display());
+        }
+        gone.insert(dir.clone());
+        removed.push(dir);
+    }
+
+    removed
+}
+
+/// Check whether `dir` has no entries other than those in `gone`.
+fn is_empty_except(dir: &Path, gone: &HashSet<PathBuf>) -> bool {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .map(|entry| entry.map(|e| e.path()))
+            .all(|path| path.is_ok_and(|p| gone.contains(&p))),
+        Err(_) => false,
+    }
+}
+
+fn is_below_root(dir: &Path, roots: &[PathBuf]) -> bool {
+    roots
+        .iter()
+        .any(|root| dir != root && dir.starts_with(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn touch(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, b"x").unwrap();
+    }
+
+    #[test]
+    fn test_candidate_dirs_skips_protected() {
+        let deleted = vec![
+            PathBuf::from("/scan/a/one.txt"),
+            PathBuf::from("/scan/a/two.txt"),
+            PathBuf::from("/scan/ref/sub/three.txt"),
+        ];
+        let protected = vec![PathBuf::from("/scan/ref")];
+
+        assert_eq!(
+            candidate_dirs(&deleted, &protected),
+            vec![PathBuf::from("/scan/a")]
+        );
+    }
+
+    #[test]
+    fn test_cleanup_order_deepest_first_within_roots() {
+        let candidates = vec![
+            PathBuf::from("/scan/a/b/c"),
+            PathBuf::from("/scan/d"),
+            PathBuf::from("/elsewhere/x"),
+        ];
+        let roots = vec![PathBuf::from("/scan")];
+
+        let order = cleanup_order(&candidates, &roots);
+        assert_eq!(
+            order,
+            vec![
+                PathBuf::from("/scan/a/b/c"),
+                PathBuf::from("/scan/a/b"),
+                PathBuf::from("/scan/a"),
+                PathBuf::from("/scan/d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_nested_empty_dirs_stops_at_root() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        let file = root.join("a/b/c/dupe.txt");
+        touch(&file);
+        fs::remove_file(&file).unwrap();
+
+        let candidates = candidate_dirs(&[file], &[]);
+        let removed = remove_empty_dirs(&candidates, std::slice::from_ref(&root));
+
+        assert_eq!(
+            removed,
+            vec![root.join("a/b/c"), root.join("a/b"), root.join("a")]
+        );
+        assert!(root.exists());
+        assert!(!root.join("a").exists());
+    }
+
+    #[test]
+    fn test_remove_keeps_non_empty_ancestors() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        let file = root.join("a/b/dupe.txt");
+        touch(&file);
+        touch(&root.join("a/keep.txt"));
+        fs::create_dir_all(root.join("a/b2")).unwrap();
+        fs::remove_file(&file).unwrap();
+
+        let removed = remove_empty_dirs(&candidate_dirs(&[file], &[]), std::slice::from_ref(&root));
+
+        assert_eq!(removed, vec![root.join("a/b")]);
+        assert!(root.join("a/keep.txt").exists());
+        // Pre-existing empty siblings are not candidates
+        assert!(root.join("a/b2").exists());
+    }
+
+    #[test]
+    fn test_remove_never_touches_root_or_reference() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("scan");
+        let reference = root.join("ref");
+        let top = root.join("top.txt");
+        let in_ref = reference.join("inner/copy.txt");
+        touch(&top);
+        touch(&in_ref);
+        fs::remove_file(&top).unwrap();
+        fs::remove_file(&in_ref).unwrap();
+
+        let candidates = candidate_dirs(&[top, in_ref], std::slice::from_ref(&reference));
+        let removed = remove_empty_dirs(&candidates, std::slice::from_ref(&root));
+
+        assert!(removed.is_empty());
+        assert!(root.exists());
+        assert!(reference.join("inner").exists());
+    }
+
+    #[test]
+    fn test_find_empty_dirs_reports_without_removing() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        let first = root.join("x/y/one.txt");
+        let second = root.join("x/y/two.txt");
+        let kept = root.join("z/keep.txt");
+        let third = root.join("z/three.txt");
+        for path in [&first, &second, &kept, &third] {
+            touch(path);
+        }
+
+        let pending = vec![first, second, third];
+        let candidates = candidate_dirs(&pending, &[]);
+        let planned = find_empty_dirs(&candidates, std::slice::from_ref(&root), &pending);
+
+        assert_eq!(planned, vec![root.join("x/y"), root.join("x")]);
+        for path in &pending {
+            assert!(path.exists());
+        }
+    }
+}
@@ -4,9 +4,16 @@
 //!
 //! This module provides safe file deletion functionality:
 //! - Move to system trash (default, recoverable)
+//! - Move to a managed trash directory instead (`--trash-dir`), for systems
+//!   without a desktop trash
 //! - Permanent deletion (with explicit flag)
+//! - Per-directory [`DeleteMode`] rules (e.g. permanently delete scratch files)
 //! - Batch operations with progress reporting
+//! - Transactional batches that restore trashed files if any deletion fails
 //! - TOCTOU verification before deletion
+//! - A settle window refusing to delete files that changed moments ago
+//! - Owner filters refusing to delete other users' files (Unix)
+//! - A total size cap refusing whole batches that would delete too much
 //!
 //! # Safety
 //!
@@ -29,11 +36,13 @@
 
 use std::fs;
 use std::io;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::time::SystemTime;
 
 use thiserror::Error;
 
+use super::xattrs::move_preserving_xattrs;
+
 /// Error type for deletion operations.
 #[derive(Debug, Error)]
 pub enum DeleteError {
@@ -49,6 +58,14 @@ pub enum DeleteError {
     #[error("file modified since scan: {0}")]
     Modified(PathBuf),
 
+    /// File was modified too recently and may still be in use.
+    #[error("file modified within the last {seconds}s, may still be in use: {path}")]
+    Unsettled { path: PathBuf, seconds: u64 },
+
+    /// File is owned by a user outside the owner filter.
+    #[error("file owned by uid {uid} is excluded by the owner filter: {path}")]
+    OwnerExcluded { path: PathBuf, uid: u32 },
+
     /// Trash operation failed.
     #[error("trash operation failed for {path}: {message}")]
     TrashFailed { path: PathBuf, message: String },
@@ -61,6 +78,18 @@ pub enum DeleteError {
     #[error("cannot delete all copies - at least one file must be preserved")]
     AllCopiesWouldBeDeleted,
 
+    /// A trashed file could not be restored during rollback.
+    #[error("restore from trash failed for {path}: {message}")]
+    RestoreFailed { path: PathBuf, message: String },
+
+    /// The batch would delete more bytes than the configured cap allows.
+    #[error("refusing to delete {planned} bytes: exceeds the cap of {cap} bytes")]
+    ExceedsCap { planned: u64, cap: u64 },
+
+    /// Transactional deletion cannot guarantee rollback in this configuration.
+    #[error("transactional deletion unavailable: {0}")]
+    TransactionUnavailable(String),
+
     /// General I/O error.
     #[error("I/O error for {path}: {source}")]
     Io {
@@ -78,10 +107,15 @@ impl DeleteError {
             Self::NotFound(p)
             | Self::PermissionDenied(p)
             | Self::Modified(p)
+            | Self::Unsettled { path: p, .. }
+            | Self::OwnerExcluded { path: p, .. }
             | Self::TrashFailed { path: p, .. }
             | Self::PermanentDeleteFailed { path: p, .. }
+            | Self::RestoreFailed { path: p, .. }
             | Self::Io { path: p, .. } => Some(p),
-            Self::AllCopiesWouldBeDeleted => None,
+            Self::AllCopiesWouldBeDeleted
+            | Self::ExceedsCap { .. }
+            | Self::TransactionUnavailable(_) => None,
         }
     }
 }
@@ -95,6 +129,9 @@ pub struct DeleteResult {
     pub size: u64,
     /// Whether deletion was permanent (true) or to trash (false).
     pub permanent: bool,
+    /// Where the file was moved when a trash directory replaced the system
+    /// trash (see [`DeleteConfig::trash_override`]).
+    pub trashed_to: Option<PathBuf>,
 }
 
 impl DeleteResult {
@@ -105,10 +142,22 @@ impl DeleteResult {
             path,
             size,
             permanent,
+            trashed_to: None,
         }
     }
 }
 
+/// Where a trashed file went, used to restore it with [`DeleteConfig::restore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrashLocation {
+    /// The operating system trash, holding the token from
+    /// [`trash_restore_token`].
+    System(PathBuf),
+    /// A trash directory (`--trash-dir`), holding the path the file was
+    /// moved to.
+    Directory(PathBuf),
+}
+
 /// Results of a batch deletion operation.
 #[derive(Debug, Clone, Default)]
 pub struct BatchDeleteResult {
@@ -118,6 +167,14 @@ pub struct BatchDeleteResult {
     pub failures: Vec<(PathBuf, String)>,
     /// Total bytes freed.
     pub bytes_freed: u64,
+    /// Whether a transactional batch failed and restored its trashed files.
+    pub rolled_back: bool,
+    /// Files restored from the trash during rollback.
+    pub restored: Vec<PathBuf>,
+    /// Files that could not be restored during rollback, with their errors.
+    ///
+    /// These remain in the trash and are still listed in `successes`.
+    pub restore_failures: Vec<(PathBuf, String)>,
 }
 
 impl BatchDeleteResult {
@@ -145,10 +202,31 @@ impl BatchDeleteResult {
         self.failures.is_empty()
     }
 
+    /// Check if a rollback was attempted and every trashed file was restored.
+    #[must_use]
+    pub fn rollback_succeeded(&self) -> bool {
+        self.rolled_back && self.restore_failures.is_empty()
+    }
+
     /// Human-readable summary of the operation.
     #[must_use]
     pub fn summary(&self) -> String {
-        if self.all_succeeded() {
+        if self.rolled_back {
+            if self.rollback_succeeded() {
+                format!(
+                    "Rolled back: {} failed, restored {} file(s) from trash",
+                    self.failure_count(),
+                    self.restored.len()
+                )
+            } else {
+                format!(
+                    "Rollback incomplete: {} failed, restored {} file(s), {} still in trash",
+                    self.failure_count(),
+                    self.restored.len(),
+                    self.restore_failures.len()
+                )
+            }
+        } else if self.all_succeeded() {
             format!(
                 "Deleted {} file(s), freed {} bytes",
                 self.success_count(),
@@ -165,23 +243,69 @@ impl BatchDeleteResult {
     }
 }
 
+/// How a single file is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeleteMode {
+    /// Move to the system trash (recoverable).
+    #[default]
+    Trash,
+    /// Remove permanently.
+    Permanent,
+}
+
 /// Configuration for deletion operations.
 #[derive(Debug, Clone)]
 pub struct DeleteConfig {
-    /// Use permanent deletion instead of trash.
+    /// Use permanent deletion instead of trash for files no rule matches.
     pub permanent: bool,
+    /// Per-directory deletion modes as `(path_prefix, mode)` pairs.
+    ///
+    /// The rule with the most specific (longest) matching prefix decides;
+    /// among equally specific rules the last one wins. See [`Self::mode_for`].
+    pub rules: Vec<(PathBuf, DeleteMode)>,
+    /// Directories whose files are always trashed, whatever the rules say
+    /// (reference directories).
+    pub protected_paths: Vec<PathBuf>,
     /// Verify file modification time before deletion (TOCTOU protection).
     pub verify_mtime: bool,
     /// Continue on error (process remaining files even if some fail).
     pub continue_on_error: bool,
+    /// All-or-nothing batches: on the first failure, restore every file
+    /// already moved to the trash. Requires trash deletion and a platform
+    /// that can restore from the trash (Windows, Linux/BSD).
+    pub transactional: bool,
+    /// Refuse to delete files modified within this many seconds.
+    pub settle_seconds: Option<u64>,
+    /// Only delete files owned by these user ids (Unix only).
+    pub owner_uids: Option<Vec<u32>>,
+    /// Never delete files owned by these user ids (Unix only).
+    pub exclude_owner_uids: Vec<u32>,
+    /// Refuse batches whose files total more than this many bytes.
+    pub max_total_bytes: Option<u64>,
+    /// Move trashed files into this directory instead of the system trash.
+    ///
+    /// See [`move_to_trash_dir`] for the layout.
+    pub trash_override: Option<PathBuf>,
+    /// Carry extended attributes along when moving files into
+    /// [`trash_override`](Self::trash_override) (`--preserve-xattrs`).
+    pub preserve_xattrs: bool,
 }
 
 impl Default for DeleteConfig {
     fn default() -> Self {
         Self {
             permanent: false,
+            rules: Vec::new(),
+            protected_paths: Vec::new(),
             verify_mtime: true,
             continue_on_error: true,
+            transactional: false,
+            settle_seconds: None,
+            owner_uids: None,
+            exclude_owner_uids: Vec::new(),
+            max_total_bytes: None,
+            trash_override: None,
+            preserve_xattrs: false,
         }
     }
 }
@@ -215,6 +339,225 @@ impl DeleteConfig {
         self.continue_on_error = continue_on_error;
         self
     }
+
+    /// Enable/disable transactional (all-or-nothing) batch deletion.
+    #[must_use]
+    pub fn with_transactional(mut self, transactional: bool) -> Self {
+        self.transactional = transactional;
+        self
+    }
+
+    /// Refuse to delete files modified within the last `seconds` seconds.
+    #[must_use]
+    pub fn with_settle_seconds(mut self, seconds: Option<u64>) -> Self {
+        self.settle_seconds = seconds;
+        self
+    }
+
+    /// Only delete files owned by one of `only` (if set) and by none of `exclude`.
+    #[must_use]
+    pub fn with_owner_filter(mut self, only: Option<Vec<u32>>, exclude: Vec<u32>) -> Self {
+        self.owner_uids = only;
+        self.exclude_owner_uids = exclude;
+        self
+    }
+
+    /// Refuse batches that would delete more than `bytes` in total.
+    #[must_use]
+    pub fn with_max_total_bytes(mut self, bytes: Option<u64>) -> Self {
+        self.max_total_bytes = bytes;
+        self
+    }
+
+    /// Move trashed files into `dir` instead of the system trash.
+    #[must_use]
+    pub fn with_trash_override(mut self, dir: Option<PathBuf>) -> Self {
+        self.trash_override = dir;
+        self
+    }
+
+    /// Keep extended attributes of files moved into the trash directory.
+    #[must_use]
+    pub fn with_preserve_xattrs(mut self, preserve: bool) -> Self {
+        self.preserve_xattrs = preserve;
+        self
+    }
+
+    /// Whether files trashed with this config can be restored.
+    ///
+    /// A trash directory always can; the system trash depends on the
+    /// platform (see [`trash_restore_supported`]).
+    #[must_use]
+    pub fn can_restore(&self) -> bool {
+        self.trash_override.is_some() || trash_restore_supported()
+    }
+
+    /// Move a trashed file back to `original` from wherever it went.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RestoreFailed` if the file cannot be found or moved back, or
+    /// if something already exists at `original`.
+    pub fn restore(&self, original: &Path, location: &TrashLocation) -> Result<(), DeleteError> {
+        match location {
+            TrashLocation::System(token) => restore_from_trash(token),
+            TrashLocation::Directory(handle) => {
+                restore_from_trash_dir(original, handle, self.preserve_xattrs)
+            }
+        }
+    }
+
+    /// Add a rule deleting files under `prefix` with `mode`.
+    #[must_use]
+    pub fn with_rule(mut self, prefix: impl Into<PathBuf>, mode: DeleteMode) -> Self {
+        self.rules.push((prefix.into(), mode));
+        self
+    }
+
+    /// Permanently delete files under each of `dirs`.
+    #[must_use]
+    pub fn with_permanent_under(mut self, dirs: &[PathBuf]) -> Self {
+        self.rules
+            .extend(dirs.iter().map(|dir| (dir.clone(), DeleteMode::Permanent)));
+        self
+    }
+
+    /// Always trash files under `paths`, overriding any rule.
+    #[must_use]
+    pub fn with_protected_paths(mut self, paths: Vec<PathBuf>) -> Self {
+        self.protected_paths = paths;
+        self
+    }
+
+    /// Resolve the deletion mode for `path`.
+    ///
+    /// Files under a protected path are always trashed. Otherwise the most
+    /// specific matching rule applies, and files no rule matches are trashed
+    /// unless [`permanent`](Self::permanent) is set.
+    #[must_use]
+    pub fn mode_for(&self, path: &Path) -> DeleteMode {
+        if self.protected_paths.iter().any(|p| path.starts_with(p)) {
+            return DeleteMode::Trash;
+        }
+
+        let default = if self.permanent {
+            DeleteMode::Permanent
+        } else {
+            DeleteMode::Trash
+        };
+        self.rules
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.components().count())
+            .map_or(default, |(_, mode)| *mode)
+    }
+
+    /// Check that `path` was not modified within the settle window.
+    ///
+    /// Files whose metadata cannot be read pass; the deletion itself then
+    /// reports the error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Unsettled` if the file changed too recently.
+    pub fn check_settled(&self, path: &Path) -> Result<(), DeleteError> {
+        let Some(seconds) = self.settle_seconds else {
+            return Ok(());
+        };
+        let modified = fs::metadata(path).and_then(|m| m.modified());
+        if modified.is_ok_and(|m| crate::scanner::is_unsettled(m, seconds)) {
+            log::warn!(
+                "Refusing to delete recently modified file: {}",
+                path.display()
+            );
+            return Err(DeleteError::Unsettled {
+                path: path.to_path_buf(),
+                seconds,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that `path` is owned by a user the owner filter allows.
+    ///
+    /// Files whose metadata cannot be read pass; the deletion itself then
+    /// reports the error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OwnerExcluded` if the file's owner is filtered out.
+    pub fn check_owner(&self, path: &Path) -> Result<(), DeleteError> {
+        if self.owner_uids.is_none() && self.exclude_owner_uids.is_empty() {
+            return Ok(());
+        }
+        let owner = fs::metadata(path)
+            .ok()
+            .and_then(|m| crate::scanner::owner::file_owner(&m));
+        match owner {
+            Some(uid)
+                if !crate::scanner::owner::owner_allowed(
+                    Some(uid),
+                    self.owner_uids.as_deref(),
+                    &self.exclude_owner_uids,
+                ) =>
+            {
+                log::warn!(
+                    "Refusing to delete file owned by uid {}: {}",
+                    uid,
+                    path.display()
+                );
+                Err(DeleteError::OwnerExcluded {
+                    path: path.to_path_buf(),
+                    uid,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that deleting all of `paths` stays within the size cap.
+    ///
+    /// Sizes are read from disk; files that cannot be read count as zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExceedsCap` if the planned total is above the cap.
+    pub fn check_cap(&self, paths: &[PathBuf]) -> Result<(), DeleteError> {
+        let Some(cap) = self.max_total_bytes else {
+            return Ok(());
+        };
+        let planned: u64 = paths
+            .iter()
+            .filter_map(|p| fs::metadata(p).ok())
+            .map(|m| m.len())
+            .sum();
+        if planned > cap {
+            return Err(DeleteError::ExceedsCap { planned, cap });
+        }
+        Ok(())
+    }
+
+    /// Run the settle and owner guards for `path`.
+    fn check_guards(&self, path: &Path) -> Result<(), DeleteError> {
+        self.check_settled(path)?;
+        self.check_owner(path)
+    }
+
+    /// Delete `path` with its resolved mode.
+    fn delete(&self, path: &Path) -> Result<DeleteResult, DeleteError> {
+        self.check_guards(path)?;
+        match (self.mode_for(path), &self.trash_override) {
+            (DeleteMode::Trash, Some(dir)) => move_to_trash_dir_at(
+                path,
+                dir,
+                &chrono::Local::now().format("%Y-%m-%d").to_string(),
+                self.preserve_xattrs,
+            )
+            .map(|(result, _)| result),
+            (DeleteMode::Trash, None) => delete_to_trash(path),
+            (DeleteMode::Permanent, _) => permanent_delete(path),
+        }
+    }
 }
 
 /// Callback trait for deletion progress reporting.
@@ -357,6 +700,167 @@ pub fn delete_to_trash(path: &Path) -> Result<DeleteResult, DeleteError> {
     Ok(DeleteResult::new(path.to_path_buf(), size, false))
 }
 
+/// Move a single file into a managed trash directory.
+///
+/// Used instead of [`delete_to_trash`] when `--trash-dir` is set. Files land
+/// in a subfolder named after today's date, under their original absolute
+/// path with the root stripped:
+///
+/// ```text
+/// /home/me/photos/a.jpg  ->  <trash_dir>/2026-01-31/home/me/photos/a.jpg
+/// C:\Users\me\a.jpg      ->  <trash_dir>\2026-01-31\C\Users\me\a.jpg
+/// ```
+///
+/// If the destination already exists, a numeric suffix is added
+/// (`a-1.jpg`, `a-2.jpg`, ...). Files on another file system are copied and
+/// then removed.
+///
+/// # Errors
+///
+/// - `NotFound` if the file doesn't exist
+/// - `PermissionDenied` if the file cannot be inspected
+/// - `TrashFailed` if the file cannot be moved into the trash directory
+pub fn move_to_trash_dir(path: &Path, trash_dir: &Path) -> Result<DeleteResult, DeleteError> {
+    move_to_trash_dir_at(
+        path,
+        trash_dir,
+        &chrono::Local::now().format("%Y-%m-%d").to_string(),
+        false,
+    )
+    .map(|(result, _)| result)
+}
+
+/// Move `path` into the `date` subfolder of `trash_dir`, returning where it
+/// ended up. With `preserve_xattrs`, extended attributes survive a move that
+/// has to copy across file systems.
+fn move_to_trash_dir_at(
+    path: &Path,
+    trash_dir: &Path,
+    date: &str,
+    preserve_xattrs: bool,
+) -> Result<(DeleteResult, PathBuf), DeleteError> {
+    let snapshot = FileSnapshot::capture(path)?;
+    let trash_failed = |e: io::Error| {
+        log::error!("Trash operation failed for {}: {}", path.display(), e);
+        DeleteError::TrashFailed {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        }
+    };
+
+    let original = std::path::absolute(path).map_err(trash_failed)?;
+    let destination = unique_destination(&trash_dir_destination(trash_dir, date, &original));
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(trash_failed)?;
+    }
+    move_file(path, &destination, preserve_xattrs).map_err(trash_failed)?;
+
+    log::info!(
+        "Moved to {}: {} ({} bytes)",
+        destination.display(),
+        path.display(),
+        snapshot.size
+    );
+    let mut result = DeleteResult::new(path.to_path_buf(), snapshot.size, false);
+    result.trashed_to = Some(destination.clone());
+    Ok((result, destination))
+}
+
+/// Move a file back from `handle` in a trash directory to `original`.
+///
+/// Refuses if `original` exists again, since a rename would silently replace
+/// it; the parent directory is recreated if it was removed.
+fn restore_from_trash_dir(
+    original: &Path,
+    handle: &Path,
+    preserve_xattrs: bool,
+) -> Result<(), DeleteError> {
+    let restore_failed = |message: String| DeleteError::RestoreFailed {
+        path: original.to_path_buf(),
+        message,
+    };
+
+    if fs::symlink_metadata(original).is_ok() {
+        return Err(restore_failed(
+            "a file already exists at the original path".to_string(),
+        ));
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent).map_err(|e| restore_failed(e.to_string()))?;
+    }
+    move_file(handle, original, preserve_xattrs).map_err(|e| restore_failed(e.to_string()))?;
+    log::info!("Restored from {}: {}", handle.display(), original.display());
+    Ok(())
+}
+
+/// Where an absolute `original` path goes inside the `date` folder of
+/// `trash_dir`, before collision handling.
+fn trash_dir_destination(trash_dir: &Path, date: &str, original: &Path) -> PathBuf {
+    let base = trash_dir.join(date);
+    let mut destination = base.clone();
+    for component in original.components() {
+        match component {
+            // `C:` becomes `C`; UNC prefixes keep their server and share
+            Component::Prefix(prefix) => {
+                let prefix = prefix.as_os_str().to_string_lossy();
+                destination.extend(
+                    prefix
+                        .split(['\\', '/', ':', '?'])
+                        .filter(|part| !part.is_empty() && *part != "."),
+                );
+            }
+            Component::Normal(part) => destination.push(part),
+            Component::RootDir | Component::CurDir => {}
+            // Never climb out of the dated folder
+            Component::ParentDir => {
+                if destination != base {
+                    destination.pop();
+                }
+            }
+        }
+    }
+    destination
+}
+
+/// `path`, or the first of `stem-1.ext`, `stem-2.ext`, ... that does not exist.
+fn unique_destination(path: &Path) -> PathBuf {
+    if fs::symlink_metadata(path).is_err() {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    (1u64..)
+        .map(|n| path.with_file_name(format!("{stem}-{n}{extension}")))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .expect("some numeric suffix is free")
+}
+
+/// Rename `from` to `to`, copying across file systems.
+///
+/// A copy does not carry extended attributes, so with `preserve_xattrs` they
+/// are read first and written back to `to`.
+fn move_file(from: &Path, to: &Path, preserve_xattrs: bool) -> io::Result<()> {
+    if preserve_xattrs {
+        move_preserving_xattrs(from, to, rename_or_copy)
+    } else {
+        rename_or_copy(from, to)
+    }
+}
+
+/// Rename `from` to `to`, falling back to copy and remove across file systems.
+fn rename_or_copy(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            fs::copy(from, to)?;
+            fs::remove_file(from)
+        }
+        other => other,
+    }
+}
+
 /// Permanently delete a single file.
 ///
 /// **WARNING**: This operation cannot be undone. The file will be permanently removed.
@@ -447,11 +951,7 @@ pub fn delete_verified(
     }
 
     // Perform deletion
-    if config.permanent {
-        permanent_delete(path)
-    } else {
-        delete_to_trash(path)
-    }
+    config.delete(path)
 }
 
 /// Delete multiple files in batch.
@@ -497,6 +997,14 @@ pub fn delete_batch<C: DeleteProgressCallback>(
     config: &DeleteConfig,
     callback: Option<&C>,
 ) -> BatchDeleteResult {
+    if config.transactional {
+        return delete_batch_transactional(paths, config, callback);
+    }
+    // The cap covers the whole batch, so check it before deleting anything
+    if let Err(e) = config.check_cap(paths) {
+        return refuse_batch(paths, &e, callback);
+    }
+
     let mut result = BatchDeleteResult::default();
     let total = paths.len();
 
@@ -506,12 +1014,8 @@ pub fn delete_batch<C: DeleteProgressCallback>(
             cb.on_before_delete(path, index, total);
         }
 
-        // Attempt deletion
-        let delete_result = if config.permanent {
-            permanent_delete(path)
-        } else {
-            delete_to_trash(path)
-        };
+        // Attempt deletion with the mode resolved for this file
+        let delete_result = config.delete(path);
 
         match delete_result {
             Ok(del) => {
@@ -549,6 +1053,303 @@ pub fn delete_batch<C: DeleteProgressCallback>(
     result
 }
 
+/// Report every path in a batch as failed with `error`, deleting nothing.
+fn refuse_batch<C: DeleteProgressCallback>(
+    paths: &[PathBuf],
+    error: &DeleteError,
+    callback: Option<&C>,
+) -> BatchDeleteResult {
+    let error_msg = error.to_string();
+    log::error!("{}", error_msg);
+    let mut result = BatchDeleteResult::default();
+    for path in paths {
+        if let Some(cb) = callback {
+            cb.on_delete_failure(path, &error_msg);
+        }
+        result.failures.push((path.clone(), error_msg.clone()));
+    }
+    if let Some(cb) = callback {
+        cb.on_complete(&result);
+    }
+    result
+}
+
+/// Delete multiple files to the trash as a single all-or-nothing operation.
+///
+/// Files are trashed in order. On the first failure the batch stops and every
+/// file already trashed is restored to its original location, newest first.
+/// Check [`BatchDeleteResult::rolled_back`] and
+/// [`BatchDeleteResult::rollback_succeeded`] for the outcome; files that could
+/// not be restored are listed in `restore_failures` and stay in the trash.
+///
+/// Rollback needs the trash's restore metadata, which is only available on
+/// Windows and freedesktop platforms (Linux/BSD). With a
+/// [`trash_override`](DeleteConfig::trash_override) files are simply moved
+/// back out of the trash directory, which works everywhere. Elsewhere, or
+/// when any
+/// path resolves to [`DeleteMode::Permanent`], nothing is deleted and every
+/// path is reported as failed with [`DeleteError::TransactionUnavailable`].
+///
+/// # Arguments
+///
+/// * `paths` - Slice of paths to delete
+/// * `config` - Deletion configuration (`continue_on_error` is ignored)
+/// * `callback` - Optional progress callback
+pub fn delete_batch_transactional<C: DeleteProgressCallback>(
+    paths: &[PathBuf],
+    config: &DeleteConfig,
+    callback: Option<&C>,
+) -> BatchDeleteResult {
+    match &config.trash_override {
+        Some(dir) => run_transactional(
+            paths,
+            config,
+            callback,
+            &TrashDir::new(dir, config.preserve_xattrs),
+        ),
+        None => run_transactional(paths, config, callback, &SystemTrash),
+    }
+}
+
+/// Absolute path the trash records for `path`, used to find the entry again
+/// when restoring.
+///
+/// Call before the file is deleted; afterwards the path can no longer be
+/// resolved and is returned unchanged.
+#[must_use]
+pub fn trash_restore_token(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whether files moved to the trash can be restored on this platform.
+///
+/// Restoring is supported on Windows and freedesktop.org trash
+/// implementations (Linux, BSD), but not on macOS.
+#[must_use]
+pub fn trash_restore_supported() -> bool {
+    SystemTrash.supports_restore()
+}
+
+/// Restore the most recently trashed file that was deleted from `original`.
+///
+/// `original` is the token returned by [`trash_restore_token`] before the
+/// file was deleted.
+///
+/// # Errors
+///
+/// Returns `RestoreFailed` if no matching trash entry exists, the trash
+/// cannot be read, or the platform does not support restoring.
+pub fn restore_from_trash(original: &Path) -> Result<(), DeleteError> {
+    SystemTrash.restore(original, original)
+}
+
+/// Trash operations needed by transactional deletion.
+trait TrashBackend {
+    /// Whether trashed files can be restored on this platform.
+    fn supports_restore(&self) -> bool;
+
+    /// Move a file to the trash, returning its original absolute path as the
+    /// restore handle.
+    fn trash(&self, path: &Path) -> Result<(DeleteResult, PathBuf), DeleteError>;
+
+    /// Restore a file previously trashed from `original`, given the handle
+    /// [`trash`](Self::trash) returned for it.
+    fn restore(&self, original: &Path, handle: &Path) -> Result<(), DeleteError>;
+}
+
+/// The operating system trash.
+struct SystemTrash;
+
+/// A managed trash directory (`--trash-dir`).
+struct TrashDir<'a> {
+    dir: &'a Path,
+    /// Dated subfolder, fixed for the whole batch
+    date: String,
+    /// Carry extended attributes across moves (`--preserve-xattrs`)
+    preserve_xattrs: bool,
+}
+
+impl<'a> TrashDir<'a> {
+    fn new(dir: &'a Path, preserve_xattrs: bool) -> Self {
+        Self {
+            dir,
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            preserve_xattrs,
+        }
+    }
+}
+
+impl TrashBackend for TrashDir<'_> {
+    fn supports_restore(&self) -> bool {
+        true
+    }
+
+    fn trash(&self, path: &Path) -> Result<(DeleteResult, PathBuf), DeleteError> {
+        move_to_trash_dir_at(path, self.dir, &self.date, self.preserve_xattrs)
+    }
+
+    fn restore(&self, original: &Path, handle: &Path) -> Result<(), DeleteError> {
+        restore_from_trash_dir(original, handle, self.preserve_xattrs)
+    }
+}
+
+#[cfg(any(
+    windows,
+    all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    )
+))]
+impl TrashBackend for SystemTrash {
+    fn supports_restore(&self) -> bool {
+        true
+    }
+
+    fn trash(&self, path: &Path) -> Result<(DeleteResult, PathBuf), DeleteError> {
+        // Trash entries record absolute paths, so resolve before deleting
+        let original = trash_restore_token(path);
+        Ok((delete_to_trash(path)?, original))
+    }
+
+    fn restore(&self, _original: &Path, handle: &Path) -> Result<(), DeleteError> {
+        // The handle is the absolute path the trash recorded
+        let restore_failed = |message: String| DeleteError::RestoreFailed {
+            path: handle.to_path_buf(),
+            message,
+        };
+
+        // The most recent entry for this path is the one this batch created
+        let item = trash::os_limited::list()
+            .map_err(|e| restore_failed(e.to_string()))?
+            .into_iter()
+            .filter(|item| item.original_path() == handle)
+            .max_by_key(|item| item.time_deleted)
+            .ok_or_else(|| restore_failed("no matching trash entry".to_string()))?;
+
+        trash::os_limited::restore_all([item]).map_err(|e| restore_failed(e.to_string()))?;
+        log::info!("Restored from trash: {}", handle.display());
+        Ok(())
+    }
+}
+
+#[cfg(not(any(
+    windows,
+    all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    )
+)))]
+impl TrashBackend for SystemTrash {
+    fn supports_restore(&self) -> bool {
+        false
+    }
+
+    fn trash(&self, path: &Path) -> Result<(DeleteResult, PathBuf), DeleteError> {
+        Ok((delete_to_trash(path)?, path.to_path_buf()))
+    }
+
+    fn restore(&self, original: &Path, _handle: &Path) -> Result<(), DeleteError> {
+        Err(DeleteError::RestoreFailed {
+            path: original.to_path_buf(),
+            message: "restoring from the trash is not supported on this platform".to_string(),
+        })
+    }
+}
+
+/// Run a transactional batch against the given trash backend.
+fn run_transactional<B: TrashBackend, C: DeleteProgressCallback>(
+    paths: &[PathBuf],
+    config: &DeleteConfig,
+    callback: Option<&C>,
+    backend: &B,
+) -> BatchDeleteResult {
+    let mut result = BatchDeleteResult::default();
+
+    // Refuse up front rather than delete files we could not put back
+    let unavailable = if paths
+        .iter()
+        .any(|p| config.mode_for(p) == DeleteMode::Permanent)
+    {
+        Some("permanent deletion cannot be rolled back; use the trash")
+    } else if !backend.supports_restore() {
+        Some("this platform cannot restore files from the trash")
+    } else {
+        None
+    };
+    if let Some(reason) = unavailable {
+        let error = DeleteError::TransactionUnavailable(reason.to_string());
+        return refuse_batch(paths, &error, callback);
+    }
+    if let Err(e) = config.check_cap(paths) {
+        return refuse_batch(paths, &e, callback);
+    }
+
+    let total = paths.len();
+    let mut handles = Vec::with_capacity(total);
+
+    for (index, path) in paths.iter().enumerate() {
+        if let Some(cb) = callback {
+            cb.on_before_delete(path, index, total);
+        }
+
+        match config.check_guards(path).and_then(|()| backend.trash(path)) {
+            Ok((del, handle)) => {
+                result.bytes_freed += del.size;
+                if let Some(cb) = callback {
+                    cb.on_delete_success(path, del.size);
+                }
+                result.successes.push(del);
+                handles.push(handle);
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                log::warn!("Failed to delete {}: {}", path.display(), error_msg);
+                if let Some(cb) = callback {
+                    cb.on_delete_failure(path, &error_msg);
+                }
+                result.failures.push((path.clone(), error_msg));
+                rollback(&mut result, handles, backend);
+                break;
+            }
+        }
+    }
+
+    if let Some(cb) = callback {
+        cb.on_complete(&result);
+    }
+
+    log::info!("{}", result.summary());
+
+    result
+}
+
+/// Restore every trashed file in `result`, newest first.
+fn rollback<B: TrashBackend>(result: &mut BatchDeleteResult, handles: Vec<PathBuf>, backend: &B) {
+    log::warn!(
+        "Rolling back {} trashed file(s) after a failed deletion",
+        handles.len()
+    );
+    result.rolled_back = true;
+
+    let trashed = std::mem::take(&mut result.successes);
+    for (del, handle) in trashed.into_iter().zip(handles).rev() {
+        match backend.restore(&del.path, &handle) {
+            Ok(()) => {
+                result.bytes_freed -= del.size;
+                result.restored.push(del.path);
+            }
+            Err(e) => {
+                log::error!("Rollback could not restore {}: {}", del.path.display(), e);
+                result
+                    .restore_failures
+                    .push((del.path.clone(), e.to_string()));
+                result.successes.push(del);
+            }
+        }
+    }
+    result.successes.reverse();
+}
+
 /// Validate that a selection doesn't delete all copies.
 ///
 /// At least one copy of each duplicate group must be preserved.
@@ -743,6 +1544,54 @@ mod tests {
         assert!(config.permanent);
     }
 
+    #[test]
+    fn test_delete_mode_rule_precedence() {
+        let config = DeleteConfig::trash()
+            .with_rule("/data", DeleteMode::Permanent)
+            .with_rule("/data/docs", DeleteMode::Trash)
+            .with_permanent_under(&[PathBuf::from("/data/docs/scratch")]);
+
+        let mode = |p: &str| config.mode_for(Path::new(p));
+        assert_eq!(mode("/data/tmp/a.bin"), DeleteMode::Permanent);
+        assert_eq!(mode("/data/docs/report.pdf"), DeleteMode::Trash);
+        assert_eq!(mode("/data/docs/scratch/draft.txt"), DeleteMode::Permanent);
+        // Prefixes match whole components, not string prefixes
+        assert_eq!(mode("/data/docs-old/a.txt"), DeleteMode::Permanent);
+        assert_eq!(mode("/other/a.txt"), DeleteMode::Trash);
+
+        // Equally specific rules: the last one wins
+        let config = config.with_rule("/data/docs", DeleteMode::Permanent);
+        assert_eq!(
+            config.mode_for(Path::new("/data/docs/report.pdf")),
+            DeleteMode::Permanent
+        );
+    }
+
+    #[test]
+    fn test_delete_mode_safe_default() {
+        assert_eq!(
+            DeleteConfig::default().mode_for(Path::new("/any/file.txt")),
+            DeleteMode::Trash
+        );
+        assert_eq!(
+            DeleteConfig::permanent().mode_for(Path::new("/any/file.txt")),
+            DeleteMode::Permanent
+        );
+
+        // Reference paths are never permanently deleted
+        let config = DeleteConfig::permanent()
+            .with_permanent_under(&[PathBuf::from("/data")])
+            .with_protected_paths(vec![PathBuf::from("/data/reference")]);
+        assert_eq!(
+            config.mode_for(Path::new("/data/reference/keep.jpg")),
+            DeleteMode::Trash
+        );
+        assert_eq!(
+            config.mode_for(Path::new("/data/copy.jpg")),
+            DeleteMode::Permanent
+        );
+    }
+
     #[test]
     fn test_delete_config_builders() {
         let config = DeleteConfig::default()
@@ -888,6 +1737,177 @@ mod tests {
     // Note: Actual trash tests are platform-dependent and may not work in all environments.
     // The trash crate handles the platform-specific implementation.
 
+    // ==================== Trash Directory Tests ====================
+
+    #[test]
+    fn test_move_to_trash_dir_creates_dated_folder() {
+        let dir = TempDir::new().unwrap();
+        let trash = dir.path().join("trash");
+        let file = create_temp_file(&dir, "a.txt", b"hello");
+
+        let (result, moved) = move_to_trash_dir_at(&file, &trash, "2026-01-31", false).unwrap();
+        assert_eq!(result.size, 5);
+        assert!(!result.permanent);
+        assert!(!file.exists());
+        assert!(moved.starts_with(trash.join("2026-01-31")));
+        assert!(moved.ends_with(Path::new(dir.path().file_name().unwrap()).join("a.txt")));
+        assert_eq!(fs::read(&moved).unwrap(), b"hello");
+
+        // The public entry point files under today's date
+        let file = create_temp_file(&dir, "b.txt", b"b");
+        move_to_trash_dir(&file, &trash).unwrap();
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        assert!(trash.join(&today).is_dir());
+
+        assert!(matches!(
+            move_to_trash_dir(&dir.path().join("missing.txt"), &trash),
+            Err(DeleteError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_trash_dir_destination_layout() {
+        let trash = Path::new("/srv/trash");
+        assert_eq!(
+            trash_dir_destination(trash, "2026-01-31", Path::new("/home/me/a.jpg")),
+            PathBuf::from("/srv/trash/2026-01-31/home/me/a.jpg")
+        );
+        assert_eq!(
+            trash_dir_destination(trash, "2026-01-31", Path::new("/home/../../etc/x")),
+            PathBuf::from("/srv/trash/2026-01-31/etc/x")
+        );
+    }
+
+    #[test]
+    fn test_move_to_trash_dir_resolves_collisions() {
+        let dir = TempDir::new().unwrap();
+        let trash = dir.path().join("trash");
+        let file = dir.path().join("photo.jpg");
+
+        let mut moved = Vec::new();
+        for content in [&b"one"[..], b"two", b"three"] {
+            fs::write(&file, content).unwrap();
+            moved.push(
+                move_to_trash_dir_at(&file, &trash, "2026-01-31", false)
+                    .unwrap()
+                    .1,
+            );
+        }
+
+        let names: Vec<_> = moved
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["photo.jpg", "photo-1.jpg", "photo-2.jpg"]);
+        assert_eq!(fs::read(&moved[0]).unwrap(), b"one");
+        assert_eq!(fs::read(&moved[2]).unwrap(), b"three");
+
+        let noext = dir.path().join("trash").join("README");
+        fs::write(&noext, "x").unwrap();
+        assert_eq!(unique_destination(&noext), trash.join("README-1"));
+    }
+
+    #[test]
+    fn test_delete_batch_uses_trash_override() {
+        let dir = TempDir::new().unwrap();
+        let trash = dir.path().join("trash");
+        let a = create_temp_file(&dir, "a.txt", b"aaa");
+        let b = create_temp_file(&dir, "b.txt", b"bb");
+
+        let config = DeleteConfig::trash().with_trash_override(Some(trash.clone()));
+        let result = delete_batch::<NoOpCallback>(&[a.clone(), b.clone()], &config, None);
+        assert!(result.all_succeeded());
+        assert!(!a.exists() && !b.exists());
+        assert_eq!(fs::read_dir(&trash).unwrap().count(), 1);
+
+        // Transactional batches move files back out of the trash directory
+        let c = create_temp_file(&dir, "c.txt", b"c");
+        let config = config.with_transactional(true);
+        let result =
+            delete_batch::<NoOpCallback>(&[c.clone(), dir.path().join("gone")], &config, None);
+        assert!(result.rolled_back);
+        assert!(result.rollback_succeeded());
+        assert_eq!(fs::read(&c).unwrap(), b"c");
+    }
+
+    #[test]
+    fn test_restore_from_trash_override() {
+        let dir = TempDir::new().unwrap();
+        let trash = dir.path().join("trash");
+        let a = create_temp_file(&dir, "a.txt", b"aaa");
+
+        let config = DeleteConfig::trash().with_trash_override(Some(trash.clone()));
+        assert!(config.can_restore());
+        let result = delete_batch::<NoOpCallback>(std::slice::from_ref(&a), &config, None);
+        let moved = result.successes[0].trashed_to.clone().unwrap();
+        assert!(moved.starts_with(&trash));
+        assert!(!a.exists());
+
+        config
+            .restore(&a, &TrashLocation::Directory(moved.clone()))
+            .unwrap();
+        assert_eq!(fs::read(&a).unwrap(), b"aaa");
+        assert!(!moved.exists());
+    }
+
+    #[test]
+    fn test_restore_from_trash_dir_recreates_parent() {
+        let dir = TempDir::new().unwrap();
+        let trash = dir.path().join("trash");
+        let sub = dir.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+        let a = sub.join("a.txt");
+        fs::write(&a, b"aaa").unwrap();
+
+        let config = DeleteConfig::trash().with_trash_override(Some(trash));
+        let result = delete_batch::<NoOpCallback>(std::slice::from_ref(&a), &config, None);
+        let moved = result.successes[0].trashed_to.clone().unwrap();
+        fs::remove_dir(&sub).unwrap();
+
+        config
+            .restore(&a, &TrashLocation::Directory(moved))
+            .unwrap();
+        assert_eq!(fs::read(&a).unwrap(), b"aaa");
+    }
+
+    #[test]
+    fn test_restore_from_trash_dir_never_overwrites() {
+        let dir = TempDir::new().unwrap();
+        let trash = dir.path().join("trash");
+        let a = create_temp_file(&dir, "a.txt", b"aaa");
+
+        // Undo: a new file took the original name after trashing
+        let config = DeleteConfig::trash().with_trash_override(Some(trash.clone()));
+        let result = delete_batch::<NoOpCallback>(std::slice::from_ref(&a), &config, None);
+        let moved = result.successes[0].trashed_to.clone().unwrap();
+        fs::write(&a, b"new").unwrap();
+
+        let err = config
+            .restore(&a, &TrashLocation::Directory(moved.clone()))
+            .unwrap_err();
+        assert!(matches!(err, DeleteError::RestoreFailed { .. }));
+        assert_eq!(fs::read(&a).unwrap(), b"new");
+        assert_eq!(fs::read(&moved).unwrap(), b"aaa");
+
+        // Rollback: same check through the trash directory backend
+        let b = create_temp_file(&dir, "b.txt", b"bb");
+        let backend = TrashDir::new(&trash, false);
+        let (deleted, handle) = backend.trash(&b).unwrap();
+        fs::write(&b, b"keeper").unwrap();
+
+        let mut result = BatchDeleteResult {
+            bytes_freed: deleted.size,
+            successes: vec![deleted],
+            ..Default::default()
+        };
+        rollback(&mut result, vec![handle.clone()], &backend);
+        assert!(!result.rollback_succeeded());
+        assert_eq!(result.restore_failures.len(), 1);
+        assert_eq!(result.restore_failures[0].0, b);
+        assert_eq!(fs::read(&b).unwrap(), b"keeper");
+        assert_eq!(fs::read(&handle).unwrap(), b"bb");
+    }
+
     // ==================== delete_batch Tests ====================
 
     #[test]
@@ -952,6 +1972,93 @@ mod tests {
         assert!(existing.exists());
     }
 
+    #[test]
+    fn test_delete_batch_routes_by_rule() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let scratch = dir.path().join("scratch");
+        fs::create_dir(&scratch).unwrap();
+        let path = scratch.join("tmp.bin");
+        fs::write(&path, b"content").unwrap();
+
+        let config = DeleteConfig::trash().with_permanent_under(std::slice::from_ref(&scratch));
+        let result = delete_batch::<NoOpCallback>(&[path.clone()], &config, None);
+
+        assert_eq!(result.success_count(), 1);
+        assert!(result.successes[0].permanent);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_delete_batch_refuses_unsettled_files() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let path = create_temp_file(&dir, "active.log", b"still being written");
+        let config = DeleteConfig::permanent().with_settle_seconds(Some(60));
+
+        let result = delete_batch::<NoOpCallback>(&[path.clone()], &config, None);
+        assert_eq!(result.failure_count(), 1);
+        assert!(result.failures[0].1.contains("within the last 60s"));
+        assert!(path.exists());
+
+        // Outside the window the file is deleted as usual
+        let settled = SystemTime::now() - std::time::Duration::from_secs(61);
+        filetime::set_file_mtime(&path, filetime::FileTime::from_system_time(settled)).unwrap();
+        let result = delete_batch::<NoOpCallback>(&[path.clone()], &config, None);
+        assert_eq!(result.success_count(), 1);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_delete_batch_refuses_to_start_over_cap() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let paths = vec![
+            create_temp_file(&dir, "a.bin", &[0u8; 600]),
+            create_temp_file(&dir, "b.bin", &[0u8; 600]),
+        ];
+        let config = DeleteConfig::permanent().with_max_total_bytes(Some(1000));
+
+        let result = delete_batch::<NoOpCallback>(&paths, &config, None);
+        assert_eq!(result.success_count(), 0);
+        assert_eq!(result.failure_count(), 2);
+        assert!(result.failures[0]
+            .1
+            .contains("exceeds the cap of 1000 bytes"));
+        assert!(paths.iter().all(|p| p.exists()));
+    }
+
+    #[test]
+    fn test_delete_batch_proceeds_under_cap() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let paths = vec![
+            create_temp_file(&dir, "a.bin", &[0u8; 400]),
+            create_temp_file(&dir, "b.bin", &[0u8; 600]),
+        ];
+        let config = DeleteConfig::permanent().with_max_total_bytes(Some(1000));
+
+        let result = delete_batch::<NoOpCallback>(&paths, &config, None);
+        assert!(result.all_succeeded());
+        assert_eq!(result.bytes_freed, 1000);
+        assert!(paths.iter().all(|p| !p.exists()));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_delete_batch_refuses_excluded_owner() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let path = create_temp_file(&dir, "mine.bin", b"content");
+        let uid = crate::scanner::owner::file_owner(&fs::metadata(&path).unwrap()).unwrap();
+
+        let config = DeleteConfig::permanent().with_owner_filter(None, vec![uid]);
+        let result = delete_batch::<NoOpCallback>(&[path.clone()], &config, None);
+        assert_eq!(result.failure_count(), 1);
+        assert!(result.failures[0].1.contains("owner filter"));
+        assert!(path.exists());
+
+        let config = DeleteConfig::permanent().with_owner_filter(Some(vec![uid]), Vec::new());
+        let result = delete_batch::<NoOpCallback>(&[path.clone()], &config, None);
+        assert_eq!(result.success_count(), 1);
+        assert!(!path.exists());
+    }
+
     #[test]
     fn test_delete_batch_with_callback() {
         let dir = TempDir::new().expect("Failed to create temp dir");
@@ -969,6 +2076,160 @@ mod tests {
         assert!(callback.complete_called());
     }
 
+    // ==================== Transactional Tests ====================
+
+    /// Fake trash that moves files into a directory, keyed by original path.
+    struct DirTrash {
+        dir: PathBuf,
+        supports_restore: bool,
+        fail_restore: bool,
+    }
+
+    impl DirTrash {
+        fn new(dir: &TempDir) -> Self {
+            let trash = dir.path().join(".trash");
+            fs::create_dir_all(&trash).unwrap();
+            Self {
+                dir: trash,
+                supports_restore: true,
+                fail_restore: false,
+            }
+        }
+
+        fn slot(&self, original: &Path) -> PathBuf {
+            self.dir.join(original.file_name().unwrap())
+        }
+    }
+
+    impl TrashBackend for DirTrash {
+        fn supports_restore(&self) -> bool {
+            self.supports_restore
+        }
+
+        fn trash(&self, path: &Path) -> Result<(DeleteResult, PathBuf), DeleteError> {
+            let snapshot = FileSnapshot::capture(path)?;
+            fs::rename(path, self.slot(path)).map_err(|e| DeleteError::Io {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+            Ok((
+                DeleteResult::new(path.to_path_buf(), snapshot.size, false),
+                path.to_path_buf(),
+            ))
+        }
+
+        fn restore(&self, original: &Path, _handle: &Path) -> Result<(), DeleteError> {
+            if self.fail_restore {
+                return Err(DeleteError::RestoreFailed {
+                    path: original.to_path_buf(),
+                    message: "simulated".to_string(),
+                });
+            }
+            fs::rename(self.slot(original), original).map_err(|e| DeleteError::Io {
+                path: original.to_path_buf(),
+                source: e,
+            })
+        }
+    }
+
+    #[test]
+    fn test_transactional_rolls_back_on_mid_batch_failure() {
+        let dir = TempDir::new().unwrap();
+        let a = create_temp_file(&dir, "a.txt", b"aaa");
+        let b = create_temp_file(&dir, "b.txt", b"bbbb");
+        let missing = dir.path().join("missing.txt");
+        let d = create_temp_file(&dir, "d.txt", b"d");
+        let backend = DirTrash::new(&dir);
+
+        let paths = vec![a.clone(), b.clone(), missing.clone(), d.clone()];
+        let callback = TestCallback::new();
+        let result = run_transactional(&paths, &DeleteConfig::trash(), Some(&callback), &backend);
+
+        // Stopped at the failure; earlier files are back, later ones untouched
+        assert!(result.rolled_back);
+        assert!(result.rollback_succeeded());
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].0, missing);
+        assert_eq!(result.restored, vec![b.clone(), a.clone()]);
+        assert!(result.successes.is_empty());
+        assert_eq!(result.bytes_freed, 0);
+        assert!(a.exists() && b.exists() && d.exists());
+        assert_eq!(fs::read(&b).unwrap(), b"bbbb");
+        assert_eq!(callback.before_count(), 3);
+        assert!(callback.complete_called());
+        assert!(result.summary().starts_with("Rolled back"));
+    }
+
+    #[test]
+    fn test_transactional_all_succeed_keeps_deletions() {
+        let dir = TempDir::new().unwrap();
+        let a = create_temp_file(&dir, "a.txt", b"aaa");
+        let b = create_temp_file(&dir, "b.txt", b"bb");
+        let backend = DirTrash::new(&dir);
+
+        let paths = vec![a.clone(), b.clone()];
+        let result =
+            run_transactional::<_, NoOpCallback>(&paths, &DeleteConfig::trash(), None, &backend);
+
+        assert!(!result.rolled_back);
+        assert!(result.all_succeeded());
+        assert_eq!(result.bytes_freed, 5);
+        assert!(!a.exists() && !b.exists());
+    }
+
+    #[test]
+    fn test_transactional_reports_failed_restore() {
+        let dir = TempDir::new().unwrap();
+        let a = create_temp_file(&dir, "a.txt", b"aaa");
+        let mut backend = DirTrash::new(&dir);
+        backend.fail_restore = true;
+
+        let paths = vec![a.clone(), dir.path().join("missing.txt")];
+        let result =
+            run_transactional::<_, NoOpCallback>(&paths, &DeleteConfig::trash(), None, &backend);
+
+        assert!(result.rolled_back);
+        assert!(!result.rollback_succeeded());
+        assert_eq!(result.restore_failures.len(), 1);
+        assert_eq!(result.restore_failures[0].0, a);
+        // The unrestored file is still counted as deleted
+        assert_eq!(result.success_count(), 1);
+        assert_eq!(result.bytes_freed, 3);
+        assert!(result.summary().starts_with("Rollback incomplete"));
+    }
+
+    #[test]
+    fn test_transactional_refuses_without_restore_support() {
+        let dir = TempDir::new().unwrap();
+        let a = create_temp_file(&dir, "a.txt", b"aaa");
+        let mut backend = DirTrash::new(&dir);
+        backend.supports_restore = false;
+
+        let paths = vec![a.clone()];
+        let result =
+            run_transactional::<_, NoOpCallback>(&paths, &DeleteConfig::trash(), None, &backend);
+        assert_eq!(result.failure_count(), 1);
+        assert!(result.failures[0]
+            .1
+            .contains("transactional deletion unavailable"));
+        assert!(a.exists());
+
+        // Permanent deletion can never be rolled back
+        let config = DeleteConfig::permanent().with_transactional(true);
+        let result = delete_batch::<NoOpCallback>(&paths, &config, None);
+        assert_eq!(result.failure_count(), 1);
+        assert!(!result.rolled_back);
+        assert!(a.exists());
+
+        // ...including files that only a rule deletes permanently
+        let config = DeleteConfig::trash().with_permanent_under(&[dir.path().to_path_buf()]);
+        let result =
+            run_transactional::<_, NoOpCallback>(&paths, &config, None, &DirTrash::new(&dir));
+        assert_eq!(result.failure_count(), 1);
+        assert!(result.failures[0].1.contains("cannot be rolled back"));
+        assert!(a.exists());
+    }
+
     // ==================== Test Helpers ====================
 
     /// No-op callback for tests that don't need progress reporting.
@@ -4,6 +4,9 @@
 //! - Safe deletion via trash crate
 //! - Permanent deletion (with confirmation)
 //! - File preview (text, binary, image)
+//! - Revealing files in the platform file manager
+//! - Removing directories left empty after deletion
+//! - Applying deletion decisions from an edited CSV/JSON report
 //!
 //! # Deletion
 //!
@@ -30,20 +33,65 @@
 //!
 //! ```no_run
 //! use rustdupe::actions::preview::preview_file_simple;
+//! use rustdupe::output::SizeFormat;
 //! use std::path::Path;
 //!
-//! let content = preview_file_simple(Path::new("example.txt"));
+//! let content = preview_file_simple(Path::new("example.txt"), SizeFormat::default());
 //! println!("{}", content);
 //! ```
+//!
+//! # Reveal
+//!
+//! The reveal module opens the system file manager at a file's location
+//! (Explorer, Finder, or `xdg-open`).
+//!
+//! # Cleanup
+//!
+//! The cleanup module removes directories left empty by a deletion, walking
+//! bottom-up and stopping at the scan roots (opt-in via `--delete-empty-dirs`).
+//!
+//! # Rename
+//!
+//! The rename module applies a sed-style substitution to the file name of
+//! each surviving keeper after a deletion (opt-in via `--rename-keeper`),
+//! refusing to overwrite existing files.
+//!
+//! # Script Runs
+//!
+//! The script_run module runs a saved deletion script through `sh` or
+//! PowerShell after confirmation (`--delete-to-script-and-run`) and collects
+//! the files it failed to delete.
+//!
+//! # Extended Attributes
+//!
+//! The xattrs module carries extended attributes and ACLs over to moved
+//! files (opt-in via `--preserve-xattrs`), warning about any it cannot
+//! reproduce instead of failing.
 
+pub mod apply;
+pub mod cleanup;
 pub mod delete;
 pub mod preview;
+pub mod rename;
+pub mod reveal;
+pub mod script_run;
+pub mod xattrs;
 
 // Re-export commonly used types
+pub use apply::{ApplyError, ApplyOutcome, ApplyPlan, SkipReason, SkippedFile};
+pub use cleanup::{candidate_dirs, find_empty_dirs, remove_empty_dirs};
 pub use delete::{
-    delete_batch, delete_to_trash, delete_verified, permanent_delete, validate_preserves_copy,
-    BatchDeleteResult, DeleteConfig, DeleteError, DeleteProgressCallback, DeleteResult,
-    FileSnapshot,
+    delete_batch, delete_batch_transactional, delete_to_trash, delete_verified, move_to_trash_dir,
+    permanent_delete, restore_from_trash, trash_restore_supported, trash_restore_token,
+    validate_preserves_copy, BatchDeleteResult, DeleteConfig, DeleteError, DeleteMode,
+    DeleteProgressCallback, DeleteResult, FileSnapshot, TrashLocation,
 };
 
-pub use preview::{preview_file, preview_file_simple, PreviewContent, PreviewError, PreviewType};
+pub use preview::{
+    diff_files, preview_file, preview_file_simple, PreviewContent, PreviewError, PreviewType,
+    TextPager,
+};
+pub use rename::{apply_rename, plan_keeper_renames, KeeperRename, RenameError};
+pub use reveal::{reveal_in_file_manager, RevealError};
+pub use script_run::{run_script, ScriptRun, ScriptRunError};
+pub use xattrs::{move_preserving_xattrs, read_xattrs, restore_xattrs, XattrSnapshot};
@@ -1,28 +1,33 @@
 //! File preview functionality.
 //!
 //! This module provides file preview capabilities for the TUI:
-//! - Text file content preview (first 50 lines)
+//! - Text file content preview (first 50 lines), plus a [`TextPager`] for
+//!   scrolling through the rest of the file
 //! - Binary file hex dump (first 256 bytes)
 //! - Image file metadata (dimensions, format, size)
+//! - Line-level unified diff between two text files
 //!
 //! # Performance
 //!
 //! All preview functions limit data to the first 4KB for fast loading.
-//! This ensures responsive UI even for very large files.
+//! This ensures responsive UI even for very large files. The pager reads
+//! further lines on demand and only remembers where each line starts.
 //!
 //! # Example
 //!
 //! ```
 //! use rustdupe::actions::preview::{preview_file, PreviewContent, PreviewType};
+//! use rustdupe::output::SizeFormat;
 //! use std::path::Path;
 //!
-//! let result = preview_file(Path::new("test.txt"));
+//! let result = preview_file(Path::new("test.txt"), SizeFormat::default());
 //! match result {
 //!     Ok(content) => match content.preview_type {
 //!         PreviewType::Text => println!("Text: {}", content.content),
 //!         PreviewType::Binary => println!("Binary: {}", content.content),
 //!         PreviewType::Image => println!("Image: {}", content.content),
 //!         PreviewType::Empty => println!("Empty file"),
+//!         PreviewType::Diff => println!("Diff: {}", content.content),
 //!         PreviewType::Error => println!("Error: {}", content.content),
 //!     },
 //!     Err(e) => println!("Preview failed: {}", e),
@@ -31,9 +36,13 @@
 
 use std::fs::{self, File};
 use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use similar::TextDiff;
 use thiserror::Error;
 
+use crate::output::size::SizeFormat;
+
 /// Maximum bytes to read for preview (4KB).
 const MAX_PREVIEW_BYTES: usize = 4096;
 
@@ -46,6 +55,12 @@ const HEX_DUMP_BYTES: usize = 256;
 /// Bytes to sample for binary detection.
 const BINARY_DETECT_BYTES: usize = 512;
 
+/// Maximum bytes read from each file when computing a diff (1MB).
+const MAX_DIFF_BYTES: u64 = 1024 * 1024;
+
+/// Unchanged lines shown around each diff hunk.
+const DIFF_CONTEXT_LINES: usize = 3;
+
 /// Known text file extensions.
 const TEXT_EXTENSIONS: &[&str] = &[
     "txt",
@@ -132,6 +147,8 @@ pub enum PreviewType {
     Image,
     /// Empty file.
     Empty,
+    /// Unified diff against another file.
+    Diff,
     /// Error message.
     Error,
 }
@@ -147,6 +164,8 @@ pub struct PreviewContent {
     pub file_size: u64,
     /// Optional file metadata.
     pub metadata: Option<PreviewMetadata>,
+    /// Pager over the whole file (text previews only).
+    pub pager: Option<TextPager>,
 }
 
 /// Additional metadata for preview.
@@ -173,6 +192,7 @@ impl PreviewContent {
                 line_count: Some(line_count),
                 dimensions: None,
             }),
+            pager: None,
         }
     }
 
@@ -184,6 +204,7 @@ impl PreviewContent {
             content: hex_dump,
             file_size,
             metadata: None,
+            pager: None,
         }
     }
 
@@ -199,6 +220,7 @@ impl PreviewContent {
                 line_count: None,
                 dimensions,
             }),
+            pager: None,
         }
     }
 
@@ -210,6 +232,24 @@ impl PreviewContent {
             content: "(empty file)".to_string(),
             file_size,
             metadata: None,
+            pager: None,
+        }
+    }
+
+    /// Create a unified diff preview.
+    #[must_use]
+    pub fn diff(diff: String, file_size: u64) -> Self {
+        let line_count = diff.lines().count();
+        Self {
+            preview_type: PreviewType::Diff,
+            content: diff,
+            file_size,
+            metadata: Some(PreviewMetadata {
+                extension: None,
+                line_count: Some(line_count),
+                dimensions: None,
+            }),
+            pager: None,
         }
     }
 
@@ -221,7 +261,115 @@ impl PreviewContent {
             content: message,
             file_size: 0,
             metadata: None,
+            pager: None,
+        }
+    }
+}
+
+/// Line-by-line view of a text file that reads lazily from disk.
+///
+/// Only the byte offset of each line seen so far is kept. Lines are read on
+/// demand, and the file is indexed no further than the furthest line asked
+/// for, so paging through a multi-gigabyte log never buffers it.
+///
+/// # Example
+///
+/// ```no_run
+/// use rustdupe::actions::preview::TextPager;
+/// use std::path::Path;
+///
+/// let mut pager = TextPager::new(Path::new("server.log"));
+/// for line in pager.read_lines(1000, 20).unwrap() {
+///     println!("{}", line);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextPager {
+    path: PathBuf,
+    /// Byte offset where each indexed line starts
+    line_starts: Vec<u64>,
+    /// Byte offset just past the last indexed line
+    indexed_to: u64,
+    /// Whether the whole file has been indexed
+    complete: bool,
+}
+
+impl TextPager {
+    /// Create a pager over `path`. Nothing is read until lines are requested.
+    #[must_use]
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            line_starts: Vec::new(),
+            indexed_to: 0,
+            complete: false,
+        }
+    }
+
+    /// Path of the paged file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Total number of lines, once the end of the file has been reached.
+    #[must_use]
+    pub fn line_count(&self) -> Option<usize> {
+        self.complete.then_some(self.line_starts.len())
+    }
+
+    /// Whether line `line` (0-based) exists, indexing up to it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn has_line(&mut self, line: usize) -> io::Result<bool> {
+        if line >= self.line_starts.len() && !self.complete {
+            let mut reader = BufReader::new(File::open(&self.path)?);
+            reader.seek(SeekFrom::Start(self.indexed_to))?;
+            let mut buf = Vec::new();
+            while line >= self.line_starts.len() {
+                buf.clear();
+                let read = reader.read_until(b'\n', &mut buf)?;
+                if read == 0 {
+                    self.complete = true;
+                    break;
+                }
+                self.line_starts.push(self.indexed_to);
+                self.indexed_to += read as u64;
+            }
+        }
+        Ok(line < self.line_starts.len())
+    }
+
+    /// Read up to `count` lines starting at line `first` (0-based).
+    ///
+    /// Returns fewer lines near the end of the file and none past it.
+    /// Invalid UTF-8 is replaced rather than rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn read_lines(&mut self, first: usize, count: usize) -> io::Result<Vec<String>> {
+        if count == 0 || !self.has_line(first)? {
+            return Ok(Vec::new());
+        }
+        self.has_line(first.saturating_add(count - 1))?;
+        let available = count.min(self.line_starts.len() - first);
+
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        reader.seek(SeekFrom::Start(self.line_starts[first]))?;
+        let mut lines = Vec::with_capacity(available);
+        let mut buf = Vec::new();
+        for _ in 0..available {
+            buf.clear();
+            if reader.read_until(b'\n', &mut buf)? == 0 {
+                break;
+            }
+            let line = String::from_utf8_lossy(&buf);
+            lines.push(line.trim_end_matches(['\n', '\r']).to_string());
         }
+        Ok(lines)
     }
 }
 
@@ -233,6 +381,7 @@ impl PreviewContent {
 /// # Arguments
 ///
 /// * `path` - Path to the file to preview
+/// * `size_format` - Unit system for sizes shown in the preview
 ///
 /// # Returns
 ///
@@ -246,14 +395,15 @@ impl PreviewContent {
 ///
 /// ```no_run
 /// use rustdupe::actions::preview::preview_file;
+/// use rustdupe::output::SizeFormat;
 /// use std::path::Path;
 ///
-/// let result = preview_file(Path::new("example.txt"));
+/// let result = preview_file(Path::new("example.txt"), SizeFormat::default());
 /// if let Ok(preview) = result {
 ///     println!("{}", preview.content);
 /// }
 /// ```
-pub fn preview_file(path: &Path) -> Result<PreviewContent, PreviewError> {
+pub fn preview_file(path: &Path, size_format: SizeFormat) -> Result<PreviewContent, PreviewError> {
     // Get file metadata
     let metadata = match fs::metadata(path) {
         Ok(m) => m,
@@ -282,7 +432,7 @@ pub fn preview_file(path: &Path) -> Result<PreviewContent, PreviewError> {
     // Check if it's an image file
     if let Some(ref ext) = extension {
         if IMAGE_EXTENSIONS.contains(&ext.as_str()) {
-            return preview_image(path, file_size, ext);
+            return preview_image(path, file_size, ext, size_format);
         }
     }
 
@@ -300,6 +450,94 @@ pub fn preview_file(path: &Path) -> Result<PreviewContent, PreviewError> {
     }
 }
 
+/// Compute a line-level unified diff between two files.
+///
+/// `a` is shown as the old side (`-` lines) and `b` as the new side
+/// (`+` lines), with three lines of context around each hunk. Only the
+/// first 1MB of each file is compared. Binary files are not diffed; a
+/// `PreviewType::Binary` message explaining why is returned instead.
+///
+/// # Arguments
+///
+/// * `a` - The file to diff from (e.g. the group's reference file)
+/// * `b` - The file to diff to (e.g. the file under review)
+/// * `size_format` - Unit system for the truncation notice
+///
+/// # Errors
+///
+/// Returns `PreviewError` if either file cannot be read.
+///
+/// # Example
+///
+/// ```no_run
+/// use rustdupe::actions::preview::diff_files;
+/// use rustdupe::output::SizeFormat;
+/// use std::path::Path;
+///
+/// let diff = diff_files(
+///     Path::new("notes.txt"),
+///     Path::new("notes (1).txt"),
+///     SizeFormat::default(),
+/// )
+/// .unwrap();
+/// println!("{}", diff.content);
+/// ```
+pub fn diff_files(
+    a: &Path,
+    b: &Path,
+    size_format: SizeFormat,
+) -> Result<PreviewContent, PreviewError> {
+    let (old, old_truncated) = read_for_diff(a)?;
+    let (new, new_truncated) = read_for_diff(b)?;
+    let file_size = fs::metadata(b).map(|m| m.len()).unwrap_or(0);
+
+    let sample = |data: &[u8]| is_binary(&data[..data.len().min(BINARY_DETECT_BYTES)]);
+    if sample(&old) || sample(&new) {
+        return Ok(PreviewContent {
+            preview_type: PreviewType::Binary,
+            content: format!(
+                "Binary files cannot be diffed line by line:\n  {}\n  {}",
+                a.display(),
+                b.display()
+            ),
+            file_size,
+            metadata: None,
+            pager: None,
+        });
+    }
+
+    let old = String::from_utf8_lossy(&old);
+    let new = String::from_utf8_lossy(&new);
+    let diff = TextDiff::from_lines(old.as_ref(), new.as_ref());
+
+    let mut output = diff
+        .unified_diff()
+        .context_radius(DIFF_CONTEXT_LINES)
+        .header(&a.display().to_string(), &b.display().to_string())
+        .to_string();
+    if output.is_empty() {
+        output = "(no differences in text content)\n".to_string();
+    }
+    if old_truncated || new_truncated {
+        output.push_str(&format!(
+            "\n(diff limited to the first {} of each file)\n",
+            size_format.format(MAX_DIFF_BYTES)
+        ));
+    }
+
+    Ok(PreviewContent::diff(output, file_size))
+}
+
+/// Read up to `MAX_DIFF_BYTES` of a file, reporting whether it was cut short.
+fn read_for_diff(path: &Path) -> Result<(Vec<u8>, bool), PreviewError> {
+    let file = open_file(path)?;
+    let mut data = Vec::new();
+    file.take(MAX_DIFF_BYTES + 1).read_to_end(&mut data)?;
+    let truncated = data.len() as u64 > MAX_DIFF_BYTES;
+    data.truncate(MAX_DIFF_BYTES as usize);
+    Ok((data, truncated))
+}
+
 /// Preview a text file, returning the first N lines.
 ///
 /// # Arguments
@@ -348,11 +586,9 @@ fn preview_text(path: &Path, file_size: u64) -> Result<PreviewContent, PreviewEr
     }
 
     let shown_lines = lines.len();
-    Ok(PreviewContent::text(
-        lines.join("\n"),
-        file_size,
-        shown_lines,
-    ))
+    let mut preview = PreviewContent::text(lines.join("\n"), file_size, shown_lines);
+    preview.pager = Some(TextPager::new(path));
+    Ok(preview)
 }
 
 /// Preview a binary file with hex dump.
@@ -386,6 +622,7 @@ fn preview_binary(path: &Path, file_size: u64) -> Result<PreviewContent, Preview
 /// * `path` - Path to the file
 /// * `file_size` - File size in bytes
 /// * `extension` - File extension
+/// * `size_format` - Unit system for the displayed size
 ///
 /// # Returns
 ///
@@ -394,9 +631,10 @@ fn preview_image(
     path: &Path,
     file_size: u64,
     extension: &str,
+    size_format: SizeFormat,
 ) -> Result<PreviewContent, PreviewError> {
     // Format file size
-    let size_str = format_file_size(file_size);
+    let size_str = size_format.format(file_size);
 
     // Try to read image dimensions (basic detection for common formats)
     let dimensions = detect_image_dimensions(path, extension);
@@ -520,23 +758,6 @@ fn format_hex_dump(data: &[u8]) -> String {
     output
 }
 
-/// Format file size as human-readable string.
-fn format_file_size(size: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-
-    if size >= GB {
-        format!("{:.2} GB", size as f64 / GB as f64)
-    } else if size >= MB {
-        format!("{:.2} MB", size as f64 / MB as f64)
-    } else if size >= KB {
-        format!("{:.2} KB", size as f64 / KB as f64)
-    } else {
-        format!("{} bytes", size)
-    }
-}
-
 /// Detect image dimensions for common formats.
 ///
 /// This is a simplified detection that reads the header bytes.
@@ -657,6 +878,7 @@ fn detect_bmp_dimensions(header: &[u8]) -> Option<(u32, u32)> {
 /// # Arguments
 ///
 /// * `path` - Path to the file to preview
+/// * `size_format` - Unit system for sizes shown in the preview
 ///
 /// # Returns
 ///
@@ -665,14 +887,15 @@ fn detect_bmp_dimensions(header: &[u8]) -> Option<(u32, u32)> {
 ///
 /// ```no_run
 /// use rustdupe::actions::preview::preview_file_simple;
+/// use rustdupe::output::SizeFormat;
 /// use std::path::Path;
 ///
-/// let content = preview_file_simple(Path::new("test.txt"));
+/// let content = preview_file_simple(Path::new("test.txt"), SizeFormat::default());
 /// println!("{}", content);
 /// ```
 #[must_use]
-pub fn preview_file_simple(path: &Path) -> String {
-    match preview_file(path) {
+pub fn preview_file_simple(path: &Path, size_format: SizeFormat) -> String {
+    match preview_file(path, size_format) {
         Ok(content) => content.content,
         Err(e) => format!("Preview error: {}", e),
     }
@@ -726,12 +949,34 @@ mod tests {
         writeln!(file, "Line 2").unwrap();
         writeln!(file, "Line 3").unwrap();
 
-        let result = preview_file(file.path());
+        let result = preview_file(file.path(), SizeFormat::default());
         assert!(result.is_ok());
         let preview = result.unwrap();
         assert_eq!(preview.preview_type, PreviewType::Text);
         assert!(preview.content.contains("Line 1"));
         assert!(preview.content.contains("Line 2"));
+        assert!(preview.pager.is_some());
+    }
+
+    #[test]
+    fn test_text_pager_reads_lazily() {
+        let mut file = NamedTempFile::new().unwrap();
+        for i in 0..100 {
+            writeln!(file, "Line {}", i).unwrap();
+        }
+        write!(file, "last\r\n").unwrap();
+
+        let mut pager = TextPager::new(file.path());
+        assert_eq!(pager.read_lines(10, 2).unwrap(), ["Line 10", "Line 11"]);
+        // Indexed only as far as needed
+        assert_eq!(pager.line_count(), None);
+
+        assert_eq!(pager.read_lines(99, 5).unwrap(), ["Line 99", "last"]);
+        assert_eq!(pager.line_count(), Some(101));
+        assert!(pager.has_line(100).unwrap());
+        assert!(!pager.has_line(101).unwrap());
+        assert!(pager.read_lines(101, 5).unwrap().is_empty());
+        assert!(pager.read_lines(0, 0).unwrap().is_empty());
     }
 
     #[test]
@@ -739,7 +984,7 @@ mod tests {
         let file = NamedTempFile::new().unwrap();
         // Don't write anything - file is empty
 
-        let result = preview_file(file.path());
+        let result = preview_file(file.path(), SizeFormat::default());
         assert!(result.is_ok());
         let preview = result.unwrap();
         assert_eq!(preview.preview_type, PreviewType::Empty);
@@ -752,7 +997,7 @@ mod tests {
         file.write_all(&[0x00, 0x01, 0x02, 0x03, 0xFF, 0xFE])
             .unwrap();
 
-        let result = preview_file(file.path());
+        let result = preview_file(file.path(), SizeFormat::default());
         assert!(result.is_ok());
         let preview = result.unwrap();
         assert_eq!(preview.preview_type, PreviewType::Binary);
@@ -760,7 +1005,7 @@ mod tests {
 
     #[test]
     fn test_preview_nonexistent_file() {
-        let result = preview_file(Path::new("/nonexistent/file.txt"));
+        let result = preview_file(Path::new("/nonexistent/file.txt"), SizeFormat::default());
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), PreviewError::NotFound(_)));
     }
@@ -789,11 +1034,15 @@ mod tests {
     }
 
     #[test]
-    fn test_format_file_size() {
-        assert_eq!(format_file_size(100), "100 bytes");
-        assert_eq!(format_file_size(1024), "1.00 KB");
-        assert_eq!(format_file_size(1024 * 1024), "1.00 MB");
-        assert_eq!(format_file_size(1024 * 1024 * 1024), "1.00 GB");
+    fn test_image_preview_size_honors_units() {
+        let file = write_temp(&[0u8; 2048]);
+        let path = file.path();
+
+        let iec = preview_image(path, 2048, "png", SizeFormat::IecProper).unwrap();
+        assert!(iec.content.contains("Size: 2.0 KiB"));
+
+        let si = preview_image(path, 2048, "png", SizeFormat::Si).unwrap();
+        assert!(si.content.contains("Size: 2.0 kB"));
     }
 
     #[test]
@@ -844,13 +1093,13 @@ mod tests {
         let mut file = NamedTempFile::new().unwrap();
         writeln!(file, "Simple content").unwrap();
 
-        let content = preview_file_simple(file.path());
+        let content = preview_file_simple(file.path(), SizeFormat::default());
         assert!(content.contains("Simple content"));
     }
 
     #[test]
     fn test_preview_file_simple_error() {
-        let content = preview_file_simple(Path::new("/nonexistent/path"));
+        let content = preview_file_simple(Path::new("/nonexistent/path"), SizeFormat::default());
         assert!(content.contains("error") || content.contains("Error"));
     }
 
@@ -862,4 +1111,71 @@ mod tests {
         let err = PreviewError::PermissionDenied("secret.txt".to_string());
         assert!(err.to_string().contains("permission denied"));
     }
+
+    fn write_temp(content: &[u8]) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_diff_files_added_lines() {
+        let a = write_temp(b"one\ntwo\n");
+        let b = write_temp(b"one\ntwo\nthree\n");
+
+        let diff = diff_files(a.path(), b.path(), SizeFormat::default()).unwrap();
+        assert_eq!(diff.preview_type, PreviewType::Diff);
+        assert!(diff.content.contains("\n+three\n"));
+        assert!(!diff
+            .content
+            .lines()
+            .any(|l| l.starts_with('-') && !l.starts_with("---")));
+    }
+
+    #[test]
+    fn test_diff_files_removed_lines() {
+        let a = write_temp(b"one\ntwo\nthree\n");
+        let b = write_temp(b"one\nthree\n");
+
+        let diff = diff_files(a.path(), b.path(), SizeFormat::default()).unwrap();
+        assert_eq!(diff.preview_type, PreviewType::Diff);
+        assert!(diff.content.contains("\n-two\n"));
+        assert!(!diff
+            .content
+            .lines()
+            .any(|l| l.starts_with('+') && !l.starts_with("+++")));
+    }
+
+    #[test]
+    fn test_diff_files_changed_lines() {
+        let a = write_temp(b"alpha\nbeta\ngamma\n");
+        let b = write_temp(b"alpha\nBETA\ngamma\n");
+
+        let diff = diff_files(a.path(), b.path(), SizeFormat::default()).unwrap();
+        assert!(diff.content.starts_with("--- "));
+        assert!(diff.content.contains("@@"));
+        assert!(diff.content.contains("\n-beta\n+BETA\n"));
+        assert!(diff.content.contains("\n alpha\n"));
+    }
+
+    #[test]
+    fn test_diff_files_identical() {
+        let a = write_temp(b"same\n");
+        let b = write_temp(b"same\n");
+
+        let diff = diff_files(a.path(), b.path(), SizeFormat::default()).unwrap();
+        assert_eq!(diff.preview_type, PreviewType::Diff);
+        assert!(diff.content.contains("no differences"));
+    }
+
+    #[test]
+    fn test_diff_files_binary_fallback() {
+        let a = write_temp(&[0x00, 0x01, 0x02, 0xFF]);
+        let b = write_temp(b"text\n");
+
+        let diff = diff_files(a.path(), b.path(), SizeFormat::default()).unwrap();
+        assert_eq!(diff.preview_type, PreviewType::Binary);
+        assert!(diff.content.contains("Binary files cannot be diffed"));
+    }
 }
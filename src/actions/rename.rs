@@ -0,0 +1,451 @@
+//! Renaming of surviving keepers after deleting duplicates.
+//!
+//! # Overview
+//!
+//! Copies such as `report (1).pdf` often end up as the only file left in a
+//! group. With `--rename-keeper 's/ \(\d+\)//'` the surviving keeper of every
+//! group that had files deleted is renamed by a sed-style substitution
+//! applied to its file name:
+//! - Only the file name changes; the file stays in its directory
+//! - Capture groups are available as `\1` (sed style) or `$1` (regex style)
+//! - Without the `g` flag only the first match is replaced
+//! - A rename never overwrites an existing file, and a name that would leave
+//!   the directory (empty, `.`, `..` or containing a separator) is refused
+//!
+//! Renaming is opt-in and skipped in dry-run mode, where the intended renames
+//! are reported instead. With `--preserve-xattrs` the keeper's extended
+//! attributes are re-applied after the rename.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::actions::rename::KeeperRename;
+//! use std::path::Path;
+//!
+//! let rule = KeeperRename::parse(r"s/ \(\d+\)//").unwrap();
+//! match rule.apply(Path::new("/photos/beach (1).jpg")) {
+//!     Ok(Some(new_path)) => println!("Renamed to {}", new_path.display()),
+//!     Ok(None) => println!("Name unchanged"),
+//!     Err(e) => eprintln!("{}", e),
+//! }
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use thiserror::Error;
+
+use super::xattrs::move_preserving_xattrs;
+use crate::duplicates::DuplicateGroup;
+
+/// Errors that can occur when renaming a keeper.
+#[derive(Debug, Error)]
+pub enum RenameError {
+    /// The substitution is not of the form `s/PATTERN/REPLACEMENT/[g]`.
+    #[error("invalid rename expression '{0}': expected s/PATTERN/REPLACEMENT/[g]")]
+    InvalidExpression(String),
+
+    /// The pattern is not a valid regular expression.
+    #[error("invalid rename pattern: {0}")]
+    InvalidPattern(#[from] regex::Error),
+
+    /// The substitution produced a name that is not a plain file name.
+    #[error("refusing to rename {path}: '{name}' is not a valid file name")]
+    InvalidFileName { path: PathBuf, name: String },
+
+    /// The target name is already taken.
+    #[error("refusing to rename {from} to {to}: target already exists")]
+    TargetExists { from: PathBuf, to: PathBuf },
+
+    /// General I/O error.
+    #[error("failed to rename {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// A sed-style substitution applied to keeper file names.
+#[derive(Debug, Clone)]
+pub struct KeeperRename {
+    pattern: Regex,
+    replacement: String,
+    global: bool,
+    preserve_xattrs: bool,
+}
+
+impl KeeperRename {
+    /// Create a rule replacing every match of `pattern` with `replacement`.
+    ///
+    /// `replacement` uses regex syntax for capture groups (`$1`, `${name}`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenameError::InvalidPattern`] if `pattern` does not compile.
+    pub fn new(pattern: &str, replacement: &str) -> Result<Self, RenameError> {
+        Ok(Self {
+            pattern: Regex::new(pattern)?,
+            replacement: replacement.to_string(),
+            global: true,
+            preserve_xattrs: false,
+        })
+    }
+
+    /// Parse a sed-style `s/PATTERN/REPLACEMENT/[g]` expression.
+    ///
+    /// Any character may be used as the delimiter (`s|a|b|`), and it can be
+    /// escaped with a backslash inside the pattern or replacement. `\1` to
+    /// `\9` in the replacement refer to capture groups.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenameError::InvalidExpression`] if the expression is
+    /// malformed, or [`RenameError::InvalidPattern`] if the pattern does not
+    /// compile.
+    pub fn parse(expr: &str) -> Result<Self, RenameError> {
+        let invalid = || RenameError::InvalidExpression(expr.to_string());
+
+        let rest = expr.strip_prefix('s').ok_or_else(invalid)?;
+        let mut chars = rest.chars();
+        let delimiter = chars.next().filter(|c| *c != '\\').ok_or_else(invalid)?;
+
+        let mut parts = vec![String::new()];
+        let mut escaped = false;
+        for c in chars {
+            let part = parts.last_mut().expect("parts is never empty");
+            if escaped {
+                if c != delimiter {
+                    part.push('\\');
+                }
+                part.push(c);
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == delimiter {
+                parts.push(String::new());
+            } else {
+                part.push(c);
+            }
+        }
+        if escaped || parts.len() != 3 {
+            return Err(invalid());
+        }
+
+        let global = match parts[2].as_str() {
+            "" => false,
+            "g" => true,
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self {
+            pattern: Regex::new(&parts[0])?,
+            replacement: sed_to_regex_replacement(&parts[1]),
+            global,
+            preserve_xattrs: false,
+        })
+    }
+
+    /// Carry extended attributes (and ACLs stored in them) over to the
+    /// renamed file. See [`crate::actions::xattrs`].
+    #[must_use]
+    pub fn with_preserve_xattrs(mut self, preserve: bool) -> Self {
+        self.preserve_xattrs = preserve;
+        self
+    }
+
+    /// Compute the new path for `path`, or `None` if the name is unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenameError::InvalidFileName`] if the new name would not be
+    /// a plain file name in the same directory.
+    pub fn target(&self, path: &Path) -> Result<Option<PathBuf>, RenameError> {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            return Ok(None);
+        };
+
+        let limit = if self.global { 0 } else { 1 };
+        let new_name = self
+            .pattern
+            .replacen(name, limit, self.replacement.as_str());
+        if new_name == name {
+            return Ok(None);
+        }
+
+        if new_name.is_empty()
+            || new_name == "."
+            || new_name == ".."
+            || new_name.contains(std::path::is_separator)
+        {
+            return Err(RenameError::InvalidFileName {
+                path: path.to_path_buf(),
+                name: new_name.into_owned(),
+            });
+        }
+
+        Ok(Some(path.with_file_name(&*new_name)))
+    }
+
+    /// Rename `path` according to this rule.
+    ///
+    /// # Errors
+    ///
+    /// See [`apply_rename`].
+    pub fn apply(&self, path: &Path) -> Result<Option<PathBuf>, RenameError> {
+        let Some(target) = self.target(path)? else {
+            return Ok(None);
+        };
+
+        // symlink_metadata also catches dangling symlinks at the target
+        if fs::symlink_metadata(&target).is_ok() {
+            return Err(RenameError::TargetExists {
+                from: path.to_path_buf(),
+                to: target,
+            });
+        }
+
+        let renamed = if self.preserve_xattrs {
+            move_preserving_xattrs(path, &target, |from, to| fs::rename(from, to))
+        } else {
+            fs::rename(path, &target)
+        };
+        renamed.map_err(|source| RenameError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        log::info!("Renamed {} to {}", path.display(), target.display());
+        Ok(Some(target))
+    }
+}
+
+/// Rename the file at `path` by replacing `pattern` with `replacement` in its
+/// file name.
+///
+/// Every match is replaced; `replacement` uses regex syntax for capture
+/// groups (`$1`). The file keeps its directory.
+///
+/// # Returns
+///
+/// The new path, or `None` if the substitution leaves the name unchanged.
+///
+/// # Errors
+///
+/// - [`RenameError::InvalidPattern`] if `pattern` does not compile
+/// - [`RenameError::InvalidFileName`] if the new name is not a plain file name
+/// - [`RenameError::TargetExists`] if a file already has the new name
+/// - [`RenameError::Io`] if the rename itself fails
+pub fn apply_rename(
+    path: &Path,
+    pattern: &str,
+    replacement: &str,
+) -> Result<Option<PathBuf>, RenameError> {
+    KeeperRename::new(pattern, replacement)?.apply(path)
+}
+
+/// Plan keeper renames for groups that lose files to deletion.
+///
+/// In every group with at least one file in `is_deleted`, the first file that
+/// survives is the keeper; it is paired with its new path when `rule` changes
+/// its name. Groups where every copy is deleted, or nothing is deleted, are
+/// left out. Names the rule cannot produce are logged and skipped.
+#[must_use]
+pub fn plan_keeper_renames(
+    groups: &[DuplicateGroup],
+    is_deleted: impl Fn(&Path) -> bool,
+    rule: &KeeperRename,
+) -> Vec<(PathBuf, PathBuf)> {
+    let mut planned = Vec::new();
+    for group in groups {
+        if !group.files.iter().any(|f| is_deleted(&f.path)) {
+            continue;
+        }
+        let Some(keeper) = group.files.iter().find(|f| !is_deleted(&f.path)) else {
+            continue;
+        };
+        match rule.target(&keeper.path) {
+            Ok(Some(target)) => {
+                if !planned.iter().any(|(from, _)| from == &keeper.path) {
+                    planned.push((keeper.path.clone(), target));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+    planned
+}
+
+/// Convert sed-style `\1` back-references to regex `${1}` syntax.
+///
+/// Literal `$` signs are escaped so they are not read as capture references.
+fn sed_to_regex_replacement(replacement: &str) -> String {
+    let mut out = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(d) if d.is_ascii_digit() => {
+                    out.push_str("${");
+                    out.push(d);
+                    out.push('}');
+                }
+                Some('$') => out.push_str("$$"),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            },
+            '$' if !matches!(chars.peek(), Some(d) if d.is_ascii_digit() || *d == '{') => {
+                out.push_str("$$");
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    fn make_group(paths: &[&str]) -> DuplicateGroup {
+        DuplicateGroup::new(
+            [0u8; 32],
+            10,
+            paths
+                .iter()
+                .map(|p| FileEntry::new(PathBuf::from(p), 10, SystemTime::UNIX_EPOCH))
+                .collect(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn test_parse_strips_copy_suffix() {
+        let rule = KeeperRename::parse(r"s/ \(\d+\)//").unwrap();
+        assert_eq!(
+            rule.target(Path::new("/photos/beach (1).jpg")).unwrap(),
+            Some(PathBuf::from("/photos/beach.jpg"))
+        );
+        assert_eq!(rule.target(Path::new("/photos/beach.jpg")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_capture_groups_and_flags() {
+        let rule = KeeperRename::parse(r"s/^(\w+)-copy/\1/").unwrap();
+        assert_eq!(
+            rule.target(Path::new("/d/notes-copy.txt")).unwrap(),
+            Some(PathBuf::from("/d/notes.txt"))
+        );
+
+        // First match only unless the g flag is given
+        let first = KeeperRename::parse("s/_/-/").unwrap();
+        let all = KeeperRename::parse("s/_/-/g").unwrap();
+        let path = Path::new("/d/a_b_c.txt");
+        assert_eq!(
+            first.target(path).unwrap(),
+            Some(PathBuf::from("/d/a-b_c.txt"))
+        );
+        assert_eq!(
+            all.target(path).unwrap(),
+            Some(PathBuf::from("/d/a-b-c.txt"))
+        );
+
+        // Custom delimiter, escaped delimiter and a literal dollar sign
+        let rule = KeeperRename::parse(r"s#x\#y#$#").unwrap();
+        assert_eq!(
+            rule.target(Path::new("/d/ax#y.txt")).unwrap(),
+            Some(PathBuf::from("/d/a$.txt"))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_expressions() {
+        for expr in [
+            "", "s", "s/a/b", "s/a/b/x", "s/a/b/c/", "y/a/b/", r"s/a/b/\",
+        ] {
+            assert!(
+                matches!(
+                    KeeperRename::parse(expr),
+                    Err(RenameError::InvalidExpression(_))
+                ),
+                "{expr:?} should be rejected"
+            );
+        }
+        assert!(matches!(
+            KeeperRename::parse("s/(/x/"),
+            Err(RenameError::InvalidPattern(_))
+        ));
+    }
+
+    #[test]
+    fn test_target_rejects_names_leaving_directory() {
+        let rule = KeeperRename::new(".*", "").unwrap();
+        assert!(matches!(
+            rule.target(Path::new("/d/file.txt")),
+            Err(RenameError::InvalidFileName { .. })
+        ));
+
+        let rule = KeeperRename::new("^", "../").unwrap();
+        assert!(matches!(
+            rule.target(Path::new("/d/file.txt")),
+            Err(RenameError::InvalidFileName { .. })
+        ));
+    }
+
+    #[test]
+    fn test_apply_rename_renames_file() {
+        let temp = TempDir::new().unwrap();
+        let original = temp.path().join("song (2).mp3");
+        fs::write(&original, b"audio").unwrap();
+
+        let renamed = apply_rename(&original, r" \(\d+\)", "").unwrap();
+
+        let expected = temp.path().join("song.mp3");
+        assert_eq!(renamed, Some(expected.clone()));
+        assert!(!original.exists());
+        assert_eq!(fs::read(&expected).unwrap(), b"audio");
+    }
+
+    #[test]
+    fn test_apply_rename_refuses_to_overwrite() {
+        let temp = TempDir::new().unwrap();
+        let original = temp.path().join("song (2).mp3");
+        let existing = temp.path().join("song.mp3");
+        fs::write(&original, b"copy").unwrap();
+        fs::write(&existing, b"unrelated").unwrap();
+
+        let err = apply_rename(&original, r" \(\d+\)", "").unwrap_err();
+
+        assert!(matches!(err, RenameError::TargetExists { .. }));
+        assert_eq!(fs::read(&original).unwrap(), b"copy");
+        assert_eq!(fs::read(&existing).unwrap(), b"unrelated");
+    }
+
+    #[test]
+    fn test_plan_keeper_renames_uses_first_survivor() {
+        let groups = vec![
+            make_group(&["/a/x (1).txt", "/a/x (2).txt", "/b/x (3).txt"]),
+            make_group(&["/c/y (1).txt", "/c/y (2).txt"]),
+            make_group(&["/d/z (1).txt", "/d/z (2).txt"]),
+        ];
+        let deleted = [
+            PathBuf::from("/a/x (1).txt"),
+            PathBuf::from("/d/z (1).txt"),
+            PathBuf::from("/d/z (2).txt"),
+        ];
+        let rule = KeeperRename::parse(r"s/ \(\d+\)//").unwrap();
+
+        let planned = plan_keeper_renames(&groups, |p| deleted.iter().any(|d| d == p), &rule);
+
+        // Group 2 lost nothing and group 3 lost every copy
+        assert_eq!(
+            planned,
+            vec![(PathBuf::from("/a/x (2).txt"), PathBuf::from("/a/x.txt"))]
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Reveal files in the platform file manager.
+//!
+//! This module opens the system file manager at a file's location so its
+//! surroundings can be inspected during review:
+//! - Windows: `explorer /select,<path>` (file is highlighted)
+//! - macOS: `open -R <path>` (file is highlighted in Finder)
+//! - Linux and other Unix: `xdg-open <parent dir>` (best effort, no selection)
+//!
+//! Revealing is read-only, so it is allowed in dry-run mode.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::actions::reveal::reveal_in_file_manager;
+//! use std::path::Path;
+//!
+//! if let Err(e) = reveal_in_file_manager(Path::new("/photos/IMG_1234.jpg")) {
+//!     eprintln!("{}", e);
+//! }
+//! ```
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+/// Errors that can occur when revealing a file.
+#[derive(Debug, Error)]
+pub enum RevealError {
+    /// File was not found (may have been deleted or moved).
+    #[error("file not found: {0}")]
+    NotFound(PathBuf),
+
+    /// The file manager could not be launched.
+    #[error("failed to launch {program}: {source}")]
+    LaunchFailed {
+        /// Program that failed to start
+        program: String,
+        /// The underlying I/O error
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Platform whose file manager conventions to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    /// Windows Explorer.
+    Windows,
+    /// macOS Finder.
+    MacOs,
+    /// Linux and other Unix desktops via `xdg-open`.
+    Linux,
+}
+
+impl Platform {
+    /// The platform this binary was built for.
+    #[must_use]
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else {
+            Self::Linux
+        }
+    }
+}
+
+/// Build the command that reveals `path` on `platform` without running it.
+#[must_use]
+pub fn reveal_command(path: &Path, platform: Platform) -> Command {
+    match platform {
+        Platform::Windows => {
+            let mut arg = OsString::from("/select,");
+            arg.push(path.as_os_str());
+            let mut cmd = Command::new("explorer");
+            cmd.arg(arg);
+            cmd
+        }
+        Platform::MacOs => {
+            let mut cmd = Command::new("open");
+            cmd.arg("-R").arg(path);
+            cmd
+        }
+        Platform::Linux => {
+            // xdg-open cannot select a file, so open its containing folder
+            let dir = path
+                .parent()
+                .filter(|p| !p.as_os_str().is_empty())
+                .unwrap_or(Path::new("."));
+            let mut cmd = Command::new("xdg-open");
+            cmd.arg(dir);
+            cmd
+        }
+    }
+}
+
+/// Reveal `path` in the platform file manager.
+///
+/// The file manager is started in the background; this does not wait for it
+/// to exit.
+///
+/// # Errors
+///
+/// Returns [`RevealError::NotFound`] if the file no longer exists, or
+/// [`RevealError::LaunchFailed`] if the file manager could not be started.
+pub fn reveal_in_file_manager(path: &Path) -> Result<(), RevealError> {
+    if !path.exists() {
+        return Err(RevealError::NotFound(path.to_path_buf()));
+    }
+
+    let mut cmd = reveal_command(path, Platform::current());
+    let program = cmd.get_program().to_string_lossy().into_owned();
+    log::debug!("Revealing {} with {}", path.display(), program);
+
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|source| RevealError::LaunchFailed { program, source })?;
+
+    // Reap the launcher in the background so it does not linger as a zombie
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    fn args(cmd: &Command) -> Vec<&OsStr> {
+        cmd.get_args().collect()
+    }
+
+    #[test]
+    fn test_reveal_command_windows() {
+        let cmd = reveal_command(Path::new(r"C:\photos\a.jpg"), Platform::Windows);
+        assert_eq!(cmd.get_program(), "explorer");
+        assert_eq!(args(&cmd), vec![OsStr::new(r"/select,C:\photos\a.jpg")]);
+    }
+
+    #[test]
+    fn test_reveal_command_macos() {
+        let cmd = reveal_command(Path::new("/photos/a.jpg"), Platform::MacOs);
+        assert_eq!(cmd.get_program(), "open");
+        assert_eq!(
+            args(&cmd),
+            vec![OsStr::new("-R"), OsStr::new("/photos/a.jpg")]
+        );
+    }
+
+    #[test]
+    fn test_reveal_command_linux_opens_parent() {
+        let cmd = reveal_command(Path::new("/photos/a.jpg"), Platform::Linux);
+        assert_eq!(cmd.get_program(), "xdg-open");
+        assert_eq!(args(&cmd), vec![OsStr::new("/photos")]);
+
+        let cmd = reveal_command(Path::new("a.jpg"), Platform::Linux);
+        assert_eq!(args(&cmd), vec![OsStr::new(".")]);
+    }
+
+    #[test]
+    fn test_reveal_missing_file() {
+        let result = reveal_in_file_manager(Path::new("/nonexistent/rustdupe/file.txt"));
+        assert!(matches!(result, Err(RevealError::NotFound(_))));
+    }
+}
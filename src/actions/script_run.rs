@@ -0,0 +1,231 @@
+//! Run a generated deletion script (`--delete-to-script-and-run`).
+//!
+//! The script written by [`ScriptOutput`](crate::output::ScriptOutput) stays
+//! on disk as an audit record. Once the user has reviewed and confirmed it,
+//! it is run with `--confirm` through the matching shell:
+//! - POSIX scripts: `sh <script> --confirm`
+//! - PowerShell scripts: `powershell -File <script> --confirm` on Windows,
+//!   `pwsh` elsewhere
+//!
+//! Scripts print a `FAILED: <path>` line for each file they could not delete;
+//! [`parse_failed_lines`] collects those paths from the captured output.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::actions::script_run::run_script;
+//! use rustdupe::output::ScriptType;
+//! use std::path::Path;
+//!
+//! let run = run_script(Path::new("delete.sh"), ScriptType::Posix).unwrap();
+//! for path in &run.failed {
+//!     eprintln!("Failed to delete {}", path.display());
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+use crate::output::script::{ScriptType, FAILED_MARKER};
+
+/// Errors that can occur when running a deletion script.
+#[derive(Debug, Error)]
+pub enum ScriptRunError {
+    /// The shell could not be started.
+    #[error("failed to launch {program}: {source}")]
+    LaunchFailed {
+        /// Program that failed to start
+        program: String,
+        /// The underlying I/O error
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Outcome of running a deletion script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptRun {
+    /// Exit code of the shell (None if it was killed by a signal)
+    pub exit_code: Option<i32>,
+    /// Captured standard output
+    pub stdout: String,
+    /// Captured standard error
+    pub stderr: String,
+    /// Files the script reported it could not delete
+    pub failed: Vec<PathBuf>,
+}
+
+impl ScriptRun {
+    /// Whether the script exited cleanly without failed deletions.
+    #[must_use]
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0) && self.failed.is_empty()
+    }
+}
+
+/// Build the command that runs `script` for real, without running it.
+#[must_use]
+pub fn script_command(script: &Path, script_type: ScriptType) -> Command {
+    let mut cmd = match script_type {
+        ScriptType::Posix => Command::new("sh"),
+        ScriptType::PowerShell => {
+            let shell = if cfg!(windows) { "powershell" } else { "pwsh" };
+            let mut cmd = Command::new(shell);
+            cmd.args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-ExecutionPolicy",
+                "Bypass",
+                "-File",
+            ]);
+            cmd
+        }
+    };
+    cmd.arg(script).arg("--confirm");
+    cmd
+}
+
+/// Collect the paths of `FAILED: <path>` lines in script output.
+#[must_use]
+pub fn parse_failed_lines(output: &str) -> Vec<PathBuf> {
+    output
+        .lines()
+        .filter_map(|line| {
+            line.trim_end_matches('\r')
+                .strip_prefix(FAILED_MARKER)?
+                .strip_prefix(' ')
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from)
+        })
+        .collect()
+}
+
+/// Run `script` with `--confirm` and wait for it to finish.
+///
+/// # Errors
+///
+/// Returns [`ScriptRunError::LaunchFailed`] if the shell could not be
+/// started. A script that runs but fails is reported through [`ScriptRun`].
+pub fn run_script(script: &Path, script_type: ScriptType) -> Result<ScriptRun, ScriptRunError> {
+    let mut cmd = script_command(script, script_type);
+    let program = cmd.get_program().to_string_lossy().into_owned();
+    log::debug!(
+        "Running deletion script {} with {}",
+        script.display(),
+        program
+    );
+
+    let output = cmd
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|source| ScriptRunError::LaunchFailed { program, source })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    let mut failed = parse_failed_lines(&stdout);
+    failed.extend(parse_failed_lines(&stderr));
+    Ok(ScriptRun {
+        exit_code: output.status.code(),
+        stdout,
+        stderr,
+        failed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    fn args(cmd: &Command) -> Vec<&OsStr> {
+        cmd.get_args().collect()
+    }
+
+    #[test]
+    fn test_script_command_posix() {
+        let cmd = script_command(Path::new("/tmp/delete me.sh"), ScriptType::Posix);
+        assert_eq!(cmd.get_program(), "sh");
+        assert_eq!(
+            args(&cmd),
+            vec![OsStr::new("/tmp/delete me.sh"), OsStr::new("--confirm")]
+        );
+    }
+
+    #[test]
+    fn test_script_command_powershell() {
+        let cmd = script_command(Path::new(r"C:\audit\delete.ps1"), ScriptType::PowerShell);
+        let expected = if cfg!(windows) { "powershell" } else { "pwsh" };
+        assert_eq!(cmd.get_program(), expected);
+        assert_eq!(
+            args(&cmd),
+            [
+                "-NoProfile",
+                "-NonInteractive",
+                "-ExecutionPolicy",
+                "Bypass",
+                "-File",
+                r"C:\audit\delete.ps1",
+                "--confirm",
+            ]
+            .map(OsStr::new)
+        );
+    }
+
+    #[test]
+    fn test_parse_failed_lines() {
+        let output = "rm: cannot remove '/a/b.txt': Permission denied\n\
+                      FAILED: /a/b.txt\n\
+                      FAILED: /path with spaces/c.txt\r\n\
+                      Deletion complete. Deleted 1 files.\n\
+                      FAILED:\n\
+                      NOT FAILED: /d.txt\n";
+        assert_eq!(
+            parse_failed_lines(output),
+            vec![
+                PathBuf::from("/a/b.txt"),
+                PathBuf::from("/path with spaces/c.txt")
+            ]
+        );
+        assert!(parse_failed_lines("").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_script_reports_failed_deletions() {
+        use crate::duplicates::{DuplicateGroup, ScanSummary};
+        use crate::output::ScriptOutput;
+        use crate::scanner::FileEntry;
+        use std::time::SystemTime;
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        let keep = dir.path().join("keep.txt");
+        let copy = dir.path().join("copy.txt");
+        let gone = dir.path().join("gone.txt");
+        std::fs::write(&keep, "same").unwrap();
+        std::fs::write(&copy, "same").unwrap();
+
+        let files = [&keep, &copy, &gone]
+            .iter()
+            .map(|p| FileEntry::new(p.to_path_buf(), 4, SystemTime::now()))
+            .collect();
+        let groups = vec![DuplicateGroup::new([1; 32], 4, files, Vec::new())];
+        let summary = ScanSummary::default();
+
+        let script = dir.path().join("delete.sh");
+        let mut file = std::fs::File::create(&script).unwrap();
+        ScriptOutput::new(&groups, &summary, ScriptType::Posix)
+            .write_to(&mut file)
+            .unwrap();
+        drop(file);
+
+        let run = run_script(&script, ScriptType::Posix).unwrap();
+        assert!(keep.exists());
+        assert!(!copy.exists());
+        assert_eq!(run.failed, vec![gone]);
+        assert_eq!(run.exit_code, Some(1));
+        assert!(!run.success());
+        assert!(run.stdout.contains("Deleted 1 files."));
+    }
+}
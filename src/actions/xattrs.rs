@@ -0,0 +1,242 @@
+//! Extended attribute preservation for moved keepers (`--preserve-xattrs`).
+//!
+//! Extended attributes carry Finder tags and quarantine flags on macOS,
+//! SELinux labels and desktop metadata on Linux, and POSIX ACLs, which Linux
+//! stores as `system.posix_acl_*` attributes. A plain rename keeps them, but
+//! a move that falls back to copying (or lands on a file system that
+//! rewrites metadata) can drop them.
+//!
+//! With preservation enabled, a file's attributes are read before it is
+//! moved and written back to the new path afterwards. Preservation is
+//! best-effort: an attribute that cannot be reproduced, for example a
+//! `security.*` attribute without the needed privileges, is logged as a
+//! warning, and on file systems or platforms without extended attributes
+//! the whole step is skipped with a warning. It never fails the move.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::actions::xattrs::{read_xattrs, restore_xattrs};
+//! use std::path::Path;
+//!
+//! let saved = read_xattrs(Path::new("/photos/beach (1).jpg")).unwrap();
+//! std::fs::rename("/photos/beach (1).jpg", "/photos/beach.jpg").unwrap();
+//! for name in restore_xattrs(Path::new("/photos/beach.jpg"), &saved) {
+//!     eprintln!("lost attribute {}", name.to_string_lossy());
+//! }
+//! ```
+
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+
+/// Extended attributes read from a file, as name/value pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XattrSnapshot {
+    attrs: Vec<(OsString, Vec<u8>)>,
+}
+
+impl XattrSnapshot {
+    /// Number of attributes captured.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.attrs.len()
+    }
+
+    /// Whether no attributes were captured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.attrs.is_empty()
+    }
+}
+
+/// Whether extended attributes can be preserved on this platform.
+#[must_use]
+pub fn xattrs_supported() -> bool {
+    #[cfg(unix)]
+    {
+        xattr::SUPPORTED_PLATFORM
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+/// Read every extended attribute of `path`.
+///
+/// Attributes that disappear between listing and reading are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the attributes cannot be listed, including when the
+/// file system or platform does not support them.
+pub fn read_xattrs(path: &Path) -> io::Result<XattrSnapshot> {
+    #[cfg(unix)]
+    {
+        let mut attrs = Vec::new();
+        for name in xattr::list(path)? {
+            if let Some(value) = xattr::get(path, &name)? {
+                attrs.push((name, value));
+            }
+        }
+        Ok(XattrSnapshot { attrs })
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "extended attributes are not supported on this platform",
+        ))
+    }
+}
+
+/// Write `snapshot` back to `path`, skipping attributes it already has.
+///
+/// Returns the names of attributes that could not be reproduced.
+#[must_use]
+pub fn restore_xattrs(path: &Path, snapshot: &XattrSnapshot) -> Vec<OsString> {
+    let mut failed = Vec::new();
+    for (name, value) in &snapshot.attrs {
+        #[cfg(unix)]
+        {
+            if xattr::get(path, name).ok().flatten().as_ref() == Some(value) {
+                continue;
+            }
+            if let Err(e) = xattr::set(path, name, value) {
+                log::debug!(
+                    "Could not set {} on {}: {}",
+                    name.to_string_lossy(),
+                    path.display(),
+                    e
+                );
+                failed.push(name.clone());
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (path, value);
+            failed.push(name.clone());
+        }
+    }
+    failed
+}
+
+/// Move `from` to `to` with `move_file`, carrying its extended attributes.
+///
+/// Attribute problems are logged as warnings; only the move itself can fail.
+///
+/// # Errors
+///
+/// Returns the error from `move_file`.
+pub fn move_preserving_xattrs<F>(from: &Path, to: &Path, move_file: F) -> io::Result<()>
+where
+    F: FnOnce(&Path, &Path) -> io::Result<()>,
+{
+    let snapshot = match read_xattrs(from) {
+        Ok(snapshot) => Some(snapshot),
+        Err(e) => {
+            log::warn!(
+                "Cannot preserve extended attributes of {}: {}",
+                from.display(),
+                e
+            );
+            None
+        }
+    };
+
+    move_file(from, to)?;
+
+    if let Some(snapshot) = snapshot {
+        let failed = restore_xattrs(to, &snapshot);
+        if !failed.is_empty() {
+            let names: Vec<_> = failed.iter().map(|n| n.to_string_lossy()).collect();
+            log::warn!(
+                "Could not preserve extended attributes on {}: {}",
+                to.display(),
+                names.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Set a user attribute, or return false if the file system refuses.
+    fn try_set_user_xattr(path: &Path) -> bool {
+        match xattr::set(path, "user.rustdupe.test", b"keep me") {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("Skipping: user xattrs unsupported here ({})", e);
+                false
+            }
+        }
+    }
+
+    #[test]
+    fn test_move_preserves_user_xattr() {
+        let dir = TempDir::new().unwrap();
+        let from = dir.path().join("photo (1).jpg");
+        let to = dir.path().join("photo.jpg");
+        fs::write(&from, "image").unwrap();
+        if !try_set_user_xattr(&from) {
+            return;
+        }
+
+        move_preserving_xattrs(&from, &to, |a, b| fs::rename(a, b)).unwrap();
+        assert!(!from.exists());
+        assert_eq!(
+            xattr::get(&to, "user.rustdupe.test").unwrap().as_deref(),
+            Some(&b"keep me"[..])
+        );
+    }
+
+    #[test]
+    fn test_copying_move_preserves_user_xattr() {
+        let dir = TempDir::new().unwrap();
+        let from = dir.path().join("a.txt");
+        let to = dir.path().join("b.txt");
+        fs::write(&from, "data").unwrap();
+        if !try_set_user_xattr(&from) {
+            return;
+        }
+
+        // Simulates the cross-device fallback, which copies instead of renaming
+        move_preserving_xattrs(&from, &to, |a, b| {
+            fs::copy(a, b)?;
+            fs::remove_file(a)
+        })
+        .unwrap();
+        assert!(!from.exists());
+        assert_eq!(
+            xattr::get(&to, "user.rustdupe.test").unwrap().as_deref(),
+            Some(&b"keep me"[..])
+        );
+    }
+
+    #[test]
+    fn test_restore_xattrs_onto_copy() {
+        let dir = TempDir::new().unwrap();
+        let original = dir.path().join("a.txt");
+        let copy = dir.path().join("b.txt");
+        fs::write(&original, "data").unwrap();
+        fs::write(&copy, "data").unwrap();
+        if !try_set_user_xattr(&original) {
+            return;
+        }
+
+        let snapshot = read_xattrs(&original).unwrap();
+        assert!(!snapshot.is_empty());
+        assert!(restore_xattrs(&copy, &snapshot).is_empty());
+        assert_eq!(
+            xattr::get(&copy, "user.rustdupe.test").unwrap().as_deref(),
+            Some(&b"keep me"[..])
+        );
+    }
+}
@@ -1,9 +1,17 @@
 //! SQLite-backed hash cache database.
-
-use rusqlite::{params, Connection};
-use std::path::Path;
+//!
+//! With [`HashCache::with_compression`] the database is kept zstd-compressed
+//! at rest. It is unpacked to a working copy when opened and packed back when
+//! closed, so lookups run at full speed while the file on disk stays small.
+//! While the cache is written to it is also packed every [`PACK_INTERVAL`],
+//! so a crash loses at most the entries of the last interval.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error;
 
 use crate::cache::CacheEntry;
@@ -32,17 +40,42 @@ pub enum CacheError {
 /// Result type for cache operations.
 pub type CacheResult<T> = std::result::Result<T, CacheError>;
 
+/// Magic bytes at the start of a zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// zstd compression level for compressed caches.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Value of the `compression` key in the `meta` table for compressed caches.
+const COMPRESSION_ZSTD: &str = "zstd";
+
+/// How often a compressed cache that is being written to is packed.
+pub const PACK_INTERVAL: Duration = Duration::from_secs(120);
+
+/// Files backing a compressed cache while it is open.
+struct CompressedStore {
+    /// Compressed database at the cache path
+    archive: PathBuf,
+    /// Plain SQLite database used until the cache is closed
+    working: PathBuf,
+    /// When the archive was last written
+    packed_at: Mutex<Instant>,
+}
+
 /// Persistent cache for file hashes using SQLite.
 ///
 /// This struct is thread-safe and can be shared across multiple threads
 /// using an `Arc<HashCache>`.
 pub struct HashCache {
     conn: Mutex<Option<Connection>>,
+    compressed: Option<CompressedStore>,
 }
 
 impl HashCache {
     /// Opens or creates a new hash cache at the specified path.
     ///
+    /// A cache previously stored compressed is opened and kept compressed.
+    ///
     /// # Arguments
     ///
     /// * `path` - Path to the SQLite database file.
@@ -52,6 +85,71 @@ impl HashCache {
     /// Returns `CacheError` if the database cannot be opened or the schema
     /// cannot be initialized.
     pub fn new(path: &Path) -> CacheResult<Self> {
+        Self::with_compression(path, false)
+    }
+
+    /// Opens or creates a hash cache, storing it zstd-compressed if `compress` is set.
+    ///
+    /// A compressed cache is unpacked to `<path>.<pid>.work` on open and packed
+    /// back to `path` by [`close`](Self::close), when the cache is dropped, and
+    /// every [`PACK_INTERVAL`] while entries are written. This makes opening
+    /// and closing slower in exchange for a much smaller file. Each process
+    /// works on its own copy, so concurrent runs never share a working file;
+    /// the run that packs last decides what the cache holds.
+    ///
+    /// The mode is recorded in the cache's `meta` table, and a cache that is
+    /// already compressed stays compressed even if `compress` is false, so
+    /// runs with and without the option can share one cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CacheError` if the cache cannot be unpacked, the database
+    /// cannot be opened or the schema cannot be initialized.
+    pub fn with_compression(path: &Path, compress: bool) -> CacheResult<Self> {
+        let stored_compressed = is_zstd_file(path)?;
+        let working = if stored_compressed {
+            let working = work_path(path);
+            log::debug!("Unpacking compressed cache to {:?}", working);
+            let mut decoder = zstd::Decoder::new(File::open(path)?)?;
+            io::copy(&mut decoder, &mut File::create(&working)?)?;
+            working
+        } else {
+            path.to_path_buf()
+        };
+
+        let conn = Self::open_connection(&working)?;
+        let recorded: Option<String> = conn
+            .query_row(
+                "SELECT value FROM meta WHERE key = 'compression'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let compressed =
+            compress || stored_compressed || recorded.as_deref() == Some(COMPRESSION_ZSTD);
+        conn.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('compression', ?1)",
+            params![if compressed { COMPRESSION_ZSTD } else { "none" }],
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(Some(conn)),
+            compressed: compressed.then(|| CompressedStore {
+                archive: path.to_path_buf(),
+                working,
+                packed_at: Mutex::new(Instant::now()),
+            }),
+        })
+    }
+
+    /// Whether the cache is stored zstd-compressed on disk.
+    #[must_use]
+    pub fn is_compressed(&self) -> bool {
+        self.compressed.is_some()
+    }
+
+    /// Open the SQLite database at `path` and bring its schema up to date.
+    fn open_connection(path: &Path) -> CacheResult<Connection> {
         let conn = Connection::open(path)?;
 
         // Configure SQLite for better concurrency:
@@ -101,24 +199,83 @@ impl HashCache {
             [],
         );
 
-        Ok(Self {
-            conn: Mutex::new(Some(conn)),
-        })
+        // Migration: Add samplehash column if it doesn't exist. Sampled hashes
+        // live in their own column so they are never returned as full hashes.
+        let _ = conn.execute("ALTER TABLE hashes ADD COLUMN samplehash BLOB", []);
+
+        // Cache-wide settings, such as whether the file is stored compressed
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL)",
+            [],
+        )?;
+
+        Ok(conn)
     }
 
     /// Closes the database connection.
     ///
+    /// A compressed cache is packed back to its path at this point.
+    ///
     /// # Errors
     ///
-    /// Returns `CacheError` if the database connection cannot be closed cleanly.
+    /// Returns `CacheError` if the database connection cannot be closed
+    /// cleanly or a compressed cache cannot be written.
     pub fn close(&self) -> CacheResult<()> {
         let mut lock = self.conn.lock().map_err(|_| CacheError::LockError)?;
         if let Some(conn) = lock.take() {
             conn.close().map_err(|(_, e)| CacheError::Database(e))?;
+            if let Some(ref store) = self.compressed {
+                store.pack()?;
+            }
         }
         Ok(())
     }
 
+    /// Write a compressed cache back to its path without closing it.
+    ///
+    /// Does nothing for plain caches, whose entries are on disk already.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CacheError` if the connection is closed or the compressed
+    /// cache cannot be written.
+    pub fn sync(&self) -> CacheResult<()> {
+        let lock = self.conn.lock().map_err(|_| CacheError::LockError)?;
+        let conn = lock.as_ref().ok_or(CacheError::ConnectionClosed)?;
+        self.write_archive(conn)
+    }
+
+    /// Pack a compressed cache if [`PACK_INTERVAL`] has passed since it was
+    /// last written. Called after each write, with the connection locked.
+    fn pack_if_due(&self, conn: &Connection) {
+        let due = self
+            .compressed
+            .as_ref()
+            .is_some_and(CompressedStore::is_due);
+        if due {
+            if let Err(e) = self.write_archive(conn) {
+                log::warn!("Failed to pack hash cache: {}", e);
+            }
+        }
+    }
+
+    /// Compress the working database of a compressed cache into its archive.
+    ///
+    /// `conn` must be the locked connection, so no write lands mid-copy.
+    fn write_archive(&self, conn: &Connection) -> CacheResult<()> {
+        let Some(ref store) = self.compressed else {
+            return Ok(());
+        };
+        // A plain cache being converted is packed on close; until then the
+        // file at the cache path is an ordinary database and loses nothing
+        if store.working == store.archive {
+            return Ok(());
+        }
+        // Move the WAL into the database file so the copy is complete
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        store.write_archive()
+    }
+
     /// Helper to convert SystemTime to nanoseconds since UNIX epoch.
     fn system_time_to_ns(time: SystemTime) -> i64 {
         time.duration_since(SystemTime::UNIX_EPOCH)
@@ -198,6 +355,39 @@ impl HashCache {
         Ok(None)
     }
 
+    /// Retrieve the sampled hash for a file if it exists and metadata matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CacheError` if database access fails.
+    pub fn get_samplehash(
+        &self,
+        path: &Path,
+        size: u64,
+        mtime: SystemTime,
+    ) -> CacheResult<Option<Hash>> {
+        let lock = self.conn.lock().map_err(|_| CacheError::LockError)?;
+        let conn = lock.as_ref().ok_or(CacheError::ConnectionClosed)?;
+        let mtime_ns = Self::system_time_to_ns(mtime);
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT samplehash FROM hashes WHERE path = ?1 AND size = ?2 AND mtime_ns = ?3",
+        )?;
+        let mut rows = stmt.query(params![path.to_string_lossy().to_string(), size, mtime_ns])?;
+
+        if let Some(row) = rows.next()? {
+            let blob: Option<Vec<u8>> = row.get(0)?;
+            if let Some(blob) = blob {
+                if blob.len() == 32 {
+                    let mut hash = [0u8; 32];
+                    hash.copy_from_slice(&blob);
+                    return Ok(Some(hash));
+                }
+            }
+        }
+        Ok(None)
+    }
+
     /// Retrieve the perceptual hash for a file if it exists and metadata matches.
     ///
     /// # Errors
@@ -273,6 +463,7 @@ impl HashCache {
             "INSERT INTO hashes (path, size, mtime_ns, inode, prehash, fullhash, perceptual_hash, document_fingerprint, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5, NULL, ?6, ?7, ?8)
              ON CONFLICT(path) DO UPDATE SET
+                samplehash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.samplehash ELSE NULL END,
                 size = excluded.size,
                 mtime_ns = excluded.mtime_ns,
                 inode = excluded.inode,
@@ -292,6 +483,7 @@ impl HashCache {
                 now,
             ],
         )?;
+        self.pack_if_due(conn);
         Ok(())
     }
 
@@ -310,6 +502,7 @@ impl HashCache {
             "INSERT INTO hashes (path, size, mtime_ns, inode, prehash, fullhash, perceptual_hash, document_fingerprint, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
              ON CONFLICT(path) DO UPDATE SET
+                samplehash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.samplehash ELSE NULL END,
                 size = excluded.size,
                 mtime_ns = excluded.mtime_ns,
                 inode = excluded.inode,
@@ -330,6 +523,48 @@ impl HashCache {
                 now,
             ],
         )?;
+        self.pack_if_due(conn);
+        Ok(())
+    }
+
+    /// Insert or update a sampled hash in the cache.
+    ///
+    /// Sampled hashes are stored separately from full hashes, so a file
+    /// hashed both ways keeps both values.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CacheError` if database access fails.
+    pub fn insert_samplehash(&self, entry: &CacheEntry, hash: Hash) -> CacheResult<()> {
+        let lock = self.conn.lock().map_err(|_| CacheError::LockError)?;
+        let conn = lock.as_ref().ok_or(CacheError::ConnectionClosed)?;
+        let mtime_ns = Self::system_time_to_ns(entry.mtime);
+        let now = Self::now_secs();
+
+        conn.execute(
+            "INSERT INTO hashes (path, size, mtime_ns, inode, prehash, fullhash, perceptual_hash, document_fingerprint, samplehash, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, NULL, NULL, NULL, ?6, ?7)
+             ON CONFLICT(path) DO UPDATE SET
+                fullhash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.fullhash ELSE NULL END,
+                perceptual_hash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.perceptual_hash ELSE NULL END,
+                document_fingerprint = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.document_fingerprint ELSE NULL END,
+                size = excluded.size,
+                mtime_ns = excluded.mtime_ns,
+                inode = excluded.inode,
+                prehash = excluded.prehash,
+                samplehash = excluded.samplehash,
+                created_at = excluded.created_at",
+            params![
+                entry.path.to_string_lossy().to_string(),
+                entry.size,
+                mtime_ns,
+                entry.inode,
+                &entry.prehash[..],
+                &hash[..],
+                now,
+            ],
+        )?;
+        self.pack_if_due(conn);
         Ok(())
     }
 
@@ -350,6 +585,7 @@ impl HashCache {
              ON CONFLICT(path) DO UPDATE SET
                 prehash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.prehash ELSE excluded.prehash END,
                 fullhash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.fullhash ELSE NULL END,
+                samplehash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.samplehash ELSE NULL END,
                 size = excluded.size,
                 mtime_ns = excluded.mtime_ns,
                 inode = excluded.inode,
@@ -364,6 +600,7 @@ impl HashCache {
                 now,
             ],
         )?;
+        self.pack_if_due(conn);
         Ok(())
     }
 
@@ -388,6 +625,7 @@ impl HashCache {
              ON CONFLICT(path) DO UPDATE SET
                 prehash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.prehash ELSE excluded.prehash END,
                 fullhash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.fullhash ELSE NULL END,
+                samplehash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.samplehash ELSE NULL END,
                 size = excluded.size,
                 mtime_ns = excluded.mtime_ns,
                 inode = excluded.inode,
@@ -402,6 +640,7 @@ impl HashCache {
                 now,
             ],
         )?;
+        self.pack_if_due(conn);
         Ok(())
     }
 
@@ -421,6 +660,7 @@ impl HashCache {
                 "INSERT INTO hashes (path, size, mtime_ns, inode, prehash, fullhash, perceptual_hash, document_fingerprint, created_at)
                   VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
                   ON CONFLICT(path) DO UPDATE SET
+                     samplehash = CASE WHEN size = excluded.size AND mtime_ns = excluded.mtime_ns THEN hashes.samplehash ELSE NULL END,
                      size = excluded.size,
                      mtime_ns = excluded.mtime_ns,
                      inode = excluded.inode,
@@ -447,6 +687,7 @@ impl HashCache {
             }
         }
         tx.commit()?;
+        self.pack_if_due(conn);
         Ok(())
     }
 
@@ -543,6 +784,82 @@ impl HashCache {
     }
 }
 
+impl Drop for HashCache {
+    fn drop(&mut self) {
+        // Compressed caches must be packed or this run's entries are lost
+        if let Err(e) = self.close() {
+            log::warn!("Failed to close hash cache: {}", e);
+        }
+    }
+}
+
+impl CompressedStore {
+    /// Whether [`PACK_INTERVAL`] has passed since the archive was written.
+    fn is_due(&self) -> bool {
+        self.packed_at
+            .lock()
+            .is_ok_and(|packed_at| packed_at.elapsed() >= PACK_INTERVAL)
+    }
+
+    /// Compress the working database into the archive.
+    ///
+    /// The archive is replaced atomically, so an interrupted write leaves the
+    /// previous cache intact.
+    fn write_archive(&self) -> CacheResult<()> {
+        let partial = sibling_path(&self.archive, &format!(".{}.partial", std::process::id()));
+        {
+            let mut encoder = zstd::Encoder::new(File::create(&partial)?, ZSTD_LEVEL)?;
+            io::copy(&mut File::open(&self.working)?, &mut encoder)?;
+            encoder.finish()?.sync_all()?;
+        }
+        fs::rename(&partial, &self.archive)?;
+        if let Ok(mut packed_at) = self.packed_at.lock() {
+            *packed_at = Instant::now();
+        }
+        log::debug!("Packed compressed cache to {:?}", self.archive);
+        Ok(())
+    }
+
+    /// Compress the working database into the archive and remove it.
+    fn pack(&self) -> CacheResult<()> {
+        self.write_archive()?;
+        if self.working != self.archive {
+            fs::remove_file(&self.working)?;
+        }
+        for suffix in ["-wal", "-shm"] {
+            let _ = fs::remove_file(sibling_path(&self.working, suffix));
+        }
+        Ok(())
+    }
+}
+
+/// Working copy of the compressed cache at `path` for this process.
+fn work_path(path: &Path) -> PathBuf {
+    sibling_path(path, &format!(".{}.work", std::process::id()))
+}
+
+/// `path` with `suffix` appended to its file name.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut sibling = path.as_os_str().to_os_string();
+    sibling.push(suffix);
+    PathBuf::from(sibling)
+}
+
+/// Whether `path` holds zstd data (false if it does not exist).
+fn is_zstd_file(path: &Path) -> io::Result<bool> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let mut magic = [0u8; 4];
+    match file.read_exact(&mut magic) {
+        Ok(()) => Ok(magic == ZSTD_MAGIC),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -627,6 +944,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hash_cache_samplehash_is_separate() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let cache = HashCache::new(temp_file.path()).unwrap();
+
+        let now = SystemTime::now();
+        let file_path = Path::new("/test/movie.mkv");
+        let entry = CacheEntry {
+            path: file_path.to_path_buf(),
+            size: 4096,
+            mtime: now,
+            inode: None,
+            prehash: [1u8; 32],
+            fullhash: None,
+            perceptual_hash: None,
+            document_fingerprint: None,
+        };
+
+        // A sampled hash is never served as a full hash
+        cache.insert_samplehash(&entry, [5u8; 32]).unwrap();
+        assert_eq!(
+            cache.get_samplehash(file_path, 4096, now).unwrap(),
+            Some([5u8; 32])
+        );
+        assert!(cache.get_fullhash(file_path, 4096, now).unwrap().is_none());
+
+        // Both values coexist for unchanged metadata
+        cache.insert_fullhash(&entry, [6u8; 32]).unwrap();
+        assert_eq!(
+            cache.get_samplehash(file_path, 4096, now).unwrap(),
+            Some([5u8; 32])
+        );
+        assert_eq!(
+            cache.get_fullhash(file_path, 4096, now).unwrap(),
+            Some([6u8; 32])
+        );
+
+        // A metadata change drops the stale sampled hash
+        let changed = CacheEntry {
+            size: 8192,
+            ..entry
+        };
+        cache.insert_fullhash(&changed, [7u8; 32]).unwrap();
+        assert!(cache
+            .get_samplehash(file_path, 8192, now)
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn test_hash_cache_batch() {
         let temp_file = NamedTempFile::new().unwrap();
@@ -944,4 +1310,100 @@ mod tests {
         let res = HashCache::new(path);
         assert!(res.is_err());
     }
+
+    #[test]
+    fn test_hash_cache_compressed_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let plain_path = dir.path().join("plain.db");
+        let packed_path = dir.path().join("packed.db");
+        let now = SystemTime::now();
+        let entry = |i: u32| CacheEntry {
+            path: PathBuf::from(format!("/home/user/Pictures/2024/holiday/IMG_{:05}.jpg", i)),
+            size: 4096 + u64::from(i),
+            mtime: now,
+            inode: Some(u64::from(i)),
+            prehash: *blake3::hash(&i.to_le_bytes()).as_bytes(),
+            fullhash: Some(*blake3::hash(&(i + 1).to_le_bytes()).as_bytes()),
+            perceptual_hash: None,
+            document_fingerprint: None,
+        };
+        let entries: Vec<CacheEntry> = (0..2000).map(entry).collect();
+
+        let plain = HashCache::new(&plain_path).unwrap();
+        plain.insert_batch(&entries).unwrap();
+        assert!(!plain.is_compressed());
+        plain.close().unwrap();
+
+        let packed = HashCache::with_compression(&packed_path, true).unwrap();
+        packed.insert_batch(&entries).unwrap();
+        assert!(packed.is_compressed());
+        drop(packed);
+
+        assert!(is_zstd_file(&packed_path).unwrap());
+        assert!(!work_path(&packed_path).exists());
+        let plain_size = fs::metadata(&plain_path).unwrap().len();
+        let packed_size = fs::metadata(&packed_path).unwrap().len();
+        assert!(
+            packed_size < plain_size,
+            "compressed {} >= plain {}",
+            packed_size,
+            plain_size
+        );
+
+        // Reopening without the option reads the compressed cache and keeps it packed
+        let reopened = HashCache::new(&packed_path).unwrap();
+        assert!(reopened.is_compressed());
+        let sample = &entries[1234];
+        assert_eq!(
+            reopened
+                .get_fullhash(&sample.path, sample.size, now)
+                .unwrap(),
+            sample.fullhash
+        );
+        reopened.close().unwrap();
+        assert!(is_zstd_file(&packed_path).unwrap());
+    }
+
+    #[test]
+    fn test_hash_cache_sync_keeps_entries_without_close() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("packed.db");
+        let now = SystemTime::now();
+        let entry = CacheEntry {
+            path: PathBuf::from("/data/a.bin"),
+            size: 1024,
+            mtime: now,
+            inode: None,
+            prehash: [1u8; 32],
+            fullhash: Some([2u8; 32]),
+            perceptual_hash: None,
+            document_fingerprint: None,
+        };
+
+        // Start from a packed cache so this run works on its own copy
+        HashCache::with_compression(&path, true)
+            .unwrap()
+            .close()
+            .unwrap();
+        let cache = HashCache::new(&path).unwrap();
+        assert!(work_path(&path).exists());
+        assert!(work_path(&path)
+            .to_string_lossy()
+            .contains(&std::process::id().to_string()));
+        cache.insert_batch(std::slice::from_ref(&entry)).unwrap();
+        cache.sync().unwrap();
+
+        // What a crash would leave behind: the archive, with the new entry
+        let snapshot = dir.path().join("snapshot.db");
+        fs::copy(&path, &snapshot).unwrap();
+        let recovered = HashCache::new(&snapshot).unwrap();
+        assert_eq!(
+            recovered
+                .get_fullhash(&entry.path, entry.size, now)
+                .unwrap(),
+            entry.fullhash
+        );
+        recovered.close().unwrap();
+        cache.close().unwrap();
+    }
 }
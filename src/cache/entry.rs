@@ -43,20 +43,24 @@ pub struct CacheEntry {
 impl CacheEntry {
     /// Generate a unique key for the cache entry based on file metadata.
     ///
-    /// The key consists of path, size, modification time, and optionally inode.
+    /// The key consists of path, size, modification time, and the inode when
+    /// one is available; without an inode the key has only three parts.
     /// If any of these change, the cache entry is considered invalid for that file.
     #[must_use]
     pub fn generate_key(path: &Path, size: u64, mtime: SystemTime, inode: Option<u64>) -> String {
-        format!(
-            "{}:{}:{}:{}",
+        let key = format!(
+            "{}:{}:{}",
             path.to_string_lossy(),
             size,
             mtime
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .map(|d| d.as_nanos())
-                .unwrap_or(0),
-            inode.unwrap_or(0)
-        )
+                .unwrap_or(0)
+        );
+        match inode {
+            Some(inode) => format!("{}:{}", key, inode),
+            None => key,
+        }
     }
 
     /// Check if this cache entry is still valid for a given file metadata.
@@ -130,4 +134,19 @@ mod tests {
         assert_eq!(key1, key2);
         assert_ne!(key1, key3);
     }
+
+    #[test]
+    fn test_generate_key_without_inode() {
+        let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1);
+        let path = Path::new("/test.txt");
+
+        assert_eq!(
+            CacheEntry::generate_key(path, 100, mtime, None),
+            "/test.txt:100:1000000000"
+        );
+        assert_eq!(
+            CacheEntry::generate_key(path, 100, mtime, Some(0)),
+            "/test.txt:100:1000000000:0"
+        );
+    }
 }
@@ -27,6 +27,7 @@ use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::output::size::SizeFormat;
 use crate::tui::keybindings::KeybindingProfile;
 
 /// A named directory group mapping a name to a path.
@@ -142,6 +143,17 @@ pub struct Cli {
     #[arg(short, long, global = true, conflicts_with = "verbose")]
     pub quiet: bool,
 
+    /// Write log records to FILE instead of stderr
+    ///
+    /// Records include timestamps and module paths and honor -v/-q. The file
+    /// is rotated at 10 MiB, keeping FILE.1 through FILE.3.
+    #[arg(long = "log-file", value_name = "FILE", global = true)]
+    pub log_file: Option<PathBuf>,
+
+    /// With --log-file, also write log records to stderr
+    #[arg(long = "log-stderr", global = true, requires = "log_file")]
+    pub log_stderr: bool,
+
     /// Disable colored output
     #[arg(long = "no-color", global = true, env = "NO_COLOR", value_parser = clap::builder::BoolishValueParser::new())]
     pub no_color: bool,
@@ -180,6 +192,14 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub list_profiles: bool,
 
+    /// Print the JSON output schema version and exit
+    #[arg(long, global = true)]
+    pub schema_version: bool,
+
+    /// Print every exit code with its number and meaning as JSON and exit
+    #[arg(long, global = true, hide = true)]
+    pub print_exit_codes: bool,
+
     /// Enable accessible mode for screen reader compatibility
     ///
     /// When enabled:
@@ -194,13 +214,39 @@ pub struct Cli {
     #[arg(long = "no-accessible", overrides_with = "accessible", hide = true)]
     pub no_accessible: bool,
 
+    /// Show full modification timestamps in the TUI instead of relative ages
+    ///
+    /// By default the file list shows ages such as "3 days ago".
+    #[arg(long = "absolute-times", global = true)]
+    pub absolute_times: bool,
+
+    /// Units for displayed sizes
+    ///
+    /// iec-proper: 1024-based KiB/MiB (default); iec: 1024-based KB/MB;
+    /// si: 1000-based kB/MB.
+    #[arg(long = "size-units", value_enum, value_name = "UNITS", global = true)]
+    pub size_units: Option<SizeFormat>,
+
     /// Output errors as JSON instead of plain text
     #[arg(long = "json-errors", global = true)]
     pub json_errors: bool,
 
     /// Subcommand to execute
+    ///
+    /// Optional so that `--schema-version`, `--print-exit-codes` and
+    /// `--list-profiles` work on their own; see [`Cli::is_info_request`].
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
+}
+
+impl Cli {
+    /// Whether a flag that prints information and exits was given.
+    ///
+    /// These flags are the only way to run without a subcommand.
+    #[must_use]
+    pub fn is_info_request(&self) -> bool {
+        self.schema_version || self.print_exit_codes || self.list_profiles
+    }
 }
 
 /// Available subcommands for RustDupe.
@@ -210,6 +256,20 @@ pub enum Commands {
     Scan(Box<ScanArgs>),
     /// Load a previously saved session
     Load(LoadArgs),
+    /// Watch directories and report duplicates as files change
+    Watch(WatchArgs),
+    /// Print statistics for a saved session without rescanning
+    Stats(StatsArgs),
+    /// Print a single file's prehash, full hash and cache key
+    Hash(HashArgs),
+    /// Delete the files marked in a reviewed CSV or JSON report
+    Apply(ApplyArgs),
+    /// Compare two saved sessions and report how duplicates changed
+    Diff(DiffArgs),
+    /// Check that a saved session's files are unchanged on disk
+    Verify(VerifyArgs),
+    /// Print version and build information
+    Version(VersionArgs),
 }
 
 /// Arguments for the scan subcommand.
@@ -221,9 +281,35 @@ pub struct ScanArgs {
     /// Duplicates will be found across all specified directories.
     ///
     /// Example: rustdupe scan /path/1 /path/2 /path/3
-    #[arg(value_name = "PATH", num_args = 1.., required_unless_present = "load_session")]
+    #[arg(
+        value_name = "PATH",
+        num_args = 1..,
+        required_unless_present_any = ["load_session", "paths_from", "paths_from0"]
+    )]
     pub paths: Vec<PathBuf>,
 
+    /// Read more directories to scan from FILE, one per line (`-` for stdin)
+    ///
+    /// Listed paths are appended after any PATH arguments, so in multi-path
+    /// mode the first path of the combined list is the reference. Blank lines
+    /// are ignored.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["load_session", "paths_from0"],
+        help_heading = "Scanning Options"
+    )]
+    pub paths_from: Option<PathBuf>,
+
+    /// Like --paths-from, but entries are NUL-separated (e.g. `find -print0`)
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "load_session",
+        help_heading = "Scanning Options"
+    )]
+    pub paths_from0: Option<PathBuf>,
+
     /// Load a previously saved session instead of scanning
     #[arg(
         long,
@@ -233,11 +319,37 @@ pub struct ScanArgs {
     )]
     pub load_session: Option<PathBuf>,
 
+    /// Hide groups already decided in a previous session
+    ///
+    /// Groups whose hash and members match a group of SESSION_FILE that had
+    /// files selected for deletion are skipped. Groups that gained or lost
+    /// members are shown again.
+    #[arg(
+        long,
+        value_name = "SESSION_FILE",
+        conflicts_with = "load_session",
+        help_heading = "Scanning Options"
+    )]
+    pub since_session: Option<PathBuf>,
+
     /// Save scan results to a session file
+    ///
+    /// Paths ending in .json.gz or .json.zst are written compressed.
     #[arg(long, value_name = "PATH", help_heading = "Output Options")]
     pub save_session: Option<PathBuf>,
 
-    /// Output format (tui for interactive, json/csv for scripting, session for persistence, html for report, script for deletion)
+    /// Compress saved session files regardless of their extension
+    #[arg(
+        long = "session-compression",
+        value_enum,
+        value_name = "CODEC",
+        help_heading = "Output Options"
+    )]
+    pub session_compression: Option<crate::session::SessionCompression>,
+
+    /// Output format (tui for interactive, text for piping, json/yaml/csv for scripting, tree for terminal triage, keepers for audit, compact for shell pipelines, plan for a dry-run deletion plan, session for persistence, html for report, script for deletion)
+    ///
+    /// Defaults to tui, or text when stdin or stdout is not a terminal.
     #[arg(short, long, value_enum, help_heading = "Output Options")]
     pub output: Option<OutputFormat>,
 
@@ -249,6 +361,27 @@ pub struct ScanArgs {
     #[arg(long, value_enum, value_name = "TYPE", help_heading = "Output Options")]
     pub script_type: Option<ScriptTypeArg>,
 
+    /// Write the deletion script to --output-file, show it, and run it once confirmed
+    ///
+    /// Implies `--output script`. The script is kept as an audit record, and
+    /// any file it fails to delete is reported. Not allowed with --dry-run.
+    #[arg(
+        long = "delete-to-script-and-run",
+        requires = "output_file",
+        conflicts_with = "dry_run",
+        help_heading = "Output Options"
+    )]
+    pub delete_to_script_and_run: bool,
+
+    /// Progress display (bar for interactive terminals, json for one JSON line per update on stderr)
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help_heading = "Output Options"
+    )]
+    pub progress_format: Option<ProgressFormat>,
+
     /// Minimum file size to consider (e.g., 1KB, 1MB, 1GB)
     ///
     /// Supports suffixes: B, KB, KiB, MB, MiB, GB, GiB, TB, TiB
@@ -269,6 +402,59 @@ pub struct ScanArgs {
     #[arg(long, value_name = "DATE", value_parser = parse_date, help_heading = "Filtering Options")]
     pub older_than: Option<std::time::SystemTime>,
 
+    /// Only include files modified within this long before the scan (e.g. 7d)
+    ///
+    /// Units: s, m, h, d, w; parts can be combined (1w2d). Resolved to an
+    /// absolute --newer-than time when the scan starts.
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        conflicts_with = "newer_than",
+        help_heading = "Filtering Options"
+    )]
+    pub modified_within: Option<std::time::Duration>,
+
+    /// Only include files modified at least this long before the scan (e.g. 30d)
+    ///
+    /// Same units as --modified-within; resolved to an absolute --older-than
+    /// time when the scan starts.
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        conflicts_with = "older_than",
+        help_heading = "Filtering Options"
+    )]
+    pub modified_before: Option<std::time::Duration>,
+
+    /// Skip files modified within the last SECONDS, and refuse to delete them
+    ///
+    /// Files that changed moments ago may still be open or being written.
+    /// Example: --settle 5
+    #[arg(long, value_name = "SECONDS", help_heading = "Filtering Options")]
+    pub settle: Option<u64>,
+
+    /// Only scan and delete files owned by this user (uid or name, Unix only)
+    ///
+    /// Can be specified multiple times.
+    #[arg(
+        long = "only-owner",
+        value_name = "USER",
+        help_heading = "Filtering Options"
+    )]
+    pub only_owner: Vec<String>,
+
+    /// Never scan or delete files owned by this user (uid or name, Unix only)
+    ///
+    /// Can be specified multiple times, e.g. --exclude-owner root
+    #[arg(
+        long = "exclude-owner",
+        value_name = "USER",
+        help_heading = "Filtering Options"
+    )]
+    pub exclude_owner: Vec<String>,
+
     /// Regex patterns to include (filename must match at least one)
     ///
     /// Example: --regex ".*\.jpg$"
@@ -299,6 +485,27 @@ pub struct ScanArgs {
     )]
     pub file_types: Vec<FileType>,
 
+    /// Filter by explicit file extensions (comma-separated, case-insensitive)
+    ///
+    /// Combined with --file-type: a file is included if it matches either.
+    ///
+    /// Example: --ext raw,cr2
+    #[arg(
+        long = "ext",
+        value_name = "EXT",
+        value_delimiter = ',',
+        help_heading = "Filtering Options"
+    )]
+    pub extensions: Vec<String>,
+
+    /// Detect file types from their content (magic bytes) instead of extensions
+    ///
+    /// Affects --file-type filtering and which files --similar-images and
+    /// --similar-documents consider. Files without an extension are always
+    /// sniffed.
+    #[arg(long = "sniff-content", help_heading = "Filtering Options")]
+    pub sniff_content: bool,
+
     /// Glob patterns to ignore (can be specified multiple times)
     ///
     /// These patterns are added to any .gitignore patterns found.
@@ -310,12 +517,41 @@ pub struct ScanArgs {
     )]
     pub ignore_patterns: Vec<String>,
 
-    /// Follow symbolic links during scan
+    /// Skip a directory subtree entirely (can be specified multiple times)
+    ///
+    /// A bare name like `node_modules` matches that directory name anywhere;
+    /// a path prunes that directory. Excluded directories are never read.
+    #[arg(
+        long = "exclude-dir",
+        value_name = "DIR",
+        help_heading = "Filtering Options"
+    )]
+    pub exclude_dirs: Vec<PathBuf>,
+
+    /// Follow symbolic links during scan (both files and directories)
     ///
-    /// Warning: May cause infinite loops if symlinks form cycles.
+    /// Symlink cycles are detected: each directory is scanned at most once.
     #[arg(long = "follow-symlinks", help_heading = "Scanning Options")]
     pub follow_symlinks: bool,
 
+    /// Descend into symlinked directories, but skip symlinked files
+    #[arg(long = "follow-symlinks-dirs", help_heading = "Scanning Options")]
+    pub follow_dir_symlinks: bool,
+
+    /// Scan the targets of symlinked files, but do not descend into symlinked directories
+    ///
+    /// Symlinks whose target is inside the scanned directory are skipped, so
+    /// the target is only counted once.
+    #[arg(long = "follow-symlinks-files", help_heading = "Scanning Options")]
+    pub follow_file_symlinks: bool,
+
+    /// Report symlinks whose target does not exist instead of skipping them
+    ///
+    /// Broken links are listed in the scan summary and the JSON `summary`;
+    /// they are not counted as scan errors.
+    #[arg(long = "report-broken-symlinks", help_heading = "Scanning Options")]
+    pub report_broken_symlinks: bool,
+
     /// Do not follow symbolic links during scan
     #[arg(
         long = "no-follow-symlinks",
@@ -332,15 +568,28 @@ pub struct ScanArgs {
     #[arg(long = "no-skip-hidden", overrides_with = "skip_hidden", hide = true)]
     pub no_skip_hidden: bool,
 
-    /// Number of I/O threads for hashing (default: 4)
+    /// Number of I/O threads for hashing, or `auto` (default: 4)
     ///
-    /// Lower values reduce disk thrashing on HDDs.
+    /// Lower values reduce disk thrashing on HDDs. `auto` picks 2 threads
+    /// when a scan root is on a rotational disk and 8 on solid-state storage
+    /// (detected on Linux; elsewhere it falls back to 4).
     #[arg(
         long = "io-threads",
+        value_name = "N|auto",
+        value_parser = parse_io_threads,
+        help_heading = "Scanning Options"
+    )]
+    pub io_threads: Option<IoThreadsArg>,
+
+    /// Number of CPU threads for traversal and in-memory work (default: all cores)
+    ///
+    /// Independent of --io-threads, which only limits concurrent file reads.
+    #[arg(
+        long = "cpu-threads",
         value_name = "N",
         help_heading = "Scanning Options"
     )]
-    pub io_threads: Option<usize>,
+    pub cpu_threads: Option<usize>,
 
     /// Manual I/O buffer size (e.g., 64KB, 1MB)
     #[arg(long, value_name = "SIZE", value_parser = parse_size_usize, help_heading = "Scanning Options")]
@@ -354,6 +603,22 @@ pub struct ScanArgs {
     #[arg(long, value_name = "SIZE", value_parser = parse_size_usize, help_heading = "Scanning Options")]
     pub io_buffer_max: Option<usize>,
 
+    /// Read buffer size for hashing, a power of two (e.g., 1M)
+    ///
+    /// Larger buffers can improve throughput on RAID arrays and fast storage.
+    /// Values outside 4KiB-64MiB are clamped and others are rounded to the
+    /// nearest power of two, with a warning. Conflicts with --io-buffer-size,
+    /// which sets the same buffer without validation; in the config file
+    /// `read_buffer_size` wins over `io_buffer_size`.
+    #[arg(
+        long = "read-buffer",
+        value_name = "SIZE",
+        value_parser = parse_size_usize,
+        conflicts_with = "io_buffer_size",
+        help_heading = "Scanning Options"
+    )]
+    pub read_buffer: Option<usize>,
+
     /// Enable adaptive buffer sizing based on system resources (default)
     #[arg(long = "io-adaptive-buffer", help_heading = "Scanning Options")]
     pub io_adaptive_buffer: bool,
@@ -376,6 +641,113 @@ pub struct ScanArgs {
     #[arg(long = "no-paranoid", overrides_with = "paranoid", hide = true)]
     pub no_paranoid: bool,
 
+    /// Bytes read from each file per step of paranoid comparison (e.g. 1MiB)
+    ///
+    /// Larger chunks mean fewer reads on fast disks; memory use is two
+    /// chunks. Default: 64KiB.
+    #[arg(long = "paranoid-chunk", value_name = "SIZE", value_parser = parse_size_usize, help_heading = "Scanning Options")]
+    pub paranoid_chunk: Option<usize>,
+
+    /// Maximum files of a group compared at once in paranoid mode (default: 64)
+    ///
+    /// Larger groups are compared in batches so verification never holds
+    /// more than N open files. Values below 2 are raised to 2.
+    #[arg(
+        long = "paranoid-max-open",
+        value_name = "N",
+        help_heading = "Scanning Options"
+    )]
+    pub paranoid_max_open: Option<usize>,
+
+    /// Include zero-byte files, reported as a single labeled group
+    ///
+    /// By default empty files are skipped, since they all trivially match.
+    #[arg(long = "include-empty", help_heading = "Scanning Options")]
+    pub include_empty: bool,
+
+    /// Skip zero-byte files (default)
+    #[arg(
+        long = "no-include-empty",
+        overrides_with = "include_empty",
+        hide = true
+    )]
+    pub no_include_empty: bool,
+
+    /// Also group files whose names match after stripping copy markers
+    ///
+    /// Catches `IMG_1234 (1).jpg` or `IMG_1234 copy.jpg` next to
+    /// `IMG_1234.jpg` even when their contents differ. These groups are
+    /// advisory and deleting from them requires an extra confirmation.
+    #[arg(long = "name-cluster", help_heading = "Scanning Options")]
+    pub name_cluster: bool,
+
+    /// Disable similar-filename grouping
+    #[arg(long = "no-name-cluster", overrides_with = "name_cluster", hide = true)]
+    pub no_name_cluster: bool,
+
+    /// Sort groups and files deterministically for reproducible output (default)
+    ///
+    /// Groups are ordered by size (largest first) then hash, and files within
+    /// a group by path.
+    #[arg(long = "stable-order", help_heading = "Scanning Options")]
+    pub stable_order: bool,
+
+    /// Keep groups in discovery order, skipping the final sort
+    #[arg(long = "no-stable-order", overrides_with = "stable_order", hide = true)]
+    pub no_stable_order: bool,
+
+    /// Count files before walking so walk progress shows a percentage
+    ///
+    /// Runs a metadata-only pass over the tree first, which roughly doubles
+    /// traversal time. Ctrl+C cancels the count like the scan itself.
+    #[arg(long = "precount", help_heading = "Scanning Options")]
+    pub precount: bool,
+
+    /// Walk without counting files first (default)
+    #[arg(long = "no-precount", overrides_with = "precount", hide = true)]
+    pub no_precount: bool,
+
+    /// Only compare files whose name occurs more than once (approximate)
+    ///
+    /// A fast first cut: files with a unique basename are skipped before
+    /// any hashing. Duplicates saved under different names (e.g.
+    /// IMG_0001.jpg and holiday.jpg) are NOT found. Names must match
+    /// exactly, including case.
+    #[arg(long = "same-name-only", help_heading = "Scanning Options")]
+    pub same_name_only: bool,
+
+    /// Compare files regardless of name (default)
+    #[arg(
+        long = "no-same-name-only",
+        overrides_with = "same_name_only",
+        hide = true
+    )]
+    pub no_same_name_only: bool,
+
+    /// Write a resumable checkpoint to this file while scanning
+    ///
+    /// The checkpoint is updated after each phase and periodically while
+    /// hashing, kept if the scan is interrupted, and removed on completion.
+    #[arg(
+        long = "checkpoint",
+        value_name = "PATH",
+        conflicts_with = "load_session",
+        help_heading = "Scanning Options"
+    )]
+    pub checkpoint: Option<PathBuf>,
+
+    /// Resume an interrupted scan from a checkpoint file
+    ///
+    /// The scan paths and filters must match the interrupted scan. Progress
+    /// keeps being written to the same file unless --checkpoint is given.
+    #[arg(
+        long = "resume",
+        value_name = "PATH",
+        conflicts_with = "load_session",
+        help_heading = "Scanning Options"
+    )]
+    pub resume: Option<PathBuf>,
+
     /// Use permanent deletion instead of moving to trash
     ///
     /// Warning: Files cannot be recovered after permanent deletion.
@@ -386,6 +758,50 @@ pub struct ScanArgs {
     #[arg(long = "no-permanent", overrides_with = "permanent", hide = true)]
     pub no_permanent: bool,
 
+    /// Permanently delete files under DIR instead of trashing them
+    ///
+    /// Can be specified multiple times; the most specific directory wins.
+    /// Files in reference directories are always trashed.
+    #[arg(
+        long = "permanent-under",
+        value_name = "DIR",
+        help_heading = "Safety & Deletion Options"
+    )]
+    pub permanent_under: Vec<PathBuf>,
+
+    /// Refuse any deletion run that would delete more than SIZE in total (e.g., 50G)
+    ///
+    /// Also emitted as a guard at the top of generated deletion scripts.
+    #[arg(
+        long = "max-total-delete",
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help_heading = "Safety & Deletion Options"
+    )]
+    pub max_total_delete: Option<u64>,
+
+    /// Move deleted files into DIR instead of the system trash
+    ///
+    /// For servers without a desktop trash. Files are filed under a dated
+    /// subfolder (DIR/YYYY-MM-DD/...) by their original path; name
+    /// collisions get a numeric suffix. Permanent deletion is unaffected.
+    #[arg(
+        long = "trash-dir",
+        value_name = "DIR",
+        help_heading = "Safety & Deletion Options"
+    )]
+    pub trash_dir: Option<PathBuf>,
+
+    /// Make each deletion batch all-or-nothing
+    ///
+    /// On the first failure every file already trashed is restored. Refused
+    /// for permanent deletion and where the trash cannot be restored (macOS).
+    #[arg(
+        long = "transactional-delete",
+        help_heading = "Safety & Deletion Options"
+    )]
+    pub transactional_delete: bool,
+
     /// Skip confirmation prompts (required with --permanent in non-interactive mode)
     #[arg(short = 'y', long = "yes", help_heading = "Safety & Deletion Options")]
     pub yes: bool,
@@ -412,6 +828,18 @@ pub struct ScanArgs {
     #[arg(long = "clear-cache", help_heading = "Cache Options")]
     pub clear_cache: bool,
 
+    /// Keep the hash cache zstd-compressed on disk
+    ///
+    /// The cache is unpacked when a run starts and packed again when it ends,
+    /// so startup and exit are slower but the file is much smaller. A cache
+    /// stored compressed stays compressed on later runs without this flag.
+    #[arg(
+        long = "cache-compress",
+        conflicts_with = "no_cache",
+        help_heading = "Cache Options"
+    )]
+    pub cache_compress: bool,
+
     /// Do not perform any deletions (read-only mode)
     #[arg(
         long = "dry-run",
@@ -424,6 +852,55 @@ pub struct ScanArgs {
     #[arg(long = "no-dry-run", overrides_with = "dry_run", hide = true)]
     pub no_dry_run: bool,
 
+    /// Remove directories left empty after deleting files
+    ///
+    /// Parent directories of deleted files are removed bottom-up if they
+    /// became empty, stopping at the scan roots. Reference directories are
+    /// never touched. Deletion scripts only remove directories that are
+    /// empty when the script runs, and list them in dry-run mode.
+    #[arg(long = "delete-empty-dirs", help_heading = "Safety & Deletion Options")]
+    pub delete_empty_dirs: bool,
+
+    /// Rename surviving keepers with a sed-style substitution after deletion
+    ///
+    /// Applied to the file name of the first surviving file in each group
+    /// that had files deleted, e.g. 's/ \(\d+\)//' turns "photo (1).jpg"
+    /// into "photo.jpg". Use \1 for capture groups and a trailing g to
+    /// replace every match. Existing files are never overwritten. In dry-run
+    /// mode the intended renames are only reported.
+    #[arg(
+        long = "rename-keeper",
+        value_name = "SUBST",
+        help_heading = "Safety & Deletion Options"
+    )]
+    pub rename_keeper: Option<String>,
+
+    /// Carry extended attributes and ACLs over to renamed keepers and --trash-dir moves
+    ///
+    /// Attributes are read before each keeper rename or move into the trash
+    /// directory and re-applied to the new path. Any that cannot be reproduced are logged as warnings; on
+    /// file systems or platforms without extended attributes this does
+    /// nothing.
+    #[arg(long = "preserve-xattrs", help_heading = "Safety & Deletion Options")]
+    pub preserve_xattrs: bool,
+
+    /// Require typing DELETE in the TUI when deleting more than N files [default: 100]
+    #[arg(
+        long = "confirm-threshold-count",
+        value_name = "N",
+        help_heading = "Safety & Deletion Options"
+    )]
+    pub confirm_threshold_count: Option<usize>,
+
+    /// Require typing DELETE in the TUI when deleting more than SIZE [default: 10GB]
+    #[arg(
+        long = "confirm-threshold-bytes",
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help_heading = "Safety & Deletion Options"
+    )]
+    pub confirm_threshold_bytes: Option<u64>,
+
     /// Reference directories (files here are never selected for deletion)
     ///
     /// Example: --reference /backups/photos
@@ -437,6 +914,44 @@ pub struct ScanArgs {
     )]
     pub reference_paths: Vec<PathBuf>,
 
+    /// Read more reference directories from FILE, one per line (`-` for stdin)
+    ///
+    /// Combined with any --reference flags; duplicates are dropped. Blank
+    /// lines are ignored.
+    #[arg(
+        long = "reference-from",
+        value_name = "FILE",
+        help_heading = "Safety & Deletion Options"
+    )]
+    pub reference_from: Option<PathBuf>,
+
+    /// Compare paths case-insensitively, as on a Windows share
+    ///
+    /// Reference directories then protect `Foo.txt` and `foo.txt` alike even
+    /// on case-sensitive file systems. Content hashing is unaffected.
+    #[arg(
+        long,
+        overrides_with = "no_ignore_case_paths",
+        help_heading = "Safety & Deletion Options"
+    )]
+    pub ignore_case_paths: bool,
+
+    /// Compare paths case-sensitively (default except on Windows)
+    #[arg(long, overrides_with = "ignore_case_paths", hide = true)]
+    pub no_ignore_case_paths: bool,
+
+    /// Auto-protect one file per group so bulk selection never targets it
+    ///
+    /// Policies: none, oldest, newest, shallowest-path. Protected files are
+    /// shown with a lock icon in the TUI and kept first in other outputs.
+    #[arg(
+        long = "protect",
+        value_name = "POLICY",
+        value_enum,
+        help_heading = "Safety & Deletion Options"
+    )]
+    pub protect: Option<crate::duplicates::ProtectPolicy>,
+
     /// Named directory groups for organizing and batch-selecting duplicates
     ///
     /// Format: NAME=PATH (e.g., --group photos=/path/to/photos)
@@ -462,6 +977,26 @@ pub struct ScanArgs {
     #[arg(long = "no-strict", overrides_with = "strict", hide = true)]
     pub no_strict: bool,
 
+    /// Exit with code 4 if the scan hits more than N errors
+    ///
+    /// Unlike --strict the scan still finishes and reports its results; only
+    /// the exit code changes from 3 (partial success) to 4.
+    #[arg(
+        long = "max-errors",
+        value_name = "N",
+        help_heading = "Scanning Options"
+    )]
+    pub max_errors: Option<usize>,
+
+    /// Exit with code 4 if more than PCT percent of scanned files had errors
+    #[arg(
+        long = "max-error-rate",
+        value_name = "PCT",
+        value_parser = parse_error_rate,
+        help_heading = "Scanning Options"
+    )]
+    pub max_error_rate: Option<f64>,
+
     /// Enable similar image detection using perceptual hashing
     #[arg(long = "similar-images", help_heading = "Scanning Options")]
     pub similar_images: bool,
@@ -490,6 +1025,23 @@ pub struct ScanArgs {
     )]
     pub no_similar_documents: bool,
 
+    /// Report pairs of files sharing most of their content (slow)
+    ///
+    /// Splits files of 64 KiB or more into content-defined chunks and
+    /// reports pairs whose shared chunks cover at least --partial-threshold
+    /// of the larger file, such as appended logs or VM snapshots. Only pairs
+    /// whose sizes could reach the threshold are read.
+    #[arg(long = "partial-similarity", help_heading = "Scanning Options")]
+    pub partial_similarity: bool,
+
+    /// Disable partial duplicate detection
+    #[arg(
+        long = "no-partial-similarity",
+        overrides_with = "partial_similarity",
+        hide = true
+    )]
+    pub no_partial_similarity: bool,
+
     /// Enable memory-mapped file I/O for hashing large files
     ///
     /// Uses BLAKE3's parallel memory-mapped hashing for better performance.
@@ -500,14 +1052,82 @@ pub struct ScanArgs {
     #[arg(long = "no-mmap", overrides_with = "mmap", hide = true)]
     pub no_mmap: bool,
 
+    /// Skip the holes of sparse files (VM images) when hashing (Linux)
+    ///
+    /// Holes are hashed as the zeros they read as, so results match a
+    /// normal scan; only the disk reads are avoided.
+    #[arg(long = "sparse-aware", help_heading = "Scanning Options")]
+    pub sparse_aware: bool,
+
+    /// Read sparse files in full
+    #[arg(long = "no-sparse-aware", overrides_with = "sparse_aware", hide = true)]
+    pub no_sparse_aware: bool,
+
+    /// Fold alternate data streams (NTFS) and resource forks (macOS) into hashes
+    ///
+    /// Files whose main content matches but whose streams differ are no
+    /// longer reported as duplicates. Has no effect on other platforms.
+    #[arg(long = "include-streams", help_heading = "Scanning Options")]
+    pub include_streams: bool,
+
+    /// Hash the main stream only
+    #[arg(
+        long = "no-include-streams",
+        overrides_with = "include_streams",
+        hide = true
+    )]
+    pub no_include_streams: bool,
+
+    /// Retry transient read errors up to N times per file (default: 0)
+    ///
+    /// Retries back off exponentially from 100ms. Missing files and
+    /// permission errors are never retried.
+    #[arg(
+        long = "hash-retries",
+        value_name = "N",
+        help_heading = "Scanning Options"
+    )]
+    pub hash_retries: Option<u32>,
+
     /// Threshold for memory-mapped I/O (default: 64MB)
     ///
     /// Files larger than this threshold will be hashed using memory mapping if --mmap is enabled.
     #[arg(long, value_name = "SIZE", value_parser = parse_size, help_heading = "Scanning Options")]
     pub mmap_threshold: Option<u64>,
 
+    /// Compare files larger than SIZE by sampling instead of a full hash
+    ///
+    /// Hashes fixed windows at the start, middle and end of each file plus its
+    /// length. Much faster for very large media, but only the sampled regions
+    /// are compared; combine with --paranoid to verify matches in full.
+    #[arg(long, value_name = "SIZE", value_parser = parse_size, help_heading = "Scanning Options")]
+    pub sample_above: Option<u64>,
+
+    /// How to treat hardlinks to an already-seen file (skip, report, protect)
+    ///
+    /// Hardlinks share their data, so deleting one frees no space. `skip`
+    /// (default) leaves them out of grouping, `report` lists them as ordinary
+    /// duplicates and `protect` lists them but never lets them be selected.
+    #[arg(
+        long = "hardlinks",
+        value_name = "POLICY",
+        value_enum,
+        help_heading = "Scanning Options"
+    )]
+    pub hardlinks: Option<crate::duplicates::HardlinkPolicy>,
+
     /// False positive rate for Bloom filters (default: 0.01)
-    #[arg(long, value_name = "RATE", help_heading = "Scanning Options")]
+    ///
+    /// Used by the size filter while walking and the prehash filter in
+    /// phase 2. A lower rate uses more memory but sends fewer unique files on
+    /// to be hashed; it never changes which duplicates are found. Values are
+    /// clamped to 0.0001..=0.1.
+    #[arg(
+        long,
+        value_name = "RATE",
+        value_parser = parse_bloom_fp_rate,
+        help_heading = "Scanning Options"
+    )]
     pub bloom_fp_rate: Option<f64>,
 
     /// Threshold for similarity matching (Hamming distance)
@@ -530,12 +1150,44 @@ pub struct ScanArgs {
     )]
     pub doc_similarity_threshold: Option<u32>,
 
-    /// Minimum number of files in a group to be considered a duplicate (default: 2)
-    #[arg(long, value_name = "N", help_heading = "Scanning Options")]
-    pub min_group_size: Option<usize>,
+    /// Shared content fraction for --partial-similarity (default: 0.8)
+    ///
+    /// A value between 0 and 1; 0.9 reports only pairs where nine tenths of
+    /// the larger file also appear in the smaller one.
+    #[arg(
+        long = "partial-threshold",
+        value_name = "RATIO",
+        value_parser = parse_partial_threshold,
+        help_heading = "Scanning Options"
+    )]
+    pub partial_threshold: Option<f64>,
 
-    /// Enable image thumbnails in HTML reports
-    #[arg(long, help_heading = "Output Options")]
+    /// Minimum number of files in a group to be considered a duplicate (default: 2)
+    ///
+    /// Groups with fewer copies are dropped before summary statistics are
+    /// computed, e.g. `--min-duplicates 3` ignores pairs.
+    #[arg(
+        long,
+        visible_alias = "min-duplicates",
+        value_name = "N",
+        help_heading = "Scanning Options"
+    )]
+    pub min_group_size: Option<usize>,
+
+    /// Only report duplicates whose files all sit under the same boundary directory
+    ///
+    /// Can be specified multiple times, e.g. one per mirrored backup root.
+    /// Groups spanning several boundaries are split per boundary, and files
+    /// outside every boundary are left out of exact duplicate groups.
+    #[arg(
+        long = "dedupe-within",
+        value_name = "DIR",
+        help_heading = "Scanning Options"
+    )]
+    pub dedupe_within: Vec<PathBuf>,
+
+    /// Enable image thumbnails in HTML reports
+    #[arg(long, help_heading = "Output Options")]
     pub html_thumbnails: bool,
 
     /// Disable image thumbnails in HTML reports
@@ -550,9 +1202,153 @@ pub struct ScanArgs {
     #[arg(long, help_heading = "Output Options")]
     pub html_thumbnail_link: bool,
 
+    /// Emit a static HTML report without the sorting and expand/collapse script
+    #[arg(long, help_heading = "Output Options")]
+    pub html_plain: bool,
+
+    /// Include the interactive script in HTML reports (default)
+    #[arg(long, overrides_with = "html_plain", hide = true)]
+    pub no_html_plain: bool,
+
     /// Export only files selected for deletion (requires a session with selections)
     #[arg(long, help_heading = "Output Options")]
     pub export_selected: bool,
+
+    /// Field delimiter for CSV output: a single ASCII character, or `tab`
+    #[arg(long = "csv-delimiter", value_name = "CHAR", value_parser = parse_csv_delimiter, help_heading = "Output Options")]
+    pub csv_delimiter: Option<char>,
+
+    /// Field separator for compact output (default: tab)
+    #[arg(long = "compact-field-sep", value_name = "SEP", value_parser = parse_compact_separator, help_heading = "Output Options")]
+    pub compact_field_sep: Option<String>,
+
+    /// Separator between paths in compact output (default: `|`)
+    #[arg(long = "compact-path-sep", value_name = "SEP", value_parser = parse_compact_separator, help_heading = "Output Options")]
+    pub compact_path_sep: Option<String>,
+
+    /// Keeper rule for plan output (default: reference copies, else the first file)
+    #[arg(
+        long = "plan-keep",
+        value_name = "RULE",
+        help_heading = "Output Options"
+    )]
+    pub plan_keep: Option<PlanKeepArg>,
+
+    /// Format of plan output: human or json
+    #[arg(
+        long = "plan-format",
+        value_name = "FORMAT",
+        help_heading = "Output Options"
+    )]
+    pub plan_format: Option<StatsOutputFormat>,
+
+    /// Print human-readable sizes instead of bytes in compact output
+    #[arg(
+        long,
+        overrides_with = "no_human_sizes",
+        help_heading = "Output Options"
+    )]
+    pub human_sizes: bool,
+
+    /// Print sizes in bytes in compact output (default)
+    #[arg(long, overrides_with = "human_sizes", hide = true)]
+    pub no_human_sizes: bool,
+
+    /// Write JSON, YAML and CSV file paths relative to BASE
+    ///
+    /// Paths outside BASE, or on another drive, stay absolute. Deletion
+    /// still uses the full paths.
+    #[arg(
+        long = "json-paths-relative",
+        value_name = "BASE",
+        help_heading = "Output Options"
+    )]
+    pub json_paths_relative: Option<PathBuf>,
+
+    /// Emit minified JSON instead of pretty-printed output
+    #[arg(long = "json-compact", help_heading = "Output Options")]
+    pub json_compact: bool,
+
+    /// Pretty-print JSON output (default)
+    #[arg(long = "no-json-compact", overrides_with = "json_compact", hide = true)]
+    pub no_json_compact: bool,
+
+    /// Print only summary statistics, without listing duplicate groups
+    ///
+    /// JSON and YAML omit the `duplicates` array; other formats print a
+    /// compact text block.
+    #[arg(long = "summary-only", help_heading = "Output Options")]
+    pub summary_only: bool,
+
+    /// List duplicate groups in the output (default)
+    #[arg(long = "no-summary-only", overrides_with = "summary_only", hide = true)]
+    pub no_summary_only: bool,
+
+    /// Ring the terminal bell and show a desktop notification when the scan finishes
+    ///
+    /// Falls back to just the bell when no desktop environment is available.
+    #[arg(long, help_heading = "Output Options")]
+    pub notify: bool,
+
+    /// Do not notify when the scan finishes (default)
+    #[arg(long = "no-notify", overrides_with = "notify", hide = true)]
+    pub no_notify: bool,
+}
+
+impl ScanArgs {
+    /// Positional paths followed by any paths read from `--paths-from`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path list cannot be read, or if both it and
+    /// `--reference-from` would read stdin.
+    pub fn scan_paths(&self) -> std::io::Result<Vec<PathBuf>> {
+        let mut paths = self.paths.clone();
+        let list = match (&self.paths_from, &self.paths_from0) {
+            (Some(source), _) => Some((source, false)),
+            (None, Some(source)) => Some((source, true)),
+            (None, None) => None,
+        };
+        if let Some((source, nul_separated)) = list {
+            let from_stdin = source.as_os_str() == "-";
+            if from_stdin
+                && self
+                    .reference_from
+                    .as_ref()
+                    .is_some_and(|reference| reference.as_os_str() == "-")
+            {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "the path list and --reference-from cannot both be read from stdin",
+                ));
+            }
+            let listed = if from_stdin {
+                read_path_list(std::io::stdin().lock(), nul_separated)?
+            } else {
+                read_path_list(std::fs::File::open(source)?, nul_separated)?
+            };
+            paths.extend(listed);
+        }
+        Ok(paths)
+    }
+
+    /// `--reference` paths followed by any read from `--reference-from`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reference list cannot be read.
+    pub fn all_reference_paths(&self) -> std::io::Result<Vec<PathBuf>> {
+        let mut paths = self.reference_paths.clone();
+        if let Some(ref source) = self.reference_from {
+            let listed = if source.as_os_str() == "-" {
+                read_path_list(std::io::stdin().lock(), false)?
+            } else {
+                read_path_list(std::fs::File::open(source)?, false)?
+            };
+            paths.extend(listed);
+        }
+        Ok(paths)
+    }
 }
 
 /// Arguments for the load subcommand.
@@ -562,7 +1358,9 @@ pub struct LoadArgs {
     #[arg(value_name = "SESSION_FILE")]
     pub path: PathBuf,
 
-    /// Output format (tui for interactive, json/csv for scripting, html for report, script for deletion)
+    /// Output format (tui for interactive, text for piping, json/yaml/csv for scripting, tree for terminal triage, keepers for audit, compact for shell pipelines, plan for a dry-run deletion plan, html for report, script for deletion)
+    ///
+    /// Defaults to tui, or text when stdin or stdout is not a terminal.
     #[arg(short, long, value_enum, help_heading = "Output Options")]
     pub output: Option<OutputFormat>,
 
@@ -574,17 +1372,376 @@ pub struct LoadArgs {
     #[arg(long, help_heading = "Output Options")]
     pub export_selected: bool,
 
+    /// Emit a static HTML report without the sorting and expand/collapse script
+    #[arg(long, help_heading = "Output Options")]
+    pub html_plain: bool,
+
+    /// Include the interactive script in HTML reports (default)
+    #[arg(long, overrides_with = "html_plain", hide = true)]
+    pub no_html_plain: bool,
+
+    /// Field delimiter for CSV output: a single ASCII character, or `tab`
+    #[arg(long = "csv-delimiter", value_name = "CHAR", value_parser = parse_csv_delimiter, help_heading = "Output Options")]
+    pub csv_delimiter: Option<char>,
+
+    /// Field separator for compact output (default: tab)
+    #[arg(long = "compact-field-sep", value_name = "SEP", value_parser = parse_compact_separator, help_heading = "Output Options")]
+    pub compact_field_sep: Option<String>,
+
+    /// Separator between paths in compact output (default: `|`)
+    #[arg(long = "compact-path-sep", value_name = "SEP", value_parser = parse_compact_separator, help_heading = "Output Options")]
+    pub compact_path_sep: Option<String>,
+
+    /// Keeper rule for plan output (default: reference copies, else the first file)
+    #[arg(
+        long = "plan-keep",
+        value_name = "RULE",
+        help_heading = "Output Options"
+    )]
+    pub plan_keep: Option<PlanKeepArg>,
+
+    /// Format of plan output: human or json
+    #[arg(
+        long = "plan-format",
+        value_name = "FORMAT",
+        help_heading = "Output Options"
+    )]
+    pub plan_format: Option<StatsOutputFormat>,
+
+    /// Print human-readable sizes instead of bytes in compact output
+    #[arg(
+        long,
+        overrides_with = "no_human_sizes",
+        help_heading = "Output Options"
+    )]
+    pub human_sizes: bool,
+
+    /// Print sizes in bytes in compact output (default)
+    #[arg(long, overrides_with = "human_sizes", hide = true)]
+    pub no_human_sizes: bool,
+
+    /// Write JSON, YAML and CSV file paths relative to BASE
+    ///
+    /// Paths outside BASE, or on another drive, stay absolute. Deletion
+    /// still uses the full paths.
+    #[arg(
+        long = "json-paths-relative",
+        value_name = "BASE",
+        help_heading = "Output Options"
+    )]
+    pub json_paths_relative: Option<PathBuf>,
+
+    /// Compress session output files regardless of their extension
+    #[arg(
+        long = "session-compression",
+        value_enum,
+        value_name = "CODEC",
+        help_heading = "Output Options"
+    )]
+    pub session_compression: Option<crate::session::SessionCompression>,
+
+    /// Emit minified JSON instead of pretty-printed output
+    #[arg(long = "json-compact", help_heading = "Output Options")]
+    pub json_compact: bool,
+
+    /// Pretty-print JSON output (default)
+    #[arg(long = "no-json-compact", overrides_with = "json_compact", hide = true)]
+    pub no_json_compact: bool,
+
+    /// Print only summary statistics, without listing duplicate groups
+    ///
+    /// JSON and YAML omit the `duplicates` array; other formats print a
+    /// compact text block.
+    #[arg(long = "summary-only", help_heading = "Output Options")]
+    pub summary_only: bool,
+
+    /// List duplicate groups in the output (default)
+    #[arg(long = "no-summary-only", overrides_with = "summary_only", hide = true)]
+    pub no_summary_only: bool,
+
     /// Type of deletion script to generate
     #[arg(long, value_enum, value_name = "TYPE", help_heading = "Output Options")]
     pub script_type: Option<ScriptTypeArg>,
 
+    /// Write the deletion script to --output-file, show it, and run it once confirmed
+    ///
+    /// Implies `--output script`. The script is kept as an audit record, and
+    /// any file it fails to delete is reported. Not allowed with --dry-run.
+    #[arg(
+        long = "delete-to-script-and-run",
+        requires = "output_file",
+        conflicts_with = "dry_run",
+        help_heading = "Output Options"
+    )]
+    pub delete_to_script_and_run: bool,
+
     /// Do not perform any deletions (read-only mode)
     #[arg(long, alias = "analyze-only", help_heading = "Safety Options")]
     pub dry_run: bool,
 
+    /// Skip the confirmation prompt before running a deletion script
+    #[arg(short = 'y', long = "yes", help_heading = "Safety Options")]
+    pub yes: bool,
+
     /// Disable read-only mode (allow deletions)
     #[arg(long = "no-dry-run", overrides_with = "dry_run", hide = true)]
     pub no_dry_run: bool,
+
+    /// Compare paths case-insensitively, as on a Windows share
+    #[arg(
+        long,
+        overrides_with = "no_ignore_case_paths",
+        help_heading = "Safety Options"
+    )]
+    pub ignore_case_paths: bool,
+
+    /// Compare paths case-sensitively (default except on Windows)
+    #[arg(long, overrides_with = "ignore_case_paths", hide = true)]
+    pub no_ignore_case_paths: bool,
+
+    /// Remove directories left empty after deleting files
+    #[arg(long = "delete-empty-dirs", help_heading = "Safety Options")]
+    pub delete_empty_dirs: bool,
+
+    /// Rename surviving keepers with a sed-style substitution after deletion
+    #[arg(
+        long = "rename-keeper",
+        value_name = "SUBST",
+        help_heading = "Safety Options"
+    )]
+    pub rename_keeper: Option<String>,
+
+    /// Carry extended attributes and ACLs over to renamed keepers and --trash-dir moves
+    #[arg(long = "preserve-xattrs", help_heading = "Safety Options")]
+    pub preserve_xattrs: bool,
+
+    /// Require typing DELETE in the TUI when deleting more than N files [default: 100]
+    #[arg(
+        long = "confirm-threshold-count",
+        value_name = "N",
+        help_heading = "Safety Options"
+    )]
+    pub confirm_threshold_count: Option<usize>,
+
+    /// Require typing DELETE in the TUI when deleting more than SIZE [default: 10GB]
+    #[arg(
+        long = "confirm-threshold-bytes",
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help_heading = "Safety Options"
+    )]
+    pub confirm_threshold_bytes: Option<u64>,
+
+    /// Auto-protect one file per group (none, oldest, newest, shallowest-path)
+    #[arg(
+        long = "protect",
+        value_name = "POLICY",
+        value_enum,
+        help_heading = "Safety Options"
+    )]
+    pub protect: Option<crate::duplicates::ProtectPolicy>,
+
+    /// Permanently delete files under DIR instead of trashing them (repeatable)
+    #[arg(
+        long = "permanent-under",
+        value_name = "DIR",
+        help_heading = "Safety Options"
+    )]
+    pub permanent_under: Vec<PathBuf>,
+
+    /// Refuse any deletion run that would delete more than SIZE in total (e.g., 50G)
+    #[arg(
+        long = "max-total-delete",
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help_heading = "Safety Options"
+    )]
+    pub max_total_delete: Option<u64>,
+
+    /// Move deleted files into DIR instead of the system trash
+    #[arg(
+        long = "trash-dir",
+        value_name = "DIR",
+        help_heading = "Safety Options"
+    )]
+    pub trash_dir: Option<PathBuf>,
+
+    /// Make each deletion batch all-or-nothing
+    #[arg(long = "transactional-delete", help_heading = "Safety Options")]
+    pub transactional_delete: bool,
+}
+
+/// Arguments for the watch subcommand.
+///
+/// Filtering options are taken from the configuration file.
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// Directory paths to watch for duplicates
+    #[arg(value_name = "PATH", num_args = 1.., required = true)]
+    pub paths: Vec<PathBuf>,
+
+    /// Output format for each batch (ndjson emits one compact JSON document per line)
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value_t = WatchOutputFormat::Ndjson,
+        help_heading = "Output Options"
+    )]
+    pub output: WatchOutputFormat,
+
+    /// Quiet period in milliseconds before a batch of changes is processed
+    #[arg(
+        long,
+        value_name = "MS",
+        default_value_t = 500,
+        help_heading = "Watch Options"
+    )]
+    pub debounce_ms: u64,
+}
+
+/// Output format for watch mode batches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum WatchOutputFormat {
+    /// Pretty-printed JSON document per batch
+    Json,
+    /// Newline-delimited JSON, one compact document per batch
+    #[default]
+    Ndjson,
+}
+
+impl std::fmt::Display for WatchOutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WatchOutputFormat::Json => write!(f, "json"),
+            WatchOutputFormat::Ndjson => write!(f, "ndjson"),
+        }
+    }
+}
+
+/// Arguments for the stats subcommand.
+#[derive(Debug, Args)]
+pub struct StatsArgs {
+    /// Session file to analyze
+    #[arg(value_name = "SESSION_FILE")]
+    pub session: PathBuf,
+
+    /// Output format (human-readable report or JSON)
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value_t = StatsOutputFormat::Human,
+        help_heading = "Output Options"
+    )]
+    pub output: StatsOutputFormat,
+
+    /// Number of largest groups to list
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 10,
+        help_heading = "Output Options"
+    )]
+    pub top: usize,
+}
+
+/// Arguments for the diff subcommand.
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// Earlier session file
+    #[arg(value_name = "OLD_SESSION")]
+    pub old: PathBuf,
+
+    /// Later session file
+    #[arg(value_name = "NEW_SESSION")]
+    pub new: PathBuf,
+
+    /// Output format (human-readable report or JSON)
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value_t = StatsOutputFormat::Human,
+        help_heading = "Output Options"
+    )]
+    pub output: StatsOutputFormat,
+}
+
+/// Arguments for the verify subcommand.
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    /// Session file to verify
+    #[arg(value_name = "SESSION_FILE")]
+    pub session: PathBuf,
+
+    /// Compare size and modification time only instead of rehashing
+    #[arg(long)]
+    pub fast: bool,
+}
+
+/// Arguments for the version subcommand.
+#[derive(Debug, Args)]
+pub struct VersionArgs {
+    /// Print the version, git commit, build date and features as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Arguments for the hash subcommand.
+#[derive(Debug, Args)]
+pub struct HashArgs {
+    /// File to hash
+    ///
+    /// Hashing uses the same settings as a scan (memory mapping, buffer
+    /// sizes and `sample_above` from the configuration).
+    #[arg(value_name = "FILE")]
+    pub path: PathBuf,
+
+    /// Only compute the prehash (first 4 KiB)
+    #[arg(long)]
+    pub prehash_only: bool,
+}
+
+/// Arguments for the apply subcommand.
+#[derive(Debug, Args)]
+pub struct ApplyArgs {
+    /// Reviewed report to apply
+    ///
+    /// A CSV/TSV from `--output csv` with a `delete` column, or a JSON
+    /// report whose groups list the paths to remove in a `delete` array.
+    #[arg(value_name = "INPUT")]
+    pub input: PathBuf,
+
+    /// Rehash marked files and skip any whose content changed
+    #[arg(long = "paranoid")]
+    pub paranoid: bool,
+
+    /// List the files that would be deleted without deleting them
+    #[arg(long = "dry-run")]
+    pub dry_run: bool,
+}
+
+/// Output format for the stats and diff subcommands and dedupe plans.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsOutputFormat {
+    /// Human-readable report
+    #[default]
+    Human,
+    /// Pretty-printed JSON document
+    Json,
+}
+
+impl std::fmt::Display for StatsOutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatsOutputFormat::Human => write!(f, "human"),
+            StatsOutputFormat::Json => write!(f, "json"),
+        }
+    }
 }
 
 /// Output format for scan results.
@@ -606,6 +1763,65 @@ pub enum OutputFormat {
     Session,
     /// Shell script for deletion
     Script,
+    /// YAML output for configuration-management tooling
+    Yaml,
+    /// Tree-style text listing for quick terminal triage
+    Tree,
+    /// Plaintext report of the file kept in each group
+    Keepers,
+    /// One line per group (size, count, hash, paths) for grep and cut
+    Compact,
+    /// Dry-run plan of the files each group keeps and deletes
+    Plan,
+    /// Human-readable grouped listing (default when not run interactively)
+    Text,
+}
+
+/// Keeper rule for dedupe plans (`--plan-keep`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PlanKeepArg {
+    /// Keep the first file in the group
+    First,
+    /// Keep the file with the earliest modification time
+    Oldest,
+    /// Keep the file with the latest modification time
+    Newest,
+    /// Keep the file with the shortest path
+    ShortestPath,
+}
+
+impl From<PlanKeepArg> for crate::tui::app::KeeperStrategy {
+    fn from(arg: PlanKeepArg) -> Self {
+        match arg {
+            PlanKeepArg::First => Self::First,
+            PlanKeepArg::Oldest => Self::Oldest,
+            PlanKeepArg::Newest => Self::Newest,
+            PlanKeepArg::ShortestPath => Self::ShortestPath,
+        }
+    }
+}
+
+/// Progress display used while scanning.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, ValueEnum, serde::Serialize, serde::Deserialize, Default,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressFormat {
+    /// Interactive progress bars and spinners
+    #[default]
+    Bar,
+    /// One JSON object per line on stderr
+    Json,
+}
+
+impl std::fmt::Display for ProgressFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProgressFormat::Bar => write!(f, "bar"),
+            ProgressFormat::Json => write!(f, "json"),
+        }
+    }
 }
 
 /// Script type for deletion script generation.
@@ -634,6 +1850,15 @@ pub enum FileType {
     Archives,
 }
 
+/// I/O thread count given to `--io-threads`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoThreadsArg {
+    /// Pick the count from the storage type of the scan roots
+    Auto,
+    /// Use exactly this many threads
+    Count(usize),
+}
+
 /// TUI theme options.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default, serde::Serialize, serde::Deserialize,
@@ -670,10 +1895,68 @@ impl std::fmt::Display for OutputFormat {
             OutputFormat::Html => write!(f, "html"),
             OutputFormat::Session => write!(f, "session"),
             OutputFormat::Script => write!(f, "script"),
+            OutputFormat::Yaml => write!(f, "yaml"),
+            OutputFormat::Tree => write!(f, "tree"),
+            OutputFormat::Keepers => write!(f, "keepers"),
+            OutputFormat::Compact => write!(f, "compact"),
+            OutputFormat::Plan => write!(f, "plan"),
+            OutputFormat::Text => write!(f, "text"),
         }
     }
 }
 
+/// Read a list of paths, one per line or NUL-separated.
+///
+/// Empty entries are skipped and a trailing `\r` is stripped from each line,
+/// so lists written on Windows work unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use rustdupe::cli::read_path_list;
+/// use std::path::PathBuf;
+///
+/// let paths = read_path_list("/photos\n\n/backup\n".as_bytes(), false).unwrap();
+/// assert_eq!(paths, vec![PathBuf::from("/photos"), PathBuf::from("/backup")]);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if reading fails or an entry is not valid for the platform.
+pub fn read_path_list<R: std::io::Read>(
+    mut reader: R,
+    nul_separated: bool,
+) -> std::io::Result<Vec<PathBuf>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    let separator = if nul_separated { b'\0' } else { b'\n' };
+    data.split(|&b| b == separator)
+        .map(|entry| {
+            if nul_separated {
+                entry
+            } else {
+                entry.strip_suffix(b"\r").unwrap_or(entry)
+            }
+        })
+        .filter(|entry| !entry.is_empty())
+        .map(bytes_to_path)
+        .collect()
+}
+
+#[cfg(unix)]
+fn bytes_to_path(bytes: &[u8]) -> std::io::Result<PathBuf> {
+    use std::os::unix::ffi::OsStrExt;
+    Ok(PathBuf::from(std::ffi::OsStr::from_bytes(bytes)))
+}
+
+#[cfg(not(unix))]
+fn bytes_to_path(bytes: &[u8]) -> std::io::Result<PathBuf> {
+    std::str::from_utf8(bytes)
+        .map(PathBuf::from)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
 /// Parse a human-readable size string into bytes.
 ///
 /// Supports suffixes: B, KB, KiB, MB, MiB, GB, GiB, TB, TiB
@@ -735,8 +2018,208 @@ pub fn parse_size_usize(s: &str) -> Result<usize, String> {
     parse_size(s).map(|s| s as usize)
 }
 
-/// Parse a date string in YYYY-MM-DD format into SystemTime.
-pub fn parse_date(s: &str) -> Result<std::time::SystemTime, String> {
+/// Parse a CSV delimiter: `tab` (or `\t`) or a single ASCII character.
+///
+/// # Examples
+///
+/// ```
+/// use rustdupe::cli::parse_csv_delimiter;
+///
+/// assert_eq!(parse_csv_delimiter("tab").unwrap(), '\t');
+/// assert_eq!(parse_csv_delimiter(";").unwrap(), ';');
+/// ```
+/// # Errors
+///
+/// Returns an error for empty, multi-character or non-ASCII input, and for
+/// characters that cannot separate fields (quotes and line breaks).
+pub fn parse_csv_delimiter(s: &str) -> Result<char, String> {
+    if s.eq_ignore_ascii_case("tab") || s == "\\t" {
+        return Ok('\t');
+    }
+
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii() && !matches!(c, '"' | '\n' | '\r') => Ok(c),
+        (Some(c), None) if c.is_ascii() => Err(format!(
+            "'{}' cannot be used as a delimiter",
+            c.escape_default()
+        )),
+        _ => Err(format!(
+            "Invalid delimiter '{s}' (expected a single ASCII character or 'tab')"
+        )),
+    }
+}
+
+/// Parse a compact output separator: `tab` (or `\t`), `nul` (or `\0`), or
+/// any string without line breaks.
+///
+/// # Examples
+///
+/// ```
+/// use rustdupe::cli::parse_compact_separator;
+///
+/// assert_eq!(parse_compact_separator("tab").unwrap(), "\t");
+/// assert_eq!(parse_compact_separator(" | ").unwrap(), " | ");
+/// ```
+/// # Errors
+///
+/// Returns an error for empty input and for separators containing line
+/// breaks, which would split a group across lines.
+pub fn parse_compact_separator(s: &str) -> Result<String, String> {
+    if s.eq_ignore_ascii_case("tab") || s == "\\t" {
+        return Ok("\t".to_string());
+    }
+    if s.eq_ignore_ascii_case("nul") || s == "\\0" {
+        return Ok("\0".to_string());
+    }
+    if s.is_empty() {
+        return Err("Separator cannot be empty".to_string());
+    }
+    if s.contains(['\n', '\r']) {
+        return Err("Separator cannot contain line breaks".to_string());
+    }
+    Ok(s.to_string())
+}
+
+/// Parse an `--io-threads` value: a positive number or `auto`.
+///
+/// # Examples
+///
+/// ```
+/// use rustdupe::cli::{parse_io_threads, IoThreadsArg};
+///
+/// assert_eq!(parse_io_threads("auto").unwrap(), IoThreadsArg::Auto);
+/// assert_eq!(parse_io_threads("8").unwrap(), IoThreadsArg::Count(8));
+/// ```
+/// # Errors
+///
+/// Returns an error for zero and for anything that is neither a number nor
+/// `auto`.
+pub fn parse_io_threads(s: &str) -> Result<IoThreadsArg, String> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("auto") {
+        return Ok(IoThreadsArg::Auto);
+    }
+    match s.parse::<usize>() {
+        Ok(0) => Err("I/O thread count must be at least 1".to_string()),
+        Ok(n) => Ok(IoThreadsArg::Count(n)),
+        Err(_) => Err(format!(
+            "Invalid thread count '{s}' (expected a number or 'auto')"
+        )),
+    }
+}
+
+/// Parse a `--bloom-fp-rate` value: a probability between 0 and 1.
+///
+/// Range clamping is left to
+/// [`FinderConfig::with_bloom_fp_rate`](crate::duplicates::FinderConfig::with_bloom_fp_rate),
+/// which also covers rates from the config file.
+///
+/// # Examples
+///
+/// ```
+/// use rustdupe::cli::parse_bloom_fp_rate;
+///
+/// assert_eq!(parse_bloom_fp_rate("0.001").unwrap(), 0.001);
+/// assert!(parse_bloom_fp_rate("1.5").is_err());
+/// ```
+/// # Errors
+///
+/// Returns an error for non-numeric input and for rates outside (0, 1).
+pub fn parse_bloom_fp_rate(s: &str) -> Result<f64, String> {
+    let s = s.trim();
+    match s.parse::<f64>() {
+        Ok(rate) if rate > 0.0 && rate < 1.0 => Ok(rate),
+        Ok(_) => Err(format!(
+            "False positive rate '{s}' must be greater than 0 and less than 1"
+        )),
+        Err(_) => Err(format!("Invalid false positive rate '{s}'")),
+    }
+}
+
+/// Parse a `--partial-threshold` ratio: greater than 0, at most 1.
+pub fn parse_partial_threshold(s: &str) -> Result<f64, String> {
+    let s = s.trim();
+    match s.parse::<f64>() {
+        Ok(ratio) if ratio > 0.0 && ratio <= 1.0 => Ok(ratio),
+        Ok(_) => Err(format!(
+            "Partial threshold '{s}' must be greater than 0 and at most 1"
+        )),
+        Err(_) => Err(format!("Invalid partial threshold '{s}'")),
+    }
+}
+
+/// Parse a `--max-error-rate` percentage: 0 to 100.
+pub fn parse_error_rate(s: &str) -> Result<f64, String> {
+    let s = s.trim();
+    let s = s.strip_suffix('%').unwrap_or(s);
+    match s.parse::<f64>() {
+        Ok(rate) if (0.0..=100.0).contains(&rate) => Ok(rate),
+        Ok(_) => Err(format!("Error rate '{s}' must be between 0 and 100")),
+        Err(_) => Err(format!("Invalid error rate '{s}'")),
+    }
+}
+
+/// Parse a human duration such as `90s`, `12h`, `7d` or `1w2d`.
+///
+/// Each part is a whole number followed by a unit: `s` (seconds), `m`
+/// (minutes), `h` (hours), `d` (days) or `w` (weeks).
+pub fn parse_duration(s: &str) -> Result<std::time::Duration, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("Duration cannot be empty".to_string());
+    }
+
+    let mut total: u64 = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(format!("Invalid duration '{s}': expected a number"));
+        }
+        let num: u64 = rest[..digits]
+            .parse()
+            .map_err(|_| format!("Invalid number in duration '{s}'"))?;
+        let unit = rest[digits..]
+            .chars()
+            .next()
+            .ok_or_else(|| format!("Duration '{s}' needs a unit: s, m, h, d or w (e.g. 7d)"))?;
+        let seconds: u64 = match unit.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 3_600,
+            'd' => 86_400,
+            'w' => 604_800,
+            _ => {
+                return Err(format!(
+                    "Unknown duration unit '{unit}' (use s, m, h, d or w)"
+                ))
+            }
+        };
+        total = num
+            .checked_mul(seconds)
+            .and_then(|part| total.checked_add(part))
+            .ok_or_else(|| format!("Duration '{s}' is too large"))?;
+        rest = &rest[digits + unit.len_utf8()..];
+    }
+    Ok(std::time::Duration::from_secs(total))
+}
+
+/// The time `ago` before `now`, clamped to the Unix epoch.
+///
+/// Resolves `--modified-within` and `--modified-before` against the scan
+/// start so the window is recorded as absolute times.
+#[must_use]
+pub fn time_ago(now: std::time::SystemTime, ago: std::time::Duration) -> std::time::SystemTime {
+    now.checked_sub(ago)
+        .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
+        .max(std::time::SystemTime::UNIX_EPOCH)
+}
+
+/// Parse a date string in YYYY-MM-DD format into SystemTime.
+pub fn parse_date(s: &str) -> Result<std::time::SystemTime, String> {
     use chrono::{NaiveDate, TimeZone, Utc};
     NaiveDate::parse_from_str(s, "%Y-%m-%d")
         .map(|d| {
@@ -797,6 +2280,61 @@ mod tests {
         assert_eq!(parse_size("1 MB").unwrap(), 1_000_000);
     }
 
+    #[test]
+    fn test_parse_io_threads() {
+        assert_eq!(parse_io_threads("AUTO"), Ok(IoThreadsArg::Auto));
+        assert_eq!(parse_io_threads(" 2 "), Ok(IoThreadsArg::Count(2)));
+        assert!(parse_io_threads("0").is_err());
+        assert!(parse_io_threads("-1").is_err());
+        assert!(parse_io_threads("many").is_err());
+    }
+
+    #[test]
+    fn test_parse_partial_threshold() {
+        assert_eq!(parse_partial_threshold("0.9"), Ok(0.9));
+        assert_eq!(parse_partial_threshold("1"), Ok(1.0));
+        assert!(parse_partial_threshold("0").is_err());
+        assert!(parse_partial_threshold("1.5").is_err());
+        assert!(parse_partial_threshold("NaN").is_err());
+        assert!(parse_partial_threshold("most").is_err());
+
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            ".",
+            "--partial-similarity",
+            "--partial-threshold",
+            "0.7",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert!(args.partial_similarity);
+                assert_eq!(args.partial_threshold, Some(0.7));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_bloom_fp_rate() {
+        assert_eq!(parse_bloom_fp_rate("0.05"), Ok(0.05));
+        assert_eq!(parse_bloom_fp_rate(" 1e-3 "), Ok(0.001));
+        assert!(parse_bloom_fp_rate("0").is_err());
+        assert!(parse_bloom_fp_rate("1").is_err());
+        assert!(parse_bloom_fp_rate("-0.1").is_err());
+        assert!(parse_bloom_fp_rate("NaN").is_err());
+        assert!(parse_bloom_fp_rate("often").is_err());
+
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", ".", "--bloom-fp-rate", "0.001"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(args.bloom_fp_rate, Some(0.001)),
+            _ => panic!("Expected Scan command"),
+        }
+        assert!(Cli::try_parse_from(["rustdupe", "scan", ".", "--bloom-fp-rate", "2"]).is_err());
+    }
+
     #[test]
     fn test_parse_size_errors() {
         assert!(parse_size("").is_err());
@@ -817,7 +2355,7 @@ mod tests {
     fn test_cli_parse_scan_basic() {
         let cli = Cli::try_parse_from(["rustdupe", "scan", "/some/path"]).unwrap();
         assert_eq!(cli.verbose, 0);
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.paths, vec![PathBuf::from("/some/path")]);
                 assert_eq!(args.output, None); // default is None now
@@ -843,6 +2381,8 @@ mod tests {
             "2026-01-01",
             "--older-than",
             "2026-12-31",
+            "--settle",
+            "5",
             "--ignore",
             "*.tmp",
             "--ignore",
@@ -856,13 +2396,14 @@ mod tests {
 
         assert_eq!(cli.verbose, 1);
 
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.output, Some(OutputFormat::Json));
                 assert_eq!(args.min_size, Some(1_000_000));
                 assert_eq!(args.max_size, Some(1_000_000_000));
                 assert!(args.newer_than.is_some());
                 assert!(args.older_than.is_some());
+                assert_eq!(args.settle, Some(5));
                 assert_eq!(args.ignore_patterns, vec!["*.tmp", "node_modules"]);
                 assert_eq!(args.regex_include, vec!["foo.*"]);
                 assert_eq!(args.regex_exclude, vec!["bar.*"]);
@@ -884,7 +2425,7 @@ mod tests {
         ])
         .unwrap();
 
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.file_types, vec![FileType::Images, FileType::Documents]);
             }
@@ -892,6 +2433,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_owner_filters() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--only-owner",
+            "1000",
+            "--only-owner",
+            "alice",
+            "--exclude-owner",
+            "root",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.only_owner, vec!["1000", "alice"]);
+                assert_eq!(args.exclude_owner, vec!["root"]);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_exclude_dirs() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--exclude-dir",
+            "node_modules",
+            "--exclude-dir",
+            "/path/build",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(
+                args.exclude_dirs,
+                vec![PathBuf::from("node_modules"), PathBuf::from("/path/build")]
+            ),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_scan_extensions() {
+        let cli = Cli::try_parse_from([
+            "rustdupe", "scan", "/path", "--ext", "raw,cr2", "--ext", "nef",
+        ])
+        .unwrap();
+
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.extensions, vec!["raw", "cr2", "nef"]);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
     #[test]
     fn test_parse_date() {
         assert!(parse_date("2026-02-01").is_ok());
@@ -911,7 +2511,7 @@ mod tests {
             "posix",
         ])
         .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.output, Some(OutputFormat::Script));
                 assert_eq!(args.script_type, Some(ScriptTypeArg::Posix));
@@ -932,7 +2532,7 @@ mod tests {
             "powershell",
         ])
         .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Load(args) => {
                 assert_eq!(args.output, Some(OutputFormat::Script));
                 assert_eq!(args.script_type, Some(ScriptTypeArg::Powershell));
@@ -941,6 +2541,186 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_scan_yaml() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--output", "yaml"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.output, Some(OutputFormat::Yaml));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_load_keepers() {
+        let cli =
+            Cli::try_parse_from(["rustdupe", "load", "s.json", "--output", "keepers"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => {
+                assert_eq!(args.output, Some(OutputFormat::Keepers));
+            }
+            _ => panic!("Expected Load command"),
+        }
+        assert_eq!(OutputFormat::Keepers.to_string(), "keepers");
+    }
+
+    #[test]
+    fn test_cli_parse_scan_text() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "-o", "text"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.output, Some(OutputFormat::Text));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+        assert_eq!(OutputFormat::Text.to_string(), "text");
+    }
+
+    #[test]
+    fn test_cli_parse_scan_tree() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--output", "tree"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.output, Some(OutputFormat::Tree));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_watch() {
+        let cli =
+            Cli::try_parse_from(["rustdupe", "watch", "/a", "/b", "--debounce-ms", "250"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Watch(args) => {
+                assert_eq!(args.paths.len(), 2);
+                assert_eq!(args.output, WatchOutputFormat::Ndjson);
+                assert_eq!(args.debounce_ms, 250);
+            }
+            _ => panic!("Expected Watch command"),
+        }
+
+        assert!(Cli::try_parse_from(["rustdupe", "watch"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_stats() {
+        let cli = Cli::try_parse_from(["rustdupe", "stats", "session.json"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Stats(args) => {
+                assert_eq!(args.session, PathBuf::from("session.json"));
+                assert_eq!(args.output, StatsOutputFormat::Human);
+                assert_eq!(args.top, 10);
+            }
+            _ => panic!("Expected Stats command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "rustdupe", "stats", "s.json", "--output", "json", "--top", "3",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Stats(args) => {
+                assert_eq!(args.output, StatsOutputFormat::Json);
+                assert_eq!(args.top, 3);
+            }
+            _ => panic!("Expected Stats command"),
+        }
+
+        assert!(Cli::try_parse_from(["rustdupe", "stats"]).is_err());
+        assert!(Cli::try_parse_from(["rustdupe", "stats", "s.json", "-o", "csv"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_diff() {
+        let cli =
+            Cli::try_parse_from(["rustdupe", "diff", "old.json", "new.json.zst", "-o", "json"])
+                .unwrap();
+        match cli.command.unwrap() {
+            Commands::Diff(args) => {
+                assert_eq!(args.old, PathBuf::from("old.json"));
+                assert_eq!(args.new, PathBuf::from("new.json.zst"));
+                assert_eq!(args.output, StatsOutputFormat::Json);
+            }
+            _ => panic!("Expected Diff command"),
+        }
+
+        assert!(Cli::try_parse_from(["rustdupe", "diff", "old.json"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_verify() {
+        let cli = Cli::try_parse_from(["rustdupe", "verify", "s.json"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Verify(args) => {
+                assert_eq!(args.session, PathBuf::from("s.json"));
+                assert!(!args.fast);
+            }
+            _ => panic!("Expected Verify command"),
+        }
+
+        let cli = Cli::try_parse_from(["rustdupe", "verify", "s.json", "--fast"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Verify(args)) if args.fast));
+    }
+
+    #[test]
+    fn test_cli_parse_version_subcommand() {
+        let cli = Cli::try_parse_from(["rustdupe", "version"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Version(args)) if !args.json));
+
+        let cli = Cli::try_parse_from(["rustdupe", "version", "--json"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Version(args)) if args.json));
+    }
+
+    #[test]
+    fn test_cli_parse_hash() {
+        let cli = Cli::try_parse_from(["rustdupe", "hash", "file.bin"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Hash(args) => {
+                assert_eq!(args.path, PathBuf::from("file.bin"));
+                assert!(!args.prehash_only);
+            }
+            _ => panic!("Expected Hash command"),
+        }
+
+        let cli = Cli::try_parse_from(["rustdupe", "hash", "file.bin", "--prehash-only"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Hash(args) => assert!(args.prehash_only),
+            _ => panic!("Expected Hash command"),
+        }
+
+        assert!(Cli::try_parse_from(["rustdupe", "hash"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_apply() {
+        let cli = Cli::try_parse_from(["rustdupe", "apply", "reviewed.csv"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Apply(args) => {
+                assert_eq!(args.input, PathBuf::from("reviewed.csv"));
+                assert!(!args.paranoid);
+                assert!(!args.dry_run);
+            }
+            _ => panic!("Expected Apply command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "apply",
+            "reviewed.json",
+            "--paranoid",
+            "--dry-run",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Apply(args) => assert!(args.paranoid && args.dry_run),
+            _ => panic!("Expected Apply command"),
+        }
+
+        assert!(Cli::try_parse_from(["rustdupe", "apply"]).is_err());
+    }
+
     #[test]
     fn test_cli_quiet_conflicts_with_verbose() {
         let result = Cli::try_parse_from(["rustdupe", "-v", "-q", "scan", "/path"]);
@@ -950,7 +2730,7 @@ mod tests {
     #[test]
     fn test_cli_parse_scan_csv() {
         let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--output", "csv"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.output, Some(OutputFormat::Csv));
             }
@@ -965,6 +2745,29 @@ mod tests {
         assert_eq!(cli.verbose, 0);
     }
 
+    #[test]
+    fn test_cli_parse_log_file() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path"]).unwrap();
+        assert_eq!(cli.log_file, None);
+        assert!(!cli.log_stderr);
+
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "-v",
+            "scan",
+            "/path",
+            "--log-file",
+            "/tmp/rustdupe.log",
+            "--log-stderr",
+        ])
+        .unwrap();
+        assert_eq!(cli.log_file, Some(PathBuf::from("/tmp/rustdupe.log")));
+        assert!(cli.log_stderr);
+
+        // Mirroring only makes sense with a file
+        assert!(Cli::try_parse_from(["rustdupe", "scan", "/path", "--log-stderr"]).is_err());
+    }
+
     #[test]
     fn test_cli_parse_scan_all_flags() {
         let cli = Cli::try_parse_from([
@@ -988,11 +2791,12 @@ mod tests {
         ])
         .unwrap();
 
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert!(args.follow_symlinks);
                 assert!(args.skip_hidden);
-                assert_eq!(args.io_threads, Some(8));
+                assert_eq!(args.io_threads, Some(IoThreadsArg::Count(8)));
+                assert_eq!(args.cpu_threads, None);
                 assert_eq!(args.io_buffer_size, Some(1_000_000));
                 assert_eq!(args.io_buffer_min, Some(128_000));
                 assert_eq!(args.io_buffer_max, Some(32_000_000));
@@ -1006,14 +2810,181 @@ mod tests {
     }
 
     #[test]
-    fn test_cli_no_color_env() {
-        // Use a lock if we had one, but since we don't, we'll just be careful.
-        // Note: This test may fail if run in parallel with other CLI tests.
-        std::env::set_var("NO_COLOR", "true");
-        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path"]).unwrap();
-        assert!(cli.no_color);
-        std::env::remove_var("NO_COLOR");
-    }
+    fn test_cli_parse_rename_keeper() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--rename-keeper",
+            r"s/ \(\d+\)//",
+            "--preserve-xattrs",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.rename_keeper.as_deref(), Some(r"s/ \(\d+\)//"));
+                assert!(args.preserve_xattrs);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli = Cli::try_parse_from(["rustdupe", "load", "s.json"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => {
+                assert!(args.rename_keeper.is_none());
+                assert!(!args.preserve_xattrs);
+            }
+            _ => panic!("Expected Load command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_follow_symlink_kinds() {
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--follow-symlinks-files"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert!(args.follow_file_symlinks);
+                assert!(!args.follow_dir_symlinks);
+                assert!(!args.follow_symlinks);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--follow-symlinks-dirs"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert!(args.follow_dir_symlinks);
+                assert!(!args.follow_file_symlinks);
+                assert!(!args.report_broken_symlinks);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--report-broken-symlinks"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert!(args.report_broken_symlinks),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_progress_format() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--progress-format", "json"])
+            .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(args.progress_format, Some(ProgressFormat::Json)),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert!(args.progress_format.is_none()),
+            _ => panic!("Expected Scan command"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--progress-format", "xml"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_permanent_under() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--permanent-under",
+            "/path/scratch",
+            "--permanent-under",
+            "/path/tmp",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(
+                args.permanent_under,
+                vec![PathBuf::from("/path/scratch"), PathBuf::from("/path/tmp")]
+            ),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "load",
+            "s.json",
+            "--permanent-under",
+            "/scratch",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => {
+                assert_eq!(args.permanent_under, vec![PathBuf::from("/scratch")]);
+            }
+            _ => panic!("Expected Load command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_max_total_delete() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--max-total-delete", "50G"])
+            .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(args.max_total_delete, Some(50_000_000_000)),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli = Cli::try_parse_from(["rustdupe", "load", "s.json", "--max-total-delete", "1MiB"])
+            .unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => assert_eq!(args.max_total_delete, Some(1_048_576)),
+            _ => panic!("Expected Load command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_trash_dir() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--trash-dir", "/srv/trash"])
+            .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(args.trash_dir, Some(PathBuf::from("/srv/trash"))),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli = Cli::try_parse_from(["rustdupe", "load", "s.json", "--trash-dir", "t"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => assert_eq!(args.trash_dir, Some(PathBuf::from("t"))),
+            _ => panic!("Expected Load command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_transactional_delete() {
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--transactional-delete"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert!(args.transactional_delete),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["rustdupe", "load", "s.json", "--transactional-delete"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => assert!(args.transactional_delete),
+            _ => panic!("Expected Load command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_no_color_env() {
+        // Use a lock if we had one, but since we don't, we'll just be careful.
+        // Note: This test may fail if run in parallel with other CLI tests.
+        std::env::set_var("NO_COLOR", "true");
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path"]).unwrap();
+        assert!(cli.no_color);
+        std::env::remove_var("NO_COLOR");
+    }
 
     #[test]
     fn test_cli_invalid_subcommand() {
@@ -1046,13 +3017,19 @@ mod tests {
             "--reference",
             "/ref2",
             "--dry-run",
+            "--session-compression",
+            "zstd",
         ])
         .unwrap();
 
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.paths, vec![PathBuf::from("/path")]);
                 assert_eq!(args.save_session, Some(PathBuf::from("session.json")));
+                assert_eq!(
+                    args.session_compression,
+                    Some(crate::session::SessionCompression::Zstd)
+                );
                 assert_eq!(
                     args.reference_paths,
                     vec![PathBuf::from("/ref1"), PathBuf::from("/ref2")]
@@ -1062,8 +3039,36 @@ mod tests {
             _ => panic!("Expected Scan command"),
         }
 
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--delete-empty-dirs"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert!(args.delete_empty_dirs);
+                assert!(!args.dry_run);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--confirm-threshold-count",
+            "50",
+            "--confirm-threshold-bytes",
+            "2GB",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.confirm_threshold_count, Some(50));
+                assert_eq!(args.confirm_threshold_bytes, Some(2_000_000_000));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
         let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--analyze-only"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert!(args.dry_run);
             }
@@ -1072,7 +3077,7 @@ mod tests {
 
         let cli =
             Cli::try_parse_from(["rustdupe", "scan", "--load-session", "session.json"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert!(args.paths.is_empty());
                 assert_eq!(args.load_session, Some(PathBuf::from("session.json")));
@@ -1085,7 +3090,7 @@ mod tests {
     fn test_cli_parse_load_subcommand() {
         let cli =
             Cli::try_parse_from(["rustdupe", "load", "session.json", "--output", "json"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Load(args) => {
                 assert_eq!(args.path, PathBuf::from("session.json"));
                 assert_eq!(args.output, Some(OutputFormat::Json));
@@ -1103,20 +3108,22 @@ mod tests {
             "--cache",
             "mycache.db",
             "--clear-cache",
+            "--cache-compress",
         ])
         .unwrap();
 
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.cache, Some(PathBuf::from("mycache.db")));
                 assert!(args.clear_cache);
+                assert!(args.cache_compress);
                 assert!(!args.no_cache);
             }
             _ => panic!("Expected Scan command"),
         }
 
         let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--no-cache"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert!(args.no_cache);
                 assert!(args.cache.is_none());
@@ -1241,11 +3248,35 @@ mod tests {
         assert_eq!(cli.verbose, 1);
     }
 
+    #[test]
+    fn test_cli_size_units_flag() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path"]).unwrap();
+        assert_eq!(cli.size_units, None);
+
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--size-units", "si"]).unwrap();
+        assert_eq!(cli.size_units, Some(SizeFormat::Si));
+
+        let cli = Cli::try_parse_from(["rustdupe", "--size-units", "iec-proper", "load", "s.json"])
+            .unwrap();
+        assert_eq!(cli.size_units, Some(SizeFormat::IecProper));
+
+        assert!(Cli::try_parse_from(["rustdupe", "scan", "/path", "--size-units", "kb"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_absolute_times_flag() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path"]).unwrap();
+        assert!(!cli.absolute_times);
+
+        let cli = Cli::try_parse_from(["rustdupe", "load", "s.json", "--absolute-times"]).unwrap();
+        assert!(cli.absolute_times);
+    }
+
     #[test]
     fn test_cli_parse_scan_multiple_paths() {
         let cli =
             Cli::try_parse_from(["rustdupe", "scan", "/path/1", "/path/2", "/path/3"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.paths.len(), 3);
                 assert_eq!(args.paths[0], PathBuf::from("/path/1"));
@@ -1259,7 +3290,7 @@ mod tests {
     #[test]
     fn test_cli_parse_scan_two_paths() {
         let cli = Cli::try_parse_from(["rustdupe", "scan", "/downloads", "/documents"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(
                     args.paths,
@@ -1285,7 +3316,7 @@ mod tests {
         ])
         .unwrap();
         assert_eq!(cli.verbose, 1);
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.paths.len(), 2);
                 assert_eq!(args.paths[0], PathBuf::from("/path/1"));
@@ -1311,7 +3342,7 @@ mod tests {
     fn test_cli_parse_scan_single_path_backward_compat() {
         // Single path should still work (backward compatibility)
         let cli = Cli::try_parse_from(["rustdupe", "scan", "/single/path"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.paths.len(), 1);
                 assert_eq!(args.paths[0], PathBuf::from("/single/path"));
@@ -1388,7 +3419,7 @@ mod tests {
     fn test_cli_parse_scan_single_group() {
         let cli = Cli::try_parse_from(["rustdupe", "scan", "--group", "photos=/Photos", "/path"])
             .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.groups.len(), 1);
                 assert_eq!(args.groups[0].name, "photos");
@@ -1410,7 +3441,7 @@ mod tests {
             "/path",
         ])
         .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.groups.len(), 2);
                 assert_eq!(args.groups[0].name, "photos");
@@ -1433,7 +3464,7 @@ mod tests {
             "/Downloads",
         ])
         .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.groups.len(), 2);
                 assert_eq!(args.paths.len(), 2);
@@ -1455,12 +3486,665 @@ mod tests {
         assert!(cli.list_profiles);
     }
 
+    #[test]
+    fn test_cli_parse_include_empty() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--include-empty"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert!(args.include_empty);
+                assert!(!args.no_include_empty);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_name_cluster() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--name-cluster"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert!(args.name_cluster);
+                assert!(!args.no_name_cluster);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_dedupe_within() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/backups",
+            "--dedupe-within",
+            "/backups/a",
+            "--dedupe-within",
+            "/backups/b",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(
+                args.dedupe_within,
+                vec![PathBuf::from("/backups/a"), PathBuf::from("/backups/b")]
+            ),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_min_duplicates_alias() {
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--min-duplicates", "3"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(args.min_group_size, Some(3)),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_csv_delimiter() {
+        assert_eq!(parse_csv_delimiter("tab").unwrap(), '\t');
+        assert_eq!(parse_csv_delimiter("TAB").unwrap(), '\t');
+        assert_eq!(parse_csv_delimiter("\\t").unwrap(), '\t');
+        assert_eq!(parse_csv_delimiter("\t").unwrap(), '\t');
+        assert_eq!(parse_csv_delimiter(";").unwrap(), ';');
+        assert!(parse_csv_delimiter("").is_err());
+        assert!(parse_csv_delimiter(";;").is_err());
+        assert!(parse_csv_delimiter("\"").is_err());
+        assert!(parse_csv_delimiter("é").is_err());
+    }
+
+    #[test]
+    fn test_parse_compact_separator() {
+        assert_eq!(parse_compact_separator("tab").unwrap(), "\t");
+        assert_eq!(parse_compact_separator("\\t").unwrap(), "\t");
+        assert_eq!(parse_compact_separator("NUL").unwrap(), "\0");
+        assert_eq!(parse_compact_separator(",").unwrap(), ",");
+        assert_eq!(parse_compact_separator(" :: ").unwrap(), " :: ");
+        assert!(parse_compact_separator("").is_err());
+        assert!(parse_compact_separator("a\nb").is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_compact_options() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--output",
+            "compact",
+            "--compact-field-sep",
+            ",",
+            "--compact-path-sep",
+            "nul",
+            "--human-sizes",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.output, Some(OutputFormat::Compact));
+                assert_eq!(args.compact_field_sep.as_deref(), Some(","));
+                assert_eq!(args.compact_path_sep.as_deref(), Some("\0"));
+                assert!(args.human_sizes);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+        assert_eq!(OutputFormat::Compact.to_string(), "compact");
+    }
+
+    #[test]
+    fn test_cli_parse_plan_options() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "load",
+            "s.json",
+            "--output",
+            "plan",
+            "--plan-keep",
+            "shortest-path",
+            "--plan-format",
+            "json",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => {
+                assert_eq!(args.output, Some(OutputFormat::Plan));
+                assert_eq!(args.plan_keep, Some(PlanKeepArg::ShortestPath));
+                assert_eq!(args.plan_format, Some(StatsOutputFormat::Json));
+            }
+            _ => panic!("Expected Load command"),
+        }
+        assert_eq!(OutputFormat::Plan.to_string(), "plan");
+        assert_eq!(
+            crate::tui::app::KeeperStrategy::from(PlanKeepArg::Newest),
+            crate::tui::app::KeeperStrategy::Newest
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_delete_to_script_and_run() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--delete-to-script-and-run",
+            "--output-file",
+            "delete.sh",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert!(args.delete_to_script_and_run),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "load",
+            "session.json",
+            "--delete-to-script-and-run",
+            "--output-file",
+            "delete.sh",
+            "--yes",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => assert!(args.delete_to_script_and_run && args.yes),
+            _ => panic!("Expected Load command"),
+        }
+
+        // The script must be saved somewhere to be audited
+        assert!(
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--delete-to-script-and-run"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from([
+            "rustdupe",
+            "load",
+            "s.json",
+            "--delete-to-script-and-run",
+            "--output-file",
+            "delete.sh",
+            "--dry-run",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_csv_delimiter() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--output",
+            "csv",
+            "--csv-delimiter",
+            "tab",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(args.csv_delimiter, Some('\t')),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_summary_only() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--summary-only"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert!(args.summary_only),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_notify() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "--notify"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert!(args.notify);
+                assert!(!args.no_notify);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_json_compact() {
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "-o", "json", "--json-compact"])
+                .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert!(args.json_compact),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli = Cli::try_parse_from(["rustdupe", "load", "s.json", "--json-compact"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => assert!(args.json_compact),
+            _ => panic!("Expected Load command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_json_paths_relative() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/data",
+            "-o",
+            "json",
+            "--json-paths-relative",
+            "/data",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.json_paths_relative, Some(PathBuf::from("/data")));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli = Cli::try_parse_from(["rustdupe", "load", "s.json", "-o", "csv"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => assert_eq!(args.json_paths_relative, None),
+            _ => panic!("Expected Load command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_html_plain() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path", "-o", "html", "--html-plain"])
+            .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert!(args.html_plain),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "load",
+            "s.json",
+            "--html-plain",
+            "--no-html-plain",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Load(args) => assert!(!args.html_plain && args.no_html_plain),
+            _ => panic!("Expected Load command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_paranoid_tuning() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--paranoid",
+            "--paranoid-chunk",
+            "1MiB",
+            "--paranoid-max-open",
+            "16",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.paranoid_chunk, Some(1_048_576));
+                assert_eq!(args.paranoid_max_open, Some(16));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_read_buffer() {
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--read-buffer", "1MiB"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(args.read_buffer, Some(1_048_576)),
+            _ => panic!("Expected Scan command"),
+        }
+
+        // Both flags set the same buffer
+        assert!(Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--read-buffer",
+            "1MiB",
+            "--io-buffer-size",
+            "64KiB",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_hash_retries() {
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--hash-retries", "3"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(args.hash_retries, Some(3)),
+            _ => panic!("Expected Scan command"),
+        }
+        assert!(
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--hash-retries", "-1"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        use std::time::Duration;
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("15m"), Ok(Duration::from_secs(900)));
+        assert_eq!(parse_duration("12h"), Ok(Duration::from_secs(43_200)));
+        assert_eq!(parse_duration("7d"), Ok(Duration::from_secs(604_800)));
+        assert_eq!(parse_duration("2W"), Ok(Duration::from_secs(1_209_600)));
+        assert_eq!(parse_duration(" 1w2d "), Ok(Duration::from_secs(777_600)));
+        assert_eq!(parse_duration("0d"), Ok(Duration::ZERO));
+
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("7").is_err());
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("7y").is_err());
+        assert!(parse_duration("-7d").is_err());
+        assert!(parse_duration("1.5d").is_err());
+        assert!(parse_duration("99999999999999999w").is_err());
+    }
+
+    #[test]
+    fn test_time_ago() {
+        use std::time::{Duration, SystemTime};
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        assert_eq!(
+            time_ago(now, Duration::from_secs(86_400)),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(913_600)
+        );
+        assert_eq!(time_ago(now, Duration::ZERO), now);
+        // Windows reaching past the epoch clamp to it
+        assert_eq!(
+            time_ago(now, Duration::from_secs(2_000_000)),
+            SystemTime::UNIX_EPOCH
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_modified_window() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--modified-within",
+            "7d",
+            "--modified-before",
+            "1d",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(
+                    args.modified_within,
+                    Some(std::time::Duration::from_secs(604_800))
+                );
+                assert_eq!(
+                    args.modified_before,
+                    Some(std::time::Duration::from_secs(86_400))
+                );
+            }
+            _ => panic!("Expected Scan command"),
+        }
+        // An absolute and a relative bound on the same side conflict
+        assert!(Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--modified-within",
+            "7d",
+            "--newer-than",
+            "2024-01-01",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_error_thresholds() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--max-errors",
+            "10",
+            "--max-error-rate",
+            "2.5%",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.max_errors, Some(10));
+                assert_eq!(args.max_error_rate, Some(2.5));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+        assert_eq!(parse_error_rate("0"), Ok(0.0));
+        assert_eq!(parse_error_rate("100"), Ok(100.0));
+        assert!(parse_error_rate("100.1").is_err());
+        assert!(parse_error_rate("-1").is_err());
+        assert!(parse_error_rate("lots").is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_since_session() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--since-session",
+            "nightly.json",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.since_session, Some(PathBuf::from("nightly.json")))
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "--load-session",
+            "a.json",
+            "--since-session",
+            "b.json"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_paths_from() {
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "--paths-from", "list.txt"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert!(args.paths.is_empty());
+                assert_eq!(args.paths_from, Some(PathBuf::from("list.txt")));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "--paths-from",
+            "a.txt",
+            "--paths-from0",
+            "b.txt"
+        ])
+        .is_err());
+        assert!(Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "--paths-from0",
+            "-",
+            "--load-session",
+            "s.json"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_scan_paths_appends_list_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let list = dir.path().join("roots.txt");
+        std::fs::write(&list, "/data/one\r\n\n/data/two\n").unwrap();
+
+        let list_arg = list.to_string_lossy();
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/first", "--paths-from", &list_arg]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(
+                args.scan_paths().unwrap(),
+                vec![
+                    PathBuf::from("/first"),
+                    PathBuf::from("/data/one"),
+                    PathBuf::from("/data/two"),
+                ]
+            ),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_scan_paths_missing_list_file() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "--paths-from",
+            "/nonexistent/rustdupe-roots.txt",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert!(args.scan_paths().is_err()),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_scan_paths_rejects_two_stdin_lists() {
+        for list_flag in ["--paths-from", "--paths-from0"] {
+            let cli =
+                Cli::try_parse_from(["rustdupe", "scan", list_flag, "-", "--reference-from", "-"])
+                    .unwrap();
+            match cli.command.unwrap() {
+                Commands::Scan(args) => assert_eq!(
+                    args.scan_paths().unwrap_err().kind(),
+                    std::io::ErrorKind::InvalidInput
+                ),
+                _ => panic!("Expected Scan command"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_path_list_nul_separated() {
+        let paths = read_path_list(&b"/with space\0/line\nbreak\0\0"[..], true).unwrap();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("/with space"), PathBuf::from("/line\nbreak")]
+        );
+        assert!(read_path_list(&b""[..], false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cli_parse_hardlinks() {
+        use crate::duplicates::HardlinkPolicy;
+
+        for (value, expected) in [
+            ("skip", HardlinkPolicy::Skip),
+            ("report", HardlinkPolicy::Report),
+            ("protect", HardlinkPolicy::ReportButProtect),
+        ] {
+            let cli =
+                Cli::try_parse_from(["rustdupe", "scan", "/path", "--hardlinks", value]).unwrap();
+            match cli.command.unwrap() {
+                Commands::Scan(args) => assert_eq!(args.hardlinks, Some(expected)),
+                _ => panic!("Expected Scan command"),
+            }
+        }
+        assert!(Cli::try_parse_from(["rustdupe", "scan", "/path", "--hardlinks", "keep"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_sample_above() {
+        let cli =
+            Cli::try_parse_from(["rustdupe", "scan", "/path", "--sample-above", "1GiB"]).unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => assert_eq!(args.sample_above, Some(1_073_741_824)),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_checkpoint_and_resume() {
+        let cli = Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "/path",
+            "--checkpoint",
+            "scan.ckpt",
+            "--resume",
+            "old.ckpt",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            Commands::Scan(args) => {
+                assert_eq!(args.checkpoint, Some(PathBuf::from("scan.ckpt")));
+                assert_eq!(args.resume, Some(PathBuf::from("old.ckpt")));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "rustdupe",
+            "scan",
+            "--load-session",
+            "s.json",
+            "--resume",
+            "old.ckpt"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_print_exit_codes_flag() {
+        let cli = Cli::try_parse_from(["rustdupe", "--print-exit-codes", "scan", "/path"]).unwrap();
+        assert!(cli.print_exit_codes);
+
+        let cli = Cli::try_parse_from(["rustdupe", "scan", "/path"]).unwrap();
+        assert!(!cli.print_exit_codes);
+        assert!(!cli.is_info_request());
+
+        let cli = Cli::try_parse_from(["rustdupe", "--print-exit-codes"]).unwrap();
+        assert!(cli.print_exit_codes);
+        assert!(cli.command.is_none());
+        assert!(cli.is_info_request());
+    }
+
+    #[test]
+    fn test_cli_parse_schema_version_flag() {
+        let cli = Cli::try_parse_from(["rustdupe", "--schema-version", "scan", "/path"]).unwrap();
+        assert!(cli.schema_version);
+
+        let cli = Cli::try_parse_from(["rustdupe", "--schema-version"]).unwrap();
+        assert!(cli.schema_version);
+        assert!(cli.command.is_none());
+        assert!(cli.is_info_request());
+    }
+
     #[test]
     fn test_cli_parse_scan_similarity_threshold() {
         let cli =
             Cli::try_parse_from(["rustdupe", "scan", "/path", "--similarity-threshold", "15"])
                 .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             Commands::Scan(args) => {
                 assert_eq!(args.similarity_threshold, Some(15));
             }
@@ -44,6 +44,19 @@
 //! - Special keys: `PageUp`, `PageDown`, `Home`, `End`, `Delete`
 //! - Function keys: `F1`, `F2`, ..., `F12`
 //! - With modifiers: `Ctrl+c`, `Alt+j`, `Shift+Enter`, `Ctrl+Shift+a`
+//!
+//! # Intentional Copies
+//!
+//! Groups that look like deliberate copies are tagged and listed last in the
+//! TUI. The `intentional` section tunes the patterns; omitted keys keep their
+//! defaults:
+//!
+//! ```toml
+//! [intentional]
+//! backup_suffixes = [".bak", ".orig", ".save"]
+//! boilerplate_names = ["LICENSE", "__init__.py"]
+//! parallel_trees = false
+//! ```
 
 use anyhow::Result;
 use directories::ProjectDirs;
@@ -57,7 +70,12 @@ use std::fs;
 use std::path::PathBuf;
 use strsim::levenshtein;
 
-use crate::cli::{FileType, OutputFormat, ThemeArg};
+use crate::cli::{
+    FileType, IoThreadsArg, OutputFormat, PlanKeepArg, ProgressFormat, StatsOutputFormat, ThemeArg,
+};
+use crate::duplicates::{HardlinkPolicy, IntentionalPatterns, ParanoidConfig, ProtectPolicy};
+use crate::output::size::SizeFormat;
+use crate::tui::app::ColumnPreset;
 use crate::tui::keybindings::KeybindingProfile;
 
 /// Type alias for custom keybinding overrides.
@@ -146,11 +164,40 @@ pub struct Config {
     #[serde(default)]
     pub accessibility: AccessibilityConfig,
 
+    /// Show full modification timestamps in the TUI instead of relative ages.
+    #[serde(default)]
+    pub absolute_times: bool,
+
+    /// Columns shown in the TUI lists (`standard`, `full`, `compact` or
+    /// `paths`). Updated when cycled with the column toggle.
+    #[serde(default)]
+    pub tui_columns: ColumnPreset,
+
+    /// Units for displayed sizes (`iec-proper`, `iec` or `si`).
+    #[serde(default)]
+    pub size_units: SizeFormat,
+
+    /// Compare paths case-insensitively on case-sensitive file systems.
+    #[serde(default)]
+    pub ignore_case_paths: bool,
+
     // Scanning Defaults
-    /// Follow symbolic links during scan.
+    /// Follow symbolic links during scan (alias setting both kinds below).
     #[serde(default)]
     pub follow_symlinks: bool,
 
+    /// Descend into symlinked directories during scan.
+    #[serde(default)]
+    pub follow_dir_symlinks: bool,
+
+    /// Scan the targets of symlinked files.
+    #[serde(default)]
+    pub follow_file_symlinks: bool,
+
+    /// Report symlinks whose target does not exist.
+    #[serde(default)]
+    pub report_broken_symlinks: bool,
+
     /// Skip hidden files and directories.
     #[serde(default)]
     pub skip_hidden: bool,
@@ -171,10 +218,31 @@ pub struct Config {
     #[serde(default)]
     pub older_than: Option<chrono::DateTime<chrono::Utc>>,
 
+    /// Skip, and refuse to delete, files modified within this many seconds.
+    #[serde(default)]
+    pub settle: Option<u64>,
+
+    /// Only scan and delete files owned by these users (uids or names).
+    #[serde(default)]
+    pub only_owner: Vec<String>,
+
+    /// Never scan or delete files owned by these users (uids or names).
+    #[serde(default)]
+    pub exclude_owner: Vec<String>,
+
     /// Number of I/O threads for hashing.
     #[serde(default = "default_io_threads")]
     pub io_threads: usize,
 
+    /// Pick the I/O thread count from the scan roots' storage type,
+    /// overriding `io_threads`.
+    #[serde(default)]
+    pub io_threads_auto: bool,
+
+    /// Number of CPU threads for traversal and in-memory work (all cores if unset).
+    #[serde(default)]
+    pub cpu_threads: Option<usize>,
+
     /// I/O buffer size for streaming operations (manual override).
     #[serde(default)]
     pub io_buffer_size: Option<usize>,
@@ -187,14 +255,57 @@ pub struct Config {
     #[serde(default = "default_buffer_max")]
     pub io_buffer_max: usize,
 
+    /// Streaming read buffer size for hashing (power of two).
+    ///
+    /// Sets the same buffer as `io_buffer_size` and takes precedence over it.
+    #[serde(default)]
+    pub read_buffer_size: Option<usize>,
+
+    /// Skip reading the holes of sparse files when hashing.
+    #[serde(default)]
+    pub sparse_aware: bool,
+
+    /// Fold alternate data streams and resource forks into content hashes.
+    #[serde(default)]
+    pub include_streams: bool,
+
+    /// Retries for transient I/O errors while hashing a file.
+    #[serde(default)]
+    pub hash_retries: u32,
+
     /// Automatically adjust buffer size based on system resources.
     #[serde(default = "default_true")]
     pub io_adaptive_buffer: bool,
 
+    /// Sort groups and files deterministically after a scan.
+    #[serde(default = "default_true")]
+    pub stable_order: bool,
+
+    /// Count files before walking so walk progress has a total.
+    #[serde(default)]
+    pub precount: bool,
+
+    /// Only hash files whose basename occurs more than once (approximate).
+    #[serde(default)]
+    pub same_name_only: bool,
+
+    /// Patterns tagging groups of likely-intentional copies.
+    #[serde(default)]
+    pub intentional: IntentionalPatterns,
+
     /// Fail-fast on any error during scan.
     #[serde(default)]
     pub strict: bool,
 
+    /// Scan errors tolerated before exiting with `TooManyErrors`.
+    #[serde(default)]
+    pub max_errors: Option<usize>,
+
+    /// Percentage of scanned files with errors tolerated before exiting
+    /// with `TooManyErrors`.
+    #[serde(default)]
+    pub max_error_rate: Option<f64>,
+
     /// Enable similar image detection using perceptual hashing.
     #[serde(default)]
     pub similar_images: bool,
@@ -203,6 +314,14 @@ pub struct Config {
     #[serde(default)]
     pub similar_documents: bool,
 
+    /// Report pairs of files sharing most of their content-defined chunks.
+    #[serde(default)]
+    pub partial_similarity: bool,
+
+    /// Shared content fraction reported as a partial duplicate.
+    #[serde(default)]
+    pub partial_threshold: Option<f64>,
+
     /// Enable memory-mapped file I/O for hashing large files.
     #[serde(default)]
     pub mmap: bool,
@@ -211,15 +330,39 @@ pub struct Config {
     #[serde(default = "default_mmap_threshold")]
     pub mmap_threshold: u64,
 
+    /// Compare files larger than this many bytes by sampled hashing.
+    #[serde(default)]
+    pub sample_above: Option<u64>,
+
     /// Enable paranoid mode (byte-by-byte verification).
     #[serde(default)]
     pub paranoid: bool,
 
+    /// Bytes read from each file per step of paranoid comparison.
+    #[serde(default)]
+    pub paranoid_chunk_size: Option<usize>,
+
+    /// Maximum files of a group compared at once in paranoid mode.
+    #[serde(default)]
+    pub paranoid_max_open_files: Option<usize>,
+
+    /// Report zero-byte files as a single labeled group.
+    #[serde(default)]
+    pub include_empty: bool,
+
+    /// Report advisory groups of files sharing a normalized filename.
+    #[serde(default)]
+    pub name_cluster: bool,
+
     // Filtering Defaults
     /// Glob patterns to ignore.
     #[serde(default)]
     pub ignore_patterns: Vec<String>,
 
+    /// Directories to prune during traversal (names or paths).
+    #[serde(default)]
+    pub exclude_dirs: Vec<PathBuf>,
+
     /// Regex patterns to include.
     #[serde(default)]
     pub regex_include: Vec<String>,
@@ -232,6 +375,14 @@ pub struct Config {
     #[serde(default)]
     pub file_types: Vec<FileType>,
 
+    /// Detect file types from magic bytes instead of extensions.
+    #[serde(default)]
+    pub sniff_content: bool,
+
+    /// Filter by explicit file extensions.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+
     // Cache Defaults
     /// Disable hash caching.
     #[serde(default)]
@@ -241,20 +392,77 @@ pub struct Config {
     #[serde(default)]
     pub cache: Option<PathBuf>,
 
+    /// Store the hash cache zstd-compressed on disk.
+    #[serde(default)]
+    pub cache_compress: bool,
+
     // Safety & Deletion Defaults
     /// Use permanent deletion instead of moving to trash.
     #[serde(default)]
     pub permanent: bool,
 
+    /// Directories whose files are permanently deleted instead of trashed.
+    #[serde(default)]
+    pub permanent_under: Vec<PathBuf>,
+
+    /// Refuse deletion runs that would delete more than this many bytes.
+    #[serde(default)]
+    pub max_total_delete: Option<u64>,
+
+    /// Move deleted files into this directory instead of the system trash.
+    #[serde(default)]
+    pub trash_dir: Option<PathBuf>,
+
+    /// Restore already-trashed files when a deletion batch fails part way.
+    #[serde(default)]
+    pub transactional_delete: bool,
+
+    /// Compress saved sessions regardless of their extension.
+    #[serde(default)]
+    pub session_compression: Option<crate::session::SessionCompression>,
+
     /// Do not perform any deletions (read-only mode).
     #[serde(default)]
     pub dry_run: bool,
 
+    /// Remove directories left empty after deleting files.
+    #[serde(default)]
+    pub delete_empty_dirs: bool,
+
+    /// Sed-style substitution applied to surviving keepers after deletion.
+    #[serde(default)]
+    pub rename_keeper: Option<String>,
+
+    /// Carry extended attributes and ACLs over to renamed keepers and
+    /// `--trash-dir` moves.
+    #[serde(default)]
+    pub preserve_xattrs: bool,
+
+    /// Deleting more files than this in the TUI requires typing DELETE.
+    #[serde(default = "default_confirm_threshold_count")]
+    pub confirm_threshold_count: usize,
+
+    /// Deleting more bytes than this in the TUI requires typing DELETE.
+    #[serde(default = "default_confirm_threshold_bytes")]
+    pub confirm_threshold_bytes: u64,
+
+    /// Policy for auto-protecting one file per duplicate group.
+    #[serde(default)]
+    pub protect: ProtectPolicy,
+
+    /// How hardlinks to already-seen files are treated.
+    #[serde(default)]
+    pub hardlinks: HardlinkPolicy,
+
     // Output Defaults
     /// Default output format.
     #[serde(default)]
     pub output: OutputFormat,
 
+    /// Progress display while scanning (`bar` or `json`).
+    #[serde(default)]
+    pub progress_format: ProgressFormat,
+
     /// Threshold for similarity matching (Hamming distance).
     #[serde(default)]
     pub similarity_threshold: Option<u32>,
@@ -271,6 +479,10 @@ pub struct Config {
     #[serde(default = "default_min_group_size")]
     pub min_group_size: usize,
 
+    /// Boundary directories that every file of a duplicate group must share.
+    #[serde(default)]
+    pub dedupe_within: Vec<PathBuf>,
+
     // HTML Report Defaults
     /// Enable image thumbnails in HTML reports.
     #[serde(default)]
@@ -284,10 +496,54 @@ pub struct Config {
     #[serde(default = "default_true")]
     pub html_thumbnail_embed: bool,
 
+    /// Emit static HTML reports without the interactive script.
+    #[serde(default)]
+    pub html_plain: bool,
+
     /// Export only files selected for deletion.
     #[serde(default)]
     pub export_selected: bool,
 
+    /// Emit minified JSON output instead of pretty-printed.
+    #[serde(default)]
+    pub json_compact: bool,
+
+    /// Field delimiter for CSV output.
+    #[serde(default = "default_csv_delimiter")]
+    pub csv_delimiter: char,
+
+    /// Field separator for compact output.
+    #[serde(default = "default_compact_field_separator")]
+    pub compact_field_separator: String,
+
+    /// Separator between paths in compact output.
+    #[serde(default = "default_compact_path_separator")]
+    pub compact_path_separator: String,
+
+    /// Print human-readable sizes in compact output.
+    #[serde(default)]
+    pub human_sizes: bool,
+
+    /// Keeper rule for plan output (None = default rule).
+    #[serde(default)]
+    pub plan_keep: Option<PlanKeepArg>,
+
+    /// Format of plan output.
+    #[serde(default)]
+    pub plan_format: StatsOutputFormat,
+
+    /// Write JSON, YAML and CSV file paths relative to this directory.
+    #[serde(default)]
+    pub json_paths_relative: Option<PathBuf>,
+
+    /// Print only summary statistics, without duplicate groups.
+    #[serde(default)]
+    pub summary_only: bool,
+
+    /// Ring the bell and send a desktop notification when a scan finishes.
+    #[serde(default)]
+    pub notify: bool,
+
     // Named Profiles
     /// Named configuration profiles.
     ///
@@ -324,6 +580,26 @@ fn default_thumbnail_size() -> u32 {
     100
 }
 
+fn default_csv_delimiter() -> char {
+    ','
+}
+
+fn default_compact_field_separator() -> String {
+    crate::output::compact::DEFAULT_FIELD_SEPARATOR.to_string()
+}
+
+fn default_compact_path_separator() -> String {
+    crate::output::compact::DEFAULT_PATH_SEPARATOR.to_string()
+}
+
+fn default_confirm_threshold_count() -> usize {
+    crate::tui::app::DEFAULT_CONFIRM_THRESHOLD_COUNT
+}
+
+fn default_confirm_threshold_bytes() -> u64 {
+    crate::tui::app::DEFAULT_CONFIRM_THRESHOLD_BYTES
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -331,40 +607,98 @@ impl Default for Config {
             keybinding_profile: KeybindingProfile::Universal,
             custom_keybindings: CustomKeybindings::new(),
             accessibility: AccessibilityConfig::default(),
+            absolute_times: false,
+            tui_columns: ColumnPreset::default(),
+            size_units: SizeFormat::default(),
+            ignore_case_paths: false,
             follow_symlinks: false,
+            follow_dir_symlinks: false,
+            follow_file_symlinks: false,
+            report_broken_symlinks: false,
             skip_hidden: false,
             min_size: None,
             max_size: None,
             newer_than: None,
             older_than: None,
+            settle: None,
+            only_owner: Vec::new(),
+            exclude_owner: Vec::new(),
             io_threads: 4,
+            io_threads_auto: false,
+            cpu_threads: None,
             io_buffer_size: None,
             io_buffer_min: 64 * 1024,
             io_buffer_max: 16 * 1024 * 1024,
+            read_buffer_size: None,
+            sparse_aware: false,
+            include_streams: false,
+            hash_retries: 0,
             io_adaptive_buffer: true,
+            stable_order: true,
+            precount: false,
+            same_name_only: false,
+            intentional: IntentionalPatterns::default(),
             strict: false,
+            max_errors: None,
+            max_error_rate: None,
             similar_images: false,
             similar_documents: false,
+            partial_similarity: false,
+            partial_threshold: None,
             mmap: false,
             mmap_threshold: 64 * 1024 * 1024,
+            sample_above: None,
             paranoid: false,
+            paranoid_chunk_size: None,
+            paranoid_max_open_files: None,
+            include_empty: false,
+            name_cluster: false,
             ignore_patterns: Vec::new(),
+            exclude_dirs: Vec::new(),
             regex_include: Vec::new(),
             regex_exclude: Vec::new(),
             file_types: Vec::new(),
+            sniff_content: false,
+            extensions: Vec::new(),
             no_cache: false,
             cache: None,
+            cache_compress: false,
             permanent: false,
+            permanent_under: Vec::new(),
+            max_total_delete: None,
+            trash_dir: None,
+            transactional_delete: false,
+            session_compression: None,
             dry_run: false,
+            delete_empty_dirs: false,
+            rename_keeper: None,
+            preserve_xattrs: false,
+            confirm_threshold_count: default_confirm_threshold_count(),
+            confirm_threshold_bytes: default_confirm_threshold_bytes(),
+            protect: ProtectPolicy::None,
+            hardlinks: HardlinkPolicy::Skip,
             output: OutputFormat::Tui,
+            progress_format: ProgressFormat::default(),
             similarity_threshold: None,
             doc_similarity_threshold: None,
             bloom_fp_rate: 0.01,
             min_group_size: 2,
+            dedupe_within: Vec::new(),
             html_thumbnails: false,
             html_thumbnail_size: 100,
             html_thumbnail_embed: true,
+            html_plain: false,
             export_selected: false,
+            json_compact: false,
+            csv_delimiter: ',',
+            compact_field_separator: default_compact_field_separator(),
+            compact_path_separator: default_compact_path_separator(),
+            human_sizes: false,
+            plan_keep: None,
+            plan_format: StatsOutputFormat::Human,
+            json_paths_relative: None,
+            summary_only: false,
+            notify: false,
             profile: HashMap::new(),
         }
     }
@@ -476,6 +810,19 @@ impl Config {
         self.accessibility.enabled = true;
     }
 
+    /// Byte-comparison settings for paranoid mode.
+    #[must_use]
+    pub fn paranoid_config(&self) -> ParanoidConfig {
+        let mut paranoid = ParanoidConfig::default();
+        if let Some(size) = self.paranoid_chunk_size {
+            paranoid = paranoid.with_chunk_size(size);
+        }
+        if let Some(count) = self.paranoid_max_open_files {
+            paranoid = paranoid.with_max_open_files(count);
+        }
+        paranoid
+    }
+
     /// Merge global CLI arguments into the configuration.
     pub fn merge_cli(&mut self, cli: &crate::cli::Cli) {
         if let Some(theme) = cli.theme {
@@ -490,6 +837,12 @@ impl Config {
         if cli.no_accessible {
             self.accessibility.enabled = false;
         }
+        if cli.absolute_times {
+            self.absolute_times = true;
+        }
+        if let Some(units) = cli.size_units {
+            self.size_units = units;
+        }
         if cli.no_color {
             std::env::set_var("NO_COLOR", "1");
         }
@@ -500,11 +853,28 @@ impl Config {
 
     /// Merge scan arguments into the configuration.
     pub fn merge_scan_args(&mut self, args: &crate::cli::ScanArgs) {
+        if args.ignore_case_paths {
+            self.ignore_case_paths = true;
+        }
+        if args.no_ignore_case_paths {
+            self.ignore_case_paths = false;
+        }
         if args.follow_symlinks {
             self.follow_symlinks = true;
         }
+        if args.follow_dir_symlinks {
+            self.follow_dir_symlinks = true;
+        }
+        if args.follow_file_symlinks {
+            self.follow_file_symlinks = true;
+        }
+        if args.report_broken_symlinks {
+            self.report_broken_symlinks = true;
+        }
         if args.no_follow_symlinks {
             self.follow_symlinks = false;
+            self.follow_dir_symlinks = false;
+            self.follow_file_symlinks = false;
         }
         if args.skip_hidden {
             self.skip_hidden = true;
@@ -524,11 +894,42 @@ impl Config {
         if let Some(older) = args.older_than {
             self.older_than = Some(chrono::DateTime::from(older));
         }
-        if let Some(threads) = args.io_threads {
-            self.io_threads = threads;
+        // Relative windows are pinned to absolute times as the scan starts
+        let scan_start = std::time::SystemTime::now();
+        if let Some(within) = args.modified_within {
+            self.newer_than = Some(chrono::DateTime::from(crate::cli::time_ago(
+                scan_start, within,
+            )));
+        }
+        if let Some(before) = args.modified_before {
+            self.older_than = Some(chrono::DateTime::from(crate::cli::time_ago(
+                scan_start, before,
+            )));
+        }
+        if let Some(settle) = args.settle {
+            self.settle = Some(settle);
+        }
+        if !args.only_owner.is_empty() {
+            self.only_owner = args.only_owner.clone();
+        }
+        if !args.exclude_owner.is_empty() {
+            self.exclude_owner = args.exclude_owner.clone();
+        }
+        match args.io_threads {
+            Some(IoThreadsArg::Auto) => self.io_threads_auto = true,
+            Some(IoThreadsArg::Count(threads)) => {
+                self.io_threads = threads;
+                self.io_threads_auto = false;
+            }
+            None => {}
+        }
+        if let Some(threads) = args.cpu_threads {
+            self.cpu_threads = Some(threads);
         }
         if let Some(size) = args.io_buffer_size {
             self.io_buffer_size = Some(size);
+            // A configured read_buffer_size would otherwise take precedence
+            self.read_buffer_size = None;
         }
         if let Some(min) = args.io_buffer_min {
             self.io_buffer_min = min;
@@ -536,18 +937,60 @@ impl Config {
         if let Some(max) = args.io_buffer_max {
             self.io_buffer_max = max;
         }
+        if let Some(size) = args.read_buffer {
+            self.read_buffer_size = Some(size);
+        }
+        if args.sparse_aware {
+            self.sparse_aware = true;
+        }
+        if args.no_sparse_aware {
+            self.sparse_aware = false;
+        }
+        if args.include_streams {
+            self.include_streams = true;
+        }
+        if args.no_include_streams {
+            self.include_streams = false;
+        }
+        if let Some(retries) = args.hash_retries {
+            self.hash_retries = retries;
+        }
         if args.io_adaptive_buffer {
             self.io_adaptive_buffer = true;
         }
         if args.no_io_adaptive_buffer {
             self.io_adaptive_buffer = false;
         }
+        if args.stable_order {
+            self.stable_order = true;
+        }
+        if args.no_stable_order {
+            self.stable_order = false;
+        }
+        if args.precount {
+            self.precount = true;
+        }
+        if args.no_precount {
+            self.precount = false;
+        }
+        if args.same_name_only {
+            self.same_name_only = true;
+        }
+        if args.no_same_name_only {
+            self.same_name_only = false;
+        }
         if args.strict {
             self.strict = true;
         }
         if args.no_strict {
             self.strict = false;
         }
+        if let Some(max) = args.max_errors {
+            self.max_errors = Some(max);
+        }
+        if let Some(rate) = args.max_error_rate {
+            self.max_error_rate = Some(rate);
+        }
         if args.similar_images {
             self.similar_images = true;
         }
@@ -560,6 +1003,12 @@ impl Config {
         if args.no_similar_documents {
             self.similar_documents = false;
         }
+        if args.partial_similarity {
+            self.partial_similarity = true;
+        }
+        if args.no_partial_similarity {
+            self.partial_similarity = false;
+        }
         if args.mmap {
             self.mmap = true;
         }
@@ -569,15 +1018,42 @@ impl Config {
         if let Some(threshold) = args.mmap_threshold {
             self.mmap_threshold = threshold;
         }
+        if let Some(threshold) = args.sample_above {
+            self.sample_above = Some(threshold);
+        }
+        if let Some(policy) = args.hardlinks {
+            self.hardlinks = policy;
+        }
         if args.paranoid {
             self.paranoid = true;
         }
         if args.no_paranoid {
             self.paranoid = false;
         }
+        if let Some(size) = args.paranoid_chunk {
+            self.paranoid_chunk_size = Some(size);
+        }
+        if let Some(count) = args.paranoid_max_open {
+            self.paranoid_max_open_files = Some(count);
+        }
+        if args.include_empty {
+            self.include_empty = true;
+        }
+        if args.no_include_empty {
+            self.include_empty = false;
+        }
+        if args.name_cluster {
+            self.name_cluster = true;
+        }
+        if args.no_name_cluster {
+            self.name_cluster = false;
+        }
         if !args.ignore_patterns.is_empty() {
             self.ignore_patterns = args.ignore_patterns.clone();
         }
+        if !args.exclude_dirs.is_empty() {
+            self.exclude_dirs = args.exclude_dirs.clone();
+        }
         if !args.regex_include.is_empty() {
             self.regex_include = args.regex_include.clone();
         }
@@ -587,6 +1063,12 @@ impl Config {
         if !args.file_types.is_empty() {
             self.file_types = args.file_types.clone();
         }
+        if args.sniff_content {
+            self.sniff_content = true;
+        }
+        if !args.extensions.is_empty() {
+            self.extensions = args.extensions.clone();
+        }
         if args.no_cache {
             self.no_cache = true;
         }
@@ -596,6 +1078,9 @@ impl Config {
         if let Some(cache) = &args.cache {
             self.cache = Some(cache.clone());
         }
+        if args.cache_compress {
+            self.cache_compress = true;
+        }
         if args.permanent {
             self.permanent = true;
         }
@@ -608,9 +1093,45 @@ impl Config {
         if args.no_dry_run {
             self.dry_run = false;
         }
+        if args.delete_empty_dirs {
+            self.delete_empty_dirs = true;
+        }
+        if let Some(rename) = &args.rename_keeper {
+            self.rename_keeper = Some(rename.clone());
+        }
+        if args.preserve_xattrs {
+            self.preserve_xattrs = true;
+        }
+        if let Some(count) = args.confirm_threshold_count {
+            self.confirm_threshold_count = count;
+        }
+        if let Some(bytes) = args.confirm_threshold_bytes {
+            self.confirm_threshold_bytes = bytes;
+        }
+        if let Some(protect) = args.protect {
+            self.protect = protect;
+        }
+        if !args.permanent_under.is_empty() {
+            self.permanent_under = args.permanent_under.clone();
+        }
+        if let Some(bytes) = args.max_total_delete {
+            self.max_total_delete = Some(bytes);
+        }
+        if let Some(ref dir) = args.trash_dir {
+            self.trash_dir = Some(dir.clone());
+        }
+        if args.transactional_delete {
+            self.transactional_delete = true;
+        }
+        if let Some(compression) = args.session_compression {
+            self.session_compression = Some(compression);
+        }
         if let Some(output) = args.output {
             self.output = output;
         }
+        if let Some(format) = args.progress_format {
+            self.progress_format = format;
+        }
         if let Some(rate) = args.bloom_fp_rate {
             self.bloom_fp_rate = rate;
         }
@@ -620,9 +1141,15 @@ impl Config {
         if let Some(threshold) = args.doc_similarity_threshold {
             self.doc_similarity_threshold = Some(threshold);
         }
+        if let Some(threshold) = args.partial_threshold {
+            self.partial_threshold = Some(threshold);
+        }
         if let Some(min_group) = args.min_group_size {
             self.min_group_size = min_group;
         }
+        if !args.dedupe_within.is_empty() {
+            self.dedupe_within = args.dedupe_within.clone();
+        }
         if args.html_thumbnails {
             self.html_thumbnails = true;
         }
@@ -635,25 +1162,154 @@ impl Config {
         if args.html_thumbnail_link {
             self.html_thumbnail_embed = false;
         }
+        if args.html_plain {
+            self.html_plain = true;
+        }
+        if args.no_html_plain {
+            self.html_plain = false;
+        }
         if args.export_selected {
             self.export_selected = true;
         }
+        if args.json_compact {
+            self.json_compact = true;
+        }
+        if args.no_json_compact {
+            self.json_compact = false;
+        }
+        if let Some(delimiter) = args.csv_delimiter {
+            self.csv_delimiter = delimiter;
+        }
+        if let Some(ref separator) = args.compact_field_sep {
+            self.compact_field_separator = separator.clone();
+        }
+        if let Some(ref separator) = args.compact_path_sep {
+            self.compact_path_separator = separator.clone();
+        }
+        if let Some(keep) = args.plan_keep {
+            self.plan_keep = Some(keep);
+        }
+        if let Some(format) = args.plan_format {
+            self.plan_format = format;
+        }
+        if args.human_sizes {
+            self.human_sizes = true;
+        }
+        if args.no_human_sizes {
+            self.human_sizes = false;
+        }
+        if let Some(ref base) = args.json_paths_relative {
+            self.json_paths_relative = Some(base.clone());
+        }
+        if args.summary_only {
+            self.summary_only = true;
+        }
+        if args.no_summary_only {
+            self.summary_only = false;
+        }
+        if args.notify {
+            self.notify = true;
+        }
+        if args.no_notify {
+            self.notify = false;
+        }
     }
 
     /// Merge load arguments into the configuration.
     pub fn merge_load_args(&mut self, args: &crate::cli::LoadArgs) {
+        if args.ignore_case_paths {
+            self.ignore_case_paths = true;
+        }
+        if args.no_ignore_case_paths {
+            self.ignore_case_paths = false;
+        }
         if args.dry_run {
             self.dry_run = true;
         }
         if args.no_dry_run {
             self.dry_run = false;
         }
+        if args.delete_empty_dirs {
+            self.delete_empty_dirs = true;
+        }
+        if let Some(rename) = &args.rename_keeper {
+            self.rename_keeper = Some(rename.clone());
+        }
+        if args.preserve_xattrs {
+            self.preserve_xattrs = true;
+        }
+        if let Some(count) = args.confirm_threshold_count {
+            self.confirm_threshold_count = count;
+        }
+        if let Some(bytes) = args.confirm_threshold_bytes {
+            self.confirm_threshold_bytes = bytes;
+        }
+        if let Some(protect) = args.protect {
+            self.protect = protect;
+        }
+        if !args.permanent_under.is_empty() {
+            self.permanent_under = args.permanent_under.clone();
+        }
+        if let Some(bytes) = args.max_total_delete {
+            self.max_total_delete = Some(bytes);
+        }
+        if let Some(ref dir) = args.trash_dir {
+            self.trash_dir = Some(dir.clone());
+        }
+        if args.transactional_delete {
+            self.transactional_delete = true;
+        }
+        if let Some(compression) = args.session_compression {
+            self.session_compression = Some(compression);
+        }
         if let Some(output) = args.output {
             self.output = output;
         }
         if args.export_selected {
             self.export_selected = true;
         }
+        if args.html_plain {
+            self.html_plain = true;
+        }
+        if args.no_html_plain {
+            self.html_plain = false;
+        }
+        if args.json_compact {
+            self.json_compact = true;
+        }
+        if args.no_json_compact {
+            self.json_compact = false;
+        }
+        if let Some(delimiter) = args.csv_delimiter {
+            self.csv_delimiter = delimiter;
+        }
+        if let Some(ref separator) = args.compact_field_sep {
+            self.compact_field_separator = separator.clone();
+        }
+        if let Some(ref separator) = args.compact_path_sep {
+            self.compact_path_separator = separator.clone();
+        }
+        if let Some(keep) = args.plan_keep {
+            self.plan_keep = Some(keep);
+        }
+        if let Some(format) = args.plan_format {
+            self.plan_format = format;
+        }
+        if args.human_sizes {
+            self.human_sizes = true;
+        }
+        if args.no_human_sizes {
+            self.human_sizes = false;
+        }
+        if let Some(ref base) = args.json_paths_relative {
+            self.json_paths_relative = Some(base.clone());
+        }
+        if args.summary_only {
+            self.summary_only = true;
+        }
+        if args.no_summary_only {
+            self.summary_only = false;
+        }
     }
 }
 
@@ -664,32 +1320,79 @@ fn validate_config_keys(doc: &toml_edit::DocumentMut, path: &str, content: &str)
         "keybinding_profile",
         "custom_keybindings",
         "accessibility",
+        "absolute_times",
+        "tui_columns",
+        "size_units",
+        "ignore_case_paths",
         "follow_symlinks",
+        "follow_dir_symlinks",
+        "follow_file_symlinks",
+        "report_broken_symlinks",
         "skip_hidden",
         "min_size",
         "max_size",
         "newer_than",
         "older_than",
+        "settle",
+        "only_owner",
+        "exclude_owner",
         "io_threads",
+        "io_threads_auto",
+        "cpu_threads",
         "io_buffer_size",
         "io_buffer_min",
         "io_buffer_max",
+        "read_buffer_size",
+        "sparse_aware",
+        "include_streams",
+        "hash_retries",
         "io_adaptive_buffer",
+        "stable_order",
+        "precount",
+        "same_name_only",
+        "intentional",
         "strict",
+        "max_errors",
+        "max_error_rate",
         "similar_images",
         "similar_documents",
+        "partial_similarity",
+        "partial_threshold",
         "mmap",
         "mmap_threshold",
+        "sample_above",
         "paranoid",
+        "paranoid_chunk_size",
+        "paranoid_max_open_files",
+        "include_empty",
+        "name_cluster",
         "ignore_patterns",
+        "exclude_dirs",
+        "dedupe_within",
         "regex_include",
         "regex_exclude",
         "file_types",
+        "sniff_content",
+        "extensions",
         "no_cache",
         "cache",
+        "cache_compress",
         "permanent",
+        "permanent_under",
+        "max_total_delete",
+        "trash_dir",
+        "transactional_delete",
+        "session_compression",
         "dry_run",
+        "delete_empty_dirs",
+        "rename_keeper",
+        "preserve_xattrs",
+        "confirm_threshold_count",
+        "confirm_threshold_bytes",
+        "protect",
+        "hardlinks",
         "output",
+        "progress_format",
         "similarity_threshold",
         "doc_similarity_threshold",
         "bloom_fp_rate",
@@ -697,7 +1400,18 @@ fn validate_config_keys(doc: &toml_edit::DocumentMut, path: &str, content: &str)
         "html_thumbnails",
         "html_thumbnail_size",
         "html_thumbnail_embed",
+        "html_plain",
         "export_selected",
+        "json_compact",
+        "csv_delimiter",
+        "compact_field_separator",
+        "compact_path_separator",
+        "human_sizes",
+        "plan_keep",
+        "plan_format",
+        "json_paths_relative",
+        "summary_only",
+        "notify",
         "profile",
     ];
 
@@ -723,6 +1437,10 @@ fn validate_config_keys(doc: &toml_edit::DocumentMut, path: &str, content: &str)
             if let Some(table) = item.as_table() {
                 validate_accessibility_keys(table, path, content);
             }
+        } else if key == "intentional" {
+            if let Some(table) = item.as_table() {
+                validate_intentional_keys(table, path, content);
+            }
         } else if key == "profile" {
             if let Some(profiles) = item.as_table() {
                 for (profile_name, profile_item) in profiles.iter() {
@@ -768,6 +1486,34 @@ fn validate_accessibility_keys(table: &toml_edit::Table, path: &str, content: &s
     }
 }
 
+/// Validate intentional-copy pattern keys.
+fn validate_intentional_keys(table: &toml_edit::Table, path: &str, content: &str) {
+    let valid_keys = [
+        "enabled",
+        "backup_suffixes",
+        "boilerplate_names",
+        "parallel_trees",
+    ];
+
+    for (key, _) in table.iter() {
+        if !valid_keys.contains(&key) {
+            let line = get_line_number_in_table(table, key, content);
+            let suggestion = find_best_match(key, &valid_keys);
+            if let Some(s) = suggestion {
+                eprintln!(
+                    "Warning: Unknown intentional field '{}' at line {} in {}. Did you mean '{}'?",
+                    key, line, path, s
+                );
+            } else {
+                eprintln!(
+                    "Warning: Unknown intentional field '{}' at line {} in {}.",
+                    key, line, path
+                );
+            }
+        }
+    }
+}
+
 /// Validate keys within a profile section.
 fn validate_profile_keys(table: &toml_edit::Table, path: &str, content: &str) {
     let valid_keys = [
@@ -775,39 +1521,97 @@ fn validate_profile_keys(table: &toml_edit::Table, path: &str, content: &str) {
         "keybinding_profile",
         "custom_keybindings",
         "accessibility",
+        "absolute_times",
+        "tui_columns",
+        "size_units",
+        "ignore_case_paths",
         "follow_symlinks",
+        "follow_dir_symlinks",
+        "follow_file_symlinks",
+        "report_broken_symlinks",
         "skip_hidden",
         "min_size",
         "max_size",
         "newer_than",
         "older_than",
+        "settle",
+        "only_owner",
+        "exclude_owner",
         "io_threads",
+        "io_threads_auto",
+        "cpu_threads",
         "io_buffer_size",
         "io_buffer_min",
         "io_buffer_max",
+        "read_buffer_size",
+        "sparse_aware",
+        "include_streams",
+        "hash_retries",
         "io_adaptive_buffer",
+        "stable_order",
+        "precount",
+        "same_name_only",
+        "intentional",
         "strict",
+        "max_errors",
+        "max_error_rate",
         "similar_images",
         "similar_documents",
+        "partial_similarity",
+        "partial_threshold",
         "mmap",
         "mmap_threshold",
+        "sample_above",
         "paranoid",
+        "paranoid_chunk_size",
+        "paranoid_max_open_files",
+        "include_empty",
+        "name_cluster",
         "ignore_patterns",
+        "exclude_dirs",
+        "dedupe_within",
         "regex_include",
         "regex_exclude",
         "file_types",
+        "sniff_content",
+        "extensions",
         "no_cache",
         "cache",
+        "cache_compress",
         "permanent",
+        "permanent_under",
+        "max_total_delete",
+        "trash_dir",
+        "transactional_delete",
+        "session_compression",
         "dry_run",
+        "delete_empty_dirs",
+        "rename_keeper",
+        "preserve_xattrs",
+        "confirm_threshold_count",
+        "confirm_threshold_bytes",
+        "protect",
+        "hardlinks",
         "output",
+        "progress_format",
         "similarity_threshold",
         "doc_similarity_threshold",
         "bloom_fp_rate",
         "html_thumbnails",
         "html_thumbnail_size",
         "html_thumbnail_embed",
+        "html_plain",
         "export_selected",
+        "json_compact",
+        "csv_delimiter",
+        "compact_field_separator",
+        "compact_path_separator",
+        "human_sizes",
+        "plan_keep",
+        "plan_format",
+        "json_paths_relative",
+        "summary_only",
+        "notify",
     ];
 
     for (key, _) in table.iter() {
@@ -0,0 +1,583 @@
+//! Resumable scan checkpoints.
+//!
+//! # Overview
+//!
+//! Long multi-directory scans can write a compact checkpoint file that
+//! records how far the pipeline got. A later run started with the same roots
+//! and walker settings can resume from it instead of starting over:
+//!
+//! - After walking and size grouping, the candidate size groups are stored.
+//! - After prehash comparison, the candidate prehash groups are stored.
+//! - While a hashing phase runs, completed hashes are recorded and the file
+//!   is rewritten every [`CHECKPOINT_INTERVAL`] files.
+//!
+//! On resume, completed stages are skipped entirely and files hashed before
+//! the interruption are not read again. The hash cache still applies to the
+//! remaining files.
+//!
+//! Files modified between the interrupted run and the resume are not
+//! re-detected; start a fresh scan if the tree changed substantially.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::duplicates::{DuplicateFinder, FinderConfig, ScanCheckpoint};
+//! use std::path::{Path, PathBuf};
+//!
+//! let checkpoint = ScanCheckpoint::load(Path::new("scan.checkpoint")).unwrap();
+//! let config = FinderConfig::default()
+//!     .with_checkpoint_path(PathBuf::from("scan.checkpoint"))
+//!     .with_resume_checkpoint(checkpoint);
+//! let finder = DuplicateFinder::new(config);
+//! let (groups, _summary) = finder
+//!     .find_duplicates_in_paths(vec![PathBuf::from("/archive")])
+//!     .unwrap();
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::scanner::{FileCategory, FileEntry, Hash, WalkerConfig};
+
+/// Current checkpoint file format version.
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+/// Number of files hashed between periodic checkpoint writes.
+pub const CHECKPOINT_INTERVAL: usize = 1000;
+
+/// Errors that can occur while reading, writing, or validating a checkpoint.
+#[derive(thiserror::Error, Debug)]
+pub enum CheckpointError {
+    /// The checkpoint file could not be read or written.
+    #[error("Checkpoint I/O error for {path}: {source}")]
+    Io {
+        /// Path of the checkpoint file
+        path: PathBuf,
+        /// The underlying I/O error
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The checkpoint file is not valid JSON for this format.
+    #[error("Invalid checkpoint file: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    /// The checkpoint was written by an incompatible version.
+    #[error("Unsupported checkpoint version {found} (expected {expected})")]
+    Version {
+        /// Version found in the file
+        found: u32,
+        /// Version this build understands
+        expected: u32,
+    },
+
+    /// The checkpoint was written for different scan roots or settings.
+    #[error("Checkpoint does not match this scan: {0}")]
+    Mismatch(String),
+}
+
+/// The last pipeline stage fully captured by a checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointStage {
+    /// Walking and size grouping (Phase 1) are complete.
+    SizeGrouped,
+    /// Prehash comparison (Phase 2) is complete.
+    Prehashed,
+}
+
+/// Walker settings that must match for a checkpoint to be resumed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalkerFingerprint {
+    /// Descend into symlinked directories.
+    #[serde(default)]
+    pub follow_dir_symlinks: bool,
+    /// Scan the targets of symlinked files.
+    #[serde(default)]
+    pub follow_file_symlinks: bool,
+    /// Skip hidden files and directories.
+    pub skip_hidden: bool,
+    /// Minimum file size filter.
+    pub min_size: Option<u64>,
+    /// Maximum file size filter.
+    pub max_size: Option<u64>,
+    /// Only include files modified after this time.
+    pub newer_than: Option<SystemTime>,
+    /// Only include files modified before this time.
+    pub older_than: Option<SystemTime>,
+    /// Glob patterns to ignore.
+    pub ignore_patterns: Vec<String>,
+    /// Directories pruned during traversal.
+    #[serde(default)]
+    pub exclude_dirs: Vec<PathBuf>,
+    /// Regex include patterns (source form).
+    pub regex_include: Vec<String>,
+    /// Regex exclude patterns (source form).
+    pub regex_exclude: Vec<String>,
+    /// File categories to include.
+    pub file_categories: Vec<FileCategory>,
+    /// Explicit file extensions to include.
+    pub extensions: Vec<String>,
+    /// Whether zero-byte files are included.
+    pub include_empty: bool,
+    /// Whether hardlinks to already-seen files are included.
+    #[serde(default)]
+    pub include_hardlinks: bool,
+    /// Whether category filters sniff file content.
+    #[serde(default)]
+    pub sniff_content: bool,
+}
+
+impl From<&WalkerConfig> for WalkerFingerprint {
+    fn from(config: &WalkerConfig) -> Self {
+        Self {
+            follow_dir_symlinks: config.follow_dir_symlinks,
+            follow_file_symlinks: config.follow_file_symlinks,
+            skip_hidden: config.skip_hidden,
+            min_size: config.min_size,
+            max_size: config.max_size,
+            newer_than: config.newer_than,
+            older_than: config.older_than,
+            ignore_patterns: config.ignore_patterns.clone(),
+            exclude_dirs: config.exclude_dirs.clone(),
+            regex_include: config
+                .regex_include
+                .iter()
+                .map(|r| r.as_str().to_string())
+                .collect(),
+            regex_exclude: config
+                .regex_exclude
+                .iter()
+                .map(|r| r.as_str().to_string())
+                .collect(),
+            file_categories: config.file_categories.clone(),
+            extensions: config.extensions.clone(),
+            include_empty: config.include_empty,
+            include_hardlinks: config.include_hardlinks,
+            sniff_content: config.sniff_content,
+        }
+    }
+}
+
+/// A group of candidate files carried over to the next phase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointGroup {
+    /// Shared prehash, set once Phase 2 has completed.
+    #[serde(default)]
+    pub prehash: Option<Hash>,
+    /// Candidate files in this group.
+    pub files: Vec<FileEntry>,
+}
+
+/// Summary counters restored on resume.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointCounts {
+    /// Total number of files discovered by the walk.
+    pub total_files: usize,
+    /// Total size of discovered files in bytes.
+    pub total_size: u64,
+    /// Files eliminated by size grouping.
+    pub eliminated_by_size: usize,
+    /// Files eliminated by prehash comparison.
+    pub eliminated_by_prehash: usize,
+}
+
+/// Persisted state of an in-progress multi-directory scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanCheckpoint {
+    /// Checkpoint format version.
+    pub version: u32,
+    /// Scan roots as passed to the finder.
+    pub roots: Vec<PathBuf>,
+    /// Walker settings the scan was started with.
+    pub walker: WalkerFingerprint,
+    /// Last completed stage, or `None` if the walk has not finished.
+    pub stage: Option<CheckpointStage>,
+    /// Summary counters up to the last completed stage.
+    pub counts: CheckpointCounts,
+    /// Candidate groups produced by the last completed stage.
+    pub groups: Vec<CheckpointGroup>,
+    /// Images collected for similarity detection.
+    #[serde(default)]
+    pub images: Vec<FileEntry>,
+    /// Documents collected for similarity detection.
+    #[serde(default)]
+    pub documents: Vec<FileEntry>,
+    /// Files collected for similar-filename clustering.
+    #[serde(default)]
+    pub named_files: Vec<FileEntry>,
+    /// Zero-byte files set aside before size grouping.
+    #[serde(default)]
+    pub empty_files: Vec<FileEntry>,
+    /// Files collected for partial duplicate detection.
+    #[serde(default)]
+    pub partial_files: Vec<FileEntry>,
+    /// Hashes completed so far in the phase after `stage`.
+    #[serde(default)]
+    pub hashed: BTreeMap<PathBuf, Hash>,
+}
+
+impl ScanCheckpoint {
+    /// Create an empty checkpoint for a new scan.
+    #[must_use]
+    pub fn new(roots: Vec<PathBuf>, walker: &WalkerConfig) -> Self {
+        Self {
+            version: CHECKPOINT_VERSION,
+            roots,
+            walker: WalkerFingerprint::from(walker),
+            stage: None,
+            counts: CheckpointCounts::default(),
+            groups: Vec::new(),
+            images: Vec::new(),
+            documents: Vec::new(),
+            named_files: Vec::new(),
+            empty_files: Vec::new(),
+            partial_files: Vec::new(),
+            hashed: BTreeMap::new(),
+        }
+    }
+
+    /// Load a checkpoint from disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not a valid
+    /// checkpoint, or was written by an incompatible version.
+    pub fn load(path: &Path) -> Result<Self, CheckpointError> {
+        let data = std::fs::read(path).map_err(|source| CheckpointError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let checkpoint: Self = serde_json::from_slice(&data)?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::Version {
+                found: checkpoint.version,
+                expected: CHECKPOINT_VERSION,
+            });
+        }
+        Ok(checkpoint)
+    }
+
+    /// Write the checkpoint to disk.
+    ///
+    /// The file is written to a temporary sibling and renamed into place so
+    /// an interruption never leaves a truncated checkpoint behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or any file operation fails.
+    pub fn save(&self, path: &Path) -> Result<(), CheckpointError> {
+        let json = serde_json::to_vec(self)?;
+        let tmp = path.with_extension("tmp");
+        let io_err = |source| CheckpointError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+        std::fs::write(&tmp, json).map_err(io_err)?;
+        std::fs::rename(&tmp, path).map_err(io_err)
+    }
+
+    /// Check that this checkpoint was written for the same scan.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CheckpointError::Mismatch`] if the roots or walker settings
+    /// differ.
+    pub fn validate(
+        &self,
+        roots: &[PathBuf],
+        walker: &WalkerConfig,
+    ) -> Result<(), CheckpointError> {
+        let mut expected = self.roots.clone();
+        let mut actual = roots.to_vec();
+        expected.sort();
+        actual.sort();
+        if expected != actual {
+            return Err(CheckpointError::Mismatch(format!(
+                "scan roots differ (checkpoint: {:?})",
+                self.roots
+            )));
+        }
+        if self.walker != WalkerFingerprint::from(walker) {
+            return Err(CheckpointError::Mismatch(
+                "walker settings (filters, symlinks, hidden files) differ".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Candidate size groups stored by the [`CheckpointStage::SizeGrouped`] stage.
+    #[must_use]
+    pub fn size_groups(&self) -> HashMap<u64, Vec<FileEntry>> {
+        if self.stage != Some(CheckpointStage::SizeGrouped) {
+            return HashMap::new();
+        }
+        let mut groups: HashMap<u64, Vec<FileEntry>> = HashMap::new();
+        for group in &self.groups {
+            for file in &group.files {
+                groups.entry(file.size).or_default().push(file.clone());
+            }
+        }
+        groups
+    }
+
+    /// Candidate prehash groups stored by the [`CheckpointStage::Prehashed`] stage.
+    #[must_use]
+    pub fn prehash_groups(&self) -> HashMap<Hash, Vec<FileEntry>> {
+        if self.stage != Some(CheckpointStage::Prehashed) {
+            return HashMap::new();
+        }
+        self.groups
+            .iter()
+            .filter_map(|g| g.prehash.map(|hash| (hash, g.files.clone())))
+            .collect()
+    }
+}
+
+/// Thread-safe writer that keeps a checkpoint up to date during a scan.
+///
+/// Hashing phases call [`record_hash`](Self::record_hash) from worker threads;
+/// the checkpoint is written every [`CHECKPOINT_INTERVAL`] records, at each
+/// completed stage, and on [`flush`](Self::flush).
+#[derive(Debug)]
+pub struct CheckpointRecorder {
+    path: Option<PathBuf>,
+    state: Mutex<RecorderState>,
+}
+
+#[derive(Debug)]
+struct RecorderState {
+    checkpoint: ScanCheckpoint,
+    since_save: usize,
+}
+
+impl CheckpointRecorder {
+    /// Create a recorder writing to `path` (or only tracking state if `None`).
+    #[must_use]
+    pub fn new(path: Option<PathBuf>, checkpoint: ScanCheckpoint) -> Self {
+        Self {
+            path,
+            state: Mutex::new(RecorderState {
+                checkpoint,
+                since_save: 0,
+            }),
+        }
+    }
+
+    /// Path the checkpoint is written to, if any.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Snapshot of the current checkpoint state.
+    #[must_use]
+    pub fn snapshot(&self) -> ScanCheckpoint {
+        self.lock().checkpoint.clone()
+    }
+
+    /// Last completed stage.
+    #[must_use]
+    pub fn stage(&self) -> Option<CheckpointStage> {
+        self.lock().checkpoint.stage
+    }
+
+    /// Hash recorded for `path` in the current phase, if any.
+    #[must_use]
+    pub fn completed_hash(&self, path: &Path) -> Option<Hash> {
+        self.lock().checkpoint.hashed.get(path).copied()
+    }
+
+    /// Record a completed hash, writing the checkpoint periodically.
+    pub fn record_hash(&self, path: &Path, hash: Hash) {
+        let mut state = self.lock();
+        state.checkpoint.hashed.insert(path.to_path_buf(), hash);
+        state.since_save += 1;
+        if state.since_save >= CHECKPOINT_INTERVAL {
+            state.since_save = 0;
+            if let Err(e) = self.write(&state.checkpoint) {
+                log::warn!("Failed to write checkpoint: {}", e);
+            }
+        }
+    }
+
+    /// Mark a stage complete, replacing the candidate groups and clearing
+    /// per-file hashes, then write the checkpoint.
+    pub fn complete_stage(
+        &self,
+        stage: CheckpointStage,
+        groups: Vec<CheckpointGroup>,
+        counts: CheckpointCounts,
+    ) {
+        let mut state = self.lock();
+        state.checkpoint.stage = Some(stage);
+        state.checkpoint.groups = groups;
+        state.checkpoint.counts = counts;
+        state.checkpoint.hashed.clear();
+        state.since_save = 0;
+        if let Err(e) = self.write(&state.checkpoint) {
+            log::warn!("Failed to write checkpoint: {}", e);
+        }
+    }
+
+    /// Store the similarity and empty-file candidates collected by the walk.
+    pub fn set_side_files(
+        &self,
+        images: Vec<FileEntry>,
+        documents: Vec<FileEntry>,
+        named_files: Vec<FileEntry>,
+        empty_files: Vec<FileEntry>,
+    ) {
+        let mut state = self.lock();
+        state.checkpoint.images = images;
+        state.checkpoint.documents = documents;
+        state.checkpoint.named_files = named_files;
+        state.checkpoint.empty_files = empty_files;
+    }
+
+    /// Store the candidates collected for partial duplicate detection.
+    pub fn set_partial_files(&self, partial_files: Vec<FileEntry>) {
+        self.lock().checkpoint.partial_files = partial_files;
+    }
+
+    /// Write the current state to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be written.
+    pub fn flush(&self) -> Result<(), CheckpointError> {
+        let mut state = self.lock();
+        state.since_save = 0;
+        self.write(&state.checkpoint)
+    }
+
+    /// Remove the checkpoint file after a completed scan.
+    pub fn remove(&self) {
+        if let Some(ref path) = self.path {
+            match std::fs::remove_file(path) {
+                Ok(()) => log::debug!("Removed checkpoint {}", path.display()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => log::warn!("Failed to remove checkpoint {}: {}", path.display(), e),
+            }
+        }
+    }
+
+    fn write(&self, checkpoint: &ScanCheckpoint) -> Result<(), CheckpointError> {
+        match self.path {
+            Some(ref path) => checkpoint.save(path),
+            None => Ok(()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RecorderState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(path: &str, size: u64) -> FileEntry {
+        FileEntry::new(PathBuf::from(path), size, SystemTime::UNIX_EPOCH)
+    }
+
+    #[test]
+    fn test_save_load_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("scan.checkpoint");
+
+        let mut checkpoint =
+            ScanCheckpoint::new(vec![PathBuf::from("/data")], &WalkerConfig::default());
+        checkpoint.stage = Some(CheckpointStage::Prehashed);
+        checkpoint.groups.push(CheckpointGroup {
+            prehash: Some([3u8; 32]),
+            files: vec![entry("/data/a", 10), entry("/data/b", 10)],
+        });
+        checkpoint
+            .hashed
+            .insert(PathBuf::from("/data/a"), [9u8; 32]);
+        checkpoint.save(&path).unwrap();
+
+        let loaded = ScanCheckpoint::load(&path).unwrap();
+        assert_eq!(loaded.stage, Some(CheckpointStage::Prehashed));
+        assert_eq!(loaded.prehash_groups()[&[3u8; 32]].len(), 2);
+        assert!(loaded.size_groups().is_empty());
+        assert_eq!(loaded.hashed[Path::new("/data/a")], [9u8; 32]);
+    }
+
+    #[test]
+    fn test_validate_mismatch() {
+        let walker = WalkerConfig::default();
+        let checkpoint = ScanCheckpoint::new(vec![PathBuf::from("/data")], &walker);
+
+        assert!(checkpoint
+            .validate(&[PathBuf::from("/data")], &walker)
+            .is_ok());
+        assert!(matches!(
+            checkpoint.validate(&[PathBuf::from("/other")], &walker),
+            Err(CheckpointError::Mismatch(_))
+        ));
+        assert!(matches!(
+            checkpoint.validate(
+                &[PathBuf::from("/data")],
+                &walker.clone().with_skip_hidden(true)
+            ),
+            Err(CheckpointError::Mismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_rejects_other_version() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("scan.checkpoint");
+        let mut checkpoint = ScanCheckpoint::new(Vec::new(), &WalkerConfig::default());
+        checkpoint.version = CHECKPOINT_VERSION + 1;
+        checkpoint.save(&path).unwrap();
+
+        assert!(matches!(
+            ScanCheckpoint::load(&path),
+            Err(CheckpointError::Version { .. })
+        ));
+    }
+
+    #[test]
+    fn test_recorder_periodic_write() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("scan.checkpoint");
+        let recorder = CheckpointRecorder::new(
+            Some(path.clone()),
+            ScanCheckpoint::new(Vec::new(), &WalkerConfig::default()),
+        );
+
+        recorder.record_hash(Path::new("/a"), [1u8; 32]);
+        assert!(!path.exists());
+        assert_eq!(recorder.completed_hash(Path::new("/a")), Some([1u8; 32]));
+
+        for i in 1..CHECKPOINT_INTERVAL {
+            recorder.record_hash(&PathBuf::from(format!("/f{i}")), [2u8; 32]);
+        }
+        assert!(path.exists());
+        assert_eq!(
+            ScanCheckpoint::load(&path).unwrap().hashed.len(),
+            CHECKPOINT_INTERVAL
+        );
+
+        recorder.complete_stage(
+            CheckpointStage::SizeGrouped,
+            Vec::new(),
+            CheckpointCounts::default(),
+        );
+        assert!(recorder.completed_hash(Path::new("/a")).is_none());
+
+        recorder.remove();
+        assert!(!path.exists());
+    }
+}
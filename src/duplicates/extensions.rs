@@ -0,0 +1,127 @@
+//! Reclaimable space broken down by file extension.
+//!
+//! Tallies duplicate files per lowercased extension so reports can show
+//! which kinds of files the reclaimable space is made of, e.g. that most of
+//! it is `.mov` footage. Files without an extension are counted under
+//! [`NO_EXTENSION`].
+//!
+//! Like the [size histogram](super::histogram), each group contributes its
+//! duplicate copies (every file except the first, which would be kept), so
+//! the counts sum to
+//! [`ScanSummary::duplicate_files`](super::ScanSummary::duplicate_files) and
+//! the bytes to
+//! [`ScanSummary::reclaimable_space`](super::ScanSummary::reclaimable_space).
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::duplicates::extensions::extension_key;
+//! use std::path::Path;
+//!
+//! assert_eq!(extension_key(Path::new("/videos/Trip.MOV")), "mov");
+//! assert_eq!(extension_key(Path::new("/docs/README")), "(none)");
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::DuplicateGroup;
+
+/// Key for files that have no extension.
+pub const NO_EXTENSION: &str = "(none)";
+
+/// Duplicate file count and reclaimable bytes per extension.
+pub type ExtensionTallies = BTreeMap<String, (usize, u64)>;
+
+/// Lowercased extension of `path`, or [`NO_EXTENSION`].
+#[must_use]
+pub fn extension_key(path: &Path) -> String {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .unwrap_or_else(|| NO_EXTENSION.to_string())
+}
+
+/// Tally the duplicate copies of every group by extension.
+#[must_use]
+pub fn reclaimable_by_extension(groups: &[DuplicateGroup]) -> ExtensionTallies {
+    let mut tallies = ExtensionTallies::new();
+    for group in groups {
+        for file in group.files.iter().skip(1) {
+            let tally = tallies.entry(extension_key(&file.path)).or_default();
+            tally.0 += 1;
+            tally.1 += file.size;
+        }
+    }
+    tallies
+}
+
+/// The `limit` extensions with the most reclaimable bytes, largest first.
+///
+/// Ties are broken by extension name.
+#[must_use]
+pub fn top_extensions(tallies: &ExtensionTallies, limit: usize) -> Vec<(&str, usize, u64)> {
+    let mut top: Vec<(&str, usize, u64)> = tallies
+        .iter()
+        .map(|(ext, &(count, bytes))| (ext.as_str(), count, bytes))
+        .collect();
+    top.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(b.0)));
+    top.truncate(limit);
+    top
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn group(size: u64, paths: &[&str]) -> DuplicateGroup {
+        let files = paths
+            .iter()
+            .map(|p| FileEntry::new(PathBuf::from(p), size, SystemTime::UNIX_EPOCH))
+            .collect();
+        DuplicateGroup::new([0u8; 32], size, files, Vec::new())
+    }
+
+    #[test]
+    fn test_reclaimable_by_extension_mixed_groups() {
+        let groups = vec![
+            group(1000, &["/v/a.mov", "/v/b.MOV", "/v/c.mov"]),
+            // The kept first file is not counted, whatever its extension
+            group(300, &["/p/a.jpg", "/p/a.jpeg", "/p/copy"]),
+            group(50, &["/x/Makefile", "/y/Makefile"]),
+            group(10, &["/only.txt"]),
+        ];
+        let tallies = reclaimable_by_extension(&groups);
+
+        assert_eq!(tallies.get("mov"), Some(&(2, 2000)));
+        assert_eq!(tallies.get("jpeg"), Some(&(1, 300)));
+        assert_eq!(tallies.get("jpg"), None);
+        assert_eq!(tallies.get(NO_EXTENSION), Some(&(2, 350)));
+        assert_eq!(tallies.get("txt"), None);
+
+        let files: usize = tallies.values().map(|t| t.0).sum();
+        let bytes: u64 = tallies.values().map(|t| t.1).sum();
+        assert_eq!(files, groups.iter().map(|g| g.duplicate_count()).sum());
+        assert_eq!(bytes, groups.iter().map(|g| g.wasted_space()).sum());
+    }
+
+    #[test]
+    fn test_extension_key_and_top_extensions() {
+        assert_eq!(extension_key(Path::new("/a/photo.JPG")), "jpg");
+        assert_eq!(extension_key(Path::new("/a/archive.tar.gz")), "gz");
+        assert_eq!(extension_key(Path::new("/a/.bashrc")), NO_EXTENSION);
+        assert_eq!(extension_key(Path::new("/a/trailing.")), NO_EXTENSION);
+
+        let mut tallies = ExtensionTallies::new();
+        tallies.insert("mov".to_string(), (2, 2000));
+        tallies.insert("jpg".to_string(), (5, 500));
+        tallies.insert("png".to_string(), (1, 500));
+        assert_eq!(
+            top_extensions(&tallies, 2),
+            vec![("mov", 2, 2000), ("jpg", 5, 500)]
+        );
+    }
+}
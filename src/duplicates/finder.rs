@@ -6,6 +6,11 @@
 //! 1. **Phase 1 - Size grouping**: Group files by size (see [`crate::duplicates::groups`] module)
 //! 2. **Phase 2 - Prehash**: Hash first 4KB of same-size files
 //! 3. **Phase 3 - Full hash**: Hash entire content of prehash matches
+//! 4. **Phase 4 - Verification** (paranoid mode): Byte-compare files sharing a full hash
+//!
+//! Unless similarity detection needs the full file list first, the walk is
+//! pipelined with Phase 1 and the prehash I/O of Phase 2 (see
+//! [`crate::duplicates::pipeline`]); results are identical either way.
 //!
 //! # Example
 //!
@@ -30,8 +35,9 @@
 //! ```
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::SyncSender;
 use std::sync::Arc;
 
 use growable_bloom_filter::GrowableBloom;
@@ -40,8 +46,21 @@ use rayon::prelude::*;
 use yansi::Paint;
 
 use crate::cache::{CacheEntry, HashCache};
-use crate::progress::ProgressCallback;
-use crate::scanner::{FileEntry, Hash, Hasher};
+use crate::duplicates::checkpoint::{
+    CheckpointCounts, CheckpointGroup, CheckpointRecorder, CheckpointStage, ScanCheckpoint,
+};
+use crate::duplicates::pipeline::{
+    PrehashStore, PrehashWarmer, SizeSieve, PIPELINE_CHANNEL_CAPACITY,
+};
+use crate::output::size::SizeFormat;
+use crate::progress::{wait_while_paused, ProgressCallback};
+use crate::scanner::{FileEntry, Hash, Hasher, RetryConfig, SAMPLE_WINDOW_SIZE};
+
+/// Lowest accepted Bloom filter false positive rate.
+pub const MIN_BLOOM_FP_RATE: f64 = 0.0001;
+
+/// Highest accepted Bloom filter false positive rate.
+pub const MAX_BLOOM_FP_RATE: f64 = 0.1;
 
 /// Configuration for prehash phase.
 #[derive(Clone)]
@@ -53,12 +72,18 @@ pub struct PrehashConfig {
     pub cache: Option<Arc<HashCache>>,
     /// Optional shutdown flag for graceful termination.
     pub shutdown_flag: Option<Arc<AtomicBool>>,
+    /// Optional pause flag; workers park between files while it is set.
+    pub pause_flag: Option<Arc<AtomicBool>>,
     /// Optional progress callback.
     pub progress_callback: Option<Arc<dyn ProgressCallback>>,
     /// Protected reference paths.
     pub reference_paths: Vec<PathBuf>,
     /// False positive rate for Bloom filters.
     pub bloom_fp_rate: f64,
+    /// Optional checkpoint recorder for resumable scans.
+    pub checkpoint: Option<Arc<CheckpointRecorder>>,
+    /// Prehashes already computed while walking (pipelined scans).
+    pub precomputed: Option<Arc<PrehashStore>>,
 }
 
 impl std::fmt::Debug for PrehashConfig {
@@ -67,12 +92,18 @@ impl std::fmt::Debug for PrehashConfig {
             .field("io_threads", &self.io_threads)
             .field("cache", &self.cache.as_ref().map(|_| "<cache>"))
             .field("shutdown_flag", &self.shutdown_flag)
+            .field("pause_flag", &self.pause_flag)
             .field(
                 "progress_callback",
                 &self.progress_callback.as_ref().map(|_| "<callback>"),
             )
             .field("reference_paths", &self.reference_paths)
             .field("bloom_fp_rate", &self.bloom_fp_rate)
+            .field(
+                "checkpoint",
+                &self.checkpoint.as_ref().map(|_| "<checkpoint>"),
+            )
+            .field("precomputed", &self.precomputed.as_ref().map(|s| s.len()))
             .finish()
     }
 }
@@ -83,9 +114,12 @@ impl Default for PrehashConfig {
             io_threads: 4,
             cache: None,
             shutdown_flag: None,
+            pause_flag: None,
             progress_callback: None,
             reference_paths: Vec::new(),
             bloom_fp_rate: 0.01,
+            checkpoint: None,
+            precomputed: None,
         }
     }
 }
@@ -112,6 +146,13 @@ impl PrehashConfig {
         self
     }
 
+    /// Set the pause flag; hashing parks between files while it is set.
+    #[must_use]
+    pub fn with_pause_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.pause_flag = Some(flag);
+        self
+    }
+
     /// Set the progress callback.
     #[must_use]
     pub fn with_progress_callback(mut self, callback: Arc<dyn ProgressCallback>) -> Self {
@@ -126,10 +167,25 @@ impl PrehashConfig {
         self
     }
 
-    /// Set the Bloom filter false positive rate.
+    /// Set the Bloom filter false positive rate, clamped to
+    /// [`MIN_BLOOM_FP_RATE`]..=[`MAX_BLOOM_FP_RATE`].
     #[must_use]
     pub fn with_bloom_fp_rate(mut self, rate: f64) -> Self {
-        self.bloom_fp_rate = rate;
+        self.bloom_fp_rate = rate.clamp(MIN_BLOOM_FP_RATE, MAX_BLOOM_FP_RATE);
+        self
+    }
+
+    /// Set the checkpoint recorder for resumable scans.
+    #[must_use]
+    pub fn with_checkpoint(mut self, checkpoint: Arc<CheckpointRecorder>) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Set prehashes computed ahead of Phase 2.
+    #[must_use]
+    pub fn with_precomputed(mut self, store: Arc<PrehashStore>) -> Self {
+        self.precomputed = Some(store);
         self
     }
 
@@ -139,6 +195,11 @@ impl PrehashConfig {
             .as_ref()
             .is_some_and(|f| f.load(Ordering::SeqCst))
     }
+
+    /// Park the calling worker while the scan is paused.
+    fn wait_while_paused(&self) {
+        wait_while_paused(self.pause_flag.as_deref(), self.shutdown_flag.as_deref());
+    }
 }
 
 /// Statistics from prehash phase.
@@ -258,6 +319,7 @@ pub fn phase2_prehash(
     // Notify progress callback
     if let Some(ref callback) = config.progress_callback {
         callback.on_phase_start("prehash", all_files.len());
+        callback.on_phase_bytes(all_files.iter().map(|f| f.size.min(4096)).sum());
     }
 
     log::info!("Phase 2: Computing prehashes for {} files", all_files.len());
@@ -285,6 +347,7 @@ pub fn phase2_prehash(
             .into_par_iter()
             .enumerate()
             .map(|(idx, file)| {
+                config.wait_while_paused();
                 // Check shutdown flag
                 if config.is_shutdown_requested() {
                     log::debug!("Phase 2: Shutdown requested, skipping remaining files");
@@ -307,6 +370,32 @@ pub fn phase2_prehash(
                     callback.on_progress(idx + 1, file.path.to_string_lossy().as_ref());
                 }
 
+                // Reuse hashes recorded before a resumed interruption
+                if let Some(hash) = config
+                    .checkpoint
+                    .as_ref()
+                    .and_then(|c| c.completed_hash(&file.path))
+                {
+                    log::trace!("Prehash restored from checkpoint: {}", file.path.display());
+                    return (file, Ok(hash), true, false);
+                }
+
+                // Reuse prehashes computed while the walk was still running
+                if let Some((hash, is_hit)) =
+                    config.precomputed.as_ref().and_then(|s| s.get(&file.path))
+                {
+                    log::trace!("Prehash precomputed: {}", file.path.display());
+                    if !is_hit {
+                        if let Some(ref callback) = config.progress_callback {
+                            callback.on_item_completed(file.size.min(4096));
+                        }
+                        if let Some(ref checkpoint) = config.checkpoint {
+                            checkpoint.record_hash(&file.path, hash);
+                        }
+                    }
+                    return (file, Ok(hash), is_hit, false);
+                }
+
                 // Check cache first
                 if let Some(ref cache) = config.cache {
                     match cache.get_prehash(&file.path, file.size, file.modified) {
@@ -332,6 +421,10 @@ pub fn phase2_prehash(
                             callback.on_item_completed(file.size.min(4096));
                         }
 
+                        if let Some(ref checkpoint) = config.checkpoint {
+                            checkpoint.record_hash(&file.path, hash);
+                        }
+
                         // Update cache
                         if let Some(ref cache) = config.cache {
                             let entry = CacheEntry::from(file.clone());
@@ -489,6 +582,7 @@ pub fn compute_prehashes(
         all_files
             .into_par_iter()
             .filter_map(|file| {
+                config.wait_while_paused();
                 if config.is_shutdown_requested() {
                     return None;
                 }
@@ -554,10 +648,16 @@ pub struct FullhashConfig {
     pub cache: Option<Arc<HashCache>>,
     /// Optional shutdown flag for graceful termination.
     pub shutdown_flag: Option<Arc<AtomicBool>>,
+    /// Optional pause flag; workers park between files while it is set.
+    pub pause_flag: Option<Arc<AtomicBool>>,
     /// Optional progress callback.
     pub progress_callback: Option<Arc<dyn ProgressCallback>>,
     /// Protected reference paths.
     pub reference_paths: Vec<PathBuf>,
+    /// Optional checkpoint recorder for resumable scans.
+    pub checkpoint: Option<Arc<CheckpointRecorder>>,
+    /// Files larger than this many bytes use a sampled hash instead of a full hash.
+    pub sample_above: Option<u64>,
 }
 
 impl std::fmt::Debug for FullhashConfig {
@@ -566,11 +666,17 @@ impl std::fmt::Debug for FullhashConfig {
             .field("io_threads", &self.io_threads)
             .field("cache", &self.cache.as_ref().map(|_| "<cache>"))
             .field("shutdown_flag", &self.shutdown_flag)
+            .field("pause_flag", &self.pause_flag)
             .field(
                 "progress_callback",
                 &self.progress_callback.as_ref().map(|_| "<callback>"),
             )
             .field("reference_paths", &self.reference_paths)
+            .field(
+                "checkpoint",
+                &self.checkpoint.as_ref().map(|_| "<checkpoint>"),
+            )
+            .field("sample_above", &self.sample_above)
             .finish()
     }
 }
@@ -581,8 +687,11 @@ impl Default for FullhashConfig {
             io_threads: 4,
             cache: None,
             shutdown_flag: None,
+            pause_flag: None,
             progress_callback: None,
             reference_paths: Vec::new(),
+            checkpoint: None,
+            sample_above: None,
         }
     }
 }
@@ -609,6 +718,13 @@ impl FullhashConfig {
         self
     }
 
+    /// Set the pause flag; hashing parks between files while it is set.
+    #[must_use]
+    pub fn with_pause_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.pause_flag = Some(flag);
+        self
+    }
+
     /// Set the progress callback.
     #[must_use]
     pub fn with_progress_callback(mut self, callback: Arc<dyn ProgressCallback>) -> Self {
@@ -623,12 +739,36 @@ impl FullhashConfig {
         self
     }
 
+    /// Set the checkpoint recorder for resumable scans.
+    #[must_use]
+    pub fn with_checkpoint(mut self, checkpoint: Arc<CheckpointRecorder>) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Use sampled hashing for files larger than `threshold` bytes.
+    #[must_use]
+    pub fn with_sample_above(mut self, threshold: Option<u64>) -> Self {
+        self.sample_above = threshold;
+        self
+    }
+
+    /// Check whether a file of `size` bytes is hashed by sampling.
+    fn samples(&self, size: u64) -> bool {
+        self.sample_above.is_some_and(|threshold| size > threshold)
+    }
+
     /// Check if shutdown has been requested.
     fn is_shutdown_requested(&self) -> bool {
         self.shutdown_flag
             .as_ref()
             .is_some_and(|f| f.load(Ordering::SeqCst))
     }
+
+    /// Park the calling worker while the scan is paused.
+    fn wait_while_paused(&self) {
+        wait_while_paused(self.pause_flag.as_deref(), self.shutdown_flag.as_deref());
+    }
 }
 
 /// Statistics from full hash phase.
@@ -646,8 +786,12 @@ pub struct FullhashStats {
     pub cache_hits: usize,
     /// Number of cache misses for full hashes
     pub cache_misses: usize,
+    /// Number of hashed files whose full hash matched no other file
+    pub unique_fullhashes: usize,
     /// Total bytes hashed across all files
     pub bytes_hashed: u64,
+    /// Number of files hashed by sampling instead of reading every byte
+    pub sampled_files: usize,
     /// Number of confirmed duplicate groups
     pub duplicate_groups: usize,
     /// Number of confirmed duplicate files (excluding originals)
@@ -676,6 +820,15 @@ impl FullhashStats {
 /// group, it computes the full content hash of each file to confirm that they
 /// are true duplicates.
 ///
+/// Files larger than [`FullhashConfig::sample_above`] are hashed with
+/// [`Hasher::sample_hash`] instead. Sampled hashes are cached separately and
+/// never equal a full hash, so sampled and fully hashed files cannot end up
+/// in the same group.
+///
+/// When the hasher includes alternate streams, they are folded into each
+/// hash after the cache lookup (see [`Hasher::fold_streams`]), so the cache
+/// keeps main-stream hashes only.
+///
 /// # Arguments
 ///
 /// * `prehash_groups` - Files grouped by prehash from Phase 2
@@ -739,6 +892,7 @@ pub fn phase3_fullhash(
     // Notify progress callback
     if let Some(ref callback) = config.progress_callback {
         callback.on_phase_start("fullhash", all_files.len());
+        callback.on_phase_bytes(all_files.iter().map(|(f, _)| f.size).sum());
     }
 
     log::info!(
@@ -769,6 +923,7 @@ pub fn phase3_fullhash(
             .into_par_iter()
             .enumerate()
             .map(|(idx, (file, prehash))| {
+                config.wait_while_paused();
                 // Check shutdown flag
                 if config.is_shutdown_requested() {
                     log::debug!("Phase 3: Shutdown requested, skipping remaining files");
@@ -800,9 +955,30 @@ pub fn phase3_fullhash(
                     callback.on_progress(idx + 1, file.path.to_string_lossy().as_ref());
                 }
 
+                let sampled = config.samples(file.size);
+
+                // Reuse hashes recorded before a resumed interruption
+                if let Some(hash) = config
+                    .checkpoint
+                    .as_ref()
+                    .filter(|_| !sampled)
+                    .and_then(|c| c.completed_hash(&file.path))
+                {
+                    log::trace!(
+                        "Full hash restored from checkpoint: {}",
+                        file.path.display()
+                    );
+                    return (file, Ok(hash), true, false);
+                }
+
                 // Check cache first
                 if let Some(ref cache) = config.cache {
-                    match cache.get_fullhash(&file.path, file.size, file.modified) {
+                    let cached = if sampled {
+                        cache.get_samplehash(&file.path, file.size, file.modified)
+                    } else {
+                        cache.get_fullhash(&file.path, file.size, file.modified)
+                    };
+                    match cached {
                         Ok(Some(hash)) => {
                             log::trace!("Full hash cache hit: {}", file.path.display());
                             return (file, Ok(hash), true, false);
@@ -816,19 +992,37 @@ pub fn phase3_fullhash(
                     }
                 }
 
-                // Compute full hash
-                match hasher.full_hash(&file.path) {
+                // Compute full (or sampled) hash
+                let computed = if sampled {
+                    hasher.sample_hash(&file.path, file.size)
+                } else {
+                    hasher.full_hash(&file.path)
+                };
+                match computed {
                     Ok(hash) => {
-                        log::trace!("Full hash computed: {}", file.path.display());
+                        log::trace!(
+                            "{} hash computed: {}",
+                            if sampled { "Sampled" } else { "Full" },
+                            file.path.display()
+                        );
                         if let Some(ref callback) = config.progress_callback {
                             callback.on_item_completed(file.size);
                         }
 
+                        if let Some(checkpoint) = config.checkpoint.as_ref().filter(|_| !sampled) {
+                            checkpoint.record_hash(&file.path, hash);
+                        }
+
                         // Update cache
                         if let Some(ref cache) = config.cache {
                             let mut entry = CacheEntry::from(file.clone());
                             entry.prehash = prehash;
-                            if let Err(e) = cache.insert_fullhash(&entry, hash) {
+                            let updated = if sampled {
+                                cache.insert_samplehash(&entry, hash)
+                            } else {
+                                cache.insert_fullhash(&entry, hash)
+                            };
+                            if let Err(e) = updated {
                                 log::warn!(
                                     "Failed to update cache for {}: {}",
                                     file.path.display(),
@@ -845,6 +1039,10 @@ pub fn phase3_fullhash(
                     }
                 }
             })
+            .map(|(file, res, is_hit, is_interrupted)| {
+                let res = res.and_then(|hash| hasher.fold_streams(&file.path, hash));
+                (file, res, is_hit, is_interrupted)
+            })
             .collect()
     });
 
@@ -864,7 +1062,12 @@ pub fn phase3_fullhash(
         match res {
             Ok(fullhash) => {
                 stats.hashed_files += 1;
-                stats.bytes_hashed += file.size;
+                if config.samples(file.size) {
+                    stats.sampled_files += 1;
+                    stats.bytes_hashed += file.size.min(3 * SAMPLE_WINDOW_SIZE as u64);
+                } else {
+                    stats.bytes_hashed += file.size;
+                }
                 if is_hit {
                     stats.cache_hits += 1;
                 } else {
@@ -879,6 +1082,11 @@ pub fn phase3_fullhash(
         }
     }
 
+    stats.unique_fullhashes = fullhash_groups
+        .values()
+        .filter(|files| files.len() == 1)
+        .count();
+
     // Convert to DuplicateGroup structs, filtering to only groups with 2+ files
     let duplicate_groups: Vec<super::DuplicateGroup> = fullhash_groups
         .into_iter()
@@ -903,6 +1111,13 @@ pub fn phase3_fullhash(
         callback.on_phase_end("fullhash");
     }
 
+    if stats.sampled_files > 0 {
+        log::info!(
+            "Phase 3: {} large files were compared by sampling; use --paranoid to verify them in full",
+            stats.sampled_files
+        );
+    }
+
     log::info!(
         "Phase 3 complete: {} groups, {} duplicates, {} bytes reclaimable",
         stats.duplicate_groups,
@@ -913,6 +1128,260 @@ pub fn phase3_fullhash(
     (duplicate_groups, stats)
 }
 
+/// Chunk size used when streaming files for byte-by-byte verification.
+const VERIFY_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Default number of files open at once while byte-comparing a group.
+pub const DEFAULT_PARANOID_MAX_OPEN_FILES: usize = 64;
+
+/// Tuning for byte-by-byte verification in paranoid mode (Phase 4).
+///
+/// The files of a group are read in lockstep, `chunk_size` bytes at a time.
+/// Groups with more than `max_open_files` files are compared in batches that
+/// each include the group's first file, so at most that many file handles
+/// are open at once. Results do not depend on either setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParanoidConfig {
+    /// Bytes read from each file per comparison step (default: 64 KiB).
+    pub chunk_size: usize,
+    /// Maximum files compared in lockstep (default: 64, minimum: 2).
+    pub max_open_files: usize,
+}
+
+impl Default for ParanoidConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: VERIFY_CHUNK_SIZE,
+            max_open_files: DEFAULT_PARANOID_MAX_OPEN_FILES,
+        }
+    }
+}
+
+impl ParanoidConfig {
+    /// Set the read size per comparison step (at least 1 byte).
+    #[must_use]
+    pub fn with_chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size.max(1);
+        self
+    }
+
+    /// Set how many files may be open at once (at least 2).
+    #[must_use]
+    pub fn with_max_open_files(mut self, count: usize) -> Self {
+        self.max_open_files = count.max(2);
+        self
+    }
+}
+
+/// Verify that all files are byte-for-byte identical (Phase 4).
+///
+/// Uses the default [`ParanoidConfig`]; see
+/// [`verify_group_identical_with`].
+///
+/// # Errors
+///
+/// Returns a [`HashError`](crate::scanner::HashError) if any file cannot be
+/// opened or read.
+///
+/// # Example
+///
+/// ```no_run
+/// use rustdupe::duplicates::verify_group_identical;
+/// use rustdupe::scanner::FileEntry;
+/// use std::path::PathBuf;
+/// use std::time::SystemTime;
+///
+/// let files = vec![
+///     FileEntry::new(PathBuf::from("a.bin"), 4, SystemTime::now()),
+///     FileEntry::new(PathBuf::from("b.bin"), 4, SystemTime::now()),
+/// ];
+/// let identical = verify_group_identical(&files).unwrap();
+/// ```
+pub fn verify_group_identical(files: &[FileEntry]) -> Result<bool, crate::scanner::HashError> {
+    verify_group_identical_with(files, &ParanoidConfig::default())
+}
+
+/// Verify that all files are byte-for-byte identical, tuned by `config`.
+///
+/// Files are compared against the first one in batches of at most
+/// `config.max_open_files` open files. Each batch is read once in lockstep,
+/// `config.chunk_size` bytes at a time, so memory use is two chunks
+/// regardless of file size. Returns as soon as the first differing byte (or
+/// length) is found.
+///
+/// # Errors
+///
+/// Returns a [`HashError`](crate::scanner::HashError) if any file cannot be
+/// opened or read.
+pub fn verify_group_identical_with(
+    files: &[FileEntry],
+    config: &ParanoidConfig,
+) -> Result<bool, crate::scanner::HashError> {
+    let Some((reference, others)) = files.split_first() else {
+        return Ok(true);
+    };
+    let batch_size = config.max_open_files.max(2) - 1;
+    for batch in others.chunks(batch_size) {
+        if !compare_lockstep(reference, batch, config.chunk_size.max(1))? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Read `reference` and every file of `others` in lockstep, comparing chunks.
+fn compare_lockstep(
+    reference: &FileEntry,
+    others: &[FileEntry],
+    chunk_size: usize,
+) -> Result<bool, crate::scanner::HashError> {
+    let open = |file: &FileEntry| {
+        std::fs::File::open(&file.path).map_err(|e| verify_io_error(&file.path, e))
+    };
+    let mut reference_reader = open(reference)?;
+    let mut readers = others.iter().map(open).collect::<Result<Vec<_>, _>>()?;
+
+    let mut expected = vec![0u8; chunk_size];
+    let mut candidate = vec![0u8; chunk_size];
+
+    loop {
+        let ref_len = read_chunk(&mut reference_reader, &mut expected)
+            .map_err(|e| verify_io_error(&reference.path, e))?;
+
+        for (reader, file) in readers.iter_mut().zip(others) {
+            let len =
+                read_chunk(reader, &mut candidate).map_err(|e| verify_io_error(&file.path, e))?;
+            if len != ref_len || candidate[..len] != expected[..ref_len] {
+                log::debug!(
+                    "Byte mismatch between {} and {}",
+                    reference.path.display(),
+                    file.path.display()
+                );
+                return Ok(false);
+            }
+        }
+
+        if ref_len == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// Fill `buf` as far as possible, returning fewer bytes only at EOF.
+fn read_chunk(reader: &mut impl std::io::Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Split files sharing a full hash into byte-identical subsets.
+///
+/// Falls back to pairwise comparison against a pivot only when the group is
+/// not uniformly identical, which should only happen on a hash collision.
+/// Unreadable files are dropped and their errors returned.
+fn split_identical(
+    files: Vec<FileEntry>,
+    config: &ParanoidConfig,
+) -> (Vec<Vec<FileEntry>>, Vec<crate::scanner::HashError>) {
+    let mut errors = Vec::new();
+
+    match verify_group_identical_with(&files, config) {
+        Ok(true) => return (vec![files], errors),
+        Ok(false) => {}
+        Err(e) => log::debug!("Verification needs pairwise fallback: {}", e),
+    }
+
+    // Drop files that cannot be opened so they don't poison every comparison
+    let mut remaining: Vec<FileEntry> = files
+        .into_iter()
+        .filter(|f| match std::fs::File::open(&f.path) {
+            Ok(_) => true,
+            Err(e) => {
+                errors.push(verify_io_error(&f.path, e));
+                false
+            }
+        })
+        .collect();
+
+    let mut subsets = Vec::new();
+    while let Some(pivot) = remaining.pop() {
+        let mut same = vec![pivot];
+        let mut different = Vec::new();
+        for file in remaining {
+            match verify_group_identical_with(&[same[0].clone(), file.clone()], config) {
+                Ok(true) => same.push(file),
+                Ok(false) => different.push(file),
+                Err(e) => errors.push(e),
+            }
+        }
+        subsets.push(same);
+        remaining = different;
+    }
+
+    (subsets, errors)
+}
+
+/// Index of the innermost boundary directory containing `path`, if any.
+fn boundary_index(path: &std::path::Path, boundaries: &[PathBuf]) -> Option<usize> {
+    boundaries
+        .iter()
+        .enumerate()
+        .filter(|(_, boundary)| path.starts_with(boundary))
+        .max_by_key(|(_, boundary)| boundary.components().count())
+        .map(|(i, _)| i)
+}
+
+/// Split each group into per-boundary subsets (see [`FinderConfig::dedupe_within`]).
+///
+/// Each file belongs to the innermost boundary containing it. Files in no
+/// boundary are discarded, as are subsets left with a single file.
+fn split_groups_by_boundaries(
+    groups: Vec<super::DuplicateGroup>,
+    boundaries: &[PathBuf],
+) -> Vec<super::DuplicateGroup> {
+    let mut result = Vec::with_capacity(groups.len());
+    for group in groups {
+        let mut subsets: Vec<Vec<FileEntry>> = vec![Vec::new(); boundaries.len()];
+        for file in &group.files {
+            if let Some(i) = boundary_index(&file.path, boundaries) {
+                subsets[i].push(file.clone());
+            }
+        }
+        result.extend(
+            subsets
+                .into_iter()
+                .filter(|files| files.len() >= 2)
+                .map(|files| {
+                    let mut subset = group.clone();
+                    subset.files = files;
+                    subset
+                }),
+        );
+    }
+    result
+}
+
+/// Map an I/O error from verification to a [`HashError`](crate::scanner::HashError).
+fn verify_io_error(path: &std::path::Path, error: std::io::Error) -> crate::scanner::HashError {
+    match error.kind() {
+        std::io::ErrorKind::NotFound => crate::scanner::HashError::NotFound(path.to_path_buf()),
+        std::io::ErrorKind::PermissionDenied => {
+            crate::scanner::HashError::PermissionDenied(path.to_path_buf())
+        }
+        _ => crate::scanner::HashError::Io {
+            path: path.to_path_buf(),
+            source: Arc::new(error),
+        },
+    }
+}
+
 // ============================================================================
 // DuplicateFinder - Pipeline Orchestrator
 // ============================================================================
@@ -925,16 +1394,25 @@ pub struct FinderConfig {
     /// Number of I/O threads for parallel hashing.
     /// Default is 4 to prevent disk thrashing.
     pub io_threads: usize,
+    /// Number of threads in rayon's global pool, which runs directory
+    /// traversal and in-memory work. `None` uses one thread per core.
+    ///
+    /// File reads for hashing always use a separate pool of `io_threads`.
+    pub cpu_threads: Option<usize>,
     /// Fail-fast on any error during scan.
     pub strict: bool,
     /// Optional hash cache for faster rescans.
     pub cache: Option<Arc<HashCache>>,
     /// Enable byte-by-byte verification after hash matching (paranoid mode).
     pub paranoid: bool,
+    /// Read size and open-file limit for paranoid verification.
+    pub paranoid_config: ParanoidConfig,
     /// Walker configuration for directory traversal.
     pub walker_config: crate::scanner::WalkerConfig,
     /// Optional shutdown flag for graceful termination.
     pub shutdown_flag: Option<Arc<AtomicBool>>,
+    /// Optional pause flag; hashing parks between files while it is set.
+    pub pause_flag: Option<Arc<AtomicBool>>,
     /// Optional progress callback for reporting.
     pub progress_callback: Option<Arc<dyn ProgressCallback>>,
     /// Protected reference paths.
@@ -945,6 +1423,8 @@ pub struct FinderConfig {
     pub bloom_fp_rate: f64,
     /// Minimum number of files in a group to be considered a duplicate (default: 2).
     pub min_group_size: usize,
+    /// Boundary directories confining exact duplicate groups (empty = unconstrained).
+    pub dedupe_within: Vec<PathBuf>,
     /// Enable similar image detection using perceptual hashing.
     pub similar_images: bool,
     /// Enable similar document detection using SimHash.
@@ -959,6 +1439,10 @@ pub struct FinderConfig {
     pub similarity_threshold: Option<u32>,
     /// Threshold for document similarity matching (Hamming distance).
     pub doc_similarity_threshold: Option<u32>,
+    /// Report pairs of files sharing most of their content-defined chunks.
+    pub partial_similarity: bool,
+    /// Shared content fraction reported as a partial duplicate (default: 0.8).
+    pub partial_threshold: Option<f64>,
     /// Manual I/O buffer size override.
     pub io_buffer_size: Option<usize>,
     /// Minimum I/O buffer size.
@@ -967,16 +1451,57 @@ pub struct FinderConfig {
     pub io_buffer_max: usize,
     /// Enable adaptive buffer sizing.
     pub io_adaptive_buffer: bool,
+    /// Streaming read buffer size for hashing (overrides adaptive sizing).
+    pub read_buffer_size: Option<usize>,
+    /// Skip reading holes of sparse files during full hashing (Linux).
+    pub sparse_aware: bool,
+    /// Fold alternate data streams and resource forks into full hashes.
+    pub include_streams: bool,
+    /// Retry policy for transient I/O errors while hashing.
+    pub retry: RetryConfig,
+    /// Report zero-byte files as a single labeled group (default: false).
+    pub include_empty: bool,
+    /// Policy choosing which file in each group is kept first and protected.
+    pub protect_policy: super::ProtectPolicy,
+    /// How hardlinks to already-seen files are treated (default: skipped).
+    pub hardlinks: super::HardlinkPolicy,
+    /// Report advisory groups of files sharing a normalized filename.
+    pub name_cluster: bool,
+    /// Path where scan checkpoints are written (multi-directory scans only).
+    pub checkpoint_path: Option<PathBuf>,
+    /// Previously written checkpoint to resume from.
+    pub resume_checkpoint: Option<ScanCheckpoint>,
+    /// Files larger than this many bytes are compared by a sampled hash in
+    /// Phase 3. Paranoid mode still byte-compares every resulting group.
+    pub sample_above: Option<u64>,
+    /// Overlap the walk with size grouping and prehashing (default: true).
+    /// Ignored when image, document or partial similarity needs every file first.
+    pub pipeline: bool,
+    /// Sort groups by size (largest first) then hash, and files within each
+    /// group by path, so identical inputs give identical output (default: true).
+    pub stable_order: bool,
+    /// Count files in a separate pass before walking so the walk reports a
+    /// real total instead of an open-ended count (default: false).
+    pub precount: bool,
+    /// Only hash files whose basename occurs more than once (default: false).
+    /// Misses duplicates with different names; see
+    /// [`retain_duplicated_names`](super::retain_duplicated_names).
+    pub same_name_only: bool,
+    /// Patterns tagging groups of likely-intentional copies.
+    pub intentional: super::IntentionalPatterns,
 }
 
 impl std::fmt::Debug for FinderConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("FinderConfig")
             .field("io_threads", &self.io_threads)
+            .field("cpu_threads", &self.cpu_threads)
             .field("cache", &self.cache.as_ref().map(|_| "<cache>"))
             .field("paranoid", &self.paranoid)
+            .field("paranoid_config", &self.paranoid_config)
             .field("walker_config", &self.walker_config)
             .field("shutdown_flag", &self.shutdown_flag)
+            .field("pause_flag", &self.pause_flag)
             .field(
                 "progress_callback",
                 &self.progress_callback.as_ref().map(|_| "<callback>"),
@@ -985,8 +1510,29 @@ impl std::fmt::Debug for FinderConfig {
             .field("group_map", &self.group_map)
             .field("bloom_fp_rate", &self.bloom_fp_rate)
             .field("min_group_size", &self.min_group_size)
+            .field("dedupe_within", &self.dedupe_within)
             .field("similar_images", &self.similar_images)
             .field("similar_documents", &self.similar_documents)
+            .field("partial_similarity", &self.partial_similarity)
+            .field("partial_threshold", &self.partial_threshold)
+            .field("include_empty", &self.include_empty)
+            .field("protect_policy", &self.protect_policy)
+            .field("hardlinks", &self.hardlinks)
+            .field("name_cluster", &self.name_cluster)
+            .field("checkpoint_path", &self.checkpoint_path)
+            .field(
+                "resume_checkpoint",
+                &self.resume_checkpoint.as_ref().map(|c| c.stage),
+            )
+            .field("sample_above", &self.sample_above)
+            .field("sparse_aware", &self.sparse_aware)
+            .field("include_streams", &self.include_streams)
+            .field("retry", &self.retry)
+            .field("pipeline", &self.pipeline)
+            .field("stable_order", &self.stable_order)
+            .field("precount", &self.precount)
+            .field("same_name_only", &self.same_name_only)
+            .field("intentional", &self.intentional)
             .finish()
     }
 }
@@ -995,16 +1541,20 @@ impl Default for FinderConfig {
     fn default() -> Self {
         Self {
             io_threads: 4,
+            cpu_threads: None,
             strict: false,
             cache: None,
             paranoid: false,
+            paranoid_config: ParanoidConfig::default(),
             walker_config: crate::scanner::WalkerConfig::default(),
             shutdown_flag: None,
+            pause_flag: None,
             progress_callback: None,
             reference_paths: Vec::new(),
             group_map: std::collections::HashMap::new(),
             bloom_fp_rate: 0.01,
             min_group_size: 2,
+            dedupe_within: Vec::new(),
             similar_images: false,
             similar_documents: false,
             mmap: false,
@@ -1012,15 +1562,49 @@ impl Default for FinderConfig {
             perceptual_algorithm: crate::scanner::PerceptualAlgorithm::default(),
             similarity_threshold: None,
             doc_similarity_threshold: None,
+            partial_similarity: false,
+            partial_threshold: None,
             io_buffer_size: None,
             io_buffer_min: 64 * 1024,
             io_buffer_max: 16 * 1024 * 1024,
             io_adaptive_buffer: true,
+            read_buffer_size: None,
+            sparse_aware: false,
+            include_streams: false,
+            retry: RetryConfig::default(),
+            include_empty: false,
+            protect_policy: super::ProtectPolicy::None,
+            hardlinks: super::HardlinkPolicy::Skip,
+            name_cluster: false,
+            checkpoint_path: None,
+            resume_checkpoint: None,
+            sample_above: None,
+            pipeline: true,
+            stable_order: true,
+            precount: false,
+            same_name_only: false,
+            intentional: super::IntentionalPatterns::default(),
         }
     }
 }
 
 impl FinderConfig {
+    /// Phase 2 settings derived from this configuration, without a
+    /// checkpoint recorder or precomputed prehashes.
+    fn prehash_config(&self) -> PrehashConfig {
+        PrehashConfig {
+            io_threads: self.io_threads,
+            cache: self.cache.clone(),
+            shutdown_flag: self.shutdown_flag.clone(),
+            pause_flag: self.pause_flag.clone(),
+            progress_callback: self.progress_callback.clone(),
+            reference_paths: self.reference_paths.clone(),
+            bloom_fp_rate: self.bloom_fp_rate,
+            checkpoint: None,
+            precomputed: None,
+        }
+    }
+
     /// Create a new configuration with custom I/O thread count.
     #[must_use]
     pub fn with_io_threads(mut self, threads: usize) -> Self {
@@ -1028,6 +1612,43 @@ impl FinderConfig {
         self
     }
 
+    /// Set the CPU thread count for rayon's global pool (`None` = all cores).
+    #[must_use]
+    pub fn with_cpu_threads(mut self, threads: Option<usize>) -> Self {
+        self.cpu_threads = threads.map(|t| t.max(1));
+        self
+    }
+
+    /// Thread pool builder sized by `io_threads`, used for file reads.
+    #[must_use]
+    pub fn io_pool_builder(&self) -> rayon::ThreadPoolBuilder {
+        rayon::ThreadPoolBuilder::new().num_threads(self.io_threads)
+    }
+
+    /// Thread pool builder sized by `cpu_threads`, used for in-memory work.
+    ///
+    /// Zero threads tells rayon to pick its default when `cpu_threads` is unset.
+    #[must_use]
+    pub fn cpu_pool_builder(&self) -> rayon::ThreadPoolBuilder {
+        rayon::ThreadPoolBuilder::new().num_threads(self.cpu_threads.unwrap_or(0))
+    }
+
+    /// Size rayon's global pool from `cpu_threads`.
+    ///
+    /// Does nothing when `cpu_threads` is unset. The global pool can only be
+    /// configured once per process, before anything uses it, so call this
+    /// before the first scan.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the global pool has already been initialized.
+    pub fn init_global_cpu_pool(&self) -> Result<(), rayon::ThreadPoolBuildError> {
+        if self.cpu_threads.is_none() {
+            return Ok(());
+        }
+        self.cpu_pool_builder().build_global()
+    }
+
     /// Set fail-fast on any error.
     #[must_use]
     pub fn with_strict(mut self, strict: bool) -> Self {
@@ -1063,6 +1684,13 @@ impl FinderConfig {
         self
     }
 
+    /// Set the read size and open-file limit for paranoid verification.
+    #[must_use]
+    pub fn with_paranoid_config(mut self, config: ParanoidConfig) -> Self {
+        self.paranoid_config = config;
+        self
+    }
+
     /// Set the walker configuration.
     #[must_use]
     pub fn with_walker_config(mut self, config: crate::scanner::WalkerConfig) -> Self {
@@ -1077,6 +1705,13 @@ impl FinderConfig {
         self
     }
 
+    /// Set the pause flag; hashing parks between files while it is set.
+    #[must_use]
+    pub fn with_pause_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.pause_flag = Some(flag);
+        self
+    }
+
     /// Set the progress callback.
     #[must_use]
     pub fn with_progress_callback(mut self, callback: Arc<dyn ProgressCallback>) -> Self {
@@ -1098,10 +1733,17 @@ impl FinderConfig {
         self
     }
 
-    /// Set the Bloom filter false positive rate.
+    /// Set the Bloom filter false positive rate, clamped to
+    /// [`MIN_BLOOM_FP_RATE`]..=[`MAX_BLOOM_FP_RATE`].
+    ///
+    /// The rate applies to both the walk-time size filter and the phase 2
+    /// prehash filter. Lower rates cost more memory per tracked entry but let
+    /// fewer unique sizes and prehashes through to the next, more expensive
+    /// phase; a false positive never produces a wrong duplicate, only extra
+    /// hashing work.
     #[must_use]
     pub fn with_bloom_fp_rate(mut self, rate: f64) -> Self {
-        self.bloom_fp_rate = rate.clamp(0.0001, 0.1);
+        self.bloom_fp_rate = rate.clamp(MIN_BLOOM_FP_RATE, MAX_BLOOM_FP_RATE);
         self
     }
 
@@ -1112,6 +1754,15 @@ impl FinderConfig {
         self
     }
 
+    /// Confine exact duplicate groups to files sharing one of these directories.
+    ///
+    /// Paths should be canonical, like the scan roots.
+    #[must_use]
+    pub fn with_dedupe_within(mut self, boundaries: Vec<PathBuf>) -> Self {
+        self.dedupe_within = boundaries;
+        self
+    }
+
     /// Enable similar image detection.
     #[must_use]
     pub fn with_similar_images(mut self, enabled: bool) -> Self {
@@ -1150,6 +1801,20 @@ impl FinderConfig {
         self
     }
 
+    /// Enable partial duplicate detection by content-defined chunking.
+    #[must_use]
+    pub fn with_partial_similarity(mut self, enabled: bool) -> Self {
+        self.partial_similarity = enabled;
+        self
+    }
+
+    /// Set the shared content fraction reported as a partial duplicate.
+    #[must_use]
+    pub fn with_partial_threshold(mut self, threshold: Option<f64>) -> Self {
+        self.partial_threshold = threshold;
+        self
+    }
+
     /// Set manual I/O buffer size.
     #[must_use]
     pub fn with_io_buffer_size(mut self, size: Option<usize>) -> Self {
@@ -1178,62 +1843,192 @@ impl FinderConfig {
         self
     }
 
-    /// Check if shutdown has been requested.
-    fn is_shutdown_requested(&self) -> bool {
-        self.shutdown_flag
-            .as_ref()
-            .is_some_and(|f| f.load(Ordering::SeqCst))
+    /// Set the streaming read buffer size used for hashing.
+    ///
+    /// The value is validated when the hasher is built; see
+    /// [`crate::scanner::normalize_read_buffer_size`].
+    #[must_use]
+    pub fn with_read_buffer_size(mut self, size: Option<usize>) -> Self {
+        self.read_buffer_size = size;
+        self
     }
-}
 
-/// Summary statistics from a duplicate scan.
-///
-/// Provides comprehensive metrics about the scan results including
-/// file counts, sizes, and potential space savings.
-#[derive(Debug, Clone, Default)]
-pub struct ScanSummary {
-    /// Total number of files scanned
-    pub total_files: usize,
-    /// Total size of all scanned files in bytes
-    pub total_size: u64,
-    /// Number of files eliminated by size grouping (unique sizes)
-    pub eliminated_by_size: usize,
-    /// Number of files eliminated by prehash (different first 4KB)
-    pub eliminated_by_prehash: usize,
-    /// Number of cache hits for prehashes
-    pub cache_prehash_hits: usize,
-    /// Number of cache misses for prehashes
-    pub cache_prehash_misses: usize,
-    /// Number of cache hits for full hashes
-    pub cache_fullhash_hits: usize,
-    /// Number of cache misses for full hashes
-    pub cache_fullhash_misses: usize,
-    /// Number of confirmed duplicate groups
-    pub duplicate_groups: usize,
-    /// Total number of duplicate files (excluding originals)
-    pub duplicate_files: usize,
-    /// Total size of all files in duplicate groups
-    pub total_duplicate_size: u64,
-    /// Total space that can be reclaimed by removing duplicates
-    pub reclaimable_space: u64,
-    /// Duration of the entire scan
-    pub scan_duration: std::time::Duration,
-    /// Duration of the walking phase
-    pub walk_duration: std::time::Duration,
-    /// Duration of the perceptual hashing phase
-    pub perceptual_duration: std::time::Duration,
-    /// Duration of the document fingerprinting phase
-    pub document_duration: std::time::Duration,
-    /// Duration of the size grouping phase (Phase 1)
-    pub size_duration: std::time::Duration,
-    /// Duration of the prehash phase (Phase 2)
-    pub prehash_duration: std::time::Duration,
-    /// Duration of the full hash phase (Phase 3)
-    pub fullhash_duration: std::time::Duration,
-    /// Duration of the similar image detection phase (Phase 4)
-    pub clustering_duration: std::time::Duration,
-    /// Whether the scan was interrupted
-    pub interrupted: bool,
+    /// Read only the data regions of sparse files when hashing.
+    #[must_use]
+    pub fn with_sparse_aware(mut self, enabled: bool) -> Self {
+        self.sparse_aware = enabled;
+        self
+    }
+
+    /// Fold alternate data streams and resource forks into full hashes.
+    #[must_use]
+    pub fn with_include_streams(mut self, enabled: bool) -> Self {
+        self.include_streams = enabled;
+        self
+    }
+
+    /// Retry transient I/O errors while hashing.
+    #[must_use]
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Include zero-byte files as a separate empty-file group.
+    #[must_use]
+    pub fn with_include_empty(mut self, enabled: bool) -> Self {
+        self.include_empty = enabled;
+        self
+    }
+
+    /// Set the policy for protecting one file per group.
+    #[must_use]
+    pub fn with_protect_policy(mut self, policy: super::ProtectPolicy) -> Self {
+        self.protect_policy = policy;
+        self
+    }
+
+    /// Set how hardlinks to already-seen files are treated.
+    #[must_use]
+    pub fn with_hardlinks(mut self, policy: super::HardlinkPolicy) -> Self {
+        self.hardlinks = policy;
+        self
+    }
+
+    /// Enable similar-filename clustering (e.g. `a.jpg` and `a (1).jpg`).
+    #[must_use]
+    pub fn with_name_cluster(mut self, enabled: bool) -> Self {
+        self.name_cluster = enabled;
+        self
+    }
+
+    /// Write scan checkpoints to `path` so an interrupted scan can be resumed.
+    #[must_use]
+    pub fn with_checkpoint_path(mut self, path: PathBuf) -> Self {
+        self.checkpoint_path = Some(path);
+        self
+    }
+
+    /// Resume from a previously written checkpoint.
+    #[must_use]
+    pub fn with_resume_checkpoint(mut self, checkpoint: ScanCheckpoint) -> Self {
+        self.resume_checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Compare files larger than `threshold` bytes by sampling instead of full hashing.
+    #[must_use]
+    pub fn with_sample_above(mut self, threshold: Option<u64>) -> Self {
+        self.sample_above = threshold;
+        self
+    }
+
+    /// Enable or disable overlapping the walk with prehashing.
+    #[must_use]
+    pub fn with_pipeline(mut self, enabled: bool) -> Self {
+        self.pipeline = enabled;
+        self
+    }
+
+    /// Enable or disable the final deterministic sort of groups and files.
+    #[must_use]
+    pub fn with_stable_order(mut self, enabled: bool) -> Self {
+        self.stable_order = enabled;
+        self
+    }
+
+    /// Enable or disable counting files before the walk.
+    #[must_use]
+    pub fn with_precount(mut self, enabled: bool) -> Self {
+        self.precount = enabled;
+        self
+    }
+
+    /// Enable or disable skipping files with a unique basename.
+    ///
+    /// A fast approximation: duplicates that do not share a name are missed.
+    #[must_use]
+    pub fn with_same_name_only(mut self, enabled: bool) -> Self {
+        self.same_name_only = enabled;
+        self
+    }
+
+    /// Set the patterns that tag groups as likely intentional.
+    #[must_use]
+    pub fn with_intentional_patterns(mut self, patterns: super::IntentionalPatterns) -> Self {
+        self.intentional = patterns;
+        self
+    }
+
+    /// Check if shutdown has been requested.
+    fn is_shutdown_requested(&self) -> bool {
+        self.shutdown_flag
+            .as_ref()
+            .is_some_and(|f| f.load(Ordering::SeqCst))
+    }
+}
+
+/// Extensions listed in the human-readable scan summary.
+const SUMMARY_TOP_EXTENSIONS: usize = 5;
+
+/// Summary statistics from a duplicate scan.
+///
+/// Provides comprehensive metrics about the scan results including
+/// file counts, sizes, and potential space savings.
+#[derive(Debug, Clone, Default)]
+pub struct ScanSummary {
+    /// Total number of files scanned
+    pub total_files: usize,
+    /// Total size of all scanned files in bytes
+    pub total_size: u64,
+    /// Number of files eliminated by size grouping (unique sizes)
+    pub eliminated_by_size: usize,
+    /// Number of files skipped for a unique basename (`--same-name-only`)
+    pub eliminated_by_name: usize,
+    /// Number of files eliminated by prehash (different first 4KB)
+    pub eliminated_by_prehash: usize,
+    /// Number of files eliminated by full hash (same prehash, different content)
+    pub eliminated_by_fullhash: usize,
+    /// Number of cache hits for prehashes
+    pub cache_prehash_hits: usize,
+    /// Number of cache misses for prehashes
+    pub cache_prehash_misses: usize,
+    /// Number of cache hits for full hashes
+    pub cache_fullhash_hits: usize,
+    /// Number of cache misses for full hashes
+    pub cache_fullhash_misses: usize,
+    /// Number of confirmed duplicate groups
+    pub duplicate_groups: usize,
+    /// Total number of duplicate files (excluding originals)
+    pub duplicate_files: usize,
+    /// Total size of all files in duplicate groups
+    pub total_duplicate_size: u64,
+    /// Total space that can be reclaimed by removing duplicates
+    pub reclaimable_space: u64,
+    /// Duplicate files and reclaimable space bucketed by file size
+    pub size_histogram: super::SizeHistogram,
+    /// Duplicate files and reclaimable space per lowercased extension
+    pub reclaimable_by_extension: super::ExtensionTallies,
+    /// Duration of the entire scan
+    pub scan_duration: std::time::Duration,
+    /// Duration of the walking phase
+    pub walk_duration: std::time::Duration,
+    /// Duration of the perceptual hashing phase
+    pub perceptual_duration: std::time::Duration,
+    /// Duration of the document fingerprinting phase
+    pub document_duration: std::time::Duration,
+    /// Duration of the partial duplicate chunking phase
+    pub partial_duration: std::time::Duration,
+    /// Duration of the size grouping phase (Phase 1)
+    pub size_duration: std::time::Duration,
+    /// Duration of the prehash phase (Phase 2)
+    pub prehash_duration: std::time::Duration,
+    /// Duration of the full hash phase (Phase 3)
+    pub fullhash_duration: std::time::Duration,
+    /// Duration of the similar image detection phase (Phase 4)
+    pub clustering_duration: std::time::Duration,
+    /// Whether the scan was interrupted
+    pub interrupted: bool,
     /// Errors encountered during the scan
     pub scan_errors: Vec<crate::scanner::ScanError>,
     /// Number of unique file sizes correctly identified by Bloom filter
@@ -1252,6 +2047,12 @@ pub struct ScanSummary {
     pub documents_fingerprinted: usize,
     /// Number of document fingerprint cache hits
     pub documents_fingerprint_cache_hits: usize,
+    /// Number of files split into chunks for partial duplicate detection
+    pub partial_files_chunked: usize,
+    /// Number of partial duplicate pairs reported
+    pub partial_pairs: usize,
+    /// Symlinks whose target does not exist (`--report-broken-symlinks`)
+    pub broken_symlinks: Vec<PathBuf>,
 }
 
 impl ScanSummary {
@@ -1283,6 +2084,12 @@ impl ScanSummary {
         format_size(self.total_duplicate_size)
     }
 
+    /// Suggest a `--min-size` if most reclaimable space is in a few large files.
+    #[must_use]
+    pub fn min_size_recommendation(&self) -> Option<super::MinSizeRecommendation> {
+        self.size_histogram.recommend_min_size()
+    }
+
     /// Calculate the observed false positive rate for the size Bloom filter.
     #[must_use]
     pub fn bloom_size_fp_rate(&self) -> f64 {
@@ -1306,7 +2113,9 @@ impl ScanSummary {
     }
 
     /// Print a human-readable summary of the scan results.
-    pub fn print(&self) {
+    ///
+    /// Byte sizes are rendered in `size_format` (`--size-units`).
+    pub fn print(&self, size_format: SizeFormat) {
         if self.interrupted {
             eprintln!("{}", "Scan Interrupted".yellow().bold());
         } else {
@@ -1321,7 +2130,7 @@ impl ScanSummary {
         eprintln!(
             "  {: <18} {}",
             "Total size:",
-            self.total_size_display().white().bold()
+            size_format.format(self.total_size).white().bold()
         );
         eprintln!(
             "  {: <18} {} (in {} groups)",
@@ -1329,15 +2138,29 @@ impl ScanSummary {
             self.duplicate_files.red().bold(),
             self.duplicate_groups
         );
+        if self.eliminated_by_name > 0 {
+            eprintln!(
+                "  {: <18} {} (unique names, not compared)",
+                "Skipped by name:",
+                self.eliminated_by_name.yellow()
+            );
+        }
+        if self.partial_pairs > 0 {
+            eprintln!(
+                "  {: <18} {} (share most of their content)",
+                "Partial pairs:",
+                self.partial_pairs.yellow()
+            );
+        }
         eprintln!(
             "  {: <18} {}",
             "Duplicate size:",
-            self.total_duplicate_size_display().white().bold()
+            size_format.format(self.total_duplicate_size).white().bold()
         );
         eprintln!(
             "  {: <18} {}",
             "Reclaimable:",
-            self.reclaimable_display().green().bold()
+            size_format.format(self.reclaimable_space).green().bold()
         );
         eprintln!(
             "  {: <18} {}",
@@ -1345,6 +2168,26 @@ impl ScanSummary {
             HumanDuration(self.scan_duration).magenta().bold()
         );
 
+        if !self.reclaimable_by_extension.is_empty() {
+            eprintln!("{}", "\nTop Extensions".cyan().bold());
+            for (ext, count, bytes) in
+                super::top_extensions(&self.reclaimable_by_extension, SUMMARY_TOP_EXTENSIONS)
+            {
+                let share = if self.reclaimable_space == 0 {
+                    0.0
+                } else {
+                    bytes as f64 / self.reclaimable_space as f64 * 100.0
+                };
+                eprintln!(
+                    "  {: <18} {} in {} files ({:.1}%)",
+                    format!("{}:", ext),
+                    size_format.format(bytes).green(),
+                    count,
+                    share
+                );
+            }
+        }
+
         eprintln!("{}", "\nPhase Breakdown".cyan().bold());
         eprintln!(
             "  {: <18} {:>10}",
@@ -1380,6 +2223,13 @@ impl ScanSummary {
                 HumanDuration(self.document_duration)
             );
         }
+        if self.partial_files_chunked > 0 {
+            eprintln!(
+                "  {: <18} {:>10}",
+                "Partial Chunking:",
+                HumanDuration(self.partial_duration)
+            );
+        }
         eprintln!(
             "  {: <18} {:>10}",
             "Clustering:",
@@ -1436,24 +2286,12 @@ impl ScanSummary {
     }
 }
 
-/// Format a byte size as a human-readable string.
+/// Format bytes with traditional 1024-based labels (KB, MB, ...).
+///
+/// Used for log lines and the `*_display` helpers; user-facing summaries
+/// take an explicit [`SizeFormat`] instead.
 fn format_size(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-    const TB: u64 = GB * 1024;
-
-    if bytes >= TB {
-        format!("{:.2} TB", bytes as f64 / TB as f64)
-    } else if bytes >= GB {
-        format!("{:.2} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
-    }
+    SizeFormat::Iec.format(bytes)
 }
 
 /// Errors that can occur during duplicate finding.
@@ -1488,6 +2326,10 @@ pub enum FinderError {
     /// A scan error occurred.
     #[error(transparent)]
     ScanError(#[from] crate::scanner::ScanError),
+
+    /// A checkpoint could not be used for resuming.
+    #[error(transparent)]
+    Checkpoint(#[from] super::checkpoint::CheckpointError),
 }
 
 /// Duplicate finder that orchestrates the multi-phase detection pipeline.
@@ -1532,10 +2374,19 @@ impl DuplicateFinder {
             .with_buffer_size(config.io_buffer_size)
             .with_buffer_min(config.io_buffer_min)
             .with_buffer_max(config.io_buffer_max)
-            .with_adaptive_buffer(config.io_adaptive_buffer);
+            .with_adaptive_buffer(config.io_adaptive_buffer)
+            .with_sparse_aware(config.sparse_aware)
+            .with_include_streams(config.include_streams)
+            .with_retry(config.retry);
+        if let Some(size) = config.read_buffer_size {
+            hasher = hasher.with_read_buffer_size(size);
+        }
         if let Some(ref flag) = config.shutdown_flag {
             hasher = hasher.with_shutdown_flag(flag.clone());
         }
+        if let Some(ref callback) = config.progress_callback {
+            hasher = hasher.with_progress_callback(callback.clone());
+        }
 
         let perceptual_hasher = if config.similar_images {
             Some(crate::scanner::PerceptualHasher::new(
@@ -1558,6 +2409,312 @@ impl DuplicateFinder {
         Self::new(FinderConfig::default())
     }
 
+    /// Record an error yielded by the walker.
+    ///
+    /// Broken symlinks are findings rather than failures: they are listed in
+    /// [`ScanSummary::broken_symlinks`] and never abort a strict scan.
+    fn record_walk_error(
+        &self,
+        error: crate::scanner::ScanError,
+        summary: &mut ScanSummary,
+    ) -> Result<(), FinderError> {
+        match error {
+            crate::scanner::ScanError::BrokenSymlink { path, .. } => {
+                summary.broken_symlinks.push(path);
+            }
+            e if self.config.strict => return Err(FinderError::ScanError(e)),
+            e => summary.scan_errors.push(e),
+        }
+        Ok(())
+    }
+
+    /// Walker configuration with the finder's empty-file and hardlink
+    /// settings applied.
+    fn walker_config(&self) -> crate::scanner::WalkerConfig {
+        self.config
+            .walker_config
+            .clone()
+            .with_include_empty(self.config.include_empty)
+            .with_include_hardlinks(self.config.hardlinks != super::HardlinkPolicy::Skip)
+    }
+
+    /// File total to report for the walk phase: counted up front with
+    /// `precount`, otherwise 0 (unknown).
+    fn precount_files(&self, paths: &[PathBuf]) -> Result<usize, FinderError> {
+        if !self.config.precount {
+            return Ok(0);
+        }
+        if let Some(ref callback) = self.config.progress_callback {
+            callback.on_message("Counting files...");
+        }
+        let total = crate::scanner::precount_files(
+            paths.to_vec(),
+            self.walker_config(),
+            self.config.shutdown_flag.clone(),
+        )
+        .ok_or(FinderError::Interrupted)?;
+        log::debug!("Precount found {} files", total);
+        Ok(total)
+    }
+
+    /// Whether the walk can be overlapped with size grouping and prehashing.
+    ///
+    /// Perceptual hashing, document fingerprinting and partial duplicate
+    /// detection need the complete file list, so those scans stay phase-serial.
+    /// So does `same_name_only`: a name is only known to be unique once the
+    /// walk ends, and uniquely named files must never be read.
+    fn pipelined(&self) -> bool {
+        self.config.pipeline
+            && !self.config.similar_images
+            && !self.config.similar_documents
+            && !self.config.partial_similarity
+            && !self.config.same_name_only
+    }
+
+    /// Run `produce` on its own thread, sieving the files it sends by size
+    /// and prehashing size collisions while the walk continues.
+    ///
+    /// Returns the sieve and the prehashes computed during the walk.
+    fn walk_pipelined<F>(
+        &self,
+        produce: F,
+        named_files: &mut Vec<FileEntry>,
+        summary: &mut ScanSummary,
+    ) -> Result<(SizeSieve, PrehashStore), FinderError>
+    where
+        F: FnOnce(&SyncSender<Result<FileEntry, crate::scanner::ScanError>>) + Send,
+    {
+        let warmer = PrehashWarmer::spawn(
+            self.hasher.clone(),
+            self.config.io_threads,
+            self.config.cache.clone(),
+            self.config.shutdown_flag.clone(),
+            self.config.pause_flag.clone(),
+        );
+        let mut sieve = SizeSieve::new(self.config.bloom_fp_rate);
+        let (sender, receiver) = std::sync::mpsc::sync_channel(PIPELINE_CHANNEL_CAPACITY);
+
+        std::thread::scope(|scope| {
+            // The walker stops sending once the receiver is dropped
+            scope.spawn(move || produce(&sender));
+            for result in receiver {
+                match result {
+                    Ok(file) => {
+                        if self.config.name_cluster {
+                            named_files.push(file.clone());
+                        }
+                        sieve.push(file, |f| warmer.submit(f));
+                    }
+                    Err(e) => self.record_walk_error(e, summary)?,
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok((sieve, warmer.finish()))
+    }
+
+    /// Collect zero-byte files into a single empty-file group.
+    ///
+    /// Empty files are partitioned out before size grouping; they are only
+    /// reported when `include_empty` is set and enough of them were found.
+    fn empty_file_group(
+        &self,
+        empty_files: Vec<FileEntry>,
+        summary: &mut ScanSummary,
+    ) -> Option<super::DuplicateGroup> {
+        if !self.config.include_empty || empty_files.len() < self.config.min_group_size.max(2) {
+            if !empty_files.is_empty() {
+                log::debug!("Skipped {} empty file(s)", empty_files.len());
+            }
+            return None;
+        }
+
+        log::info!("Found {} empty files", empty_files.len());
+        let group =
+            super::DuplicateGroup::new_empty(empty_files, self.config.reference_paths.clone());
+        summary.duplicate_groups += 1;
+        summary.duplicate_files += group.duplicate_count();
+        Some(group)
+    }
+
+    /// Split exact duplicate groups so each stays within one `dedupe_within`
+    /// boundary directory.
+    ///
+    /// Files outside every boundary are dropped, and the per-boundary subsets
+    /// that still hold at least two files become groups of their own.
+    fn split_by_boundaries(
+        &self,
+        groups: Vec<super::DuplicateGroup>,
+        stats: &mut FullhashStats,
+    ) -> Vec<super::DuplicateGroup> {
+        if self.config.dedupe_within.is_empty() {
+            return groups;
+        }
+        let before = groups.len();
+        let groups = split_groups_by_boundaries(groups, &self.config.dedupe_within);
+        log::debug!(
+            "Boundary filter turned {} groups into {}",
+            before,
+            groups.len()
+        );
+        stats.calculate_wasted_space(&groups);
+        groups
+    }
+
+    /// Drop size-grouping candidates with a unique basename when
+    /// `same_name_only` is set, counting them in the summary.
+    fn retain_same_names(
+        &self,
+        files: Vec<FileEntry>,
+        summary: &mut ScanSummary,
+    ) -> Vec<FileEntry> {
+        if !self.config.same_name_only {
+            return files;
+        }
+        let (files, dropped) = super::retain_duplicated_names(files);
+        summary.eliminated_by_name = dropped;
+        log::info!(
+            "Same-name filter: skipped {} uniquely named file(s), {} remain",
+            dropped,
+            files.len()
+        );
+        files
+    }
+
+    /// Drop exact duplicate groups with fewer than `min_group_size` files.
+    ///
+    /// Runs before the summary is filled in so duplicate counts and
+    /// reclaimable space reflect only the reported groups.
+    fn retain_min_group_size(
+        &self,
+        mut groups: Vec<super::DuplicateGroup>,
+        stats: &mut FullhashStats,
+    ) -> Vec<super::DuplicateGroup> {
+        if self.config.min_group_size <= 2 {
+            return groups;
+        }
+        let before = groups.len();
+        groups.retain(|g| g.files.len() >= self.config.min_group_size);
+        if groups.len() != before {
+            log::debug!(
+                "Dropped {} groups with fewer than {} files",
+                before - groups.len(),
+                self.config.min_group_size
+            );
+            stats.calculate_wasted_space(&groups);
+        }
+        groups
+    }
+
+    /// Put groups and their files in a deterministic order.
+    ///
+    /// Grouping goes through hash maps, so without this the order of groups
+    /// (and of files within a group) can change from run to run. Runs before
+    /// the protect policy, which moves the protected file to the front.
+    /// Exact groups stay ahead of similar, partial, name and empty-file
+    /// groups, which keep their own sections in that order.
+    fn sort_stably(&self, groups: &mut [super::DuplicateGroup]) {
+        if !self.config.stable_order {
+            return;
+        }
+        let section = |group: &super::DuplicateGroup| group.match_kind().section();
+        for group in groups.iter_mut() {
+            group.files.sort_by(|a, b| a.path.cmp(&b.path));
+        }
+        groups.sort_by(|a, b| {
+            section(a)
+                .cmp(&section(b))
+                .then_with(|| b.size.cmp(&a.size))
+                .then_with(|| a.hash.cmp(&b.hash))
+                .then_with(|| {
+                    let first_a = a.files.first().map(|f| &f.path);
+                    first_a.cmp(&b.files.first().map(|f| &f.path))
+                })
+        });
+    }
+
+    /// Byte-compare confirmed duplicate groups in paranoid mode (Phase 4).
+    ///
+    /// Groups whose files differ despite sharing a full hash (a hash
+    /// collision) are split into identical subsets; subsets with fewer than
+    /// `min_group_size` files are discarded.
+    fn verify_groups(
+        &self,
+        groups: Vec<super::DuplicateGroup>,
+        summary: &mut ScanSummary,
+    ) -> Result<Vec<super::DuplicateGroup>, FinderError> {
+        log::info!("Phase 4: Verifying {} groups byte-by-byte", groups.len());
+
+        if let Some(ref callback) = self.config.progress_callback {
+            callback.on_phase_start("verify", groups.len());
+        }
+
+        let mut verified = Vec::with_capacity(groups.len());
+        for (i, group) in groups.into_iter().enumerate() {
+            if self.config.is_shutdown_requested() {
+                return Err(FinderError::Interrupted);
+            }
+
+            if let Some(ref callback) = self.config.progress_callback {
+                if let Some(first) = group.files.first() {
+                    callback.on_progress(i + 1, &first.path.to_string_lossy());
+                }
+            }
+
+            let hash = group.hash;
+            let size = group.size;
+            let reference_paths = group.reference_paths;
+            let (subsets, errors) = split_identical(group.files, &self.config.paranoid_config);
+
+            if let Some(e) = errors.first() {
+                if self.config.strict {
+                    return Err(FinderError::ScanError(
+                        crate::scanner::ScanError::HashError(e.clone()),
+                    ));
+                }
+            }
+            summary
+                .scan_errors
+                .extend(errors.into_iter().map(crate::scanner::ScanError::from));
+
+            if subsets.len() > 1 {
+                log::warn!(
+                    "Hash collision detected for {}: group split into {} subsets",
+                    crate::scanner::hash_to_hex(&hash),
+                    subsets.len()
+                );
+            }
+
+            verified.extend(
+                subsets
+                    .into_iter()
+                    .filter(|files| files.len() >= self.config.min_group_size.max(2))
+                    .map(|files| {
+                        super::DuplicateGroup::new(hash, size, files, reference_paths.clone())
+                    }),
+            );
+        }
+
+        if let Some(ref callback) = self.config.progress_callback {
+            callback.on_phase_end("verify");
+        }
+
+        Ok(verified)
+    }
+
+    /// Sniff the format of files without an extension (or of every file with
+    /// `sniff_content`) so similarity detection sees their real category.
+    fn sniff_kinds(&self, files: &mut [FileEntry]) {
+        if !self.config.similar_images && !self.config.similar_documents {
+            return;
+        }
+        let sniff_content = self.config.walker_config.sniff_content;
+        files.par_iter_mut().for_each(|file| {
+            file.sniff_kind(sniff_content);
+        });
+    }
+
     /// Compute perceptual hashes for a list of files in parallel.
     /// Returns (total_processed, cache_hits)
     fn compute_perceptual_hashes(
@@ -1572,14 +2729,11 @@ impl DuplicateFinder {
         let cache_hits = AtomicUsize::new(0);
 
         // Build thread pool for I/O
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(self.config.io_threads)
-            .build()
-            .unwrap_or_else(|_| {
-                rayon::ThreadPoolBuilder::new()
-                    .build()
-                    .expect("Failed to build global thread pool")
-            });
+        let pool = self.config.io_pool_builder().build().unwrap_or_else(|_| {
+            rayon::ThreadPoolBuilder::new()
+                .build()
+                .expect("Failed to build global thread pool")
+        });
 
         pool.install(|| {
             files.par_iter_mut().for_each(|file| {
@@ -1641,14 +2795,11 @@ impl DuplicateFinder {
         let cache_hits = AtomicUsize::new(0);
 
         // Build thread pool for I/O
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(self.config.io_threads)
-            .build()
-            .unwrap_or_else(|_| {
-                rayon::ThreadPoolBuilder::new()
-                    .build()
-                    .expect("Failed to build global thread pool")
-            });
+        let pool = self.config.io_pool_builder().build().unwrap_or_else(|_| {
+            rayon::ThreadPoolBuilder::new()
+                .build()
+                .expect("Failed to build global thread pool")
+        });
 
         pool.install(|| {
             files.par_iter_mut().for_each(|file| {
@@ -1853,20 +3004,135 @@ impl DuplicateFinder {
         groups
     }
 
-    /// Find all duplicate files starting from the given path.
-    ///
-    /// Runs the complete multi-phase duplicate detection pipeline and
-    /// returns confirmed duplicate groups along with summary statistics.
-    ///
-    /// # Arguments
-    ///
-    /// * `path` - Root directory to scan for duplicates
-    ///
-    /// # Returns
-    ///
-    /// A tuple of:
-    /// - `Vec<DuplicateGroup>` - Confirmed duplicate groups
-    /// - `ScanSummary` - Statistics about the scan
+    /// Cluster files whose names match after stripping copy markers.
+    ///
+    /// Only clusters containing at least one renamed copy (such as
+    /// `a (1).jpg` next to `a.jpg`) are reported, so files that merely share
+    /// a common name like `index.html` are not grouped. Clusters already fully
+    /// covered by an exact duplicate group are skipped.
+    fn find_name_similar_groups(
+        &self,
+        files: &[FileEntry],
+        existing: &[super::DuplicateGroup],
+    ) -> Vec<super::DuplicateGroup> {
+        let mut clusters: std::collections::BTreeMap<String, Vec<&FileEntry>> =
+            std::collections::BTreeMap::new();
+        let mut renamed: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+        for file in files {
+            let Some(name) = file.path.file_name() else {
+                continue;
+            };
+            let name = name.to_string_lossy();
+            let key = crate::scanner::normalize_filename(&name);
+            if key != crate::scanner::normalize_path_str(name.trim()).to_lowercase() {
+                renamed.insert(key.clone());
+            }
+            clusters.entry(key).or_default().push(file);
+        }
+
+        let mut groups = Vec::new();
+        for (key, members) in clusters {
+            if members.len() < self.config.min_group_size.max(2) || !renamed.contains(&key) {
+                continue;
+            }
+
+            let is_redundant = existing.iter().any(|group| {
+                !group.is_similar
+                    && members
+                        .iter()
+                        .all(|m| group.files.iter().any(|f| f.path == m.path))
+            });
+            if is_redundant {
+                log::debug!("Skipping redundant name cluster: {}", key);
+                continue;
+            }
+
+            groups.push(super::DuplicateGroup::new_name_similar(
+                &key,
+                members.into_iter().cloned().collect(),
+                self.config.reference_paths.clone(),
+            ));
+        }
+
+        log::info!("Found {} similar filename groups", groups.len());
+        groups
+    }
+
+    /// Report pairs of files sharing most of their content (Phase 5.5).
+    ///
+    /// Pairs whose files already sit together in an exact group are skipped.
+    /// Pairs are groups of two, so nothing is reported when `--min-duplicates`
+    /// asks for larger groups.
+    fn append_partial_groups(
+        &self,
+        candidates: &[FileEntry],
+        all_groups: &mut Vec<super::DuplicateGroup>,
+        summary: &mut ScanSummary,
+    ) {
+        if self.config.min_group_size > 2 {
+            log::debug!("Skipping partial duplicates: pairs are below --min-duplicates");
+            return;
+        }
+        let partial_start = std::time::Instant::now();
+        log::info!("Phase 5.5: Detecting partial duplicates...");
+        if let Some(ref callback) = self.config.progress_callback {
+            callback.on_phase_start("partial_chunking", candidates.len());
+        }
+
+        let threshold = self
+            .config
+            .partial_threshold
+            .unwrap_or(super::DEFAULT_PARTIAL_THRESHOLD);
+        let (matches, chunked) = super::find_partial_matches(candidates, threshold, &|| {
+            self.config.is_shutdown_requested()
+        });
+
+        let exact_group_of: HashMap<&Path, usize> = all_groups
+            .iter()
+            .enumerate()
+            .filter(|(_, group)| !group.is_similar)
+            .flat_map(|(i, group)| group.files.iter().map(move |f| (f.path.as_path(), i)))
+            .collect();
+        let mut partial_groups = Vec::new();
+        for found in matches {
+            let (first, second) = (&candidates[found.first], &candidates[found.second]);
+            let first_group = exact_group_of.get(first.path.as_path());
+            if first_group.is_some() && first_group == exact_group_of.get(second.path.as_path()) {
+                log::debug!("Skipping partial pair already in an exact group");
+                continue;
+            }
+            partial_groups.push(super::DuplicateGroup::new_partial(
+                found.similarity,
+                vec![first.clone(), second.clone()],
+                self.config.reference_paths.clone(),
+            ));
+        }
+
+        if let Some(ref callback) = self.config.progress_callback {
+            callback.on_phase_end("partial_chunking");
+        }
+        summary.partial_files_chunked = chunked;
+        summary.partial_pairs = partial_groups.len();
+        summary.partial_duration = partial_start.elapsed();
+        log::info!("Found {} partial duplicate pairs", partial_groups.len());
+        all_groups.extend(partial_groups);
+    }
+
+    /// Find all duplicate files starting from the given path.
+    ///
+    /// Runs the complete multi-phase duplicate detection pipeline and
+    /// returns confirmed duplicate groups along with summary statistics.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Root directory to scan for duplicates
+    ///
+    /// # Returns
+    ///
+    /// A tuple of:
+    /// - `Vec<DuplicateGroup>` - Confirmed duplicate groups
+    /// - `ScanSummary` - Statistics about the scan
     ///
     /// # Errors
     ///
@@ -1913,13 +3179,14 @@ impl DuplicateFinder {
         }
 
         // Phase 0: Walk directory and collect files
+        let walk_total = self.precount_files(&[path.to_path_buf()])?;
         let walk_start = std::time::Instant::now();
         if let Some(ref callback) = self.config.progress_callback {
-            callback.on_phase_start("walking", 0);
+            callback.on_phase_start("walking", walk_total);
             callback.on_message(&format!("Walking {}", path.display()));
         }
 
-        let mut walker = crate::scanner::Walker::new(path, self.config.walker_config.clone());
+        let mut walker = crate::scanner::Walker::new(path, self.walker_config());
 
         // Set shutdown flag on walker if available
         if let Some(ref flag) = self.config.shutdown_flag {
@@ -1931,114 +3198,126 @@ impl DuplicateFinder {
             walker = walker.with_progress_callback(callback.clone());
         }
 
-        let mut all_discovered = Vec::new();
-        for result in walker.walk() {
-            match result {
-                Ok(file) => {
-                    all_discovered.push(file);
-                }
-                Err(e) => {
-                    if self.config.strict {
-                        return Err(FinderError::ScanError(e));
-                    } else {
-                        summary.scan_errors.push(e);
+        let mut images = Vec::new();
+        let mut documents = Vec::new();
+        let mut named_files = Vec::new();
+        let mut partial_files = Vec::new();
+        let (sieve, precomputed, size_start) = if self.pipelined() {
+            // Phase 1 (and the prehash I/O of Phase 2) overlaps the walk
+            log::debug!("Pipelining walk with size grouping and prehashing");
+            let (sieve, store) = self.walk_pipelined(
+                move |sender| {
+                    for result in walker.walk() {
+                        if sender.send(result).is_err() {
+                            break;
+                        }
+                    }
+                },
+                &mut named_files,
+                &mut summary,
+            )?;
+
+            if let Some(ref callback) = self.config.progress_callback {
+                callback.on_phase_end("walking");
+            }
+            summary.walk_duration = walk_start.elapsed();
+            (sieve, Some(Arc::new(store)), std::time::Instant::now())
+        } else {
+            let mut all_discovered = Vec::new();
+            for result in walker.walk() {
+                match result {
+                    Ok(file) => {
+                        all_discovered.push(file);
                     }
+                    Err(e) => self.record_walk_error(e, &mut summary)?,
                 }
             }
-        }
 
-        if let Some(ref callback) = self.config.progress_callback {
-            callback.on_phase_end("walking");
-        }
-        summary.walk_duration = walk_start.elapsed();
+            if let Some(ref callback) = self.config.progress_callback {
+                callback.on_phase_end("walking");
+            }
+            summary.walk_duration = walk_start.elapsed();
 
-        // Phase 0.5: Perceptual Hashing
-        if self.config.similar_images {
-            if let Some(ref hasher) = self.perceptual_hasher {
-                let perceptual_start = std::time::Instant::now();
-                log::info!("Phase 0.5: Computing perceptual hashes for images...");
-                let mut image_refs: Vec<&mut FileEntry> =
-                    all_discovered.iter_mut().filter(|f| f.is_image()).collect();
+            self.sniff_kinds(&mut all_discovered);
 
-                if let Some(ref callback) = self.config.progress_callback {
-                    callback.on_phase_start("perceptual_hashing", image_refs.len());
-                }
+            // Phase 0.5: Perceptual Hashing
+            if self.config.similar_images {
+                if let Some(ref hasher) = self.perceptual_hasher {
+                    let perceptual_start = std::time::Instant::now();
+                    log::info!("Phase 0.5: Computing perceptual hashes for images...");
+                    let mut image_refs: Vec<&mut FileEntry> =
+                        all_discovered.iter_mut().filter(|f| f.is_image()).collect();
 
-                let (count, hits) = self.compute_perceptual_hashes(&mut image_refs, hasher);
-                summary.images_perceptual_hashed = count;
-                summary.images_perceptual_hash_cache_hits = hits;
+                    if let Some(ref callback) = self.config.progress_callback {
+                        callback.on_phase_start("perceptual_hashing", image_refs.len());
+                    }
 
-                if let Some(ref callback) = self.config.progress_callback {
-                    callback.on_phase_end("perceptual_hashing");
+                    let (count, hits) = self.compute_perceptual_hashes(&mut image_refs, hasher);
+                    summary.images_perceptual_hashed = count;
+                    summary.images_perceptual_hash_cache_hits = hits;
+
+                    if let Some(ref callback) = self.config.progress_callback {
+                        callback.on_phase_end("perceptual_hashing");
+                    }
+                    summary.perceptual_duration = perceptual_start.elapsed();
                 }
-                summary.perceptual_duration = perceptual_start.elapsed();
             }
-        }
 
-        // Phase 0.6: Document Fingerprinting
-        if self.config.similar_documents {
-            let doc_start = std::time::Instant::now();
-            log::info!("Phase 0.6: Computing fingerprints for documents...");
-            let mut doc_refs: Vec<&mut FileEntry> = all_discovered
-                .iter_mut()
-                .filter(|f| f.is_document())
-                .collect();
+            // Phase 0.6: Document Fingerprinting
+            if self.config.similar_documents {
+                let doc_start = std::time::Instant::now();
+                log::info!("Phase 0.6: Computing fingerprints for documents...");
+                let mut doc_refs: Vec<&mut FileEntry> = all_discovered
+                    .iter_mut()
+                    .filter(|f| f.is_document())
+                    .collect();
 
-            if let Some(ref callback) = self.config.progress_callback {
-                callback.on_phase_start("document_fingerprinting", doc_refs.len());
-            }
+                if let Some(ref callback) = self.config.progress_callback {
+                    callback.on_phase_start("document_fingerprinting", doc_refs.len());
+                }
 
-            let (count, hits) = self.compute_document_fingerprints(&mut doc_refs);
-            summary.documents_fingerprinted = count;
-            summary.documents_fingerprint_cache_hits = hits;
+                let (count, hits) = self.compute_document_fingerprints(&mut doc_refs);
+                summary.documents_fingerprinted = count;
+                summary.documents_fingerprint_cache_hits = hits;
 
-            if let Some(ref callback) = self.config.progress_callback {
-                callback.on_phase_end("document_fingerprinting");
+                if let Some(ref callback) = self.config.progress_callback {
+                    callback.on_phase_end("document_fingerprinting");
+                }
+                summary.document_duration = doc_start.elapsed();
             }
-            summary.document_duration = doc_start.elapsed();
-        }
-
-        // Phase 1: Group by size (and prepare for Phase 2)
-        let size_start = std::time::Instant::now();
-        let mut files = Vec::new();
-        let mut images = Vec::new();
-        let mut documents = Vec::new();
-        let mut seen_sizes = GrowableBloom::new(self.config.bloom_fp_rate, 1000);
-        let mut duplicate_sizes = GrowableBloom::new(self.config.bloom_fp_rate, 1000);
-        let mut first_occurrences: HashMap<u64, FileEntry> = HashMap::new();
 
-        for file in all_discovered {
-            // Collect images for similarity detection
-            if self.config.similar_images && file.is_image() {
-                images.push(file.clone());
-            }
+            // Phase 1: Group by size (and prepare for Phase 2)
+            let size_start = std::time::Instant::now();
+            let mut sieve = SizeSieve::new(self.config.bloom_fp_rate);
+            for file in all_discovered {
+                // Collect images for similarity detection
+                if self.config.similar_images && file.is_image() {
+                    images.push(file.clone());
+                }
 
-            // Collect documents for similarity detection
-            if self.config.similar_documents && file.is_document() {
-                documents.push(file.clone());
-            }
+                // Collect documents for similarity detection
+                if self.config.similar_documents && file.is_document() {
+                    documents.push(file.clone());
+                }
 
-            if file.size == 0 {
-                files.push(file);
-                continue;
-            }
+                // Collect files for filename clustering
+                if self.config.name_cluster {
+                    named_files.push(file.clone());
+                }
 
-            if duplicate_sizes.contains(file.size) {
-                files.push(file);
-            } else if seen_sizes.contains(file.size) {
-                duplicate_sizes.insert(file.size);
-                if let Some(first) = first_occurrences.remove(&file.size) {
-                    files.push(first);
+                // Collect files for partial duplicate detection
+                if self.config.partial_similarity && file.size >= super::PARTIAL_MIN_SIZE {
+                    partial_files.push(file.clone());
                 }
-                files.push(file);
-            } else {
-                seen_sizes.insert(file.size);
-                first_occurrences.insert(file.size, file);
+
+                sieve.push(file, |_| {});
             }
-        }
+            (sieve, None, size_start)
+        };
+        let (files, first_occurrences, empty_files) = sieve.into_parts();
 
         // Summary counts should reflect what we actually found
-        summary.total_files = files.len() + first_occurrences.len();
+        summary.total_files = files.len() + first_occurrences.len() + empty_files.len();
         summary.total_size = files.iter().map(|f| f.size).sum::<u64>()
             + first_occurrences.values().map(|f| f.size).sum::<u64>();
         summary.bloom_size_unique = first_occurrences.len();
@@ -2054,13 +3333,21 @@ impl DuplicateFinder {
             return Err(FinderError::Interrupted);
         }
 
-        if files.is_empty() && images.is_empty() && documents.is_empty() {
+        if files.is_empty()
+            && images.is_empty()
+            && documents.is_empty()
+            && named_files.is_empty()
+            && partial_files.is_empty()
+        {
             log::info!("No potential duplicates or similar files found, scan complete");
             summary.scan_duration = start_time.elapsed();
             summary.size_duration = size_start.elapsed();
-            return Ok((Vec::new(), summary));
+            let groups = self.empty_file_group(empty_files, &mut summary);
+            return Ok((groups.into_iter().collect(), summary));
         }
 
+        let files = self.retain_same_names(files, &mut summary);
+
         // Phase 1: Group by size
         log::info!("Phase 1: Grouping by size...");
         let (size_groups, size_stats) = if !files.is_empty() {
@@ -2087,10 +3374,16 @@ impl DuplicateFinder {
             return Err(FinderError::Interrupted);
         }
 
-        if size_groups.is_empty() && images.is_empty() && documents.is_empty() {
+        if size_groups.is_empty()
+            && images.is_empty()
+            && documents.is_empty()
+            && named_files.is_empty()
+            && partial_files.is_empty()
+        {
             log::info!("No potential duplicates found after size grouping");
             summary.scan_duration = start_time.elapsed();
-            return Ok((Vec::new(), summary));
+            let groups = self.empty_file_group(empty_files, &mut summary);
+            return Ok((groups.into_iter().collect(), summary));
         }
 
         // Phase 2: Prehash comparison
@@ -2098,12 +3391,8 @@ impl DuplicateFinder {
         let (prehash_groups, prehash_stats) = if !size_groups.is_empty() {
             log::info!("Phase 2: Computing prehashes...");
             let prehash_config = PrehashConfig {
-                io_threads: self.config.io_threads,
-                cache: self.config.cache.clone(),
-                shutdown_flag: self.config.shutdown_flag.clone(),
-                progress_callback: self.config.progress_callback.clone(),
-                reference_paths: self.config.reference_paths.clone(),
-                bloom_fp_rate: self.config.bloom_fp_rate,
+                precomputed,
+                ..self.config.prehash_config()
             };
 
             phase2_prehash(size_groups, self.hasher.clone(), prehash_config)
@@ -2139,13 +3428,16 @@ impl DuplicateFinder {
 
         // Phase 3: Full hash comparison
         let fullhash_start = std::time::Instant::now();
-        let (duplicate_groups, fullhash_stats) = if !prehash_groups.is_empty() {
+        let (duplicate_groups, mut fullhash_stats) = if !prehash_groups.is_empty() {
             let fullhash_config = FullhashConfig {
                 io_threads: self.config.io_threads,
                 cache: self.config.cache.clone(),
                 shutdown_flag: self.config.shutdown_flag.clone(),
+                pause_flag: self.config.pause_flag.clone(),
                 progress_callback: self.config.progress_callback.clone(),
                 reference_paths: self.config.reference_paths.clone(),
+                checkpoint: None,
+                sample_above: self.config.sample_above,
             };
 
             phase3_fullhash(prehash_groups, self.hasher.clone(), fullhash_config)
@@ -2162,7 +3454,7 @@ impl DuplicateFinder {
                 summary.scan_errors.extend(
                     fullhash_stats
                         .errors
-                        .into_iter()
+                        .drain(..)
                         .map(crate::scanner::ScanError::from),
                 );
             }
@@ -2172,11 +3464,25 @@ impl DuplicateFinder {
             return Err(FinderError::Interrupted);
         }
 
+        // Phase 4: Byte-by-byte verification (paranoid mode)
+        let duplicate_groups = if self.config.paranoid {
+            let verified = self.verify_groups(duplicate_groups, &mut summary)?;
+            fullhash_stats.calculate_wasted_space(&verified);
+            verified
+        } else {
+            duplicate_groups
+        };
+        let duplicate_groups = self.split_by_boundaries(duplicate_groups, &mut fullhash_stats);
+        let duplicate_groups = self.retain_min_group_size(duplicate_groups, &mut fullhash_stats);
+
         // Update summary
         summary.duplicate_groups = fullhash_stats.duplicate_groups;
         summary.duplicate_files = fullhash_stats.duplicate_files;
         summary.reclaimable_space = fullhash_stats.wasted_space;
         summary.total_duplicate_size = fullhash_stats.total_duplicate_size;
+        summary.size_histogram = super::SizeHistogram::from_groups(&duplicate_groups);
+        summary.reclaimable_by_extension = super::reclaimable_by_extension(&duplicate_groups);
+        summary.eliminated_by_fullhash = fullhash_stats.unique_fullhashes;
         summary.cache_fullhash_hits = fullhash_stats.cache_hits;
         summary.cache_fullhash_misses = fullhash_stats.cache_misses;
         summary.fullhash_duration = fullhash_start.elapsed();
@@ -2238,8 +3544,27 @@ impl DuplicateFinder {
                 }
             }
         }
+
+        // Phase 5.5: Partial Duplicate Detection
+        if self.config.partial_similarity {
+            self.append_partial_groups(&partial_files, &mut all_groups, &mut summary);
+        }
+
+        // Phase 6: Similar Filename Clustering
+        if self.config.name_cluster {
+            log::info!("Phase 6: Clustering files by normalized filename...");
+            let name_groups = self.find_name_similar_groups(&named_files, &all_groups);
+            all_groups.extend(name_groups);
+        }
         summary.clustering_duration = clustering_start.elapsed();
 
+        all_groups.extend(self.empty_file_group(empty_files, &mut summary));
+        self.sort_stably(&mut all_groups);
+        for group in &mut all_groups {
+            group.apply_protect_policy(self.config.protect_policy);
+            group.likely_intentional = self.config.intentional.classify(&group.files);
+        }
+
         log::info!(
             "Scan complete: {} duplicate/similar groups, {} duplicate files, {} reclaimable, {} cache hits",
             all_groups.len(),
@@ -2277,9 +3602,16 @@ impl DuplicateFinder {
     /// - `ScanSummary` - Statistics about the scan
     pub fn find_duplicates_from_files(
         &self,
-        files: Vec<FileEntry>,
+        mut files: Vec<FileEntry>,
     ) -> Result<(Vec<super::DuplicateGroup>, ScanSummary), FinderError> {
         let start_time = std::time::Instant::now();
+        if self.config.hardlinks == super::HardlinkPolicy::Skip {
+            let before = files.len();
+            files.retain(|f| !f.is_hardlink);
+            if files.len() < before {
+                log::debug!("Skipped {} hardlink(s)", before - files.len());
+            }
+        }
         let total_files = files.len();
         let total_size: u64 = files.iter().map(|f| f.size).sum();
 
@@ -2305,8 +3637,9 @@ impl DuplicateFinder {
             return Ok((Vec::new(), summary));
         }
 
+        self.sniff_kinds(&mut files);
+
         // Phase 0.5: Perceptual Hashing
-        let mut files = files;
         if self.config.similar_images {
             if let Some(ref hasher) = self.perceptual_hasher {
                 let perceptual_start = std::time::Instant::now();
@@ -2354,6 +3687,9 @@ impl DuplicateFinder {
         let size_start = std::time::Instant::now();
         let mut images = Vec::new();
         let mut documents = Vec::new();
+        let mut named_files = Vec::new();
+        let mut partial_files = Vec::new();
+        let mut empty_files = Vec::new();
         let mut potential_files = Vec::new();
         let mut seen_sizes = GrowableBloom::new(self.config.bloom_fp_rate, files.len());
         let mut duplicate_sizes = GrowableBloom::new(self.config.bloom_fp_rate, files.len());
@@ -2370,8 +3706,18 @@ impl DuplicateFinder {
                 documents.push(file.clone());
             }
 
+            // Collect files for filename clustering
+            if self.config.name_cluster {
+                named_files.push(file.clone());
+            }
+
+            // Collect files for partial duplicate detection
+            if self.config.partial_similarity && file.size >= super::PARTIAL_MIN_SIZE {
+                partial_files.push(file.clone());
+            }
+
             if file.size == 0 {
-                potential_files.push(file);
+                empty_files.push(file);
                 continue;
             }
 
@@ -2389,13 +3735,21 @@ impl DuplicateFinder {
             }
         }
 
-        if potential_files.is_empty() && images.is_empty() && documents.is_empty() {
+        if potential_files.is_empty()
+            && images.is_empty()
+            && documents.is_empty()
+            && named_files.is_empty()
+            && partial_files.is_empty()
+        {
             log::info!("No potential duplicates or similar files found, scan complete");
             summary.scan_duration = start_time.elapsed();
             summary.size_duration = size_start.elapsed();
-            return Ok((Vec::new(), summary));
+            let groups = self.empty_file_group(empty_files, &mut summary);
+            return Ok((groups.into_iter().collect(), summary));
         }
 
+        let potential_files = self.retain_same_names(potential_files, &mut summary);
+
         // Phase 1: Group by size
         log::info!("Phase 1: Grouping by size...");
         let (size_groups, size_stats) = if !potential_files.is_empty() {
@@ -2413,24 +3767,23 @@ impl DuplicateFinder {
             return Err(FinderError::Interrupted);
         }
 
-        if size_groups.is_empty() && images.is_empty() && documents.is_empty() {
+        if size_groups.is_empty()
+            && images.is_empty()
+            && documents.is_empty()
+            && named_files.is_empty()
+            && partial_files.is_empty()
+        {
             log::info!("No potential duplicates found after size grouping");
             summary.scan_duration = start_time.elapsed();
-            return Ok((Vec::new(), summary));
+            let groups = self.empty_file_group(empty_files, &mut summary);
+            return Ok((groups.into_iter().collect(), summary));
         }
 
         // Phase 2: Prehash comparison
         let prehash_start = std::time::Instant::now();
         let (prehash_groups, prehash_stats) = if !size_groups.is_empty() {
             log::info!("Phase 2: Computing prehashes...");
-            let prehash_config = PrehashConfig {
-                io_threads: self.config.io_threads,
-                cache: self.config.cache.clone(),
-                shutdown_flag: self.config.shutdown_flag.clone(),
-                progress_callback: self.config.progress_callback.clone(),
-                reference_paths: self.config.reference_paths.clone(),
-                bloom_fp_rate: self.config.bloom_fp_rate,
-            };
+            let prehash_config = self.config.prehash_config();
 
             phase2_prehash(size_groups, self.hasher.clone(), prehash_config)
         } else {
@@ -2465,13 +3818,16 @@ impl DuplicateFinder {
 
         // Phase 3: Full hash comparison
         let fullhash_start = std::time::Instant::now();
-        let (duplicate_groups, fullhash_stats) = if !prehash_groups.is_empty() {
+        let (duplicate_groups, mut fullhash_stats) = if !prehash_groups.is_empty() {
             let fullhash_config = FullhashConfig {
                 io_threads: self.config.io_threads,
                 cache: self.config.cache.clone(),
                 shutdown_flag: self.config.shutdown_flag.clone(),
+                pause_flag: self.config.pause_flag.clone(),
                 progress_callback: self.config.progress_callback.clone(),
                 reference_paths: self.config.reference_paths.clone(),
+                checkpoint: None,
+                sample_above: self.config.sample_above,
             };
 
             phase3_fullhash(prehash_groups, self.hasher.clone(), fullhash_config)
@@ -2488,7 +3844,7 @@ impl DuplicateFinder {
                 summary.scan_errors.extend(
                     fullhash_stats
                         .errors
-                        .into_iter()
+                        .drain(..)
                         .map(crate::scanner::ScanError::from),
                 );
             }
@@ -2498,11 +3854,25 @@ impl DuplicateFinder {
             return Err(FinderError::Interrupted);
         }
 
+        // Phase 4: Byte-by-byte verification (paranoid mode)
+        let duplicate_groups = if self.config.paranoid {
+            let verified = self.verify_groups(duplicate_groups, &mut summary)?;
+            fullhash_stats.calculate_wasted_space(&verified);
+            verified
+        } else {
+            duplicate_groups
+        };
+        let duplicate_groups = self.split_by_boundaries(duplicate_groups, &mut fullhash_stats);
+        let duplicate_groups = self.retain_min_group_size(duplicate_groups, &mut fullhash_stats);
+
         // Update summary
         summary.duplicate_groups = fullhash_stats.duplicate_groups;
         summary.duplicate_files = fullhash_stats.duplicate_files;
         summary.reclaimable_space = fullhash_stats.wasted_space;
         summary.total_duplicate_size = fullhash_stats.total_duplicate_size;
+        summary.size_histogram = super::SizeHistogram::from_groups(&duplicate_groups);
+        summary.reclaimable_by_extension = super::reclaimable_by_extension(&duplicate_groups);
+        summary.eliminated_by_fullhash = fullhash_stats.unique_fullhashes;
         summary.cache_fullhash_hits = fullhash_stats.cache_hits;
         summary.cache_fullhash_misses = fullhash_stats.cache_misses;
         summary.fullhash_duration = fullhash_start.elapsed();
@@ -2564,8 +3934,27 @@ impl DuplicateFinder {
                 }
             }
         }
+
+        // Phase 5.5: Partial Duplicate Detection
+        if self.config.partial_similarity {
+            self.append_partial_groups(&partial_files, &mut all_groups, &mut summary);
+        }
+
+        // Phase 6: Similar Filename Clustering
+        if self.config.name_cluster {
+            log::info!("Phase 6: Clustering files by normalized filename...");
+            let name_groups = self.find_name_similar_groups(&named_files, &all_groups);
+            all_groups.extend(name_groups);
+        }
         summary.clustering_duration = clustering_start.elapsed();
 
+        all_groups.extend(self.empty_file_group(empty_files, &mut summary));
+        self.sort_stably(&mut all_groups);
+        for group in &mut all_groups {
+            group.apply_protect_policy(self.config.protect_policy);
+            group.likely_intentional = self.config.intentional.classify(&group.files);
+        }
+
         Ok((all_groups, summary))
     }
 
@@ -2590,6 +3979,14 @@ impl DuplicateFinder {
     /// Returns `FinderError` if:
     /// - All paths are invalid (non-existent or not directories)
     /// - The scan is interrupted by shutdown signal
+    /// - A resume checkpoint does not match the roots or walker settings
+    ///
+    /// # Checkpoints
+    ///
+    /// When [`FinderConfig::checkpoint_path`] is set, progress is written to a
+    /// checkpoint after each phase and periodically while hashing. The file is
+    /// flushed when the scan stops early and removed once it completes. See
+    /// [`crate::duplicates::checkpoint`] for resuming.
     ///
     /// # Example
     ///
@@ -2616,6 +4013,61 @@ impl DuplicateFinder {
     pub fn find_duplicates_in_paths(
         &self,
         paths: Vec<PathBuf>,
+    ) -> Result<(Vec<super::DuplicateGroup>, ScanSummary), FinderError> {
+        let recorder = self.checkpoint_recorder(&paths)?;
+        let result = self.scan_paths(paths, recorder.as_ref());
+
+        if let Some(recorder) = recorder {
+            match result {
+                Ok(_) => recorder.remove(),
+                Err(ref e) => match recorder.flush() {
+                    Ok(()) => {
+                        if let Some(path) = recorder.path() {
+                            log::info!(
+                                "Scan stopped ({}); checkpoint saved to {}",
+                                e,
+                                path.display()
+                            );
+                        }
+                    }
+                    Err(flush_err) => log::warn!("Failed to write checkpoint: {}", flush_err),
+                },
+            }
+        }
+
+        result
+    }
+
+    /// Open a checkpoint recorder when checkpointing or resuming is configured.
+    ///
+    /// A resume checkpoint is validated against the scan roots and walker
+    /// settings so results from a different scan are never mixed in.
+    fn checkpoint_recorder(
+        &self,
+        paths: &[PathBuf],
+    ) -> Result<Option<Arc<CheckpointRecorder>>, FinderError> {
+        let walker_config = self.walker_config();
+        let checkpoint = match self.config.resume_checkpoint {
+            Some(ref checkpoint) => {
+                checkpoint.validate(paths, &walker_config)?;
+                checkpoint.clone()
+            }
+            None if self.config.checkpoint_path.is_some() => {
+                ScanCheckpoint::new(paths.to_vec(), &walker_config)
+            }
+            None => return Ok(None),
+        };
+        Ok(Some(Arc::new(CheckpointRecorder::new(
+            self.config.checkpoint_path.clone(),
+            checkpoint,
+        ))))
+    }
+
+    /// Multi-directory scan pipeline, optionally recording a checkpoint.
+    fn scan_paths(
+        &self,
+        paths: Vec<PathBuf>,
+        recorder: Option<&Arc<CheckpointRecorder>>,
     ) -> Result<(Vec<super::DuplicateGroup>, ScanSummary), FinderError> {
         let start_time = std::time::Instant::now();
         let mut summary = ScanSummary::default();
@@ -2634,212 +4086,310 @@ impl DuplicateFinder {
 
         log::info!("Starting multi-directory scan of {} path(s)", paths.len());
 
-        // Phase 0: Walk all directories and collect files
-        let walk_start = std::time::Instant::now();
-        if let Some(ref callback) = self.config.progress_callback {
-            callback.on_phase_start("walking", 0);
-            callback.on_message(&format!("Walking {} directories", paths.len()));
-        }
+        let resumed = recorder.and_then(|r| r.stage().map(|_| r.snapshot()));
+
+        let mut partial_files = Vec::new();
+        let (size_groups, images, documents, named_files, empty_files, precomputed) = if let Some(
+            ref checkpoint,
+        ) = resumed
+        {
+            log::info!(
+                "Resuming from checkpoint after {:?}; skipping walk and size grouping",
+                checkpoint.stage
+            );
+            if let Some(ref callback) = self.config.progress_callback {
+                callback.on_message("Resuming scan from checkpoint");
+            }
+            summary.total_files = checkpoint.counts.total_files;
+            summary.total_size = checkpoint.counts.total_size;
+            summary.eliminated_by_size = checkpoint.counts.eliminated_by_size;
+            summary.eliminated_by_prehash = checkpoint.counts.eliminated_by_prehash;
+            partial_files = checkpoint.partial_files.clone();
+            (
+                checkpoint.size_groups(),
+                checkpoint.images.clone(),
+                checkpoint.documents.clone(),
+                checkpoint.named_files.clone(),
+                checkpoint.empty_files.clone(),
+                None,
+            )
+        } else {
+            // Phase 0: Walk all directories and collect files
+            let walk_total = self.precount_files(&paths)?;
+            let walk_start = std::time::Instant::now();
+            if let Some(ref callback) = self.config.progress_callback {
+                callback.on_phase_start("walking", walk_total);
+                callback.on_message(&format!("Walking {} directories", paths.len()));
+            }
 
-        let mut multi_walker =
-            crate::scanner::MultiWalker::new(paths, self.config.walker_config.clone());
+            let mut multi_walker = crate::scanner::MultiWalker::new(paths, self.walker_config());
 
-        // Log the actual roots being scanned (after dedup/overlap detection)
-        let roots = multi_walker.roots();
-        if roots.is_empty() {
-            log::warn!("No valid directories to scan after path normalization");
-            summary.scan_duration = start_time.elapsed();
-            return Ok((Vec::new(), summary));
-        }
+            // Log the actual roots being scanned (after dedup/overlap detection)
+            let roots = multi_walker.roots();
+            if roots.is_empty() {
+                log::warn!("No valid directories to scan after path normalization");
+                summary.scan_duration = start_time.elapsed();
+                return Ok((Vec::new(), summary));
+            }
 
-        log::info!("Scanning {} directory root(s): {:?}", roots.len(), roots);
+            log::info!("Scanning {} directory root(s): {:?}", roots.len(), roots);
 
-        // Set shutdown flag on multi_walker if available
-        if let Some(ref flag) = self.config.shutdown_flag {
-            multi_walker = multi_walker.with_shutdown_flag(flag.clone());
-        }
+            // Set shutdown flag on multi_walker if available
+            if let Some(ref flag) = self.config.shutdown_flag {
+                multi_walker = multi_walker.with_shutdown_flag(flag.clone());
+            }
 
-        // Set group map for named directory groups
-        if !self.config.group_map.is_empty() {
-            multi_walker = multi_walker.with_group_map(self.config.group_map.clone());
-        }
+            // Set group map for named directory groups
+            if !self.config.group_map.is_empty() {
+                multi_walker = multi_walker.with_group_map(self.config.group_map.clone());
+            }
 
-        // Set progress callback on multi_walker if available
-        if let Some(ref callback) = self.config.progress_callback {
-            multi_walker = multi_walker.with_progress_callback(callback.clone());
-        }
+            // Set progress callback on multi_walker if available
+            if let Some(ref callback) = self.config.progress_callback {
+                multi_walker = multi_walker.with_progress_callback(callback.clone());
+            }
 
-        let mut all_discovered = Vec::new();
-        for result in multi_walker.walk() {
-            match result {
-                Ok(file) => {
-                    all_discovered.push(file);
+            let mut images = Vec::new();
+            let mut documents = Vec::new();
+            let mut named_files = Vec::new();
+            let (sieve, precomputed, size_start) = if self.pipelined() {
+                // Phase 1 (and the prehash I/O of Phase 2) overlaps the walk
+                log::debug!("Pipelining walk with size grouping and prehashing");
+                let (sieve, store) = self.walk_pipelined(
+                    move |sender| {
+                        for result in multi_walker.walk() {
+                            if sender.send(result).is_err() {
+                                break;
+                            }
+                        }
+                    },
+                    &mut named_files,
+                    &mut summary,
+                )?;
+
+                if let Some(ref callback) = self.config.progress_callback {
+                    callback.on_phase_end("walking");
                 }
-                Err(e) => {
-                    if self.config.strict {
-                        return Err(FinderError::ScanError(e));
-                    } else {
-                        summary.scan_errors.push(e);
+                summary.walk_duration = walk_start.elapsed();
+                (sieve, Some(Arc::new(store)), std::time::Instant::now())
+            } else {
+                let mut all_discovered = Vec::new();
+                for result in multi_walker.walk() {
+                    match result {
+                        Ok(file) => {
+                            all_discovered.push(file);
+                        }
+                        Err(e) => self.record_walk_error(e, &mut summary)?,
                     }
                 }
-            }
-        }
-
-        if let Some(ref callback) = self.config.progress_callback {
-            callback.on_phase_end("walking");
-        }
-        summary.walk_duration = walk_start.elapsed();
-
-        // Phase 0.5: Perceptual Hashing
-        if self.config.similar_images {
-            if let Some(ref hasher) = self.perceptual_hasher {
-                let perceptual_start = std::time::Instant::now();
-                log::info!("Phase 0.5: Computing perceptual hashes for images...");
-                let mut image_refs: Vec<&mut FileEntry> =
-                    all_discovered.iter_mut().filter(|f| f.is_image()).collect();
 
                 if let Some(ref callback) = self.config.progress_callback {
-                    callback.on_phase_start("perceptual_hashing", image_refs.len());
+                    callback.on_phase_end("walking");
                 }
+                summary.walk_duration = walk_start.elapsed();
 
-                let (count, hits) = self.compute_perceptual_hashes(&mut image_refs, hasher);
-                summary.images_perceptual_hashed = count;
-                summary.images_perceptual_hash_cache_hits = hits;
+                self.sniff_kinds(&mut all_discovered);
 
-                if let Some(ref callback) = self.config.progress_callback {
-                    callback.on_phase_end("perceptual_hashing");
-                }
-                summary.perceptual_duration = perceptual_start.elapsed();
-            }
-        }
+                // Phase 0.5: Perceptual Hashing
+                if self.config.similar_images {
+                    if let Some(ref hasher) = self.perceptual_hasher {
+                        let perceptual_start = std::time::Instant::now();
+                        log::info!("Phase 0.5: Computing perceptual hashes for images...");
+                        let mut image_refs: Vec<&mut FileEntry> =
+                            all_discovered.iter_mut().filter(|f| f.is_image()).collect();
 
-        // Phase 0.6: Document Fingerprinting
-        if self.config.similar_documents {
-            let doc_start = std::time::Instant::now();
-            log::info!("Phase 0.6: Computing fingerprints for documents...");
-            let mut doc_refs: Vec<&mut FileEntry> = all_discovered
-                .iter_mut()
-                .filter(|f| f.is_document())
-                .collect();
+                        if let Some(ref callback) = self.config.progress_callback {
+                            callback.on_phase_start("perceptual_hashing", image_refs.len());
+                        }
 
-            if let Some(ref callback) = self.config.progress_callback {
-                callback.on_phase_start("document_fingerprinting", doc_refs.len());
-            }
+                        let (count, hits) = self.compute_perceptual_hashes(&mut image_refs, hasher);
+                        summary.images_perceptual_hashed = count;
+                        summary.images_perceptual_hash_cache_hits = hits;
 
-            let (count, hits) = self.compute_document_fingerprints(&mut doc_refs);
-            summary.documents_fingerprinted = count;
-            summary.documents_fingerprint_cache_hits = hits;
+                        if let Some(ref callback) = self.config.progress_callback {
+                            callback.on_phase_end("perceptual_hashing");
+                        }
+                        summary.perceptual_duration = perceptual_start.elapsed();
+                    }
+                }
 
-            if let Some(ref callback) = self.config.progress_callback {
-                callback.on_phase_end("document_fingerprinting");
-            }
-            summary.document_duration = doc_start.elapsed();
-        }
+                // Phase 0.6: Document Fingerprinting
+                if self.config.similar_documents {
+                    let doc_start = std::time::Instant::now();
+                    log::info!("Phase 0.6: Computing fingerprints for documents...");
+                    let mut doc_refs: Vec<&mut FileEntry> = all_discovered
+                        .iter_mut()
+                        .filter(|f| f.is_document())
+                        .collect();
+
+                    if let Some(ref callback) = self.config.progress_callback {
+                        callback.on_phase_start("document_fingerprinting", doc_refs.len());
+                    }
 
-        // Phase 1: Group by size
-        let size_start = std::time::Instant::now();
-        let mut files = Vec::new();
-        let mut images = Vec::new();
-        let mut documents = Vec::new();
-        let mut seen_sizes = GrowableBloom::new(self.config.bloom_fp_rate, 1000);
-        let mut duplicate_sizes = GrowableBloom::new(self.config.bloom_fp_rate, 1000);
-        let mut first_occurrences: HashMap<u64, FileEntry> = HashMap::new();
+                    let (count, hits) = self.compute_document_fingerprints(&mut doc_refs);
+                    summary.documents_fingerprinted = count;
+                    summary.documents_fingerprint_cache_hits = hits;
 
-        for file in all_discovered {
-            // Collect images for similarity detection
-            if self.config.similar_images && file.is_image() {
-                images.push(file.clone());
-            }
+                    if let Some(ref callback) = self.config.progress_callback {
+                        callback.on_phase_end("document_fingerprinting");
+                    }
+                    summary.document_duration = doc_start.elapsed();
+                }
 
-            // Collect documents for similarity detection
-            if self.config.similar_documents && file.is_document() {
-                documents.push(file.clone());
-            }
+                // Phase 1: Group by size
+                let size_start = std::time::Instant::now();
+                let mut sieve = SizeSieve::new(self.config.bloom_fp_rate);
+                for file in all_discovered {
+                    // Collect images for similarity detection
+                    if self.config.similar_images && file.is_image() {
+                        images.push(file.clone());
+                    }
 
-            if file.size == 0 {
-                files.push(file);
-                continue;
-            }
+                    // Collect documents for similarity detection
+                    if self.config.similar_documents && file.is_document() {
+                        documents.push(file.clone());
+                    }
 
-            if duplicate_sizes.contains(file.size) {
-                files.push(file);
-            } else if seen_sizes.contains(file.size) {
-                duplicate_sizes.insert(file.size);
-                if let Some(first) = first_occurrences.remove(&file.size) {
-                    files.push(first);
+                    // Collect files for filename clustering
+                    if self.config.name_cluster {
+                        named_files.push(file.clone());
+                    }
+
+                    // Collect files for partial duplicate detection
+                    if self.config.partial_similarity && file.size >= super::PARTIAL_MIN_SIZE {
+                        partial_files.push(file.clone());
+                    }
+
+                    sieve.push(file, |_| {});
                 }
-                files.push(file);
-            } else {
-                seen_sizes.insert(file.size);
-                first_occurrences.insert(file.size, file);
+                (sieve, None, size_start)
+            };
+            let (files, first_occurrences, empty_files) = sieve.into_parts();
+
+            summary.total_files = files.len() + first_occurrences.len() + empty_files.len();
+            summary.total_size = files.iter().map(|f| f.size).sum::<u64>()
+                + first_occurrences.values().map(|f| f.size).sum::<u64>();
+
+            log::info!(
+                "Found {} files ({} total) across all directories",
+                summary.total_files,
+                format_size(summary.total_size)
+            );
+
+            // Check for shutdown after walking
+            if self.config.is_shutdown_requested() {
+                return Err(FinderError::Interrupted);
             }
-        }
 
-        summary.total_files = files.len() + first_occurrences.len();
-        summary.total_size = files.iter().map(|f| f.size).sum::<u64>()
-            + first_occurrences.values().map(|f| f.size).sum::<u64>();
+            if files.is_empty()
+                && images.is_empty()
+                && documents.is_empty()
+                && named_files.is_empty()
+                && partial_files.is_empty()
+            {
+                log::info!("No potential duplicates or similar files found across all directories, scan complete");
+                summary.scan_duration = start_time.elapsed();
+                summary.size_duration = size_start.elapsed();
+                let groups = self.empty_file_group(empty_files, &mut summary);
+                return Ok((groups.into_iter().collect(), summary));
+            }
 
-        log::info!(
-            "Found {} files ({} total) across all directories",
-            summary.total_files,
-            format_size(summary.total_size)
-        );
+            let files = self.retain_same_names(files, &mut summary);
 
-        // Check for shutdown after walking
-        if self.config.is_shutdown_requested() {
-            return Err(FinderError::Interrupted);
-        }
+            // Phase 1: Group by size
+            log::info!("Phase 1: Grouping by size...");
+            let (size_groups, size_stats) = if !files.is_empty() {
+                super::group_by_size(files)
+            } else {
+                (HashMap::new(), super::GroupingStats::default())
+            };
 
-        if files.is_empty() && images.is_empty() && documents.is_empty() {
-            log::info!("No potential duplicates or similar files found across all directories, scan complete");
-            summary.scan_duration = start_time.elapsed();
+            // Update eliminated count to include files we discarded during walk
+            summary.eliminated_by_size = size_stats.eliminated_unique + first_occurrences.len();
+            summary.bloom_size_unique = first_occurrences.len();
+            summary.bloom_size_fp = size_stats.eliminated_unique;
             summary.size_duration = size_start.elapsed();
-            return Ok((Vec::new(), summary));
-        }
 
-        // Phase 1: Group by size
-        log::info!("Phase 1: Grouping by size...");
-        let (size_groups, size_stats) = if !files.is_empty() {
-            super::group_by_size(files)
-        } else {
-            (HashMap::new(), super::GroupingStats::default())
-        };
+            log::info!(
+                "Phase 1 complete: {} → {} files ({:.1}% eliminated)",
+                size_stats.total_files,
+                size_stats.potential_duplicates,
+                size_stats.elimination_rate()
+            );
 
-        // Update eliminated count to include files we discarded during walk
-        summary.eliminated_by_size = size_stats.eliminated_unique + first_occurrences.len();
-        summary.bloom_size_unique = first_occurrences.len();
-        summary.bloom_size_fp = size_stats.eliminated_unique;
-        summary.size_duration = size_start.elapsed();
+            // Check for shutdown after Phase 1
+            if self.config.is_shutdown_requested() {
+                return Err(FinderError::Interrupted);
+            }
 
-        log::info!(
-            "Phase 1 complete: {} → {} files ({:.1}% eliminated)",
-            size_stats.total_files,
-            size_stats.potential_duplicates,
-            size_stats.elimination_rate()
-        );
+            if size_groups.is_empty()
+                && images.is_empty()
+                && documents.is_empty()
+                && named_files.is_empty()
+                && partial_files.is_empty()
+            {
+                log::info!("No potential duplicates found after size grouping");
+                summary.scan_duration = start_time.elapsed();
+                let groups = self.empty_file_group(empty_files, &mut summary);
+                return Ok((groups.into_iter().collect(), summary));
+            }
 
-        // Check for shutdown after Phase 1
-        if self.config.is_shutdown_requested() {
-            return Err(FinderError::Interrupted);
-        }
+            if let Some(recorder) = recorder {
+                recorder.set_side_files(
+                    images.clone(),
+                    documents.clone(),
+                    named_files.clone(),
+                    empty_files.clone(),
+                );
+                recorder.set_partial_files(partial_files.clone());
+                recorder.complete_stage(
+                    CheckpointStage::SizeGrouped,
+                    size_groups
+                        .values()
+                        .map(|files| CheckpointGroup {
+                            prehash: None,
+                            files: files.clone(),
+                        })
+                        .collect(),
+                    CheckpointCounts {
+                        total_files: summary.total_files,
+                        total_size: summary.total_size,
+                        eliminated_by_size: summary.eliminated_by_size,
+                        eliminated_by_prehash: 0,
+                    },
+                );
+            }
 
-        if size_groups.is_empty() && images.is_empty() && documents.is_empty() {
-            log::info!("No potential duplicates found after size grouping");
-            summary.scan_duration = start_time.elapsed();
-            return Ok((Vec::new(), summary));
-        }
+            (
+                size_groups,
+                images,
+                documents,
+                named_files,
+                empty_files,
+                precomputed,
+            )
+        };
 
         // Phase 2: Prehash comparison
         let prehash_start = std::time::Instant::now();
-        let (prehash_groups, prehash_stats) = if !size_groups.is_empty() {
+        let prehash_done = resumed
+            .as_ref()
+            .is_some_and(|c| c.stage == Some(CheckpointStage::Prehashed));
+        let (prehash_groups, prehash_stats) = if prehash_done {
+            log::info!("Phase 2: Restored prehash groups from checkpoint");
+            (
+                resumed
+                    .as_ref()
+                    .map(ScanCheckpoint::prehash_groups)
+                    .unwrap_or_default(),
+                PrehashStats::default(),
+            )
+        } else if !size_groups.is_empty() {
             log::info!("Phase 2: Computing prehashes...");
             let prehash_config = PrehashConfig {
-                io_threads: self.config.io_threads,
-                cache: self.config.cache.clone(),
-                shutdown_flag: self.config.shutdown_flag.clone(),
-                progress_callback: self.config.progress_callback.clone(),
-                reference_paths: self.config.reference_paths.clone(),
-                bloom_fp_rate: self.config.bloom_fp_rate,
+                checkpoint: recorder.cloned(),
+                precomputed,
+                ..self.config.prehash_config()
             };
 
             phase2_prehash(size_groups, self.hasher.clone(), prehash_config)
@@ -2847,7 +4397,7 @@ impl DuplicateFinder {
             (HashMap::new(), PrehashStats::default())
         };
 
-        summary.eliminated_by_prehash = prehash_stats.unique_prehashes;
+        summary.eliminated_by_prehash += prehash_stats.unique_prehashes;
         summary.cache_prehash_hits = prehash_stats.cache_hits;
         summary.cache_prehash_misses = prehash_stats.cache_misses;
         summary.bloom_prehash_unique = prehash_stats.bloom_unique;
@@ -2873,15 +4423,37 @@ impl DuplicateFinder {
             return Err(FinderError::Interrupted);
         }
 
+        if let (Some(recorder), false) = (recorder, prehash_done) {
+            recorder.complete_stage(
+                CheckpointStage::Prehashed,
+                prehash_groups
+                    .iter()
+                    .map(|(hash, files)| CheckpointGroup {
+                        prehash: Some(*hash),
+                        files: files.clone(),
+                    })
+                    .collect(),
+                CheckpointCounts {
+                    total_files: summary.total_files,
+                    total_size: summary.total_size,
+                    eliminated_by_size: summary.eliminated_by_size,
+                    eliminated_by_prehash: summary.eliminated_by_prehash,
+                },
+            );
+        }
+
         // Phase 3: Full hash comparison
         let fullhash_start = std::time::Instant::now();
-        let (duplicate_groups, fullhash_stats) = if !prehash_groups.is_empty() {
+        let (duplicate_groups, mut fullhash_stats) = if !prehash_groups.is_empty() {
             let fullhash_config = FullhashConfig {
                 io_threads: self.config.io_threads,
                 cache: self.config.cache.clone(),
                 shutdown_flag: self.config.shutdown_flag.clone(),
+                pause_flag: self.config.pause_flag.clone(),
                 progress_callback: self.config.progress_callback.clone(),
                 reference_paths: self.config.reference_paths.clone(),
+                checkpoint: recorder.cloned(),
+                sample_above: self.config.sample_above,
             };
 
             phase3_fullhash(prehash_groups, self.hasher.clone(), fullhash_config)
@@ -2898,7 +4470,7 @@ impl DuplicateFinder {
                 summary.scan_errors.extend(
                     fullhash_stats
                         .errors
-                        .into_iter()
+                        .drain(..)
                         .map(crate::scanner::ScanError::from),
                 );
             }
@@ -2908,11 +4480,25 @@ impl DuplicateFinder {
             return Err(FinderError::Interrupted);
         }
 
+        // Phase 4: Byte-by-byte verification (paranoid mode)
+        let duplicate_groups = if self.config.paranoid {
+            let verified = self.verify_groups(duplicate_groups, &mut summary)?;
+            fullhash_stats.calculate_wasted_space(&verified);
+            verified
+        } else {
+            duplicate_groups
+        };
+        let duplicate_groups = self.split_by_boundaries(duplicate_groups, &mut fullhash_stats);
+        let duplicate_groups = self.retain_min_group_size(duplicate_groups, &mut fullhash_stats);
+
         // Update summary
         summary.duplicate_groups = fullhash_stats.duplicate_groups;
         summary.duplicate_files = fullhash_stats.duplicate_files;
         summary.reclaimable_space = fullhash_stats.wasted_space;
         summary.total_duplicate_size = fullhash_stats.total_duplicate_size;
+        summary.size_histogram = super::SizeHistogram::from_groups(&duplicate_groups);
+        summary.reclaimable_by_extension = super::reclaimable_by_extension(&duplicate_groups);
+        summary.eliminated_by_fullhash = fullhash_stats.unique_fullhashes;
         summary.cache_fullhash_hits = fullhash_stats.cache_hits;
         summary.cache_fullhash_misses = fullhash_stats.cache_misses;
         summary.fullhash_duration = fullhash_start.elapsed();
@@ -2974,8 +4560,27 @@ impl DuplicateFinder {
                 }
             }
         }
+
+        // Phase 5.5: Partial Duplicate Detection
+        if self.config.partial_similarity {
+            self.append_partial_groups(&partial_files, &mut all_groups, &mut summary);
+        }
+
+        // Phase 6: Similar Filename Clustering
+        if self.config.name_cluster {
+            log::info!("Phase 6: Clustering files by normalized filename...");
+            let name_groups = self.find_name_similar_groups(&named_files, &all_groups);
+            all_groups.extend(name_groups);
+        }
         summary.clustering_duration = clustering_start.elapsed();
 
+        all_groups.extend(self.empty_file_group(empty_files, &mut summary));
+        self.sort_stably(&mut all_groups);
+        for group in &mut all_groups {
+            group.apply_protect_policy(self.config.protect_policy);
+            group.likely_intentional = self.config.intentional.classify(&group.files);
+        }
+
         log::info!(
             "Multi-directory scan complete: {} duplicate/similar groups, {} duplicate files, {} reclaimable",
             all_groups.len(),
@@ -2991,6 +4596,7 @@ impl DuplicateFinder {
 mod tests {
 
     use super::*;
+    use crate::duplicates::IntentionalPatterns;
     use std::fs::File;
     use std::io::Write;
     use std::time::SystemTime;
@@ -3420,6 +5026,54 @@ mod tests {
         assert!(stats.interrupted);
     }
 
+    #[test]
+    fn test_phase3_pause_flag_parks_workers() {
+        let dir = TempDir::new().unwrap();
+        let file1 = create_test_file(&dir, "file1.txt", b"content");
+        let file2 = create_test_file(&dir, "file2.txt", b"content");
+
+        let mut prehash_groups = HashMap::new();
+        prehash_groups.insert([0u8; 32], vec![file1, file2]);
+
+        let pause = Arc::new(AtomicBool::new(true));
+        let callback = Arc::new(TestProgressCallback::new());
+        let config = FullhashConfig::default()
+            .with_pause_flag(pause.clone())
+            .with_progress_callback(callback.clone());
+        let worker = std::thread::spawn(move || {
+            phase3_fullhash(prehash_groups, Arc::new(Hasher::new()), config)
+        });
+
+        // Paused workers do not advance the progress counter
+        std::thread::sleep(crate::progress::PAUSE_POLL_INTERVAL * 4);
+        assert!(*callback.phase_started.lock().unwrap());
+        assert_eq!(callback.progress_count.load(Ordering::SeqCst), 0);
+        assert!(!worker.is_finished());
+
+        pause.store(false, Ordering::SeqCst);
+        let (groups, stats) = worker.join().unwrap();
+        assert_eq!(callback.progress_count.load(Ordering::SeqCst), 2);
+        assert_eq!(groups.len(), 1);
+        assert!(!stats.interrupted);
+    }
+
+    #[test]
+    fn test_phase3_paused_scan_can_shut_down() {
+        let dir = TempDir::new().unwrap();
+        let file1 = create_test_file(&dir, "file1.txt", b"content");
+        let file2 = create_test_file(&dir, "file2.txt", b"content");
+
+        let mut prehash_groups = HashMap::new();
+        prehash_groups.insert([0u8; 32], vec![file1, file2]);
+
+        // Paused forever, but shutdown still ends the phase
+        let config = FullhashConfig::default()
+            .with_pause_flag(Arc::new(AtomicBool::new(true)))
+            .with_shutdown_flag(Arc::new(AtomicBool::new(true)));
+        let (_, stats) = phase3_fullhash(prehash_groups, Arc::new(Hasher::new()), config);
+        assert!(stats.interrupted);
+    }
+
     #[test]
     fn test_phase3_multiple_duplicate_groups() {
         let dir = TempDir::new().unwrap();
@@ -3472,6 +5126,110 @@ mod tests {
         assert_eq!(stats.bytes_hashed, (content.len() * 2) as u64);
     }
 
+    #[test]
+    fn test_phase3_sampling_distinguishes_middle() {
+        let dir = TempDir::new().unwrap();
+        let size = SAMPLE_WINDOW_SIZE * 8;
+        let content = vec![3u8; size];
+        let mut changed = content.clone();
+        changed[size / 2] = 4;
+
+        let file1 = create_test_file(&dir, "a.bin", &content);
+        let file2 = create_test_file(&dir, "b.bin", &content);
+        let file3 = create_test_file(&dir, "c.bin", &changed);
+        let full = Hasher::new().full_hash(&file1.path).unwrap();
+
+        let mut prehash_groups = HashMap::new();
+        prehash_groups.insert([0u8; 32], vec![file1, file2, file3]);
+
+        let hasher = Arc::new(Hasher::new());
+        let config = FullhashConfig::default().with_sample_above(Some(1024));
+        let (groups, stats) = phase3_fullhash(prehash_groups, hasher, config);
+
+        assert_eq!(stats.sampled_files, 3);
+        assert_eq!(stats.bytes_hashed, 9 * SAMPLE_WINDOW_SIZE as u64);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].files.len(), 2);
+        assert!(groups[0].files.iter().all(|f| !f.path.ends_with("c.bin")));
+        // Sampled group hashes are never full hashes
+        assert_ne!(groups[0].hash, full);
+    }
+
+    #[test]
+    fn test_phase3_sampling_threshold_and_cache() {
+        let dir = TempDir::new().unwrap();
+        let cache_file = dir.path().join("cache.db");
+        let cache = Arc::new(HashCache::new(&cache_file).unwrap());
+        let small = create_test_file(&dir, "small.bin", b"small content");
+        let large = create_test_file(&dir, "large.bin", &[9u8; 4096]);
+
+        let mut prehash_groups = HashMap::new();
+        prehash_groups.insert([1u8; 32], vec![small.clone()]);
+        prehash_groups.insert([2u8; 32], vec![large.clone()]);
+
+        let hasher = Arc::new(Hasher::new());
+        let config = FullhashConfig::default()
+            .with_sample_above(Some(1024))
+            .with_cache(cache.clone());
+        let (_, stats) = phase3_fullhash(prehash_groups, hasher, config);
+
+        assert_eq!(stats.sampled_files, 1);
+        assert!(cache
+            .get_fullhash(&small.path, small.size, small.modified)
+            .unwrap()
+            .is_some());
+        assert!(cache
+            .get_fullhash(&large.path, large.size, large.modified)
+            .unwrap()
+            .is_none());
+        assert!(cache
+            .get_samplehash(&large.path, large.size, large.modified)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_paranoid_verifies_sampled_groups() {
+        let dir = TempDir::new().unwrap();
+        let size = SAMPLE_WINDOW_SIZE * 8;
+        let content = vec![5u8; size];
+        // Differs only between the start and middle windows
+        let mut changed = content.clone();
+        changed[SAMPLE_WINDOW_SIZE * 2] = 6;
+        std::fs::write(dir.path().join("a.bin"), &content).unwrap();
+        std::fs::write(dir.path().join("b.bin"), &changed).unwrap();
+
+        let sampled = FinderConfig::default().with_sample_above(Some(1024));
+        let (groups, _) = DuplicateFinder::new(sampled.clone())
+            .find_duplicates(dir.path())
+            .unwrap();
+        assert_eq!(groups.len(), 1);
+
+        let (groups, _) = DuplicateFinder::new(sampled.with_paranoid(true))
+            .find_duplicates(dir.path())
+            .unwrap();
+        assert!(groups.is_empty());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_include_streams_splits_files_with_different_ads() {
+        let dir = TempDir::new().unwrap();
+        create_test_file(&dir, "a.txt", b"identical main stream");
+        create_test_file(&dir, "b.txt", b"identical main stream");
+        std::fs::write(dir.path().join("a.txt:Zone.Identifier"), b"[ZoneTransfer]").unwrap();
+
+        let (groups, _) = DuplicateFinder::new(FinderConfig::default())
+            .find_duplicates(dir.path())
+            .unwrap();
+        assert_eq!(groups.len(), 1);
+
+        let (groups, _) = DuplicateFinder::new(FinderConfig::default().with_include_streams(true))
+            .find_duplicates(dir.path())
+            .unwrap();
+        assert!(groups.is_empty());
+    }
+
     #[test]
     fn test_phase3_progress_callback() {
         let dir = TempDir::new().unwrap();
@@ -3499,6 +5257,132 @@ mod tests {
     // DuplicateFinder Tests
     // ========================================================================
 
+    #[test]
+    fn test_verify_group_identical() {
+        let dir = TempDir::new().unwrap();
+        // Larger than one chunk so the lockstep loop runs more than once
+        let content = vec![0xABu8; VERIFY_CHUNK_SIZE * 2 + 17];
+        let a = create_test_file(&dir, "a.bin", &content);
+        let b = create_test_file(&dir, "b.bin", &content);
+
+        let mut tail_differs = content.clone();
+        *tail_differs.last_mut().unwrap() = 0;
+        let c = create_test_file(&dir, "c.bin", &tail_differs);
+        let d = create_test_file(&dir, "d.bin", &content[..content.len() - 1]);
+
+        assert!(verify_group_identical(&[a.clone(), b.clone()]).unwrap());
+        assert!(!verify_group_identical(&[a.clone(), b.clone(), c]).unwrap());
+        assert!(!verify_group_identical(&[a.clone(), d]).unwrap());
+
+        let missing = make_file_entry(&dir.path().join("missing.bin").to_string_lossy(), 1);
+        assert!(verify_group_identical(&[a, missing]).is_err());
+    }
+
+    #[test]
+    fn test_verify_group_identical_independent_of_paranoid_config() {
+        let dir = TempDir::new().unwrap();
+        let content: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let same: Vec<FileEntry> = (0..5)
+            .map(|i| create_test_file(&dir, &format!("same{}.bin", i), &content))
+            .collect();
+        let mut middle_differs = content.clone();
+        middle_differs[5_000] ^= 0xFF;
+        let odd = create_test_file(&dir, "odd.bin", &middle_differs);
+        let short = create_test_file(&dir, "short.bin", &content[..9_999]);
+
+        let mut with_odd = same.clone();
+        with_odd.push(odd.clone());
+        let mut with_short = same.clone();
+        with_short.insert(2, short);
+
+        for chunk_size in [1, 7, 4096, 10_000, 1 << 20] {
+            for max_open_files in [2, 3, 64] {
+                let config = ParanoidConfig::default()
+                    .with_chunk_size(chunk_size)
+                    .with_max_open_files(max_open_files);
+                assert!(verify_group_identical_with(&same, &config).unwrap());
+                assert!(!verify_group_identical_with(&with_odd, &config).unwrap());
+                assert!(!verify_group_identical_with(&with_short, &config).unwrap());
+                assert!(verify_group_identical_with(&[odd.clone()], &config).unwrap());
+            }
+        }
+
+        // Degenerate settings are clamped
+        let config = ParanoidConfig::default()
+            .with_chunk_size(0)
+            .with_max_open_files(0);
+        assert_eq!(config.chunk_size, 1);
+        assert_eq!(config.max_open_files, 2);
+        assert!(verify_group_identical_with(&same, &config).unwrap());
+    }
+
+    #[test]
+    fn test_paranoid_config_batches_large_groups() {
+        let dir = TempDir::new().unwrap();
+        let mut files: Vec<FileEntry> = (0..9)
+            .map(|i| create_test_file(&dir, &format!("f{}.bin", i), b"identical"))
+            .collect();
+        files.push(create_test_file(&dir, "x.bin", b"different"));
+        let groups = vec![crate::duplicates::DuplicateGroup::new(
+            [3u8; 32],
+            9,
+            files,
+            Vec::new(),
+        )];
+
+        let paranoid = ParanoidConfig::default()
+            .with_chunk_size(4)
+            .with_max_open_files(3);
+        let finder = DuplicateFinder::new(
+            FinderConfig::default()
+                .with_paranoid(true)
+                .with_paranoid_config(paranoid),
+        );
+        let mut summary = ScanSummary::default();
+        let verified = finder.verify_groups(groups, &mut summary).unwrap();
+
+        assert_eq!(verified.len(), 1);
+        assert_eq!(verified[0].files.len(), 9);
+        assert!(summary.scan_errors.is_empty());
+    }
+
+    #[test]
+    fn test_paranoid_splits_hash_collision() {
+        let dir = TempDir::new().unwrap();
+        let a1 = create_test_file(&dir, "a1.bin", b"AAAA");
+        let a2 = create_test_file(&dir, "a2.bin", b"AAAA");
+        let b1 = create_test_file(&dir, "b1.bin", b"BBBB");
+
+        // Forced identical hash stub: pretend all three collided in Phase 3
+        let groups = vec![
+            crate::duplicates::DuplicateGroup::new([7u8; 32], 4, vec![a1, a2, b1], Vec::new()),
+            crate::duplicates::DuplicateGroup::new(
+                [8u8; 32],
+                4,
+                vec![
+                    create_test_file(&dir, "x.bin", b"XXXX"),
+                    create_test_file(&dir, "y.bin", b"YYYY"),
+                ],
+                Vec::new(),
+            ),
+        ];
+
+        let finder = DuplicateFinder::new(FinderConfig::default().with_paranoid(true));
+        let mut summary = ScanSummary::default();
+        let verified = finder.verify_groups(groups, &mut summary).unwrap();
+
+        // The collided group keeps only the identical pair; the all-different group is dropped
+        assert_eq!(verified.len(), 1);
+        assert_eq!(verified[0].files.len(), 2);
+        assert!(verified[0].files.iter().all(|f| f
+            .path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with('a')));
+        assert!(summary.scan_errors.is_empty());
+    }
+
     #[test]
     fn test_finder_config_default() {
         let config = FinderConfig::default();
@@ -3521,12 +5405,84 @@ mod tests {
         assert!(config.shutdown_flag.is_some());
     }
 
+    #[test]
+    fn test_finder_config_cpu_and_io_pools_independent() {
+        let config = FinderConfig::default()
+            .with_io_threads(2)
+            .with_cpu_threads(Some(5));
+        let io_pool = config.io_pool_builder().build().unwrap();
+        let cpu_pool = config.cpu_pool_builder().build().unwrap();
+        assert_eq!(io_pool.current_num_threads(), 2);
+        assert_eq!(cpu_pool.current_num_threads(), 5);
+
+        // Changing one leaves the other alone
+        let config = config.with_io_threads(3);
+        assert_eq!(
+            config
+                .io_pool_builder()
+                .build()
+                .unwrap()
+                .current_num_threads(),
+            3
+        );
+        assert_eq!(
+            config
+                .cpu_pool_builder()
+                .build()
+                .unwrap()
+                .current_num_threads(),
+            5
+        );
+
+        // Unset CPU threads fall back to rayon's default sizing
+        let config = FinderConfig::default().with_cpu_threads(None);
+        let default_threads = rayon::ThreadPoolBuilder::new()
+            .build()
+            .unwrap()
+            .current_num_threads();
+        assert_eq!(
+            config
+                .cpu_pool_builder()
+                .build()
+                .unwrap()
+                .current_num_threads(),
+            default_threads
+        );
+        assert_eq!(
+            config
+                .io_pool_builder()
+                .build()
+                .unwrap()
+                .current_num_threads(),
+            4
+        );
+
+        assert_eq!(
+            FinderConfig::default()
+                .with_cpu_threads(Some(0))
+                .cpu_threads,
+            Some(1)
+        );
+    }
+
     #[test]
     fn test_finder_config_io_threads_min() {
         let config = FinderConfig::default().with_io_threads(0);
         assert_eq!(config.io_threads, 1); // Minimum 1
     }
 
+    #[test]
+    fn test_finder_config_bloom_fp_rate_clamped_and_propagated() {
+        let config = FinderConfig::default().with_bloom_fp_rate(0.5);
+        assert_eq!(config.bloom_fp_rate, MAX_BLOOM_FP_RATE);
+        let config = FinderConfig::default().with_bloom_fp_rate(1e-9);
+        assert_eq!(config.bloom_fp_rate, MIN_BLOOM_FP_RATE);
+
+        let config = FinderConfig::default().with_bloom_fp_rate(0.002);
+        assert_eq!(config.bloom_fp_rate, 0.002);
+        assert_eq!(config.prehash_config().bloom_fp_rate, 0.002);
+    }
+
     #[test]
     fn test_scan_summary_default() {
         let summary = ScanSummary::default();
@@ -3604,6 +5560,166 @@ mod tests {
         assert!(Arc::strong_count(&finder.hasher) >= 1);
     }
 
+    #[test]
+    fn test_find_duplicates_same_name_only() {
+        let dir = TempDir::new().unwrap();
+        for sub in ["a", "b"] {
+            std::fs::create_dir(dir.path().join(sub)).unwrap();
+        }
+        create_test_file(&dir, "a/report.pdf", b"same named duplicate");
+        create_test_file(&dir, "b/report.pdf", b"same named duplicate");
+        create_test_file(&dir, "a/IMG_0001.jpg", b"renamed duplicate!!");
+        create_test_file(&dir, "b/holiday.jpg", b"renamed duplicate!!");
+
+        // Both groups without the filter
+        let (groups, _) = DuplicateFinder::with_defaults()
+            .find_duplicates(dir.path())
+            .unwrap();
+        assert_eq!(groups.len(), 2);
+
+        // Only the same-named pair survives with it
+        let finder = DuplicateFinder::new(FinderConfig::default().with_same_name_only(true));
+        let (groups, summary) = finder.find_duplicates(dir.path()).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert!(groups[0]
+            .files
+            .iter()
+            .all(|f| f.path.file_name().unwrap() == "report.pdf"));
+        assert_eq!(summary.eliminated_by_name, 2);
+    }
+
+    #[test]
+    fn test_same_name_only_never_prehashes_unique_names() {
+        let dir = TempDir::new().unwrap();
+        for sub in ["a", "b"] {
+            std::fs::create_dir(dir.path().join(sub)).unwrap();
+        }
+        let same = create_test_file(&dir, "a/report.pdf", b"same named duplicate").path;
+        create_test_file(&dir, "b/report.pdf", b"same named duplicate");
+        let unique = [
+            create_test_file(&dir, "a/IMG_0001.jpg", b"renamed duplicate!!").path,
+            create_test_file(&dir, "b/holiday.jpg", b"renamed duplicate!!").path,
+        ];
+        let cache_dir = TempDir::new().unwrap();
+        let cache = Arc::new(HashCache::new(&cache_dir.path().join("cache.db")).unwrap());
+
+        // Pipelining is on by default; the cache records every prehash read
+        let config = FinderConfig::default()
+            .with_same_name_only(true)
+            .with_cache(cache.clone());
+        assert!(config.pipeline);
+        let (groups, _) = DuplicateFinder::new(config)
+            .find_duplicates(dir.path())
+            .unwrap();
+        assert_eq!(groups.len(), 1);
+
+        let prehash = |path: &PathBuf| {
+            let metadata = std::fs::metadata(path).unwrap();
+            cache
+                .get_prehash(path, metadata.len(), metadata.modified().unwrap())
+                .unwrap()
+        };
+        assert!(prehash(&same).is_some());
+        for path in &unique {
+            assert!(prehash(path).is_none());
+        }
+    }
+
+    #[test]
+    fn test_find_duplicates_tags_likely_intentional() {
+        let dir = TempDir::new().unwrap();
+        create_test_file(&dir, "app.conf", b"backed up configuration");
+        create_test_file(&dir, "app.conf.bak", b"backed up configuration");
+        create_test_file(&dir, "one.txt", b"incidental copy");
+        create_test_file(&dir, "two.txt", b"incidental copy");
+
+        let (groups, _) = DuplicateFinder::with_defaults()
+            .find_duplicates(dir.path())
+            .unwrap();
+        assert_eq!(groups.len(), 2);
+        let intentional: Vec<_> = groups.iter().filter(|g| g.likely_intentional).collect();
+        assert_eq!(intentional.len(), 1);
+        assert!(intentional[0]
+            .files
+            .iter()
+            .any(|f| f.path.ends_with("app.conf.bak")));
+
+        let finder = DuplicateFinder::new(
+            FinderConfig::default().with_intentional_patterns(IntentionalPatterns::disabled()),
+        );
+        let (groups, _) = finder.find_duplicates(dir.path()).unwrap();
+        assert!(groups.iter().all(|g| !g.likely_intentional));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_find_duplicates_reports_broken_symlinks() {
+        let dir = TempDir::new().unwrap();
+        create_test_file(&dir, "a.txt", b"same content");
+        create_test_file(&dir, "b.txt", b"same content");
+        std::os::unix::fs::symlink(dir.path().join("missing"), dir.path().join("dangling"))
+            .unwrap();
+
+        let (_, summary) = DuplicateFinder::with_defaults()
+            .find_duplicates(dir.path())
+            .unwrap();
+        assert!(summary.broken_symlinks.is_empty());
+
+        // Reported, but not as an error, even in strict mode
+        let walker_config =
+            crate::scanner::WalkerConfig::default().with_report_broken_symlinks(true);
+        let finder = DuplicateFinder::new(
+            FinderConfig::default()
+                .with_walker_config(walker_config)
+                .with_strict(true),
+        );
+        let (groups, summary) = finder.find_duplicates(dir.path()).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(summary.broken_symlinks, vec![dir.path().join("dangling")]);
+        assert!(summary.scan_errors.is_empty());
+    }
+
+    #[test]
+    fn test_find_duplicates_partial_similarity() {
+        let dir = TempDir::new().unwrap();
+        // A log and a longer copy of it, plus an exact duplicate pair
+        let log: Vec<u8> = (0..400_000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 11) as u8)
+            .collect();
+        let mut grown = log.clone();
+        grown.extend((0..40_000u32).map(|i| (i.wrapping_mul(40_503) >> 7) as u8));
+        create_test_file(&dir, "app.log", &log);
+        create_test_file(&dir, "app.log.1", &grown);
+        create_test_file(&dir, "copy-a.bin", &log[..200_000]);
+        create_test_file(&dir, "copy-b.bin", &log[..200_000]);
+
+        let (groups, _) = DuplicateFinder::with_defaults()
+            .find_duplicates(dir.path())
+            .unwrap();
+        assert_eq!(groups.len(), 1);
+
+        let config = FinderConfig::default()
+            .with_partial_similarity(true)
+            .with_partial_threshold(Some(0.8));
+        let (groups, summary) = DuplicateFinder::new(config)
+            .find_duplicates(dir.path())
+            .unwrap();
+        let partial: Vec<_> = groups
+            .iter()
+            .filter(|g| g.match_kind() == crate::duplicates::MatchKind::Partial)
+            .collect();
+        assert_eq!(partial.len(), 1);
+        assert_eq!(partial[0].files.len(), 2);
+        assert!(partial[0]
+            .files
+            .iter()
+            .any(|f| f.path.ends_with("app.log.1")));
+        assert!(partial[0].partial_similarity.unwrap() > 0.8);
+        assert_eq!(summary.partial_pairs, 1);
+        // The exact pair is not repeated as a partial one
+        assert_eq!(summary.duplicate_groups, 1);
+    }
+
     #[test]
     fn test_find_duplicates_path_not_found() {
         let finder = DuplicateFinder::with_defaults();
@@ -3679,6 +5795,272 @@ mod tests {
         assert_eq!(summary.duplicate_groups, 1);
         assert_eq!(summary.duplicate_files, 1); // 2 files - 1 original
         assert_eq!(summary.reclaimable_space, content.len() as u64);
+        assert_eq!(summary.size_histogram.buckets[0].count, 1);
+        assert_eq!(
+            summary.size_histogram.total_reclaimable(),
+            summary.reclaimable_space
+        );
+    }
+
+    #[test]
+    fn test_stable_order_gives_identical_output() {
+        let dir = TempDir::new().unwrap();
+        for (copies, content) in [(3, "alpha"), (2, "bravo"), (4, "charlie!"), (2, "delta")] {
+            for i in (0..copies).rev() {
+                let sub = dir.path().join(format!("d{i}"));
+                std::fs::create_dir_all(&sub).unwrap();
+                std::fs::write(sub.join(format!("{content}.txt")), content).unwrap();
+            }
+        }
+
+        let scan = || {
+            let finder = DuplicateFinder::new(FinderConfig::default().with_io_threads(4));
+            let (groups, _) = finder.find_duplicates(dir.path()).unwrap();
+            groups
+        };
+        let first = scan();
+        let second = scan();
+
+        let sizes: Vec<u64> = first.iter().map(|g| g.size).collect();
+        assert_eq!(sizes, vec![8, 5, 5, 5]);
+        assert!(first[1].hash < first[2].hash && first[2].hash < first[3].hash);
+        for group in &first {
+            assert!(group.files.windows(2).all(|w| w[0].path < w[1].path));
+        }
+        assert_eq!(
+            crate::output::CsvOutput::new(&first).to_string().unwrap(),
+            crate::output::CsvOutput::new(&second).to_string().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_min_group_size_filters_exact_groups() {
+        let dir = TempDir::new().unwrap();
+        // Groups of 2, 3 and 4 copies with distinct sizes
+        for (copies, content) in [(2, "pair"), (3, "triple!"), (4, "quadruple")] {
+            for i in 0..copies {
+                std::fs::write(dir.path().join(format!("{content}{i}.txt")), content).unwrap();
+            }
+        }
+
+        for (min, expected) in [
+            (2, vec![2, 3, 4]),
+            (3, vec![3, 4]),
+            (4, vec![4]),
+            (5, vec![]),
+        ] {
+            let finder = DuplicateFinder::new(FinderConfig::default().with_min_group_size(min));
+            let (groups, summary) = finder.find_duplicates(dir.path()).unwrap();
+
+            let mut sizes: Vec<usize> = groups.iter().map(|g| g.files.len()).collect();
+            sizes.sort_unstable();
+            assert_eq!(sizes, expected, "min_group_size = {min}");
+            assert_eq!(summary.duplicate_groups, groups.len());
+            assert_eq!(
+                summary.reclaimable_space,
+                groups.iter().map(|g| g.wasted_space()).sum::<u64>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_dedupe_within_splits_groups_at_boundaries() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        for sub in ["backup1", "backup2", "loose"] {
+            std::fs::create_dir(root.join(sub)).unwrap();
+        }
+        // Duplicated inside backup1, plus one copy in each other directory
+        let spread = "present in every directory";
+        std::fs::write(root.join("backup1/a.txt"), spread).unwrap();
+        std::fs::write(root.join("backup1/b.txt"), spread).unwrap();
+        std::fs::write(root.join("backup2/a.txt"), spread).unwrap();
+        std::fs::write(root.join("loose/a.txt"), spread).unwrap();
+        // Only duplicated across backups
+        let mirrored = "mirrored between backups";
+        std::fs::write(root.join("backup1/m.txt"), mirrored).unwrap();
+        std::fs::write(root.join("backup2/m.txt"), mirrored).unwrap();
+        // Only duplicated outside any boundary
+        let outside = "outside every boundary";
+        std::fs::write(root.join("loose/x.txt"), outside).unwrap();
+        std::fs::write(root.join("loose/y.txt"), outside).unwrap();
+
+        let finder = DuplicateFinder::new(
+            FinderConfig::default()
+                .with_dedupe_within(vec![root.join("backup1"), root.join("backup2")]),
+        );
+        let (groups, summary) = finder.find_duplicates(&root).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        let mut paths: Vec<_> = groups[0].files.iter().map(|f| f.path.clone()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![root.join("backup1/a.txt"), root.join("backup1/b.txt")]
+        );
+        assert_eq!(summary.duplicate_groups, 1);
+        assert_eq!(summary.duplicate_files, 1);
+        assert_eq!(summary.reclaimable_space, spread.len() as u64);
+
+        // Without boundaries every content is a duplicate group
+        let (groups, _) = DuplicateFinder::with_defaults()
+            .find_duplicates(&root)
+            .unwrap();
+        assert_eq!(groups.len(), 3);
+    }
+
+    #[test]
+    fn test_split_groups_by_boundaries_uses_innermost() {
+        let make = |path: &str| FileEntry::new(PathBuf::from(path), 10, SystemTime::now());
+        let group = crate::duplicates::DuplicateGroup::new(
+            [7u8; 32],
+            10,
+            vec![
+                make("/b/outer.txt"),
+                make("/b/inner/one.txt"),
+                make("/b/inner/two.txt"),
+                make("/c/other.txt"),
+            ],
+            Vec::new(),
+        );
+        let boundaries = vec![PathBuf::from("/b"), PathBuf::from("/b/inner")];
+
+        let split = split_groups_by_boundaries(vec![group], &boundaries);
+        assert_eq!(split.len(), 1);
+        assert_eq!(split[0].hash, [7u8; 32]);
+        assert_eq!(
+            split[0]
+                .files
+                .iter()
+                .map(|f| f.path.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("/b/inner/one.txt"),
+                PathBuf::from("/b/inner/two.txt")
+            ]
+        );
+        assert_eq!(
+            boundary_index(std::path::Path::new("/c/other.txt"), &boundaries),
+            None
+        );
+        assert_eq!(
+            boundary_index(std::path::Path::new("/bb/x.txt"), &boundaries),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_duplicates_excludes_empty_files_by_default() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("empty1.txt"), b"").unwrap();
+        std::fs::write(dir.path().join("empty2.txt"), b"").unwrap();
+        std::fs::write(dir.path().join("dup1.txt"), b"same").unwrap();
+        std::fs::write(dir.path().join("dup2.txt"), b"same").unwrap();
+
+        let finder = DuplicateFinder::with_defaults();
+        let (groups, summary) = finder.find_duplicates(dir.path()).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        assert!(!groups[0].is_empty_group);
+        assert_eq!(groups[0].size, 4);
+        assert_eq!(summary.duplicate_groups, 1);
+    }
+
+    #[test]
+    fn test_find_duplicates_include_empty() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("empty1.txt"), b"").unwrap();
+        std::fs::write(dir.path().join("empty2.txt"), b"").unwrap();
+        std::fs::write(dir.path().join("empty3.txt"), b"").unwrap();
+        std::fs::write(dir.path().join("unique.txt"), b"content").unwrap();
+
+        let finder = DuplicateFinder::new(FinderConfig::default().with_include_empty(true));
+        let (groups, summary) = finder.find_duplicates(dir.path()).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        assert!(groups[0].is_empty_group);
+        assert_eq!(groups[0].size, 0);
+        assert_eq!(groups[0].files.len(), 3);
+        assert_eq!(summary.total_files, 4);
+        assert_eq!(summary.duplicate_groups, 1);
+        assert_eq!(summary.duplicate_files, 2);
+        assert_eq!(summary.reclaimable_space, 0);
+    }
+
+    #[test]
+    fn test_find_duplicates_protect_policy_orders_files() {
+        let dir = TempDir::new().unwrap();
+        let nested = dir.path().join("nested");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(nested.join("deep.txt"), b"same content").unwrap();
+        std::fs::write(dir.path().join("top.txt"), b"same content").unwrap();
+
+        let config = FinderConfig::default()
+            .with_protect_policy(crate::duplicates::ProtectPolicy::ShallowestPath);
+        let finder = DuplicateFinder::new(config);
+        let (groups, _) = finder.find_duplicates(dir.path()).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        assert!(groups[0].files[0].path.ends_with("top.txt"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_find_duplicates_hardlink_policies() {
+        use crate::duplicates::HardlinkPolicy;
+
+        let dir = TempDir::new().unwrap();
+        let original = create_test_file(&dir, "original.bin", b"linked content").path;
+        std::fs::hard_link(&original, dir.path().join("link.bin")).unwrap();
+
+        let finder = DuplicateFinder::with_defaults();
+        let (groups, _) = finder.find_duplicates(dir.path()).unwrap();
+        assert!(groups.is_empty(), "hardlinks are skipped by default");
+
+        for policy in [HardlinkPolicy::Report, HardlinkPolicy::ReportButProtect] {
+            let finder = DuplicateFinder::new(FinderConfig::default().with_hardlinks(policy));
+            let (groups, summary) = finder.find_duplicates(dir.path()).unwrap();
+
+            assert_eq!(groups.len(), 1, "{policy} should report the hardlink");
+            assert_eq!(groups[0].files.len(), 2);
+            assert_eq!(groups[0].files.iter().filter(|f| f.is_hardlink).count(), 1);
+            assert_eq!(summary.total_files, 2);
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_find_duplicates_from_files_skips_tracked_hardlinks() {
+        use crate::duplicates::HardlinkPolicy;
+        use crate::scanner::HardlinkTracker;
+
+        let dir = TempDir::new().unwrap();
+        let original = create_test_file(&dir, "original.bin", b"linked content").path;
+        let link = dir.path().join("link.bin");
+        std::fs::hard_link(&original, &link).unwrap();
+
+        let mut tracker = HardlinkTracker::new();
+        let files: Vec<FileEntry> = [original, link]
+            .into_iter()
+            .map(|path| {
+                let meta = std::fs::metadata(&path).unwrap();
+                let mut entry = FileEntry::new(path, meta.len(), meta.modified().unwrap());
+                entry.is_hardlink = tracker.is_hardlink(&meta);
+                entry
+            })
+            .collect();
+        assert!(files[1].is_hardlink);
+
+        let skip = DuplicateFinder::with_defaults();
+        let (groups, summary) = skip.find_duplicates_from_files(files.clone()).unwrap();
+        assert!(groups.is_empty());
+        assert_eq!(summary.total_files, 1);
+
+        let report =
+            DuplicateFinder::new(FinderConfig::default().with_hardlinks(HardlinkPolicy::Report));
+        let (groups, _) = report.find_duplicates_from_files(files).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].files.len(), 2);
     }
 
     #[test]
@@ -3833,4 +6215,249 @@ mod tests {
         assert_eq!(groups.len(), 1);
         assert!(!groups[0].is_similar);
     }
+
+    struct InterruptingCallback {
+        phases: std::sync::Mutex<Vec<String>>,
+        interrupt_at: Option<&'static str>,
+        flag: Arc<AtomicBool>,
+    }
+
+    impl ProgressCallback for InterruptingCallback {
+        fn on_phase_start(&self, phase: &str, _total: usize) {
+            self.phases.lock().unwrap().push(phase.to_string());
+            if self.interrupt_at == Some(phase) {
+                self.flag.store(true, Ordering::SeqCst);
+            }
+        }
+
+        fn on_progress(&self, _current: usize, _path: &str) {}
+    }
+
+    #[test]
+    fn test_checkpoint_interrupt_and_resume() {
+        use std::fs;
+        let dir = TempDir::new().unwrap();
+        let scan_dir = dir.path().join("scan");
+        fs::create_dir(&scan_dir).unwrap();
+        fs::write(scan_dir.join("a.txt"), b"identical").unwrap();
+        fs::write(scan_dir.join("b.txt"), b"identical").unwrap();
+        fs::write(scan_dir.join("c.txt"), b"different").unwrap();
+        let checkpoint_path = dir.path().join("scan.checkpoint");
+        let paths = vec![scan_dir.clone()];
+
+        // First run: stop as soon as Phase 3 begins
+        let flag = Arc::new(AtomicBool::new(false));
+        let callback = Arc::new(InterruptingCallback {
+            phases: std::sync::Mutex::new(Vec::new()),
+            interrupt_at: Some("fullhash"),
+            flag: flag.clone(),
+        });
+        let finder = DuplicateFinder::new(
+            FinderConfig::default()
+                .with_shutdown_flag(flag)
+                .with_progress_callback(callback)
+                .with_checkpoint_path(checkpoint_path.clone()),
+        );
+        assert!(matches!(
+            finder.find_duplicates_in_paths(paths.clone()),
+            Err(FinderError::Interrupted)
+        ));
+
+        let checkpoint = ScanCheckpoint::load(&checkpoint_path).unwrap();
+        assert_eq!(checkpoint.stage, Some(CheckpointStage::Prehashed));
+        assert_eq!(checkpoint.counts.total_files, 3);
+        assert_eq!(checkpoint.prehash_groups().len(), 1);
+
+        // Second run: resume straight into Phase 3
+        let callback = Arc::new(InterruptingCallback {
+            phases: std::sync::Mutex::new(Vec::new()),
+            interrupt_at: None,
+            flag: Arc::new(AtomicBool::new(false)),
+        });
+        let finder = DuplicateFinder::new(
+            FinderConfig::default()
+                .with_progress_callback(callback.clone())
+                .with_checkpoint_path(checkpoint_path.clone())
+                .with_resume_checkpoint(checkpoint),
+        );
+        let (groups, summary) = finder.find_duplicates_in_paths(paths).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].files.len(), 2);
+        assert_eq!(summary.total_files, 3);
+        let phases = callback.phases.lock().unwrap();
+        assert!(!phases.iter().any(|p| p == "walking" || p == "prehash"));
+        assert!(phases.iter().any(|p| p == "fullhash"));
+        assert!(!checkpoint_path.exists());
+    }
+
+    #[test]
+    fn test_resume_rejects_mismatched_settings() {
+        let dir = TempDir::new().unwrap();
+        let paths = vec![dir.path().to_path_buf()];
+        let checkpoint = ScanCheckpoint::new(
+            paths.clone(),
+            &crate::scanner::WalkerConfig::default().with_skip_hidden(true),
+        );
+
+        let finder =
+            DuplicateFinder::new(FinderConfig::default().with_resume_checkpoint(checkpoint));
+        assert!(matches!(
+            finder.find_duplicates_in_paths(paths),
+            Err(FinderError::Checkpoint(_))
+        ));
+    }
+
+    #[test]
+    fn test_name_cluster_groups() {
+        use std::fs;
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        fs::create_dir(path.join("x")).unwrap();
+        fs::create_dir(path.join("y")).unwrap();
+
+        fs::write(path.join("photo.jpg"), b"a").unwrap();
+        fs::write(path.join("photo (1).jpg"), b"ab").unwrap();
+        fs::write(path.join("Photo copy.jpg"), b"abc").unwrap();
+        // Same name without a copy marker is not clustered
+        fs::write(path.join("x/index.html"), b"one").unwrap();
+        fs::write(path.join("y/index.html"), b"three").unwrap();
+        // Already an exact duplicate group
+        fs::write(path.join("doc.txt"), b"same").unwrap();
+        fs::write(path.join("doc copy.txt"), b"same").unwrap();
+
+        let finder = DuplicateFinder::new(FinderConfig::default().with_name_cluster(true));
+        let (groups, summary) = finder.find_duplicates(path).unwrap();
+
+        let named: Vec<_> = groups.iter().filter(|g| g.is_name_similar).collect();
+        assert_eq!(named.len(), 1);
+        assert_eq!(named[0].files.len(), 3);
+        assert_eq!(named[0].match_kind(), super::super::MatchKind::NameSimilar);
+        assert_eq!(groups.iter().filter(|g| !g.is_similar).count(), 1);
+        assert_eq!(summary.duplicate_groups, 1);
+
+        let finder = DuplicateFinder::with_defaults();
+        let (groups, _) = finder.find_duplicates(path).unwrap();
+        assert!(groups.iter().all(|g| !g.is_name_similar));
+    }
+
+    #[test]
+    fn test_stable_order_keeps_special_groups_after_exact_groups() {
+        use std::fs;
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        // A small exact group and a larger name cluster
+        fs::write(path.join("a.txt"), b"hi").unwrap();
+        fs::write(path.join("b.txt"), b"hi").unwrap();
+        fs::write(path.join("photo.jpg"), b"first photo").unwrap();
+        fs::write(path.join("photo (1).jpg"), b"another photo").unwrap();
+
+        let finder = DuplicateFinder::new(FinderConfig::default().with_name_cluster(true));
+        let (groups, _) = finder.find_duplicates(path).unwrap();
+
+        let kinds: Vec<_> = groups.iter().map(|g| g.match_kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                super::super::MatchKind::Exact,
+                super::super::MatchKind::NameSimilar
+            ]
+        );
+    }
+
+    /// Build a tree of `dirs` x `per_dir` files mixing exact duplicates,
+    /// same-size files differing after the prehash window, and unique sizes.
+    fn generate_tree(root: &std::path::Path, dirs: usize, per_dir: usize) {
+        for d in 0..dirs {
+            let dir = root.join(format!("d{}", d % 7)).join(format!("sub{d}"));
+            std::fs::create_dir_all(&dir).unwrap();
+            for i in 0..per_dir {
+                let n = d * per_dir + i;
+                let content = match n % 4 {
+                    // Exact duplicates in sets of up to 5
+                    0 => format!("duplicate {}", n % 20).into_bytes(),
+                    // Same size and prehash, different tail
+                    1 => {
+                        let mut bytes = vec![b'x'; 6000];
+                        bytes.extend_from_slice(format!("{:06}", n % 50).as_bytes());
+                        bytes
+                    }
+                    // Unique sizes
+                    2 => vec![b'u'; 10_000 + n],
+                    // Same size, different prehash
+                    _ => format!("{n:08}").into_bytes(),
+                };
+                std::fs::write(dir.join(format!("f{i}.bin")), content).unwrap();
+            }
+        }
+    }
+
+    fn normalized(groups: &[super::super::DuplicateGroup]) -> Vec<(Hash, u64, Vec<PathBuf>)> {
+        let mut out: Vec<_> = groups
+            .iter()
+            .map(|g| {
+                let mut paths: Vec<_> = g.files.iter().map(|f| f.path.clone()).collect();
+                paths.sort();
+                (g.hash, g.size, paths)
+            })
+            .collect();
+        out.sort();
+        out
+    }
+
+    fn assert_same_scan(
+        (serial_groups, serial): &(Vec<super::super::DuplicateGroup>, ScanSummary),
+        (piped_groups, piped): &(Vec<super::super::DuplicateGroup>, ScanSummary),
+    ) {
+        assert_eq!(normalized(serial_groups), normalized(piped_groups));
+        assert_eq!(serial.total_files, piped.total_files);
+        assert_eq!(serial.total_size, piped.total_size);
+        assert_eq!(serial.duplicate_groups, piped.duplicate_groups);
+        assert_eq!(serial.duplicate_files, piped.duplicate_files);
+        assert_eq!(serial.reclaimable_space, piped.reclaimable_space);
+        assert_eq!(serial.eliminated_by_size, piped.eliminated_by_size);
+        assert_eq!(serial.eliminated_by_prehash, piped.eliminated_by_prehash);
+        assert_eq!(serial.bloom_size_unique, piped.bloom_size_unique);
+        assert_eq!(serial.bloom_size_fp, piped.bloom_size_fp);
+    }
+
+    #[test]
+    fn test_pipelined_scan_matches_serial() {
+        let dir = TempDir::new().unwrap();
+        generate_tree(dir.path(), 60, 25);
+
+        let serial = DuplicateFinder::new(FinderConfig::default().with_pipeline(false));
+        let piped = DuplicateFinder::new(FinderConfig::default().with_pipeline(true));
+
+        let expected = serial.find_duplicates(dir.path()).unwrap();
+        assert_eq!(expected.1.total_files, 60 * 25);
+        assert!(expected.1.duplicate_groups > 20);
+        assert!(expected.1.eliminated_by_prehash > 0);
+        assert_same_scan(&expected, &piped.find_duplicates(dir.path()).unwrap());
+
+        let paths = vec![dir.path().join("d0"), dir.path().join("d3")];
+        assert_same_scan(
+            &serial.find_duplicates_in_paths(paths.clone()).unwrap(),
+            &piped.find_duplicates_in_paths(paths).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_pipelined_scan_uses_and_fills_cache() {
+        let dir = TempDir::new().unwrap();
+        generate_tree(dir.path(), 10, 20);
+        let cache_dir = TempDir::new().unwrap();
+        let cache = Arc::new(HashCache::new(&cache_dir.path().join("cache.db")).unwrap());
+
+        let piped = DuplicateFinder::new(FinderConfig::default().with_cache(cache.clone()));
+        let first = piped.find_duplicates(dir.path()).unwrap();
+        assert_eq!(first.1.cache_prehash_hits, 0);
+        assert!(first.1.cache_prehash_misses > 0);
+
+        let second = piped.find_duplicates(dir.path()).unwrap();
+        assert_eq!(second.1.cache_prehash_misses, 0);
+        assert_eq!(second.1.cache_prehash_hits, first.1.cache_prehash_misses);
+        assert_eq!(normalized(&first.0), normalized(&second.0));
+    }
 }
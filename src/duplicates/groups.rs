@@ -90,6 +90,47 @@ impl SizeGroup {
         self.files.push(file);
     }
 
+    /// Create an advisory group of files whose names match after normalization.
+    ///
+    /// Name-similar groups are never confirmed by content, so they are also
+    /// flagged as similar and excluded from duplicate statistics.
+    #[must_use]
+    pub fn new_name_similar(
+        normalized_name: &str,
+        files: Vec<FileEntry>,
+        reference_paths: Vec<std::path::PathBuf>,
+    ) -> Self {
+        let size = files.first().map_or(0, |f| f.size);
+        Self {
+            hash: *blake3::hash(normalized_name.as_bytes()).as_bytes(),
+            size,
+            files,
+            reference_paths,
+            is_similar: true,
+            is_empty_group: false,
+            is_name_similar: true,
+            partial_similarity: None,
+            likely_intentional: false,
+            ignore_case_paths: false,
+        }
+    }
+
+    /// How the files in this group were matched.
+    #[must_use]
+    pub fn match_kind(&self) -> MatchKind {
+        if self.partial_similarity.is_some() {
+            MatchKind::Partial
+        } else if self.is_name_similar {
+            MatchKind::NameSimilar
+        } else if self.is_similar {
+            MatchKind::Similar
+        } else if self.is_empty_group {
+            MatchKind::Empty
+        } else {
+            MatchKind::Exact
+        }
+    }
+
     /// Number of files in this group.
     #[must_use]
     pub fn len(&self) -> usize {
@@ -125,6 +166,179 @@ impl SizeGroup {
     }
 }
 
+/// Policy for automatically protecting one file per duplicate group.
+///
+/// The chosen file acts like a reference file: bulk selections never target
+/// it, so at least one copy always survives.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default, clap::ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProtectPolicy {
+    /// No automatic protection.
+    #[default]
+    None,
+    /// Protect the file with the oldest modification time.
+    Oldest,
+    /// Protect the file with the newest modification time.
+    Newest,
+    /// Protect the file with the fewest path components.
+    ShallowestPath,
+}
+
+impl ProtectPolicy {
+    /// Choose the protected file among `files`, if any.
+    ///
+    /// Ties are broken in favor of the earliest file in the slice.
+    #[must_use]
+    pub fn choose<'a>(&self, files: &'a [FileEntry]) -> Option<&'a FileEntry> {
+        match self {
+            Self::None => None,
+            Self::Oldest => files.iter().min_by_key(|f| f.modified),
+            Self::Newest => files.iter().rev().max_by_key(|f| f.modified),
+            Self::ShallowestPath => files.iter().min_by_key(|f| f.path.components().count()),
+        }
+    }
+}
+
+impl std::fmt::Display for ProtectPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Oldest => write!(f, "oldest"),
+            Self::Newest => write!(f, "newest"),
+            Self::ShallowestPath => write!(f, "shallowest-path"),
+        }
+    }
+}
+
+/// How hardlinked copies of an already-seen file are treated.
+///
+/// Hardlinks share one inode, so deleting any of them frees no space while
+/// another link remains. The default skips them during the walk.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default, clap::ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum HardlinkPolicy {
+    /// Exclude already-seen hardlinks from grouping.
+    #[default]
+    Skip,
+    /// Report hardlinks as ordinary duplicates.
+    Report,
+    /// Report hardlinks but never allow selecting them for deletion.
+    #[value(name = "protect")]
+    #[serde(rename = "protect")]
+    ReportButProtect,
+}
+
+impl std::fmt::Display for HardlinkPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Skip => write!(f, "skip"),
+            Self::Report => write!(f, "report"),
+            Self::ReportButProtect => write!(f, "protect"),
+        }
+    }
+}
+
+/// Number of hash hex characters in a group ID.
+pub const GROUP_ID_LEN: usize = 12;
+
+/// Hash prefix that group IDs are built from.
+///
+/// The prefix is the first [`GROUP_ID_LEN`] hex characters of the hash, so
+/// unlike a group's position it survives sorting, deletions and re-scans of
+/// the same files. It is only unique among groups with distinct hashes; see
+/// [`group_ids`] for the IDs reports and the TUI use.
+#[must_use]
+pub fn group_id(hash: &[u8; 32]) -> String {
+    let mut id = crate::scanner::hash_to_hex(hash);
+    id.truncate(GROUP_ID_LEN);
+    id
+}
+
+/// Stable external IDs of `groups`, in list order.
+///
+/// Each ID is the [`group_id`] of the group's hash. Similar groups, paranoid
+/// subsets and `--dedupe-within` splits can share a hash, so every group
+/// after the first with the same prefix gets an occurrence suffix
+/// (`abc123def456-2`, `-3`, ...). Occurrences are ranked by the groups'
+/// sorted paths rather than their positions, so sorting keeps the IDs.
+/// Reports, the TUI and the `apply` and `diff` subcommands all refer to
+/// groups by them.
+#[must_use]
+pub fn group_ids(groups: &[DuplicateGroup]) -> Vec<String> {
+    unique_group_ids(
+        groups
+            .iter()
+            .map(|g| (&g.hash, g.files.iter().map(|f| f.path.as_path()))),
+    )
+}
+
+/// [`group_ids`] for any list of `(hash, member paths)` pairs.
+pub(crate) fn unique_group_ids<'a, I, P>(groups: I) -> Vec<String>
+where
+    I: IntoIterator<Item = (&'a [u8; 32], P)>,
+    P: IntoIterator<Item = &'a std::path::Path>,
+{
+    let keyed: Vec<(String, Vec<&std::path::Path>)> = groups
+        .into_iter()
+        .map(|(hash, paths)| {
+            let mut paths: Vec<_> = paths.into_iter().collect();
+            paths.sort_unstable();
+            (group_id(hash), paths)
+        })
+        .collect();
+
+    let mut by_prefix: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, (prefix, _)) in keyed.iter().enumerate() {
+        by_prefix.entry(prefix.as_str()).or_default().push(index);
+    }
+
+    let mut ids: Vec<String> = keyed.iter().map(|(prefix, _)| prefix.clone()).collect();
+    for indices in by_prefix.values_mut().filter(|indices| indices.len() > 1) {
+        indices.sort_by(|&a, &b| keyed[a].1.cmp(&keyed[b].1).then(a.cmp(&b)));
+        for (occurrence, &index) in indices.iter().enumerate().skip(1) {
+            ids[index] = format!("{}-{}", keyed[index].0, occurrence + 1);
+        }
+    }
+    ids
+}
+
+/// How the files in a [`DuplicateGroup`] were matched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchKind {
+    /// Identical content confirmed by full hash.
+    #[default]
+    Exact,
+    /// Perceptually similar images or documents.
+    Similar,
+    /// Zero-byte files (see `--include-empty`).
+    Empty,
+    /// Advisory group of files sharing a normalized filename.
+    NameSimilar,
+    /// Pair of files sharing most of their content (see `--partial-similarity`).
+    Partial,
+}
+
+impl MatchKind {
+    /// Position of this kind's section in group listings.
+    ///
+    /// Exact groups come first, followed by similar, partial, name-cluster
+    /// and empty-file groups, the order in which a scan produces them.
+    #[must_use]
+    pub fn section(self) -> u8 {
+        match self {
+            Self::Exact => 0,
+            Self::Similar => 1,
+            Self::Partial => 2,
+            Self::NameSimilar => 3,
+            Self::Empty => 4,
+        }
+    }
+}
+
 /// Confirmed duplicate group of files.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DuplicateGroup {
@@ -140,6 +354,21 @@ pub struct DuplicateGroup {
     /// Whether this is a similarity-based group rather than an exact duplicate
     #[serde(default)]
     pub is_similar: bool,
+    /// Whether this group holds zero-byte files rather than content duplicates
+    #[serde(default)]
+    pub is_empty_group: bool,
+    /// Whether this is an advisory group of files sharing a normalized filename
+    #[serde(default)]
+    pub is_name_similar: bool,
+    /// Fraction of content shared by a partial duplicate pair (0.0 to 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial_similarity: Option<f64>,
+    /// Whether the files look like deliberate copies (backups, parallel trees)
+    #[serde(default)]
+    pub likely_intentional: bool,
+    /// Whether reference paths are matched case-insensitively (`--ignore-case-paths`)
+    #[serde(default)]
+    pub ignore_case_paths: bool,
 }
 
 impl DuplicateGroup {
@@ -164,6 +393,63 @@ impl DuplicateGroup {
             files,
             reference_paths,
             is_similar: false,
+            is_empty_group: false,
+            is_name_similar: false,
+            partial_similarity: None,
+            likely_intentional: false,
+            ignore_case_paths: false,
+        }
+    }
+
+    /// Create a group of zero-byte files.
+    ///
+    /// All empty files share the same content hash, so they are collected into
+    /// a single group keyed by the BLAKE3 hash of empty input.
+    #[must_use]
+    pub fn new_empty(files: Vec<FileEntry>, reference_paths: Vec<std::path::PathBuf>) -> Self {
+        Self {
+            hash: *blake3::hash(&[]).as_bytes(),
+            size: 0,
+            files,
+            reference_paths,
+            is_similar: false,
+            is_empty_group: true,
+            is_name_similar: false,
+            partial_similarity: None,
+            likely_intentional: false,
+            ignore_case_paths: false,
+        }
+    }
+
+    /// Create a pair of files sharing `similarity` of their content.
+    ///
+    /// Partial duplicates differ somewhere, so like other similarity groups
+    /// they are excluded from duplicate statistics. The group hash is derived
+    /// from the paths.
+    #[must_use]
+    pub fn new_partial(
+        similarity: f64,
+        files: Vec<FileEntry>,
+        reference_paths: Vec<std::path::PathBuf>,
+    ) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"partial");
+        for file in &files {
+            hasher.update(file.path.as_os_str().as_encoded_bytes());
+            hasher.update(&[0]);
+        }
+        let size = files.first().map_or(0, |f| f.size);
+        Self {
+            hash: *hasher.finalize().as_bytes(),
+            size,
+            files,
+            reference_paths,
+            is_similar: true,
+            is_empty_group: false,
+            is_name_similar: false,
+            partial_similarity: Some(similarity),
+            likely_intentional: false,
+            ignore_case_paths: false,
         }
     }
 
@@ -181,6 +467,11 @@ impl DuplicateGroup {
             files,
             reference_paths,
             is_similar: true,
+            is_empty_group: false,
+            is_name_similar: false,
+            partial_similarity: None,
+            likely_intentional: false,
+            ignore_case_paths: false,
         }
     }
 
@@ -232,24 +523,37 @@ impl DuplicateGroup {
 
     /// Check if a path is in a protected reference directory.
     ///
+    /// Case is ignored on Windows and when [`Self::ignore_case_paths`] is set.
+    ///
     /// # Arguments
     ///
     /// * `path` - The path to check
     #[must_use]
     pub fn is_in_reference_dir(&self, path: &std::path::Path) -> bool {
         self.reference_paths.iter().any(|ref_path| {
-            if cfg!(windows) {
-                // Windows is case-insensitive. Convert to lowercase PathBuf for reliable
-                // component-based comparison.
-                let p = std::path::PathBuf::from(path.to_string_lossy().to_lowercase());
-                let r = std::path::PathBuf::from(ref_path.to_string_lossy().to_lowercase());
-                p.starts_with(r)
-            } else {
-                path.starts_with(ref_path)
-            }
+            crate::scanner::path_starts_with(path, ref_path, self.ignore_case_paths)
         })
     }
 
+    /// The file protected by `policy` in this group, if any.
+    #[must_use]
+    pub fn protected_file(&self, policy: ProtectPolicy) -> Option<&FileEntry> {
+        policy.choose(&self.files)
+    }
+
+    /// Move the file protected by `policy` to the front of the group.
+    ///
+    /// The first file is treated as the original to keep by script output
+    /// and by the TUI's keep-first selections.
+    pub fn apply_protect_policy(&mut self, policy: ProtectPolicy) {
+        let index = self
+            .protected_file(policy)
+            .and_then(|p| self.files.iter().position(|f| f.path == p.path));
+        if let Some(index) = index {
+            self.files[..=index].rotate_right(1);
+        }
+    }
+
     /// Create a new group containing only the files present in the given selection set.
     ///
     /// Returns `None` if no files from this group are in the selection set.
@@ -297,6 +601,23 @@ pub fn filter_selected(
         .map(|g| g.files.iter().map(|f| f.size).sum::<u64>())
         .sum();
     new_summary.total_duplicate_size = new_summary.reclaimable_space;
+    new_summary.size_histogram = crate::duplicates::SizeHistogram::new();
+    for g in &filtered_groups {
+        new_summary.size_histogram.record(
+            g.size,
+            g.files.len(),
+            g.files.iter().map(|f| f.size).sum(),
+        );
+    }
+    new_summary.reclaimable_by_extension = crate::duplicates::ExtensionTallies::new();
+    for f in filtered_groups.iter().flat_map(|g| &g.files) {
+        let tally = new_summary
+            .reclaimable_by_extension
+            .entry(crate::duplicates::extensions::extension_key(&f.path))
+            .or_default();
+        tally.0 += 1;
+        tally.1 += f.size;
+    }
 
     (filtered_groups, new_summary)
 }
@@ -348,6 +669,56 @@ impl GroupingStats {
     }
 }
 
+/// Keep only files whose basename occurs more than once (`--same-name-only`).
+///
+/// A cheap pre-filter for Phase 1: uniquely named files are dropped before
+/// any hashing. This is an approximation. Duplicates that were renamed, such
+/// as `IMG_0001.jpg` and `holiday.jpg`, are missed. Names are compared
+/// exactly, so `a.JPG` and `a.jpg` differ.
+///
+/// Returns the kept files in their original order and the number dropped.
+///
+/// # Example
+///
+/// ```
+/// use rustdupe::scanner::FileEntry;
+/// use rustdupe::duplicates::retain_duplicated_names;
+/// use std::path::PathBuf;
+/// use std::time::SystemTime;
+///
+/// let files = vec![
+///     FileEntry::new(PathBuf::from("/a/photo.jpg"), 100, SystemTime::now()),
+///     FileEntry::new(PathBuf::from("/b/photo.jpg"), 100, SystemTime::now()),
+///     FileEntry::new(PathBuf::from("/b/copy.jpg"), 100, SystemTime::now()),
+/// ];
+///
+/// let (kept, dropped) = retain_duplicated_names(files);
+/// assert_eq!(kept.len(), 2);
+/// assert_eq!(dropped, 1);
+/// ```
+#[must_use]
+pub fn retain_duplicated_names(files: Vec<FileEntry>) -> (Vec<FileEntry>, usize) {
+    let mut name_counts: HashMap<&std::ffi::OsStr, usize> = HashMap::new();
+    for file in &files {
+        if let Some(name) = file.path.file_name() {
+            *name_counts.entry(name).or_default() += 1;
+        }
+    }
+    let keep: Vec<bool> = files
+        .iter()
+        .map(|f| f.path.file_name().is_some_and(|name| name_counts[name] > 1))
+        .collect();
+
+    let before = files.len();
+    let kept: Vec<FileEntry> = files
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(file, keep)| keep.then_some(file))
+        .collect();
+    let dropped = before - kept.len();
+    (kept, dropped)
+}
+
 /// Group files by size (Phase 1 of duplicate detection).
 ///
 /// This is the first phase of duplicate detection. It groups all files by their
@@ -529,6 +900,112 @@ mod tests {
         FileEntry::new(PathBuf::from(path), size, SystemTime::now())
     }
 
+    fn make_dated_file(path: &str, secs: u64) -> FileEntry {
+        FileEntry::new(
+            PathBuf::from(path),
+            100,
+            SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs),
+        )
+    }
+
+    #[test]
+    fn test_group_id() {
+        let mut hash = [0u8; 32];
+        hash[..7].copy_from_slice(&[0xab, 0xcd, 0xef, 0x01, 0x23, 0x45, 0x67]);
+        let group = DuplicateGroup::new(hash, 1, vec![make_file("/a", 1)], Vec::new());
+        assert_eq!(group_id(&group.hash), "abcdef012345");
+        assert_eq!(group_id(&group.hash).len(), GROUP_ID_LEN);
+        assert_eq!(
+            group_ids(std::slice::from_ref(&group)),
+            vec!["abcdef012345"]
+        );
+    }
+
+    #[test]
+    fn test_group_ids_unique_for_shared_hash() {
+        let shared = [7u8; 32];
+        let a = DuplicateGroup::new(shared, 1, vec![make_file("/a1", 1)], Vec::new());
+        let b = DuplicateGroup::new(shared, 1, vec![make_file("/b1", 1)], Vec::new());
+        let other = DuplicateGroup::new([8u8; 32], 1, vec![make_file("/c1", 1)], Vec::new());
+
+        let ids = group_ids(&[a.clone(), other.clone(), b.clone()]);
+        assert_eq!(ids, vec!["070707070707", "080808080808", "070707070707-2"]);
+
+        // Occurrences follow the file sets, not the list order
+        let reordered = group_ids(&[b, other, a]);
+        assert_eq!(
+            reordered,
+            vec!["070707070707-2", "080808080808", "070707070707"]
+        );
+    }
+
+    #[test]
+    fn test_match_kind() {
+        let files = vec![make_file("/a.jpg", 10), make_file("/a (1).jpg", 12)];
+        let exact = DuplicateGroup::new([0u8; 32], 10, files.clone(), Vec::new());
+        let similar = DuplicateGroup::new_similar([1u8; 32], files.clone(), Vec::new());
+        let empty = DuplicateGroup::new_empty(files.clone(), Vec::new());
+        let partial = DuplicateGroup::new_partial(0.9, files.clone(), Vec::new());
+        let named = DuplicateGroup::new_name_similar("a.jpg", files, Vec::new());
+
+        assert_eq!(exact.match_kind(), MatchKind::Exact);
+        assert_eq!(similar.match_kind(), MatchKind::Similar);
+        assert_eq!(empty.match_kind(), MatchKind::Empty);
+        assert_eq!(partial.match_kind(), MatchKind::Partial);
+        assert!(partial.is_similar);
+        assert_eq!(named.match_kind(), MatchKind::NameSimilar);
+        assert!(named.is_similar);
+        assert_eq!(named.size, 10);
+    }
+
+    #[test]
+    fn test_protect_policy_choose() {
+        let files = vec![
+            make_dated_file("/a/b/c/mid.txt", 20),
+            make_dated_file("/a/old.txt", 10),
+            make_dated_file("/a/b/new.txt", 30),
+        ];
+
+        assert!(ProtectPolicy::None.choose(&files).is_none());
+        assert_eq!(
+            ProtectPolicy::Oldest.choose(&files).unwrap().path,
+            PathBuf::from("/a/old.txt")
+        );
+        assert_eq!(
+            ProtectPolicy::Newest.choose(&files).unwrap().path,
+            PathBuf::from("/a/b/new.txt")
+        );
+        assert_eq!(
+            ProtectPolicy::ShallowestPath.choose(&files).unwrap().path,
+            PathBuf::from("/a/old.txt")
+        );
+    }
+
+    #[test]
+    fn test_apply_protect_policy_moves_file_first() {
+        let mut group = DuplicateGroup::new(
+            [0u8; 32],
+            100,
+            vec![
+                make_dated_file("/x.txt", 20),
+                make_dated_file("/y.txt", 30),
+                make_dated_file("/z.txt", 10),
+            ],
+            Vec::new(),
+        );
+
+        group.apply_protect_policy(ProtectPolicy::Oldest);
+        let order: Vec<_> = group.files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(
+            order,
+            vec![
+                PathBuf::from("/z.txt"),
+                PathBuf::from("/x.txt"),
+                PathBuf::from("/y.txt")
+            ]
+        );
+    }
+
     #[test]
     fn test_size_group_new() {
         let group = SizeGroup::new(1024);
@@ -615,6 +1092,42 @@ mod tests {
         assert_eq!(group.duplicate_count(), 0);
     }
 
+    #[test]
+    fn test_retain_duplicated_names() {
+        let now = SystemTime::now();
+        let files = vec![
+            // Same name, same content: survives
+            FileEntry::new(PathBuf::from("/a/report.pdf"), 500, now),
+            FileEntry::new(PathBuf::from("/b/report.pdf"), 500, now),
+            // Same content, unique names: dropped even though duplicates
+            FileEntry::new(PathBuf::from("/a/IMG_0001.jpg"), 900, now),
+            FileEntry::new(PathBuf::from("/b/holiday.jpg"), 900, now),
+            // Same name, different size: survives the name filter
+            FileEntry::new(PathBuf::from("/c/report.pdf"), 700, now),
+            // Names compare exactly
+            FileEntry::new(PathBuf::from("/c/holiday.JPG"), 900, now),
+        ];
+
+        let (kept, dropped) = retain_duplicated_names(files);
+        let paths: Vec<&Path> = kept.iter().map(|f| f.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("/a/report.pdf"),
+                Path::new("/b/report.pdf"),
+                Path::new("/c/report.pdf"),
+            ]
+        );
+        assert_eq!(dropped, 3);
+
+        // Only same-named duplicates make it to size grouping
+        let (groups, _) = group_by_size(kept);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[&500].len(), 2);
+
+        assert_eq!(retain_duplicated_names(Vec::new()), (Vec::new(), 0));
+    }
+
     #[test]
     fn test_group_by_size_empty_input() {
         let files: Vec<FileEntry> = vec![];
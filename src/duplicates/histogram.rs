@@ -0,0 +1,335 @@
+//! Size histogram of duplicate files.
+//!
+//! Buckets duplicate files into log-scale size ranges so reports can show
+//! where the reclaimable space actually lives: a million tiny files or a
+//! handful of disk images. Bucket boundaries come from
+//! [`SIZE_HISTOGRAM_BUCKETS`].
+//!
+//! Each group contributes its duplicate copies (every file except the one
+//! that would be kept) to the bucket for its file size, so bucket counts sum
+//! to [`ScanSummary::duplicate_files`](super::ScanSummary::duplicate_files)
+//! and bucket bytes sum to
+//! [`ScanSummary::reclaimable_space`](super::ScanSummary::reclaimable_space).
+//!
+//! When most duplicate files are small but most reclaimable space sits in
+//! large ones, [`SizeHistogram::recommend_min_size`] suggests a `--min-size`
+//! at a bucket boundary that keeps the space and drops the clutter.
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::duplicates::SizeHistogram;
+//!
+//! let mut histogram = SizeHistogram::new();
+//! histogram.record(4096, 2, 8192);
+//! assert_eq!(histogram.buckets[1].count, 2);
+//! assert_eq!(histogram.total_reclaimable(), 8192);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::DuplicateGroup;
+
+/// Histogram buckets as `(label, exclusive upper bound in bytes)`.
+///
+/// Bounds must be ascending; the last bucket should use `u64::MAX` so every
+/// size lands somewhere. Sizes at or above the last bound are counted in the
+/// last bucket.
+pub const SIZE_HISTOGRAM_BUCKETS: [(&str, u64); 4] = [
+    ("0-1KB", 1024),
+    ("1KB-1MB", 1024 * 1024),
+    ("1MB-1GB", 1024 * 1024 * 1024),
+    (">1GB", u64::MAX),
+];
+
+/// Share of reclaimable space a recommended `--min-size` must keep.
+pub const MIN_SIZE_HINT_SPACE_SHARE: f64 = 0.9;
+
+/// Share of duplicate files a recommended `--min-size` must drop.
+///
+/// Below this the threshold would hide too little to be worth suggesting.
+pub const MIN_SIZE_HINT_FILE_SHARE: f64 = 0.5;
+
+/// A `--min-size` suggested by [`SizeHistogram::recommend_min_size`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinSizeRecommendation {
+    /// Suggested minimum file size (bytes, a bucket boundary)
+    pub min_size: u64,
+    /// Fraction of reclaimable space in files at or above `min_size`
+    pub space_share: f64,
+    /// Fraction of duplicate files below `min_size`
+    pub file_share: f64,
+}
+
+impl MinSizeRecommendation {
+    /// The threshold as a `--min-size` argument (e.g. `1MiB`).
+    #[must_use]
+    pub fn min_size_arg(&self) -> String {
+        const UNITS: [(u64, &str); 3] = [(1 << 30, "GiB"), (1 << 20, "MiB"), (1 << 10, "KiB")];
+        UNITS
+            .iter()
+            .find(|&&(unit, _)| self.min_size >= unit && self.min_size % unit == 0)
+            .map_or_else(
+                || self.min_size.to_string(),
+                |&(unit, suffix)| format!("{}{}", self.min_size / unit, suffix),
+            )
+    }
+}
+
+/// A single size range in a [`SizeHistogram`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeBucket {
+    /// Human-readable range label (e.g. "1KB-1MB")
+    pub label: String,
+    /// Smallest file size in this bucket (inclusive, bytes)
+    pub min_size: u64,
+    /// Largest file size in this bucket (exclusive, bytes), or `None` if unbounded
+    #[serde(default)]
+    pub max_size: Option<u64>,
+    /// Number of duplicate files (excluding originals) in this range
+    pub count: usize,
+    /// Space reclaimable from files in this range (bytes)
+    pub reclaimable_space: u64,
+}
+
+/// Duplicate files bucketed by size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SizeHistogram {
+    /// Buckets in ascending size order, one per [`SIZE_HISTOGRAM_BUCKETS`] entry
+    pub buckets: Vec<SizeBucket>,
+}
+
+impl Default for SizeHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SizeHistogram {
+    /// Create an empty histogram with the standard buckets.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut min_size = 0;
+        let buckets = SIZE_HISTOGRAM_BUCKETS
+            .iter()
+            .map(|&(label, upper)| {
+                let bucket = SizeBucket {
+                    label: label.to_string(),
+                    min_size,
+                    max_size: (upper != u64::MAX).then_some(upper),
+                    count: 0,
+                    reclaimable_space: 0,
+                };
+                min_size = upper;
+                bucket
+            })
+            .collect();
+        Self { buckets }
+    }
+
+    /// Build a histogram from duplicate groups.
+    #[must_use]
+    pub fn from_groups(groups: &[DuplicateGroup]) -> Self {
+        let mut histogram = Self::new();
+        for group in groups {
+            histogram.add_group(group);
+        }
+        histogram
+    }
+
+    /// Index of the bucket holding files of `size` bytes.
+    #[must_use]
+    pub fn bucket_index(size: u64) -> usize {
+        SIZE_HISTOGRAM_BUCKETS
+            .iter()
+            .position(|&(_, upper)| size < upper)
+            .unwrap_or(SIZE_HISTOGRAM_BUCKETS.len() - 1)
+    }
+
+    /// Add `count` files of `size` bytes accounting for `reclaimable` bytes.
+    pub fn record(&mut self, size: u64, count: usize, reclaimable: u64) {
+        if let Some(bucket) = self.buckets.get_mut(Self::bucket_index(size)) {
+            bucket.count += count;
+            bucket.reclaimable_space += reclaimable;
+        }
+    }
+
+    /// Add the duplicate copies of a group.
+    pub fn add_group(&mut self, group: &DuplicateGroup) {
+        self.record(group.size, group.duplicate_count(), group.wasted_space());
+    }
+
+    /// Remove the duplicate copies of a group previously added.
+    pub fn remove_group(&mut self, group: &DuplicateGroup) {
+        if let Some(bucket) = self.buckets.get_mut(Self::bucket_index(group.size)) {
+            bucket.count = bucket.count.saturating_sub(group.duplicate_count());
+            bucket.reclaimable_space = bucket
+                .reclaimable_space
+                .saturating_sub(group.wasted_space());
+        }
+    }
+
+    /// Total number of files across all buckets.
+    #[must_use]
+    pub fn total_count(&self) -> usize {
+        self.buckets.iter().map(|b| b.count).sum()
+    }
+
+    /// Total reclaimable bytes across all buckets.
+    #[must_use]
+    pub fn total_reclaimable(&self) -> u64 {
+        self.buckets.iter().map(|b| b.reclaimable_space).sum()
+    }
+
+    /// Whether no files have been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.total_count() == 0
+    }
+
+    /// Suggest a `--min-size` when reclaimable space is concentrated in large files.
+    ///
+    /// Picks the highest bucket boundary that keeps at least
+    /// [`MIN_SIZE_HINT_SPACE_SHARE`] of the reclaimable space while dropping
+    /// at least [`MIN_SIZE_HINT_FILE_SHARE`] of the duplicate files. Returns
+    /// `None` if no boundary does both.
+    #[must_use]
+    pub fn recommend_min_size(&self) -> Option<MinSizeRecommendation> {
+        let total_space = self.total_reclaimable();
+        let total_count = self.total_count();
+        if total_space == 0 || total_count == 0 {
+            return None;
+        }
+        (1..self.buckets.len()).rev().find_map(|i| {
+            let (below, above) = self.buckets.split_at(i);
+            let space_share =
+                above.iter().map(|b| b.reclaimable_space).sum::<u64>() as f64 / total_space as f64;
+            let file_share =
+                below.iter().map(|b| b.count).sum::<usize>() as f64 / total_count as f64;
+            (space_share >= MIN_SIZE_HINT_SPACE_SHARE && file_share >= MIN_SIZE_HINT_FILE_SHARE)
+                .then_some(MinSizeRecommendation {
+                    min_size: above[0].min_size,
+                    space_share,
+                    file_share,
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+    const GB: u64 = 1024 * MB;
+
+    fn group(size: u64, copies: usize) -> DuplicateGroup {
+        let files = (0..copies)
+            .map(|i| {
+                FileEntry::new(
+                    PathBuf::from(format!("/data/{}-{}.bin", size, i)),
+                    size,
+                    SystemTime::UNIX_EPOCH,
+                )
+            })
+            .collect();
+        DuplicateGroup::new([size as u8; 32], size, files, Vec::new())
+    }
+
+    #[test]
+    fn test_bucket_boundaries() {
+        assert_eq!(SizeHistogram::bucket_index(0), 0);
+        assert_eq!(SizeHistogram::bucket_index(KB - 1), 0);
+        assert_eq!(SizeHistogram::bucket_index(KB), 1);
+        assert_eq!(SizeHistogram::bucket_index(MB - 1), 1);
+        assert_eq!(SizeHistogram::bucket_index(MB), 2);
+        assert_eq!(SizeHistogram::bucket_index(GB - 1), 2);
+        assert_eq!(SizeHistogram::bucket_index(GB), 3);
+        assert_eq!(SizeHistogram::bucket_index(u64::MAX), 3);
+    }
+
+    #[test]
+    fn test_new_histogram_ranges() {
+        let histogram = SizeHistogram::new();
+        assert_eq!(histogram.buckets.len(), SIZE_HISTOGRAM_BUCKETS.len());
+        assert_eq!(histogram.buckets[0].min_size, 0);
+        assert_eq!(histogram.buckets[1].min_size, KB);
+        assert_eq!(histogram.buckets[1].max_size, Some(MB));
+        assert_eq!(histogram.buckets[3].label, ">1GB");
+        assert_eq!(histogram.buckets[3].max_size, None);
+        assert!(histogram.is_empty());
+    }
+
+    #[test]
+    fn test_groups_land_in_correct_buckets() {
+        let groups = vec![
+            group(100, 3),
+            group(KB, 2),
+            group(5 * MB, 2),
+            group(2 * GB, 2),
+            group(0, 2),
+        ];
+        let histogram = SizeHistogram::from_groups(&groups);
+
+        assert_eq!(histogram.buckets[0].count, 3); // 2 x 100 B + 1 empty file
+        assert_eq!(histogram.buckets[0].reclaimable_space, 200);
+        assert_eq!(histogram.buckets[1].count, 1);
+        assert_eq!(histogram.buckets[1].reclaimable_space, KB);
+        assert_eq!(histogram.buckets[2].count, 1);
+        assert_eq!(histogram.buckets[2].reclaimable_space, 5 * MB);
+        assert_eq!(histogram.buckets[3].count, 1);
+        assert_eq!(histogram.buckets[3].reclaimable_space, 2 * GB);
+    }
+
+    #[test]
+    fn test_totals_reconcile_with_summary() {
+        let groups = vec![group(10, 4), group(3 * KB, 2), group(7 * MB, 3)];
+        let mut stats = crate::duplicates::FullhashStats::default();
+        stats.calculate_wasted_space(&groups);
+
+        let mut histogram = SizeHistogram::from_groups(&groups);
+        assert_eq!(histogram.total_count(), stats.duplicate_files);
+        assert_eq!(histogram.total_reclaimable(), stats.wasted_space);
+
+        histogram.remove_group(&groups[2]);
+        assert_eq!(histogram.total_count(), stats.duplicate_files - 2);
+        assert_eq!(histogram.total_reclaimable(), stats.wasted_space - 14 * MB);
+        assert_eq!(histogram.buckets[2], SizeHistogram::new().buckets[2]);
+    }
+
+    #[test]
+    fn test_recommend_min_size() {
+        // Thousands of tiny duplicates, nearly all space in a few videos
+        let mut histogram = SizeHistogram::new();
+        histogram.record(200, 3000, 600 * KB);
+        histogram.record(50 * KB, 400, 20 * MB);
+        histogram.record(700 * MB, 6, 4 * GB);
+        let hint = histogram.recommend_min_size().unwrap();
+        assert_eq!(hint.min_size, MB);
+        assert_eq!(hint.min_size_arg(), "1MiB");
+        assert!(hint.space_share > 0.99);
+        assert!(hint.file_share > 0.99);
+
+        // Space spread across the mid-range: only the tiny files can go
+        let mut histogram = SizeHistogram::new();
+        histogram.record(200, 3000, 600 * KB);
+        histogram.record(500 * KB, 400, 2 * GB);
+        histogram.record(700 * MB, 2, 2 * GB);
+        let hint = histogram.recommend_min_size().unwrap();
+        assert_eq!(hint.min_size, KB);
+        assert_eq!(hint.min_size_arg(), "1KiB");
+
+        // Balanced: nothing to recommend
+        let mut histogram = SizeHistogram::new();
+        histogram.record(200, 10, 2 * KB);
+        histogram.record(500 * KB, 10, 150 * MB);
+        histogram.record(20 * MB, 10, 200 * MB);
+        assert_eq!(histogram.recommend_min_size(), None);
+        assert_eq!(SizeHistogram::new().recommend_min_size(), None);
+    }
+}
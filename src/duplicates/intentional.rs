@@ -0,0 +1,300 @@
+//! Heuristics for duplicate groups that were probably made on purpose.
+//!
+//! Some duplicates are copies nobody wants removed: a `config.toml.bak`
+//! next to `config.toml`, or the same `src/util.rs` in `v1/` and `v2/`
+//! checkouts of a project. [`IntentionalPatterns::classify`] tags such
+//! groups so the TUI can sink them below the incidental duplicates. A group
+//! is likely intentional when any rule covers all of its files:
+//!
+//! - **Backup siblings**: every file shares its directory with another file
+//!   of the group whose name is its own plus a backup suffix (`a.txt` and
+//!   `a.txt.bak`), or its stem plus a backup suffix (`a.txt` and `a.bak`).
+//! - **Boilerplate names**: every file has the same basename, and that name
+//!   is one expected to repeat across projects (`LICENSE`, `__init__.py`).
+//! - **Parallel trees**: every file has the same basename and depth, and
+//!   the paths differ in exactly one directory that is not the parent
+//!   (`/p/v1/src/a.rs` and `/p/v2/src/a.rs`). Copies in sibling
+//!   directories (`/a/x.jpg` and `/b/x.jpg`) do not count.
+//!
+//! The tag is advisory: intentional groups are still reported and can still
+//! be deleted.
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::duplicates::IntentionalPatterns;
+//! use rustdupe::scanner::FileEntry;
+//! use std::path::PathBuf;
+//! use std::time::SystemTime;
+//!
+//! let files: Vec<FileEntry> = ["/etc/app.conf", "/etc/app.conf.orig"]
+//!     .iter()
+//!     .map(|p| FileEntry::new(PathBuf::from(p), 10, SystemTime::UNIX_EPOCH))
+//!     .collect();
+//! assert!(IntentionalPatterns::default().classify(&files));
+//! ```
+
+use std::ffi::OsStr;
+use std::path::{Component, Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::scanner::FileEntry;
+
+/// Default suffixes marking a file as a backup of a sibling.
+pub const DEFAULT_BACKUP_SUFFIXES: &[&str] = &[".bak", ".orig", ".old", ".backup", ".prev", "~"];
+
+/// Default basenames expected to repeat across unrelated directories.
+pub const DEFAULT_BOILERPLATE_NAMES: &[&str] = &[
+    "__init__.py",
+    ".gitignore",
+    ".gitkeep",
+    ".keep",
+    ".npmignore",
+    "LICENSE",
+    "LICENSE.md",
+    "LICENSE.txt",
+    "COPYING",
+];
+
+/// Configurable patterns for spotting likely-intentional duplicate groups.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntentionalPatterns {
+    /// Enable the classifier (default: true).
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Suffixes marking a file as a backup of a sibling in the same directory.
+    #[serde(default = "default_backup_suffixes")]
+    pub backup_suffixes: Vec<String>,
+    /// Basenames whose copies are expected anywhere.
+    #[serde(default = "default_boilerplate_names")]
+    pub boilerplate_names: Vec<String>,
+    /// Treat same-named files in parallel directory trees as intentional.
+    #[serde(default = "default_true")]
+    pub parallel_trees: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_backup_suffixes() -> Vec<String> {
+    DEFAULT_BACKUP_SUFFIXES
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn default_boilerplate_names() -> Vec<String> {
+    DEFAULT_BOILERPLATE_NAMES
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+impl Default for IntentionalPatterns {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            backup_suffixes: default_backup_suffixes(),
+            boilerplate_names: default_boilerplate_names(),
+            parallel_trees: true,
+        }
+    }
+}
+
+impl IntentionalPatterns {
+    /// Patterns that never tag a group.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    /// Whether the files of a group look like deliberate copies.
+    #[must_use]
+    pub fn classify(&self, files: &[FileEntry]) -> bool {
+        if !self.enabled || files.len() < 2 {
+            return false;
+        }
+        self.all_backup_siblings(files)
+            || self.all_boilerplate(files)
+            || (self.parallel_trees && in_parallel_trees(files))
+    }
+
+    /// Whether `backup` is named like a backup of `original`.
+    fn is_backup_of(&self, backup: &Path, original: &Path) -> bool {
+        if backup.parent() != original.parent() {
+            return false;
+        }
+        let (Some(backup), Some(name)) = (
+            backup.file_name().and_then(OsStr::to_str),
+            original.file_name().and_then(OsStr::to_str),
+        ) else {
+            return false;
+        };
+        let stem = original.file_stem().and_then(OsStr::to_str).unwrap_or(name);
+        self.backup_suffixes.iter().any(|suffix| {
+            !suffix.is_empty()
+                && backup
+                    .strip_suffix(suffix.as_str())
+                    .is_some_and(|base| base == name || base == stem)
+        })
+    }
+
+    /// Every file is a backup of, or backed up by, another file of the group.
+    fn all_backup_siblings(&self, files: &[FileEntry]) -> bool {
+        files.iter().enumerate().all(|(i, file)| {
+            files.iter().enumerate().any(|(j, other)| {
+                i != j
+                    && (self.is_backup_of(&file.path, &other.path)
+                        || self.is_backup_of(&other.path, &file.path))
+            })
+        })
+    }
+
+    /// Every file has the same boilerplate basename.
+    fn all_boilerplate(&self, files: &[FileEntry]) -> bool {
+        shared_basename(files).is_some_and(|name| {
+            name.to_str()
+                .is_some_and(|name| self.boilerplate_names.iter().any(|b| b == name))
+        })
+    }
+}
+
+/// The basename shared by every file, if there is one.
+fn shared_basename(files: &[FileEntry]) -> Option<&OsStr> {
+    let name = files.first()?.path.file_name()?;
+    files
+        .iter()
+        .all(|f| f.path.file_name() == Some(name))
+        .then_some(name)
+}
+
+/// Same basename and depth, differing in one directory above the parent.
+fn in_parallel_trees(files: &[FileEntry]) -> bool {
+    if shared_basename(files).is_none() {
+        return false;
+    }
+    let paths: Vec<Vec<Component<'_>>> = files
+        .iter()
+        .map(|f| f.path.components().collect())
+        .collect();
+    let depth = paths[0].len();
+    if paths.iter().any(|p| p.len() != depth) {
+        return false;
+    }
+    let mut differing = (0..depth).filter(|&i| paths.iter().any(|p| p[i] != paths[0][i]));
+    match (differing.next(), differing.next()) {
+        // The last two components are the parent and the shared basename
+        (Some(index), None) => index + 2 < depth,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn files(paths: &[&str]) -> Vec<FileEntry> {
+        paths
+            .iter()
+            .map(|p| FileEntry::new(PathBuf::from(p), 100, SystemTime::UNIX_EPOCH))
+            .collect()
+    }
+
+    fn classify(paths: &[&str]) -> bool {
+        IntentionalPatterns::default().classify(&files(paths))
+    }
+
+    #[test]
+    fn test_backup_siblings_are_intentional() {
+        assert!(classify(&["/etc/app.conf", "/etc/app.conf.bak"]));
+        assert!(classify(&["/etc/app.conf", "/etc/app.orig"]));
+        assert!(classify(&[
+            "/src/main.c",
+            "/src/main.c~",
+            "/src/main.c.old"
+        ]));
+        assert!(classify(&["/a/notes.txt.bak", "/a/notes.txt"]));
+    }
+
+    #[test]
+    fn test_incidental_groups_are_not_intentional() {
+        // Backup name, but in another directory
+        assert!(!classify(&["/etc/app.conf", "/tmp/app.conf.bak"]));
+        // One unrelated copy next to a backup pair
+        assert!(!classify(&[
+            "/etc/app.conf",
+            "/etc/app.conf.bak",
+            "/home/me/Downloads/app.conf"
+        ]));
+        // Same name in sibling directories
+        assert!(!classify(&[
+            "/home/Downloads/photo.jpg",
+            "/home/Pictures/photo.jpg"
+        ]));
+        // Different names
+        assert!(!classify(&["/a/report.pdf", "/a/report (1).pdf"]));
+        assert!(!classify(&["/only/one.txt"]));
+    }
+
+    #[test]
+    fn test_parallel_trees_are_intentional() {
+        assert!(classify(&["/proj/v1/src/util.rs", "/proj/v2/src/util.rs"]));
+        assert!(classify(&[
+            "/site/en/assets/img/logo.png",
+            "/site/de/assets/img/logo.png",
+            "/site/fr/assets/img/logo.png"
+        ]));
+        // Differs in two directories
+        assert!(!classify(&["/a/v1/src/util.rs", "/b/v2/src/util.rs"]));
+        // Different depths
+        assert!(!classify(&[
+            "/proj/v1/src/util.rs",
+            "/proj/v2/src/lib/util.rs"
+        ]));
+
+        let patterns = IntentionalPatterns {
+            parallel_trees: false,
+            ..IntentionalPatterns::default()
+        };
+        assert!(!patterns.classify(&files(&["/proj/v1/src/util.rs", "/proj/v2/src/util.rs"])));
+    }
+
+    #[test]
+    fn test_boilerplate_names_are_intentional() {
+        assert!(classify(&["/a/LICENSE", "/b/LICENSE"]));
+        assert!(classify(&["/x/pkg/__init__.py", "/y/other/__init__.py"]));
+        assert!(!classify(&["/a/LICENSE", "/b/COPYING"]));
+    }
+
+    #[test]
+    fn test_custom_patterns() {
+        let patterns = IntentionalPatterns {
+            backup_suffixes: vec![".keepme".to_string()],
+            boilerplate_names: vec!["settings.json".to_string()],
+            ..IntentionalPatterns::default()
+        };
+        assert!(patterns.classify(&files(&["/a/x.txt", "/a/x.txt.keepme"])));
+        assert!(!patterns.classify(&files(&["/a/x.txt", "/a/x.txt.bak"])));
+        assert!(patterns.classify(&files(&["/a/settings.json", "/b/settings.json"])));
+        assert!(!patterns.classify(&files(&["/a/LICENSE", "/b/LICENSE"])));
+
+        assert!(!IntentionalPatterns::disabled().classify(&files(&["/a/x", "/a/x.bak"])));
+    }
+
+    #[test]
+    fn test_patterns_deserialize_with_defaults() {
+        let patterns: IntentionalPatterns =
+            toml::from_str("backup_suffixes = [\".save\"]").unwrap();
+        assert_eq!(patterns.backup_suffixes, vec![".save".to_string()]);
+        assert!(patterns.enabled);
+        assert!(patterns.parallel_trees);
+        assert_eq!(patterns.boilerplate_names, default_boilerplate_names());
+    }
+}
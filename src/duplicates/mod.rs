@@ -4,7 +4,14 @@
 //! - Size-based file grouping (Phase 1)
 //! - Prehash comparison (Phase 2)
 //! - Full hash comparison (Phase 3)
+//! - Byte-by-byte verification (Phase 4, paranoid mode)
 //! - Duplicate group management
+//! - Size histograms of duplicate files
+//! - Reclaimable space by file extension
+//! - Resumable scan checkpoints
+//! - Partial duplicates sharing content-defined chunks
+//! - Heuristic tagging of likely-intentional copies
+//! - Pipelined walking and prehashing
 //!
 //! # Architecture
 //!
@@ -48,18 +55,51 @@
 //!     prehash_stats.potential_duplicates, prehash_stats.elimination_rate());
 //! ```
 
+pub mod checkpoint;
+pub mod extensions;
 pub mod finder;
 pub mod groups;
+pub mod histogram;
+pub mod intentional;
+pub mod partial;
+pub mod pipeline;
+
+// Re-export checkpoint types
+pub use checkpoint::{CheckpointError, CheckpointRecorder, CheckpointStage, ScanCheckpoint};
 
 // Re-export main types from groups
 pub use groups::{
-    group_by_size, group_by_size_structured, DuplicateGroup, GroupingStats, SizeGroup,
+    group_by_size, group_by_size_structured, group_id, group_ids, retain_duplicated_names,
+    DuplicateGroup, GroupingStats, HardlinkPolicy, MatchKind, ProtectPolicy, SizeGroup,
+    GROUP_ID_LEN,
+};
+
+// Re-export extension tallies
+pub use extensions::{reclaimable_by_extension, top_extensions, ExtensionTallies, NO_EXTENSION};
+
+// Re-export histogram types
+pub use histogram::{
+    MinSizeRecommendation, SizeBucket, SizeHistogram, MIN_SIZE_HINT_FILE_SHARE,
+    MIN_SIZE_HINT_SPACE_SHARE, SIZE_HISTOGRAM_BUCKETS,
 };
 
+// Re-export intentional-copy heuristics
+pub use intentional::{IntentionalPatterns, DEFAULT_BACKUP_SUFFIXES, DEFAULT_BOILERPLATE_NAMES};
+
+// Re-export partial duplicate detection
+pub use partial::{
+    chunk_file, find_partial_matches, shared_ratio, ChunkSignature, PartialMatch,
+    DEFAULT_PARTIAL_THRESHOLD, PARTIAL_MIN_SIZE,
+};
+
+// Re-export pipeline types
+pub use pipeline::{PrehashStore, PrehashWarmer, SizeSieve, PIPELINE_CHANNEL_CAPACITY};
+
 // Re-export main types from finder
 pub use crate::progress::ProgressCallback;
 pub use finder::{
-    compute_prehashes, extract_paths, phase2_prehash, phase3_fullhash, DuplicateFinder,
-    FinderConfig, FinderError, FullhashConfig, FullhashStats, PrehashConfig, PrehashEntry,
-    PrehashStats, ScanSummary,
+    compute_prehashes, extract_paths, phase2_prehash, phase3_fullhash, verify_group_identical,
+    verify_group_identical_with, DuplicateFinder, FinderConfig, FinderError, FullhashConfig,
+    FullhashStats, ParanoidConfig, PrehashConfig, PrehashEntry, PrehashStats, ScanSummary,
+    DEFAULT_PARANOID_MAX_OPEN_FILES, MAX_BLOOM_FP_RATE, MIN_BLOOM_FP_RATE,
 };
@@ -0,0 +1,453 @@
+//! Partial duplicate detection with content-defined chunking (`--partial-similarity`).
+//!
+//! Exact matching misses files that share most of their bytes: a log that
+//! was appended to, two snapshots of the same VM disk, an archive with one
+//! member replaced. This phase splits candidate files into content-defined
+//! chunks and reports pairs that share a large fraction of them.
+//!
+//! # Chunking
+//!
+//! Chunk boundaries come from a gear rolling hash in the style of FastCDC:
+//! a boundary is cut where the hash of the preceding bytes matches a mask,
+//! so inserting or removing bytes only moves the boundaries next to the
+//! edit. Chunks are [`CDC_MIN_CHUNK`] to [`CDC_MAX_CHUNK`] long, and a
+//! stricter mask before [`CDC_AVG_CHUNK`] and a looser one after it keep
+//! most chunks near the average. Each chunk is identified by a 64-bit prefix
+//! of its BLAKE3 hash.
+//!
+//! # Similarity
+//!
+//! The similarity of two files is the number of bytes in chunks they share
+//! divided by the size of the larger file, so a log that grew from 80 MB to
+//! 100 MB scores about 0.8 whichever way round it is compared.
+//!
+//! Shared bytes never exceed the smaller file, so a pair can only reach a
+//! threshold `t` when `smaller >= t * larger`. Only files with another file
+//! in that size band are chunked, and files below [`PARTIAL_MIN_SIZE`] are
+//! skipped as too small for a meaningful ratio. Pairs are then found through
+//! an index from chunk to files, so only files that share at least one chunk
+//! are ever compared.
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::duplicates::partial::{chunk_reader, shared_ratio};
+//!
+//! let log: Vec<u8> = (0..400_000u32)
+//!     .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+//!     .collect();
+//! let mut grown = log.clone();
+//! grown.extend_from_slice(&[b'x'; 100_000]);
+//!
+//! let before = chunk_reader(&log[..]).unwrap();
+//! let after = chunk_reader(&grown[..]).unwrap();
+//! assert_eq!(shared_ratio(&before, &before), 1.0);
+//! assert!(shared_ratio(&before, &after) > 0.5);
+//! ```
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use rayon::prelude::*;
+
+use crate::scanner::FileEntry;
+
+/// Smallest chunk, except at the end of a file (2 KiB).
+pub const CDC_MIN_CHUNK: usize = 2 * 1024;
+
+/// Chunk size the boundary masks aim for (8 KiB).
+pub const CDC_AVG_CHUNK: usize = 8 * 1024;
+
+/// Largest chunk; a boundary is forced here (64 KiB).
+pub const CDC_MAX_CHUNK: usize = 64 * 1024;
+
+/// Files smaller than this are not checked for partial duplicates (64 KiB).
+pub const PARTIAL_MIN_SIZE: u64 = 64 * 1024;
+
+/// Shared fraction reported when `--partial-threshold` is not given.
+pub const DEFAULT_PARTIAL_THRESHOLD: f64 = 0.8;
+
+/// Boundary mask before the average chunk size (1 in 32768 positions).
+const MASK_SMALL: u64 = top_bits(15);
+
+/// Boundary mask after the average chunk size (1 in 2048 positions).
+const MASK_LARGE: u64 = top_bits(11);
+
+/// Per-byte values mixed into the rolling hash.
+const GEAR: [u64; 256] = gear_table();
+
+/// Mask of the `bits` most significant bits.
+///
+/// With a left-shifting gear hash the high bits depend on the most recent
+/// 64 bytes, the low bits only on the last few.
+const fn top_bits(bits: u32) -> u64 {
+    !0u64 << (64 - bits)
+}
+
+/// Fixed pseudo-random gear values (SplitMix64), so chunking is reproducible.
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state = 0u64;
+    let mut i = 0;
+    while i < table.len() {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Length of the chunk starting at the beginning of `data`.
+///
+/// `data` must hold at least [`CDC_MAX_CHUNK`] bytes unless it is the end of
+/// the file, or the cut would depend on how the file was read.
+fn cut_point(data: &[u8]) -> usize {
+    if data.len() <= CDC_MIN_CHUNK {
+        return data.len();
+    }
+    let normal = data.len().min(CDC_AVG_CHUNK);
+    let max = data.len().min(CDC_MAX_CHUNK);
+    let mut hash = 0u64;
+    for (i, &byte) in data.iter().enumerate().take(max).skip(CDC_MIN_CHUNK) {
+        hash = (hash << 1).wrapping_add(GEAR[usize::from(byte)]);
+        let mask = if i < normal { MASK_SMALL } else { MASK_LARGE };
+        if hash & mask == 0 {
+            return i + 1;
+        }
+    }
+    max
+}
+
+/// Identifier of a chunk: the first 8 bytes of its BLAKE3 hash.
+fn chunk_id(chunk: &[u8]) -> u64 {
+    let hash = blake3::hash(chunk);
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&hash.as_bytes()[..8]);
+    u64::from_le_bytes(prefix)
+}
+
+/// The chunks of one file, for comparison with [`shared_ratio`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkSignature {
+    size: u64,
+    /// Bytes covered by each distinct chunk (repeated chunks add up)
+    chunks: HashMap<u64, u64>,
+}
+
+impl ChunkSignature {
+    /// Total bytes chunked.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Number of distinct chunks.
+    #[must_use]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+}
+
+/// Split everything `reader` yields into content-defined chunks.
+///
+/// # Errors
+///
+/// Returns an error if reading fails.
+pub fn chunk_reader<R: Read>(mut reader: R) -> io::Result<ChunkSignature> {
+    let mut signature = ChunkSignature::default();
+    let mut buffer = Vec::with_capacity(2 * CDC_MAX_CHUNK);
+    let mut pos = 0;
+    let mut eof = false;
+
+    loop {
+        // Keep a full maximum chunk ahead so every cut is content-defined
+        if !eof && buffer.len() - pos < CDC_MAX_CHUNK {
+            buffer.drain(..pos);
+            pos = 0;
+            while !eof && buffer.len() < 2 * CDC_MAX_CHUNK {
+                let filled = buffer.len();
+                buffer.resize(2 * CDC_MAX_CHUNK, 0);
+                match reader.read(&mut buffer[filled..]) {
+                    Ok(n) => {
+                        buffer.truncate(filled + n);
+                        eof = n == 0;
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => buffer.truncate(filled),
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+        if pos == buffer.len() {
+            break;
+        }
+
+        let len = cut_point(&buffer[pos..]);
+        *signature
+            .chunks
+            .entry(chunk_id(&buffer[pos..pos + len]))
+            .or_default() += len as u64;
+        signature.size += len as u64;
+        pos += len;
+    }
+    Ok(signature)
+}
+
+/// Split the file at `path` into content-defined chunks.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn chunk_file(path: &Path) -> io::Result<ChunkSignature> {
+    chunk_reader(File::open(path)?)
+}
+
+/// Fraction of the larger file's bytes that lie in chunks both files share.
+///
+/// Returns 0.0 when both files are empty.
+#[must_use]
+pub fn shared_ratio(a: &ChunkSignature, b: &ChunkSignature) -> f64 {
+    let larger = a.size.max(b.size);
+    if larger == 0 {
+        return 0.0;
+    }
+    let (small, large) = if a.chunks.len() <= b.chunks.len() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let shared: u64 = small
+        .chunks
+        .iter()
+        .filter_map(|(id, &bytes)| large.chunks.get(id).map(|&other| bytes.min(other)))
+        .sum();
+    shared as f64 / larger as f64
+}
+
+/// Two candidate files that share at least the threshold fraction of content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialMatch {
+    /// Index of the smaller file in the candidate list
+    pub first: usize,
+    /// Index of the larger file in the candidate list
+    pub second: usize,
+    /// Fraction of the larger file found in the smaller one (0.0 to 1.0)
+    pub similarity: f64,
+}
+
+/// Find pairs of `files` sharing at least `threshold` of their content.
+///
+/// Only files of at least [`PARTIAL_MIN_SIZE`] with another file in the size
+/// band the threshold allows are chunked. Files that cannot be read are
+/// logged and skipped. Chunking stops early once `cancelled` returns true.
+///
+/// Returns the matches, most similar first, and the number of files chunked.
+#[must_use]
+pub fn find_partial_matches(
+    files: &[FileEntry],
+    threshold: f64,
+    cancelled: &(dyn Fn() -> bool + Sync),
+) -> (Vec<PartialMatch>, usize) {
+    let mut order: Vec<usize> = (0..files.len())
+        .filter(|&i| files[i].size >= PARTIAL_MIN_SIZE)
+        .collect();
+    order.sort_by_key(|&i| files[i].size);
+
+    let needed = banded_candidates(files, &order, threshold);
+    log::debug!("Partial duplicates: {} candidate files", needed.len());
+
+    let signatures: HashMap<usize, ChunkSignature> = needed
+        .par_iter()
+        .filter_map(|&i| {
+            if cancelled() {
+                return None;
+            }
+            match chunk_file(&files[i].path) {
+                Ok(signature) => Some((i, signature)),
+                Err(e) => {
+                    log::warn!("Failed to chunk {}: {}", files[i].path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect();
+
+    // Which files contain each chunk
+    let mut index: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (&i, signature) in &signatures {
+        for &id in signature.chunks.keys() {
+            index.entry(id).or_default().push(i);
+        }
+    }
+
+    // Position by size, so each pair is compared once, smaller file first
+    let mut rank = vec![usize::MAX; files.len()];
+    for (pos, &i) in order.iter().enumerate() {
+        rank[i] = pos;
+    }
+
+    let mut matches: Vec<PartialMatch> = signatures
+        .par_iter()
+        .flat_map_iter(|(&first, signature)| {
+            // Bytes shared with each larger file that has a chunk in common
+            let mut shared: HashMap<usize, u64> = HashMap::new();
+            for (id, &bytes) in &signature.chunks {
+                for &second in &index[id] {
+                    if rank[second] > rank[first] {
+                        let other = signatures[&second].chunks[id];
+                        *shared.entry(second).or_default() += bytes.min(other);
+                    }
+                }
+            }
+            shared
+                .into_iter()
+                .filter_map(|(second, bytes)| {
+                    let larger = signature.size.max(signatures[&second].size);
+                    let similarity = bytes as f64 / larger as f64;
+                    (similarity >= threshold).then_some(PartialMatch {
+                        first,
+                        second,
+                        similarity,
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then(a.first.cmp(&b.first))
+            .then(a.second.cmp(&b.second))
+    });
+    (matches, signatures.len())
+}
+
+/// Indices from `order` with another file whose size can reach `threshold`
+/// together with theirs.
+///
+/// `order` lists the candidate indices sorted by size, so checking the
+/// neighbours on either side is enough.
+fn banded_candidates(files: &[FileEntry], order: &[usize], threshold: f64) -> Vec<usize> {
+    let within = |small: usize, large: usize| {
+        files[large].size as f64 * threshold <= files[small].size as f64
+    };
+    order
+        .iter()
+        .enumerate()
+        .filter(|&(pos, &i)| {
+            (pos > 0 && within(order[pos - 1], i))
+                || order.get(pos + 1).is_some_and(|&next| within(i, next))
+        })
+        .map(|(_, &i)| i)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    /// Deterministic pseudo-random bytes (xorshift).
+    fn noise(seed: u64, len: usize) -> Vec<u8> {
+        let mut state = seed | 1;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
+    fn write(dir: &TempDir, name: &str, data: &[u8]) -> FileEntry {
+        let path = dir.path().join(name);
+        std::fs::write(&path, data).unwrap();
+        FileEntry::new(path, data.len() as u64, SystemTime::now())
+    }
+
+    #[test]
+    fn test_chunking_is_content_defined() {
+        let data = noise(1, 1 << 20);
+        let signature = chunk_reader(&data[..]).unwrap();
+        assert_eq!(signature.size(), 1 << 20);
+        // Roughly average-sized chunks
+        let chunks = signature.chunk_count();
+        assert!((40..=200).contains(&chunks), "{chunks} chunks");
+
+        // A prefix insertion only disturbs the chunks next to it
+        let mut shifted = b"inserted header".to_vec();
+        shifted.extend_from_slice(&data);
+        let other = chunk_reader(&shifted[..]).unwrap();
+        assert!(shared_ratio(&signature, &other) > 0.9);
+        assert_eq!(shared_ratio(&signature, &signature), 1.0);
+        assert_eq!(shared_ratio(&ChunkSignature::default(), &signature), 0.0);
+    }
+
+    #[test]
+    fn test_find_partial_matches_known_fraction() {
+        let dir = TempDir::new().unwrap();
+        let common = noise(7, 768 * 1024);
+
+        // Three quarters shared, the last quarter different
+        let mut a = common.clone();
+        a.extend_from_slice(&noise(8, 256 * 1024));
+        let mut b = common.clone();
+        b.extend_from_slice(&noise(9, 256 * 1024));
+        // Unrelated content of the same size
+        let c = noise(10, 1024 * 1024);
+        // Too small to be considered
+        let tiny = common[..1024].to_vec();
+
+        let files = vec![
+            write(&dir, "a.bin", &a),
+            write(&dir, "b.bin", &b),
+            write(&dir, "c.bin", &c),
+            write(&dir, "tiny.bin", &tiny),
+        ];
+
+        let (matches, chunked) = find_partial_matches(&files, 0.6, &|| false);
+        assert_eq!(chunked, 3);
+        assert_eq!(matches.len(), 1);
+        let found = &matches[0];
+        assert_eq!(
+            (found.first.min(found.second), found.first.max(found.second)),
+            (0, 1)
+        );
+        assert!(
+            (found.similarity - 0.75).abs() < 0.1,
+            "similarity {}",
+            found.similarity
+        );
+
+        // Above the shared fraction: nothing reported
+        let (matches, _) = find_partial_matches(&files, 0.9, &|| false);
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_banded_candidates_size_band() {
+        let files: Vec<FileEntry> = [100u64, 70, 90, 200]
+            .iter()
+            .enumerate()
+            .map(|(i, &size)| {
+                FileEntry::new(
+                    PathBuf::from(format!("/f{i}")),
+                    size,
+                    SystemTime::UNIX_EPOCH,
+                )
+            })
+            .collect();
+        let order = [1, 2, 0, 3];
+
+        // 70 can pair with 90 and 100 at 0.7, nothing reaches 200
+        assert_eq!(banded_candidates(&files, &order, 0.7), vec![1, 2, 0]);
+        assert_eq!(banded_candidates(&files, &order, 0.85), vec![2, 0]);
+    }
+}
@@ -0,0 +1,397 @@
+//! Pipelined size filtering and prehashing.
+//!
+//! On large trees the walk dominates the scan, and a phase-serial finder
+//! leaves the disk idle for hashing until the last directory is listed. The
+//! pipeline overlaps the two: the walker streams [`FileEntry`]s through a
+//! bounded channel into a [`SizeSieve`], and every file whose size has
+//! already collided is handed to a [`PrehashWarmer`] that prehashes it on
+//! background I/O threads while traversal continues.
+//!
+//! The warmer only fills a [`PrehashStore`]; Phase 2 still runs as before
+//! and looks files up there first (see [`PrehashConfig::precomputed`]), so
+//! grouping, statistics, caching and error reporting are identical to a
+//! serial scan. Files the warmer could not hash are simply hashed again in
+//! Phase 2, which reports the error.
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::duplicates::SizeSieve;
+//! use rustdupe::scanner::FileEntry;
+//! use std::path::PathBuf;
+//! use std::time::SystemTime;
+//!
+//! let mut sieve = SizeSieve::new(0.01);
+//! let mut candidates = Vec::new();
+//! for (name, size) in [("a", 10), ("b", 20), ("c", 10)] {
+//!     let file = FileEntry::new(PathBuf::from(name), size, SystemTime::now());
+//!     sieve.push(file, |f| candidates.push(f.path.clone()));
+//! }
+//! // "a" becomes a candidate as soon as "c" collides with its size
+//! assert_eq!(candidates, vec![PathBuf::from("a"), PathBuf::from("c")]);
+//! ```
+//!
+//! [`PrehashConfig::precomputed`]: super::PrehashConfig::precomputed
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use growable_bloom_filter::GrowableBloom;
+
+use crate::cache::{CacheEntry, HashCache};
+use crate::progress::wait_while_paused;
+use crate::scanner::{FileEntry, Hash, Hasher};
+
+/// Capacity of the bounded channels between walker, sieve and warmer.
+///
+/// Large enough to absorb bursts from a parallel walker, small enough that a
+/// slow disk applies back-pressure instead of buffering the whole tree.
+pub const PIPELINE_CHANNEL_CAPACITY: usize = 4096;
+
+/// Streaming Phase 1 filter separating files whose size occurs more than once.
+///
+/// Uses the same pair of Bloom filters as the serial finder: the first file
+/// of each size is held back until a second file of that size arrives, at
+/// which point both become candidates. Later files of a known duplicate size
+/// become candidates immediately. Bloom false positives can admit a file
+/// with a unique size; [`group_by_size`](super::group_by_size) drops those.
+pub struct SizeSieve {
+    seen_sizes: GrowableBloom,
+    duplicate_sizes: GrowableBloom,
+    first_occurrences: HashMap<u64, FileEntry>,
+    candidates: Vec<FileEntry>,
+    empty_files: Vec<FileEntry>,
+}
+
+impl SizeSieve {
+    /// Create an empty sieve with the given Bloom filter false positive rate.
+    #[must_use]
+    pub fn new(bloom_fp_rate: f64) -> Self {
+        Self::with_capacity(bloom_fp_rate, 1000)
+    }
+
+    /// Create an empty sieve sized for about `capacity` files.
+    #[must_use]
+    pub fn with_capacity(bloom_fp_rate: f64, capacity: usize) -> Self {
+        Self {
+            seen_sizes: GrowableBloom::new(bloom_fp_rate, capacity.max(1)),
+            duplicate_sizes: GrowableBloom::new(bloom_fp_rate, capacity.max(1)),
+            first_occurrences: HashMap::new(),
+            candidates: Vec::new(),
+            empty_files: Vec::new(),
+        }
+    }
+
+    /// Sort one file in, calling `on_candidate` for every file that becomes
+    /// a potential duplicate as a result (zero, one or two files).
+    ///
+    /// Zero-byte files are set aside and never become candidates.
+    pub fn push(&mut self, file: FileEntry, mut on_candidate: impl FnMut(&FileEntry)) {
+        if file.size == 0 {
+            self.empty_files.push(file);
+            return;
+        }
+
+        if self.duplicate_sizes.contains(file.size) {
+            on_candidate(&file);
+            self.candidates.push(file);
+        } else if self.seen_sizes.contains(file.size) {
+            self.duplicate_sizes.insert(file.size);
+            if let Some(first) = self.first_occurrences.remove(&file.size) {
+                on_candidate(&first);
+                self.candidates.push(first);
+            }
+            on_candidate(&file);
+            self.candidates.push(file);
+        } else {
+            self.seen_sizes.insert(file.size);
+            self.first_occurrences.insert(file.size, file);
+        }
+    }
+
+    /// Split the sieve into candidates, held-back files with a unique size
+    /// (keyed by size) and zero-byte files.
+    #[must_use]
+    pub fn into_parts(self) -> (Vec<FileEntry>, HashMap<u64, FileEntry>, Vec<FileEntry>) {
+        (self.candidates, self.first_occurrences, self.empty_files)
+    }
+}
+
+/// Prehashes computed ahead of Phase 2, keyed by path.
+#[derive(Debug, Default)]
+pub struct PrehashStore {
+    hashes: HashMap<PathBuf, (Hash, bool)>,
+}
+
+impl PrehashStore {
+    /// Look up the prehash of `path` and whether it came from the cache.
+    #[must_use]
+    pub fn get(&self, path: &Path) -> Option<(Hash, bool)> {
+        self.hashes.get(path).copied()
+    }
+
+    /// Number of stored prehashes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Whether no prehashes were stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+/// Background prehashing of candidate files while the walk continues.
+///
+/// Files are submitted through a bounded channel and hashed by `io_threads`
+/// worker threads, consulting and updating the hash cache exactly like
+/// Phase 2 does. Workers park between files while the scan is paused.
+pub struct PrehashWarmer {
+    sender: Option<SyncSender<FileEntry>>,
+    workers: Vec<JoinHandle<()>>,
+    store: Arc<Mutex<HashMap<PathBuf, (Hash, bool)>>>,
+}
+
+impl PrehashWarmer {
+    /// Start `io_threads` worker threads.
+    #[must_use]
+    pub fn spawn(
+        hasher: Arc<Hasher>,
+        io_threads: usize,
+        cache: Option<Arc<HashCache>>,
+        shutdown_flag: Option<Arc<AtomicBool>>,
+        pause_flag: Option<Arc<AtomicBool>>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<FileEntry>(PIPELINE_CHANNEL_CAPACITY);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let store = Arc::new(Mutex::new(HashMap::new()));
+
+        let workers = (0..io_threads.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                let store = Arc::clone(&store);
+                let hasher = Arc::clone(&hasher);
+                let cache = cache.clone();
+                let shutdown_flag = shutdown_flag.clone();
+                let pause_flag = pause_flag.clone();
+                std::thread::spawn(move || {
+                    warm_prehashes(
+                        &receiver,
+                        &store,
+                        &hasher,
+                        cache.as_deref(),
+                        shutdown_flag,
+                        pause_flag,
+                    )
+                })
+            })
+            .collect();
+
+        Self {
+            sender: Some(sender),
+            workers,
+            store,
+        }
+    }
+
+    /// Queue a file for prehashing, blocking while the queue is full.
+    pub fn submit(&self, file: &FileEntry) {
+        if let Some(ref sender) = self.sender {
+            // Workers only stop early on shutdown; Phase 2 covers the rest
+            let _ = sender.send(file.clone());
+        }
+    }
+
+    /// Wait for queued files to be hashed and return the results.
+    #[must_use]
+    pub fn finish(mut self) -> PrehashStore {
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            if worker.join().is_err() {
+                log::warn!("Prehash worker panicked; Phase 2 will rehash its files");
+            }
+        }
+        let hashes = std::mem::take(
+            &mut *self
+                .store
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
+        log::debug!(
+            "Pipeline prehashed {} file(s) during the walk",
+            hashes.len()
+        );
+        PrehashStore { hashes }
+    }
+}
+
+/// Worker loop: prehash files from `receiver` until the channel closes.
+fn warm_prehashes(
+    receiver: &Mutex<Receiver<FileEntry>>,
+    store: &Mutex<HashMap<PathBuf, (Hash, bool)>>,
+    hasher: &Hasher,
+    cache: Option<&HashCache>,
+    shutdown_flag: Option<Arc<AtomicBool>>,
+    pause_flag: Option<Arc<AtomicBool>>,
+) {
+    loop {
+        let file = match receiver.lock() {
+            Ok(receiver) => match receiver.recv() {
+                Ok(file) => file,
+                Err(_) => return,
+            },
+            Err(_) => return,
+        };
+        wait_while_paused(pause_flag.as_deref(), shutdown_flag.as_deref());
+        if shutdown_flag
+            .as_ref()
+            .is_some_and(|f| f.load(Ordering::SeqCst))
+        {
+            // Keep draining so the walker never blocks on a full queue
+            continue;
+        }
+
+        let cached = cache.and_then(|c| {
+            c.get_prehash(&file.path, file.size, file.modified)
+                .ok()
+                .flatten()
+        });
+        let result = match cached {
+            Some(hash) => Some((hash, true)),
+            None => match hasher.prehash(&file.path) {
+                Ok(hash) => {
+                    if let Some(cache) = cache {
+                        if let Err(e) = cache.insert_prehash(&CacheEntry::from(file.clone()), hash)
+                        {
+                            log::warn!("Failed to update cache for {}: {}", file.path.display(), e);
+                        }
+                    }
+                    Some((hash, false))
+                }
+                Err(e) => {
+                    log::debug!("Pipeline prehash failed for {}: {}", file.path.display(), e);
+                    None
+                }
+            },
+        };
+
+        if let Some(result) = result {
+            if let Ok(mut store) = store.lock() {
+                store.insert(file.path, result);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    fn entry(path: &str, size: u64) -> FileEntry {
+        FileEntry::new(PathBuf::from(path), size, SystemTime::UNIX_EPOCH)
+    }
+
+    #[test]
+    fn test_size_sieve_emits_candidates_on_collision() {
+        let mut sieve = SizeSieve::new(0.01);
+        let mut emitted = Vec::new();
+        for file in [
+            entry("/a", 10),
+            entry("/b", 20),
+            entry("/empty", 0),
+            entry("/c", 10),
+            entry("/d", 10),
+        ] {
+            sieve.push(file, |f| emitted.push(f.path.clone()));
+        }
+
+        let expected: Vec<PathBuf> = ["/a", "/c", "/d"].into_iter().map(PathBuf::from).collect();
+        assert_eq!(emitted, expected);
+
+        let (candidates, unique, empty) = sieve.into_parts();
+        assert_eq!(
+            candidates
+                .iter()
+                .map(|f| f.path.clone())
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(unique.len(), 1);
+        assert_eq!(unique[&20].path, PathBuf::from("/b"));
+        assert_eq!(empty.len(), 1);
+    }
+
+    #[test]
+    fn test_prehash_warmer_matches_hasher() {
+        let dir = TempDir::new().unwrap();
+        let hasher = Arc::new(Hasher::new());
+        let warmer = PrehashWarmer::spawn(hasher.clone(), 2, None, None, None);
+
+        let mut files = Vec::new();
+        for i in 0..20 {
+            let path = dir.path().join(format!("file{i}.bin"));
+            std::fs::write(&path, format!("content {}", i % 3)).unwrap();
+            let file = FileEntry::new(path, 9, SystemTime::now());
+            warmer.submit(&file);
+            files.push(file);
+        }
+        warmer.submit(&entry("/missing/file.bin", 9));
+
+        let store = warmer.finish();
+        assert_eq!(store.len(), 20);
+        for file in &files {
+            assert_eq!(
+                store.get(&file.path),
+                Some((hasher.prehash(&file.path).unwrap(), false))
+            );
+        }
+        assert!(store.get(Path::new("/missing/file.bin")).is_none());
+    }
+
+    #[test]
+    fn test_prehash_warmer_parks_while_paused() {
+        let dir = TempDir::new().unwrap();
+        let pause = Arc::new(AtomicBool::new(true));
+        let warmer =
+            PrehashWarmer::spawn(Arc::new(Hasher::new()), 2, None, None, Some(pause.clone()));
+
+        for i in 0..4 {
+            let path = dir.path().join(format!("file{i}.bin"));
+            std::fs::write(&path, b"content").unwrap();
+            warmer.submit(&FileEntry::new(path, 7, SystemTime::now()));
+        }
+
+        // Paused workers read nothing
+        std::thread::sleep(crate::progress::PAUSE_POLL_INTERVAL * 4);
+        assert!(warmer.store.lock().unwrap().is_empty());
+
+        pause.store(false, Ordering::SeqCst);
+        assert_eq!(warmer.finish().len(), 4);
+    }
+
+    #[test]
+    fn test_paused_prehash_warmer_can_shut_down() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("file.bin");
+        std::fs::write(&path, b"content").unwrap();
+
+        // Paused forever, but shutdown still lets the workers drain and exit
+        let warmer = PrehashWarmer::spawn(
+            Arc::new(Hasher::new()),
+            1,
+            None,
+            Some(Arc::new(AtomicBool::new(true))),
+            Some(Arc::new(AtomicBool::new(true))),
+        );
+        warmer.submit(&FileEntry::new(path, 7, SystemTime::now()));
+        assert!(warmer.finish().is_empty());
+    }
+}
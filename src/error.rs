@@ -4,12 +4,17 @@ use serde::Serialize;
 
 /// Exit codes for the RustDupe application.
 ///
-/// Following the recommendations in Task 2.4.3:
-/// - 0: Success (completed normally, duplicates found)
-/// - 1: General error (unexpected failure)
-/// - 2: No duplicates found (completed normally, no duplicates)
-/// - 3: Partial success (completed with some non-fatal scan errors)
-/// - 130: Interrupted by user (Ctrl+C)
+/// The numeric values are stable and safe to branch on in scripts and CI;
+/// `rustdupe --print-exit-codes` prints this table as JSON.
+///
+/// | Code | Prefix  | Variant          | Meaning                                         |
+/// |------|---------|------------------|-------------------------------------------------|
+/// | 0    | `RD000` | `Success`        | Completed normally, duplicates found            |
+/// | 1    | `RD001` | `GeneralError`   | Unexpected failure                              |
+/// | 2    | `RD002` | `NoDuplicates`   | Completed normally, no duplicates found         |
+/// | 3    | `RD003` | `PartialSuccess` | Completed with some non-fatal scan errors       |
+/// | 4    | `RD004` | `TooManyErrors`  | Completed, but scan errors exceeded a threshold |
+/// | 130  | `RD130` | `Interrupted`    | Interrupted by the user (Ctrl+C)                |
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum ExitCode {
     /// Success: Scan completed and duplicates were found.
@@ -20,17 +25,58 @@ pub enum ExitCode {
     NoDuplicates = 2,
     /// Partial success: Scan completed but encountered some non-fatal errors.
     PartialSuccess = 3,
+    /// Too many errors: Scan completed but its errors exceeded `--max-errors`
+    /// or `--max-error-rate`.
+    TooManyErrors = 4,
     /// Interrupted: Scan was interrupted by user (Ctrl+C).
     Interrupted = 130,
 }
 
 impl ExitCode {
+    /// Every exit code, in numeric order.
+    pub const ALL: [ExitCode; 6] = [
+        Self::Success,
+        Self::GeneralError,
+        Self::NoDuplicates,
+        Self::PartialSuccess,
+        Self::TooManyErrors,
+        Self::Interrupted,
+    ];
+
     /// Get the numeric exit code.
+    ///
+    /// The values are part of the public interface and never change.
     #[must_use]
     pub fn as_i32(self) -> i32 {
         self as i32
     }
 
+    /// Get the snake_case name used in the exit-code map.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::GeneralError => "general_error",
+            Self::NoDuplicates => "no_duplicates",
+            Self::PartialSuccess => "partial_success",
+            Self::TooManyErrors => "too_many_errors",
+            Self::Interrupted => "interrupted",
+        }
+    }
+
+    /// Get a one-line description of when this code is returned.
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Success => "Completed normally, duplicates found",
+            Self::GeneralError => "Unexpected failure",
+            Self::NoDuplicates => "Completed normally, no duplicates found",
+            Self::PartialSuccess => "Completed with some non-fatal scan errors",
+            Self::TooManyErrors => "Completed, but scan errors exceeded a threshold",
+            Self::Interrupted => "Interrupted by the user (Ctrl+C)",
+        }
+    }
+
     /// Get the machine-readable code prefix.
     #[must_use]
     pub fn code_prefix(self) -> &'static str {
@@ -39,11 +85,69 @@ impl ExitCode {
             Self::GeneralError => "RD001",
             Self::NoDuplicates => "RD002",
             Self::PartialSuccess => "RD003",
+            Self::TooManyErrors => "RD004",
             Self::Interrupted => "RD130",
         }
     }
 }
 
+/// Limits on non-fatal scan errors (`--max-errors`, `--max-error-rate`).
+///
+/// `--strict` aborts on the first error; without it a scan always finishes
+/// and reports [`ExitCode::PartialSuccess`] when some files failed. These
+/// thresholds sit in between: the scan still finishes, but the exit code
+/// becomes [`ExitCode::TooManyErrors`] once either limit is exceeded.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ErrorThresholds {
+    /// Most errors tolerated
+    pub max_errors: Option<usize>,
+    /// Highest tolerated error rate, in percent of scanned files
+    pub max_error_rate: Option<f64>,
+}
+
+impl ErrorThresholds {
+    /// Errors as a percentage of scanned files.
+    ///
+    /// Errors with no scanned files at all count as a 100% rate.
+    #[must_use]
+    pub fn error_rate(errors: usize, files: usize) -> f64 {
+        match (errors, files) {
+            (0, _) => 0.0,
+            (_, 0) => 100.0,
+            _ => errors as f64 * 100.0 / files as f64,
+        }
+    }
+
+    /// Describe the exceeded limit, if `errors` among `files` exceed one.
+    #[must_use]
+    pub fn exceeded(&self, errors: usize, files: usize) -> Option<String> {
+        if let Some(max) = self.max_errors.filter(|&max| errors > max) {
+            return Some(format!(
+                "{} scan error(s) exceed --max-errors {}",
+                errors, max
+            ));
+        }
+        let rate = Self::error_rate(errors, files);
+        self.max_error_rate.filter(|&max| rate > max).map(|max| {
+            format!(
+                "scan error rate {:.1}% exceeds --max-error-rate {}",
+                rate, max
+            )
+        })
+    }
+
+    /// Escalate [`ExitCode::PartialSuccess`] to [`ExitCode::TooManyErrors`]
+    /// when a limit is exceeded; any other code is returned unchanged.
+    #[must_use]
+    pub fn escalate(&self, code: ExitCode, errors: usize, files: usize) -> ExitCode {
+        if code == ExitCode::PartialSuccess && self.exceeded(errors, files).is_some() {
+            ExitCode::TooManyErrors
+        } else {
+            code
+        }
+    }
+}
+
 /// Structured error information for JSON output.
 #[derive(Debug, Serialize)]
 pub struct StructuredError {
@@ -69,3 +173,159 @@ impl StructuredError {
         }
     }
 }
+
+/// One entry of the machine-readable exit-code map.
+#[derive(Debug, Serialize)]
+pub struct ExitCodeInfo {
+    /// Snake_case variant name (e.g., "no_duplicates")
+    pub name: &'static str,
+    /// The numeric exit code
+    pub code: i32,
+    /// The error code prefix (e.g., "RD002")
+    pub prefix: &'static str,
+    /// What the code means
+    pub description: &'static str,
+}
+
+impl From<ExitCode> for ExitCodeInfo {
+    fn from(code: ExitCode) -> Self {
+        Self {
+            name: code.name(),
+            code: code.as_i32(),
+            prefix: code.code_prefix(),
+            description: code.description(),
+        }
+    }
+}
+
+/// Build the exit-code map printed by `--print-exit-codes`.
+#[must_use]
+pub fn exit_code_map() -> Vec<ExitCodeInfo> {
+    ExitCode::ALL.into_iter().map(ExitCodeInfo::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_i32_is_stable() {
+        assert_eq!(ExitCode::Success.as_i32(), 0);
+        assert_eq!(ExitCode::GeneralError.as_i32(), 1);
+        assert_eq!(ExitCode::NoDuplicates.as_i32(), 2);
+        assert_eq!(ExitCode::PartialSuccess.as_i32(), 3);
+        assert_eq!(ExitCode::TooManyErrors.as_i32(), 4);
+        assert_eq!(ExitCode::Interrupted.as_i32(), 130);
+    }
+
+    #[test]
+    fn test_exit_code_map_lists_every_variant() {
+        let json = serde_json::to_value(exit_code_map()).unwrap();
+        let entries = json.as_array().unwrap();
+        assert_eq!(entries.len(), ExitCode::ALL.len());
+
+        let expected = [
+            ("success", 0, "RD000"),
+            ("general_error", 1, "RD001"),
+            ("no_duplicates", 2, "RD002"),
+            ("partial_success", 3, "RD003"),
+            ("too_many_errors", 4, "RD004"),
+            ("interrupted", 130, "RD130"),
+        ];
+        for (entry, (name, code, prefix)) in entries.iter().zip(expected) {
+            assert_eq!(entry["name"], name);
+            assert_eq!(entry["code"], code);
+            assert_eq!(entry["prefix"], prefix);
+            assert!(!entry["description"].as_str().unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_error_thresholds_default_never_escalates() {
+        let thresholds = ErrorThresholds::default();
+        assert_eq!(
+            thresholds.escalate(ExitCode::PartialSuccess, 1000, 10),
+            ExitCode::PartialSuccess
+        );
+        assert!(thresholds.exceeded(1000, 10).is_none());
+    }
+
+    #[test]
+    fn test_error_thresholds_max_errors() {
+        let thresholds = ErrorThresholds {
+            max_errors: Some(5),
+            max_error_rate: None,
+        };
+        for (errors, expected) in [
+            (1, ExitCode::PartialSuccess),
+            (5, ExitCode::PartialSuccess),
+            (6, ExitCode::TooManyErrors),
+            (500, ExitCode::TooManyErrors),
+        ] {
+            assert_eq!(
+                thresholds.escalate(ExitCode::PartialSuccess, errors, 1000),
+                expected,
+                "{} errors",
+                errors
+            );
+        }
+        assert_eq!(
+            thresholds.exceeded(6, 1000).unwrap(),
+            "6 scan error(s) exceed --max-errors 5"
+        );
+        // Only partial success is escalated
+        assert_eq!(
+            thresholds.escalate(ExitCode::Interrupted, 10, 1000),
+            ExitCode::Interrupted
+        );
+        assert_eq!(
+            thresholds.escalate(ExitCode::Success, 10, 1000),
+            ExitCode::Success
+        );
+    }
+
+    #[test]
+    fn test_error_thresholds_max_error_rate() {
+        let thresholds = ErrorThresholds {
+            max_errors: None,
+            max_error_rate: Some(2.5),
+        };
+        assert_eq!(
+            thresholds.escalate(ExitCode::PartialSuccess, 25, 1000),
+            ExitCode::PartialSuccess
+        );
+        assert_eq!(
+            thresholds.escalate(ExitCode::PartialSuccess, 26, 1000),
+            ExitCode::TooManyErrors
+        );
+        assert_eq!(
+            thresholds.exceeded(3, 100).unwrap(),
+            "scan error rate 3.0% exceeds --max-error-rate 2.5"
+        );
+        // Errors without any scanned file are a 100% rate
+        assert_eq!(ErrorThresholds::error_rate(2, 0), 100.0);
+        assert_eq!(ErrorThresholds::error_rate(0, 0), 0.0);
+        assert_eq!(
+            thresholds.escalate(ExitCode::PartialSuccess, 1, 0),
+            ExitCode::TooManyErrors
+        );
+
+        // Either limit is enough
+        let both = ErrorThresholds {
+            max_errors: Some(100),
+            max_error_rate: Some(50.0),
+        };
+        assert_eq!(
+            both.escalate(ExitCode::PartialSuccess, 60, 100),
+            ExitCode::TooManyErrors
+        );
+        assert_eq!(
+            both.escalate(ExitCode::PartialSuccess, 101, 100_000),
+            ExitCode::TooManyErrors
+        );
+        assert_eq!(
+            both.escalate(ExitCode::PartialSuccess, 40, 100),
+            ExitCode::PartialSuccess
+        );
+    }
+}
@@ -15,7 +15,10 @@
 //! - [`tui`]: Interactive terminal user interface
 //! - [`actions`]: File operations (delete, preview)
 //! - [`cache`]: Persistent hash caching for faster rescans
-//! - [`output`]: Output formatters (JSON, CSV)
+//! - [`output`]: Output formatters (JSON, YAML, CSV)
+//! - [`watch`]: Watch mode that re-reports duplicates as files change
+//! - [`notify`]: Terminal bell and desktop notifications on scan completion
+//! - [`version`]: Version and build metadata
 
 // =============================================================================
 // Clippy Lint Configuration
@@ -47,28 +50,34 @@ pub mod config;
 pub mod duplicates;
 pub mod error;
 pub mod logging;
+pub mod notify;
 pub mod output;
 pub mod progress;
 pub mod scanner;
 pub mod session;
 pub mod signal;
 pub mod tui;
+pub mod version;
+pub mod watch;
 
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::sync::Arc;
 
+use crate::actions::rename::{plan_keeper_renames, KeeperRename};
 use crate::cache::HashCache;
 use crate::cli::{
-    build_group_map, Cli, Commands, LoadArgs, OutputFormat, ScanArgs, ScriptTypeArg, ThemeArg,
+    build_group_map, ApplyArgs, Cli, Commands, DiffArgs, HashArgs, LoadArgs, OutputFormat,
+    ProgressFormat, ScanArgs, ScriptTypeArg, StatsArgs, StatsOutputFormat, ThemeArg, VerifyArgs,
+    VersionArgs, WatchArgs, WatchOutputFormat,
 };
 use crate::config::Config;
-use crate::duplicates::{DuplicateFinder, FinderConfig};
-use crate::error::ExitCode;
-use crate::scanner::WalkerConfig;
-use crate::session::{Session, SessionGroup, SessionSettings};
+use crate::duplicates::{DuplicateFinder, FinderConfig, ScanCheckpoint};
+use crate::error::{ErrorThresholds, ExitCode};
+use crate::scanner::{RetryConfig, WalkerConfig};
+use crate::session::{Session, SessionCompression, SessionGroup, SessionSettings};
 use crate::tui::keybindings::KeyBindings;
 
 /// Run the application logic with the given CLI arguments.
@@ -79,10 +88,24 @@ use crate::tui::keybindings::KeyBindings;
 /// # Errors
 ///
 /// Returns an error if a fatal issue occurs during initialization or execution.
-pub fn run_app(cli: Cli) -> Result<ExitCode> {
+pub fn run_app(mut cli: Cli) -> Result<ExitCode> {
     // Load configuration with optional profile
     let mut config = Config::load_with_profile(cli.profile.as_deref());
 
+    // Handle --schema-version
+    if cli.schema_version {
+        println!("{}", crate::output::json::JSON_SCHEMA_VERSION);
+        return Ok(ExitCode::Success);
+    }
+
+    // Handle --print-exit-codes
+    if cli.print_exit_codes {
+        let json = serde_json::to_string_pretty(&crate::error::exit_code_map())
+            .context("Failed to serialize exit codes")?;
+        println!("{}", json);
+        return Ok(ExitCode::Success);
+    }
+
     // Handle --list-profiles
     if cli.list_profiles {
         if config.profile.is_empty() {
@@ -98,6 +121,10 @@ pub fn run_app(cli: Cli) -> Result<ExitCode> {
         return Ok(ExitCode::Success);
     }
 
+    let Some(command) = cli.command.take() else {
+        anyhow::bail!("A subcommand is required (see --help)");
+    };
+
     // Merge global CLI flags into config
     config.merge_cli(&cli);
 
@@ -132,14 +159,20 @@ pub fn run_app(cli: Cli) -> Result<ExitCode> {
     };
 
     // Initialize logging based on verbosity flags
-    logging::init_logging(cli.verbose, cli.quiet);
+    logging::init_logging(
+        cli.verbose,
+        cli.quiet,
+        cli.log_file.as_deref(),
+        cli.log_stderr,
+    )
+    .map_err(|e| anyhow::anyhow!("Cannot open log file: {}", e))?;
 
     // Install signal handler for graceful shutdown (Ctrl+C)
     let shutdown_handler = signal::install_handler().map_err(|e| anyhow::anyhow!("{}", e))?;
     let shutdown_flag = shutdown_handler.get_flag();
 
     // Handle subcommands
-    let result = match cli.command {
+    let result = match command {
         Commands::Scan(args) => {
             config.merge_scan_args(&args);
             handle_scan(
@@ -164,6 +197,13 @@ pub fn run_app(cli: Cli) -> Result<ExitCode> {
                 accessible,
             )
         }
+        Commands::Watch(args) => handle_watch(args, config, shutdown_flag.clone()),
+        Commands::Stats(args) => handle_stats(args, &config),
+        Commands::Diff(args) => handle_diff(args, &config),
+        Commands::Verify(args) => handle_verify(args, &config, shutdown_flag.clone()),
+        Commands::Hash(args) => handle_hash(args, &config, shutdown_flag.clone()),
+        Commands::Apply(args) => handle_apply(args, &config, shutdown_flag.clone(), cli.quiet),
+        Commands::Version(args) => handle_version(&args),
     };
 
     // If result is Ok, check if shutdown was requested during operation
@@ -206,30 +246,24 @@ fn handle_scan(
             reference_paths,
         )
     } else {
+        // Combine positional paths with any --paths-from list
+        let raw_paths = args.scan_paths().with_context(|| {
+            let list = args.paths_from.iter().chain(&args.paths_from0).next();
+            format!(
+                "Failed to read path list: {}",
+                list.map(|p| p.display().to_string()).unwrap_or_default()
+            )
+        })?;
+
         // Validate that at least one path is provided
-        if args.paths.is_empty() {
+        if raw_paths.is_empty() {
             anyhow::bail!(
                 "At least one path is required for scanning unless --load-session is used"
             );
         }
 
         // Canonicalize all scan paths and validate they exist
-        let mut canonical_paths = Vec::with_capacity(args.paths.len());
-        for raw_path in &args.paths {
-            let path = raw_path.canonicalize().map_err(|e| {
-                anyhow::anyhow!("Failed to resolve path '{}': {}", raw_path.display(), e)
-            })?;
-
-            if !path.exists() {
-                anyhow::bail!("Path does not exist: {}", path.display());
-            }
-
-            if !path.is_dir() {
-                anyhow::bail!("Path is not a directory: {}", path.display());
-            }
-
-            canonical_paths.push(path);
-        }
+        let canonical_paths = canonicalize_scan_paths(&raw_paths)?;
 
         log::debug!(
             "Scanning {} path(s): {:?}",
@@ -250,7 +284,16 @@ fn handle_scan(
             reference_paths.push(canonical_paths[0].clone());
         }
 
-        for ref_path in args.reference_paths {
+        let listed_references = args.all_reference_paths().with_context(|| {
+            format!(
+                "Failed to read reference list: {}",
+                args.reference_from
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default()
+            )
+        })?;
+        for ref_path in listed_references {
             if !ref_path.exists() {
                 anyhow::bail!("Reference path does not exist: {}", ref_path.display());
             }
@@ -265,101 +308,26 @@ fn handle_scan(
             }
         }
 
-        // Resolve cache path
-        let cache_path = if let Some(path) = config.cache.clone() {
-            path
-        } else {
-            let project_dirs = ProjectDirs::from("com", "rustdupe", "rustdupe")
-                .ok_or_else(|| anyhow::anyhow!("Failed to determine project directories"))?;
-            let cache_dir = project_dirs.cache_dir();
-            fs::create_dir_all(cache_dir).with_context(|| {
-                format!("Failed to create cache directory: {}", cache_dir.display())
-            })?;
-            cache_dir.join("hashes.db")
-        };
-
-        // Initialize cache
-        let hash_cache = if !config.no_cache {
-            log::debug!("Using cache at: {:?}", cache_path);
-            let cache = match HashCache::new(&cache_path) {
-                Ok(cache) => Some(cache),
-                Err(e) => {
-                    log::warn!(
-                        "Failed to open cache at {:?}: {}. Attempting recovery...",
-                        cache_path,
-                        e
-                    );
-                    if cache_path.exists() {
-                        // Try to delete the corrupted cache and create a new one
-                        if let Err(err) = fs::remove_file(&cache_path) {
-                            log::error!(
-                                "Failed to delete corrupted cache: {}. Caching disabled.",
-                                err
-                            );
-                            None
-                        } else {
-                            match HashCache::new(&cache_path) {
-                                Ok(cache) => {
-                                    log::info!("Cache recovered successfully (reset to empty)");
-                                    Some(cache)
-                                }
-                                Err(e2) => {
-                                    log::error!(
-                                        "Failed to recover cache: {}. Caching disabled.",
-                                        e2
-                                    );
-                                    None
-                                }
-                            }
-                        }
-                    } else {
-                        log::error!("Cache path does not exist but failed to initialize: {}. Caching disabled.", e);
-                        None
-                    }
-                }
-            };
-
-            if let Some(ref cache) = cache {
-                if args.clear_cache {
-                    log::info!("Clearing cache...");
-                    cache.clear().context("Failed to clear cache")?;
-                }
+        // Validate and canonicalize duplicate boundary directories
+        let mut dedupe_within = Vec::new();
+        for boundary in &config.dedupe_within {
+            if !boundary.is_dir() {
+                anyhow::bail!("Dedupe boundary is not a directory: {}", boundary.display());
             }
-            cache.map(Arc::new)
-        } else {
-            log::debug!("Caching is disabled");
-            None
-        };
-
-        // Compile regex patterns
-        let mut regex_include = Vec::new();
-        for pattern in &config.regex_include {
-            match regex::Regex::new(pattern) {
-                Ok(re) => regex_include.push(re),
-                Err(e) => anyhow::bail!("Invalid include regex '{}': {}", pattern, e),
+            let canon = boundary.canonicalize().with_context(|| {
+                format!("Failed to resolve dedupe boundary: {}", boundary.display())
+            })?;
+            if !dedupe_within.contains(&canon) {
+                dedupe_within.push(canon);
             }
         }
 
-        let mut regex_exclude = Vec::new();
-        for pattern in &config.regex_exclude {
-            match regex::Regex::new(pattern) {
-                Ok(re) => regex_exclude.push(re),
-                Err(e) => anyhow::bail!("Invalid exclude regex '{}': {}", pattern, e),
-            }
-        }
+        // Initialize cache
+        let hash_cache = open_hash_cache(&config, args.clear_cache)?;
 
         // Configure the walker
-        let walker_config = WalkerConfig::default()
-            .with_follow_symlinks(config.follow_symlinks)
-            .with_skip_hidden(config.skip_hidden)
-            .with_min_size(config.min_size)
-            .with_max_size(config.max_size)
-            .with_newer_than(config.newer_than.map(std::time::SystemTime::from))
-            .with_older_than(config.older_than.map(std::time::SystemTime::from))
-            .with_patterns(config.ignore_patterns.clone())
-            .with_regex_include(regex_include)
-            .with_regex_exclude(regex_exclude)
-            .with_file_categories(config.file_types.iter().map(|&t| t.into()).collect());
+        let walker_config = build_walker_config(&config)?;
+        let io_threads = resolve_io_threads(&config, &canonical_paths);
 
         // Build group map from CLI arguments
         let group_map = if !args.groups.is_empty() {
@@ -368,30 +336,72 @@ fn handle_scan(
             std::collections::HashMap::new()
         };
 
-        // Configure progress reporting
-        let progress = Some(Arc::new(crate::progress::Progress::with_accessible(
-            quiet, accessible,
-        )));
+        // Results headed for the TUI get a live scan screen instead of bars
+        let live_scan = (!quiet
+            && config.progress_format != ProgressFormat::Json
+            && !args.delete_to_script_and_run
+            && !config.summary_only
+            && resolve_output_format(args.output, config.output) == OutputFormat::Tui)
+            .then(|| Arc::new(std::sync::Mutex::new(crate::tui::app::ScanProgress::new())));
+        // The live scan screen can pause hashing
+        let pause_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        // Configure progress reporting (JSON lines replace the interactive bars)
+        let progress: Arc<dyn crate::duplicates::ProgressCallback> =
+            if config.progress_format == ProgressFormat::Json && !quiet {
+                Arc::new(crate::progress::JsonProgress::stderr())
+            } else if let Some(ref shared) = live_scan {
+                Arc::new(
+                    crate::progress::Progress::with_accessible(true, accessible)
+                        .with_scan_progress(shared.clone()),
+                )
+            } else {
+                Arc::new(crate::progress::Progress::with_accessible(
+                    quiet, accessible,
+                ))
+            };
+
+        if config.same_name_only {
+            log::warn!("--same-name-only: duplicates with different file names will not be found");
+        }
 
         // Configure the duplicate finder
         let mut finder_config = FinderConfig::default()
-            .with_io_threads(config.io_threads)
+            .with_io_threads(io_threads)
+            .with_cpu_threads(config.cpu_threads)
             .with_strict(config.strict)
             .with_paranoid(config.paranoid)
+            .with_paranoid_config(config.paranoid_config())
+            .with_include_empty(config.include_empty)
+            .with_protect_policy(config.protect)
             .with_mmap(config.mmap)
             .with_mmap_threshold(config.mmap_threshold)
             .with_io_buffer_size(config.io_buffer_size)
             .with_io_buffer_min(config.io_buffer_min)
             .with_io_buffer_max(config.io_buffer_max)
             .with_io_adaptive_buffer(config.io_adaptive_buffer)
+            .with_read_buffer_size(config.read_buffer_size)
+            .with_sparse_aware(config.sparse_aware)
+            .with_include_streams(config.include_streams)
+            .with_retry(RetryConfig::with_retries(config.hash_retries))
+            .with_sample_above(config.sample_above)
+            .with_hardlinks(config.hardlinks)
             .with_walker_config(walker_config)
             .with_shutdown_flag(shutdown_flag.clone())
             .with_reference_paths(reference_paths.clone())
             .with_group_map(group_map)
             .with_bloom_fp_rate(config.bloom_fp_rate)
             .with_min_group_size(config.min_group_size)
+            .with_dedupe_within(dedupe_within)
             .with_similar_images(config.similar_images)
             .with_similar_documents(config.similar_documents)
+            .with_partial_similarity(config.partial_similarity)
+            .with_partial_threshold(config.partial_threshold)
+            .with_name_cluster(config.name_cluster)
+            .with_stable_order(config.stable_order)
+            .with_precount(config.precount)
+            .with_same_name_only(config.same_name_only)
+            .with_intentional_patterns(config.intentional.clone())
             .with_similarity_threshold(config.similarity_threshold)
             .with_doc_similarity_threshold(config.doc_similarity_threshold);
 
@@ -399,51 +409,132 @@ fn handle_scan(
             finder_config = finder_config.with_cache(cache);
         }
 
-        if let Some(ref p) = progress {
-            finder_config = finder_config
-                .with_progress_callback(p.clone() as Arc<dyn crate::duplicates::ProgressCallback>);
+        finder_config = finder_config.with_progress_callback(progress);
+        if live_scan.is_some() {
+            finder_config = finder_config.with_pause_flag(pause_flag.clone());
+        }
+
+        if let Some(ref resume_path) = args.resume {
+            let checkpoint = ScanCheckpoint::load(resume_path)
+                .with_context(|| format!("Failed to load checkpoint: {}", resume_path.display()))?;
+            log::info!("Resuming scan from checkpoint {}", resume_path.display());
+            finder_config = finder_config.with_resume_checkpoint(checkpoint);
+        }
+
+        if let Some(checkpoint_path) = args.checkpoint.clone().or_else(|| args.resume.clone()) {
+            finder_config = finder_config.with_checkpoint_path(checkpoint_path);
+        }
+
+        if let Err(e) = finder_config.init_global_cpu_pool() {
+            log::warn!("Could not size the CPU thread pool: {}", e);
         }
 
         let finder = DuplicateFinder::new(finder_config);
 
         log::info!("Starting scan of {} path(s)", canonical_paths.len());
 
-        match finder.find_duplicates_in_paths(canonical_paths.clone()) {
-            Ok((groups, summary)) => {
+        let scan_result = match live_scan {
+            Some(shared) => {
+                let app = crate::tui::App::new()
+                    .with_theme(theme)
+                    .with_accessible(accessible)
+                    .with_size_format(config.size_units)
+                    .with_scan_progress(shared)
+                    .with_pause_flag(pause_flag);
+                scan_with_live_tui(
+                    finder,
+                    canonical_paths.clone(),
+                    app,
+                    shutdown_flag.clone(),
+                    keybindings.clone(),
+                )
+            }
+            None => finder
+                .find_duplicates_in_paths(canonical_paths.clone())
+                .map_err(anyhow::Error::from),
+        };
+
+        match scan_result {
+            Ok((mut groups, mut summary)) => {
+                if let Some(ref prior_path) = args.since_session {
+                    let prior = Session::load(prior_path).with_context(|| {
+                        format!("Failed to load prior session: {}", prior_path.display())
+                    })?;
+                    let suppressed = prior.suppress_decided(&mut groups);
+                    if !suppressed.is_empty() {
+                        log::info!(
+                            "Skipping {} group(s) already decided in {}",
+                            suppressed.len(),
+                            prior_path.display()
+                        );
+                    }
+                    let dup_files: usize = suppressed.iter().map(|g| g.duplicate_count()).sum();
+                    let dup_size: u64 = suppressed.iter().map(|g| g.total_size()).sum();
+                    let wasted: u64 = suppressed.iter().map(|g| g.wasted_space()).sum();
+                    summary.duplicate_groups =
+                        summary.duplicate_groups.saturating_sub(suppressed.len());
+                    summary.duplicate_files = summary.duplicate_files.saturating_sub(dup_files);
+                    summary.total_duplicate_size =
+                        summary.total_duplicate_size.saturating_sub(dup_size);
+                    summary.reclaimable_space = summary.reclaimable_space.saturating_sub(wasted);
+                    for group in &suppressed {
+                        summary.size_histogram.remove_group(group);
+                    }
+                    summary.reclaimable_by_extension =
+                        crate::duplicates::reclaimable_by_extension(&groups);
+                }
+
                 let settings = SessionSettings {
                     follow_symlinks: config.follow_symlinks,
+                    follow_dir_symlinks: config.follow_dir_symlinks,
+                    follow_file_symlinks: config.follow_file_symlinks,
                     skip_hidden: config.skip_hidden,
                     min_size: config.min_size,
                     max_size: config.max_size,
                     newer_than: config.newer_than,
                     older_than: config.older_than,
                     ignore_patterns: config.ignore_patterns.clone(),
+                    exclude_dirs: config.exclude_dirs.clone(),
                     regex_include: config.regex_include.clone(),
                     regex_exclude: config.regex_exclude.clone(),
                     file_categories: config.file_types.iter().map(|&t| t.into()).collect(),
-                    io_threads: config.io_threads,
+                    extensions: config.extensions.clone(),
+                    io_threads,
                     paranoid: config.paranoid,
+                    include_empty: config.include_empty,
                     mmap: config.mmap,
                     mmap_threshold: config.mmap_threshold,
                     similar_images: config.similar_images,
                     similar_documents: config.similar_documents,
+                    name_cluster: config.name_cluster,
                     min_group_size: config.min_group_size,
                     io_buffer_size: config.io_buffer_size,
                     io_buffer_min: config.io_buffer_min,
                     io_buffer_max: config.io_buffer_max,
                     io_adaptive_buffer: config.io_adaptive_buffer,
+                    read_buffer_size: config.read_buffer_size,
+                    sample_above: config.sample_above,
+                    include_streams: config.include_streams,
+                    hardlinks: config.hardlinks,
                     doc_similarity_threshold: config.doc_similarity_threshold,
+                    sort_column: crate::tui::app::SortColumn::default(),
+                    sort_direction: crate::tui::app::SortDirection::default(),
                 };
                 (groups, summary, canonical_paths, settings, reference_paths)
             }
             Err(e) => {
-                anyhow::bail!(e);
+                return Err(e);
             }
         }
     };
 
-    let config_output = config.output;
+    let config_output = if args.delete_to_script_and_run {
+        script_and_run_format(args.output)?
+    } else {
+        resolve_output_format(args.output, config.output)
+    };
     let config_dry_run = config.dry_run;
+    let config_notify = config.notify;
 
     handle_results(ResultContext {
         groups,
@@ -452,6 +543,8 @@ fn handle_scan(
         output_format: config_output,
         output_file: args.output_file,
         script_type: args.script_type,
+        run_script: args.delete_to_script_and_run,
+        assume_yes: args.yes,
         save_session: args.save_session,
         scan_paths,
         settings,
@@ -459,6 +552,7 @@ fn handle_scan(
         initial_session: None,
         reference_paths,
         dry_run: config_dry_run,
+        notify: config_notify,
         quiet,
         theme,
         keybindings,
@@ -466,6 +560,501 @@ fn handle_scan(
     })
 }
 
+/// Run the scan on a worker thread while the TUI shows its progress.
+///
+/// Quitting the scan screen requests shutdown, which the finder honours
+/// like Ctrl+C.
+fn scan_with_live_tui(
+    finder: DuplicateFinder,
+    paths: Vec<std::path::PathBuf>,
+    mut app: crate::tui::App,
+    shutdown_flag: Arc<std::sync::atomic::AtomicBool>,
+    keybindings: KeyBindings,
+) -> Result<(
+    Vec<crate::duplicates::DuplicateGroup>,
+    crate::duplicates::ScanSummary,
+)> {
+    let scan = std::thread::spawn(move || finder.find_duplicates_in_paths(paths));
+    let tui_result = crate::tui::run_scan_tui(
+        &mut app,
+        || scan.is_finished(),
+        shutdown_flag.clone(),
+        Some(keybindings),
+    );
+    if tui_result.is_err() {
+        // Without a screen the scan result is useless; stop it promptly
+        shutdown_flag.store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+    let result = scan
+        .join()
+        .map_err(|_| anyhow::anyhow!("Scan thread panicked"))?;
+    tui_result?;
+    Ok(result?)
+}
+
+/// Canonicalize scan root paths, ensuring each exists and is a directory.
+fn canonicalize_scan_paths(paths: &[std::path::PathBuf]) -> Result<Vec<std::path::PathBuf>> {
+    let mut canonical_paths = Vec::with_capacity(paths.len());
+    for raw_path in paths {
+        let path = raw_path.canonicalize().map_err(|e| {
+            anyhow::anyhow!("Failed to resolve path '{}': {}", raw_path.display(), e)
+        })?;
+
+        if !path.exists() {
+            anyhow::bail!("Path does not exist: {}", path.display());
+        }
+
+        if !path.is_dir() {
+            anyhow::bail!("Path is not a directory: {}", path.display());
+        }
+
+        canonical_paths.push(path);
+    }
+    Ok(canonical_paths)
+}
+
+/// Open the hash cache configured in `config`, recovering from corruption.
+///
+/// Returns `None` when caching is disabled or the cache cannot be opened.
+fn open_hash_cache(config: &Config, clear_cache: bool) -> Result<Option<Arc<HashCache>>> {
+    // Resolve cache path
+    let cache_path = if let Some(path) = config.cache.clone() {
+        path
+    } else {
+        let project_dirs = ProjectDirs::from("com", "rustdupe", "rustdupe")
+            .ok_or_else(|| anyhow::anyhow!("Failed to determine project directories"))?;
+        let cache_dir = project_dirs.cache_dir();
+        fs::create_dir_all(cache_dir).with_context(|| {
+            format!("Failed to create cache directory: {}", cache_dir.display())
+        })?;
+        cache_dir.join("hashes.db")
+    };
+
+    // Initialize cache
+    let hash_cache = if !config.no_cache {
+        log::debug!("Using cache at: {:?}", cache_path);
+        let cache = match HashCache::with_compression(&cache_path, config.cache_compress) {
+            Ok(cache) => Some(cache),
+            Err(e) => {
+                log::warn!(
+                    "Failed to open cache at {:?}: {}. Attempting recovery...",
+                    cache_path,
+                    e
+                );
+                if cache_path.exists() {
+                    // Try to delete the corrupted cache and create a new one
+                    if let Err(err) = fs::remove_file(&cache_path) {
+                        log::error!(
+                            "Failed to delete corrupted cache: {}. Caching disabled.",
+                            err
+                        );
+                        None
+                    } else {
+                        match HashCache::with_compression(&cache_path, config.cache_compress) {
+                            Ok(cache) => {
+                                log::info!("Cache recovered successfully (reset to empty)");
+                                Some(cache)
+                            }
+                            Err(e2) => {
+                                log::error!("Failed to recover cache: {}. Caching disabled.", e2);
+                                None
+                            }
+                        }
+                    }
+                } else {
+                    log::error!(
+                        "Cache path does not exist but failed to initialize: {}. Caching disabled.",
+                        e
+                    );
+                    None
+                }
+            }
+        };
+
+        if let Some(ref cache) = cache {
+            if clear_cache {
+                log::info!("Clearing cache...");
+                cache.clear().context("Failed to clear cache")?;
+            }
+        }
+        cache.map(Arc::new)
+    } else {
+        log::debug!("Caching is disabled");
+        None
+    };
+
+    Ok(hash_cache)
+}
+
+/// Build the walker configuration from the merged application config.
+fn build_walker_config(config: &Config) -> Result<WalkerConfig> {
+    // Compile regex patterns
+    let mut regex_include = Vec::new();
+    for pattern in &config.regex_include {
+        match regex::Regex::new(pattern) {
+            Ok(re) => regex_include.push(re),
+            Err(e) => anyhow::bail!("Invalid include regex '{}': {}", pattern, e),
+        }
+    }
+
+    let mut regex_exclude = Vec::new();
+    for pattern in &config.regex_exclude {
+        match regex::Regex::new(pattern) {
+            Ok(re) => regex_exclude.push(re),
+            Err(e) => anyhow::bail!("Invalid exclude regex '{}': {}", pattern, e),
+        }
+    }
+
+    let (only_owner, exclude_owner) = resolve_owner_filter(config)?;
+
+    // Configure the walker
+    Ok(WalkerConfig::default()
+        .with_follow_dir_symlinks(config.follow_symlinks || config.follow_dir_symlinks)
+        .with_follow_file_symlinks(config.follow_symlinks || config.follow_file_symlinks)
+        .with_report_broken_symlinks(config.report_broken_symlinks)
+        .with_skip_hidden(config.skip_hidden)
+        .with_min_size(config.min_size)
+        .with_max_size(config.max_size)
+        .with_newer_than(config.newer_than.map(std::time::SystemTime::from))
+        .with_older_than(config.older_than.map(std::time::SystemTime::from))
+        .with_settle_seconds(config.settle)
+        .with_owner_filter(only_owner, exclude_owner)
+        .with_patterns(config.ignore_patterns.clone())
+        .with_exclude_dirs(config.exclude_dirs.clone())
+        .with_regex_include(regex_include)
+        .with_regex_exclude(regex_exclude)
+        .with_file_categories(config.file_types.iter().map(|&t| t.into()).collect())
+        .with_extensions(config.extensions.clone())
+        .with_sniff_content(config.sniff_content))
+}
+
+/// Compression for a session saved to `path`: `--session-compression` if
+/// given, otherwise whatever the file extension implies.
+fn session_compression(config: &Config, path: &std::path::Path) -> SessionCompression {
+    config
+        .session_compression
+        .unwrap_or_else(|| SessionCompression::from_path(path))
+}
+
+/// I/O thread count for a scan of `roots`: `--io-threads` as given, or
+/// picked from the roots' storage type with `--io-threads auto`.
+fn resolve_io_threads(config: &Config, roots: &[std::path::PathBuf]) -> usize {
+    if !config.io_threads_auto {
+        return config.io_threads;
+    }
+    let kind = crate::scanner::disk::detect_disk_kind_all(roots);
+    let threads = kind.io_threads();
+    log::info!(
+        "Storage detected as {}: using {} I/O thread(s)",
+        kind,
+        threads
+    );
+    threads
+}
+
+/// Resolve `--only-owner` and `--exclude-owner` users to uids.
+fn resolve_owner_filter(config: &Config) -> Result<(Option<Vec<u32>>, Vec<u32>)> {
+    use crate::scanner::owner::{owner_filtering_supported, resolve_owner};
+
+    let resolve = |specs: &[String]| -> Result<Vec<u32>> {
+        specs
+            .iter()
+            .map(|spec| {
+                resolve_owner(spec).ok_or_else(|| anyhow::anyhow!("Unknown user '{}'", spec))
+            })
+            .collect()
+    };
+
+    if !owner_filtering_supported() {
+        // Nothing to resolve user names against; the filters have no effect
+        if !(config.only_owner.is_empty() && config.exclude_owner.is_empty()) {
+            log::warn!("Owner filters are only supported on Unix and will be ignored");
+        }
+        return Ok((None, Vec::new()));
+    }
+
+    let only = if config.only_owner.is_empty() {
+        None
+    } else {
+        Some(resolve(&config.only_owner)?)
+    };
+    Ok((only, resolve(&config.exclude_owner)?))
+}
+
+/// Validate and canonicalize `--permanent-under` directories so they match
+/// the canonical paths of scanned files.
+fn resolve_permanent_under(config: &Config) -> Result<Vec<std::path::PathBuf>> {
+    let mut permanent_under = Vec::new();
+    for dir in &config.permanent_under {
+        if !dir.is_dir() {
+            anyhow::bail!(
+                "Permanent-delete path is not a directory: {}",
+                dir.display()
+            );
+        }
+        let canon = dir.canonicalize().with_context(|| {
+            format!("Failed to resolve permanent-delete path: {}", dir.display())
+        })?;
+        if !permanent_under.contains(&canon) {
+            permanent_under.push(canon);
+        }
+    }
+    Ok(permanent_under)
+}
+
+/// Print statistics recomputed from a saved session.
+fn handle_stats(args: StatsArgs, config: &Config) -> Result<ExitCode> {
+    let session = Session::load(&args.session)
+        .with_context(|| format!("Failed to load session: {}", args.session.display()))?;
+    let stats = crate::output::stats::SessionStats::from_session(&session, args.top);
+
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    match args.output {
+        StatsOutputFormat::Human => stats.write_text(&mut handle, config.size_units)?,
+        StatsOutputFormat::Json => stats.write_json(&mut handle, true)?,
+    }
+    Ok(ExitCode::Success)
+}
+
+/// Print version and build metadata.
+fn handle_version(args: &VersionArgs) -> Result<ExitCode> {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    crate::version::BuildInfo::current().write_to(&mut handle, args.json)?;
+    Ok(ExitCode::Success)
+}
+
+fn handle_diff(args: DiffArgs, config: &Config) -> Result<ExitCode> {
+    let old = Session::load(&args.old)
+        .with_context(|| format!("Failed to load session: {}", args.old.display()))?;
+    let new = Session::load(&args.new)
+        .with_context(|| format!("Failed to load session: {}", args.new.display()))?;
+    let diff = crate::session::diff_sessions(&old, &new);
+
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    match args.output {
+        StatsOutputFormat::Human => diff.write_text(&mut handle, config.size_units)?,
+        StatsOutputFormat::Json => diff.write_json(&mut handle, true)?,
+    }
+    Ok(ExitCode::Success)
+}
+
+fn handle_verify(
+    args: VerifyArgs,
+    config: &Config,
+    shutdown_flag: Arc<std::sync::atomic::AtomicBool>,
+) -> Result<ExitCode> {
+    let session = Session::load(&args.session)
+        .with_context(|| format!("Failed to load session: {}", args.session.display()))?;
+    let hasher = (!args.fast).then(|| build_hasher(config, shutdown_flag));
+    let report = crate::session::verify_session(&session, hasher.as_ref());
+
+    let stdout = io::stdout();
+    report.write_text(&mut stdout.lock())?;
+    Ok(if report.all_match() {
+        ExitCode::Success
+    } else {
+        ExitCode::PartialSuccess
+    })
+}
+
+fn handle_hash(
+    args: HashArgs,
+    config: &Config,
+    shutdown_flag: Arc<std::sync::atomic::AtomicBool>,
+) -> Result<ExitCode> {
+    // The cache keys on canonical paths, so report the same path it would
+    let path = args
+        .path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve path: {}", args.path.display()))?;
+    if !path.is_file() {
+        anyhow::bail!("Not a regular file: {}", path.display());
+    }
+
+    let hasher = build_hasher(config, shutdown_flag);
+    let report =
+        crate::output::HashReport::compute(&hasher, &path, args.prehash_only, config.sample_above)?;
+    let stdout = io::stdout();
+    report.write_to(&mut stdout.lock(), config.size_units)?;
+    Ok(ExitCode::Success)
+}
+
+/// Build a standalone hasher with the same I/O settings a scan would use.
+fn build_hasher(
+    config: &Config,
+    shutdown_flag: Arc<std::sync::atomic::AtomicBool>,
+) -> crate::scanner::Hasher {
+    let mut hasher = crate::scanner::Hasher::new()
+        .with_mmap(config.mmap)
+        .with_mmap_threshold(config.mmap_threshold)
+        .with_buffer_size(config.io_buffer_size)
+        .with_buffer_min(config.io_buffer_min)
+        .with_buffer_max(config.io_buffer_max)
+        .with_adaptive_buffer(config.io_adaptive_buffer)
+        .with_sparse_aware(config.sparse_aware)
+        .with_include_streams(config.include_streams)
+        .with_retry(RetryConfig::with_retries(config.hash_retries))
+        .with_shutdown_flag(shutdown_flag);
+    if let Some(size) = config.read_buffer_size {
+        hasher = hasher.with_read_buffer_size(size);
+    }
+    hasher
+}
+
+/// Progress callback that logs each deletion.
+struct LogProgress;
+
+impl crate::actions::DeleteProgressCallback for LogProgress {
+    fn on_before_delete(&self, _path: &std::path::Path, _index: usize, _total: usize) {}
+    fn on_delete_success(&self, path: &std::path::Path, size: u64) {
+        log::info!("Deleted {} ({} bytes)", path.display(), size);
+    }
+    fn on_delete_failure(&self, path: &std::path::Path, error: &str) {
+        log::warn!("Failed to delete {}: {}", path.display(), error);
+    }
+    fn on_complete(&self, _result: &crate::actions::BatchDeleteResult) {}
+}
+
+/// Delete the files marked in a reviewed report, skipping any that changed
+/// since the report was written.
+fn handle_apply(
+    args: ApplyArgs,
+    config: &Config,
+    shutdown_flag: Arc<std::sync::atomic::AtomicBool>,
+    quiet: bool,
+) -> Result<ExitCode> {
+    let plan = crate::actions::ApplyPlan::load(&args.input)
+        .with_context(|| format!("Failed to read decisions: {}", args.input.display()))?;
+    let hasher = (args.paranoid || config.paranoid).then(|| build_hasher(config, shutdown_flag));
+    let outcome = plan.verify(hasher.as_ref());
+
+    for skipped in &outcome.skipped {
+        eprintln!("Skipped {}: {}", skipped.path.display(), skipped.reason);
+    }
+    let partial = if outcome.skipped.is_empty() {
+        ExitCode::Success
+    } else {
+        ExitCode::PartialSuccess
+    };
+
+    if args.dry_run || config.dry_run {
+        if !quiet {
+            for path in &outcome.scheduled {
+                println!("Would delete: {}", path.display());
+            }
+            println!(
+                "Dry run: {} of {} marked file(s) would be deleted, {} skipped",
+                outcome.scheduled.len(),
+                plan.marked_count(),
+                outcome.skipped.len()
+            );
+        }
+        return Ok(partial);
+    }
+    if outcome.scheduled.is_empty() {
+        if !quiet {
+            println!(
+                "Nothing to delete: {} marked file(s), {} skipped",
+                plan.marked_count(),
+                outcome.skipped.len()
+            );
+        }
+        return Ok(partial);
+    }
+
+    // Trash by default; --permanent-under rules from the config still apply
+    let (only_owner, exclude_owner) = resolve_owner_filter(config)?;
+    let permanent_under = resolve_permanent_under(config)?;
+    let delete_config = crate::actions::DeleteConfig::trash()
+        .with_permanent_under(&permanent_under)
+        .with_settle_seconds(config.settle)
+        .with_owner_filter(only_owner, exclude_owner)
+        .with_max_total_bytes(config.max_total_delete)
+        .with_trash_override(config.trash_dir.clone())
+        .with_preserve_xattrs(config.preserve_xattrs)
+        .with_transactional(config.transactional_delete);
+    let result =
+        crate::actions::delete_batch(&outcome.scheduled, &delete_config, Some(&LogProgress));
+    for (path, error) in &result.failures {
+        eprintln!("Failed to delete {}: {}", path.display(), error);
+    }
+    if !quiet {
+        println!("{}", result.summary());
+    }
+
+    Ok(if result.failures.is_empty() {
+        partial
+    } else {
+        ExitCode::PartialSuccess
+    })
+}
+
+/// Run watch mode, emitting a JSON report for every settled batch of changes.
+fn handle_watch(
+    args: WatchArgs,
+    config: Config,
+    shutdown_flag: Arc<std::sync::atomic::AtomicBool>,
+) -> Result<ExitCode> {
+    let roots = canonicalize_scan_paths(&args.paths)?;
+    let walker_config = build_walker_config(&config)?;
+    let hash_cache = open_hash_cache(&config, false)?;
+
+    let mut finder_config = FinderConfig::default()
+        .with_io_threads(resolve_io_threads(&config, &roots))
+        .with_cpu_threads(config.cpu_threads)
+        .with_paranoid(config.paranoid)
+        .with_paranoid_config(config.paranoid_config())
+        .with_include_empty(config.include_empty)
+        .with_mmap(config.mmap)
+        .with_mmap_threshold(config.mmap_threshold)
+        .with_sparse_aware(config.sparse_aware)
+        .with_include_streams(config.include_streams)
+        .with_retry(RetryConfig::with_retries(config.hash_retries))
+        .with_sample_above(config.sample_above)
+        .with_hardlinks(config.hardlinks)
+        .with_walker_config(walker_config)
+        .with_shutdown_flag(shutdown_flag)
+        .with_bloom_fp_rate(config.bloom_fp_rate)
+        .with_min_group_size(config.min_group_size)
+        .with_stable_order(config.stable_order)
+        .with_intentional_patterns(config.intentional.clone());
+
+    match hash_cache {
+        Some(cache) => finder_config = finder_config.with_cache(cache),
+        None => log::warn!("Hash cache is disabled; every batch will re-hash candidate files"),
+    }
+
+    if let Err(e) = finder_config.init_global_cpu_pool() {
+        log::warn!("Could not size the CPU thread pool: {}", e);
+    }
+
+    let pretty = args.output == WatchOutputFormat::Json;
+    let mut watcher = watch::DuplicateWatcher::new(
+        roots,
+        finder_config,
+        std::time::Duration::from_millis(args.debounce_ms),
+    );
+
+    watcher.run(|groups, summary| {
+        let exit_code = if groups.is_empty() {
+            ExitCode::NoDuplicates
+        } else {
+            ExitCode::Success
+        };
+        let stdout = io::stdout();
+        let mut handle = stdout.lock();
+        crate::output::JsonStreamOutput::new(groups, summary, exit_code, &config)
+            .write_to(&mut handle, pretty)
+            .map_err(io::Error::other)?;
+        handle.flush()
+    })?;
+
+    Ok(ExitCode::Success)
+}
+
 fn handle_load(
     args: LoadArgs,
     config: Config,
@@ -483,7 +1072,11 @@ fn handle_load(
         .map(|g| g.reference_paths.clone())
         .unwrap_or_default();
 
-    let config_output = config.output;
+    let config_output = if args.delete_to_script_and_run {
+        script_and_run_format(args.output)?
+    } else {
+        resolve_output_format(args.output, config.output)
+    };
     let config_dry_run = config.dry_run;
 
     handle_results(ResultContext {
@@ -493,6 +1086,8 @@ fn handle_load(
         output_format: config_output,
         output_file: args.output_file,
         script_type: args.script_type,
+        run_script: args.delete_to_script_and_run,
+        assume_yes: args.yes,
         save_session: None,
         scan_paths: session.scan_paths.clone(),
         settings: session.settings.clone(),
@@ -500,6 +1095,7 @@ fn handle_load(
         initial_session: Some(session),
         reference_paths,
         dry_run: config_dry_run,
+        notify: false,
         quiet,
         theme,
         keybindings,
@@ -514,6 +1110,10 @@ struct ResultContext {
     output_format: OutputFormat,
     output_file: Option<std::path::PathBuf>,
     script_type: Option<ScriptTypeArg>,
+    /// Run the saved deletion script after confirmation
+    run_script: bool,
+    /// Skip the confirmation prompt before running the script
+    assume_yes: bool,
     save_session: Option<std::path::PathBuf>,
     scan_paths: Vec<std::path::PathBuf>,
     settings: SessionSettings,
@@ -521,12 +1121,212 @@ struct ResultContext {
     initial_session: Option<Session>,
     reference_paths: Vec<std::path::PathBuf>,
     dry_run: bool,
+    notify: bool,
     quiet: bool,
     theme: ThemeArg,
     keybindings: KeyBindings,
     accessible: bool,
 }
 
+/// Output format for `--delete-to-script-and-run`, which always writes a script.
+fn script_and_run_format(requested: Option<OutputFormat>) -> Result<OutputFormat> {
+    match requested {
+        None | Some(OutputFormat::Script) => Ok(OutputFormat::Script),
+        Some(other) => anyhow::bail!(
+            "--delete-to-script-and-run writes a deletion script and cannot be used with --output {}",
+            other
+        ),
+    }
+}
+
+/// Show a saved deletion script, ask for confirmation, and run it.
+///
+/// Declining leaves the script in place and returns success. Failed
+/// deletions and a non-zero script exit are reported as partial success.
+fn run_deletion_script(
+    path: &std::path::Path,
+    script_type: crate::output::ScriptType,
+    assume_yes: bool,
+    quiet: bool,
+) -> Result<ExitCode> {
+    let script = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read deletion script: {}", path.display()))?;
+    let deletions = script
+        .lines()
+        .filter(|line| line.starts_with("# DELETE: "))
+        .count();
+
+    if !quiet || !assume_yes {
+        println!("{}", script);
+    }
+    if !assume_yes {
+        if !io::stdin().is_terminal() {
+            anyhow::bail!(
+                "Not running {} without confirmation: stdin is not a terminal (pass --yes to run it anyway)",
+                path.display()
+            );
+        }
+        eprint!(
+            "Run {} to delete {} file(s)? [y/N] ",
+            path.display(),
+            deletions
+        );
+        io::stderr().flush().ok();
+        let mut answer = String::new();
+        io::stdin()
+            .read_line(&mut answer)
+            .context("Failed to read confirmation")?;
+        if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            println!("Script not run; it is saved at {}", path.display());
+            return Ok(ExitCode::Success);
+        }
+    }
+
+    let run = crate::actions::run_script(path, script_type)?;
+    print!("{}", run.stdout);
+    eprint!("{}", run.stderr);
+    for failed in &run.failed {
+        eprintln!("Failed to delete {}", failed.display());
+    }
+    if run.success() {
+        Ok(ExitCode::Success)
+    } else {
+        match run.exit_code {
+            Some(code) => eprintln!("Deletion script exited with status {}", code),
+            None => eprintln!("Deletion script was terminated by a signal"),
+        }
+        Ok(ExitCode::PartialSuccess)
+    }
+}
+
+/// Output format for a run, falling back from the TUI to the text listing
+/// when `--output` was not given and stdin or stdout is not a terminal.
+fn resolve_output_format(
+    requested: Option<OutputFormat>,
+    configured: OutputFormat,
+) -> OutputFormat {
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    if requested.is_none() && configured == OutputFormat::Tui && !interactive {
+        log::debug!("Not running interactively, using text output");
+        OutputFormat::Text
+    } else {
+        configured
+    }
+}
+
+/// Write only the scan summary for `--summary-only`.
+///
+/// JSON and YAML keep their envelope without the `duplicates` array; all
+/// other formats (including the TUI) print a compact text block.
+fn write_summary_only(
+    format: OutputFormat,
+    output_file: Option<&std::path::Path>,
+    summary: &crate::duplicates::ScanSummary,
+    exit_code: ExitCode,
+    config: &Config,
+) -> Result<()> {
+    let output = crate::output::SummaryOutput::new(summary, exit_code, config);
+    let mut writer: Box<dyn Write> = match output_file {
+        Some(path) => Box::new(
+            fs::File::create(path)
+                .with_context(|| format!("Failed to create output file: {}", path.display()))?,
+        ),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    match format {
+        OutputFormat::Json => output
+            .write_json(&mut writer, !config.json_compact)
+            .context("Failed to write JSON summary")?,
+        OutputFormat::Yaml => output
+            .write_yaml(&mut writer)
+            .context("Failed to write YAML summary")?,
+        _ => output
+            .write_text(&mut writer)
+            .context("Failed to write summary")?,
+    }
+    writer.flush().context("Failed to flush summary output")?;
+    if let Some(path) = output_file {
+        log::info!("Summary saved to {:?}", path);
+    }
+    Ok(())
+}
+
+/// Print the renames `--rename-keeper` would apply, for runs that delete nothing.
+fn report_keeper_renames(
+    groups: &[crate::duplicates::DuplicateGroup],
+    is_deleted: impl Fn(&std::path::Path) -> bool,
+    rule: &KeeperRename,
+) {
+    let planned = plan_keeper_renames(groups, is_deleted, rule);
+    if planned.is_empty() {
+        return;
+    }
+    eprintln!("Keeper renames (not applied):");
+    for (from, to) in &planned {
+        eprintln!("  {} -> {}", from.display(), to.display());
+    }
+}
+
+/// Print the directories `--delete-empty-dirs` would remove once `pending`
+/// files are deleted, for runs that delete nothing.
+fn report_empty_dirs(
+    pending: &[std::path::PathBuf],
+    protected: &[std::path::PathBuf],
+    roots: &[std::path::PathBuf],
+) {
+    let candidates = crate::actions::cleanup::candidate_dirs(pending, protected);
+    let planned = crate::actions::cleanup::find_empty_dirs(&candidates, roots, pending);
+    if planned.is_empty() {
+        return;
+    }
+    eprintln!("Empty directories (not removed):");
+    for dir in &planned {
+        eprintln!("  {}", dir.display());
+    }
+}
+
+/// Ring the bell and show a desktop notification for `--notify`.
+///
+/// Notification failures never fail the run.
+fn notify_completion(
+    summary: &crate::duplicates::ScanSummary,
+    size_format: crate::output::SizeFormat,
+) {
+    if let Err(e) = crate::notify::notify_scan_complete(summary, size_format) {
+        log::warn!("Could not send completion notification: {}", e);
+    }
+}
+
+/// Remember the TUI column preset in the configuration file.
+///
+/// The file is reloaded so that command-line overrides from this run are not
+/// written back along with it.
+fn save_column_preset(preset: crate::tui::app::ColumnPreset) {
+    let mut saved = Config::load();
+    saved.tui_columns = preset;
+    if let Err(e) = saved.save() {
+        log::warn!("Failed to save column preset: {}", e);
+    }
+}
+
+/// Suggest a `--min-size` after an unfiltered scan cluttered by small duplicates.
+fn print_min_size_hint(summary: &crate::duplicates::ScanSummary, settings: &SessionSettings) {
+    if settings.min_size.is_some() || summary.interrupted {
+        return;
+    }
+    if let Some(hint) = summary.min_size_recommendation() {
+        let arg = hint.min_size_arg();
+        eprintln!(
+            "\nHint: {:.0}% of reclaimable space is in files of {} or more; \
+             re-run with --min-size {} to focus.",
+            hint.space_share * 100.0,
+            arg,
+            arg
+        );
+    }
+}
+
 fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
     let ResultContext {
         mut groups,
@@ -536,6 +1336,8 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
         output_format,
         output_file,
         script_type,
+        run_script,
+        assume_yes,
         save_session,
         scan_paths,
         settings,
@@ -543,12 +1345,28 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
         initial_session,
         reference_paths,
         dry_run,
+        notify,
         quiet,
         theme,
         keybindings,
         accessible,
     } = ctx;
 
+    // Parse the keeper rename rule up front so a typo fails before any output
+    let keeper_rename = config
+        .rename_keeper
+        .as_deref()
+        .map(KeeperRename::parse)
+        .transpose()
+        .context("Invalid --rename-keeper expression")?
+        .map(|rule| rule.with_preserve_xattrs(config.preserve_xattrs));
+    if config.preserve_xattrs && !crate::actions::xattrs::xattrs_supported() {
+        log::warn!("--preserve-xattrs has no effect on this platform");
+    }
+    for group in &mut groups {
+        group.ignore_case_paths = config.ignore_case_paths;
+    }
+
     // 0. Filter results if export_selected is true
     if config.export_selected {
         if let Some(ref session) = initial_session {
@@ -576,10 +1394,12 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
             let mut session = Session::new(scan_paths.clone(), settings.clone(), session_groups);
             if let Some(ref initial) = initial_session {
                 session.user_selections = initial.user_selections.clone();
+                session.keepers = initial.keepers.clone();
+                session.pinned_groups = initial.pinned_groups.clone();
                 session.group_index = initial.group_index;
                 session.file_index = initial.file_index;
             }
-            session.save(path)?;
+            session.save_with(path, session_compression(&config, path))?;
             log::info!("Session saved to {:?}", path);
         }
     }
@@ -601,6 +1421,22 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
         }
         eprintln!();
     }
+    if !summary.broken_symlinks.is_empty() {
+        eprintln!(
+            "\nFound {} broken symlink(s):",
+            summary.broken_symlinks.len()
+        );
+        for path in summary.broken_symlinks.iter().take(10) {
+            eprintln!("  {}", path.display());
+        }
+        if summary.broken_symlinks.len() > 10 {
+            eprintln!(
+                "  ... and {} more (see --output json)",
+                summary.broken_symlinks.len() - 10
+            );
+        }
+        eprintln!();
+    }
 
     // Determine exit code based on results
     let mut exit_code = if !summary.scan_errors.is_empty() {
@@ -610,36 +1446,151 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
     } else {
         ExitCode::Success
     };
+    let thresholds = ErrorThresholds {
+        max_errors: config.max_errors,
+        max_error_rate: config.max_error_rate,
+    };
+    if let Some(reason) = thresholds.exceeded(summary.scan_errors.len(), summary.total_files) {
+        eprintln!("Error: {}", reason);
+        exit_code = thresholds.escalate(exit_code, summary.scan_errors.len(), summary.total_files);
+    }
+
+    // Summary-only mode replaces the per-group output of every format
+    if config.summary_only {
+        write_summary_only(
+            output_format,
+            output_file.as_deref(),
+            &summary,
+            exit_code,
+            &config,
+        )?;
+        if notify {
+            notify_completion(&summary, config.size_units);
+        }
+        return Ok(exit_code);
+    }
 
     // 3. Output results based on format
     if output_format != OutputFormat::Tui && !quiet {
-        summary.print();
+        summary.print(config.size_units);
+        // Machine-readable reports are usually piped; keep their stderr terse
+        if !matches!(
+            output_format,
+            OutputFormat::Json | OutputFormat::Yaml | OutputFormat::Csv | OutputFormat::Compact
+        ) {
+            print_min_size_hint(&summary, &settings);
+        }
     }
 
+    // Only the TUI deletes files, so other formats just report keeper renames
+    if output_format != OutputFormat::Tui && !quiet {
+        if let Some(ref rule) = keeper_rename {
+            let deleted: std::collections::HashSet<&std::path::Path> = match initial_session {
+                Some(ref session) => session
+                    .user_selections
+                    .iter()
+                    .map(|p| p.as_path())
+                    .collect(),
+                None => groups
+                    .iter()
+                    .flat_map(|g| {
+                        g.files.iter().enumerate().filter_map(move |(j, f)| {
+                            crate::output::script::is_deleted_by_default(g, j)
+                                .then_some(f.path.as_path())
+                        })
+                    })
+                    .collect(),
+            };
+            report_keeper_renames(&groups, |p| deleted.contains(p), rule);
+        }
+    }
+
+    // Base for --json-paths-relative, resolved like the report paths
+    let relative_base = config
+        .json_paths_relative
+        .as_deref()
+        .map(crate::output::paths::resolve_base);
+
     match output_format {
         OutputFormat::Tui => {
+            let (only_owner, exclude_owner) = resolve_owner_filter(&config)?;
+            let permanent_under = resolve_permanent_under(&config)?;
             // Initialize TUI with results
             let mut app = crate::tui::App::with_groups(groups)
                 .with_reference_paths(reference_paths)
+                .with_ignore_case_paths(config.ignore_case_paths)
+                .with_protect_policy(config.protect)
+                .with_protect_hardlinks(
+                    settings.hardlinks == crate::duplicates::HardlinkPolicy::ReportButProtect,
+                )
+                .with_permanent_under(permanent_under)
+                .with_settle_seconds(config.settle)
+                .with_owner_filter(only_owner, exclude_owner)
+                .with_max_total_bytes(config.max_total_delete)
+                .with_trash_dir(config.trash_dir.clone())
+                .with_preserve_xattrs(config.preserve_xattrs)
+                .with_transactional_delete(config.transactional_delete)
                 .with_dry_run(dry_run)
                 .with_theme(theme)
-                .with_accessible(accessible);
+                .with_accessible(accessible)
+                .with_size_format(config.size_units)
+                .with_absolute_times(config.absolute_times)
+                .with_column_preset(config.tui_columns)
+                .with_confirm_thresholds(
+                    config.confirm_threshold_count,
+                    config.confirm_threshold_bytes,
+                )
+                .with_sort(settings.sort_column, settings.sort_direction);
+            // Dry-run never deletes, so there is nothing to clean up or rename
+            if config.delete_empty_dirs && !dry_run {
+                app = app.with_empty_dir_cleanup(scan_paths.clone());
+            }
+            if let Some(ref rule) = keeper_rename {
+                if !dry_run {
+                    app = app.with_keeper_rename(rule.clone());
+                }
+            }
             if let Some(session) = initial_session {
                 app.apply_session(
                     session.user_selections,
                     session.group_index,
                     session.file_index,
                 );
+                app.apply_keepers(session.keepers);
+                app.apply_pinned_groups(session.pinned_groups);
+            }
+            if notify {
+                notify_completion(&summary, config.size_units);
             }
             crate::tui::run_tui_with_bindings(
                 &mut app,
                 Some(shutdown_flag.clone()),
                 Some(keybindings),
             )?;
+            if app.column_preset() != config.tui_columns {
+                save_column_preset(app.column_preset());
+            }
 
             // Print summary after TUI exit if not quiet
             if !quiet {
-                summary.print();
+                summary.print(config.size_units);
+                print_min_size_hint(&summary, &settings);
+                if let Some(ref rule) = keeper_rename {
+                    if dry_run {
+                        report_keeper_renames(
+                            app.groups(),
+                            |p| app.selected_files().contains(p),
+                            rule,
+                        );
+                    }
+                }
+                if config.delete_empty_dirs && dry_run {
+                    report_empty_dirs(
+                        &app.selected_files_vec(),
+                        app.reference_paths(),
+                        &scan_paths,
+                    );
+                }
             }
 
             // Save session after TUI exit if requested
@@ -652,20 +1603,30 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
                     .map(|(id, g)| SessionGroup::from_duplicate_group(g, id))
                     .collect();
 
+                let settings = SessionSettings {
+                    sort_column: app.sort_column(),
+                    sort_direction: app.sort_direction(),
+                    ..settings
+                };
                 let mut session = Session::new(scan_paths, settings, session_groups);
                 session.user_selections = app.selected_files_btree();
+                session.keepers = app.keepers_btree();
+                session.pinned_groups = app.pinned_groups_btree();
                 session.group_index = group_index;
                 session.file_index = file_index;
-                session.save(path)?;
+                session.save_with(path, session_compression(&config, path))?;
                 log::info!("Session saved to {:?}", path);
             }
         }
         OutputFormat::Json => {
-            let json_output = crate::output::JsonOutput::new(&groups, &summary, exit_code, &config);
+            let json_output =
+                crate::output::JsonStreamOutput::new(&groups, &summary, exit_code, &config)
+                    .with_paths_relative_to(relative_base.as_deref());
+            let pretty = !config.json_compact;
             if let Some(path) = output_file {
                 let mut file = fs::File::create(&path)
                     .with_context(|| format!("Failed to create output file: {}", path.display()))?;
-                json_output.write_to(&mut file, true).with_context(|| {
+                json_output.write_to(&mut file, pretty).with_context(|| {
                     format!("Failed to write JSON output to: {}", path.display())
                 })?;
                 file.flush()
@@ -674,13 +1635,143 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
             } else {
                 let mut stdout = io::stdout().lock();
                 json_output
-                    .write_to(&mut stdout, true)
+                    .write_to(&mut stdout, pretty)
                     .context("Failed to write JSON output to stdout")?;
                 stdout.flush().context("Failed to flush stdout")?;
             }
         }
+        OutputFormat::Yaml => {
+            let yaml_output = crate::output::YamlOutput::new(&groups, &summary, exit_code, &config)
+                .with_paths_relative_to(relative_base.as_deref());
+            if let Some(path) = output_file {
+                let mut file = fs::File::create(&path)
+                    .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+                yaml_output.write_to(&mut file).with_context(|| {
+                    format!("Failed to write YAML output to: {}", path.display())
+                })?;
+                log::info!("YAML results saved to {:?}", path);
+            } else {
+                let mut stdout = io::stdout().lock();
+                yaml_output
+                    .write_to(&mut stdout)
+                    .context("Failed to write YAML output to stdout")?;
+            }
+        }
+        OutputFormat::Text => {
+            if let Some(path) = output_file {
+                let mut file = fs::File::create(&path)
+                    .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+                crate::output::TextOutput::new(&groups)
+                    .with_size_format(config.size_units)
+                    .write_to(&mut file)
+                    .with_context(|| {
+                        format!("Failed to write text output to: {}", path.display())
+                    })?;
+                log::info!("Text output saved to {:?}", path);
+            } else {
+                let color = io::stdout().is_terminal()
+                    && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty());
+                let mut stdout = io::stdout().lock();
+                crate::output::TextOutput::new(&groups)
+                    .with_size_format(config.size_units)
+                    .with_color(color)
+                    .write_to(&mut stdout)
+                    .context("Failed to write text output to stdout")?;
+            }
+        }
+        OutputFormat::Tree => {
+            let tree_output = crate::output::TreeOutput::new(&groups)
+                .with_ascii(accessible)
+                .with_size_format(config.size_units);
+            if let Some(path) = output_file {
+                let mut file = fs::File::create(&path)
+                    .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+                tree_output.write_to(&mut file).with_context(|| {
+                    format!("Failed to write tree output to: {}", path.display())
+                })?;
+                log::info!("Tree output saved to {:?}", path);
+            } else {
+                let mut stdout = io::stdout().lock();
+                tree_output
+                    .write_to(&mut stdout)
+                    .context("Failed to write tree output to stdout")?;
+            }
+        }
+        OutputFormat::Keepers => {
+            let mut keepers_output =
+                crate::output::KeepersOutput::new(&groups).with_size_format(config.size_units);
+            // Honor the selections saved in a loaded session
+            if let Some(ref session) = initial_session {
+                keepers_output = keepers_output.with_user_selections(&session.user_selections);
+            }
+            if let Some(path) = output_file {
+                let mut file = fs::File::create(&path)
+                    .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+                keepers_output.write_to(&mut file).with_context(|| {
+                    format!("Failed to write keepers report to: {}", path.display())
+                })?;
+                log::info!("Keepers report saved to {:?}", path);
+            } else {
+                let mut stdout = io::stdout().lock();
+                keepers_output
+                    .write_to(&mut stdout)
+                    .context("Failed to write keepers report to stdout")?;
+            }
+        }
+        OutputFormat::Compact => {
+            let compact_output = crate::output::CompactOutput::new(&groups)
+                .with_field_separator(config.compact_field_separator.as_str())
+                .with_path_separator(config.compact_path_separator.as_str())
+                .with_human_sizes(config.human_sizes)
+                .with_size_format(config.size_units);
+            if let Some(path) = output_file {
+                let mut file = fs::File::create(&path)
+                    .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+                compact_output.write_to(&mut file).with_context(|| {
+                    format!("Failed to write compact output to: {}", path.display())
+                })?;
+                log::info!("Compact output saved to {:?}", path);
+            } else {
+                let mut stdout = io::stdout().lock();
+                compact_output
+                    .write_to(&mut stdout)
+                    .context("Failed to write compact output to stdout")?;
+            }
+        }
+        OutputFormat::Plan => {
+            let plan_output = crate::output::PlanOutput::new(&groups)
+                .with_strategy(config.plan_keep.map(Into::into))
+                .with_size_format(config.size_units);
+            let mut writer: Box<dyn Write> = match output_file {
+                Some(ref path) => Box::new(fs::File::create(path).with_context(|| {
+                    format!("Failed to create output file: {}", path.display())
+                })?),
+                None => Box::new(io::stdout().lock()),
+            };
+            match config.plan_format {
+                StatsOutputFormat::Human => plan_output.write_to(&mut writer),
+                StatsOutputFormat::Json => {
+                    plan_output.write_json(&mut writer, !config.json_compact)
+                }
+            }
+            .context("Failed to write dedupe plan")?;
+            if let Some(path) = output_file {
+                log::info!("Dedupe plan saved to {:?}", path);
+            }
+        }
         OutputFormat::Csv => {
-            let csv_output = crate::output::CsvOutput::new(&groups);
+            let delimiter = u8::try_from(config.csv_delimiter)
+                .ok()
+                .filter(u8::is_ascii)
+                .with_context(|| {
+                    format!(
+                        "Invalid CSV delimiter '{}': must be a single ASCII character",
+                        config.csv_delimiter.escape_default()
+                    )
+                })?;
+            let csv_output = crate::output::CsvOutput::new(&groups)
+                .with_delimiter(delimiter)
+                .with_paths_relative_to(relative_base.as_deref());
             if let Some(path) = output_file {
                 let file = fs::File::create(&path)
                     .with_context(|| format!("Failed to create output file: {}", path.display()))?;
@@ -724,15 +1815,16 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
             let mut session = Session::new(scan_paths, settings, session_groups);
             if let Some(initial) = initial_session {
                 session.user_selections = initial.user_selections;
+                session.keepers = initial.keepers;
+                session.pinned_groups = initial.pinned_groups;
                 session.group_index = initial.group_index;
                 session.file_index = initial.file_index;
             }
-            let json = session.to_json().context("Failed to serialize session")?;
             if let Some(path) = output_file {
-                fs::write(&path, json)
-                    .with_context(|| format!("Failed to write session file: {}", path.display()))?;
+                session.save_with(&path, session_compression(&config, &path))?;
                 log::info!("Session saved to {:?}", path);
             } else {
+                let json = session.to_json().context("Failed to serialize session")?;
                 let mut stdout = io::stdout().lock();
                 stdout
                     .write_all(json.as_bytes())
@@ -741,6 +1833,9 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
             }
         }
         OutputFormat::Script => {
+            if run_script && dry_run {
+                anyhow::bail!("--delete-to-script-and-run cannot be used in dry-run mode");
+            }
             let script_type = match script_type {
                 Some(ScriptTypeArg::Posix) => crate::output::ScriptType::Posix,
                 Some(ScriptTypeArg::Powershell) => crate::output::ScriptType::PowerShell,
@@ -748,12 +1843,17 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
             };
 
             let mut script_output =
-                crate::output::ScriptOutput::new(&groups, &summary, script_type);
+                crate::output::ScriptOutput::new(&groups, &summary, script_type)
+                    .with_size_format(config.size_units);
 
             // If we have an initial session with user selections, use them
             if let Some(ref session) = initial_session {
                 script_output = script_output.with_user_selections(&session.user_selections);
             }
+            if config.delete_empty_dirs {
+                script_output = script_output.with_empty_dir_cleanup(&scan_paths);
+            }
+            script_output = script_output.with_max_total_bytes(config.max_total_delete);
 
             if let Some(path) = output_file {
                 let mut file = fs::File::create(&path)
@@ -764,6 +1864,12 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
                 file.flush()
                     .with_context(|| format!("Failed to flush output file: {}", path.display()))?;
                 log::info!("Deletion script saved to {:?}", path);
+                if run_script {
+                    let run_code = run_deletion_script(&path, script_type, assume_yes, quiet)?;
+                    if run_code != ExitCode::Success {
+                        exit_code = run_code;
+                    }
+                }
             } else {
                 let mut stdout = io::stdout().lock();
                 script_output
@@ -774,6 +1880,10 @@ fn handle_results(ctx: ResultContext) -> Result<ExitCode> {
         }
     }
 
+    if notify && output_format != OutputFormat::Tui {
+        notify_completion(&summary, config.size_units);
+    }
+
     // Re-check shutdown flag in case it was set during TUI or output
     if shutdown_flag.load(std::sync::atomic::Ordering::SeqCst) {
         exit_code = ExitCode::Interrupted;
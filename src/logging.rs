@@ -12,28 +12,49 @@
 //! - **Debug builds**: Include timestamp, level, and module path for detailed debugging
 //! - **Release builds**: Compact format with level and message only for cleaner output
 //!
+//! # Log Files
+//!
+//! With `--log-file PATH` records go to a file instead of stderr, so they
+//! survive while the TUI owns the terminal. File records always carry a
+//! timestamp, level and module path. Once the file exceeds
+//! [`LOG_FILE_MAX_BYTES`] it is renamed to `PATH.1` (shifting older files to
+//! `PATH.2`, ...) and a fresh file is started; [`LOG_FILE_BACKUPS`] old files
+//! are kept. Adding `--log-stderr` mirrors every record to stderr as well.
+//!
 //! # Example
 //!
 //! ```rust,no_run
 //! use rustdupe::logging::init_logging;
+//! use std::path::Path;
 //!
 //! // Initialize with default (info) level
-//! init_logging(0, false);
+//! init_logging(0, false, None, false).unwrap();
 //!
 //! // Initialize with verbose mode (-v)
-//! init_logging(1, false);
+//! init_logging(1, false, None, false).unwrap();
 //!
 //! // Initialize with trace mode (-vv)
-//! init_logging(2, false);
+//! init_logging(2, false, None, false).unwrap();
 //!
 //! // Initialize with quiet mode (errors only)
-//! init_logging(0, true);
+//! init_logging(0, true, None, false).unwrap();
+//!
+//! // Debug records to a rotating file, mirrored to stderr
+//! init_logging(1, false, Some(Path::new("rustdupe.log")), true).unwrap();
 //! ```
 
-use env_logger::Builder;
+use env_logger::{Builder, Target, WriteStyle};
 use log::LevelFilter;
 use std::env;
-use std::io::Write;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Size at which a log file is rotated (10 MiB).
+pub const LOG_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Number of rotated log files kept next to the current one.
+pub const LOG_FILE_BACKUPS: usize = 3;
 
 /// Initialize the logging subsystem based on CLI verbosity flags.
 ///
@@ -52,11 +73,14 @@ use std::io::Write;
 ///
 /// * `verbose` - Verbosity count from CLI (0=normal, 1=debug, 2+=trace)
 /// * `quiet` - If true, only show errors (overridden by RUST_LOG)
+/// * `log_file` - Write records to this rotating file instead of stderr
+/// * `mirror_stderr` - With `log_file`, also write every record to stderr
 ///
-/// # Panics
+/// Only the first call installs a logger; later calls have no effect.
 ///
-/// This function will panic if called more than once, as `env_logger`
-/// can only be initialized once per process.
+/// # Errors
+///
+/// Returns an error if the log file cannot be opened.
 ///
 /// # Example
 ///
@@ -66,41 +90,77 @@ use std::io::Write;
 /// // Normal usage with CLI flags
 /// let verbose = 0;
 /// let quiet = false;
-/// init_logging(verbose, quiet);
+/// init_logging(verbose, quiet, None, false).unwrap();
 ///
 /// log::info!("Application started");
 /// log::debug!("Debug info here");
 /// ```
-pub fn init_logging(verbose: u8, quiet: bool) {
-    // Check if RUST_LOG is set - if so, use env_logger's default behavior
+pub fn init_logging(
+    verbose: u8,
+    quiet: bool,
+    log_file: Option<&Path>,
+    mirror_stderr: bool,
+) -> io::Result<()> {
+    let file = log_file
+        .map(|path| RotatingFile::open(path, LOG_FILE_MAX_BYTES, LOG_FILE_BACKUPS))
+        .transpose()?;
     let use_env = env::var("RUST_LOG").is_ok();
 
-    let mut builder = Builder::new();
+    // Initialize the logger
+    let _ = build_logger(verbose, quiet, file, mirror_stderr).try_init();
 
+    // Log initialization message
     if use_env {
-        // Use RUST_LOG environment variable
-        builder.parse_default_env();
         log::debug!(
             "Logging initialized from RUST_LOG environment variable: {:?}",
             env::var("RUST_LOG").ok()
         );
     } else {
-        // Determine level from CLI flags
         let level = determine_level(verbose, quiet);
-        builder.filter_level(level);
+        log::debug!("Logging initialized at level: {:?}", level);
     }
+    if let Some(path) = log_file {
+        log::debug!("Logging to file: {}", path.display());
+    }
+    Ok(())
+}
 
-    // Configure format based on build type
-    configure_format(&mut builder, verbose);
+/// Build a logger for the given flags, writing to `file` when set.
+fn build_logger(
+    verbose: u8,
+    quiet: bool,
+    file: Option<RotatingFile>,
+    mirror_stderr: bool,
+) -> Builder {
+    // Check if RUST_LOG is set - if so, use env_logger's default behavior
+    let use_env = env::var("RUST_LOG").is_ok();
 
-    // Initialize the logger
-    let _ = builder.try_init();
+    let mut builder = Builder::new();
 
-    // Log initialization message (only if not using RUST_LOG, as we already logged above)
-    if !use_env {
+    if use_env {
+        // Use RUST_LOG environment variable
+        builder.parse_default_env();
+    } else {
+        // Determine level from CLI flags
         let level = determine_level(verbose, quiet);
-        log::debug!("Logging initialized at level: {:?}", level);
+        builder.filter_level(level);
     }
+
+    match file {
+        Some(file) => {
+            // Files get the detailed format and no escape codes
+            builder
+                .target(Target::Pipe(Box::new(LogWriter {
+                    file,
+                    mirror_stderr,
+                })))
+                .write_style(WriteStyle::Never);
+            configure_format(&mut builder, verbose.max(1), true);
+        }
+        // Configure format based on build type
+        None => configure_format(&mut builder, verbose, false),
+    }
+    builder
 }
 
 /// Determine the log level from CLI flags.
@@ -129,10 +189,11 @@ fn determine_level(verbose: u8, quiet: bool) -> LevelFilter {
 ///
 /// - Debug builds: timestamp, level, module path (for detailed debugging)
 /// - Release builds: compact format (level + message only)
-fn configure_format(builder: &mut Builder, verbose: u8) {
-    // In debug builds, include more information
-    #[cfg(debug_assertions)]
-    {
+///
+/// `detailed` selects the debug-build format regardless of build type.
+fn configure_format(builder: &mut Builder, verbose: u8, detailed: bool) {
+    // In debug builds and log files, include more information
+    if detailed || cfg!(debug_assertions) {
         builder.format(move |buf, record| {
             let timestamp = buf.timestamp_seconds();
             let level = record.level();
@@ -169,12 +230,8 @@ fn configure_format(builder: &mut Builder, verbose: u8) {
                 )
             }
         });
-    }
-
-    // In release builds, use compact format
-    #[cfg(not(debug_assertions))]
-    {
-        let _ = verbose; // Suppress unused variable warning in release
+    } else {
+        // In release builds, use compact format
         builder.format(|buf, record| {
             let level = record.level();
             let level_style = buf.default_level_style(level);
@@ -188,6 +245,115 @@ fn configure_format(builder: &mut Builder, verbose: u8) {
     }
 }
 
+/// Log file that rotates once it grows past a size limit.
+///
+/// The current file is `path`; rotated files are `path.1` (newest) through
+/// `path.N` (oldest). Rotation happens before a write that would push the
+/// file past the limit, so records are never split across files.
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+    backups: usize,
+}
+
+impl RotatingFile {
+    /// Open `path` for appending, rotating past `max_bytes` and keeping
+    /// `backups` old files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    pub fn open(path: &Path, max_bytes: u64, backups: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            written,
+            max_bytes,
+            backups,
+        })
+    }
+
+    /// Path of the current log file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of the `index`-th rotated file (`path.1`, `path.2`, ...).
+    #[must_use]
+    pub fn backup_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    /// Shift the rotated files up by one and start a fresh current file.
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.backups == 0 {
+            self.file = File::create(&self.path)?;
+        } else {
+            for index in (1..self.backups).rev() {
+                let from = self.backup_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.backup_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.backup_path(1))?;
+            self.file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+        }
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Logger target writing to a log file and optionally stderr.
+struct LogWriter {
+    file: RotatingFile,
+    mirror_stderr: bool,
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Whole records only, so a rotation never splits one
+        self.file.write_all(buf)?;
+        if self.mirror_stderr {
+            // A closed stderr must not stop file logging
+            let _ = io::stderr().write_all(buf);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.mirror_stderr {
+            let _ = io::stderr().flush();
+        }
+        self.file.flush()
+    }
+}
+
 /// Get the current log level as a string.
 ///
 /// Useful for displaying the current logging configuration to users.
@@ -245,6 +411,51 @@ mod tests {
         assert_eq!(determine_level(2, true), LevelFilter::Error);
     }
 
+    #[test]
+    fn test_file_logging_rotates() {
+        use log::{Level, Log, Record};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("rustdupe.log");
+        let file = RotatingFile::open(&path, 300, 2).unwrap();
+        let logger = build_logger(0, false, Some(file), false).build();
+
+        logger.log(
+            &Record::builder()
+                .args(format_args!("first record"))
+                .level(Level::Info)
+                .module_path(Some("rustdupe::scanner"))
+                .build(),
+        );
+        logger.flush();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("INFO"));
+        assert!(contents.contains("[rustdupe::scanner] first record"));
+        assert!(!dir.path().join("rustdupe.log.1").exists());
+
+        for i in 0..30 {
+            logger.log(
+                &Record::builder()
+                    .args(format_args!("record number {i}"))
+                    .level(Level::Info)
+                    .build(),
+            );
+        }
+        logger.flush();
+
+        // Rotated past the cap, keeping only two old files
+        assert!(fs::metadata(&path).unwrap().len() <= 300);
+        assert!(dir.path().join("rustdupe.log.1").exists());
+        assert!(dir.path().join("rustdupe.log.2").exists());
+        assert!(!dir.path().join("rustdupe.log.3").exists());
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("record number 29"));
+        assert!(fs::read_to_string(dir.path().join("rustdupe.log.1"))
+            .unwrap()
+            .contains("record number"));
+    }
+
     #[test]
     fn test_current_level_name_values() {
         // This test verifies the function doesn't panic
@@ -2,7 +2,7 @@
 //!
 //! Entry point for the RustDupe CLI application.
 
-use clap::Parser;
+use clap::{error::ErrorKind, CommandFactory, Parser};
 use rustdupe::{
     cli::Cli,
     error::{ExitCode, StructuredError},
@@ -11,6 +11,11 @@ use rustdupe::{
 fn main() {
     // Parse command-line arguments
     let cli = Cli::parse();
+    if cli.command.is_none() && !cli.is_info_request() {
+        Cli::command()
+            .error(ErrorKind::MissingSubcommand, "a subcommand is required")
+            .exit();
+    }
     let json_errors = cli.json_errors;
 
     // Run the application logic
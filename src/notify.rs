@@ -0,0 +1,194 @@
+//! Scan completion notifications.
+//!
+//! Long scans often finish while the user is looking at another window.
+//! With `--notify`, RustDupe rings the terminal bell when results are ready
+//! and, when a desktop environment is detected, also shows a desktop
+//! notification summarizing the duplicates found.
+//!
+//! The bell is written to stderr so it never corrupts machine-readable
+//! output on stdout. Desktop notifications are best-effort: if none can be
+//! shown (headless machine, no notification daemon), only the bell rings.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::duplicates::ScanSummary;
+//! use rustdupe::notify::notify_scan_complete;
+//! use rustdupe::output::SizeFormat;
+//!
+//! let summary = ScanSummary::default();
+//! if let Err(e) = notify_scan_complete(&summary, SizeFormat::default()) {
+//!     eprintln!("Notification failed: {}", e);
+//! }
+//! ```
+
+use std::io::Write;
+
+use thiserror::Error;
+
+use crate::duplicates::ScanSummary;
+use crate::output::size::SizeFormat;
+
+/// Title used for desktop notifications.
+pub const NOTIFICATION_TITLE: &str = "RustDupe scan complete";
+
+/// Errors that can occur while notifying about scan completion.
+#[derive(Debug, Error)]
+pub enum NotifyError {
+    /// The terminal bell could not be written
+    #[error("failed to ring terminal bell: {0}")]
+    Bell(#[from] std::io::Error),
+
+    /// No desktop environment was detected
+    #[error("no desktop environment detected")]
+    NoDesktop,
+
+    /// The desktop notification could not be shown
+    #[error("failed to show desktop notification: {0}")]
+    Desktop(String),
+}
+
+/// Build the one-line notification body for a finished scan.
+///
+/// # Example
+///
+/// ```
+/// use rustdupe::duplicates::ScanSummary;
+/// use rustdupe::notify::completion_message;
+/// use rustdupe::output::SizeFormat;
+///
+/// let summary = ScanSummary::default();
+/// assert_eq!(
+///     completion_message(&summary, SizeFormat::default()),
+///     "No duplicates found in 0 files"
+/// );
+/// ```
+#[must_use]
+pub fn completion_message(summary: &ScanSummary, size_format: SizeFormat) -> String {
+    let prefix = if summary.interrupted {
+        "Scan interrupted: "
+    } else {
+        ""
+    };
+    if summary.duplicate_groups == 0 {
+        return format!(
+            "{}No duplicates found in {} files",
+            prefix, summary.total_files
+        );
+    }
+    format!(
+        "{}{} duplicate group{} ({} files), {} reclaimable",
+        prefix,
+        summary.duplicate_groups,
+        if summary.duplicate_groups == 1 {
+            ""
+        } else {
+            "s"
+        },
+        summary.duplicate_files,
+        size_format.format(summary.reclaimable_space)
+    )
+}
+
+/// Ring the terminal bell on stderr.
+///
+/// # Errors
+///
+/// Returns [`NotifyError::Bell`] if stderr cannot be written.
+pub fn ring_bell() -> Result<(), NotifyError> {
+    let mut stderr = std::io::stderr().lock();
+    stderr.write_all(b"\x07")?;
+    stderr.flush()?;
+    Ok(())
+}
+
+/// Whether a desktop environment that can show notifications is present.
+///
+/// Windows and macOS always have one; elsewhere an X11 or Wayland display
+/// must be set.
+#[must_use]
+pub fn desktop_available() -> bool {
+    if cfg!(any(target_os = "windows", target_os = "macos")) {
+        return true;
+    }
+    ["DISPLAY", "WAYLAND_DISPLAY"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|v| !v.is_empty()))
+}
+
+/// Show a desktop notification with the given body.
+///
+/// # Errors
+///
+/// Returns [`NotifyError::NoDesktop`] when no desktop environment is
+/// detected, or [`NotifyError::Desktop`] if the notification fails.
+pub fn send_desktop_notification(body: &str) -> Result<(), NotifyError> {
+    if !desktop_available() {
+        return Err(NotifyError::NoDesktop);
+    }
+    notify_rust::Notification::new()
+        .appname("rustdupe")
+        .summary(NOTIFICATION_TITLE)
+        .body(body)
+        .show()
+        .map(|_| ())
+        .map_err(|e| NotifyError::Desktop(e.to_string()))
+}
+
+/// Notify the user that a scan has finished.
+///
+/// Always rings the bell; a desktop notification is attempted as well and
+/// any failure there is logged rather than returned.
+///
+/// # Errors
+///
+/// Returns an error only if the bell cannot be rung.
+pub fn notify_scan_complete(
+    summary: &ScanSummary,
+    size_format: SizeFormat,
+) -> Result<(), NotifyError> {
+    ring_bell()?;
+    let body = completion_message(summary, size_format);
+    if let Err(e) = send_desktop_notification(&body) {
+        log::debug!("Desktop notification unavailable: {}", e);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completion_message() {
+        let mut summary = ScanSummary {
+            total_files: 120,
+            ..Default::default()
+        };
+        assert_eq!(
+            completion_message(&summary, SizeFormat::default()),
+            "No duplicates found in 120 files"
+        );
+
+        summary.duplicate_groups = 1;
+        summary.duplicate_files = 2;
+        summary.reclaimable_space = 2048;
+        assert_eq!(
+            completion_message(&summary, SizeFormat::default()),
+            "1 duplicate group (2 files), 2.0 KiB reclaimable"
+        );
+
+        summary.duplicate_groups = 3;
+        summary.duplicate_files = 5;
+        summary.reclaimable_space = 5 * 1024 * 1024;
+        summary.interrupted = true;
+        assert_eq!(
+            completion_message(&summary, SizeFormat::default()),
+            "Scan interrupted: 3 duplicate groups (5 files), 5.0 MiB reclaimable"
+        );
+        assert_eq!(
+            completion_message(&summary, SizeFormat::Si),
+            "Scan interrupted: 3 duplicate groups (5 files), 5.2 MB reclaimable"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Compact one-line-per-group output for shell pipelines.
+//!
+//! Each duplicate group becomes a single line of four fields:
+//!
+//! ```text
+//! size<TAB>count<TAB>hash<TAB>path1|path2|...
+//! ```
+//!
+//! There is no header and no color, so the output can go straight into
+//! `grep`, `cut` or `awk`. Sizes are raw byte counts unless human-readable
+//! sizes are requested. Lines are ordered by size (largest first), then by
+//! hash, and the paths of each group are sorted, so repeated scans of the
+//! same files produce identical output.
+//!
+//! Both separators can be changed; pick ones that cannot appear in your
+//! paths.
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::duplicates::DuplicateGroup;
+//! use rustdupe::output::CompactOutput;
+//! use rustdupe::scanner::FileEntry;
+//! use std::path::PathBuf;
+//! use std::time::SystemTime;
+//!
+//! let files = ["/b.txt", "/a.txt"]
+//!     .iter()
+//!     .map(|p| FileEntry::new(PathBuf::from(p), 5, SystemTime::UNIX_EPOCH))
+//!     .collect();
+//! let groups = vec![DuplicateGroup::new([0; 32], 5, files, Vec::new())];
+//!
+//! let line = CompactOutput::new(&groups).render();
+//! assert!(line.starts_with("5\t2\t0000"));
+//! assert!(line.ends_with("\t/a.txt|/b.txt\n"));
+//! ```
+
+use std::io::Write;
+
+use crate::duplicates::DuplicateGroup;
+use crate::output::size::SizeFormat;
+
+/// Default separator between the fields of a line.
+pub const DEFAULT_FIELD_SEPARATOR: &str = "\t";
+
+/// Default separator between the paths of a group.
+pub const DEFAULT_PATH_SEPARATOR: &str = "|";
+
+/// Formatter for compact one-line-per-group output.
+pub struct CompactOutput<'a> {
+    groups: &'a [DuplicateGroup],
+    field_separator: String,
+    path_separator: String,
+    human_sizes: bool,
+    size_format: SizeFormat,
+}
+
+impl<'a> CompactOutput<'a> {
+    /// Create compact output with the default separators and raw byte sizes.
+    #[must_use]
+    pub fn new(groups: &'a [DuplicateGroup]) -> Self {
+        Self {
+            groups,
+            field_separator: DEFAULT_FIELD_SEPARATOR.to_string(),
+            path_separator: DEFAULT_PATH_SEPARATOR.to_string(),
+            human_sizes: false,
+            size_format: SizeFormat::default(),
+        }
+    }
+
+    /// Separate the fields of each line with `separator`.
+    #[must_use]
+    pub fn with_field_separator(mut self, separator: impl Into<String>) -> Self {
+        self.field_separator = separator.into();
+        self
+    }
+
+    /// Separate the paths of each group with `separator`.
+    #[must_use]
+    pub fn with_path_separator(mut self, separator: impl Into<String>) -> Self {
+        self.path_separator = separator.into();
+        self
+    }
+
+    /// Print sizes in human-readable units instead of bytes.
+    #[must_use]
+    pub fn with_human_sizes(mut self, enabled: bool) -> Self {
+        self.human_sizes = enabled;
+        self
+    }
+
+    /// Set the units for human-readable sizes (`--size-units`).
+    #[must_use]
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    /// Format one group as a line, without the trailing newline.
+    #[must_use]
+    pub fn format_group(&self, group: &DuplicateGroup) -> String {
+        let size = if self.human_sizes {
+            self.size_format.format(group.size)
+        } else {
+            group.size.to_string()
+        };
+        let mut paths: Vec<String> = group
+            .files
+            .iter()
+            .map(|f| f.path.display().to_string())
+            .collect();
+        paths.sort();
+        [
+            size,
+            group.files.len().to_string(),
+            group.hash_hex(),
+            paths.join(&self.path_separator),
+        ]
+        .join(&self.field_separator)
+    }
+
+    /// Render every group, one line each.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut ordered: Vec<&DuplicateGroup> = self.groups.iter().collect();
+        ordered.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.hash.cmp(&b.hash)));
+        ordered
+            .into_iter()
+            .map(|group| self.format_group(group) + "\n")
+            .collect()
+    }
+
+    /// Write the output to the given writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the writer fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(self.render().as_bytes())?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn group(hash: u8, size: u64, paths: &[&str]) -> DuplicateGroup {
+        let files = paths
+            .iter()
+            .map(|p| FileEntry::new(PathBuf::from(p), size, SystemTime::UNIX_EPOCH))
+            .collect();
+        DuplicateGroup::new([hash; 32], size, files, Vec::new())
+    }
+
+    fn test_groups() -> Vec<DuplicateGroup> {
+        vec![
+            group(0x02, 512, &["/docs/b.txt", "/docs/a.txt"]),
+            group(
+                0xab,
+                2048,
+                &["/data/one.bin", "/backup/one.bin", "/old/one.bin"],
+            ),
+            group(0x01, 512, &["/x/c.txt", "/y/c.txt"]),
+        ]
+    }
+
+    #[test]
+    fn test_compact_exact_lines() {
+        let output = CompactOutput::new(&test_groups()).render();
+        let expected = format!(
+            "2048\t3\t{}\t/backup/one.bin|/data/one.bin|/old/one.bin\n\
+             512\t2\t{}\t/x/c.txt|/y/c.txt\n\
+             512\t2\t{}\t/docs/a.txt|/docs/b.txt\n",
+            "ab".repeat(32),
+            "01".repeat(32),
+            "02".repeat(32)
+        );
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_compact_custom_separators() {
+        let groups = vec![group(0x01, 512, &["/x/c.txt", "/y/c.txt"])];
+        let output = CompactOutput::new(&groups)
+            .with_field_separator(",")
+            .with_path_separator("\0")
+            .render();
+        assert_eq!(
+            output,
+            format!("512,2,{},/x/c.txt\0/y/c.txt\n", "01".repeat(32))
+        );
+    }
+
+    #[test]
+    fn test_compact_human_sizes() {
+        let groups = vec![group(0x01, 2048, &["/a", "/b"])];
+        let line = CompactOutput::new(&groups)
+            .with_human_sizes(true)
+            .format_group(&groups[0]);
+        let size = line.split('\t').next().unwrap();
+        assert_eq!(size, "2.0 KiB");
+
+        let line = CompactOutput::new(&groups)
+            .with_human_sizes(true)
+            .with_size_format(SizeFormat::Si)
+            .format_group(&groups[0]);
+        assert_eq!(line.split('\t').next().unwrap(), "2.0 kB");
+        assert!(CompactOutput::new(&[]).render().is_empty());
+    }
+}
@@ -5,11 +5,16 @@
 //!
 //! # Columns
 //!
-//! - `group_id`: Numeric ID identifying the duplicate group
+//! - `group_id`: Stable [group ID](crate::duplicates::group_ids) (hash prefix, with a suffix for groups sharing a hash)
 //! - `hash`: BLAKE3 content hash (hexadecimal)
 //! - `path`: Absolute path to the file
 //! - `size`: File size in bytes
 //! - `modified`: Last modified time (RFC 3339 format)
+//! - `role`: `reference` for files in a reference directory, otherwise
+//!   `original` for the first file in the group and `duplicate` for the rest
+//!
+//! The delimiter defaults to a comma; use [`CsvOutput::with_delimiter`] for
+//! tab-separated output.
 //!
 //! # Example
 //!
@@ -26,12 +31,14 @@
 //! ```
 
 use std::io;
+use std::path::Path;
 
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 use thiserror::Error;
 
-use crate::duplicates::DuplicateGroup;
+use super::paths::report_path;
+use crate::duplicates::{group_ids, DuplicateGroup};
 
 /// Errors that can occur during CSV output generation.
 #[derive(Debug, Error)]
@@ -48,28 +55,62 @@ pub enum CsvOutputError {
 /// A single row in the CSV output.
 #[derive(Debug, Serialize)]
 struct CsvRow {
-    /// Unique identifier for the duplicate group
-    group_id: usize,
+    /// Stable identifier for the duplicate group
+    group_id: String,
     /// BLAKE3 hash of the file content (hex)
     hash: String,
-    /// Absolute path to the file
+    /// Path to the file, absolute unless `--json-paths-relative` is set
     path: String,
     /// File size in bytes
     size: u64,
     /// Last modified time (RFC 3339)
     modified: String,
+    /// Role of the file within its group
+    role: FileRole,
+}
+
+/// Role of a file within its duplicate group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FileRole {
+    /// The first file in the group, treated as the keeper
+    Original,
+    /// Any other copy
+    Duplicate,
+    /// A file in a protected reference directory
+    Reference,
 }
 
 /// CSV output formatter.
 pub struct CsvOutput<'a> {
     groups: &'a [DuplicateGroup],
+    delimiter: u8,
+    relative_base: Option<&'a Path>,
 }
 
 impl<'a> CsvOutput<'a> {
     /// Create a new CSV output formatter.
     #[must_use]
     pub fn new(groups: &'a [DuplicateGroup]) -> Self {
-        Self { groups }
+        Self {
+            groups,
+            delimiter: b',',
+            relative_base: None,
+        }
+    }
+
+    /// Set the field delimiter (e.g. `b'\t'` for tab-separated output).
+    #[must_use]
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Write file paths inside `base` relative to it (`--json-paths-relative`).
+    #[must_use]
+    pub fn with_paths_relative_to(mut self, base: Option<&'a Path>) -> Self {
+        self.relative_base = base;
+        self
     }
 
     /// Write the CSV output to the given writer.
@@ -82,22 +123,32 @@ impl<'a> CsvOutput<'a> {
     ///
     /// Returns `CsvOutputError` if writing or serialization fails.
     pub fn write_to<W: io::Write>(&self, writer: W) -> Result<(), CsvOutputError> {
-        let mut csv_writer = csv::Writer::from_writer(writer);
+        let mut csv_writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(writer);
 
-        for (idx, group) in self.groups.iter().enumerate() {
-            let group_id = idx + 1;
+        for (group, group_id) in self.groups.iter().zip(group_ids(self.groups)) {
             let hash_hex = group.hash_hex();
 
-            for file in &group.files {
+            for (file_idx, file) in group.files.iter().enumerate() {
+                let role = if group.is_in_reference_dir(&file.path) {
+                    FileRole::Reference
+                } else if file_idx == 0 {
+                    FileRole::Original
+                } else {
+                    FileRole::Duplicate
+                };
+
                 let datetime: DateTime<Utc> = file.modified.into();
                 let modified = datetime.to_rfc3339();
 
                 let row = CsvRow {
-                    group_id,
+                    group_id: group_id.clone(),
                     hash: hash_hex.clone(),
-                    path: file.path.to_string_lossy().to_string(),
+                    path: report_path(&file.path, self.relative_base),
                     size: group.size,
                     modified,
+                    role,
                 };
 
                 csv_writer.serialize(row)?;
@@ -132,6 +183,7 @@ mod tests {
     use super::*;
     use std::fs::File;
     use std::io::Write;
+    use std::path::PathBuf;
     use tempfile::TempDir;
 
     #[test]
@@ -157,16 +209,35 @@ mod tests {
         let csv_str = output.to_string().unwrap();
 
         // Check header
-        assert!(csv_str.contains("group_id,hash,path,size,modified"));
+        assert!(csv_str.contains("group_id,hash,path,size,modified,role"));
         // Check rows (very basic check)
-        assert!(
-            csv_str.contains("1,0000000000000000000000000000000000000000000000000000000000000000")
-        );
+        assert!(csv_str.contains(&format!("{},{}", "0".repeat(12), "0".repeat(64))));
         assert!(csv_str.contains("file1.txt"));
         assert!(csv_str.contains("file2.txt"));
         assert!(csv_str.contains(",7,"));
     }
 
+    #[test]
+    fn test_csv_output_paths_relative() {
+        let now = std::time::SystemTime::now();
+        let groups = vec![DuplicateGroup::new(
+            [0u8; 32],
+            7,
+            vec![
+                crate::scanner::FileEntry::new(PathBuf::from("/data/photos/a.jpg"), 7, now),
+                crate::scanner::FileEntry::new(PathBuf::from("/backup/a.jpg"), 7, now),
+            ],
+            Vec::new(),
+        )];
+
+        let csv_str = CsvOutput::new(&groups)
+            .with_paths_relative_to(Some(Path::new("/data")))
+            .to_string()
+            .unwrap();
+        assert!(csv_str.contains(",photos/a.jpg,"));
+        assert!(csv_str.contains(",/backup/a.jpg,"));
+    }
+
     #[test]
     fn test_csv_output_quoting() {
         let dir = TempDir::new().unwrap();
@@ -191,4 +262,89 @@ mod tests {
         assert!(csv_str.contains("\""));
         assert!(csv_str.contains("file,with,comma.txt"));
     }
+
+    #[test]
+    fn test_csv_output_roles_and_column_order() {
+        let now = std::time::SystemTime::now();
+        let groups = vec![
+            DuplicateGroup::new(
+                [1u8; 32],
+                10,
+                vec![
+                    crate::scanner::FileEntry::new(PathBuf::from("/data/a.txt"), 10, now),
+                    crate::scanner::FileEntry::new(PathBuf::from("/data/b.txt"), 10, now),
+                    crate::scanner::FileEntry::new(PathBuf::from("/data/c.txt"), 10, now),
+                ],
+                Vec::new(),
+            ),
+            DuplicateGroup::new(
+                [2u8; 32],
+                20,
+                vec![
+                    crate::scanner::FileEntry::new(PathBuf::from("/ref/x.txt"), 20, now),
+                    crate::scanner::FileEntry::new(PathBuf::from("/data/y.txt"), 20, now),
+                    crate::scanner::FileEntry::new(PathBuf::from("/data/z.txt"), 20, now),
+                ],
+                vec![PathBuf::from("/ref")],
+            ),
+        ];
+
+        let csv_str = CsvOutput::new(&groups).to_string().unwrap();
+        let mut reader = csv::Reader::from_reader(csv_str.as_bytes());
+
+        let headers = reader.headers().unwrap().clone();
+        assert_eq!(
+            headers.iter().collect::<Vec<_>>(),
+            vec!["group_id", "hash", "path", "size", "modified", "role"]
+        );
+
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 6);
+
+        let summary: Vec<(&str, &str, &str)> = rows.iter().map(|r| (&r[0], &r[2], &r[5])).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("010101010101", "/data/a.txt", "original"),
+                ("010101010101", "/data/b.txt", "duplicate"),
+                ("010101010101", "/data/c.txt", "duplicate"),
+                ("020202020202", "/ref/x.txt", "reference"),
+                ("020202020202", "/data/y.txt", "duplicate"),
+                ("020202020202", "/data/z.txt", "duplicate"),
+            ]
+        );
+        assert_eq!(&rows[0][1], "01".repeat(32));
+        assert_eq!(&rows[3][3], "20");
+    }
+
+    #[test]
+    fn test_csv_output_tab_delimiter() {
+        let now = std::time::SystemTime::now();
+        let groups = vec![DuplicateGroup::new(
+            [0u8; 32],
+            7,
+            vec![
+                crate::scanner::FileEntry::new(PathBuf::from("/a,b.txt"), 7, now),
+                crate::scanner::FileEntry::new(PathBuf::from("/c.txt"), 7, now),
+            ],
+            Vec::new(),
+        )];
+
+        let tsv = CsvOutput::new(&groups)
+            .with_delimiter(b'\t')
+            .to_string()
+            .unwrap();
+        assert!(tsv.starts_with("group_id\thash\tpath\tsize\tmodified\trole\n"));
+        // Commas no longer need quoting
+        assert!(tsv.contains("\t/a,b.txt\t"));
+
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .from_reader(tsv.as_bytes());
+        let roles: Vec<String> = reader
+            .records()
+            .map(|r| r.unwrap()[5].to_string())
+            .collect();
+        assert_eq!(roles, vec!["original", "duplicate"]);
+    }
 }
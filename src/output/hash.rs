@@ -0,0 +1,242 @@
+//! Single-file hash report (`rustdupe hash`).
+//!
+//! Computes the prehash and full hash of one file with the same hasher
+//! settings a scan would use, alongside the size and modification time the
+//! hash cache keys on. Comparing this output with a cached entry shows why
+//! the entry was (or was not) considered stale.
+//!
+//! # Example Output
+//!
+//! ```text
+//! Path:       /data/photo.jpg
+//! Size:       5120 bytes (5.0 KiB)
+//! Modified:   2026-01-01T12:00:00Z (1767268800000000000 ns since epoch)
+//! Cache key:  /data/photo.jpg:5120:1767268800000000000
+//! Prehash:    015094013f57a5277b59d8475c0501042c0b642e531b0a1c8f58d2163229e969 (first 4096 bytes)
+//! Full hash:  9cadc15fed8b5d854562b26a9536d9707cadeda9b143978f319ab34230535833
+//! ```
+//!
+//! When `--sample-above` is configured and the file exceeds it, a `Sampled:`
+//! line shows the sampled hash that Phase 3 would compare instead.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+
+use super::size::SizeFormat;
+use crate::cache::CacheEntry;
+use crate::scanner::{hash_to_hex, Hash, HashError, Hasher};
+
+/// Hashes and cache-relevant metadata for a single file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashReport {
+    /// Path of the hashed file
+    pub path: PathBuf,
+    /// File size in bytes
+    pub size: u64,
+    /// Last modification time
+    pub modified: SystemTime,
+    /// Number of leading bytes covered by the prehash
+    pub prehash_size: usize,
+    /// Hash of the first `prehash_size` bytes
+    pub prehash: Hash,
+    /// Hash of the entire file (`None` with `--prehash-only`)
+    pub full_hash: Option<Hash>,
+    /// Sampled hash, when the file is large enough to be sampled in a scan
+    pub sampled_hash: Option<Hash>,
+}
+
+impl HashReport {
+    /// Hash `path` with `hasher`.
+    ///
+    /// # Arguments
+    ///
+    /// * `hasher` - Hasher configured like the one used for scans
+    /// * `path` - File to hash
+    /// * `prehash_only` - Skip the full (and sampled) hash
+    /// * `sample_above` - Sampling threshold from `--sample-above`, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn compute(
+        hasher: &Hasher,
+        path: &Path,
+        prehash_only: bool,
+        sample_above: Option<u64>,
+    ) -> Result<Self, HashError> {
+        let metadata = std::fs::metadata(path).map_err(|e| metadata_error(path, e))?;
+        let size = metadata.len();
+        let modified = metadata.modified().map_err(|e| metadata_error(path, e))?;
+
+        let prehash = hasher.prehash(path)?;
+        let (full_hash, sampled_hash) = if prehash_only {
+            (None, None)
+        } else {
+            let sampled = match sample_above {
+                Some(threshold) if size > threshold => Some(hasher.sample_hash(path, size)?),
+                _ => None,
+            };
+            (Some(hasher.full_hash(path)?), sampled)
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            size,
+            modified,
+            prehash_size: hasher.prehash_size(),
+            prehash,
+            full_hash,
+            sampled_hash,
+        })
+    }
+
+    /// The key the hash cache stores this file under.
+    ///
+    /// Cache entries built from a scan carry no inode, so neither does the key.
+    #[must_use]
+    pub fn cache_key(&self) -> String {
+        CacheEntry::generate_key(&self.path, self.size, self.modified, None)
+    }
+
+    /// Render the report to a string, with the size in `size_format` units.
+    #[must_use]
+    pub fn render(&self, size_format: SizeFormat) -> String {
+        let nanos = self
+            .modified
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let modified: DateTime<Utc> = self.modified.into();
+
+        let mut out = format!("Path:       {}\n", self.path.display());
+        out.push_str(&format!(
+            "Size:       {} bytes ({})\n",
+            self.size,
+            size_format.format(self.size)
+        ));
+        out.push_str(&format!(
+            "Modified:   {} ({} ns since epoch)\n",
+            modified.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            nanos
+        ));
+        out.push_str(&format!("Cache key:  {}\n", self.cache_key()));
+        out.push_str(&format!(
+            "Prehash:    {} (first {} bytes)\n",
+            hash_to_hex(&self.prehash),
+            self.prehash_size
+        ));
+        if let Some(ref hash) = self.full_hash {
+            out.push_str(&format!("Full hash:  {}\n", hash_to_hex(hash)));
+        }
+        if let Some(ref hash) = self.sampled_hash {
+            out.push_str(&format!("Sampled:    {}\n", hash_to_hex(hash)));
+        }
+        out
+    }
+
+    /// Write the report to the given writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the writer fails.
+    pub fn write_to<W: Write>(
+        &self,
+        writer: &mut W,
+        size_format: SizeFormat,
+    ) -> std::io::Result<()> {
+        writer.write_all(self.render(size_format).as_bytes())?;
+        writer.flush()
+    }
+}
+
+/// Map an I/O error from reading metadata to a [`HashError`].
+fn metadata_error(path: &Path, error: std::io::Error) -> HashError {
+    match error.kind() {
+        std::io::ErrorKind::NotFound => HashError::NotFound(path.to_path_buf()),
+        std::io::ErrorKind::PermissionDenied => HashError::PermissionDenied(path.to_path_buf()),
+        _ => HashError::Io {
+            path: path.to_path_buf(),
+            source: Arc::new(error),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// BLAKE3 reference test vector input: byte `i` is `i % 251`.
+    fn fixture(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    /// BLAKE3 of the first 4096 fixture bytes (reference test vector).
+    const FIXTURE_4096_HEX: &str =
+        "015094013f57a5277b59d8475c0501042c0b642e531b0a1c8f58d2163229e969";
+    /// BLAKE3 of all 5120 fixture bytes (reference test vector).
+    const FIXTURE_5120_HEX: &str =
+        "9cadc15fed8b5d854562b26a9536d9707cadeda9b143978f319ab34230535833";
+
+    #[test]
+    fn test_hash_report_matches_known_vectors() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("fixture.bin");
+        std::fs::write(&path, fixture(5120)).unwrap();
+
+        let report = HashReport::compute(&Hasher::new(), &path, false, None).unwrap();
+        assert_eq!(report.size, 5120);
+        assert_eq!(report.prehash_size, 4096);
+        assert_eq!(hash_to_hex(&report.prehash), FIXTURE_4096_HEX);
+        assert_eq!(
+            report.full_hash.as_ref().map(hash_to_hex).as_deref(),
+            Some(FIXTURE_5120_HEX)
+        );
+        assert!(report.sampled_hash.is_none());
+
+        let text = report.render(SizeFormat::default());
+        assert!(text.contains(&format!(
+            "Prehash:    {FIXTURE_4096_HEX} (first 4096 bytes)\n"
+        )));
+        assert!(text.contains(&format!("Full hash:  {FIXTURE_5120_HEX}\n")));
+        assert!(text.contains("Size:       5120 bytes (5.0 KiB)\n"));
+        assert!(report
+            .render(SizeFormat::Si)
+            .contains("Size:       5120 bytes (5.1 kB)\n"));
+        assert!(text.contains(&format!("Cache key:  {}\n", report.cache_key())));
+    }
+
+    #[test]
+    fn test_hash_report_prehash_only_and_sampling() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("fixture.bin");
+        std::fs::write(&path, fixture(5120)).unwrap();
+        let hasher = Hasher::new();
+
+        let report = HashReport::compute(&hasher, &path, true, Some(1024)).unwrap();
+        assert_eq!(hash_to_hex(&report.prehash), FIXTURE_4096_HEX);
+        assert!(report.full_hash.is_none());
+        assert!(report.sampled_hash.is_none());
+        assert!(!report.render(SizeFormat::default()).contains("Full hash:"));
+
+        let report = HashReport::compute(&hasher, &path, false, Some(1024)).unwrap();
+        assert_eq!(
+            report.sampled_hash,
+            Some(hasher.sample_hash(&path, 5120).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_hash_report_missing_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("missing.bin");
+        assert_eq!(
+            HashReport::compute(&Hasher::new(), &path, false, None),
+            Err(HashError::NotFound(path))
+        );
+    }
+}
@@ -7,7 +7,13 @@
 //!
 //! * **Self-contained**: All CSS is embedded in the HTML file for easy sharing.
 //! * **Responsive**: The layout adjusts to different screen sizes.
-//! * **Interactive**: Includes collapsible sections for each duplicate group.
+//! * **Interactive**: Collapsible sections for each duplicate group, plus
+//!   expand/collapse-all and client-side sorting by size, count or path. The
+//!   inline script reads group data embedded as JSON, so the report works
+//!   offline. Set `html_plain` (`--html-plain`) to emit static HTML only.
+//! * **Size histogram**: A small bar chart shows how duplicate files and
+//!   reclaimable space split across file-size ranges, and another the
+//!   extensions holding the most reclaimable space.
 //! * **Safe**: Automatically escapes file paths to prevent XSS.
 //! * **Themed**: Supports dark mode via system media queries.
 //!
@@ -23,15 +29,22 @@
 use std::io::Write;
 use std::time::SystemTime;
 
+use super::size::SizeFormat;
 use askama::Template;
 use base64::Engine;
-use bytesize::ByteSize;
 use chrono::{DateTime, Local};
 use image::ImageFormat;
 use std::io::Cursor;
 use std::path::Path;
 
-use crate::duplicates::{DuplicateGroup, ScanSummary};
+use serde::Serialize;
+
+use crate::duplicates::{
+    group_ids, top_extensions, DuplicateGroup, ExtensionTallies, ScanSummary, SizeHistogram,
+};
+
+/// Extensions charted in the report.
+const HTML_TOP_EXTENSIONS: usize = 10;
 
 /// Complete HTML output structure for the Askama template.
 #[derive(Template)]
@@ -53,10 +66,26 @@ pub struct HtmlOutput {
     pub total_duration: String,
     /// Formatted phase durations
     pub phases: Vec<HtmlPhaseDuration>,
+    /// Size histogram bars (empty when there are no duplicates)
+    pub histogram: Vec<HtmlHistogramBar>,
+    /// Bars for the extensions with the most reclaimable space, largest first
+    pub extensions: Vec<HtmlHistogramBar>,
     /// Whether thumbnails are enabled
     pub html_thumbnails: bool,
     /// List of duplicate groups formatted for HTML
     pub groups: Vec<HtmlDuplicateGroup>,
+    /// Whether to embed the sorting and expand/collapse script
+    pub interactive: bool,
+    /// Group sort keys serialized as JSON, safe to embed in a `<script>` element
+    pub groups_json: String,
+}
+
+/// Per-group sort keys embedded in the interactive report.
+#[derive(Serialize)]
+struct HtmlGroupData<'a> {
+    size: u64,
+    count: usize,
+    path: &'a str,
 }
 
 /// A phase duration formatted for HTML.
@@ -67,14 +96,34 @@ pub struct HtmlPhaseDuration {
     pub duration: String,
 }
 
+/// A size histogram bucket or extension tally formatted for HTML.
+pub struct HtmlHistogramBar {
+    /// Size range or extension label
+    pub label: String,
+    /// Number of duplicate files in this range
+    pub count: usize,
+    /// Human-readable reclaimable space in this range
+    pub reclaimable: String,
+    /// Bar width as a percentage of the largest bar
+    pub percent: u64,
+}
+
 /// A duplicate group formatted for HTML presentation.
 pub struct HtmlDuplicateGroup {
+    /// Stable group ID (hash prefix)
+    pub group_id: String,
     /// BLAKE3 hash as hexadecimal string
     pub hash_hex: String,
     /// Human-readable file size (shared by all files)
     pub size_formatted: String,
     /// Detailed file entries for this group
     pub files: Vec<HtmlFileEntry>,
+    /// Whether this group holds zero-byte files
+    pub is_empty_group: bool,
+    /// Whether this is an advisory group of similarly named files
+    pub is_name_similar: bool,
+    /// Shared content percentage of a partial duplicate pair
+    pub partial_percent: Option<u64>,
 }
 
 /// A file entry formatted for HTML presentation.
@@ -136,11 +185,17 @@ impl HtmlOutput {
             });
         }
 
+        let size_format = config.size_units;
+        let histogram = histogram_bars(&summary.size_histogram, size_format);
+        let extensions = extension_bars(&summary.reclaimable_by_extension, size_format);
+
         let html_groups = groups
             .iter()
-            .map(|g| HtmlDuplicateGroup {
+            .zip(group_ids(groups))
+            .map(|(g, group_id)| HtmlDuplicateGroup {
+                group_id,
                 hash_hex: g.hash_hex(),
-                size_formatted: ByteSize::b(g.size).to_string(),
+                size_formatted: size_format.format(g.size),
                 files: g
                     .files
                     .iter()
@@ -166,24 +221,106 @@ impl HtmlOutput {
                         }
                     })
                     .collect(),
+                is_empty_group: g.is_empty_group,
+                is_name_similar: g.is_name_similar,
+                partial_percent: g
+                    .partial_similarity
+                    .map(|similarity| (similarity * 100.0).round() as u64),
             })
             .collect();
 
+        let interactive = !config.html_plain;
+        let groups_json = if interactive {
+            group_data_json(&html_groups, groups)
+        } else {
+            String::new()
+        };
+
         Self {
             timestamp,
             version,
             summary: summary.clone(),
-            total_size: ByteSize::b(summary.total_size).to_string(),
-            total_duplicate_size: ByteSize::b(summary.total_duplicate_size).to_string(),
-            reclaimable_space: ByteSize::b(summary.reclaimable_space).to_string(),
+            total_size: size_format.format(summary.total_size),
+            total_duplicate_size: size_format.format(summary.total_duplicate_size),
+            reclaimable_space: size_format.format(summary.reclaimable_space),
             total_duration: format_duration(summary.scan_duration),
             phases,
+            histogram,
+            extensions,
             html_thumbnails: config.html_thumbnails,
             groups: html_groups,
+            interactive,
+            groups_json,
         }
     }
 }
 
+/// Scale histogram buckets to bar widths relative to the largest bucket.
+fn histogram_bars(histogram: &SizeHistogram, size_format: SizeFormat) -> Vec<HtmlHistogramBar> {
+    if histogram.is_empty() {
+        return Vec::new();
+    }
+    let max = histogram
+        .buckets
+        .iter()
+        .map(|b| b.reclaimable_space)
+        .max()
+        .unwrap_or(0);
+    histogram
+        .buckets
+        .iter()
+        .map(|b| HtmlHistogramBar {
+            label: b.label.clone(),
+            count: b.count,
+            reclaimable: size_format.format(b.reclaimable_space),
+            percent: if max == 0 {
+                0
+            } else {
+                (u128::from(b.reclaimable_space) * 100 / u128::from(max)) as u64
+            },
+        })
+        .collect()
+}
+
+/// Bars for the extensions with the most reclaimable space.
+fn extension_bars(tallies: &ExtensionTallies, size_format: SizeFormat) -> Vec<HtmlHistogramBar> {
+    let top = top_extensions(tallies, HTML_TOP_EXTENSIONS);
+    let max = top.first().map_or(0, |&(_, _, bytes)| bytes);
+    top.into_iter()
+        .map(|(ext, count, bytes)| HtmlHistogramBar {
+            label: ext.to_string(),
+            count,
+            reclaimable: size_format.format(bytes),
+            percent: if max == 0 {
+                0
+            } else {
+                (u128::from(bytes) * 100 / u128::from(max)) as u64
+            },
+        })
+        .collect()
+}
+
+/// Serialize the sort keys for each group as JSON for inline embedding.
+///
+/// `<`, `>` and `&` are written as unicode escapes so a path containing
+/// `</script>` cannot end the script element early.
+fn group_data_json(html_groups: &[HtmlDuplicateGroup], groups: &[DuplicateGroup]) -> String {
+    let data: Vec<HtmlGroupData<'_>> = html_groups
+        .iter()
+        .zip(groups)
+        .map(|(html, group)| HtmlGroupData {
+            size: group.size,
+            count: html.files.len(),
+            path: html.files.first().map_or("", |f| f.path_display.as_str()),
+        })
+        .collect();
+    serde_json::to_string(&data)
+        .unwrap_or_else(|_| "[]".to_string())
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+}
+
 /// Generate a base64-encoded thumbnail for an image file.
 fn generate_thumbnail(path: &Path, max_size: u32) -> Option<String> {
     // 1. Open the image
@@ -264,6 +401,7 @@ pub enum HtmlOutputError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::duplicates::reclaimable_by_extension;
     use crate::scanner::FileEntry;
     use std::path::PathBuf;
     use std::time::Duration;
@@ -327,7 +465,7 @@ mod tests {
 
         assert!(html.contains("<!DOCTYPE html>"));
         assert!(html.contains("Duplicate Report"));
-        // bytesize formatting - 2048 bytes and 1024 bytes
+        // Size formatting - 2048 bytes and 1024 bytes
         assert!(html.contains("2.0") && (html.contains("KiB") || html.contains("KB")));
         assert!(html.contains("1.0") && (html.contains("KiB") || html.contains("KB")));
         assert!(html.contains("abababab")); // hash
@@ -400,6 +538,99 @@ mod tests {
         assert!(html.contains("5.2") || html.contains("5.0")); // reclaimable
     }
 
+    #[test]
+    fn test_size_histogram_rendering() {
+        let now = SystemTime::now();
+        let groups = vec![
+            DuplicateGroup::new(
+                [1u8; 32],
+                100,
+                vec![
+                    FileEntry::new(PathBuf::from("/a/small1"), 100, now),
+                    FileEntry::new(PathBuf::from("/a/small2"), 100, now),
+                ],
+                Vec::new(),
+            ),
+            DuplicateGroup::new(
+                [2u8; 32],
+                4096,
+                vec![
+                    FileEntry::new(PathBuf::from("/a/big1"), 4096, now),
+                    FileEntry::new(PathBuf::from("/a/big2"), 4096, now),
+                ],
+                Vec::new(),
+            ),
+        ];
+        let summary = ScanSummary {
+            size_histogram: SizeHistogram::from_groups(&groups),
+            ..Default::default()
+        };
+        let config = crate::config::Config::default();
+
+        let output = HtmlOutput::new(&groups, &summary, &config);
+        assert_eq!(output.histogram.len(), 4);
+        assert_eq!(output.histogram[0].percent, 2); // 100 of 4096 bytes
+        assert_eq!(output.histogram[1].percent, 100);
+        assert_eq!(output.histogram[2].percent, 0);
+
+        let html = output.to_html().expect("Failed to render HTML");
+        assert!(html.contains("Duplicate Size Distribution"));
+        assert!(html.contains("1KB-1MB"));
+        assert!(html.contains("class=\"histogram-bar\" style=\"width: 100%;\""));
+
+        // No duplicates, no chart
+        let empty = HtmlOutput::new(&[], &ScanSummary::default(), &config);
+        assert!(empty.histogram.is_empty());
+        assert!(!empty
+            .to_html()
+            .unwrap()
+            .contains("Duplicate Size Distribution"));
+    }
+
+    #[test]
+    fn test_extension_bars_rendering() {
+        let now = SystemTime::now();
+        let groups = vec![
+            DuplicateGroup::new(
+                [1u8; 32],
+                4096,
+                vec![
+                    FileEntry::new(PathBuf::from("/a/clip.mov"), 4096, now),
+                    FileEntry::new(PathBuf::from("/b/clip.MOV"), 4096, now),
+                ],
+                Vec::new(),
+            ),
+            DuplicateGroup::new(
+                [2u8; 32],
+                1024,
+                vec![
+                    FileEntry::new(PathBuf::from("/a/LICENSE"), 1024, now),
+                    FileEntry::new(PathBuf::from("/b/LICENSE"), 1024, now),
+                ],
+                Vec::new(),
+            ),
+        ];
+        let summary = ScanSummary {
+            reclaimable_by_extension: reclaimable_by_extension(&groups),
+            ..Default::default()
+        };
+        let config = crate::config::Config::default();
+
+        let output = HtmlOutput::new(&groups, &summary, &config);
+        assert_eq!(output.extensions.len(), 2);
+        assert_eq!(output.extensions[0].label, "mov");
+        assert_eq!(output.extensions[0].percent, 100);
+        assert_eq!(output.extensions[1].label, "(none)");
+        assert_eq!(output.extensions[1].percent, 25);
+
+        let html = output.to_html().expect("Failed to render HTML");
+        assert!(html.contains("Top Extensions"));
+        assert!(html.contains("(none)"));
+
+        let empty = HtmlOutput::new(&[], &ScanSummary::default(), &config);
+        assert!(!empty.to_html().unwrap().contains("Top Extensions"));
+    }
+
     #[test]
     fn test_reference_badge_rendering() {
         let now = SystemTime::now();
@@ -423,6 +654,46 @@ mod tests {
         assert!(html.contains("Reference"));
     }
 
+    #[test]
+    fn test_empty_group_label_rendering() {
+        let now = SystemTime::now();
+        let groups = vec![DuplicateGroup::new_empty(
+            vec![
+                FileEntry::new(PathBuf::from("/tmp/empty1"), 0, now),
+                FileEntry::new(PathBuf::from("/tmp/empty2"), 0, now),
+            ],
+            Vec::new(),
+        )];
+        let summary = ScanSummary::default();
+        let config = crate::config::Config::default();
+
+        let output = HtmlOutput::new(&groups, &summary, &config);
+        assert!(output.groups[0].is_empty_group);
+        let html = output.to_html().expect("Failed to render HTML");
+        assert!(html.contains("badge-empty"));
+        assert!(html.contains("Empty files"));
+    }
+
+    #[test]
+    fn test_name_similar_label_rendering() {
+        let now = SystemTime::now();
+        let groups = vec![DuplicateGroup::new_name_similar(
+            "img.jpg",
+            vec![
+                FileEntry::new(PathBuf::from("/tmp/img.jpg"), 10, now),
+                FileEntry::new(PathBuf::from("/tmp/img (1).jpg"), 12, now),
+            ],
+            Vec::new(),
+        )];
+        let summary = ScanSummary::default();
+        let config = crate::config::Config::default();
+
+        let output = HtmlOutput::new(&groups, &summary, &config);
+        let html = output.to_html().expect("Failed to render HTML");
+        assert!(html.contains("badge-name"));
+        assert!(html.contains("Similar name"));
+    }
+
     #[test]
     fn test_thumbnail_generation() {
         use image::{Rgb, RgbImage};
@@ -441,4 +712,96 @@ mod tests {
         let thumb_str = thumb.unwrap();
         assert!(thumb_str.starts_with("data:image/png;base64,"));
     }
+
+    fn sort_test_groups() -> Vec<DuplicateGroup> {
+        let now = SystemTime::now();
+        vec![
+            DuplicateGroup::new(
+                [1u8; 32],
+                100,
+                vec![
+                    FileEntry::new(PathBuf::from("/b/small.txt"), 100, now),
+                    FileEntry::new(PathBuf::from("/b/small2.txt"), 100, now),
+                    FileEntry::new(PathBuf::from("/b/small3.txt"), 100, now),
+                ],
+                Vec::new(),
+            ),
+            DuplicateGroup::new(
+                [2u8; 32],
+                5000,
+                vec![
+                    FileEntry::new(PathBuf::from("/a/</script>.bin"), 5000, now),
+                    FileEntry::new(PathBuf::from("/a/big2.bin"), 5000, now),
+                ],
+                Vec::new(),
+            ),
+        ]
+    }
+
+    /// Extract the inline JSON data block from a rendered report.
+    fn embedded_data(html: &str) -> Option<&str> {
+        let open = r#"<script type="application/json" id="group-data">"#;
+        let start = html.find(open)? + open.len();
+        let end = start + html[start..].find("</script>")?;
+        Some(&html[start..end])
+    }
+
+    #[test]
+    fn test_interactive_report() {
+        let groups = sort_test_groups();
+        let summary = ScanSummary::default();
+        let config = crate::config::Config::default();
+
+        let html = HtmlOutput::new(&groups, &summary, &config)
+            .to_html()
+            .unwrap();
+
+        // Sort handler and controls are embedded inline
+        assert!(html.contains("function sortGroups(key)"));
+        assert!(html.contains(r#"data-sort="size""#));
+        assert!(html.contains(r#"data-sort="count""#));
+        assert!(html.contains(r#"data-sort="path""#));
+        assert!(html.contains(r#"data-index="1""#));
+        // No external assets
+        assert!(!html.contains("src=\"http"));
+        assert!(!html.contains("<link"));
+
+        // The data block parses and a path cannot close the script early
+        let data = embedded_data(&html).expect("missing group data");
+        assert!(!data.contains('<'));
+        let parsed: serde_json::Value = serde_json::from_str(data).unwrap();
+        let items = parsed.as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["size"], 100);
+        assert_eq!(items[0]["count"], 3);
+        assert_eq!(items[1]["path"], "/a/</script>.bin");
+
+        // Well-formed: one document, balanced elements
+        assert_eq!(html.matches("<!DOCTYPE html>").count(), 1);
+        for tag in ["html", "body", "script", "details", "div", "table"] {
+            assert_eq!(
+                html.matches(&format!("<{tag}")).count(),
+                html.matches(&format!("</{tag}>")).count(),
+                "unbalanced <{tag}>"
+            );
+        }
+    }
+
+    #[test]
+    fn test_plain_report_has_no_script() {
+        let groups = sort_test_groups();
+        let summary = ScanSummary::default();
+        let config = crate::config::Config {
+            html_plain: true,
+            ..Default::default()
+        };
+
+        let html = HtmlOutput::new(&groups, &summary, &config)
+            .to_html()
+            .unwrap();
+
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("data-sort"));
+        assert!(html.contains("class=\"group-card\""));
+    }
 }
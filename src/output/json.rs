@@ -4,27 +4,77 @@
 //!
 //! # Output Schema
 //!
+//! Every document is wrapped in a stable envelope. Consumers should check
+//! `schema_version` before reading `data`:
+//!
 //! ```json
 //! {
-//!   "duplicates": [
-//!     {
-//!       "hash": "abc123...",
-//!       "size": 1024,
-//!       "files": ["/path/to/file1.txt", "/path/to/file2.txt"]
-//!     }
-//!   ],
-//!   "summary": {
-//!     "total_files": 100,
-//!     "total_size": 1048576,
-//!     "duplicate_groups": 5,
-//!     "duplicate_files": 10,
-//!     "reclaimable_space": 51200,
-//!     "scan_duration_ms": 1234,
-//!     "interrupted": false
+//!   "schema_version": "2.0",
+//!   "generated_at": "2026-01-01T00:00:00Z",
+//!   "tool_version": "0.3.0",
+//!   "data": {
+//!     "config": { ... },
+//!     "duplicates": [
+//!       {
+//!         "group_id": "abc123def456",
+//!         "hash": "abc123def456...",
+//!         "size": 1024,
+//!         "files": ["/path/to/file1.txt", "/path/to/file2.txt"],
+//!         "is_empty_group": false,
+//!         "match_kind": "Exact",
+//!         "likely_intentional": false
+//!       }
+//!     ],
+//!     "summary": {
+//!       "total_files": 100,
+//!       "total_size": 1048576,
+//!       "duplicate_groups": 5,
+//!       "duplicate_files": 10,
+//!       "reclaimable_space": 51200,
+//!       "size_histogram": [
+//!         { "label": "0-1KB", "min_size": 0, "max_size": 1024, "count": 4, "reclaimable_space": 2048 },
+//!         ...
+//!       ],
+//!       "reclaimable_by_extension": {
+//!         "jpg": { "files": 8, "reclaimable_space": 40960 },
+//!         "(none)": { "files": 2, "reclaimable_space": 10240 }
+//!       },
+//!       "scan_duration_ms": 1234,
+//!       "interrupted": false
+//!     },
+//!     "pipeline": {
+//!       "total_files": 100,
+//!       "eliminated_by_size": 70,
+//!       "eliminated_by_name": 0,
+//!       "eliminated_by_prehash": 12,
+//!       "eliminated_by_fullhash": 3,
+//!       "confirmed_groups": 5,
+//!       "confirmed_files": 15,
+//!       "cache_prehash_hits": 0,
+//!       "cache_prehash_misses": 30,
+//!       "cache_fullhash_hits": 0,
+//!       "cache_fullhash_misses": 18
+//!     },
+//!     "errors": [
+//!       {
+//!         "kind": "permission_denied",
+//!         "path": "/path/to/locked",
+//!         "message": "Permission denied: /path/to/locked - try running with elevated privileges"
+//!       }
+//!     ]
 //!   }
 //! }
 //! ```
 //!
+//! # Versioning Contract
+//!
+//! [`JSON_SCHEMA_VERSION`] is bumped only on breaking changes: removing or
+//! renaming a field, changing a field's type, or restructuring the envelope.
+//! Adding new fields is not a breaking change, so parsers should ignore
+//! unknown keys. The envelope fields themselves (`schema_version`,
+//! `generated_at`, `tool_version`, `data`) will not change. The global
+//! `--schema-version` flag prints the version emitted by a given binary.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -44,59 +94,106 @@
 //! // Pretty-printed JSON
 //! println!("{}", output.to_json_pretty().unwrap());
 //! ```
+//!
+//! For large result sets, [`JsonStreamOutput`] writes the same document while
+//! converting one group at a time instead of building the whole `duplicates`
+//! list up front.
+//!
+//! File paths are absolute unless `--json-paths-relative <BASE>` is given, in
+//! which case paths inside the base are written relative to it (see
+//! [`paths`](super::paths)).
 
+use std::collections::BTreeMap;
 use std::io::Write;
+use std::path::Path;
 
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize, Serializer};
 
+use super::paths::report_path;
 use crate::config::Config;
-use crate::duplicates::{DuplicateGroup, ScanSummary};
-
-/// Metadata about the scan in JSON format.
-#[derive(Debug, Clone, Serialize)]
-pub struct JsonMetadata {
-    /// Application version
-    pub version: String,
-    /// Timestamp of the scan completion (RFC3339)
-    pub timestamp: DateTime<Utc>,
-    /// Schema version for the JSON output
-    pub schema_version: String,
-    /// Configuration used for the scan
-    pub config: Config,
-}
+use crate::duplicates::{group_ids, DuplicateGroup, MatchKind, ScanSummary, SizeHistogram};
+use crate::scanner::{HashError, ScanError};
+
+/// Current version of the JSON output schema.
+///
+/// See the [module documentation](self#versioning-contract) for when this changes.
+pub const JSON_SCHEMA_VERSION: &str = "2.0";
 
 /// A single duplicate group in JSON format.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonDuplicateGroup {
+    /// Stable group ID (the first 12 characters of `hash`)
+    #[serde(default)]
+    pub group_id: String,
     /// BLAKE3 hash as hexadecimal string (64 characters)
     pub hash: String,
     /// File size in bytes
     pub size: u64,
-    /// Absolute paths to all duplicate files
+    /// Paths to all duplicate files, absolute unless `--json-paths-relative` is set
     pub files: Vec<String>,
+    /// Whether this group holds zero-byte files (see `--include-empty`)
+    #[serde(default)]
+    pub is_empty_group: bool,
+    /// How the files were matched (`Exact`, `Similar`, `Empty`, `NameSimilar`, `Partial`)
+    #[serde(default)]
+    pub match_kind: MatchKind,
+    /// Fraction of content shared by a `Partial` pair (0.0 to 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<f64>,
+    /// Whether the files look like deliberate copies (backups, parallel trees)
+    #[serde(default)]
+    pub likely_intentional: bool,
 }
 
 impl JsonDuplicateGroup {
-    /// Create a JSON duplicate group from a DuplicateGroup.
+    /// Create a JSON duplicate group from a DuplicateGroup and its
+    /// [group ID](crate::duplicates::group_ids).
     ///
     /// Paths are converted to absolute paths where possible.
     #[must_use]
-    pub fn from_duplicate_group(group: &DuplicateGroup) -> Self {
+    pub fn from_duplicate_group(group: &DuplicateGroup, group_id: String) -> Self {
+        Self::from_duplicate_group_relative(group, group_id, None)
+    }
+
+    /// Create a JSON duplicate group with paths relative to `base`.
+    ///
+    /// Paths outside `base` stay absolute. `base` should already be resolved
+    /// with [`resolve_base`](super::paths::resolve_base).
+    #[must_use]
+    pub fn from_duplicate_group_relative(
+        group: &DuplicateGroup,
+        group_id: String,
+        base: Option<&Path>,
+    ) -> Self {
         Self {
+            group_id,
             hash: group.hash_hex(),
             size: group.size,
             files: group
                 .files
                 .iter()
-                .map(|f| normalize_path(f.path.as_path()))
+                .map(|f| report_path(Path::new(&normalize_path(&f.path)), base))
                 .collect(),
+            is_empty_group: group.is_empty_group,
+            match_kind: group.match_kind(),
+            similarity: group.partial_similarity,
+            likely_intentional: group.likely_intentional,
         }
     }
 }
 
+/// Duplicate files of one extension in JSON format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonExtensionTally {
+    /// Number of duplicate files with this extension
+    pub files: usize,
+    /// Space reclaimable by removing them (bytes)
+    pub reclaimable_space: u64,
+}
+
 /// Summary statistics in JSON format.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonSummary {
     /// Total number of files scanned
     pub total_files: usize,
@@ -110,6 +207,16 @@ pub struct JsonSummary {
     pub total_duplicate_size: u64,
     /// Total space that can be reclaimed by removing duplicates (bytes)
     pub reclaimable_space: u64,
+    /// Reclaimable space as a percentage of the total scanned size
+    #[serde(default)]
+    pub wasted_percentage: f64,
+    /// Duplicate files and reclaimable space bucketed by file size
+    #[serde(default)]
+    pub size_histogram: SizeHistogram,
+    /// Duplicate files and reclaimable space per lowercased extension
+    /// (`"(none)"` for files without one)
+    #[serde(default)]
+    pub reclaimable_by_extension: BTreeMap<String, JsonExtensionTally>,
     /// Duration of the scan in milliseconds
     pub scan_duration_ms: u64,
     /// Duration of the walking phase in milliseconds
@@ -146,6 +253,9 @@ pub struct JsonSummary {
     pub images_perceptual_hashed: usize,
     /// Number of perceptual hash cache hits
     pub images_perceptual_hash_cache_hits: usize,
+    /// Symlinks whose target does not exist (with `--report-broken-symlinks`)
+    #[serde(default)]
+    pub broken_symlinks: Vec<String>,
 }
 
 impl JsonSummary {
@@ -159,6 +269,21 @@ impl JsonSummary {
             duplicate_files: summary.duplicate_files,
             total_duplicate_size: summary.total_duplicate_size,
             reclaimable_space: summary.reclaimable_space,
+            wasted_percentage: summary.wasted_percentage(),
+            size_histogram: summary.size_histogram.clone(),
+            reclaimable_by_extension: summary
+                .reclaimable_by_extension
+                .iter()
+                .map(|(ext, &(files, reclaimable_space))| {
+                    (
+                        ext.clone(),
+                        JsonExtensionTally {
+                            files,
+                            reclaimable_space,
+                        },
+                    )
+                })
+                .collect(),
             scan_duration_ms: summary.scan_duration.as_millis() as u64,
             walk_duration_ms: summary.walk_duration.as_millis() as u64,
             perceptual_duration_ms: summary.perceptual_duration.as_millis() as u64,
@@ -177,19 +302,158 @@ impl JsonSummary {
             bloom_prehash_fp_rate: summary.bloom_prehash_fp_rate(),
             images_perceptual_hashed: summary.images_perceptual_hashed,
             images_perceptual_hash_cache_hits: summary.images_perceptual_hash_cache_hits,
+            broken_symlinks: summary
+                .broken_symlinks
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect(),
         }
     }
 }
 
-/// Complete JSON output structure.
-#[derive(Debug, Clone, Serialize)]
-pub struct JsonOutput {
-    /// Metadata about the scan
-    pub metadata: JsonMetadata,
+/// How many files each detection phase eliminated, in JSON format.
+///
+/// The funnel always reconciles: `total_files` minus the four
+/// `eliminated_*` counts equals `confirmed_files`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonPipeline {
+    /// Files that entered the pipeline
+    pub total_files: usize,
+    /// Files with a unique size (Phase 1)
+    pub eliminated_by_size: usize,
+    /// Files skipped for a unique basename (`--same-name-only`)
+    pub eliminated_by_name: usize,
+    /// Files with a unique prehash (Phase 2)
+    pub eliminated_by_prehash: usize,
+    /// Files that survived the prehash but were not confirmed: different
+    /// content, or groups dropped by filters such as `--min-group-size`
+    pub eliminated_by_fullhash: usize,
+    /// Number of confirmed duplicate groups
+    pub confirmed_groups: usize,
+    /// Files in confirmed groups, including the copy each group keeps
+    pub confirmed_files: usize,
+    /// Prehash cache hits
+    pub cache_prehash_hits: usize,
+    /// Prehash cache misses
+    pub cache_prehash_misses: usize,
+    /// Full hash cache hits
+    pub cache_fullhash_hits: usize,
+    /// Full hash cache misses
+    pub cache_fullhash_misses: usize,
+}
+
+impl JsonPipeline {
+    /// Build the elimination funnel of a scan.
+    #[must_use]
+    pub fn from_scan_summary(summary: &ScanSummary) -> Self {
+        Self {
+            total_files: summary.total_files,
+            eliminated_by_size: summary.eliminated_by_size,
+            eliminated_by_name: summary.eliminated_by_name,
+            eliminated_by_prehash: summary.eliminated_by_prehash,
+            eliminated_by_fullhash: summary.eliminated_by_fullhash,
+            confirmed_groups: summary.duplicate_groups,
+            confirmed_files: summary.duplicate_files + summary.duplicate_groups,
+            cache_prehash_hits: summary.cache_prehash_hits,
+            cache_prehash_misses: summary.cache_prehash_misses,
+            cache_fullhash_hits: summary.cache_fullhash_hits,
+            cache_fullhash_misses: summary.cache_fullhash_misses,
+        }
+    }
+}
+
+/// Category of a scan error, for retrying selectively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonErrorKind {
+    /// Access was denied while walking or hashing
+    PermissionDenied,
+    /// The path disappeared before it could be read
+    NotFound,
+    /// A scan path was not a directory
+    NotADirectory,
+    /// A followed symlink led back to an already visited directory
+    SymlinkCycle,
+    /// A symlink points to a target that does not exist
+    BrokenSymlink,
+    /// Any other I/O error while walking directories
+    Io,
+    /// Any other I/O error while reading a file for hashing
+    Hash,
+}
+
+/// A single scan error in JSON format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonScanError {
+    /// Error category
+    pub kind: JsonErrorKind,
+    /// Path the error refers to
+    pub path: String,
+    /// Human-readable error message
+    pub message: String,
+}
+
+impl JsonScanError {
+    /// Create a JSON scan error from a ScanError.
+    #[must_use]
+    pub fn from_scan_error(error: &ScanError) -> Self {
+        let kind = match error {
+            ScanError::PermissionDenied(_) => JsonErrorKind::PermissionDenied,
+            ScanError::NotFound(_) => JsonErrorKind::NotFound,
+            ScanError::NotADirectory(_) => JsonErrorKind::NotADirectory,
+            ScanError::SymlinkCycle { .. } => JsonErrorKind::SymlinkCycle,
+            ScanError::BrokenSymlink { .. } => JsonErrorKind::BrokenSymlink,
+            ScanError::Io { .. } => JsonErrorKind::Io,
+            ScanError::HashError(HashError::PermissionDenied(_)) => JsonErrorKind::PermissionDenied,
+            ScanError::HashError(HashError::NotFound(_)) => JsonErrorKind::NotFound,
+            ScanError::HashError(HashError::Io { .. }) => JsonErrorKind::Hash,
+        };
+        Self {
+            kind,
+            path: error.path().to_string_lossy().into_owned(),
+            message: error.to_string(),
+        }
+    }
+
+    /// Convert every error recorded in a scan summary.
+    #[must_use]
+    pub fn from_scan_summary(summary: &ScanSummary) -> Vec<Self> {
+        summary
+            .scan_errors
+            .iter()
+            .map(Self::from_scan_error)
+            .collect()
+    }
+}
+
+/// Scan results carried inside the output envelope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonData {
+    /// Configuration used for the scan
+    pub config: Config,
     /// List of duplicate groups
     pub duplicates: Vec<JsonDuplicateGroup>,
     /// Scan summary statistics
     pub summary: JsonSummary,
+    /// Files eliminated by each detection phase
+    #[serde(default)]
+    pub pipeline: JsonPipeline,
+    /// Errors encountered while scanning (empty on a clean scan)
+    #[serde(default)]
+    pub errors: Vec<JsonScanError>,
+}
+
+/// Complete JSON output structure with a stable top-level envelope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonOutput {
+    /// Schema version of this document (see [`JSON_SCHEMA_VERSION`])
+    pub schema_version: String,
+    /// Timestamp when the document was generated (RFC3339)
+    pub generated_at: DateTime<Utc>,
+    /// Version of RustDupe that generated the document
+    pub tool_version: String,
+    /// Scan results
+    pub data: JsonData,
 }
 
 impl JsonOutput {
@@ -221,8 +485,8 @@ impl JsonOutput {
     /// let config = Config::default();
     ///
     /// let output = JsonOutput::new(&groups, &summary, ExitCode::Success, &config);
-    /// assert_eq!(output.duplicates.len(), 1);
-    /// assert_eq!(output.metadata.version, env!("CARGO_PKG_VERSION"));
+    /// assert_eq!(output.data.duplicates.len(), 1);
+    /// assert_eq!(output.tool_version, env!("CARGO_PKG_VERSION"));
     /// ```
     #[must_use]
     pub fn new(
@@ -232,17 +496,20 @@ impl JsonOutput {
         config: &Config,
     ) -> Self {
         Self {
-            metadata: JsonMetadata {
-                version: env!("CARGO_PKG_VERSION").to_string(),
-                timestamp: Utc::now(),
-                schema_version: "1.0".to_string(),
+            schema_version: JSON_SCHEMA_VERSION.to_string(),
+            generated_at: Utc::now(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            data: JsonData {
                 config: config.clone(),
+                duplicates: groups
+                    .iter()
+                    .zip(group_ids(groups))
+                    .map(|(g, id)| JsonDuplicateGroup::from_duplicate_group(g, id))
+                    .collect(),
+                summary: JsonSummary::from_scan_summary(summary, exit_code),
+                pipeline: JsonPipeline::from_scan_summary(summary),
+                errors: JsonScanError::from_scan_summary(summary),
             },
-            duplicates: groups
-                .iter()
-                .map(JsonDuplicateGroup::from_duplicate_group)
-                .collect(),
-            summary: JsonSummary::from_scan_summary(summary, exit_code),
         }
     }
 
@@ -266,7 +533,9 @@ impl JsonOutput {
 
     /// Write JSON to a writer.
     ///
-    /// This method uses `serde_json::to_writer` for efficient streaming to the writer.
+    /// Serializes straight into the writer without building an intermediate
+    /// string. Use [`JsonStreamOutput`] to also avoid converting every group
+    /// up front.
     ///
     /// # Arguments
     ///
@@ -277,16 +546,171 @@ impl JsonOutput {
     ///
     /// Returns an error if writing or serialization fails.
     pub fn write_to<W: Write>(&self, writer: &mut W, pretty: bool) -> Result<(), JsonOutputError> {
-        if pretty {
-            serde_json::to_writer_pretty(&mut *writer, self)?;
-        } else {
-            serde_json::to_writer(&mut *writer, self)?;
+        write_json(writer, self, pretty)
+    }
+
+    /// Rewrite file paths inside `base` as relative to it.
+    ///
+    /// Used by `--json-paths-relative`; paths outside `base` stay absolute.
+    #[must_use]
+    pub fn with_paths_relative_to(mut self, base: Option<&Path>) -> Self {
+        self.data.rebase_paths(base);
+        self
+    }
+}
+
+impl JsonData {
+    /// Rewrite each group's file paths inside `base` as relative to it.
+    pub(crate) fn rebase_paths(&mut self, base: Option<&Path>) {
+        let Some(base) = base else {
+            return;
+        };
+        for file in self.duplicates.iter_mut().flat_map(|g| g.files.iter_mut()) {
+            *file = report_path(Path::new(file.as_str()), Some(base));
+        }
+    }
+}
+
+/// Streaming JSON writer that borrows scan results instead of owning them.
+///
+/// Produces the same document as [`JsonOutput`], but each group is converted
+/// to a [`JsonDuplicateGroup`] only as it is serialized, so memory use does
+/// not grow with the number of groups. The summary is written last, after
+/// the groups, from the precomputed [`ScanSummary`].
+///
+/// # Example
+///
+/// ```
+/// use rustdupe::config::Config;
+/// use rustdupe::duplicates::ScanSummary;
+/// use rustdupe::error::ExitCode;
+/// use rustdupe::output::json::JsonStreamOutput;
+///
+/// let config = Config::default();
+/// let output = JsonStreamOutput::new(&[], &ScanSummary::default(), ExitCode::Success, &config);
+///
+/// let mut buffer = Vec::new();
+/// output.write_to(&mut buffer, false).unwrap();
+/// assert!(buffer.ends_with(b"}\n"));
+/// ```
+#[derive(Debug, Serialize)]
+pub struct JsonStreamOutput<'a> {
+    schema_version: &'static str,
+    generated_at: DateTime<Utc>,
+    tool_version: &'static str,
+    data: JsonStreamData<'a>,
+}
+
+/// Borrowed counterpart of [`JsonData`]; field order must match it.
+#[derive(Debug, Serialize)]
+struct JsonStreamData<'a> {
+    config: &'a Config,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicates: Option<StreamedGroups<'a>>,
+    summary: JsonSummary,
+    pipeline: JsonPipeline,
+    errors: Vec<JsonScanError>,
+}
+
+/// Serializes groups as a JSON array, converting each one on demand.
+///
+/// The second field is the `--json-paths-relative` base, if any.
+#[derive(Debug)]
+struct StreamedGroups<'a>(&'a [DuplicateGroup], Option<&'a Path>);
+
+impl Serialize for StreamedGroups<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            self.0
+                .iter()
+                .zip(group_ids(self.0))
+                .map(|(g, id)| JsonDuplicateGroup::from_duplicate_group_relative(g, id, self.1)),
+        )
+    }
+}
+
+impl<'a> JsonStreamOutput<'a> {
+    /// Create a streaming JSON output borrowing the scan results.
+    ///
+    /// # Arguments
+    ///
+    /// * `groups` - The duplicate groups found during scanning
+    /// * `summary` - The scan summary statistics
+    /// * `exit_code` - The exit code for this run
+    /// * `config` - The configuration used for the scan
+    #[must_use]
+    pub fn new(
+        groups: &'a [DuplicateGroup],
+        summary: &ScanSummary,
+        exit_code: crate::error::ExitCode,
+        config: &'a Config,
+    ) -> Self {
+        Self {
+            schema_version: JSON_SCHEMA_VERSION,
+            generated_at: Utc::now(),
+            tool_version: env!("CARGO_PKG_VERSION"),
+            data: JsonStreamData {
+                config,
+                duplicates: Some(StreamedGroups(groups, None)),
+                summary: JsonSummary::from_scan_summary(summary, exit_code),
+                pipeline: JsonPipeline::from_scan_summary(summary),
+                errors: JsonScanError::from_scan_summary(summary),
+            },
+        }
+    }
+
+    /// Omit the `duplicates` array entirely, leaving only the summary.
+    ///
+    /// Used by `--summary-only`.
+    #[must_use]
+    pub fn with_summary_only(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.data.duplicates = None;
+        }
+        self
+    }
+
+    /// Write file paths inside `base` relative to it.
+    ///
+    /// Used by `--json-paths-relative`; paths outside `base` stay absolute.
+    #[must_use]
+    pub fn with_paths_relative_to(mut self, base: Option<&'a Path>) -> Self {
+        if let Some(groups) = self.data.duplicates.as_mut() {
+            groups.1 = base;
         }
-        writer.write_all(b"\n")?;
-        Ok(())
+        self
+    }
+
+    /// Stream the JSON document to a writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to output to (e.g., stdout or a file)
+    /// * `pretty` - Whether to pretty-print the output (`false` for `--json-compact`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing or serialization fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W, pretty: bool) -> Result<(), JsonOutputError> {
+        write_json(writer, self, pretty)
     }
 }
 
+/// Serialize `value` into `writer`, followed by a trailing newline.
+fn write_json<W: Write, T: Serialize>(
+    writer: &mut W,
+    value: &T,
+    pretty: bool,
+) -> Result<(), JsonOutputError> {
+    if pretty {
+        serde_json::to_writer_pretty(&mut *writer, value)?;
+    } else {
+        serde_json::to_writer(&mut *writer, value)?;
+    }
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
 /// Normalize a path to an absolute path string.
 ///
 /// Attempts to canonicalize the path. If that fails (e.g., file no longer exists),
@@ -314,6 +738,7 @@ pub enum JsonOutputError {
 mod tests {
     use super::*;
     use std::path::PathBuf;
+    use std::sync::Arc;
     use std::time::Duration;
 
     fn create_test_summary() -> ScanSummary {
@@ -321,7 +746,9 @@ mod tests {
             total_files: 100,
             total_size: 1024 * 1024,
             eliminated_by_size: 50,
+            eliminated_by_name: 0,
             eliminated_by_prehash: 30,
+            eliminated_by_fullhash: 5,
             cache_prehash_hits: 0,
             cache_prehash_misses: 0,
             cache_fullhash_hits: 0,
@@ -330,10 +757,13 @@ mod tests {
             duplicate_files: 10,
             total_duplicate_size: 1024 * 1024,
             reclaimable_space: 51200,
+            size_histogram: SizeHistogram::default(),
+            reclaimable_by_extension: Default::default(),
             scan_duration: Duration::from_millis(1234),
             walk_duration: Duration::from_millis(100),
             perceptual_duration: Duration::from_millis(0),
             document_duration: Duration::from_millis(0),
+            partial_duration: Duration::from_millis(0),
             size_duration: Duration::from_millis(50),
             prehash_duration: Duration::from_millis(200),
             fullhash_duration: Duration::from_millis(800),
@@ -348,6 +778,9 @@ mod tests {
             images_perceptual_hash_cache_hits: 0,
             documents_fingerprinted: 0,
             documents_fingerprint_cache_hits: 0,
+            partial_files_chunked: 0,
+            partial_pairs: 0,
+            broken_symlinks: Vec::new(),
         }
     }
 
@@ -384,8 +817,8 @@ mod tests {
             crate::error::ExitCode::Success,
             &Config::default(),
         );
-        assert!(output.duplicates.is_empty());
-        assert_eq!(output.summary.total_files, 0);
+        assert!(output.data.duplicates.is_empty());
+        assert_eq!(output.data.summary.total_files, 0);
     }
 
     #[test]
@@ -399,11 +832,99 @@ mod tests {
             &Config::default(),
         );
 
-        assert_eq!(output.duplicates.len(), 2);
-        assert_eq!(output.duplicates[0].files.len(), 2);
-        assert_eq!(output.duplicates[1].files.len(), 3);
-        assert_eq!(output.summary.duplicate_groups, 5);
-        assert_eq!(output.summary.scan_duration_ms, 1234);
+        assert_eq!(output.data.duplicates.len(), 2);
+        assert_eq!(output.data.duplicates[0].files.len(), 2);
+        assert_eq!(output.data.duplicates[1].files.len(), 3);
+        assert_eq!(output.data.summary.duplicate_groups, 5);
+        assert_eq!(output.data.summary.scan_duration_ms, 1234);
+    }
+
+    #[test]
+    fn test_json_empty_group_label() {
+        let now = std::time::SystemTime::now();
+        let groups = vec![DuplicateGroup::new_empty(
+            vec![
+                crate::scanner::FileEntry::new(PathBuf::from("/path/to/empty1"), 0, now),
+                crate::scanner::FileEntry::new(PathBuf::from("/path/to/empty2"), 0, now),
+            ],
+            Vec::new(),
+        )];
+        let output = JsonOutput::new(
+            &groups,
+            &ScanSummary::default(),
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+
+        assert!(output.data.duplicates[0].is_empty_group);
+        let json = output.to_json().unwrap();
+        assert!(json.contains("\"is_empty_group\":true"));
+        assert!(json.contains("\"likely_intentional\":false"));
+    }
+
+    #[test]
+    fn test_json_likely_intentional_flag() {
+        let now = std::time::SystemTime::now();
+        let mut group = DuplicateGroup::new(
+            [7; 32],
+            10,
+            vec![
+                crate::scanner::FileEntry::new(PathBuf::from("/etc/app.conf"), 10, now),
+                crate::scanner::FileEntry::new(PathBuf::from("/etc/app.conf.bak"), 10, now),
+            ],
+            Vec::new(),
+        );
+        group.likely_intentional = true;
+        let output = JsonOutput::new(
+            &[group],
+            &ScanSummary::default(),
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+
+        assert!(output.data.duplicates[0].likely_intentional);
+        let json = output.to_json().unwrap();
+        assert!(json.contains("\"likely_intentional\":true"));
+    }
+
+    #[test]
+    fn test_json_name_similar_match_kind() {
+        let now = std::time::SystemTime::now();
+        let groups = vec![DuplicateGroup::new_name_similar(
+            "img.jpg",
+            vec![
+                crate::scanner::FileEntry::new(PathBuf::from("/photos/img.jpg"), 10, now),
+                crate::scanner::FileEntry::new(PathBuf::from("/photos/img (1).jpg"), 12, now),
+            ],
+            Vec::new(),
+        )];
+        let output = JsonOutput::new(
+            &groups,
+            &ScanSummary::default(),
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+
+        assert_eq!(output.data.duplicates[0].match_kind, MatchKind::NameSimilar);
+        let json = output.to_json().unwrap();
+        assert!(json.contains("\"match_kind\":\"NameSimilar\""));
+        assert!(!json.contains("\"similarity\""));
+
+        let partial = vec![DuplicateGroup::new_partial(
+            0.875,
+            groups[0].files.clone(),
+            Vec::new(),
+        )];
+        let json = JsonOutput::new(
+            &partial,
+            &ScanSummary::default(),
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        )
+        .to_json()
+        .unwrap();
+        assert!(json.contains("\"match_kind\":\"Partial\""));
+        assert!(json.contains("\"similarity\":0.875"));
     }
 
     #[test]
@@ -452,14 +973,15 @@ mod tests {
         // Parse it back to verify it's valid JSON
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert!(parsed.get("duplicates").is_some());
-        assert!(parsed.get("summary").is_some());
-        assert!(parsed.get("metadata").is_some());
+        let data = parsed.get("data").unwrap();
+        assert!(data.get("duplicates").is_some());
+        assert!(data.get("summary").is_some());
+        assert!(data.get("config").is_some());
 
-        let duplicates = parsed.get("duplicates").unwrap().as_array().unwrap();
+        let duplicates = data.get("duplicates").unwrap().as_array().unwrap();
         assert_eq!(duplicates.len(), 2);
 
-        let summary = parsed.get("summary").unwrap();
+        let summary = data.get("summary").unwrap();
         assert_eq!(summary.get("total_files").unwrap().as_u64().unwrap(), 100);
     }
 
@@ -484,11 +1006,15 @@ mod tests {
         );
 
         // Hash should be 64 hex characters
-        assert_eq!(output.duplicates[0].hash.len(), 64);
-        assert!(output.duplicates[0]
+        assert_eq!(output.data.duplicates[0].hash.len(), 64);
+        assert!(output.data.duplicates[0]
             .hash
             .chars()
             .all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(output.data.duplicates[0].group_id, group_ids(&groups)[0]);
+        assert!(output.data.duplicates[0]
+            .hash
+            .starts_with(&output.data.duplicates[0].group_id));
     }
 
     #[test]
@@ -508,6 +1034,143 @@ mod tests {
         assert!(written.ends_with("}\n"));
     }
 
+    #[test]
+    fn test_stream_matches_buffered_output() {
+        let groups = create_test_groups();
+        let summary = create_test_summary();
+        let config = Config::default();
+        let exit_code = crate::error::ExitCode::Success;
+
+        let buffered = JsonOutput::new(&groups, &summary, exit_code, &config);
+        let mut expected: serde_json::Value =
+            serde_json::from_str(&buffered.to_json().unwrap()).unwrap();
+
+        for pretty in [true, false] {
+            let mut buffer = Vec::new();
+            JsonStreamOutput::new(&groups, &summary, exit_code, &config)
+                .write_to(&mut buffer, pretty)
+                .unwrap();
+
+            let text = String::from_utf8(buffer).unwrap();
+            assert_eq!(text.lines().count() > 1, pretty);
+            let mut streamed: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+            // Timestamps are taken at construction and naturally differ
+            streamed["generated_at"] = serde_json::Value::Null;
+            expected["generated_at"] = serde_json::Value::Null;
+            assert_eq!(streamed, expected);
+
+            // Summary follows the groups
+            let duplicates_at = text.find("\"duplicates\"").unwrap();
+            assert!(text.find("\"summary\"").unwrap() > duplicates_at);
+        }
+    }
+
+    #[test]
+    fn test_json_paths_relative() {
+        // The test paths do not exist, so they are not canonicalized
+        let groups = create_test_groups();
+        let summary = create_test_summary();
+        let config = Config::default();
+        let exit_code = crate::error::ExitCode::Success;
+        let base = Path::new("/path");
+
+        let output = JsonOutput::new(&groups, &summary, exit_code, &config)
+            .with_paths_relative_to(Some(base));
+        assert_eq!(output.data.duplicates[0].files[0], "to/file1.txt");
+
+        let mut buffer = Vec::new();
+        JsonStreamOutput::new(&groups, &summary, exit_code, &config)
+            .with_paths_relative_to(Some(base))
+            .write_to(&mut buffer, false)
+            .unwrap();
+        let streamed: JsonOutput = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(streamed.data.duplicates[1].files[2], "to/fileC.txt");
+
+        // Outside the base: unchanged
+        let output = JsonOutput::new(&groups, &summary, exit_code, &config)
+            .with_paths_relative_to(Some(Path::new("/elsewhere")));
+        assert_eq!(output.data.duplicates[0].files[0], "/path/to/file1.txt");
+        // The groups themselves keep absolute paths for deletion
+        assert_eq!(groups[0].files[0].path, PathBuf::from("/path/to/file1.txt"));
+    }
+
+    #[test]
+    fn test_json_scan_error_kinds() {
+        let io = |kind| Arc::new(std::io::Error::new(kind, "disk error"));
+        let mut summary = create_test_summary();
+        summary.scan_errors = vec![
+            ScanError::PermissionDenied(PathBuf::from("/locked")),
+            ScanError::NotFound(PathBuf::from("/gone")),
+            ScanError::NotADirectory(PathBuf::from("/file.txt")),
+            ScanError::SymlinkCycle {
+                path: PathBuf::from("/loop"),
+                target: PathBuf::from("/"),
+            },
+            ScanError::Io {
+                path: PathBuf::from("/walk"),
+                source: io(std::io::ErrorKind::Other),
+            },
+            ScanError::HashError(HashError::PermissionDenied(PathBuf::from("/secret"))),
+            ScanError::HashError(HashError::NotFound(PathBuf::from("/vanished"))),
+            ScanError::HashError(HashError::Io {
+                path: PathBuf::from("/bad-sector"),
+                source: io(std::io::ErrorKind::UnexpectedEof),
+            }),
+        ];
+
+        let output = JsonOutput::new(
+            &[],
+            &summary,
+            crate::error::ExitCode::PartialSuccess,
+            &Config::default(),
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&output.to_json().unwrap()).unwrap();
+        let errors = parsed["data"]["errors"].as_array().unwrap();
+
+        let kinds: Vec<&str> = errors.iter().map(|e| e["kind"].as_str().unwrap()).collect();
+        assert_eq!(
+            kinds,
+            [
+                "permission_denied",
+                "not_found",
+                "not_a_directory",
+                "symlink_cycle",
+                "io",
+                "permission_denied",
+                "not_found",
+                "hash",
+            ]
+        );
+        assert_eq!(errors[0]["path"], "/locked");
+        assert_eq!(errors[7]["path"], "/bad-sector");
+        assert_eq!(
+            errors[7]["message"],
+            "I/O error for /bad-sector: disk error"
+        );
+    }
+
+    #[test]
+    fn test_json_errors_empty_and_streamed() {
+        let config = Config::default();
+        let exit_code = crate::error::ExitCode::Success;
+        let output = JsonOutput::new(&[], &ScanSummary::default(), exit_code, &config);
+        let parsed: serde_json::Value = serde_json::from_str(&output.to_json().unwrap()).unwrap();
+        assert_eq!(parsed["data"]["errors"], serde_json::json!([]));
+
+        let summary = ScanSummary {
+            scan_errors: vec![ScanError::NotFound(PathBuf::from("/gone"))],
+            ..Default::default()
+        };
+        let mut buffer = Vec::new();
+        JsonStreamOutput::new(&[], &summary, exit_code, &config)
+            .with_summary_only(true)
+            .write_to(&mut buffer, false)
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(parsed["data"]["errors"][0]["kind"], "not_found");
+    }
+
     #[test]
     fn test_json_summary_duration() {
         let summary = ScanSummary {
@@ -519,6 +1182,142 @@ mod tests {
         assert_eq!(json_summary.scan_duration_ms, 5000);
     }
 
+    #[test]
+    fn test_json_summary_size_histogram() {
+        let groups = create_test_groups();
+        let summary = ScanSummary {
+            size_histogram: SizeHistogram::from_groups(&groups),
+            ..Default::default()
+        };
+        let output = JsonOutput::new(
+            &groups,
+            &summary,
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&output.to_json().unwrap()).unwrap();
+        let buckets = parsed["data"]["summary"]["size_histogram"]
+            .as_array()
+            .unwrap();
+
+        assert_eq!(
+            buckets.len(),
+            crate::duplicates::SIZE_HISTOGRAM_BUCKETS.len()
+        );
+        assert_eq!(buckets[0]["label"], "0-1KB");
+        assert_eq!(buckets[0]["count"], 0);
+        assert_eq!(buckets[1]["min_size"], 1024);
+        assert_eq!(buckets[1]["max_size"], 1024 * 1024);
+        assert_eq!(buckets[1]["count"], summary.size_histogram.total_count());
+        assert_eq!(
+            buckets[1]["reclaimable_space"],
+            summary.size_histogram.total_reclaimable()
+        );
+        assert!(buckets[3]["max_size"].is_null());
+    }
+
+    #[test]
+    fn test_json_pipeline_reconciles() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let files: [(&str, &[u8]); 7] = [
+            ("unique1", b"a"),
+            ("unique2", b"bbbb"),
+            ("same_size1", b"x123456789"),
+            ("same_size2", b"y123456789"),
+            ("dup1", b"dup content!"),
+            ("dup2", b"dup content!"),
+            ("dup3", b"dup content!"),
+        ];
+        for (name, content) in files {
+            std::fs::write(dir.path().join(name), content).unwrap();
+        }
+        // Same size and first 4KB, different tails: only the full hash tells them apart
+        for (name, tail) in [("tail1", 1u8), ("tail2", 2u8)] {
+            let mut content = vec![0u8; 5000];
+            content[4999] = tail;
+            std::fs::write(dir.path().join(name), content).unwrap();
+        }
+        let (groups, summary) = crate::duplicates::DuplicateFinder::with_defaults()
+            .find_duplicates(dir.path())
+            .unwrap();
+
+        let output = JsonOutput::new(
+            &groups,
+            &summary,
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&output.to_json().unwrap()).unwrap();
+        let pipeline: JsonPipeline =
+            serde_json::from_value(parsed["data"]["pipeline"].clone()).unwrap();
+
+        assert_eq!(pipeline.total_files, 9);
+        assert_eq!(pipeline.confirmed_groups, 1);
+        assert_eq!(pipeline.confirmed_files, 3);
+        assert_eq!(
+            pipeline.total_files
+                - pipeline.eliminated_by_size
+                - pipeline.eliminated_by_name
+                - pipeline.eliminated_by_prehash
+                - pipeline.eliminated_by_fullhash,
+            pipeline.confirmed_files
+        );
+        assert_eq!(pipeline.eliminated_by_size, 2);
+        assert_eq!(pipeline.eliminated_by_prehash, 2);
+        assert_eq!(pipeline.eliminated_by_fullhash, 2);
+        assert_eq!(pipeline.cache_fullhash_hits, 0);
+
+        // Documents written before the pipeline section still parse
+        let mut legacy = parsed.clone();
+        legacy["data"].as_object_mut().unwrap().remove("pipeline");
+        let legacy: JsonOutput = serde_json::from_value(legacy).unwrap();
+        assert_eq!(legacy.data.pipeline, JsonPipeline::default());
+    }
+
+    #[test]
+    fn test_json_summary_reclaimable_by_extension() {
+        let now = std::time::SystemTime::now();
+        let groups = vec![
+            DuplicateGroup::new(
+                [1u8; 32],
+                100,
+                vec![
+                    crate::scanner::FileEntry::new(PathBuf::from("/a/photo.jpg"), 100, now),
+                    crate::scanner::FileEntry::new(PathBuf::from("/b/photo.JPG"), 100, now),
+                    crate::scanner::FileEntry::new(PathBuf::from("/c/photo"), 100, now),
+                ],
+                Vec::new(),
+            ),
+            DuplicateGroup::new(
+                [2u8; 32],
+                40,
+                vec![
+                    crate::scanner::FileEntry::new(PathBuf::from("/a/notes.txt"), 40, now),
+                    crate::scanner::FileEntry::new(PathBuf::from("/b/notes.txt"), 40, now),
+                ],
+                Vec::new(),
+            ),
+        ];
+        let summary = ScanSummary {
+            reclaimable_by_extension: crate::duplicates::reclaimable_by_extension(&groups),
+            ..Default::default()
+        };
+        let output = JsonOutput::new(
+            &groups,
+            &summary,
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&output.to_json().unwrap()).unwrap();
+        let by_ext = &parsed["data"]["summary"]["reclaimable_by_extension"];
+
+        assert_eq!(by_ext["jpg"]["files"], 1);
+        assert_eq!(by_ext["jpg"]["reclaimable_space"], 100);
+        assert_eq!(by_ext["(none)"]["files"], 1);
+        assert_eq!(by_ext["txt"]["reclaimable_space"], 40);
+        assert_eq!(by_ext.as_object().unwrap().len(), 3);
+    }
+
     #[test]
     fn test_json_summary_interrupted() {
         let summary = ScanSummary {
@@ -531,18 +1330,51 @@ mod tests {
             crate::error::ExitCode::Interrupted,
             &Config::default(),
         );
-        assert!(output.summary.interrupted);
-        assert_eq!(output.summary.exit_code, 130);
+        assert!(output.data.summary.interrupted);
+        assert_eq!(output.data.summary.exit_code, 130);
     }
 
     #[test]
-    fn test_metadata_fields() {
+    fn test_json_summary_broken_symlinks() {
+        let summary = ScanSummary {
+            broken_symlinks: vec![PathBuf::from("/data/dangling")],
+            ..Default::default()
+        };
+        let output = JsonOutput::new(
+            &[],
+            &summary,
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&output.to_json().unwrap()).unwrap();
+        assert_eq!(
+            parsed["summary"]["broken_symlinks"],
+            serde_json::json!(["/data/dangling"])
+        );
+    }
+
+    #[test]
+    fn test_envelope_fields() {
         let config = Config::default();
         let summary = ScanSummary::default();
         let output = JsonOutput::new(&[], &summary, crate::error::ExitCode::Success, &config);
 
-        assert_eq!(output.metadata.version, env!("CARGO_PKG_VERSION"));
-        assert_eq!(output.metadata.schema_version, "1.0");
-        assert!(output.metadata.timestamp <= Utc::now());
+        assert_eq!(output.tool_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(output.schema_version, JSON_SCHEMA_VERSION);
+        assert!(output.generated_at <= Utc::now());
+
+        let parsed: serde_json::Value = serde_json::from_str(&output.to_json().unwrap()).unwrap();
+        let mut keys: Vec<&str> = parsed
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            ["data", "generated_at", "schema_version", "tool_version"]
+        );
+        assert_eq!(parsed["schema_version"], JSON_SCHEMA_VERSION);
     }
 }
@@ -0,0 +1,216 @@
+//! Plaintext "keepers report" listing the files that survive deletion.
+//!
+//! For each duplicate group the report names exactly one retained file and
+//! the deleted files it stands in for, together with the group hash and size
+//! so every decision can be traced back to the scan. Selections come from a
+//! loaded session when available; otherwise the default rule used by deletion
+//! scripts applies (keep reference-directory files, or else the first file).
+//!
+//! # Example Output
+//!
+//! ```text
+//! # RustDupe Keepers Report
+//! # 1 group(s), 1 kept, 2 deleted
+//!
+//! [1] hash 00000000... size 1.0 KiB (1024 bytes)
+//!   KEEP:    /data/a/one.bin
+//!   DELETE:  /data/a/two.bin
+//!   DELETE:  /data/b/three.bin
+//! ```
+//!
+//! Files that are neither deleted nor the chosen keeper (for example a second
+//! unselected copy in a session) are listed as `RETAIN:` so nothing that
+//! survives goes unreported.
+
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::duplicates::DuplicateGroup;
+use crate::output::size::SizeFormat;
+
+use super::script::is_deleted_by_default;
+
+/// Formatter for the keepers report.
+pub struct KeepersOutput<'a> {
+    groups: &'a [DuplicateGroup],
+    user_selections: Option<&'a BTreeSet<PathBuf>>,
+    size_format: SizeFormat,
+}
+
+impl<'a> KeepersOutput<'a> {
+    /// Create a keepers report using the default keep-first rule.
+    #[must_use]
+    pub fn new(groups: &'a [DuplicateGroup]) -> Self {
+        Self {
+            groups,
+            user_selections: None,
+            size_format: SizeFormat::default(),
+        }
+    }
+
+    /// Use the files selected for deletion in a session instead of the default rule.
+    #[must_use]
+    pub fn with_user_selections(mut self, selections: &'a BTreeSet<PathBuf>) -> Self {
+        self.user_selections = Some(selections);
+        self
+    }
+
+    /// Set the units for human-readable sizes (`--size-units`).
+    #[must_use]
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    fn is_deleted(&self, group: &DuplicateGroup, index: usize) -> bool {
+        match self.user_selections {
+            Some(selections) => selections.contains(&group.files[index].path),
+            None => is_deleted_by_default(group, index),
+        }
+    }
+
+    /// Render the report to a string.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut body = String::new();
+        let mut kept = 0usize;
+        let mut deleted = 0usize;
+
+        for (idx, group) in self.groups.iter().enumerate() {
+            body.push_str(&format!(
+                "\n[{}] hash {} size {} ({} bytes)\n",
+                idx + 1,
+                group.hash_hex(),
+                self.size_format.format(group.size),
+                group.size
+            ));
+
+            let (removed, survivors): (Vec<usize>, Vec<usize>) =
+                (0..group.files.len()).partition(|&j| self.is_deleted(group, j));
+
+            match survivors.split_first() {
+                Some((&keeper, retained)) => {
+                    kept += 1;
+                    body.push_str(&format!(
+                        "  KEEP:    {}\n",
+                        group.files[keeper].path.display()
+                    ));
+                    for &j in retained {
+                        body.push_str(&format!("  RETAIN:  {}\n", group.files[j].path.display()));
+                    }
+                }
+                None => body.push_str("  KEEP:    (none - every copy is selected)\n"),
+            }
+            for &j in &removed {
+                deleted += 1;
+                body.push_str(&format!("  DELETE:  {}\n", group.files[j].path.display()));
+            }
+        }
+
+        let mut out = String::from("# RustDupe Keepers Report\n");
+        out.push_str(&format!(
+            "# {} group(s), {} kept, {} deleted\n",
+            self.groups.len(),
+            kept,
+            deleted
+        ));
+        out.push_str(&body);
+        out
+    }
+
+    /// Write the report to the given writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the writer fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(self.render().as_bytes())?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use std::time::SystemTime;
+
+    fn make_file(path: &str, size: u64) -> FileEntry {
+        FileEntry::new(PathBuf::from(path), size, SystemTime::UNIX_EPOCH)
+    }
+
+    fn create_test_groups() -> Vec<DuplicateGroup> {
+        vec![
+            DuplicateGroup::new(
+                [0xab; 32],
+                1024,
+                vec![
+                    make_file("/data/a/one.bin", 1024),
+                    make_file("/data/a/two.bin", 1024),
+                    make_file("/data/b/three.bin", 1024),
+                ],
+                Vec::new(),
+            ),
+            DuplicateGroup::new(
+                [0x01; 32],
+                2048,
+                vec![
+                    make_file("/data/c/first.bin", 2048),
+                    make_file("/ref/original.bin", 2048),
+                ],
+                vec![PathBuf::from("/ref")],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_default_keep_first() {
+        let groups = create_test_groups();
+        let report = KeepersOutput::new(&groups).render();
+
+        assert!(report.starts_with("# RustDupe Keepers Report\n"));
+        assert!(report.contains("# 2 group(s), 2 kept, 3 deleted\n"));
+        assert!(report.contains(&format!(
+            "[1] hash {} size 1.0 KiB (1024 bytes)\n  KEEP:    /data/a/one.bin\n  DELETE:  /data/a/two.bin\n  DELETE:  /data/b/three.bin\n",
+            "ab".repeat(32)
+        )));
+        // Reference files win over the first file
+        assert!(report.contains(&format!(
+            "[2] hash {} size 2.0 KiB (2048 bytes)\n  KEEP:    /ref/original.bin\n  DELETE:  /data/c/first.bin\n",
+            "01".repeat(32)
+        )));
+        assert_eq!(report.matches("KEEP:").count(), groups.len());
+    }
+
+    #[test]
+    fn test_session_selections() {
+        let groups = create_test_groups();
+        let selections: BTreeSet<PathBuf> =
+            [PathBuf::from("/data/a/one.bin")].into_iter().collect();
+        let report = KeepersOutput::new(&groups)
+            .with_user_selections(&selections)
+            .render();
+
+        assert!(report.contains("# 2 group(s), 2 kept, 1 deleted\n"));
+        assert!(report.contains(
+            "  KEEP:    /data/a/two.bin\n  RETAIN:  /data/b/three.bin\n  DELETE:  /data/a/one.bin\n"
+        ));
+        // Nothing selected in the second group: the first file is the keeper
+        assert!(report.contains("  KEEP:    /data/c/first.bin\n  RETAIN:  /ref/original.bin\n"));
+        assert!(!report.contains("DELETE:  /data/c/first.bin"));
+    }
+
+    #[test]
+    fn test_every_copy_selected() {
+        let groups = create_test_groups();
+        let selections: BTreeSet<PathBuf> =
+            groups[1].files.iter().map(|f| f.path.clone()).collect();
+        let report = KeepersOutput::new(&groups[1..])
+            .with_user_selections(&selections)
+            .render();
+
+        assert!(report.contains("KEEP:    (none - every copy is selected)"));
+        assert!(report.contains("# 1 group(s), 0 kept, 2 deleted\n"));
+    }
+}
@@ -2,7 +2,20 @@
 //!
 //! This module provides different output formats for scan results:
 //! - JSON for automation and scripting
+//! - YAML for configuration-management pipelines
 //! - CSV for spreadsheet import
+//! - Human-readable text, the default when not run interactively
+//! - Tree-style text for quick terminal triage
+//! - Keepers report listing the file retained in each group
+//! - Compact one-line-per-group output for shell pipelines
+//! - Dedupe plans describing what deletion would keep and free
+//! - Summary-only output for monitoring (`--summary-only`)
+//! - Session statistics for the `stats` subcommand
+//! - Single-file hash reports for the `hash` subcommand
+//!
+//! Byte sizes in every format are rendered by [`SizeFormat::format`]; each
+//! formatter receives the `--size-units` setting from its caller. JSON, YAML and CSV reports can print
+//! paths relative to a base directory via [`paths::report_path`].
 //!
 //! # Example
 //!
@@ -22,13 +35,33 @@
 //! println!("{}", output.to_json_pretty().unwrap());
 //! ```
 
+pub mod compact;
 pub mod csv;
+pub mod hash;
 pub mod html;
 pub mod json;
+pub mod keepers;
+pub mod paths;
+pub mod plan;
 pub mod script;
+pub mod size;
+pub mod stats;
+pub mod summary;
+pub mod text;
+pub mod tree;
+pub mod yaml;
 
 // Re-export main types
+pub use compact::CompactOutput;
 pub use csv::CsvOutput;
+pub use hash::HashReport;
 pub use html::HtmlOutput;
-pub use json::JsonOutput;
+pub use json::{JsonOutput, JsonStreamOutput};
+pub use keepers::KeepersOutput;
+pub use plan::{DedupePlan, PlanGroup, PlanOutput};
 pub use script::{ScriptOutput, ScriptType};
+pub use size::SizeFormat;
+pub use summary::SummaryOutput;
+pub use text::TextOutput;
+pub use tree::TreeOutput;
+pub use yaml::YamlOutput;
@@ -0,0 +1,119 @@
+//! Base-relative file paths for portable reports (`--json-paths-relative`).
+//!
+//! Absolute paths mean nothing on another machine. With a base directory,
+//! JSON, YAML and CSV reports print each file path relative to it. Files
+//! outside the base keep their absolute path, as do files on a different
+//! Windows drive than the base. Only the serialized text changes; the
+//! in-memory paths used for deletion stay absolute.
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::output::paths::report_path;
+//! use std::path::Path;
+//!
+//! let base = Path::new("/data/photos");
+//! assert_eq!(report_path(Path::new("/data/photos/2024/a.jpg"), Some(base)), "2024/a.jpg");
+//! assert_eq!(report_path(Path::new("/home/b.jpg"), Some(base)), "/home/b.jpg");
+//! assert_eq!(report_path(Path::new("/home/b.jpg"), None), "/home/b.jpg");
+//! ```
+
+use std::path::{Path, PathBuf};
+
+/// Resolve a user-supplied base directory the way report paths are resolved.
+///
+/// JSON paths are canonicalized, so the base is too; a base that does not
+/// exist is made absolute instead.
+#[must_use]
+pub fn resolve_base(base: &Path) -> PathBuf {
+    base.canonicalize()
+        .or_else(|_| std::path::absolute(base))
+        .unwrap_or_else(|_| base.to_path_buf())
+}
+
+/// `path` relative to `base`, or `None` if it lies outside the base.
+///
+/// Paths on different drives (`C:` and `D:`) are never related, whatever
+/// platform the report is produced on. The base itself has no relative form.
+#[must_use]
+pub fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+    if drive_letter(path) != drive_letter(base) {
+        return None;
+    }
+    path.strip_prefix(base)
+        .ok()
+        .filter(|rel| !rel.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+}
+
+/// Text to print for `path`: relative to `base` when inside it, as given
+/// otherwise.
+#[must_use]
+pub fn report_path(path: &Path, base: Option<&Path>) -> String {
+    base.and_then(|base| relative_to(path, base))
+        .as_deref()
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Upper-case drive letter of a `C:`-style path, if any.
+///
+/// Checked on the text rather than through `Component::Prefix` so that
+/// Windows paths in a session are recognized on every platform.
+fn drive_letter(path: &Path) -> Option<char> {
+    let text = path.to_string_lossy();
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic() => {
+            Some(letter.to_ascii_uppercase())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_to_inside_and_outside_base() {
+        let base = Path::new("/data/photos");
+        assert_eq!(
+            relative_to(Path::new("/data/photos/2024/a.jpg"), base),
+            Some(PathBuf::from("2024/a.jpg"))
+        );
+        // Sibling directories sharing a name prefix are outside
+        assert_eq!(relative_to(Path::new("/data/photos-old/a.jpg"), base), None);
+        assert_eq!(relative_to(Path::new("/home/a.jpg"), base), None);
+        assert_eq!(relative_to(base, base), None);
+
+        assert_eq!(
+            report_path(Path::new("/home/a.jpg"), Some(base)),
+            "/home/a.jpg"
+        );
+    }
+
+    #[test]
+    fn test_relative_to_cross_drive() {
+        assert_eq!(drive_letter(Path::new("c:/data")), Some('C'));
+        assert_eq!(drive_letter(Path::new("/data")), None);
+
+        let base = Path::new("C:/data");
+        assert_eq!(
+            relative_to(Path::new("C:/data/a.txt"), base),
+            Some(PathBuf::from("a.txt"))
+        );
+        assert_eq!(relative_to(Path::new("D:/data/a.txt"), base), None);
+        assert_eq!(
+            report_path(Path::new("D:/data/a.txt"), Some(base)),
+            "D:/data/a.txt"
+        );
+        // A drive path is never under a driveless base, or vice versa
+        assert_eq!(
+            relative_to(Path::new("C:/data/a.txt"), Path::new("/")),
+            None
+        );
+        assert_eq!(relative_to(Path::new("/data/a.txt"), base), None);
+    }
+}
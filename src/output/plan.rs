@@ -0,0 +1,333 @@
+//! Dedupe plan: a descriptive dry run of what deletion would do.
+//!
+//! For each duplicate group the plan names the files that would be kept and
+//! the files that would be deleted, plus the bytes each deletion frees.
+//! Unlike script output it is not executable, and unlike `--dry-run` it needs
+//! no TUI, so it suits reviews of non-interactive runs.
+//!
+//! Without a [`KeeperStrategy`] the default rule of deletion scripts applies:
+//! keep every file in a reference directory, or else the first file. With a
+//! strategy, the file it picks is kept; files in reference directories are
+//! always kept as well.
+//!
+//! # Example Output
+//!
+//! ```text
+//! # RustDupe Dedupe Plan (keep: oldest)
+//! # 1 group(s), 2 file(s) to delete, 2.0 KiB (2048 bytes) freed
+//!
+//! [0123456789ab] 1.0 KiB x 3
+//!   KEEP:    /data/a/one.bin
+//!   DELETE:  /data/a/two.bin
+//!   DELETE:  /data/b/three.bin
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::duplicates::DuplicateGroup;
+//! use rustdupe::output::PlanOutput;
+//! use rustdupe::scanner::FileEntry;
+//! use std::path::PathBuf;
+//! use std::time::SystemTime;
+//!
+//! let files = ["/a.txt", "/b.txt"]
+//!     .iter()
+//!     .map(|p| FileEntry::new(PathBuf::from(p), 5, SystemTime::UNIX_EPOCH))
+//!     .collect();
+//! let groups = vec![DuplicateGroup::new([0; 32], 5, files, Vec::new())];
+//!
+//! let plan = PlanOutput::new(&groups).plan();
+//! assert_eq!(plan.groups[0].keep, vec![PathBuf::from("/a.txt")]);
+//! assert_eq!(plan.bytes_freed, 5);
+//! ```
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::duplicates::{group_ids, DuplicateGroup};
+use crate::output::size::SizeFormat;
+use crate::tui::app::KeeperStrategy;
+
+use super::script::is_deleted_by_default;
+
+/// Planned outcome for one duplicate group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlanGroup {
+    /// Stable group ID (hash prefix)
+    pub group_id: String,
+    /// Size of each file in bytes
+    pub size: u64,
+    /// Files that would be kept
+    pub keep: Vec<PathBuf>,
+    /// Files that would be deleted
+    pub delete: Vec<PathBuf>,
+    /// Bytes freed by deleting this group's files
+    pub bytes_freed: u64,
+}
+
+/// Planned outcome for every group, with totals.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DedupePlan {
+    /// Rule used to pick keepers
+    pub keeper_rule: String,
+    /// One entry per duplicate group
+    pub groups: Vec<PlanGroup>,
+    /// Number of files that would be deleted
+    pub files_to_delete: usize,
+    /// Total bytes that would be freed
+    pub bytes_freed: u64,
+}
+
+/// Formatter for the dedupe plan.
+pub struct PlanOutput<'a> {
+    groups: &'a [DuplicateGroup],
+    strategy: Option<KeeperStrategy>,
+    size_format: SizeFormat,
+}
+
+impl<'a> PlanOutput<'a> {
+    /// Create a plan using the default keeper rule.
+    #[must_use]
+    pub fn new(groups: &'a [DuplicateGroup]) -> Self {
+        Self {
+            groups,
+            strategy: None,
+            size_format: SizeFormat::default(),
+        }
+    }
+
+    /// Pick keepers with `strategy` instead of the default rule.
+    #[must_use]
+    pub fn with_strategy(mut self, strategy: Option<KeeperStrategy>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set the units for human-readable sizes (`--size-units`).
+    #[must_use]
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    /// Plan one group.
+    fn plan_group(&self, group: &DuplicateGroup, group_id: String) -> PlanGroup {
+        let keeper = self
+            .strategy
+            .as_ref()
+            .map(|s| s.choose_keeper(&group.files));
+        let (mut keep, mut delete) = (Vec::new(), Vec::new());
+        let mut bytes_freed = 0u64;
+        for (index, file) in group.files.iter().enumerate() {
+            let deleted = match keeper {
+                Some(keeper) => index != keeper && !group.is_in_reference_dir(&file.path),
+                None => is_deleted_by_default(group, index),
+            };
+            if deleted {
+                bytes_freed += file.size;
+                delete.push(file.path.clone());
+            } else {
+                keep.push(file.path.clone());
+            }
+        }
+        PlanGroup {
+            group_id,
+            size: group.size,
+            keep,
+            delete,
+            bytes_freed,
+        }
+    }
+
+    /// Build the plan without rendering it.
+    #[must_use]
+    pub fn plan(&self) -> DedupePlan {
+        let groups: Vec<PlanGroup> = self
+            .groups
+            .iter()
+            .zip(group_ids(self.groups))
+            .map(|(g, id)| self.plan_group(g, id))
+            .collect();
+        DedupePlan {
+            keeper_rule: self
+                .strategy
+                .as_ref()
+                .map_or_else(|| "default".to_string(), KeeperStrategy::display_name),
+            files_to_delete: groups.iter().map(|g| g.delete.len()).sum(),
+            bytes_freed: groups.iter().map(|g| g.bytes_freed).sum(),
+            groups,
+        }
+    }
+
+    /// Render the plan as human-readable text.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let plan = self.plan();
+        let mut out = format!(
+            "# RustDupe Dedupe Plan (keep: {})\n# {} group(s), {} file(s) to delete, {} ({} bytes) freed\n",
+            plan.keeper_rule,
+            plan.groups.len(),
+            plan.files_to_delete,
+            self.size_format.format(plan.bytes_freed),
+            plan.bytes_freed
+        );
+        for group in &plan.groups {
+            out.push_str(&format!(
+                "\n[{}] {} x {}\n",
+                group.group_id,
+                self.size_format.format(group.size),
+                group.keep.len() + group.delete.len()
+            ));
+            for path in &group.keep {
+                out.push_str(&format!("  KEEP:    {}\n", path.display()));
+            }
+            for path in &group.delete {
+                out.push_str(&format!("  DELETE:  {}\n", path.display()));
+            }
+        }
+        out
+    }
+
+    /// Write the human-readable plan to the given writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the writer fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(self.render().as_bytes())?;
+        writer.flush()
+    }
+
+    /// Write the plan as a JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing fails.
+    pub fn write_json<W: Write>(&self, writer: &mut W, pretty: bool) -> std::io::Result<()> {
+        let plan = self.plan();
+        if pretty {
+            serde_json::to_writer_pretty(&mut *writer, &plan)?;
+        } else {
+            serde_json::to_writer(&mut *writer, &plan)?;
+        }
+        writeln!(writer)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use std::time::{Duration, SystemTime};
+
+    /// A group of `(path, mtime in seconds after the epoch)` files.
+    fn group(hash: u8, size: u64, files: &[(&str, u64)], refs: &[&str]) -> DuplicateGroup {
+        let files = files
+            .iter()
+            .map(|(p, age)| {
+                FileEntry::new(
+                    PathBuf::from(p),
+                    size,
+                    SystemTime::UNIX_EPOCH + Duration::from_secs(*age),
+                )
+            })
+            .collect();
+        let refs = refs.iter().map(PathBuf::from).collect();
+        DuplicateGroup::new([hash; 32], size, files, refs)
+    }
+
+    fn paths(list: &[&str]) -> Vec<PathBuf> {
+        list.iter().map(PathBuf::from).collect()
+    }
+
+    fn test_groups() -> Vec<DuplicateGroup> {
+        vec![
+            group(
+                1,
+                1000,
+                &[
+                    ("/b/long/copy.bin", 30),
+                    ("/a/one.bin", 10),
+                    ("/c/x.bin", 20),
+                ],
+                &[],
+            ),
+            group(2, 50, &[("/work/n.txt", 5), ("/ref/n.txt", 9)], &["/ref"]),
+        ]
+    }
+
+    #[test]
+    fn test_plan_default_rule() {
+        let plan = PlanOutput::new(&test_groups()).plan();
+        assert_eq!(plan.keeper_rule, "default");
+
+        assert_eq!(plan.groups[0].group_id, "01".repeat(6));
+        assert_eq!(plan.groups[0].keep, paths(&["/b/long/copy.bin"]));
+        assert_eq!(plan.groups[0].delete, paths(&["/a/one.bin", "/c/x.bin"]));
+        assert_eq!(plan.groups[0].bytes_freed, 2000);
+
+        // Reference copies win over the first file
+        assert_eq!(plan.groups[1].keep, paths(&["/ref/n.txt"]));
+        assert_eq!(plan.groups[1].delete, paths(&["/work/n.txt"]));
+        assert_eq!(plan.groups[1].bytes_freed, 50);
+
+        assert_eq!(plan.files_to_delete, 3);
+        assert_eq!(plan.bytes_freed, 2050);
+    }
+
+    #[test]
+    fn test_plan_with_strategy() {
+        let groups = test_groups();
+        let plan = PlanOutput::new(&groups)
+            .with_strategy(Some(KeeperStrategy::Oldest))
+            .plan();
+        assert_eq!(plan.keeper_rule, "oldest");
+        assert_eq!(plan.groups[0].keep, paths(&["/a/one.bin"]));
+        assert_eq!(
+            plan.groups[0].delete,
+            paths(&["/b/long/copy.bin", "/c/x.bin"])
+        );
+        // The oldest file is not in the reference directory: both are kept
+        assert_eq!(plan.groups[1].keep, paths(&["/work/n.txt", "/ref/n.txt"]));
+        assert!(plan.groups[1].delete.is_empty());
+        assert_eq!(plan.files_to_delete, 2);
+        assert_eq!(plan.bytes_freed, 2000);
+
+        let plan = PlanOutput::new(&groups)
+            .with_strategy(Some(KeeperStrategy::ShortestPath))
+            .plan();
+        assert_eq!(plan.groups[0].keep, paths(&["/c/x.bin"]));
+        assert_eq!(plan.bytes_freed, 2050);
+    }
+
+    #[test]
+    fn test_plan_render_and_json() {
+        let groups = test_groups();
+        let output = PlanOutput::new(&groups);
+        let text = output.render();
+        assert!(text.starts_with("# RustDupe Dedupe Plan (keep: default)\n"));
+        assert!(text.contains("3 file(s) to delete"));
+        assert!(text.contains("(2050 bytes) freed"));
+        assert!(text.contains(&format!("\n[{}] ", "02".repeat(6))));
+        assert!(text.contains("  KEEP:    /ref/n.txt\n  DELETE:  /work/n.txt\n"));
+
+        let mut json = Vec::new();
+        output.write_json(&mut json, false).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(value["bytes_freed"], 2050);
+        assert_eq!(value["files_to_delete"], 3);
+        assert_eq!(value["groups"][1]["keep"][0], "/ref/n.txt");
+
+        assert_eq!(
+            PlanOutput::new(&[]).plan(),
+            DedupePlan {
+                keeper_rule: "default".to_string(),
+                ..DedupePlan::default()
+            }
+        );
+    }
+}
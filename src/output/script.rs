@@ -10,6 +10,10 @@
 //! * **Robust Escaping**: Handles spaces, quotes, and special characters in file paths.
 //! * **Informative**: Includes comments with file hashes, sizes, and group info.
 //! * **Summary**: Displays total deleted count and reclaimed space upon completion.
+//! * **Empty Directories**: Optionally removes folders left empty, bounded by the scan roots.
+//! * **Size Cap**: Optionally refuses to run, or stops, once deletions would exceed a byte cap.
+//! * **Failure Report**: Prints a `FAILED: <path>` line for each file it could not delete
+//!   and exits non-zero, so [`run_script`](crate::actions::script_run::run_script) can report them.
 //!
 //! # Usage
 //!
@@ -24,8 +28,17 @@ use std::collections::BTreeSet;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use crate::actions::cleanup::{candidate_dirs, cleanup_order};
 use crate::duplicates::{DuplicateGroup, ScanSummary};
 
+use super::size::SizeFormat;
+
+/// Word a script prints before the path of each file it could not delete.
+///
+/// The shell separates it from the path with a space, giving lines like
+/// `FAILED: /path/to/file`.
+pub const FAILED_MARKER: &str = "FAILED:";
+
 /// Type of script to generate.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScriptType {
@@ -57,6 +70,12 @@ pub struct ScriptOutput<'a> {
     pub script_type: ScriptType,
     /// Optional user selections from a session
     pub user_selections: Option<&'a BTreeSet<PathBuf>>,
+    /// Scan roots bounding empty-directory removal (None if disabled)
+    pub empty_dir_roots: Option<&'a [PathBuf]>,
+    /// Maximum total bytes the script may delete (None if uncapped)
+    pub max_total_bytes: Option<u64>,
+    /// Units for sizes in the script's comments
+    pub size_format: SizeFormat,
 }
 
 impl<'a> ScriptOutput<'a> {
@@ -72,9 +91,19 @@ impl<'a> ScriptOutput<'a> {
             summary,
             script_type,
             user_selections: None,
+            empty_dir_roots: None,
+            max_total_bytes: None,
+            size_format: SizeFormat::default(),
         }
     }
 
+    /// Set the units for sizes in comments (`--size-units`).
+    #[must_use]
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
     /// Set user selections for the script.
     #[must_use]
     pub fn with_user_selections(mut self, selections: &'a BTreeSet<PathBuf>) -> Self {
@@ -82,6 +111,63 @@ impl<'a> ScriptOutput<'a> {
         self
     }
 
+    /// Remove directories left empty by the script, stopping at `roots`.
+    ///
+    /// Reference directories are never included.
+    #[must_use]
+    pub fn with_empty_dir_cleanup(mut self, roots: &'a [PathBuf]) -> Self {
+        self.empty_dir_roots = Some(roots);
+        self
+    }
+
+    /// Refuse to run, or stop, once deletions would exceed `bytes` in total.
+    #[must_use]
+    pub fn with_max_total_bytes(mut self, bytes: Option<u64>) -> Self {
+        self.max_total_bytes = bytes;
+        self
+    }
+
+    /// Total size of the files the script deletes.
+    fn planned_bytes(&self) -> u64 {
+        self.groups
+            .iter()
+            .map(|group| {
+                let deleted = (0..group.files.len())
+                    .filter(|&j| self.should_delete(group, j))
+                    .count() as u64;
+                deleted * group.size
+            })
+            .sum()
+    }
+
+    /// Decide whether the file at `index` in `group` is deleted by the script.
+    fn should_delete(&self, group: &DuplicateGroup, index: usize) -> bool {
+        match self.user_selections {
+            Some(selections) => selections.contains(&group.files[index].path),
+            None => is_deleted_by_default(group, index),
+        }
+    }
+
+    /// Directories that may be left empty by the script, deepest first.
+    fn empty_dir_candidates(&self) -> Vec<PathBuf> {
+        let Some(roots) = self.empty_dir_roots else {
+            return Vec::new();
+        };
+
+        let mut deleted = Vec::new();
+        let mut protected = Vec::new();
+        for group in self.groups {
+            protected.extend(group.reference_paths.iter().cloned());
+            deleted.extend(
+                (0..group.files.len())
+                    .filter(|&j| self.should_delete(group, j))
+                    .map(|j| group.files[j].path.clone()),
+            );
+        }
+
+        cleanup_order(&candidate_dirs(&deleted, &protected), roots)
+    }
+
     /// Write the generated script to a writer.
     ///
     /// # Errors
@@ -117,7 +203,7 @@ impl<'a> ScriptOutput<'a> {
         writeln!(
             writer,
             "# Reclaimable space: {}",
-            bytesize::ByteSize::b(self.summary.reclaimable_space)
+            self.size_format.format(self.summary.reclaimable_space)
         )?;
         writeln!(writer)?;
 
@@ -141,8 +227,27 @@ impl<'a> ScriptOutput<'a> {
         writeln!(writer, "fi")?;
         writeln!(writer)?;
 
+        if let Some(cap) = self.max_total_bytes {
+            let planned = self.planned_bytes();
+            writeln!(
+                writer,
+                "# Refuse to run if the deletions exceed the --max-total-delete cap"
+            )?;
+            writeln!(writer, "MAX_TOTAL_BYTES={}", cap)?;
+            writeln!(writer, "if [ {} -gt \"$MAX_TOTAL_BYTES\" ]; then", planned)?;
+            writeln!(
+                writer,
+                "    echo \"Refusing to delete {} bytes: exceeds the cap of $MAX_TOTAL_BYTES bytes\" >&2",
+                planned
+            )?;
+            writeln!(writer, "    exit 1")?;
+            writeln!(writer, "fi")?;
+            writeln!(writer)?;
+        }
+
         writeln!(writer, "DELETED_COUNT=0")?;
         writeln!(writer, "RECLAIMED_BYTES=0")?;
+        writeln!(writer, "FAILED_COUNT=0")?;
         writeln!(writer)?;
 
         for (i, group) in self.groups.iter().enumerate() {
@@ -151,39 +256,40 @@ impl<'a> ScriptOutput<'a> {
                 "# Group {}: Hash {}, Size {}",
                 i + 1,
                 group.hash_hex(),
-                bytesize::ByteSize::b(group.size)
+                self.size_format.format(group.size)
             )?;
 
             let mut group_has_deletion = false;
             for (j, file) in group.files.iter().enumerate() {
                 let path_str = escape_posix(&file.path);
-                let should_delete = if let Some(selections) = self.user_selections {
-                    selections.contains(&file.path)
-                } else {
-                    // Default logic: keep reference files and the first file if no reference files exist
-                    let has_ref_in_group = group
-                        .files
-                        .iter()
-                        .any(|f| group.is_in_reference_dir(&f.path));
-                    if has_ref_in_group {
-                        // Keep ALL reference files, delete others
-                        !group.is_in_reference_dir(&file.path)
-                    } else {
-                        // No reference files, keep first, delete others
-                        j > 0
-                    }
-                };
-
-                if should_delete {
+                if self.should_delete(group, j) {
                     writeln!(writer, "# DELETE: {}", path_str)?;
                     writeln!(writer, "if [ \"$DRY_RUN\" -eq 0 ]; then")?;
+                    if self.max_total_bytes.is_some() {
+                        writeln!(
+                            writer,
+                            "    if [ $((RECLAIMED_BYTES + {})) -gt \"$MAX_TOTAL_BYTES\" ]; then",
+                            group.size
+                        )?;
+                        writeln!(
+                            writer,
+                            "        echo \"Stopping: the cap of $MAX_TOTAL_BYTES bytes would be exceeded\" >&2"
+                        )?;
+                        writeln!(writer, "        exit 1")?;
+                        writeln!(writer, "    fi")?;
+                    }
                     writeln!(writer, "    rm {} && \\", path_str)?;
                     writeln!(writer, "    DELETED_COUNT=$((DELETED_COUNT + 1)) && \\")?;
                     writeln!(
                         writer,
-                        "    RECLAIMED_BYTES=$((RECLAIMED_BYTES + {}))",
+                        "    RECLAIMED_BYTES=$((RECLAIMED_BYTES + {})) || \\",
                         group.size
                     )?;
+                    writeln!(
+                        writer,
+                        "    {{ echo \"{}\" {} >&2; FAILED_COUNT=$((FAILED_COUNT + 1)); }}",
+                        FAILED_MARKER, path_str
+                    )?;
                     writeln!(writer, "else")?;
                     writeln!(writer, "    echo \"would delete: {}\"", path_str)?;
                     writeln!(writer, "fi")?;
@@ -197,6 +303,28 @@ impl<'a> ScriptOutput<'a> {
             }
         }
 
+        let empty_dirs = self.empty_dir_candidates();
+        if !empty_dirs.is_empty() {
+            writeln!(
+                writer,
+                "# Remove directories left empty (deepest first; rmdir skips non-empty ones)"
+            )?;
+            writeln!(writer, "if [ \"$DRY_RUN\" -eq 0 ]; then")?;
+            for dir in &empty_dirs {
+                writeln!(writer, "    rmdir {} 2>/dev/null", escape_posix(dir))?;
+            }
+            writeln!(writer, "else")?;
+            for dir in &empty_dirs {
+                writeln!(
+                    writer,
+                    "    echo \"would remove if empty: {}\"",
+                    escape_posix(dir)
+                )?;
+            }
+            writeln!(writer, "fi")?;
+            writeln!(writer)?;
+        }
+
         writeln!(writer, "if [ \"$DRY_RUN\" -eq 0 ]; then")?;
         writeln!(
             writer,
@@ -209,6 +337,15 @@ impl<'a> ScriptOutput<'a> {
             "    echo \"Dry run complete. No files were deleted.\""
         )?;
         writeln!(writer, "fi")?;
+        writeln!(writer)?;
+
+        writeln!(writer, "if [ \"$FAILED_COUNT\" -gt 0 ]; then")?;
+        writeln!(
+            writer,
+            "    echo \"Failed to delete $FAILED_COUNT files.\" >&2"
+        )?;
+        writeln!(writer, "    exit 1")?;
+        writeln!(writer, "fi")?;
 
         Ok(())
     }
@@ -235,7 +372,7 @@ impl<'a> ScriptOutput<'a> {
         writeln!(
             writer,
             "# Reclaimable space: {}",
-            bytesize::ByteSize::b(self.summary.reclaimable_space)
+            self.size_format.format(self.summary.reclaimable_space)
         )?;
         writeln!(writer)?;
 
@@ -259,8 +396,27 @@ impl<'a> ScriptOutput<'a> {
         writeln!(writer, "}}")?;
         writeln!(writer)?;
 
+        if let Some(cap) = self.max_total_bytes {
+            let planned = self.planned_bytes();
+            writeln!(
+                writer,
+                "# Refuse to run if the deletions exceed the --max-total-delete cap"
+            )?;
+            writeln!(writer, "$MaxTotalBytes = {}", cap)?;
+            writeln!(writer, "if ({} -gt $MaxTotalBytes) {{", planned)?;
+            writeln!(
+                writer,
+                "    Write-Host \"Refusing to delete {} bytes: exceeds the cap of $MaxTotalBytes bytes\"",
+                planned
+            )?;
+            writeln!(writer, "    exit 1")?;
+            writeln!(writer, "}}")?;
+            writeln!(writer)?;
+        }
+
         writeln!(writer, "$DeletedCount = 0")?;
         writeln!(writer, "$ReclaimedBytes = 0")?;
+        writeln!(writer, "$FailedCount = 0")?;
         writeln!(writer)?;
 
         for (i, group) in self.groups.iter().enumerate() {
@@ -269,32 +425,28 @@ impl<'a> ScriptOutput<'a> {
                 "# Group {}: Hash {}, Size {}",
                 i + 1,
                 group.hash_hex(),
-                bytesize::ByteSize::b(group.size)
+                self.size_format.format(group.size)
             )?;
 
             let mut group_has_deletion = false;
             for (j, file) in group.files.iter().enumerate() {
                 let path_str = escape_powershell(&file.path);
-                let should_delete = if let Some(selections) = self.user_selections {
-                    selections.contains(&file.path)
-                } else {
-                    // Default logic: keep reference files and the first file if no reference files exist
-                    let has_ref_in_group = group
-                        .files
-                        .iter()
-                        .any(|f| group.is_in_reference_dir(&f.path));
-                    if has_ref_in_group {
-                        // Keep ALL reference files, delete others
-                        !group.is_in_reference_dir(&file.path)
-                    } else {
-                        // No reference files, keep first, delete others
-                        j > 0
-                    }
-                };
-
-                if should_delete {
+                if self.should_delete(group, j) {
                     writeln!(writer, "# DELETE: {}", path_str)?;
                     writeln!(writer, "if (-not $DryRun) {{")?;
+                    if self.max_total_bytes.is_some() {
+                        writeln!(
+                            writer,
+                            "    if ($ReclaimedBytes + {} -gt $MaxTotalBytes) {{",
+                            group.size
+                        )?;
+                        writeln!(
+                            writer,
+                            "        Write-Host \"Stopping: the cap of $MaxTotalBytes bytes would be exceeded\""
+                        )?;
+                        writeln!(writer, "        exit 1")?;
+                        writeln!(writer, "    }}")?;
+                    }
                     writeln!(
                         writer,
                         "    Remove-Item -Path {} -ErrorAction SilentlyContinue",
@@ -303,6 +455,13 @@ impl<'a> ScriptOutput<'a> {
                     writeln!(writer, "    if ($?) {{")?;
                     writeln!(writer, "        $DeletedCount++")?;
                     writeln!(writer, "        $ReclaimedBytes += {}", group.size)?;
+                    writeln!(writer, "    }} else {{")?;
+                    writeln!(
+                        writer,
+                        "        Write-Host \"{}\" {}",
+                        FAILED_MARKER, path_str
+                    )?;
+                    writeln!(writer, "        $FailedCount++")?;
                     writeln!(writer, "    }}")?;
                     writeln!(writer, "}} else {{")?;
                     writeln!(writer, "    Write-Host \"would delete: {}\"", path_str)?;
@@ -317,6 +476,33 @@ impl<'a> ScriptOutput<'a> {
             }
         }
 
+        let empty_dirs = self.empty_dir_candidates();
+        if !empty_dirs.is_empty() {
+            writeln!(
+                writer,
+                "# Remove directories left empty (deepest first; non-empty ones are kept)"
+            )?;
+            writeln!(writer, "if (-not $DryRun) {{")?;
+            for dir in &empty_dirs {
+                let dir_str = escape_powershell(dir);
+                writeln!(
+                    writer,
+                    "    if ((Test-Path -LiteralPath {0}) -and -not (Get-ChildItem -LiteralPath {0} -Force)) {{ Remove-Item -LiteralPath {0} }}",
+                    dir_str
+                )?;
+            }
+            writeln!(writer, "}} else {{")?;
+            for dir in &empty_dirs {
+                writeln!(
+                    writer,
+                    "    Write-Host \"would remove if empty: {}\"",
+                    escape_powershell(dir)
+                )?;
+            }
+            writeln!(writer, "}}")?;
+            writeln!(writer)?;
+        }
+
         writeln!(writer, "if (-not $DryRun) {{")?;
         writeln!(
             writer,
@@ -331,12 +517,39 @@ impl<'a> ScriptOutput<'a> {
             writer,
             "    Write-Host \"Dry run complete. No files were deleted.\""
         )?;
-        writeln!(writer, "fi")?;
+        writeln!(writer, "}}")?;
+        writeln!(writer)?;
+
+        writeln!(writer, "if ($FailedCount -gt 0) {{")?;
+        writeln!(
+            writer,
+            "    Write-Host \"Failed to delete $FailedCount files.\""
+        )?;
+        writeln!(writer, "    exit 1")?;
+        writeln!(writer, "}}")?;
 
         Ok(())
     }
 }
 
+/// Default deletion rule when no user selections are available.
+///
+/// Keeps every file in a reference directory if the group has any, otherwise
+/// keeps the first file; all other files are deleted.
+pub(crate) fn is_deleted_by_default(group: &DuplicateGroup, index: usize) -> bool {
+    let has_ref_in_group = group
+        .files
+        .iter()
+        .any(|f| group.is_in_reference_dir(&f.path));
+    if has_ref_in_group {
+        // Keep ALL reference files, delete others
+        !group.is_in_reference_dir(&group.files[index].path)
+    } else {
+        // No reference files, keep first, delete others
+        index > 0
+    }
+}
+
 fn escape_posix(path: &Path) -> String {
     let s = path.to_string_lossy();
     // Wrap in single quotes, escape single quotes as '\''
@@ -494,6 +707,7 @@ mod tests {
         assert!(script.contains("DRY_RUN=1"));
         assert!(script.contains("would delete: '/test/file2.txt'"));
         assert!(script.contains("rm '/test/file2.txt'"));
+        assert!(script.contains("{ echo \"FAILED:\" '/test/file2.txt' >&2;"));
         assert!(script.contains("# KEEP:   '/test/file1.txt'"));
         assert!(script.contains("--confirm"));
     }
@@ -509,8 +723,10 @@ mod tests {
         assert!(script.contains("$DryRun = $true"));
         assert!(script.contains("would delete: '/test/file2.txt'"));
         assert!(script.contains("Remove-Item -Path '/test/file2.txt'"));
+        assert!(script.contains("Write-Host \"FAILED:\" '/test/file2.txt'"));
         assert!(script.contains("# KEEP:   '/test/file1.txt'"));
         assert!(script.contains("--confirm"));
+        assert!(!script.contains("\nfi\n"));
     }
 
     #[test]
@@ -582,4 +798,92 @@ mod tests {
         assert!(script2.contains("# KEEP:   '/ref/original.txt'"));
         assert!(script2.contains("# DELETE: '/tmp/dupe2.txt'"));
     }
+
+    #[test]
+    fn test_empty_dir_cleanup() {
+        let now = SystemTime::now();
+        let ref_path = PathBuf::from("/scan/ref/a/original.txt");
+        let groups = vec![DuplicateGroup::new(
+            [2u8; 32],
+            100,
+            vec![
+                FileEntry::new(ref_path.clone(), 100, now),
+                FileEntry::new(PathBuf::from("/scan/old/nested/copy.txt"), 100, now),
+                FileEntry::new(PathBuf::from("/scan/top.txt"), 100, now),
+            ],
+            vec![PathBuf::from("/scan/ref")],
+        )];
+        let summary = ScanSummary::default();
+        let roots = vec![PathBuf::from("/scan")];
+
+        // Not emitted unless requested
+        let mut buffer = Vec::new();
+        ScriptOutput::new(&groups, &summary, ScriptType::Posix)
+            .write_to(&mut buffer)
+            .unwrap();
+        assert!(!String::from_utf8(buffer).unwrap().contains("rmdir"));
+
+        let mut buffer = Vec::new();
+        ScriptOutput::new(&groups, &summary, ScriptType::Posix)
+            .with_empty_dir_cleanup(&roots)
+            .write_to(&mut buffer)
+            .unwrap();
+        let script = String::from_utf8(buffer).unwrap();
+
+        let nested = script.find("rmdir '/scan/old/nested' 2>/dev/null").unwrap();
+        let parent = script.find("rmdir '/scan/old' 2>/dev/null").unwrap();
+        assert!(nested < parent, "deepest directories must come first");
+        assert!(script.contains("echo \"would remove if empty: '/scan/old'\""));
+        // Never the scan root or reference directories
+        assert!(!script.contains("rmdir '/scan' "));
+        assert!(!script.contains("rmdir '/scan/ref"));
+
+        let mut buffer = Vec::new();
+        ScriptOutput::new(&groups, &summary, ScriptType::PowerShell)
+            .with_empty_dir_cleanup(&roots)
+            .write_to(&mut buffer)
+            .unwrap();
+        let script = String::from_utf8(buffer).unwrap();
+        assert!(script.contains("Remove-Item -LiteralPath '/scan/old/nested'"));
+        assert!(script.contains("Write-Host \"would remove if empty: '/scan/old'\""));
+        assert!(!script.contains("'/scan/ref"));
+    }
+
+    #[test]
+    fn test_script_max_total_bytes_guard() {
+        let (groups, summary) = setup_test_data();
+
+        let mut buffer = Vec::new();
+        ScriptOutput::new(&groups, &summary, ScriptType::Posix)
+            .write_to(&mut buffer)
+            .unwrap();
+        assert!(!String::from_utf8(buffer)
+            .unwrap()
+            .contains("MAX_TOTAL_BYTES"));
+
+        let mut buffer = Vec::new();
+        ScriptOutput::new(&groups, &summary, ScriptType::Posix)
+            .with_max_total_bytes(Some(1000))
+            .write_to(&mut buffer)
+            .unwrap();
+        let script = String::from_utf8(buffer).unwrap();
+        let guard = script
+            .find("if [ 1024 -gt \"$MAX_TOTAL_BYTES\" ]; then")
+            .unwrap();
+        assert!(guard < script.find("rm '/test/file2.txt'").unwrap());
+        assert!(script.contains("MAX_TOTAL_BYTES=1000"));
+        assert!(
+            script.contains("if [ $((RECLAIMED_BYTES + 1024)) -gt \"$MAX_TOTAL_BYTES\" ]; then")
+        );
+
+        let mut buffer = Vec::new();
+        ScriptOutput::new(&groups, &summary, ScriptType::PowerShell)
+            .with_max_total_bytes(Some(1000))
+            .write_to(&mut buffer)
+            .unwrap();
+        let script = String::from_utf8(buffer).unwrap();
+        assert!(script.contains("$MaxTotalBytes = 1000"));
+        assert!(script.contains("if (1024 -gt $MaxTotalBytes) {"));
+        assert!(script.contains("if ($ReclaimedBytes + 1024 -gt $MaxTotalBytes) {"));
+    }
 }
@@ -0,0 +1,149 @@
+//! Human-readable byte sizes shared by every output format.
+//!
+//! All size labels in RustDupe (TUI, text reports, HTML, scripts and the scan
+//! summary) go through [`SizeFormat::format`]. The `--size-units` setting is
+//! passed to each formatter explicitly (usually as a `with_size_format`
+//! builder), so one setting changes them consistently. Three unit systems
+//! are supported:
+//!
+//! | [`SizeFormat`]   | Base | Labels                 |
+//! |------------------|------|------------------------|
+//! | `IecProper`      | 1024 | `KiB`, `MiB`, `GiB`, … |
+//! | `Iec`            | 1024 | `KB`, `MB`, `GB`, …    |
+//! | `Si`             | 1000 | `kB`, `MB`, `GB`, …    |
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::output::size::{format_size_as, SizeFormat};
+//!
+//! assert_eq!(format_size_as(1536, SizeFormat::IecProper), "1.5 KiB");
+//! assert_eq!(format_size_as(1536, SizeFormat::Iec), "1.5 KB");
+//! assert_eq!(format_size_as(1536, SizeFormat::Si), "1.5 kB");
+//! ```
+
+/// Unit system used when formatting byte sizes.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    clap::ValueEnum,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum SizeFormat {
+    /// 1024-based units with IEC labels (KiB, MiB, ...) (default)
+    #[default]
+    IecProper,
+    /// 1024-based units with traditional labels (KB, MB, ...)
+    Iec,
+    /// 1000-based SI units (kB, MB, ...)
+    Si,
+}
+
+impl SizeFormat {
+    /// Size of one unit step (1024 or 1000).
+    #[must_use]
+    pub fn base(self) -> u64 {
+        match self {
+            Self::IecProper | Self::Iec => 1024,
+            Self::Si => 1000,
+        }
+    }
+
+    /// Labels for each unit above bytes, smallest first.
+    fn labels(self) -> [&'static str; 6] {
+        match self {
+            Self::IecProper => ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"],
+            Self::Iec => ["KB", "MB", "GB", "TB", "PB", "EB"],
+            Self::Si => ["kB", "MB", "GB", "TB", "PB", "EB"],
+        }
+    }
+
+    /// Format `bytes` in this unit system.
+    #[must_use]
+    pub fn format(self, bytes: u64) -> String {
+        let base = self.base();
+        if bytes < base {
+            return format!("{} B", bytes);
+        }
+
+        let mut unit = base;
+        let mut index = 0;
+        while index + 1 < self.labels().len() && bytes / unit >= base {
+            unit *= base;
+            index += 1;
+        }
+        format!("{:.1} {}", bytes as f64 / unit as f64, self.labels()[index])
+    }
+}
+
+impl std::fmt::Display for SizeFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IecProper => write!(f, "iec-proper"),
+            Self::Iec => write!(f, "iec"),
+            Self::Si => write!(f, "si"),
+        }
+    }
+}
+
+/// Format `bytes` using an explicit unit system.
+#[must_use]
+pub fn format_size_as(bytes: u64, format: SizeFormat) -> String {
+    format.format(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iec_proper_boundaries() {
+        let f = SizeFormat::IecProper;
+        assert_eq!(f.format(0), "0 B");
+        assert_eq!(f.format(1023), "1023 B");
+        assert_eq!(f.format(1024), "1.0 KiB");
+        assert_eq!(f.format(1024 * 1024 - 1), "1024.0 KiB");
+        assert_eq!(f.format(1024 * 1024), "1.0 MiB");
+        assert_eq!(f.format(1024u64.pow(3)), "1.0 GiB");
+        assert_eq!(f.format(1024u64.pow(4)), "1.0 TiB");
+        assert_eq!(f.format(u64::MAX), "16.0 EiB");
+    }
+
+    #[test]
+    fn test_iec_boundaries() {
+        let f = SizeFormat::Iec;
+        assert_eq!(f.format(1023), "1023 B");
+        assert_eq!(f.format(1024), "1.0 KB");
+        assert_eq!(f.format(1536), "1.5 KB");
+        assert_eq!(f.format(1024 * 1024), "1.0 MB");
+        assert_eq!(f.format(1024u64.pow(3)), "1.0 GB");
+        assert_eq!(f.format(1024u64.pow(4)), "1.0 TB");
+    }
+
+    #[test]
+    fn test_si_boundaries() {
+        let f = SizeFormat::Si;
+        assert_eq!(f.format(999), "999 B");
+        assert_eq!(f.format(1000), "1.0 kB");
+        assert_eq!(f.format(1024), "1.0 kB");
+        assert_eq!(f.format(999_999), "1000.0 kB");
+        assert_eq!(f.format(1_000_000), "1.0 MB");
+        assert_eq!(f.format(1_500_000_000), "1.5 GB");
+        assert_eq!(f.format(1_000_000_000_000), "1.0 TB");
+    }
+
+    #[test]
+    fn test_display_matches_value_names() {
+        use clap::ValueEnum;
+        for format in SizeFormat::value_variants() {
+            let name = format.to_possible_value().unwrap();
+            assert_eq!(name.get_name(), format.to_string());
+        }
+    }
+}
@@ -0,0 +1,473 @@
+//! Statistics re-derived from a saved session (`rustdupe stats`).
+//!
+//! Recomputes the headline totals from the session's duplicate groups and
+//! breaks them down without rescanning:
+//! - Reclaimable space by [`FileCategory`] (plus `other`)
+//! - The largest groups by reclaimable space
+//! - How many groups have 2, 3, 4, ... files
+//! - File counts by extension
+//!
+//! Reclaimable space follows [`DuplicateGroup::wasted_space`]: every file
+//! except the first in a group counts, attributed to that file's category.
+//!
+//! # Example Output
+//!
+//! ```text
+//! Groups:           2
+//! Files:            5
+//! Duplicate files:  3
+//! Total size:       7.0 KiB
+//! Reclaimable:      4.0 KiB (57.1%)
+//!
+//! Reclaimable by category:
+//!   images          3.0 KiB  (3 files)
+//!   documents       1.0 KiB  (2 files)
+//!
+//! Largest groups:
+//!   1. 3.0 KiB  3 files  /photos/a.jpg
+//!   2. 1.0 KiB  2 files  /docs/a.txt
+//!
+//! Group sizes:
+//!   2 files: 1 group(s)
+//!   3 files: 1 group(s)
+//!
+//! Files by extension:
+//!   jpg          3
+//!   txt          2
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use super::size::SizeFormat;
+use crate::duplicates::DuplicateGroup;
+use crate::scanner::FileCategory;
+use crate::session::Session;
+
+/// Label used for files that belong to no [`FileCategory`].
+const OTHER_CATEGORY: &str = "other";
+
+/// Label used for files without an extension.
+const NO_EXTENSION: &str = "(none)";
+
+/// Files and reclaimable space for one file category.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CategoryStats {
+    /// Category name (`images`, `videos`, ..., or `other`)
+    pub category: String,
+    /// Number of files in this category
+    pub files: usize,
+    /// Bytes freed by deleting every non-first copy in this category
+    pub reclaimable_space: u64,
+}
+
+/// One of the largest duplicate groups.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GroupStats {
+    /// BLAKE3 hash (hex)
+    pub hash: String,
+    /// Number of files in the group
+    pub files: usize,
+    /// Reclaimable space in bytes
+    pub reclaimable_space: u64,
+    /// First file of the group (the one kept by default)
+    pub first_path: PathBuf,
+}
+
+/// Number of groups holding a given number of files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GroupSizeBucket {
+    /// Files per group
+    pub files: usize,
+    /// Groups with exactly that many files
+    pub groups: usize,
+}
+
+/// Number of files with a given extension.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExtensionCount {
+    /// Lowercase extension without the dot, or `(none)`
+    pub extension: String,
+    /// Number of files
+    pub files: usize,
+}
+
+/// Statistics recomputed from a session's duplicate groups.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionStats {
+    /// Number of duplicate groups
+    pub groups: usize,
+    /// Number of files across all groups
+    pub files: usize,
+    /// Number of duplicate copies (files minus one per group)
+    pub duplicate_files: usize,
+    /// Combined size of every file in every group
+    pub total_size: u64,
+    /// Space freed by keeping one file per group
+    pub reclaimable_space: u64,
+    /// Files selected for deletion in the session
+    pub selected_files: usize,
+    /// Reclaimable space by category, largest first
+    pub by_category: Vec<CategoryStats>,
+    /// Largest groups by reclaimable space
+    pub largest_groups: Vec<GroupStats>,
+    /// Group counts by number of files, smallest groups first
+    pub group_sizes: Vec<GroupSizeBucket>,
+    /// File counts by extension, most common first
+    pub extensions: Vec<ExtensionCount>,
+}
+
+impl SessionStats {
+    /// Compute statistics for a loaded session.
+    ///
+    /// At most `top` groups are listed in [`largest_groups`](Self::largest_groups).
+    #[must_use]
+    pub fn from_session(session: &Session, top: usize) -> Self {
+        let (groups, _) = session.to_results();
+        let mut stats = Self::from_groups(&groups, top);
+        stats.selected_files = session.user_selections.len();
+        stats
+    }
+
+    /// Compute statistics for a set of duplicate groups.
+    #[must_use]
+    pub fn from_groups(groups: &[DuplicateGroup], top: usize) -> Self {
+        let mut categories: HashMap<String, CategoryStats> = HashMap::new();
+        let mut extensions: HashMap<String, usize> = HashMap::new();
+        let mut group_sizes: BTreeMap<usize, usize> = BTreeMap::new();
+
+        for group in groups {
+            *group_sizes.entry(group.files.len()).or_default() += 1;
+
+            for (index, file) in group.files.iter().enumerate() {
+                let category = FileCategory::from_path(&file.path)
+                    .map_or_else(|| OTHER_CATEGORY.to_string(), |c| c.to_string());
+                let entry = categories
+                    .entry(category.clone())
+                    .or_insert_with(|| CategoryStats {
+                        category,
+                        files: 0,
+                        reclaimable_space: 0,
+                    });
+                entry.files += 1;
+                if index > 0 {
+                    entry.reclaimable_space += file.size;
+                }
+
+                let extension = file
+                    .path
+                    .extension()
+                    .and_then(|s| s.to_str())
+                    .map_or_else(|| NO_EXTENSION.to_string(), str::to_lowercase);
+                *extensions.entry(extension).or_default() += 1;
+            }
+        }
+
+        let mut by_category: Vec<CategoryStats> = categories.into_values().collect();
+        by_category.sort_by(|a, b| {
+            b.reclaimable_space
+                .cmp(&a.reclaimable_space)
+                .then_with(|| a.category.cmp(&b.category))
+        });
+
+        let mut largest: Vec<&DuplicateGroup> = groups.iter().collect();
+        // Stable sort keeps session order among equally sized groups
+        largest.sort_by_key(|g| std::cmp::Reverse(g.wasted_space()));
+        let largest_groups = largest
+            .into_iter()
+            .take(top)
+            .map(|g| GroupStats {
+                hash: g.hash_hex(),
+                files: g.files.len(),
+                reclaimable_space: g.wasted_space(),
+                first_path: g.files.first().map(|f| f.path.clone()).unwrap_or_default(),
+            })
+            .collect();
+
+        let mut extensions: Vec<ExtensionCount> = extensions
+            .into_iter()
+            .map(|(extension, files)| ExtensionCount { extension, files })
+            .collect();
+        extensions.sort_by(|a, b| {
+            b.files
+                .cmp(&a.files)
+                .then_with(|| a.extension.cmp(&b.extension))
+        });
+
+        Self {
+            groups: groups.len(),
+            files: groups.iter().map(|g| g.files.len()).sum(),
+            duplicate_files: groups.iter().map(DuplicateGroup::duplicate_count).sum(),
+            total_size: groups.iter().map(DuplicateGroup::total_size).sum(),
+            reclaimable_space: groups.iter().map(DuplicateGroup::wasted_space).sum(),
+            selected_files: 0,
+            by_category,
+            largest_groups,
+            group_sizes: group_sizes
+                .into_iter()
+                .map(|(files, groups)| GroupSizeBucket { files, groups })
+                .collect(),
+            extensions,
+        }
+    }
+
+    /// Percentage of the total size that is reclaimable.
+    #[must_use]
+    pub fn reclaimable_percentage(&self) -> f64 {
+        if self.total_size == 0 {
+            0.0
+        } else {
+            (self.reclaimable_space as f64 / self.total_size as f64) * 100.0
+        }
+    }
+
+    /// Write the statistics as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing or serialization fails.
+    pub fn write_json<W: Write>(&self, writer: &mut W, pretty: bool) -> serde_json::Result<()> {
+        if pretty {
+            serde_json::to_writer_pretty(&mut *writer, self)?;
+        } else {
+            serde_json::to_writer(&mut *writer, self)?;
+        }
+        writeln!(writer).map_err(serde_json::Error::io)
+    }
+
+    /// Write the human-readable report.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_text<W: Write>(
+        &self,
+        writer: &mut W,
+        size_format: SizeFormat,
+    ) -> std::io::Result<()> {
+        writeln!(writer, "{: <17} {}", "Groups:", self.groups)?;
+        writeln!(writer, "{: <17} {}", "Files:", self.files)?;
+        writeln!(
+            writer,
+            "{: <17} {}",
+            "Duplicate files:", self.duplicate_files
+        )?;
+        writeln!(
+            writer,
+            "{: <17} {}",
+            "Total size:",
+            size_format.format(self.total_size)
+        )?;
+        writeln!(
+            writer,
+            "{: <17} {} ({:.1}%)",
+            "Reclaimable:",
+            size_format.format(self.reclaimable_space),
+            self.reclaimable_percentage()
+        )?;
+        if self.selected_files > 0 {
+            writeln!(writer, "{: <17} {}", "Selected files:", self.selected_files)?;
+        }
+
+        if !self.by_category.is_empty() {
+            writeln!(writer, "\nReclaimable by category:")?;
+            for c in &self.by_category {
+                writeln!(
+                    writer,
+                    "  {: <12} {: >10}  ({} files)",
+                    c.category,
+                    size_format.format(c.reclaimable_space),
+                    c.files
+                )?;
+            }
+        }
+
+        if !self.largest_groups.is_empty() {
+            writeln!(writer, "\nLargest groups:")?;
+            for (i, g) in self.largest_groups.iter().enumerate() {
+                writeln!(
+                    writer,
+                    "  {}. {}  {} files  {}",
+                    i + 1,
+                    size_format.format(g.reclaimable_space),
+                    g.files,
+                    g.first_path.display()
+                )?;
+            }
+        }
+
+        if !self.group_sizes.is_empty() {
+            writeln!(writer, "\nGroup sizes:")?;
+            for bucket in &self.group_sizes {
+                writeln!(
+                    writer,
+                    "  {} files: {} group(s)",
+                    bucket.files, bucket.groups
+                )?;
+            }
+        }
+
+        if !self.extensions.is_empty() {
+            writeln!(writer, "\nFiles by extension:")?;
+            for ext in &self.extensions {
+                writeln!(writer, "  {: <12} {}", ext.extension, ext.files)?;
+            }
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use crate::session::{SessionGroup, SessionSettings};
+    use std::time::SystemTime;
+
+    fn make_group(seed: u8, size: u64, paths: &[&str]) -> DuplicateGroup {
+        DuplicateGroup::new(
+            [seed; 32],
+            size,
+            paths
+                .iter()
+                .map(|p| FileEntry::new(PathBuf::from(p), size, SystemTime::UNIX_EPOCH))
+                .collect(),
+            Vec::new(),
+        )
+    }
+
+    fn fixture_session() -> Session {
+        let groups = [
+            make_group(1, 1024, &["/docs/a.txt", "/docs/b.txt"]),
+            make_group(
+                2,
+                1536,
+                &["/photos/a.jpg", "/backup/a.JPG", "/backup/a.png"],
+            ),
+            make_group(3, 4096, &["/src/Makefile", "/old/Makefile"]),
+        ];
+        let session_groups = groups
+            .iter()
+            .enumerate()
+            .map(|(i, g)| SessionGroup::from_duplicate_group(g, i))
+            .collect();
+        let mut session = Session::new(
+            vec![PathBuf::from("/")],
+            SessionSettings::default(),
+            session_groups,
+        );
+        session
+            .user_selections
+            .insert(PathBuf::from("/backup/a.JPG"));
+        session
+    }
+
+    #[test]
+    fn test_totals() {
+        let stats = SessionStats::from_session(&fixture_session(), 10);
+
+        assert_eq!(stats.groups, 3);
+        assert_eq!(stats.files, 7);
+        assert_eq!(stats.duplicate_files, 4);
+        assert_eq!(stats.total_size, 2 * 1024 + 3 * 1536 + 2 * 4096);
+        assert_eq!(stats.reclaimable_space, 1024 + 2 * 1536 + 4096);
+        assert_eq!(stats.selected_files, 1);
+    }
+
+    #[test]
+    fn test_category_totals() {
+        let stats = SessionStats::from_session(&fixture_session(), 10);
+
+        let totals: Vec<(&str, usize, u64)> = stats
+            .by_category
+            .iter()
+            .map(|c| (c.category.as_str(), c.files, c.reclaimable_space))
+            .collect();
+        assert_eq!(
+            totals,
+            vec![
+                ("other", 2, 4096),
+                ("images", 3, 2 * 1536),
+                ("documents", 2, 1024),
+            ]
+        );
+        let sum: u64 = stats.by_category.iter().map(|c| c.reclaimable_space).sum();
+        assert_eq!(sum, stats.reclaimable_space);
+    }
+
+    #[test]
+    fn test_largest_groups_ordering() {
+        let stats = SessionStats::from_session(&fixture_session(), 2);
+
+        let order: Vec<(u64, &str)> = stats
+            .largest_groups
+            .iter()
+            .map(|g| (g.reclaimable_space, g.first_path.to_str().unwrap()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![(4096, "/src/Makefile"), (3072, "/photos/a.jpg")]
+        );
+        assert_eq!(stats.largest_groups[0].hash, "03".repeat(32));
+    }
+
+    #[test]
+    fn test_group_sizes_and_extensions() {
+        let stats = SessionStats::from_session(&fixture_session(), 10);
+
+        assert_eq!(
+            stats.group_sizes,
+            vec![
+                GroupSizeBucket {
+                    files: 2,
+                    groups: 2
+                },
+                GroupSizeBucket {
+                    files: 3,
+                    groups: 1
+                },
+            ]
+        );
+        let extensions: Vec<(&str, usize)> = stats
+            .extensions
+            .iter()
+            .map(|e| (e.extension.as_str(), e.files))
+            .collect();
+        assert_eq!(
+            extensions,
+            vec![("(none)", 2), ("jpg", 2), ("txt", 2), ("png", 1)]
+        );
+    }
+
+    #[test]
+    fn test_empty_session() {
+        let stats = SessionStats::from_groups(&[], 10);
+        assert_eq!(stats.groups, 0);
+        assert!(stats.reclaimable_percentage().abs() < f64::EPSILON);
+        assert!(stats.by_category.is_empty());
+
+        let mut buffer = Vec::new();
+        stats
+            .write_text(&mut buffer, SizeFormat::default())
+            .unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("Groups:           0"));
+        assert!(!text.contains("Largest groups:"));
+    }
+
+    #[test]
+    fn test_json_output() {
+        let stats = SessionStats::from_session(&fixture_session(), 10);
+        let mut buffer = Vec::new();
+        stats.write_json(&mut buffer, false).unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(parsed["groups"], 3);
+        assert_eq!(parsed["by_category"][0]["category"], "other");
+        assert_eq!(parsed["largest_groups"][0]["reclaimable_space"], 4096);
+        assert_eq!(parsed["extensions"][0]["extension"], "(none)");
+    }
+}
@@ -0,0 +1,182 @@
+//! Summary-only output for scan results.
+//!
+//! Used by `--summary-only` to report the headline numbers without
+//! enumerating any duplicate groups, e.g. for monitoring disk waste from a
+//! cron job. JSON and YAML keep the usual envelope but omit the `duplicates`
+//! array; every other format prints a compact text block:
+//!
+//! ```text
+//! Total files:      1200
+//! Total size:       4.0 GiB
+//! Duplicate groups: 35
+//! Duplicate files:  80
+//! Reclaimable:      512.0 MiB (12.5%)
+//! ```
+
+use std::io::Write;
+
+use super::json::{JsonOutputError, JsonStreamOutput};
+use super::yaml::YamlOutputError;
+use crate::config::Config;
+use crate::duplicates::ScanSummary;
+use crate::error::ExitCode;
+
+/// Summary-only output formatter.
+pub struct SummaryOutput<'a> {
+    summary: &'a ScanSummary,
+    exit_code: ExitCode,
+    config: &'a Config,
+}
+
+impl<'a> SummaryOutput<'a> {
+    /// Create a summary-only output.
+    ///
+    /// # Arguments
+    ///
+    /// * `summary` - The scan summary statistics
+    /// * `exit_code` - The exit code for this run
+    /// * `config` - The configuration used for the scan
+    #[must_use]
+    pub fn new(summary: &'a ScanSummary, exit_code: ExitCode, config: &'a Config) -> Self {
+        Self {
+            summary,
+            exit_code,
+            config,
+        }
+    }
+
+    fn document(&self) -> JsonStreamOutput<'a> {
+        JsonStreamOutput::new(&[], self.summary, self.exit_code, self.config)
+            .with_summary_only(true)
+    }
+
+    /// Write the JSON envelope without the `duplicates` array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing or serialization fails.
+    pub fn write_json<W: Write>(
+        &self,
+        writer: &mut W,
+        pretty: bool,
+    ) -> Result<(), JsonOutputError> {
+        self.document().write_to(writer, pretty)
+    }
+
+    /// Write the YAML envelope without the `duplicates` array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing or serialization fails.
+    pub fn write_yaml<W: Write>(&self, writer: &mut W) -> Result<(), YamlOutputError> {
+        serde_yaml::to_writer(&mut *writer, &self.document())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the compact text block.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_text<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let s = self.summary;
+        writeln!(writer, "{: <17} {}", "Total files:", s.total_files)?;
+        writeln!(
+            writer,
+            "{: <17} {}",
+            "Total size:",
+            self.config.size_units.format(s.total_size)
+        )?;
+        writeln!(
+            writer,
+            "{: <17} {}",
+            "Duplicate groups:", s.duplicate_groups
+        )?;
+        writeln!(writer, "{: <17} {}", "Duplicate files:", s.duplicate_files)?;
+        writeln!(
+            writer,
+            "{: <17} {} ({:.1}%)",
+            "Reclaimable:",
+            self.config.size_units.format(s.reclaimable_space),
+            s.wasted_percentage()
+        )?;
+        if !s.broken_symlinks.is_empty() {
+            writeln!(
+                writer,
+                "{: <17} {}",
+                "Broken symlinks:",
+                s.broken_symlinks.len()
+            )?;
+        }
+        if s.interrupted {
+            writeln!(writer, "Scan was interrupted; results are partial.")?;
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary() -> ScanSummary {
+        ScanSummary {
+            total_files: 1200,
+            total_size: 4096,
+            duplicate_groups: 35,
+            duplicate_files: 80,
+            reclaimable_space: 512,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summary_only_json_omits_groups() {
+        let summary = summary();
+        let config = Config::default();
+        let mut buffer = Vec::new();
+        SummaryOutput::new(&summary, ExitCode::Success, &config)
+            .write_json(&mut buffer, false)
+            .unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        let data = parsed["data"].as_object().unwrap();
+        assert!(!data.contains_key("duplicates"));
+        assert_eq!(data["summary"]["duplicate_groups"], 35);
+        assert_eq!(data["summary"]["reclaimable_space"], 512);
+        assert_eq!(data["summary"]["wasted_percentage"], 12.5);
+    }
+
+    #[test]
+    fn test_summary_only_yaml_omits_groups() {
+        let summary = summary();
+        let config = Config::default();
+        let mut buffer = Vec::new();
+        SummaryOutput::new(&summary, ExitCode::Success, &config)
+            .write_yaml(&mut buffer)
+            .unwrap();
+
+        let yaml = String::from_utf8(buffer).unwrap();
+        assert!(!yaml.contains("duplicates:"));
+        assert!(yaml.contains("duplicate_groups: 35"));
+    }
+
+    #[test]
+    fn test_summary_only_text_block() {
+        let summary = summary();
+        let config = Config::default();
+        let mut buffer = Vec::new();
+        SummaryOutput::new(&summary, ExitCode::Success, &config)
+            .write_text(&mut buffer)
+            .unwrap();
+
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("Total files:      1200"));
+        assert!(text.contains("Duplicate groups: 35"));
+        assert!(text.contains("(12.5%)"));
+        // No tree glyphs or group headers
+        assert!(!text.contains("──"));
+        assert!(!text.contains("[1]"));
+    }
+}
@@ -0,0 +1,214 @@
+//! Human-readable text output for duplicate scan results.
+//!
+//! The default format when `--output` is omitted and the terminal is not
+//! interactive (for example when piping to `less`). Each group gets a header
+//! with its [group ID](crate::duplicates::group_ids), file size and copy
+//! count, followed by its files indented beneath; the file that would be
+//! kept is marked with `*` and reference-directory files are tagged `[ref]`.
+//! A footer totals groups, duplicates and reclaimable space.
+//!
+//! # Example Output
+//!
+//! ```text
+//! Group 1 [3f2a9c01d4e5]: 1.0 KiB x 3, 2.0 KiB reclaimable
+//!   * /data/a/one.bin (keep)
+//!     /data/a/two.bin
+//!     /data/b/three.bin
+//!
+//! 1 group(s), 2 duplicate file(s), 2.0 KiB reclaimable
+//! ```
+//!
+//! Headers, keepers and the footer are colored when writing to a terminal;
+//! color is disabled when stdout is not a TTY or `--no-color` is set.
+
+use std::io::Write;
+
+use yansi::{Condition, Paint};
+
+use super::script::is_deleted_by_default;
+use super::size::SizeFormat;
+use crate::duplicates::{group_ids, DuplicateGroup, MatchKind};
+
+/// Human-readable text formatter.
+pub struct TextOutput<'a> {
+    groups: &'a [DuplicateGroup],
+    color: bool,
+    size_format: SizeFormat,
+}
+
+impl<'a> TextOutput<'a> {
+    /// Create a new text formatter with color disabled.
+    #[must_use]
+    pub fn new(groups: &'a [DuplicateGroup]) -> Self {
+        Self {
+            groups,
+            color: false,
+            size_format: SizeFormat::default(),
+        }
+    }
+
+    /// Enable or disable ANSI colors.
+    #[must_use]
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the units for human-readable sizes (`--size-units`).
+    #[must_use]
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    fn condition(&self) -> Condition {
+        if self.color {
+            Condition::ALWAYS
+        } else {
+            Condition::NEVER
+        }
+    }
+
+    /// Render the report to a string.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let when = self.condition();
+        let mut out = String::new();
+        let mut duplicate_files = 0usize;
+        let mut reclaimable = 0u64;
+
+        let ids = group_ids(self.groups);
+        for (idx, group) in self.groups.iter().enumerate() {
+            duplicate_files += group.duplicate_count();
+            reclaimable += group.wasted_space();
+
+            let kind = match group.match_kind() {
+                MatchKind::Exact => String::new(),
+                MatchKind::Similar => " (similar)".to_string(),
+                MatchKind::Empty => " (empty files)".to_string(),
+                MatchKind::NameSimilar => " (similar names)".to_string(),
+                MatchKind::Partial => format!(
+                    " (partial, {:.0}% shared)",
+                    group.partial_similarity.unwrap_or_default() * 100.0
+                ),
+            };
+            let header = format!(
+                "Group {} [{}]: {} x {}, {} reclaimable{}",
+                idx + 1,
+                ids[idx],
+                self.size_format.format(group.size),
+                group.len(),
+                self.size_format.format(group.wasted_space()),
+                kind
+            );
+            if idx > 0 {
+                out.push('\n');
+            }
+            out.push_str(&format!("{}\n", header.cyan().bold().whenever(when)));
+
+            // The keeper is the first file the default deletion rule spares
+            let keeper = (0..group.files.len()).find(|&j| !is_deleted_by_default(group, j));
+            for (j, file) in group.files.iter().enumerate() {
+                let path = file.path.display().to_string();
+                let reference = if group.is_in_reference_dir(&file.path) {
+                    format!(" {}", "[ref]".yellow().whenever(when))
+                } else {
+                    String::new()
+                };
+                if Some(j) == keeper {
+                    out.push_str(&format!(
+                        "  {} {} {}{}\n",
+                        "*".green().bold().whenever(when),
+                        path.green().bold().whenever(when),
+                        "(keep)".green().whenever(when),
+                        reference
+                    ));
+                } else {
+                    out.push_str(&format!("    {}{}\n", path, reference));
+                }
+            }
+        }
+
+        if !self.groups.is_empty() {
+            out.push('\n');
+        }
+        let footer = format!(
+            "{} group(s), {} duplicate file(s), {} reclaimable",
+            self.groups.len(),
+            duplicate_files,
+            self.size_format.format(reclaimable)
+        );
+        out.push_str(&format!("{}\n", footer.bold().whenever(when)));
+        out
+    }
+
+    /// Write the report to the given writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the writer fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(self.render().as_bytes())?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn group(size: u64, paths: &[&str], reference: &[&str]) -> DuplicateGroup {
+        let files = paths
+            .iter()
+            .map(|p| FileEntry::new(PathBuf::from(p), size, SystemTime::UNIX_EPOCH))
+            .collect();
+        DuplicateGroup::new(
+            [7u8; 32],
+            size,
+            files,
+            reference.iter().map(PathBuf::from).collect(),
+        )
+    }
+
+    #[test]
+    fn test_text_output_plain() {
+        let groups = vec![
+            group(
+                1024,
+                &["/data/a/one.bin", "/data/a/two.bin", "/data/b/three.bin"],
+                &[],
+            ),
+            group(2048, &["/data/x.bin", "/ref/x.bin"], &["/ref"]),
+        ];
+        let text = TextOutput::new(&groups).render();
+
+        assert_eq!(
+            text,
+            "Group 1 [070707070707]: 1.0 KiB x 3, 2.0 KiB reclaimable\n\
+             \x20 * /data/a/one.bin (keep)\n\
+             \x20   /data/a/two.bin\n\
+             \x20   /data/b/three.bin\n\
+             \n\
+             Group 2 [070707070707-2]: 2.0 KiB x 2, 2.0 KiB reclaimable\n\
+             \x20   /data/x.bin\n\
+             \x20 * /ref/x.bin (keep) [ref]\n\
+             \n\
+             2 group(s), 3 duplicate file(s), 4.0 KiB reclaimable\n"
+        );
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_text_output_empty_and_color() {
+        let text = TextOutput::new(&[]).render();
+        assert_eq!(text, "0 group(s), 0 duplicate file(s), 0 B reclaimable\n");
+
+        let groups = vec![group(10, &["/a", "/b"], &[])];
+        let colored = TextOutput::new(&groups).with_color(true).render();
+        assert!(colored.contains('\x1b'));
+        assert!(colored.contains("/a"));
+    }
+}
@@ -0,0 +1,274 @@
+//! Tree-style text output for duplicate scan results.
+//!
+//! Renders duplicate groups as a `tree`-like listing for quick command-line
+//! triage without launching the TUI. Each group gets a header with its file
+//! size, copy count, [group ID](crate::duplicates::group_ids) and reclaimable
+//! space; its files are listed beneath, bucketed by parent directory and
+//! sorted. A footer totals the reclaimable space across all groups.
+//!
+//! # Example Output
+//!
+//! ```text
+//! [1] 1.0 KiB x 3 (group 000000000000) reclaimable 2.0 KiB
+//! ├── /data/a
+//! │   ├── one.bin
+//! │   └── two.bin
+//! └── /data/b
+//!     └── three.bin
+//!
+//! 1 group(s), 3 file(s), 2.0 KiB reclaimable
+//! ```
+//!
+//! When accessible mode is enabled, box-drawing characters are replaced by
+//! plain ASCII (`|--`, `` `-- ``) for screen readers and limited terminals.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+use crate::duplicates::{group_ids, DuplicateGroup};
+use crate::output::size::SizeFormat;
+
+/// Branch glyphs used to draw the tree.
+struct Glyphs {
+    branch: &'static str,
+    last: &'static str,
+    pipe: &'static str,
+    space: &'static str,
+}
+
+const UNICODE_GLYPHS: Glyphs = Glyphs {
+    branch: "├── ",
+    last: "└── ",
+    pipe: "│   ",
+    space: "    ",
+};
+
+const ASCII_GLYPHS: Glyphs = Glyphs {
+    branch: "|-- ",
+    last: "`-- ",
+    pipe: "|   ",
+    space: "    ",
+};
+
+/// Tree-style output formatter.
+pub struct TreeOutput<'a> {
+    groups: &'a [DuplicateGroup],
+    ascii: bool,
+    size_format: SizeFormat,
+}
+
+impl<'a> TreeOutput<'a> {
+    /// Create a new tree output formatter using box-drawing characters.
+    #[must_use]
+    pub fn new(groups: &'a [DuplicateGroup]) -> Self {
+        Self {
+            groups,
+            ascii: false,
+            size_format: SizeFormat::default(),
+        }
+    }
+
+    /// Use plain ASCII branches instead of box-drawing characters.
+    #[must_use]
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Set the units for human-readable sizes (`--size-units`).
+    #[must_use]
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    /// Render the tree to a string.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let glyphs = if self.ascii {
+            &ASCII_GLYPHS
+        } else {
+            &UNICODE_GLYPHS
+        };
+
+        let mut out = String::new();
+        let mut total_files = 0usize;
+        let mut total_reclaimable = 0u64;
+
+        let ids = group_ids(self.groups);
+        for (idx, group) in self.groups.iter().enumerate() {
+            let reclaimable = group.wasted_space();
+            total_files += group.len();
+            total_reclaimable += reclaimable;
+
+            out.push_str(&format!(
+                "[{}] {} x {} (group {}) reclaimable {}\n",
+                idx + 1,
+                self.size_format.format(group.size),
+                group.len(),
+                ids[idx],
+                self.size_format.format(reclaimable)
+            ));
+
+            // Bucket files by parent directory; BTreeMap keeps both levels sorted.
+            let mut by_dir: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for file in &group.files {
+                let dir = file
+                    .path
+                    .parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .to_string_lossy()
+                    .into_owned();
+                let name = file
+                    .path
+                    .file_name()
+                    .map_or_else(|| file.path.to_string_lossy(), |n| n.to_string_lossy())
+                    .into_owned();
+                by_dir.entry(dir).or_default().push(name);
+            }
+
+            let dir_count = by_dir.len();
+            for (dir_idx, (dir, mut names)) in by_dir.into_iter().enumerate() {
+                names.sort();
+                let last_dir = dir_idx + 1 == dir_count;
+                let (dir_branch, child_indent) = if last_dir {
+                    (glyphs.last, glyphs.space)
+                } else {
+                    (glyphs.branch, glyphs.pipe)
+                };
+                out.push_str(dir_branch);
+                out.push_str(&dir);
+                out.push('\n');
+
+                let name_count = names.len();
+                for (name_idx, name) in names.iter().enumerate() {
+                    let file_branch = if name_idx + 1 == name_count {
+                        glyphs.last
+                    } else {
+                        glyphs.branch
+                    };
+                    out.push_str(child_indent);
+                    out.push_str(file_branch);
+                    out.push_str(name);
+                    out.push('\n');
+                }
+            }
+            out.push('\n');
+        }
+
+        out.push_str(&format!(
+            "{} group(s), {} file(s), {} reclaimable\n",
+            self.groups.len(),
+            total_files,
+            self.size_format.format(total_reclaimable)
+        ));
+        out
+    }
+
+    /// Write the rendered tree to the given writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the writer fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(self.render().as_bytes())?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn make_file(path: &str, size: u64) -> FileEntry {
+        FileEntry::new(PathBuf::from(path), size, SystemTime::UNIX_EPOCH)
+    }
+
+    fn create_test_groups() -> Vec<DuplicateGroup> {
+        vec![
+            DuplicateGroup::new(
+                [0xab; 32],
+                1024,
+                vec![
+                    make_file("/data/b/three.bin", 1024),
+                    make_file("/data/a/two.bin", 1024),
+                    make_file("/data/a/one.bin", 1024),
+                ],
+                Vec::new(),
+            ),
+            DuplicateGroup::new(
+                [0x01; 32],
+                2048,
+                vec![
+                    make_file("/docs/report.pdf", 2048),
+                    make_file("/docs/copy.pdf", 2048),
+                ],
+                Vec::new(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_tree_render_unicode() {
+        let groups = create_test_groups();
+        let rendered = TreeOutput::new(&groups).render();
+        let expected = "\
+[1] 1.0 KiB x 3 (group abababababab) reclaimable 2.0 KiB
+├── /data/a
+│   ├── one.bin
+│   └── two.bin
+└── /data/b
+    └── three.bin
+
+[2] 2.0 KiB x 2 (group 010101010101) reclaimable 2.0 KiB
+└── /docs
+    ├── copy.pdf
+    └── report.pdf
+
+2 group(s), 5 file(s), 4.0 KiB reclaimable
+";
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn test_tree_render_ascii() {
+        let groups = create_test_groups();
+        let rendered = TreeOutput::new(&groups).with_ascii(true).render();
+        let expected = "\
+[1] 1.0 KiB x 3 (group abababababab) reclaimable 2.0 KiB
+|-- /data/a
+|   |-- one.bin
+|   `-- two.bin
+`-- /data/b
+    `-- three.bin
+
+[2] 2.0 KiB x 2 (group 010101010101) reclaimable 2.0 KiB
+`-- /docs
+    |-- copy.pdf
+    `-- report.pdf
+
+2 group(s), 5 file(s), 4.0 KiB reclaimable
+";
+        assert_eq!(rendered, expected);
+        assert!(rendered.is_ascii());
+    }
+
+    #[test]
+    fn test_tree_render_empty() {
+        let rendered = TreeOutput::new(&[]).render();
+        assert_eq!(rendered, "0 group(s), 0 file(s), 0 B reclaimable\n");
+    }
+
+    #[test]
+    fn test_tree_write_to() {
+        let groups = create_test_groups();
+        let output = TreeOutput::new(&groups);
+        let mut buffer = Vec::new();
+        output.write_to(&mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), output.render());
+    }
+}
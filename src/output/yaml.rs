@@ -0,0 +1,262 @@
+//! YAML output formatter for duplicate scan results.
+//!
+//! Provides YAML output for configuration-management tooling (e.g. Ansible)
+//! that consumes YAML more naturally than JSON. The document structure and
+//! field names are identical to the [JSON output](super::json), so consumers
+//! can switch formats with minimal changes.
+//!
+//! # Output Schema
+//!
+//! ```yaml
+//! schema_version: '2.0'
+//! generated_at: 2026-01-01T00:00:00Z
+//! tool_version: 0.3.0
+//! data:
+//!   config: { ... }
+//!   duplicates:
+//!     - hash: abc123...
+//!       size: 1024
+//!       files:
+//!         - /path/to/file1.txt
+//!         - /path/to/file2.txt
+//!   summary:
+//!     total_files: 100
+//!     reclaimable_space: 51200
+//!     exit_code: 0
+//!     exit_code_name: RD000
+//! ```
+//!
+//! The envelope follows the same [versioning contract](super::json#versioning-contract)
+//! as the JSON output.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::duplicates::DuplicateFinder;
+//! use rustdupe::output::yaml::YamlOutput;
+//! use rustdupe::error::ExitCode;
+//! use rustdupe::config::Config;
+//! use std::path::Path;
+//!
+//! let finder = DuplicateFinder::with_defaults();
+//! let (groups, summary) = finder.find_duplicates(Path::new(".")).unwrap();
+//!
+//! let output = YamlOutput::new(&groups, &summary, ExitCode::Success, &Config::default());
+//! output.write_to(&mut std::io::stdout()).unwrap();
+//! ```
+
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::json::{JsonData, JsonOutput};
+use crate::config::Config;
+use crate::duplicates::{DuplicateGroup, ScanSummary};
+
+/// Complete YAML output structure.
+///
+/// Shares its component types with [`JsonOutput`](super::JsonOutput) so both
+/// formats always expose the same fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlOutput {
+    /// Schema version of this document
+    pub schema_version: String,
+    /// Timestamp when the document was generated (RFC3339)
+    pub generated_at: DateTime<Utc>,
+    /// Version of RustDupe that generated the document
+    pub tool_version: String,
+    /// Scan results
+    pub data: JsonData,
+}
+
+impl YamlOutput {
+    /// Create a new YAML output from duplicate groups, summary, exit code and config.
+    ///
+    /// # Arguments
+    ///
+    /// * `groups` - The duplicate groups found during scanning
+    /// * `summary` - The scan summary statistics
+    /// * `exit_code` - The exit code for this run
+    /// * `config` - The configuration used for the scan
+    #[must_use]
+    pub fn new(
+        groups: &[DuplicateGroup],
+        summary: &ScanSummary,
+        exit_code: crate::error::ExitCode,
+        config: &Config,
+    ) -> Self {
+        JsonOutput::new(groups, summary, exit_code, config).into()
+    }
+
+    /// Serialize to a YAML string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails (unlikely for valid data).
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Write YAML to a writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to output to (e.g., stdout or a file)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing or serialization fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), YamlOutputError> {
+        serde_yaml::to_writer(&mut *writer, self)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Rewrite file paths inside `base` as relative to it.
+    ///
+    /// Used by `--json-paths-relative`; paths outside `base` stay absolute.
+    #[must_use]
+    pub fn with_paths_relative_to(mut self, base: Option<&Path>) -> Self {
+        self.data.rebase_paths(base);
+        self
+    }
+}
+
+impl From<JsonOutput> for YamlOutput {
+    fn from(output: JsonOutput) -> Self {
+        Self {
+            schema_version: output.schema_version,
+            generated_at: output.generated_at,
+            tool_version: output.tool_version,
+            data: output.data,
+        }
+    }
+}
+
+/// Errors that can occur during YAML output.
+#[derive(thiserror::Error, Debug)]
+pub enum YamlOutputError {
+    /// YAML serialization error
+    #[error("YAML serialization error: {0}")]
+    Serialization(#[from] serde_yaml::Error),
+
+    /// I/O error during writing
+    #[error("I/O error during YAML generation: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn create_test_groups() -> Vec<DuplicateGroup> {
+        let now = SystemTime::now();
+        vec![
+            DuplicateGroup::new(
+                [0u8; 32],
+                1024,
+                vec![
+                    FileEntry::new(PathBuf::from("/path/to/file1.txt"), 1024, now),
+                    FileEntry::new(PathBuf::from("/path/to/file2.txt"), 1024, now),
+                ],
+                Vec::new(),
+            ),
+            DuplicateGroup::new(
+                [1u8; 32],
+                2048,
+                vec![
+                    FileEntry::new(PathBuf::from("/path/to/fileA.txt"), 2048, now),
+                    FileEntry::new(PathBuf::from("/path/to/fileB.txt"), 2048, now),
+                    FileEntry::new(PathBuf::from("/path/to/fileC.txt"), 2048, now),
+                ],
+                Vec::new(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_yaml_output_empty() {
+        let output = YamlOutput::new(
+            &[],
+            &ScanSummary::default(),
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+        assert!(output.data.duplicates.is_empty());
+        assert_eq!(output.data.summary.total_files, 0);
+    }
+
+    #[test]
+    fn test_yaml_roundtrip_matches_groups() {
+        let groups = create_test_groups();
+        let output = YamlOutput::new(
+            &groups,
+            &ScanSummary::default(),
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+
+        let mut buffer = Vec::new();
+        output.write_to(&mut buffer).unwrap();
+        let yaml = String::from_utf8(buffer).unwrap();
+
+        let parsed: YamlOutput = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.data.duplicates.len(), groups.len());
+        for (parsed_group, group) in parsed.data.duplicates.iter().zip(&groups) {
+            assert_eq!(parsed_group.hash, group.hash_hex());
+            assert_eq!(parsed_group.size, group.size);
+            let expected: Vec<String> = group
+                .files
+                .iter()
+                .map(|f| f.path.to_string_lossy().into_owned())
+                .collect();
+            assert_eq!(parsed_group.files, expected);
+        }
+        assert_eq!(parsed.data.summary.exit_code, 0);
+        assert_eq!(parsed.data.summary.exit_code_name, "RD000");
+        assert_eq!(
+            parsed.schema_version,
+            crate::output::json::JSON_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn test_yaml_field_names_match_json() {
+        let groups = create_test_groups();
+        let summary = ScanSummary::default();
+        let yaml_output = YamlOutput::new(
+            &groups,
+            &summary,
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+        let json_output = super::super::JsonOutput::new(
+            &groups,
+            &summary,
+            crate::error::ExitCode::Success,
+            &Config::default(),
+        );
+
+        let yaml_value: serde_json::Value =
+            serde_yaml::from_str(&yaml_output.to_yaml().unwrap()).unwrap();
+        let json_value: serde_json::Value =
+            serde_json::from_str(&json_output.to_json().unwrap()).unwrap();
+
+        let keys = |v: &serde_json::Value| -> Vec<String> {
+            let mut keys: Vec<String> = v.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+        assert_eq!(keys(&yaml_value), keys(&json_value));
+        assert_eq!(keys(&yaml_value["data"]), keys(&json_value["data"]));
+        assert_eq!(
+            keys(&yaml_value["data"]["summary"]),
+            keys(&json_value["data"]["summary"])
+        );
+    }
+}
@@ -3,19 +3,42 @@
 //! This module provides the [`Progress`] struct which implements [`ProgressCallback`]
 //! to display visual progress bars in the terminal for non-TUI output modes.
 //!
+//! # JSON Progress
+//!
+//! [`JsonProgress`] (selected with `--progress-format json`) replaces the bars
+//! with one JSON object per line on stderr, for job schedulers and CI logs.
+//! A line is written when a phase starts, every [`JSON_PROGRESS_INTERVAL`]
+//! processed items, and when the phase ends:
+//!
+//! ```text
+//! {"event":"start","phase":"fullhash","current":0,"total":500}
+//! {"event":"progress","phase":"fullhash","current":100,"total":500}
+//! {"event":"end","phase":"fullhash","current":500,"total":500}
+//! ```
+//!
 //! # Accessible Mode
 //!
 //! When accessible mode is enabled, progress reporting uses simplified output:
 //! - No spinners or animations
 //! - Plain text updates without cursor movement
 //! - Reduced update frequency for screen reader compatibility
+//!
+//! # Pausing
+//!
+//! Hashing workers call [`wait_while_paused`] between files. While the shared
+//! pause flag is set they sleep in short intervals instead of starting the
+//! next file, so a paused scan frees the disk without losing its place.
 
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use indicatif::{HumanBytes, HumanDuration, MultiProgress, ProgressBar, ProgressStyle};
 
+use crate::tui::app::ScanProgress;
+
 /// State for exponential moving average metrics (ETA and throughput).
 #[derive(Debug)]
 struct ProgressMetrics {
@@ -133,6 +156,29 @@ pub trait ProgressCallback: Send + Sync {
     /// * `bytes` - Size of the item in bytes
     fn on_item_completed(&self, _bytes: u64) {}
 
+    /// Called as file content is read during full hashing.
+    ///
+    /// Reports each chunk as it is consumed, so front-ends can show
+    /// byte-level progress within large files. The sum over a file equals
+    /// its size. Hashing runs on a thread pool, so this may be called
+    /// concurrently from multiple threads and should be cheap, e.g. an
+    /// atomic add.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Number of bytes read since the previous call for this file
+    fn on_bytes_read(&self, _bytes: u64) {}
+
+    /// Called after a phase starts when its total byte count is known.
+    ///
+    /// Together with [`on_item_completed`](Self::on_item_completed) this
+    /// allows byte-based ETA estimation.
+    ///
+    /// # Arguments
+    ///
+    /// * `total_bytes` - Total number of bytes the phase will process
+    fn on_phase_bytes(&self, _total_bytes: u64) {}
+
     /// Called when a phase completes.
     ///
     /// # Arguments
@@ -162,6 +208,9 @@ pub struct Progress {
     active_phase: Mutex<Option<String>>,
     metrics: Mutex<HashMap<String, ProgressMetrics>>,
     total_bytes: Mutex<HashMap<String, u64>>,
+    /// Bytes streamed by the hasher in the active phase
+    bytes_read: AtomicU64,
+    scan_progress: Option<Arc<Mutex<ScanProgress>>>,
     quiet: bool,
     accessible: bool,
 }
@@ -191,6 +240,8 @@ impl Progress {
             active_phase: Mutex::new(None),
             metrics: Mutex::new(HashMap::new()),
             total_bytes: Mutex::new(HashMap::new()),
+            bytes_read: AtomicU64::new(0),
+            scan_progress: None,
             quiet,
             accessible: false,
         }
@@ -222,11 +273,32 @@ impl Progress {
             active_phase: Mutex::new(None),
             metrics: Mutex::new(HashMap::new()),
             total_bytes: Mutex::new(HashMap::new()),
+            bytes_read: AtomicU64::new(0),
+            scan_progress: None,
             quiet,
             accessible,
         }
     }
 
+    /// Mirror progress into a shared [`ScanProgress`] for the TUI scan screen.
+    ///
+    /// The shared state is updated even in quiet mode so the TUI can render
+    /// phase, ETA and throughput while terminal bars are suppressed.
+    #[must_use]
+    pub fn with_scan_progress(mut self, scan_progress: Arc<Mutex<ScanProgress>>) -> Self {
+        self.scan_progress = Some(scan_progress);
+        self
+    }
+
+    /// Apply an update to the shared scan progress, if attached.
+    fn update_scan_progress(&self, update: impl FnOnce(&mut ScanProgress)) {
+        if let Some(ref shared) = self.scan_progress {
+            if let Ok(mut progress) = shared.lock() {
+                update(&mut progress);
+            }
+        }
+    }
+
     /// Check if accessible mode is enabled.
     #[must_use]
     pub fn is_accessible(&self) -> bool {
@@ -285,29 +357,23 @@ impl Progress {
 
 impl ProgressCallback for Progress {
     fn on_phase_start(&self, phase: &str, total: usize) {
+        let display_name = phase_display_name(phase);
+        self.update_scan_progress(|p| {
+            p.phase = display_name.clone();
+            p.current = 0;
+            p.total = total;
+            p.bytes_done = 0;
+            p.bytes_total = 0;
+            p.started_at = Instant::now();
+        });
+
         if self.quiet {
             return;
         }
 
+        self.bytes_read.store(0, Ordering::Relaxed);
         *self.active_phase.lock().unwrap() = Some(phase.to_string());
-        let display_name = match phase {
-            "walking" => "Walking",
-            "prehash" => "Prehashing",
-            "fullhash" => "Full Hashing",
-            "perceptual_hashing" => "Perceptual Hashing",
-            _ => {
-                let name = phase.replace('_', " ");
-                let mut chars = name.chars();
-                match chars.next() {
-                    None => "",
-                    Some(f) => {
-                        let capitalized = f.to_uppercase().collect::<String>() + chars.as_str();
-                        Box::leak(capitalized.into_boxed_str())
-                    }
-                }
-            }
-        };
-        *self.phase_name.lock().unwrap() = display_name.to_string();
+        *self.phase_name.lock().unwrap() = display_name;
         self.metrics
             .lock()
             .unwrap()
@@ -318,6 +384,14 @@ impl ProgressCallback for Progress {
             .insert(phase.to_string(), 0);
 
         match phase {
+            // A precounted walk (--precount) knows its total and gets a real bar
+            "walking" if total > 0 => {
+                let pb = self.multi.add(ProgressBar::new(total as u64));
+                pb.set_style(self.prehash_style());
+                pb.set_message("Walking");
+                let mut walking = self.walking.lock().unwrap();
+                *walking = Some(pb);
+            }
             "walking" => {
                 let pb = self.multi.add(ProgressBar::new_spinner());
                 pb.set_style(self.walking_style());
@@ -367,6 +441,11 @@ impl ProgressCallback for Progress {
     }
 
     fn on_progress(&self, current: usize, path: &str) {
+        self.update_scan_progress(|p| {
+            p.current = current;
+            p.current_path = path.to_string();
+        });
+
         if self.quiet {
             return;
         }
@@ -383,13 +462,15 @@ impl ProgressCallback for Progress {
         let mut metrics_display = String::new();
         let active_phase = self.active_phase.lock().unwrap();
         if let Some(ref phase) = *active_phase {
+            // Streamed bytes keep throughput moving while large files hash
             let bytes = self
                 .total_bytes
                 .lock()
                 .unwrap()
                 .get(phase)
                 .copied()
-                .unwrap_or(0);
+                .unwrap_or(0)
+                .max(self.bytes_read.load(Ordering::Relaxed));
 
             let mut metrics_map = self.metrics.lock().unwrap();
             if let Some(m) = metrics_map.get_mut(phase) {
@@ -451,6 +532,8 @@ impl ProgressCallback for Progress {
     }
 
     fn on_item_completed(&self, bytes: u64) {
+        self.update_scan_progress(|p| p.bytes_done += bytes);
+
         if self.quiet {
             return;
         }
@@ -464,6 +547,16 @@ impl ProgressCallback for Progress {
         }
     }
 
+    fn on_bytes_read(&self, bytes: u64) {
+        if !self.quiet {
+            self.bytes_read.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    fn on_phase_bytes(&self, total_bytes: u64) {
+        self.update_scan_progress(|p| p.bytes_total = total_bytes);
+    }
+
     fn on_phase_end(&self, phase: &str) {
         if self.quiet {
             return;
@@ -508,6 +601,142 @@ impl ProgressCallback for Progress {
     }
 }
 
+/// How often a paused worker re-checks the pause and shutdown flags.
+pub const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Park the calling worker while `pause` is set.
+///
+/// Returns as soon as the pause is lifted or `shutdown` is set, so a paused
+/// scan can still be cancelled. Returns immediately without a pause flag.
+pub fn wait_while_paused(pause: Option<&AtomicBool>, shutdown: Option<&AtomicBool>) {
+    let Some(pause) = pause else {
+        return;
+    };
+    let is_shutdown = || shutdown.is_some_and(|f| f.load(Ordering::SeqCst));
+    while pause.load(Ordering::SeqCst) && !is_shutdown() {
+        std::thread::sleep(PAUSE_POLL_INTERVAL);
+    }
+}
+
+/// Number of processed items between JSON progress lines.
+pub const JSON_PROGRESS_INTERVAL: usize = 100;
+
+/// One line of JSON progress output.
+#[derive(Debug, serde::Serialize)]
+struct ProgressEvent<'a> {
+    event: &'a str,
+    phase: &'a str,
+    current: usize,
+    total: usize,
+}
+
+/// Progress reporter writing line-based JSON events.
+///
+/// Writes to stderr by default so stdout stays free for results. Each line
+/// is a complete JSON object; see the [module documentation](self) for the
+/// format.
+pub struct JsonProgress<W: Write + Send = io::Stderr> {
+    writer: Mutex<W>,
+    interval: usize,
+    phase: Mutex<String>,
+    total: AtomicUsize,
+    current: AtomicUsize,
+}
+
+impl JsonProgress {
+    /// Create a reporter writing to stderr every [`JSON_PROGRESS_INTERVAL`] items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustdupe::progress::JsonProgress;
+    ///
+    /// let progress = JsonProgress::stderr();
+    /// ```
+    #[must_use]
+    pub fn stderr() -> Self {
+        Self::new(io::stderr(), JSON_PROGRESS_INTERVAL)
+    }
+}
+
+impl<W: Write + Send> JsonProgress<W> {
+    /// Create a reporter writing to `writer` every `interval` processed items.
+    ///
+    /// An `interval` of zero is treated as one.
+    #[must_use]
+    pub fn new(writer: W, interval: usize) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+            interval: interval.max(1),
+            phase: Mutex::new(String::new()),
+            total: AtomicUsize::new(0),
+            current: AtomicUsize::new(0),
+        }
+    }
+
+    /// Consume the reporter and return its writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Write one event line; write errors (e.g. a closed stderr) are ignored.
+    fn emit(&self, event: &str, phase: &str, current: usize) {
+        let line = ProgressEvent {
+            event,
+            phase,
+            current,
+            total: self.total.load(Ordering::Relaxed),
+        };
+        if let Ok(json) = serde_json::to_string(&line) {
+            if let Ok(mut writer) = self.writer.lock() {
+                let _ = writeln!(writer, "{}", json);
+                let _ = writer.flush();
+            }
+        }
+    }
+}
+
+impl<W: Write + Send> ProgressCallback for JsonProgress<W> {
+    fn on_phase_start(&self, phase: &str, total: usize) {
+        *self.phase.lock().unwrap() = phase.to_string();
+        self.total.store(total, Ordering::Relaxed);
+        self.current.store(0, Ordering::Relaxed);
+        self.emit("start", phase, 0);
+    }
+
+    fn on_progress(&self, current: usize, _path: &str) {
+        self.current.fetch_max(current, Ordering::Relaxed);
+        if current % self.interval == 0 {
+            let phase = self.phase.lock().unwrap().clone();
+            self.emit("progress", &phase, current);
+        }
+    }
+
+    fn on_phase_end(&self, phase: &str) {
+        self.emit("end", phase, self.current.load(Ordering::Relaxed));
+    }
+}
+
+/// Human-readable name for a pipeline phase (e.g. "fullhash" -> "Full Hashing").
+fn phase_display_name(phase: &str) -> String {
+    match phase {
+        "walking" => "Walking".to_string(),
+        "prehash" => "Prehashing".to_string(),
+        "fullhash" => "Full Hashing".to_string(),
+        "perceptual_hashing" => "Perceptual Hashing".to_string(),
+        _ => {
+            let name = phase.replace('_', " ");
+            let mut chars = name.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(f) => f.to_uppercase().collect::<String>() + chars.as_str(),
+            }
+        }
+    }
+}
+
 /// Truncate a path for display in the progress bar.
 fn truncate_path(path: &str, max_len: usize) -> String {
     // Use character count, not byte count, for proper Unicode handling
@@ -532,3 +761,65 @@ fn truncate_path(path: &str, max_len: usize) -> String {
 
     format!(".../{}", file_name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_progress_lines() {
+        let progress = JsonProgress::new(Vec::new(), 2);
+        progress.on_phase_start("walking", 0);
+        for i in 1..=3 {
+            progress.on_progress(i, "/scan/file");
+        }
+        progress.on_phase_end("walking");
+        progress.on_phase_start("fullhash", 5);
+        for i in 1..=5 {
+            progress.on_progress(i, "/scan/file");
+        }
+        progress.on_phase_end("fullhash");
+
+        let output = String::from_utf8(progress.into_inner()).unwrap();
+        let events: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let summary: Vec<(&str, &str, u64, u64)> = events
+            .iter()
+            .map(|e| {
+                (
+                    e["event"].as_str().unwrap(),
+                    e["phase"].as_str().unwrap(),
+                    e["current"].as_u64().unwrap(),
+                    e["total"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("start", "walking", 0, 0),
+                ("progress", "walking", 2, 0),
+                ("end", "walking", 3, 0),
+                ("start", "fullhash", 0, 5),
+                ("progress", "fullhash", 2, 5),
+                ("progress", "fullhash", 4, 5),
+                ("end", "fullhash", 5, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_progress_zero_interval() {
+        let progress = JsonProgress::new(Vec::new(), 0);
+        progress.on_phase_start("prehash", 2);
+        progress.on_progress(1, "/a");
+        progress.on_progress(2, "/b");
+        progress.on_phase_end("prehash");
+
+        let output = String::from_utf8(progress.into_inner()).unwrap();
+        assert_eq!(output.lines().count(), 4);
+    }
+}
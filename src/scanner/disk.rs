@@ -0,0 +1,187 @@
+//! Storage type detection for `--io-threads auto`.
+//!
+//! Concurrent reads help on SSDs but make a spinning disk seek back and
+//! forth between files. In auto mode the scan roots are checked for
+//! rotational media and the I/O thread count is picked accordingly:
+//!
+//! | Storage                          | I/O threads |
+//! |----------------------------------|-------------|
+//! | Rotational (HDD)                 | 2           |
+//! | Solid state (SSD, NVMe)          | 8           |
+//! | Unknown (network, other systems) | 4 (default) |
+//!
+//! If roots live on different disks, any rotational root makes the whole
+//! scan conservative, and a mix of solid-state and unknown roots falls back
+//! to the default.
+//!
+//! Detection is best-effort. On Linux the device holding each root is looked
+//! up in `/sys/dev/block/<major>:<minor>/queue/rotational` (or its parent
+//! disk's entry for a partition). Network and virtual file systems have no
+//! block device and report as unknown, as does every root on other platforms.
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::scanner::disk::{parse_rotational, DiskKind};
+//!
+//! assert_eq!(parse_rotational("1\n"), DiskKind::Rotational);
+//! assert_eq!(parse_rotational("0\n").io_threads(), 8);
+//! ```
+
+use std::path::Path;
+
+/// I/O threads for rotational media.
+pub const HDD_IO_THREADS: usize = 2;
+
+/// I/O threads for solid-state media.
+pub const SSD_IO_THREADS: usize = 8;
+
+/// I/O threads when the storage type is unknown (the `--io-threads` default).
+pub const DEFAULT_IO_THREADS: usize = 4;
+
+/// Storage type backing a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiskKind {
+    /// Spinning disk, where parallel reads cause seeking
+    Rotational,
+    /// SSD or NVMe
+    SolidState,
+    /// Could not be determined
+    #[default]
+    Unknown,
+}
+
+impl DiskKind {
+    /// I/O thread count suited to this storage type.
+    #[must_use]
+    pub fn io_threads(self) -> usize {
+        match self {
+            Self::Rotational => HDD_IO_THREADS,
+            Self::SolidState => SSD_IO_THREADS,
+            Self::Unknown => DEFAULT_IO_THREADS,
+        }
+    }
+
+    /// Storage type to assume for a scan spanning both `self` and `other`.
+    #[must_use]
+    pub fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Rotational, _) | (_, Self::Rotational) => Self::Rotational,
+            (Self::SolidState, Self::SolidState) => Self::SolidState,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl std::fmt::Display for DiskKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rotational => write!(f, "rotational"),
+            Self::SolidState => write!(f, "solid-state"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Interpret the contents of a sysfs `queue/rotational` file.
+#[must_use]
+pub fn parse_rotational(contents: &str) -> DiskKind {
+    match contents.trim() {
+        "1" => DiskKind::Rotational,
+        "0" => DiskKind::SolidState,
+        _ => DiskKind::Unknown,
+    }
+}
+
+/// Storage type of the device holding `path`.
+#[must_use]
+pub fn detect_disk_kind(path: &Path) -> DiskKind {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let Ok(metadata) = std::fs::metadata(path) else {
+            return DiskKind::Unknown;
+        };
+        let (major, minor) = split_dev(metadata.dev());
+        if major == 0 {
+            // Anonymous devices: NFS, tmpfs, FUSE, btrfs subvolumes
+            return DiskKind::Unknown;
+        }
+        let device = Path::new("/sys/dev/block").join(format!("{major}:{minor}"));
+        // Partitions have no queue of their own; their parent disk does
+        ["queue/rotational", "../queue/rotational"]
+            .iter()
+            .find_map(|rel| std::fs::read_to_string(device.join(rel)).ok())
+            .map_or(DiskKind::Unknown, |contents| parse_rotational(&contents))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = path;
+        DiskKind::Unknown
+    }
+}
+
+/// Storage type to assume for a scan over all of `paths`.
+#[must_use]
+pub fn detect_disk_kind_all<P: AsRef<Path>>(paths: &[P]) -> DiskKind {
+    paths
+        .iter()
+        .map(|p| detect_disk_kind(p.as_ref()))
+        .reduce(DiskKind::combine)
+        .unwrap_or_default()
+}
+
+/// Split a Linux `dev_t` into major and minor numbers (glibc encoding).
+#[cfg(target_os = "linux")]
+fn split_dev(dev: u64) -> (u64, u64) {
+    let major = ((dev >> 32) & 0xffff_f000) | ((dev >> 8) & 0x0000_0fff);
+    let minor = ((dev >> 12) & 0xffff_ff00) | (dev & 0x0000_00ff);
+    (major, minor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rotational() {
+        assert_eq!(parse_rotational("1\n"), DiskKind::Rotational);
+        assert_eq!(parse_rotational("0"), DiskKind::SolidState);
+        assert_eq!(parse_rotational(" 0 \n"), DiskKind::SolidState);
+        assert_eq!(parse_rotational(""), DiskKind::Unknown);
+        assert_eq!(parse_rotational("yes"), DiskKind::Unknown);
+    }
+
+    #[test]
+    fn test_disk_kind_thread_decision() {
+        use DiskKind::{Rotational, SolidState, Unknown};
+
+        assert_eq!(DiskKind::Rotational.io_threads(), HDD_IO_THREADS);
+        assert_eq!(DiskKind::SolidState.io_threads(), SSD_IO_THREADS);
+        assert_eq!(DiskKind::Unknown.io_threads(), DEFAULT_IO_THREADS);
+
+        assert_eq!(SolidState.combine(SolidState), SolidState);
+        assert_eq!(SolidState.combine(Rotational), Rotational);
+        assert_eq!(Unknown.combine(Rotational), Rotational);
+        assert_eq!(SolidState.combine(Unknown), Unknown);
+        assert_eq!(detect_disk_kind_all::<&Path>(&[]), Unknown);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_split_dev() {
+        assert_eq!(split_dev(0x0801), (8, 1));
+        assert_eq!(split_dev(0xfd00), (253, 0));
+        // Large minor numbers spill into the high bits
+        assert_eq!(split_dev(0x0100_0103), (1, 0x1003));
+    }
+
+    #[test]
+    fn test_detect_disk_kind_missing_path() {
+        assert_eq!(
+            detect_disk_kind(Path::new("/definitely/not/a/real/path")),
+            DiskKind::Unknown
+        );
+    }
+}
@@ -67,6 +67,8 @@ impl DocumentExtractor {
             "pdf" => Self::extract_pdf(path),
             "docx" => Self::extract_docx(path),
             "txt" | "md" => Self::extract_plain_text(path),
+            // Content sniffing may classify a file with a misleading extension as a PDF
+            _ if super::detect_file_kind(path) == super::FileKind::Pdf => Self::extract_pdf(path),
             _ => Err(DocumentError::UnsupportedFormat(extension)),
         }
     }
@@ -226,6 +226,24 @@ impl HardlinkTracker {
     }
 }
 
+/// Identify the file a directory entry points to, as `(device, inode)`.
+///
+/// Every hardlink to the same file returns the same pair. Returns `None`
+/// on platforms where the metadata does not expose an inode.
+#[must_use]
+pub fn inode_id(metadata: &Metadata) -> Option<(u64, u64)> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some((metadata.dev(), metadata.ino()))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
 /// Platform-specific inode key for hardlink detection.
 ///
 /// On Unix, this is (device_id, inode).
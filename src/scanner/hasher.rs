@@ -11,7 +11,7 @@
 //! BLAKE3 is optimized for modern hardware:
 //! - Single-threaded: ~8.4 GB/s on modern CPUs
 //! - Multi-threaded: ~92 GB/s on 16 cores (with rayon feature)
-//! - Uses 64KB buffer for optimal I/O throughput
+//! - Uses a 64KB read buffer by default (configurable via [`Hasher::with_read_buffer_size`])
 //!
 //! # Example
 //!
@@ -30,23 +30,155 @@
 //! // For small files, prehash equals full hash
 //! let small_hash = hasher.prehash(Path::new("small_file.txt")).unwrap();
 //! ```
+//!
+//! # Sampling
+//!
+//! For very large files where reading every byte is too slow,
+//! [`Hasher::sample_hash`] hashes only three fixed windows (start, middle and
+//! end) plus the file length. Sampled hashes are domain-separated so they can
+//! never be mistaken for a full hash, but they only prove that the sampled
+//! regions match; use paranoid verification when that is not enough.
+//!
+//! # Sparse Files
+//!
+//! With [`Hasher::with_sparse_aware`], [`Hasher::full_hash`] asks the file
+//! system for the file's data regions (`SEEK_DATA`/`SEEK_HOLE`, Linux only)
+//! and reads only those. Holes are fed to BLAKE3 as the zero bytes they read
+//! as, so the hash is that of the logical content: a sparse file, a fully
+//! allocated copy and a non-sparse-aware hash of either all agree. Where
+//! hole detection is unavailable the file is streamed as usual.
+//!
+//! # Alternate Streams
+//!
+//! Hashes cover a file's main data stream only. With
+//! [`Hasher::with_include_streams`], [`Hasher::fold_streams`] mixes the name
+//! and content hash of every NTFS alternate data stream (Windows) or the
+//! resource fork (macOS) into a finished hash, so files that differ only in
+//! those streams no longer match. Files without streams keep their plain
+//! hash, and on other platforms folding is a no-op.
+//!
+//! # Retries
+//!
+//! Network shares and failing disks sometimes return errors that go away on
+//! a second read. With [`Hasher::with_retry`], [`Hasher::prehash`],
+//! [`Hasher::full_hash`] and [`Hasher::sample_hash`] retry such transient
+//! I/O errors, sleeping [`RetryConfig::backoff_ms`] before the first retry
+//! and doubling the delay after each further failure. Missing files,
+//! permission errors and shutdown interruptions fail immediately.
 
 use std::fs::File;
-use std::io::{BufReader, ErrorKind, Read};
+use std::io::{BufReader, ErrorKind, Read, Seek, SeekFrom};
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use sysinfo::{MemoryRefreshKind, RefreshKind, System};
 
 use super::HashError;
+use crate::progress::ProgressCallback;
 
 /// Default size for prehash - first 4KB of the file.
 /// This is enough to detect most different files while minimizing I/O.
 pub const PREHASH_SIZE: usize = 4 * 1024; // 4KB
 
+/// Default streaming read buffer size (64KB).
+pub const DEFAULT_READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Smallest accepted read buffer size (4KB).
+pub const MIN_READ_BUFFER_SIZE: usize = 4 * 1024;
+
+/// Largest accepted read buffer size (64MB).
+pub const MAX_READ_BUFFER_SIZE: usize = 64 * 1024 * 1024;
+
+/// Size of each window read by [`Hasher::sample_hash`] (64KB).
+pub const SAMPLE_WINDOW_SIZE: usize = 64 * 1024;
+
+/// Domain tag mixed into every sampled hash so it never equals a full hash.
+const SAMPLE_HASH_DOMAIN: &[u8] = b"rustdupe:sample:v1";
+
+/// Domain tag mixed into hashes that fold in alternate streams.
+const STREAMS_HASH_DOMAIN: &[u8] = b"rustdupe:streams:v1";
+
+/// Zero bytes fed to BLAKE3 in place of sparse file holes.
+#[cfg(target_os = "linux")]
+static HOLE_ZEROS: [u8; DEFAULT_READ_BUFFER_SIZE] = [0; DEFAULT_READ_BUFFER_SIZE];
+
 /// BLAKE3 hash output size (32 bytes / 256 bits).
 pub type Hash = [u8; 32];
 
+/// Default delay before the first retry of a failed hash (100ms).
+pub const DEFAULT_RETRY_BACKOFF_MS: u64 = 100;
+
+/// Retry policy for transient I/O errors during hashing.
+///
+/// `attempts` counts every try including the first, so the default of 1
+/// never retries.
+///
+/// # Example
+///
+/// ```
+/// use rustdupe::scanner::hasher::{Hasher, RetryConfig};
+///
+/// // Up to three retries: 100ms, 200ms and 400ms apart
+/// let retry = RetryConfig::with_retries(3);
+/// assert_eq!(retry.attempts, 4);
+/// let hasher = Hasher::new().with_retry(retry);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Maximum number of tries per hash, including the first
+    pub attempts: u32,
+    /// Delay before the first retry in milliseconds
+    pub backoff_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            attempts: 1,
+            backoff_ms: DEFAULT_RETRY_BACKOFF_MS,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Allow `retries` retries after the first try, with the default backoff.
+    #[must_use]
+    pub fn with_retries(retries: u32) -> Self {
+        Self {
+            attempts: retries.saturating_add(1),
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (starting at 1).
+    #[must_use]
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 1u64 << retry.saturating_sub(1).min(16);
+        Duration::from_millis(self.backoff_ms.saturating_mul(factor))
+    }
+
+    /// Whether `error` may go away on another try.
+    ///
+    /// Missing files, permission errors and interruptions (shutdown) are
+    /// permanent, as are errors about the request itself.
+    #[must_use]
+    pub fn is_transient(error: &HashError) -> bool {
+        match error {
+            HashError::Io { source, .. } => !matches!(
+                source.kind(),
+                ErrorKind::Interrupted
+                    | ErrorKind::InvalidInput
+                    | ErrorKind::InvalidData
+                    | ErrorKind::Unsupported
+                    | ErrorKind::NotFound
+                    | ErrorKind::PermissionDenied
+            ),
+            _ => false,
+        }
+    }
+}
+
 /// File hasher using BLAKE3 algorithm with streaming support.
 ///
 /// The hasher is stateless and can be shared across threads.
@@ -56,7 +188,7 @@ pub type Hash = [u8; 32];
 ///
 /// `Hasher` is `Send + Sync` and can be safely shared across threads.
 /// Each hashing operation uses its own temporary state.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Hasher {
     /// Size of data to read for prehash operations
     prehash_size: usize,
@@ -72,8 +204,38 @@ pub struct Hasher {
     buffer_max: usize,
     /// Enable adaptive buffer sizing
     adaptive_buffer: bool,
+    /// Skip reading holes of sparse files in full hashes
+    sparse_aware: bool,
+    /// Fold alternate data streams and resource forks into hashes
+    include_streams: bool,
+    /// Retry policy for transient I/O errors
+    retry: RetryConfig,
     /// Optional shutdown flag for graceful termination
     shutdown_flag: Option<Arc<AtomicBool>>,
+    /// Optional callback receiving bytes read during full hashing
+    progress_callback: Option<Arc<dyn ProgressCallback>>,
+}
+
+impl std::fmt::Debug for Hasher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hasher")
+            .field("prehash_size", &self.prehash_size)
+            .field("mmap", &self.mmap)
+            .field("mmap_threshold", &self.mmap_threshold)
+            .field("buffer_size", &self.buffer_size)
+            .field("buffer_min", &self.buffer_min)
+            .field("buffer_max", &self.buffer_max)
+            .field("adaptive_buffer", &self.adaptive_buffer)
+            .field("sparse_aware", &self.sparse_aware)
+            .field("include_streams", &self.include_streams)
+            .field("retry", &self.retry)
+            .field("shutdown_flag", &self.shutdown_flag)
+            .field(
+                "progress_callback",
+                &self.progress_callback.as_ref().map(|_| "<callback>"),
+            )
+            .finish()
+    }
 }
 
 impl Default for Hasher {
@@ -100,10 +262,14 @@ impl Hasher {
             mmap: false,
             mmap_threshold: 64 * 1024 * 1024,
             buffer_size: None,
-            buffer_min: 64 * 1024,
+            buffer_min: DEFAULT_READ_BUFFER_SIZE,
             buffer_max: 16 * 1024 * 1024,
             adaptive_buffer: true,
+            sparse_aware: false,
+            include_streams: false,
+            retry: RetryConfig::default(),
             shutdown_flag: None,
+            progress_callback: None,
         }
     }
 
@@ -131,10 +297,14 @@ impl Hasher {
             mmap: false,
             mmap_threshold: 64 * 1024 * 1024,
             buffer_size: None,
-            buffer_min: 64 * 1024,
+            buffer_min: DEFAULT_READ_BUFFER_SIZE,
             buffer_max: 16 * 1024 * 1024,
             adaptive_buffer: true,
+            sparse_aware: false,
+            include_streams: false,
+            retry: RetryConfig::default(),
             shutdown_flag: None,
+            progress_callback: None,
         }
     }
 
@@ -145,6 +315,38 @@ impl Hasher {
         self
     }
 
+    /// Set the streaming read buffer size used by `prehash` and `full_hash`.
+    ///
+    /// The size is validated with [`normalize_read_buffer_size`], so values
+    /// that are not a power of two or fall outside
+    /// [`MIN_READ_BUFFER_SIZE`]..=[`MAX_READ_BUFFER_SIZE`] are clamped with a
+    /// warning. Takes precedence over adaptive sizing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustdupe::scanner::hasher::Hasher;
+    /// let hasher = Hasher::new().with_read_buffer_size(1024 * 1024);
+    /// assert_eq!(hasher.read_buffer_size(), Some(1024 * 1024));
+    /// ```
+    #[must_use]
+    pub fn with_read_buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = Some(normalize_read_buffer_size(size));
+        self
+    }
+
+    /// The explicitly configured read buffer size, if any.
+    #[must_use]
+    pub fn read_buffer_size(&self) -> Option<usize> {
+        self.buffer_size
+    }
+
+    /// Number of leading bytes hashed by [`Hasher::prehash`].
+    #[must_use]
+    pub fn prehash_size(&self) -> usize {
+        self.prehash_size
+    }
+
     /// Set minimum I/O buffer size.
     #[must_use]
     pub fn with_buffer_min(mut self, min: usize) -> Self {
@@ -173,6 +375,44 @@ impl Hasher {
         self
     }
 
+    /// Enable or disable sparse-file awareness for full hashes.
+    ///
+    /// Holes are not read from disk but hashed as zeros, so the result is
+    /// the same as without this option. Only effective on Linux.
+    #[must_use]
+    pub fn with_sparse_aware(mut self, enabled: bool) -> Self {
+        self.sparse_aware = enabled;
+        self
+    }
+
+    /// Enable or disable folding alternate streams into hashes.
+    ///
+    /// See [`Hasher::fold_streams`]. Only effective on Windows and macOS.
+    #[must_use]
+    pub fn with_include_streams(mut self, enabled: bool) -> Self {
+        self.include_streams = enabled;
+        self
+    }
+
+    /// Whether alternate streams are folded into hashes.
+    #[must_use]
+    pub fn include_streams(&self) -> bool {
+        self.include_streams
+    }
+
+    /// Set the retry policy for transient I/O errors.
+    #[must_use]
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// The retry policy for transient I/O errors.
+    #[must_use]
+    pub fn retry(&self) -> RetryConfig {
+        self.retry
+    }
+
     /// Set the threshold for memory-mapped I/O.
     #[must_use]
     pub fn with_mmap_threshold(mut self, threshold: u64) -> Self {
@@ -204,6 +444,26 @@ impl Hasher {
         self
     }
 
+    /// Set a callback to receive [`on_bytes_read`] as full hashes stream
+    /// file content.
+    ///
+    /// Prehashing does not report bytes. With memory-mapped hashing the
+    /// whole file is reported in one call once it has been hashed.
+    ///
+    /// [`on_bytes_read`]: ProgressCallback::on_bytes_read
+    #[must_use]
+    pub fn with_progress_callback(mut self, callback: Arc<dyn ProgressCallback>) -> Self {
+        self.progress_callback = Some(callback);
+        self
+    }
+
+    /// Report bytes read to the progress callback, if any.
+    fn report_bytes_read(&self, bytes: u64) {
+        if let Some(ref callback) = self.progress_callback {
+            callback.on_bytes_read(bytes);
+        }
+    }
+
     /// Check if shutdown has been requested.
     fn is_shutdown_requested(&self) -> bool {
         self.shutdown_flag
@@ -211,6 +471,37 @@ impl Hasher {
             .is_some_and(|f| f.load(Ordering::SeqCst))
     }
 
+    /// Run `op`, retrying transient errors according to the retry policy.
+    fn with_retries<T>(
+        &self,
+        path: &Path,
+        mut op: impl FnMut() -> Result<T, HashError>,
+    ) -> Result<T, HashError> {
+        let mut attempt = 1;
+        loop {
+            match op() {
+                Err(e)
+                    if attempt < self.retry.attempts
+                        && RetryConfig::is_transient(&e)
+                        && !self.is_shutdown_requested() =>
+                {
+                    let delay = self.retry.delay(attempt);
+                    log::debug!(
+                        "Retrying {} in {:?} (attempt {} of {} failed: {})",
+                        path.display(),
+                        delay,
+                        attempt,
+                        self.retry.attempts,
+                        e
+                    );
+                    std::thread::sleep(delay);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// Compute hash of the first N bytes of a file (prehash).
     ///
     /// Prehash is used in Phase 2 of duplicate detection to quickly
@@ -241,7 +532,7 @@ impl Hasher {
     /// println!("Prehash: {:x?}", hash);
     /// ```
     pub fn prehash(&self, path: &Path) -> Result<Hash, HashError> {
-        self.hash_bytes(path, Some(self.prehash_size))
+        self.with_retries(path, || self.hash_bytes(path, Some(self.prehash_size)))
     }
 
     /// Compute hash of the entire file content.
@@ -275,11 +566,25 @@ impl Hasher {
     /// println!("Full hash: {:x?}", hash);
     /// ```
     pub fn full_hash(&self, path: &Path) -> Result<Hash, HashError> {
+        self.with_retries(path, || self.full_hash_once(path))
+    }
+
+    /// Compute a full hash with a single try.
+    fn full_hash_once(&self, path: &Path) -> Result<Hash, HashError> {
+        #[cfg(target_os = "linux")]
+        if self.sparse_aware {
+            if let Some(hash) = self.hash_sparse(path)? {
+                return Ok(hash);
+            }
+        }
         if self.mmap {
             let metadata = std::fs::metadata(path).map_err(|e| self.map_io_error(path, e))?;
             if metadata.len() >= self.mmap_threshold {
                 match self.hash_mmap(path) {
-                    Ok(hash) => return Ok(hash),
+                    Ok(hash) => {
+                        self.report_bytes_read(metadata.len());
+                        return Ok(hash);
+                    }
                     Err(e) => {
                         log::debug!(
                             "Mmap hashing failed for {}, falling back to streaming: {}",
@@ -294,6 +599,119 @@ impl Hasher {
         self.hash_bytes(path, None)
     }
 
+    /// Compute a sampled hash from fixed windows of a large file.
+    ///
+    /// Hashes [`SAMPLE_WINDOW_SIZE`] bytes at the start, middle and end of
+    /// the file together with `size`, so files of different lengths never
+    /// match. Files no larger than three windows are hashed completely.
+    ///
+    /// The result is domain-separated from [`Hasher::full_hash`]: a sampled
+    /// hash never equals the full hash of the same content.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the file to hash
+    /// * `size` - File length in bytes, as recorded during the scan
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rustdupe::scanner::hasher::Hasher;
+    /// use std::path::Path;
+    ///
+    /// let hasher = Hasher::new();
+    /// let path = Path::new("movie.mkv");
+    /// let size = std::fs::metadata(path).unwrap().len();
+    /// let hash = hasher.sample_hash(path, size).unwrap();
+    /// ```
+    pub fn sample_hash(&self, path: &Path, size: u64) -> Result<Hash, HashError> {
+        self.with_retries(path, || self.sample_hash_once(path, size))
+    }
+
+    /// Compute a sampled hash with a single try.
+    fn sample_hash_once(&self, path: &Path, size: u64) -> Result<Hash, HashError> {
+        let mut file = File::open(path).map_err(|e| self.map_io_error(path, e))?;
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(SAMPLE_HASH_DOMAIN);
+        hasher.update(&size.to_le_bytes());
+
+        let mut buffer = Vec::with_capacity(SAMPLE_WINDOW_SIZE);
+        for offset in sample_offsets(size) {
+            if self.is_shutdown_requested() {
+                log::debug!("Hash operation interrupted for: {}", path.display());
+                return Err(HashError::Io {
+                    path: path.to_path_buf(),
+                    source: Arc::new(std::io::Error::new(
+                        ErrorKind::Interrupted,
+                        "Operation interrupted",
+                    )),
+                });
+            }
+
+            file.seek(SeekFrom::Start(offset))
+                .map_err(|e| self.map_io_error(path, e))?;
+            buffer.clear();
+            (&mut file)
+                .take(SAMPLE_WINDOW_SIZE as u64)
+                .read_to_end(&mut buffer)
+                .map_err(|e| self.map_io_error(path, e))?;
+
+            hasher.update(&buffer);
+            self.report_bytes_read(buffer.len() as u64);
+        }
+
+        Ok(*hasher.finalize().as_bytes())
+    }
+
+    /// Fold the alternate streams of `path` into its main-stream `hash`.
+    ///
+    /// Each stream contributes its name and the BLAKE3 hash of its content,
+    /// in name order. `hash` is returned unchanged when stream hashing is
+    /// disabled or the file has no alternate streams, so cached main-stream
+    /// hashes can be folded after lookup.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rustdupe::scanner::hasher::Hasher;
+    /// use std::path::Path;
+    ///
+    /// let hasher = Hasher::new().with_include_streams(true);
+    /// let path = Path::new("document.docx");
+    /// let hash = hasher.fold_streams(path, hasher.full_hash(path).unwrap()).unwrap();
+    /// ```
+    pub fn fold_streams(&self, path: &Path, hash: Hash) -> Result<Hash, HashError> {
+        if !self.include_streams {
+            return Ok(hash);
+        }
+        let mut streams = alternate_streams(path).map_err(|e| self.map_io_error(path, e))?;
+        if streams.is_empty() {
+            return Ok(hash);
+        }
+        streams.sort();
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(STREAMS_HASH_DOMAIN);
+        hasher.update(&hash);
+        for (name, stream_path) in &streams {
+            let file = File::open(stream_path).map_err(|e| self.map_io_error(path, e))?;
+            let mut stream_hasher = blake3::Hasher::new();
+            stream_hasher
+                .update_reader(BufReader::new(file))
+                .map_err(|e| self.map_io_error(path, e))?;
+            hasher.update(&(name.len() as u64).to_le_bytes());
+            hasher.update(name.as_bytes());
+            hasher.update(stream_hasher.finalize().as_bytes());
+        }
+        log::trace!(
+            "Folded {} alternate stream(s) into hash of {}",
+            streams.len(),
+            path.display()
+        );
+        Ok(*hasher.finalize().as_bytes())
+    }
+
     /// Compute hash using memory-mapped I/O and rayon for parallelism.
     fn hash_mmap(&self, path: &Path) -> Result<Hash, HashError> {
         // Check shutdown flag before starting expensive parallel hash
@@ -315,11 +733,92 @@ impl Hasher {
         Ok(*hasher.finalize().as_bytes())
     }
 
+    /// Hash a sparse file by reading only its data regions.
+    ///
+    /// Returns `Ok(None)` when the file has no holes or the file system
+    /// cannot report them, leaving the caller to stream it normally.
+    #[cfg(target_os = "linux")]
+    fn hash_sparse(&self, path: &Path) -> Result<Option<Hash>, HashError> {
+        use std::os::unix::io::AsRawFd;
+
+        let mut file = File::open(path).map_err(|e| self.map_io_error(path, e))?;
+        let size = file
+            .metadata()
+            .map_err(|e| self.map_io_error(path, e))?
+            .len();
+        let fd = file.as_raw_fd();
+        // Next data (or hole) offset at or after `offset`; `None` past the
+        // last data region or when the file system does not support it
+        let seek = |offset: u64, whence: libc::c_int| -> Option<u64> {
+            let offset = libc::off_t::try_from(offset).ok()?;
+            // SAFETY: lseek only repositions `fd`, which `file` keeps open
+            let result = unsafe { libc::lseek(fd, offset, whence) };
+            u64::try_from(result).ok()
+        };
+
+        match seek(0, libc::SEEK_HOLE) {
+            Some(first_hole) if first_hole < size => {}
+            _ => return Ok(None),
+        }
+
+        let interrupted = || HashError::Io {
+            path: path.to_path_buf(),
+            source: Arc::new(std::io::Error::new(
+                ErrorKind::Interrupted,
+                "Operation interrupted",
+            )),
+        };
+        let mut hasher = blake3::Hasher::new();
+        let mut buffer = vec![0u8; self.calculate_buffer_size(size, None)];
+        let mut pos = 0;
+        while pos < size {
+            let data = seek(pos, libc::SEEK_DATA).unwrap_or(size).min(size);
+            let mut hole_len = data - pos;
+            while hole_len > 0 {
+                let n = hole_len.min(HOLE_ZEROS.len() as u64);
+                hasher.update(&HOLE_ZEROS[..n as usize]);
+                hole_len -= n;
+            }
+            if data >= size {
+                break;
+            }
+
+            let hole = seek(data, libc::SEEK_HOLE).unwrap_or(size).min(size);
+            file.seek(SeekFrom::Start(data))
+                .map_err(|e| self.map_io_error(path, e))?;
+            let mut remaining = hole - data;
+            while remaining > 0 {
+                if self.is_shutdown_requested() {
+                    log::debug!("Hash operation interrupted for: {}", path.display());
+                    return Err(interrupted());
+                }
+                let want = buffer
+                    .len()
+                    .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+                let read = file
+                    .read(&mut buffer[..want])
+                    .map_err(|e| self.map_io_error(path, e))?;
+                if read == 0 {
+                    // Truncated while hashing
+                    return Err(
+                        self.map_io_error(path, std::io::Error::from(ErrorKind::UnexpectedEof))
+                    );
+                }
+                hasher.update(&buffer[..read]);
+                self.report_bytes_read(read as u64);
+                remaining -= read as u64;
+            }
+            pos = hole;
+        }
+        Ok(Some(*hasher.finalize().as_bytes()))
+    }
+
     /// Calculate the optimal buffer size for a file.
     fn calculate_buffer_size(&self, file_size: u64, max_read: Option<usize>) -> usize {
         // 1. Manual override takes highest priority
         if let Some(manual) = self.buffer_size {
-            return manual;
+            // Never allocate more than a limited read (like prehash) needs
+            return max_read.map_or(manual, |max| manual.min(max));
         }
 
         // 2. If adaptive is disabled, use the minimum buffer size
@@ -400,8 +899,20 @@ impl Hasher {
         let file = File::open(path).map_err(|e| self.map_io_error(path, e))?;
 
         // Use buffered reader for better I/O performance
-        let mut reader = BufReader::with_capacity(buf_size, file);
+        let reader = BufReader::with_capacity(buf_size, file);
+        self.hash_reader(path, reader, buf_size, max_bytes)
+    }
 
+    /// Stream `reader` into BLAKE3, reading at most `max_bytes` if given.
+    ///
+    /// Errors are reported against `path`.
+    fn hash_reader<R: Read>(
+        &self,
+        path: &Path,
+        mut reader: R,
+        buf_size: usize,
+        max_bytes: Option<usize>,
+    ) -> Result<Hash, HashError> {
         // Create BLAKE3 hasher
         let mut hasher = blake3::Hasher::new();
 
@@ -446,6 +957,9 @@ impl Hasher {
             // Update hash with read bytes
             hasher.update(&buffer[..bytes_read]);
             total_read += bytes_read as u64;
+            if limit.is_none() {
+                self.report_bytes_read(bytes_read as u64);
+            }
 
             // Check if we've read enough for limited hash
             if let Some(max) = limit {
@@ -527,6 +1041,143 @@ impl Hasher {
     }
 }
 
+/// Alternate data streams of `path` as `(name, path to open)` pairs.
+///
+/// The unnamed main stream is not listed. Files on file systems without
+/// named streams (FAT, network shares) report none.
+#[cfg(windows)]
+fn alternate_streams(path: &Path) -> std::io::Result<Vec<(String, std::path::PathBuf)>> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Foundation::{GetLastError, ERROR_HANDLE_EOF, INVALID_HANDLE_VALUE};
+    use windows_sys::Win32::Storage::FileSystem::{
+        FindClose, FindFirstStreamW, FindNextStreamW, FindStreamInfoStandard,
+        WIN32_FIND_STREAM_DATA,
+    };
+
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    // SAFETY: WIN32_FIND_STREAM_DATA is plain data; all zeros is valid
+    let mut data: WIN32_FIND_STREAM_DATA = unsafe { std::mem::zeroed() };
+    let data_ptr = std::ptr::addr_of_mut!(data).cast();
+    // SAFETY: `wide` is NUL-terminated and `data_ptr` points at `data`
+    let handle = unsafe { FindFirstStreamW(wide.as_ptr(), FindStreamInfoStandard, data_ptr, 0) };
+    if handle == INVALID_HANDLE_VALUE {
+        // SAFETY: reads the calling thread's last error only
+        let error = unsafe { GetLastError() };
+        log::trace!(
+            "No stream enumeration for {} (error {})",
+            path.display(),
+            error
+        );
+        return Ok(Vec::new());
+    }
+
+    let mut streams = Vec::new();
+    let result = loop {
+        let len = data
+            .cStreamName
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(data.cStreamName.len());
+        let name = String::from_utf16_lossy(&data.cStreamName[..len]);
+        if name != "::$DATA" {
+            let mut stream_path = path.as_os_str().to_owned();
+            stream_path.push(&name);
+            streams.push((name, std::path::PathBuf::from(stream_path)));
+        }
+        // SAFETY: `handle` stays valid until FindClose below
+        if unsafe { FindNextStreamW(handle, data_ptr) } == 0 {
+            // SAFETY: reads the calling thread's last error only
+            let error = unsafe { GetLastError() };
+            break if error == ERROR_HANDLE_EOF {
+                Ok(streams)
+            } else {
+                Err(std::io::Error::from_raw_os_error(error as i32))
+            };
+        }
+    };
+    // SAFETY: `handle` came from FindFirstStreamW and is closed once
+    unsafe { FindClose(handle) };
+    result
+}
+
+/// The resource fork of `path`, if it has a non-empty one.
+#[cfg(target_os = "macos")]
+fn alternate_streams(path: &Path) -> std::io::Result<Vec<(String, std::path::PathBuf)>> {
+    let fork = path.join("..namedfork/rsrc");
+    match std::fs::metadata(&fork) {
+        Ok(metadata) if metadata.len() > 0 => Ok(vec![("rsrc".to_string(), fork)]),
+        Ok(_) => Ok(Vec::new()),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Other platforms have no alternate streams.
+#[cfg(not(any(windows, target_os = "macos")))]
+fn alternate_streams(_path: &Path) -> std::io::Result<Vec<(String, std::path::PathBuf)>> {
+    Ok(Vec::new())
+}
+
+/// Offsets of the windows read by [`Hasher::sample_hash`].
+///
+/// Small files are covered by consecutive windows; larger files use exactly
+/// three windows at the start, middle and end.
+fn sample_offsets(size: u64) -> Vec<u64> {
+    let window = SAMPLE_WINDOW_SIZE as u64;
+    if size <= window * 3 {
+        (0..size.max(1)).step_by(SAMPLE_WINDOW_SIZE).collect()
+    } else {
+        vec![0, size / 2 - window / 2, size - window]
+    }
+}
+
+/// Validate a read buffer size, clamping it to a sane value.
+///
+/// Sizes outside [`MIN_READ_BUFFER_SIZE`]..=[`MAX_READ_BUFFER_SIZE`] are
+/// clamped to the nearest bound, and other non-power-of-two sizes are rounded
+/// to the nearest power of two, so decimal sizes like `1M` become 1MiB. A
+/// warning is logged whenever the value is adjusted.
+///
+/// # Example
+///
+/// ```
+/// use rustdupe::scanner::hasher::normalize_read_buffer_size;
+///
+/// assert_eq!(normalize_read_buffer_size(1024 * 1024), 1024 * 1024);
+/// assert_eq!(normalize_read_buffer_size(1_000_000), 1024 * 1024);
+/// assert_eq!(normalize_read_buffer_size(1), 4 * 1024);
+/// ```
+#[must_use]
+pub fn normalize_read_buffer_size(size: usize) -> usize {
+    let clamped = size.clamp(MIN_READ_BUFFER_SIZE, MAX_READ_BUFFER_SIZE);
+    let normalized = if clamped.is_power_of_two() {
+        clamped
+    } else {
+        // The bounds are powers of two, so both neighbours stay in range
+        let lower = 1 << (usize::BITS - 1 - clamped.leading_zeros());
+        let upper = lower << 1;
+        if clamped - lower < upper - clamped {
+            lower
+        } else {
+            upper
+        }
+    };
+    if normalized != size {
+        log::warn!(
+            "Read buffer size {} is not a power of two between {} and {} bytes; using {}",
+            size,
+            MIN_READ_BUFFER_SIZE,
+            MAX_READ_BUFFER_SIZE,
+            normalized
+        );
+    }
+    normalized
+}
+
 /// Format a hash as a hex string for display.
 ///
 /// # Example
@@ -782,6 +1433,58 @@ mod tests {
         assert_eq!(hash_2k, full);
     }
 
+    #[test]
+    fn test_sample_hash_distinguishes_middle() {
+        let dir = TempDir::new().unwrap();
+        let size = SAMPLE_WINDOW_SIZE * 8;
+        let original = vec![7u8; size];
+        let mut changed = original.clone();
+        changed[size / 2] = 8;
+
+        let a = create_test_file(&dir, "a.bin", &original);
+        let b = create_test_file(&dir, "b.bin", &original);
+        let c = create_test_file(&dir, "c.bin", &changed);
+
+        let hasher = Hasher::new();
+        let hash_a = hasher.sample_hash(&a, size as u64).unwrap();
+        assert_eq!(hash_a, hasher.sample_hash(&b, size as u64).unwrap());
+        assert_ne!(hash_a, hasher.sample_hash(&c, size as u64).unwrap());
+    }
+
+    #[test]
+    fn test_sample_hash_never_equals_full_hash() {
+        let dir = TempDir::new().unwrap();
+        let content = b"small enough to be read completely";
+        let file = create_test_file(&dir, "small.bin", content);
+
+        let hasher = Hasher::new();
+        let sampled = hasher.sample_hash(&file, content.len() as u64).unwrap();
+        assert_ne!(sampled, hasher.full_hash(&file).unwrap());
+    }
+
+    #[test]
+    fn test_sample_hash_includes_length() {
+        let dir = TempDir::new().unwrap();
+        let file = create_test_file(&dir, "len.bin", &[1u8; 1024]);
+
+        let hasher = Hasher::new();
+        assert_ne!(
+            hasher.sample_hash(&file, 1024).unwrap(),
+            hasher.sample_hash(&file, 1025).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_sample_offsets() {
+        let window = SAMPLE_WINDOW_SIZE as u64;
+        assert_eq!(sample_offsets(0), vec![0]);
+        assert_eq!(sample_offsets(window * 2), vec![0, window]);
+        assert_eq!(
+            sample_offsets(window * 10),
+            vec![0, window * 5 - window / 2, window * 9]
+        );
+    }
+
     #[test]
     fn test_shutdown_flag_interrupts_hash() {
         let dir = TempDir::new().unwrap();
@@ -804,6 +1507,229 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_full_hash_reports_bytes_read() {
+        use std::sync::atomic::AtomicU64;
+
+        #[derive(Default)]
+        struct ByteCounter {
+            bytes: AtomicU64,
+            calls: AtomicU64,
+        }
+
+        impl ProgressCallback for ByteCounter {
+            fn on_phase_start(&self, _: &str, _: usize) {}
+            fn on_progress(&self, _: usize, _: &str) {}
+            fn on_phase_end(&self, _: &str) {}
+            fn on_bytes_read(&self, bytes: u64) {
+                self.bytes.fetch_add(bytes, Ordering::Relaxed);
+                self.calls.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let dir = TempDir::new().unwrap();
+        let content = vec![0xA5u8; 300 * 1024 + 17];
+        let path = create_test_file(&dir, "large.bin", &content);
+
+        let counter = Arc::new(ByteCounter::default());
+        let hasher = Hasher::new()
+            .with_read_buffer_size(64 * 1024)
+            .with_progress_callback(counter.clone());
+
+        hasher.full_hash(&path).unwrap();
+        assert_eq!(counter.bytes.load(Ordering::Relaxed), content.len() as u64);
+        assert!(counter.calls.load(Ordering::Relaxed) > 1);
+
+        // Prehash reads are not reported
+        hasher.prehash(&path).unwrap();
+        assert_eq!(counter.bytes.load(Ordering::Relaxed), content.len() as u64);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_sparse_aware_hash_matches_dense_copy() {
+        let dir = TempDir::new().unwrap();
+        let len: u64 = 4 * 1024 * 1024;
+
+        // 4 MiB with data only at the start, middle and end
+        let sparse = dir.path().join("sparse.img");
+        let file = File::create(&sparse).unwrap();
+        file.set_len(len).unwrap();
+        drop(file);
+        let mut content = vec![0u8; len as usize];
+        for offset in [0, 2 * 1024 * 1024, len as usize - 5] {
+            content[offset..offset + 5].copy_from_slice(b"hello");
+            let mut file = std::fs::OpenOptions::new()
+                .write(true)
+                .open(&sparse)
+                .unwrap();
+            file.seek(SeekFrom::Start(offset as u64)).unwrap();
+            file.write_all(b"hello").unwrap();
+        }
+        let dense = create_test_file(&dir, "dense.img", &content);
+
+        let plain = Hasher::new();
+        let aware = Hasher::new().with_sparse_aware(true);
+        let expected = plain.full_hash(&dense).unwrap();
+        assert_eq!(aware.full_hash(&sparse).unwrap(), expected);
+        assert_eq!(aware.full_hash(&dense).unwrap(), expected);
+        assert_eq!(plain.full_hash(&sparse).unwrap(), expected);
+
+        // A hole-only file hashes like the same number of zero bytes
+        let empty = dir.path().join("holes.img");
+        File::create(&empty).unwrap().set_len(100_000).unwrap();
+        assert_eq!(
+            aware.full_hash(&empty).unwrap(),
+            *blake3::hash(&vec![0u8; 100_000]).as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_fold_streams_without_streams_keeps_hash() {
+        let dir = TempDir::new().unwrap();
+        let path = create_test_file(&dir, "plain.txt", b"main stream only");
+        let hash = Hasher::new().full_hash(&path).unwrap();
+
+        let streams = Hasher::new().with_include_streams(true);
+        assert!(streams.include_streams());
+        assert_eq!(streams.fold_streams(&path, hash).unwrap(), hash);
+        assert_eq!(Hasher::new().fold_streams(&path, hash).unwrap(), hash);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_fold_streams_distinguishes_alternate_data_streams() {
+        let dir = TempDir::new().unwrap();
+        let a = create_test_file(&dir, "a.txt", b"same main content");
+        let b = create_test_file(&dir, "b.txt", b"same main content");
+        let c = create_test_file(&dir, "c.txt", b"same main content");
+        std::fs::write(dir.path().join("a.txt:tag"), b"one").unwrap();
+        std::fs::write(dir.path().join("b.txt:tag"), b"two").unwrap();
+        std::fs::write(dir.path().join("c.txt:tag"), b"one").unwrap();
+
+        let listed = alternate_streams(&a).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].0, ":tag:$DATA");
+
+        let plain = Hasher::new();
+        let main = plain.full_hash(&a).unwrap();
+        assert_eq!(plain.full_hash(&b).unwrap(), main);
+
+        let streams = Hasher::new().with_include_streams(true);
+        let fold = |path: &Path| {
+            let hash = streams.full_hash(path).unwrap();
+            streams.fold_streams(path, hash).unwrap()
+        };
+        assert_ne!(fold(&a), main);
+        assert_ne!(fold(&a), fold(&b));
+        assert_eq!(fold(&a), fold(&c));
+    }
+
+    /// Reader whose reads all fail with `kind` while `fail` is set.
+    struct FlakyReader<'a> {
+        data: &'a [u8],
+        fail: bool,
+        kind: ErrorKind,
+    }
+
+    impl Read for FlakyReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.fail {
+                return Err(std::io::Error::new(self.kind, "flaky read"));
+            }
+            self.data.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_retry_recovers_from_transient_errors() {
+        let data = b"content behind a flaky network share";
+        let path = Path::new("flaky.bin");
+        let hasher = Hasher::new().with_retry(RetryConfig {
+            attempts: 3,
+            backoff_ms: 1,
+        });
+
+        let mut tries = 0;
+        let hash = hasher
+            .with_retries(path, || {
+                tries += 1;
+                let reader = FlakyReader {
+                    data,
+                    fail: tries <= 2,
+                    kind: ErrorKind::Other,
+                };
+                hasher.hash_reader(path, reader, 16, None)
+            })
+            .unwrap();
+        assert_eq!(hash, *blake3::hash(data).as_bytes());
+        assert_eq!(tries, 3);
+
+        // One attempt short: the last error is returned
+        let hasher = hasher.with_retry(RetryConfig {
+            attempts: 2,
+            backoff_ms: 1,
+        });
+        let mut tries = 0;
+        let result = hasher.with_retries(path, || {
+            tries += 1;
+            let reader = FlakyReader {
+                data,
+                fail: tries <= 2,
+                kind: ErrorKind::Other,
+            };
+            hasher.hash_reader(path, reader, 16, None)
+        });
+        assert!(matches!(result, Err(HashError::Io { .. })));
+        assert_eq!(tries, 2);
+    }
+
+    #[test]
+    fn test_retry_skips_permanent_errors() {
+        let path = Path::new("locked.bin");
+        let hasher = Hasher::new().with_retry(RetryConfig {
+            attempts: 5,
+            backoff_ms: 1,
+        });
+        for kind in [
+            ErrorKind::PermissionDenied,
+            ErrorKind::NotFound,
+            ErrorKind::Interrupted,
+        ] {
+            let mut tries = 0;
+            let result = hasher.with_retries(path, || {
+                tries += 1;
+                let reader = FlakyReader {
+                    data: b"",
+                    fail: true,
+                    kind,
+                };
+                hasher.hash_reader(path, reader, 16, None)
+            });
+            assert!(result.is_err());
+            assert_eq!(tries, 1, "{:?} should not be retried", kind);
+        }
+
+        // Missing files fail without retrying through the public API too
+        let dir = TempDir::new().unwrap();
+        let missing = dir.path().join("missing.bin");
+        assert!(matches!(
+            hasher.full_hash(&missing),
+            Err(HashError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_retry_config_backoff() {
+        let retry = RetryConfig::with_retries(3);
+        assert_eq!(retry.attempts, 4);
+        assert_eq!(RetryConfig::default().attempts, 1);
+        assert_eq!(retry.delay(1), Duration::from_millis(100));
+        assert_eq!(retry.delay(2), Duration::from_millis(200));
+        assert_eq!(retry.delay(3), Duration::from_millis(400));
+        assert_eq!(RetryConfig::with_retries(u32::MAX).attempts, u32::MAX);
+    }
+
     #[test]
     fn test_hasher_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -846,6 +1772,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normalize_read_buffer_size() {
+        assert_eq!(normalize_read_buffer_size(64 * 1024), 64 * 1024);
+        assert_eq!(normalize_read_buffer_size(1024 * 1024), 1024 * 1024);
+        assert_eq!(normalize_read_buffer_size(1_000_000), 1024 * 1024);
+        assert_eq!(normalize_read_buffer_size(100_000), 128 * 1024);
+        assert_eq!(normalize_read_buffer_size(80_000), 64 * 1024);
+        assert_eq!(normalize_read_buffer_size(0), MIN_READ_BUFFER_SIZE);
+        assert_eq!(normalize_read_buffer_size(usize::MAX), MAX_READ_BUFFER_SIZE);
+    }
+
+    #[test]
+    fn test_read_buffer_size_honored_for_large_file() {
+        let dir = TempDir::new().unwrap();
+
+        // 100KB of non-repeating content spans many 4KB reads
+        let content: Vec<u8> = (0..100 * 1024u32).map(|i| (i * 31 % 251) as u8).collect();
+        let file = create_test_file(&dir, "big.bin", &content);
+
+        let hasher = Hasher::new().with_read_buffer_size(MIN_READ_BUFFER_SIZE);
+        let file_size = content.len() as u64;
+        assert_eq!(
+            hasher.calculate_buffer_size(file_size, None),
+            MIN_READ_BUFFER_SIZE
+        );
+        assert!(content.len() > MIN_READ_BUFFER_SIZE);
+
+        let expected = *blake3::hash(&content).as_bytes();
+        assert_eq!(hasher.full_hash(&file).unwrap(), expected);
+        assert_eq!(Hasher::new().full_hash(&file).unwrap(), expected);
+
+        // Prehash reads are capped by the prehash size, not the buffer
+        let big_buffer = Hasher::new().with_read_buffer_size(1024 * 1024);
+        assert_eq!(
+            big_buffer.calculate_buffer_size(file_size, Some(PREHASH_SIZE)),
+            PREHASH_SIZE
+        );
+        assert_eq!(
+            big_buffer.prehash(&file).unwrap(),
+            *blake3::hash(&content[..PREHASH_SIZE]).as_bytes()
+        );
+    }
+
     #[test]
     #[should_panic(expected = "prehash_size must be greater than 0")]
     fn test_zero_prehash_size_panics() {
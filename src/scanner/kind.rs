@@ -0,0 +1,331 @@
+//! Content-type detection from leading magic bytes.
+//!
+//! Extensions lie: a renamed `.png` saved as `photo.jpg`, or a download
+//! without any extension, would otherwise be misclassified. [`detect_file_kind`]
+//! reads the first few hundred bytes of a file and matches them against a
+//! small built-in signature table covering the formats RustDupe's category
+//! filters and similarity detection care about.
+//!
+//! Detection is only used when it can change the answer: for files without an
+//! extension, or for every file when `--sniff-content` is set. The result is
+//! cached on [`FileEntry::file_kind`](super::FileEntry::file_kind).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::scanner::{detect_file_kind, FileCategory, FileKind};
+//! use std::path::Path;
+//!
+//! let kind = detect_file_kind(Path::new("/downloads/photo"));
+//! if kind.category() == Some(FileCategory::Images) {
+//!     println!("Looks like an image: {}", kind);
+//! }
+//! ```
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::FileCategory;
+
+/// Number of leading bytes read for detection (covers the tar header magic).
+const SNIFF_LEN: usize = 512;
+
+/// File format detected from content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileKind {
+    /// JPEG image
+    Jpeg,
+    /// PNG image
+    Png,
+    /// GIF image
+    Gif,
+    /// BMP image
+    Bmp,
+    /// TIFF image
+    Tiff,
+    /// WebP image
+    Webp,
+    /// MP4 / QuickTime video
+    Mp4,
+    /// Matroska / WebM video
+    Matroska,
+    /// AVI video
+    Avi,
+    /// MP3 audio
+    Mp3,
+    /// FLAC audio
+    Flac,
+    /// Ogg audio
+    Ogg,
+    /// WAV audio
+    Wav,
+    /// PDF document
+    Pdf,
+    /// ZIP archive (also the container for docx, xlsx, odt, ...)
+    Zip,
+    /// Gzip archive
+    Gzip,
+    /// Bzip2 archive
+    Bzip2,
+    /// XZ archive
+    Xz,
+    /// 7-Zip archive
+    SevenZip,
+    /// RAR archive
+    Rar,
+    /// Tar archive
+    Tar,
+    /// No known signature matched (or the file could not be read)
+    Unknown,
+}
+
+impl FileKind {
+    /// Get the category this format belongs to, if any.
+    #[must_use]
+    pub fn category(self) -> Option<FileCategory> {
+        match self {
+            Self::Jpeg | Self::Png | Self::Gif | Self::Bmp | Self::Tiff | Self::Webp => {
+                Some(FileCategory::Images)
+            }
+            Self::Mp4 | Self::Matroska | Self::Avi => Some(FileCategory::Videos),
+            Self::Mp3 | Self::Flac | Self::Ogg | Self::Wav => Some(FileCategory::Audio),
+            Self::Pdf => Some(FileCategory::Documents),
+            Self::Zip
+            | Self::Gzip
+            | Self::Bzip2
+            | Self::Xz
+            | Self::SevenZip
+            | Self::Rar
+            | Self::Tar => Some(FileCategory::Archives),
+            Self::Unknown => None,
+        }
+    }
+
+    /// Whether this format is a generic container that other formats build on.
+    ///
+    /// Office documents (docx, xlsx, odt, ...) are ZIP files, so a ZIP
+    /// signature does not override an extension naming a known category.
+    #[must_use]
+    pub fn is_container(self) -> bool {
+        self == Self::Zip
+    }
+
+    /// Detect the format from the leading bytes of a file.
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let starts = |magic: &[u8]| bytes.starts_with(magic);
+        let at = |offset: usize, magic: &[u8]| {
+            bytes
+                .get(offset..offset + magic.len())
+                .is_some_and(|b| b == magic)
+        };
+
+        if starts(&[0xFF, 0xD8, 0xFF]) {
+            Self::Jpeg
+        } else if starts(b"\x89PNG\r\n\x1a\n") {
+            Self::Png
+        } else if starts(b"GIF87a") || starts(b"GIF89a") {
+            Self::Gif
+        } else if starts(b"II*\0") || starts(b"MM\0*") {
+            Self::Tiff
+        } else if starts(b"RIFF") && at(8, b"WEBP") {
+            Self::Webp
+        } else if starts(b"RIFF") && at(8, b"AVI ") {
+            Self::Avi
+        } else if starts(b"RIFF") && at(8, b"WAVE") {
+            Self::Wav
+        } else if at(4, b"ftyp") {
+            Self::Mp4
+        } else if starts(&[0x1A, 0x45, 0xDF, 0xA3]) {
+            Self::Matroska
+        } else if starts(b"ID3")
+            || (bytes.len() >= 2 && bytes[0] == 0xFF && bytes[1] & 0xE0 == 0xE0)
+        {
+            Self::Mp3
+        } else if starts(b"fLaC") {
+            Self::Flac
+        } else if starts(b"OggS") {
+            Self::Ogg
+        } else if starts(b"%PDF-") {
+            Self::Pdf
+        } else if starts(b"PK\x03\x04") || starts(b"PK\x05\x06") {
+            Self::Zip
+        } else if starts(&[0x1F, 0x8B]) {
+            Self::Gzip
+        } else if starts(b"BZh") {
+            Self::Bzip2
+        } else if starts(b"\xFD7zXZ\0") {
+            Self::Xz
+        } else if starts(b"7z\xBC\xAF\x27\x1C") {
+            Self::SevenZip
+        } else if starts(b"Rar!\x1A\x07") {
+            Self::Rar
+        } else if at(257, b"ustar") {
+            Self::Tar
+        } else if starts(b"BM") && bytes.len() >= 14 {
+            // "BM" alone is too common in text; require a full file header
+            Self::Bmp
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+impl std::fmt::Display for FileKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Jpeg => "jpeg",
+            Self::Png => "png",
+            Self::Gif => "gif",
+            Self::Bmp => "bmp",
+            Self::Tiff => "tiff",
+            Self::Webp => "webp",
+            Self::Mp4 => "mp4",
+            Self::Matroska => "matroska",
+            Self::Avi => "avi",
+            Self::Mp3 => "mp3",
+            Self::Flac => "flac",
+            Self::Ogg => "ogg",
+            Self::Wav => "wav",
+            Self::Pdf => "pdf",
+            Self::Zip => "zip",
+            Self::Gzip => "gzip",
+            Self::Bzip2 => "bzip2",
+            Self::Xz => "xz",
+            Self::SevenZip => "7z",
+            Self::Rar => "rar",
+            Self::Tar => "tar",
+            Self::Unknown => "unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Detect a file's format from its leading magic bytes.
+///
+/// Unreadable files are reported as [`FileKind::Unknown`].
+#[must_use]
+pub fn detect_file_kind(path: &Path) -> FileKind {
+    let mut buffer = Vec::with_capacity(SNIFF_LEN);
+    match File::open(path).and_then(|f| f.take(SNIFF_LEN as u64).read_to_end(&mut buffer)) {
+        Ok(_) => FileKind::from_bytes(&buffer),
+        Err(e) => {
+            log::debug!("Could not sniff {}: {}", path.display(), e);
+            FileKind::Unknown
+        }
+    }
+}
+
+/// Resolve the category of `path`, preferring a detected `kind` over the
+/// extension.
+///
+/// The extension decides when nothing was detected, when the content is not
+/// recognized, or when the content is a generic container (ZIP) and the
+/// extension names a known category.
+#[must_use]
+pub fn resolve_category(path: &Path, kind: Option<FileKind>) -> Option<FileCategory> {
+    let by_extension = FileCategory::from_path(path);
+    match kind {
+        Some(kind) if kind.is_container() && by_extension.is_some() => by_extension,
+        Some(kind) => kind.category().or(by_extension),
+        None => by_extension,
+    }
+}
+
+/// Whether `path` has a non-empty file extension.
+#[must_use]
+pub fn has_extension(path: &Path) -> bool {
+    path.extension().is_some_and(|e| !e.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    const ZIP_HEADER: &[u8] = b"PK\x03\x04\x14\0\0\0";
+
+    #[test]
+    fn test_from_bytes_signatures() {
+        assert_eq!(
+            FileKind::from_bytes(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            FileKind::Jpeg
+        );
+        assert_eq!(FileKind::from_bytes(PNG_HEADER), FileKind::Png);
+        assert_eq!(FileKind::from_bytes(b"GIF89a...."), FileKind::Gif);
+        assert_eq!(
+            FileKind::from_bytes(b"RIFF\0\0\0\0WEBPVP8 "),
+            FileKind::Webp
+        );
+        assert_eq!(FileKind::from_bytes(b"RIFF\0\0\0\0WAVEfmt "), FileKind::Wav);
+        assert_eq!(FileKind::from_bytes(b"\0\0\0\x18ftypmp42"), FileKind::Mp4);
+        assert_eq!(FileKind::from_bytes(b"%PDF-1.7\n"), FileKind::Pdf);
+        assert_eq!(FileKind::from_bytes(ZIP_HEADER), FileKind::Zip);
+        assert_eq!(FileKind::from_bytes(b"hello world"), FileKind::Unknown);
+        assert_eq!(FileKind::from_bytes(b""), FileKind::Unknown);
+
+        let mut tar = vec![0u8; 512];
+        tar[257..262].copy_from_slice(b"ustar");
+        assert_eq!(FileKind::from_bytes(&tar), FileKind::Tar);
+    }
+
+    #[test]
+    fn test_detect_contradicting_extension() {
+        let temp = TempDir::new().unwrap();
+        let fake_jpg = temp.path().join("photo.jpg");
+        let fake_txt = temp.path().join("notes.txt");
+        let bare = temp.path().join("photo");
+        fs::write(&fake_jpg, b"%PDF-1.4\nnot an image").unwrap();
+        fs::write(&fake_txt, PNG_HEADER).unwrap();
+        fs::write(&bare, PNG_HEADER).unwrap();
+
+        assert_eq!(detect_file_kind(&fake_jpg), FileKind::Pdf);
+        assert_eq!(
+            resolve_category(&fake_jpg, Some(FileKind::Pdf)),
+            Some(FileCategory::Documents)
+        );
+        assert_eq!(
+            resolve_category(&fake_txt, Some(detect_file_kind(&fake_txt))),
+            Some(FileCategory::Images)
+        );
+        assert_eq!(FileCategory::from_path(&bare), None);
+        assert_eq!(
+            resolve_category(&bare, Some(detect_file_kind(&bare))),
+            Some(FileCategory::Images)
+        );
+    }
+
+    #[test]
+    fn test_resolve_category_fallbacks() {
+        // Office documents are ZIP containers: keep the extension's category
+        assert_eq!(
+            resolve_category(Path::new("report.docx"), Some(FileKind::Zip)),
+            Some(FileCategory::Documents)
+        );
+        assert_eq!(
+            resolve_category(Path::new("bundle"), Some(FileKind::Zip)),
+            Some(FileCategory::Archives)
+        );
+        // Unrecognized content (plain text) falls back to the extension
+        assert_eq!(
+            resolve_category(Path::new("notes.txt"), Some(FileKind::Unknown)),
+            Some(FileCategory::Documents)
+        );
+        assert_eq!(resolve_category(Path::new("README"), None), None);
+    }
+
+    #[test]
+    fn test_detect_unreadable_file_is_unknown() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(
+            detect_file_kind(&temp.path().join("missing")),
+            FileKind::Unknown
+        );
+    }
+}
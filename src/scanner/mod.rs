@@ -11,6 +11,9 @@
 //! The scanner is divided into submodules:
 //! - [`walker`]: Directory traversal and file discovery
 //! - [`hasher`]: BLAKE3 file hashing (streaming)
+//! - [`kind`]: Content-type detection from magic bytes
+//! - [`owner`]: File ownership filters (Unix)
+//! - [`disk`]: Rotational/solid-state detection for `--io-threads auto`
 //!
 //! # Example
 //!
@@ -35,32 +38,40 @@
 //! }
 //! ```
 
+pub mod disk;
 pub mod document;
 pub mod hardlink;
 pub mod hasher;
+pub mod kind;
+pub mod owner;
 pub mod path_utils;
 pub mod perceptual;
 pub mod walker;
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 // Re-export main types
 pub use document::{DocumentError, DocumentExtractor};
 pub use hardlink::HardlinkTracker;
-pub use hasher::{hash_to_hex, hex_to_hash, Hash, Hasher, PREHASH_SIZE};
+pub use hasher::{
+    hash_to_hex, hex_to_hash, normalize_read_buffer_size, Hash, Hasher, RetryConfig, PREHASH_SIZE,
+    SAMPLE_WINDOW_SIZE,
+};
 pub use image_hasher::ImageHash;
+pub use kind::{detect_file_kind, FileKind};
 pub use path_utils::{
-    is_nfc, normalize_path_str, normalize_path_str_cow, normalize_pathbuf, path_key, paths_equal,
-    paths_equal_normalized,
+    is_nfc, normalize_filename, normalize_path_str, normalize_path_str_cow, normalize_pathbuf,
+    path_key, path_key_with_case, path_starts_with, paths_equal, paths_equal_normalized,
+    paths_equal_normalized_with_case, paths_equal_with_case,
 };
 pub use perceptual::{
     DocumentSimilarityIndex, PerceptualAlgorithm, PerceptualError, PerceptualHasher,
     SimilarityIndex,
 };
 use regex::Regex;
-pub use walker::{MultiWalker, Walker};
+pub use walker::{precount_files, MultiWalker, Walker};
 
 /// File categories for filtering.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -78,6 +89,30 @@ pub enum FileCategory {
 }
 
 impl FileCategory {
+    /// Every category, in declaration order.
+    pub const ALL: [FileCategory; 5] = [
+        FileCategory::Images,
+        FileCategory::Videos,
+        FileCategory::Audio,
+        FileCategory::Documents,
+        FileCategory::Archives,
+    ];
+
+    /// Classify a path by its (case-insensitive) extension.
+    ///
+    /// Returns `None` if the extension belongs to no category.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path
+            .extension()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_lowercase())?;
+
+        Self::ALL
+            .into_iter()
+            .find(|category| category.extensions().contains(&extension.as_str()))
+    }
+
     /// Get the list of extensions for this category.
     pub fn extensions(&self) -> &'static [&'static str] {
         match self {
@@ -93,6 +128,18 @@ impl FileCategory {
     }
 }
 
+impl std::fmt::Display for FileCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileCategory::Images => write!(f, "images"),
+            FileCategory::Videos => write!(f, "videos"),
+            FileCategory::Audio => write!(f, "audio"),
+            FileCategory::Documents => write!(f, "documents"),
+            FileCategory::Archives => write!(f, "archives"),
+        }
+    }
+}
+
 /// Metadata for a discovered file.
 ///
 /// Contains all information needed for duplicate detection,
@@ -122,6 +169,16 @@ pub struct FileEntry {
     /// Optional document fingerprint for similarity detection (SimHash)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub document_fingerprint: Option<u64>,
+    /// Format detected from magic bytes (None if the file was not sniffed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_kind: Option<FileKind>,
+    /// Resolved target when this entry is a followed file symlink
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symlink_target: Option<PathBuf>,
+    /// Device and inode, recorded when hardlinks are reported so every
+    /// link to the same file can be recognized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inode: Option<(u64, u64)>,
 }
 
 pub mod perceptual_hash_serde {
@@ -171,6 +228,9 @@ impl FileEntry {
             group_name: None,
             perceptual_hash: None,
             document_fingerprint: None,
+            file_kind: None,
+            symlink_target: None,
+            inode: None,
         }
     }
 
@@ -186,6 +246,9 @@ impl FileEntry {
             group_name: Some(group_name),
             perceptual_hash: None,
             document_fingerprint: None,
+            file_kind: None,
+            symlink_target: None,
+            inode: None,
         }
     }
 
@@ -204,34 +267,33 @@ impl FileEntry {
         self.document_fingerprint = Some(fingerprint);
     }
 
-    /// Check if this file is likely an image based on its extension.
+    /// Detect and cache the file's format from its magic bytes.
+    ///
+    /// Files without an extension are always sniffed; others only when
+    /// `sniff_content` is set. A cached result is reused.
+    pub fn sniff_kind(&mut self, sniff_content: bool) -> Option<FileKind> {
+        if self.file_kind.is_none() && (sniff_content || !kind::has_extension(&self.path)) {
+            self.file_kind = Some(detect_file_kind(&self.path));
+        }
+        self.file_kind
+    }
+
+    /// Get the file's category, preferring the sniffed format over the extension.
     #[must_use]
-    pub fn is_image(&self) -> bool {
-        let extension = self
-            .path
-            .extension()
-            .and_then(|s| s.to_str())
-            .map(|s| s.to_lowercase())
-            .unwrap_or_default();
+    pub fn category(&self) -> Option<FileCategory> {
+        kind::resolve_category(&self.path, self.file_kind)
+    }
 
-        FileCategory::Images
-            .extensions()
-            .contains(&extension.as_str())
+    /// Check if this file is likely an image based on its content or extension.
+    #[must_use]
+    pub fn is_image(&self) -> bool {
+        self.category() == Some(FileCategory::Images)
     }
 
-    /// Check if this file is likely a document based on its extension.
+    /// Check if this file is likely a document based on its content or extension.
     #[must_use]
     pub fn is_document(&self) -> bool {
-        let extension = self
-            .path
-            .extension()
-            .and_then(|s| s.to_str())
-            .map(|s| s.to_lowercase())
-            .unwrap_or_default();
-
-        FileCategory::Documents
-            .extensions()
-            .contains(&extension.as_str())
+        self.category() == Some(FileCategory::Documents)
     }
 }
 
@@ -240,9 +302,23 @@ impl FileEntry {
 /// Controls filtering, symlink handling, and other walk behavior.
 #[derive(Debug, Clone, Default)]
 pub struct WalkerConfig {
-    /// Follow symbolic links during traversal.
-    /// Warning: May cause infinite loops with symlink cycles.
-    pub follow_symlinks: bool,
+    /// Descend into symlinked directories during traversal.
+    ///
+    /// Each directory is descended into at most once, so symlink cycles are
+    /// broken and reported as [`ScanError::SymlinkCycle`].
+    pub follow_dir_symlinks: bool,
+
+    /// Scan the targets of symlinked files.
+    ///
+    /// The resolved target is recorded in [`FileEntry::symlink_target`]. A
+    /// symlink whose target lies inside the scan root is skipped (the walk
+    /// reaches the target itself), as are further symlinks to a target that
+    /// was already yielded, so no file is counted twice.
+    pub follow_file_symlinks: bool,
+
+    /// Report symlinks whose target does not exist as
+    /// [`ScanError::BrokenSymlink`] instead of skipping them silently.
+    pub report_broken_symlinks: bool,
 
     /// Skip hidden files and directories (names starting with `.`).
     pub skip_hidden: bool,
@@ -261,10 +337,26 @@ pub struct WalkerConfig {
     /// Only include files modified before this time.
     pub older_than: Option<SystemTime>,
 
+    /// Skip files modified within this many seconds of the walk, since they
+    /// may still be open or being written.
+    pub settle_seconds: Option<u64>,
+
+    /// Only include files owned by these user ids (Unix only).
+    pub owner_uids: Option<Vec<u32>>,
+
+    /// Skip files owned by these user ids (Unix only).
+    pub exclude_owner_uids: Vec<u32>,
+
     /// Glob patterns to ignore (gitignore-style).
     /// These are applied in addition to any .gitignore files.
     pub ignore_patterns: Vec<String>,
 
+    /// Directories whose subtrees are pruned without being read.
+    ///
+    /// A bare name such as `node_modules` matches any directory with that
+    /// name; anything else is matched by canonicalized path prefix.
+    pub exclude_dirs: Vec<PathBuf>,
+
     /// Regex patterns to include (filename must match at least one).
     pub regex_include: Vec<Regex>,
 
@@ -273,6 +365,23 @@ pub struct WalkerConfig {
 
     /// File categories to include (if empty, all types are included).
     pub file_categories: Vec<FileCategory>,
+
+    /// Explicit file extensions to include, lowercase and without a leading dot.
+    ///
+    /// Combined with `file_categories` as a union: a file is included if it
+    /// matches either. If both are empty, all types are included.
+    pub extensions: Vec<String>,
+
+    /// Include zero-byte files instead of skipping them.
+    pub include_empty: bool,
+
+    /// Yield hardlinks to already-seen files (flagged with
+    /// [`FileEntry::is_hardlink`]) instead of skipping them.
+    pub include_hardlinks: bool,
+
+    /// Classify files for category filters by their magic bytes instead of
+    /// trusting the extension. Files without an extension are always sniffed.
+    pub sniff_content: bool,
 }
 
 impl WalkerConfig {
@@ -280,7 +389,7 @@ impl WalkerConfig {
     ///
     /// # Arguments
     ///
-    /// * `follow_symlinks` - Whether to follow symbolic links
+    /// * `follow_symlinks` - Whether to follow symbolic links (files and directories)
     /// * `skip_hidden` - Whether to skip hidden files
     /// * `min_size` - Minimum file size filter
     /// * `max_size` - Maximum file size filter
@@ -298,23 +407,55 @@ impl WalkerConfig {
         ignore_patterns: Vec<String>,
     ) -> Self {
         Self {
-            follow_symlinks,
+            follow_dir_symlinks: follow_symlinks,
+            follow_file_symlinks: follow_symlinks,
+            report_broken_symlinks: false,
             skip_hidden,
             min_size,
             max_size,
             newer_than,
             older_than,
+            settle_seconds: None,
+            owner_uids: None,
+            exclude_owner_uids: Vec::new(),
             ignore_patterns,
+            exclude_dirs: Vec::new(),
             regex_include: Vec::new(),
             regex_exclude: Vec::new(),
             file_categories: Vec::new(),
+            extensions: Vec::new(),
+            include_empty: false,
+            include_hardlinks: false,
+            sniff_content: false,
         }
     }
 
-    /// Set whether to follow symbolic links.
+    /// Set whether to follow symbolic links to both files and directories.
     #[must_use]
     pub fn with_follow_symlinks(mut self, follow: bool) -> Self {
-        self.follow_symlinks = follow;
+        self.follow_dir_symlinks = follow;
+        self.follow_file_symlinks = follow;
+        self
+    }
+
+    /// Set whether to descend into symlinked directories.
+    #[must_use]
+    pub fn with_follow_dir_symlinks(mut self, follow: bool) -> Self {
+        self.follow_dir_symlinks = follow;
+        self
+    }
+
+    /// Set whether to scan the targets of symlinked files.
+    #[must_use]
+    pub fn with_follow_file_symlinks(mut self, follow: bool) -> Self {
+        self.follow_file_symlinks = follow;
+        self
+    }
+
+    /// Set whether to report symlinks whose target does not exist.
+    #[must_use]
+    pub fn with_report_broken_symlinks(mut self, report: bool) -> Self {
+        self.report_broken_symlinks = report;
         self
     }
 
@@ -353,6 +494,32 @@ impl WalkerConfig {
         self
     }
 
+    /// Set the settle window for recently modified files.
+    #[must_use]
+    pub fn with_settle_seconds(mut self, seconds: Option<u64>) -> Self {
+        self.settle_seconds = seconds;
+        self
+    }
+
+    /// Set the owner filters: files must be owned by one of `only` (if set)
+    /// and by none of `exclude`.
+    #[must_use]
+    pub fn with_owner_filter(mut self, only: Option<Vec<u32>>, exclude: Vec<u32>) -> Self {
+        self.owner_uids = only;
+        self.exclude_owner_uids = exclude;
+        self
+    }
+
+    /// Whether a file with `metadata` passes the owner filters.
+    #[must_use]
+    pub fn allows_owner(&self, metadata: &std::fs::Metadata) -> bool {
+        owner::owner_allowed(
+            owner::file_owner(metadata),
+            self.owner_uids.as_deref(),
+            &self.exclude_owner_uids,
+        )
+    }
+
     /// Set glob patterns to ignore.
     #[must_use]
     pub fn with_patterns(mut self, patterns: Vec<String>) -> Self {
@@ -360,6 +527,13 @@ impl WalkerConfig {
         self
     }
 
+    /// Set directories to prune during traversal.
+    #[must_use]
+    pub fn with_exclude_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.exclude_dirs = dirs;
+        self
+    }
+
     /// Set regex include patterns.
     #[must_use]
     pub fn with_regex_include(mut self, regexes: Vec<Regex>) -> Self {
@@ -380,6 +554,64 @@ impl WalkerConfig {
         self.file_categories = categories;
         self
     }
+
+    /// Set explicit file extensions to include.
+    ///
+    /// Extensions are matched case-insensitively; a leading `.` is ignored.
+    #[must_use]
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions
+            .iter()
+            .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+            .filter(|e| !e.is_empty())
+            .collect();
+        self
+    }
+
+    /// Set whether to include zero-byte files.
+    #[must_use]
+    pub fn with_include_empty(mut self, include: bool) -> Self {
+        self.include_empty = include;
+        self
+    }
+
+    /// Set whether to yield hardlinks to already-seen files.
+    #[must_use]
+    pub fn with_include_hardlinks(mut self, include: bool) -> Self {
+        self.include_hardlinks = include;
+        self
+    }
+
+    /// Set whether category filters sniff file content instead of trusting extensions.
+    #[must_use]
+    pub fn with_sniff_content(mut self, sniff: bool) -> Self {
+        self.sniff_content = sniff;
+        self
+    }
+}
+
+/// Whether `modified` lies within the last `settle_seconds` seconds.
+///
+/// Modification times in the future count as unsettled unless the window is
+/// zero. The walker skips unsettled files and deletion refuses them, since
+/// they may still be open or being written.
+///
+/// # Example
+///
+/// ```
+/// use rustdupe::scanner::is_unsettled;
+/// use std::time::{Duration, SystemTime};
+///
+/// let now = SystemTime::now();
+/// assert!(is_unsettled(now, 5));
+/// assert!(!is_unsettled(now - Duration::from_secs(60), 5));
+/// ```
+#[must_use]
+pub fn is_unsettled(modified: SystemTime, settle_seconds: u64) -> bool {
+    match SystemTime::now().duration_since(modified) {
+        Ok(age) => age < std::time::Duration::from_secs(settle_seconds),
+        Err(_) => settle_seconds > 0,
+    }
 }
 
 use std::sync::Arc;
@@ -409,11 +641,47 @@ pub enum ScanError {
         source: Arc<std::io::Error>,
     },
 
+    /// A followed symlink led back to an already visited directory.
+    #[error("Symlink cycle: {path} -> {target} was already visited, skipping")]
+    SymlinkCycle {
+        /// Path of the link that closes the cycle
+        path: PathBuf,
+        /// Directory the link resolves to
+        target: PathBuf,
+    },
+
+    /// A symlink points to a target that does not exist.
+    ///
+    /// Only reported with [`WalkerConfig::report_broken_symlinks`]. This is
+    /// a finding rather than a failure: the duplicate finder collects it in
+    /// `ScanSummary::broken_symlinks`, not in the scan errors.
+    #[error("Broken symlink: {path} -> {target}")]
+    BrokenSymlink {
+        /// Path of the link
+        path: PathBuf,
+        /// Target the link points to, as stored in the link
+        target: PathBuf,
+    },
+
     /// An error occurred during hashing.
     #[error(transparent)]
     HashError(#[from] HashError),
 }
 
+impl ScanError {
+    /// Get the path the error refers to.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::PermissionDenied(path) | Self::NotFound(path) | Self::NotADirectory(path) => path,
+            Self::Io { path, .. }
+            | Self::SymlinkCycle { path, .. }
+            | Self::BrokenSymlink { path, .. } => path,
+            Self::HashError(err) => err.path(),
+        }
+    }
+}
+
 impl PartialEq for ScanError {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -430,6 +698,26 @@ impl PartialEq for ScanError {
                     source: s2,
                 },
             ) => p1 == p2 && s1.kind() == s2.kind() && s1.to_string() == s2.to_string(),
+            (
+                Self::SymlinkCycle {
+                    path: p1,
+                    target: t1,
+                },
+                Self::SymlinkCycle {
+                    path: p2,
+                    target: t2,
+                },
+            ) => p1 == p2 && t1 == t2,
+            (
+                Self::BrokenSymlink {
+                    path: p1,
+                    target: t1,
+                },
+                Self::BrokenSymlink {
+                    path: p2,
+                    target: t2,
+                },
+            ) => p1 == p2 && t1 == t2,
             (Self::HashError(e1), Self::HashError(e2)) => e1 == e2,
             _ => false,
         }
@@ -469,6 +757,17 @@ pub enum HashError {
     },
 }
 
+impl HashError {
+    /// Get the path of the file that failed to hash.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::NotFound(path) | Self::PermissionDenied(path) => path,
+            Self::Io { path, .. } => path,
+        }
+    }
+}
+
 impl PartialEq for HashError {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -505,11 +804,26 @@ mod tests {
         assert!(!entry.is_hardlink);
     }
 
+    #[test]
+    fn test_file_category_from_path() {
+        assert_eq!(
+            FileCategory::from_path(Path::new("/a/photo.JPG")),
+            Some(FileCategory::Images)
+        );
+        assert_eq!(
+            FileCategory::from_path(Path::new("notes.txt")),
+            Some(FileCategory::Documents)
+        );
+        assert_eq!(FileCategory::from_path(Path::new("main.rs")), None);
+        assert_eq!(FileCategory::from_path(Path::new("Makefile")), None);
+    }
+
     #[test]
     fn test_walker_config_default() {
         let config = WalkerConfig::default();
 
-        assert!(!config.follow_symlinks);
+        assert!(!config.follow_dir_symlinks);
+        assert!(!config.follow_file_symlinks);
         assert!(!config.skip_hidden);
         assert!(config.min_size.is_none());
         assert!(config.max_size.is_none());
@@ -528,7 +842,8 @@ mod tests {
             vec!["*.tmp".to_string()],
         );
 
-        assert!(config.follow_symlinks);
+        assert!(config.follow_dir_symlinks);
+        assert!(config.follow_file_symlinks);
         assert!(config.skip_hidden);
         assert_eq!(config.min_size, Some(1024));
         assert_eq!(config.max_size, Some(1_000_000));
@@ -0,0 +1,115 @@
+//! File ownership filters (`--only-owner`, `--exclude-owner`).
+//!
+//! On Unix every file carries the numeric id of its owning user. Owner
+//! filters restrict a scan to files owned by some users, or skip files owned
+//! by others, and deletion re-checks the same filter so a file that changed
+//! hands after the scan is never removed. Other platforms have no comparable
+//! owner id; there the filters are ignored.
+//!
+//! User names are resolved through `/etc/passwd`. Accounts that only exist
+//! in a directory service (LDAP, NIS) must be given by numeric uid.
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::scanner::owner::owner_allowed;
+//!
+//! // Only files owned by uid 1000
+//! assert!(owner_allowed(Some(1000), Some(&[1000]), &[]));
+//! assert!(!owner_allowed(Some(0), Some(&[1000]), &[]));
+//! // Anything but root
+//! assert!(!owner_allowed(Some(0), None, &[0]));
+//! ```
+
+use std::fs::Metadata;
+
+/// Location of the local user database used to resolve user names.
+const PASSWD_PATH: &str = "/etc/passwd";
+
+/// Whether owner filters have any effect on this platform.
+#[must_use]
+pub fn owner_filtering_supported() -> bool {
+    cfg!(unix)
+}
+
+/// Owning user id of a file, or `None` where ownership is not available.
+#[must_use]
+pub fn file_owner(metadata: &Metadata) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some(metadata.uid())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// Whether a file owned by `owner` passes the filters.
+///
+/// `only` lists the allowed owners (`None` allows everyone) and `exclude`
+/// the rejected ones. Files with unknown ownership always pass.
+#[must_use]
+pub fn owner_allowed(owner: Option<u32>, only: Option<&[u32]>, exclude: &[u32]) -> bool {
+    let Some(uid) = owner else {
+        return true;
+    };
+    only.is_none_or(|uids| uids.contains(&uid)) && !exclude.contains(&uid)
+}
+
+/// Resolve a numeric uid or a user name to a uid.
+///
+/// Returns `None` if `spec` is neither a number nor a user listed in
+/// `/etc/passwd`.
+#[must_use]
+pub fn resolve_owner(spec: &str) -> Option<u32> {
+    let spec = spec.trim();
+    if let Ok(uid) = spec.parse() {
+        return Some(uid);
+    }
+    let passwd = std::fs::read_to_string(PASSWD_PATH).ok()?;
+    lookup_passwd(&passwd, spec)
+}
+
+/// Find the uid of `name` in passwd-formatted `contents`.
+fn lookup_passwd(contents: &str, name: &str) -> Option<u32> {
+    contents
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .find_map(|line| {
+            let mut fields = line.split(':');
+            if fields.next()? != name {
+                return None;
+            }
+            fields.nth(1)?.parse().ok()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_allowed() {
+        assert!(owner_allowed(Some(5), None, &[]));
+        assert!(owner_allowed(Some(5), Some(&[5, 6]), &[]));
+        assert!(!owner_allowed(Some(7), Some(&[5, 6]), &[]));
+        assert!(!owner_allowed(Some(5), Some(&[5]), &[5]));
+        assert!(owner_allowed(None, Some(&[5]), &[0]));
+    }
+
+    #[test]
+    fn test_resolve_owner() {
+        assert_eq!(resolve_owner("1000"), Some(1000));
+        assert_eq!(resolve_owner(" 0 "), Some(0));
+
+        let passwd = "# comment\n\
+                      root:x:0:0:root:/root:/bin/sh\n\
+                      alice:x:1001:1001::/home/alice:/bin/bash\n";
+        assert_eq!(lookup_passwd(passwd, "alice"), Some(1001));
+        assert_eq!(lookup_passwd(passwd, "root"), Some(0));
+        assert_eq!(lookup_passwd(passwd, "bob"), None);
+    }
+}
@@ -14,6 +14,14 @@
 //!
 //! Without normalization, these would compare as different paths.
 //!
+//! # Case-insensitive comparison
+//!
+//! Files synced from a case-insensitive share can show up on Linux as both
+//! `Foo.txt` and `foo.txt`. For `--ignore-case-paths`, the `ignore_case`
+//! argument of [`paths_equal_with_case`], [`path_key_with_case`] and
+//! [`path_starts_with`] folds case as well, so reference protection treats
+//! such paths as the same location. Content hashing is unaffected.
+//!
 //! # Example
 //!
 //! ```
@@ -32,6 +40,16 @@ use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use unicode_normalization::UnicodeNormalization;
 
+/// NFC-normalize `s`, lowercasing it too when case is ignored.
+fn comparison_key(s: &str, ignore_case: bool) -> String {
+    let normalized = normalize_path_str(s);
+    if ignore_case {
+        normalized.to_lowercase()
+    } else {
+        normalized
+    }
+}
+
 /// Normalize a path string to NFC (Composed) form.
 ///
 /// This function converts any Unicode string to NFC normalization form,
@@ -154,7 +172,22 @@ pub fn normalize_pathbuf(path: &Path) -> PathBuf {
 /// ```
 #[must_use]
 pub fn paths_equal(a: &str, b: &str) -> bool {
-    normalize_path_str(a) == normalize_path_str(b)
+    paths_equal_with_case(a, b, false)
+}
+
+/// Like [`paths_equal`], but also ignores case when `ignore_case` is set.
+///
+/// # Example
+///
+/// ```
+/// use rustdupe::scanner::path_utils::paths_equal_with_case;
+///
+/// assert!(paths_equal_with_case("Foo.txt", "foo.txt", true));
+/// assert!(!paths_equal_with_case("Foo.txt", "foo.txt", false));
+/// ```
+#[must_use]
+pub fn paths_equal_with_case(a: &str, b: &str, ignore_case: bool) -> bool {
+    comparison_key(a, ignore_case) == comparison_key(b, ignore_case)
 }
 
 /// Check if two [`Path`]s are equal after NFC normalization.
@@ -181,8 +214,15 @@ pub fn paths_equal(a: &str, b: &str) -> bool {
 /// ```
 #[must_use]
 pub fn paths_equal_normalized(a: &Path, b: &Path) -> bool {
+    paths_equal_normalized_with_case(a, b, false)
+}
+
+/// Like [`paths_equal_normalized`], but also ignores case when
+/// `ignore_case` is set.
+#[must_use]
+pub fn paths_equal_normalized_with_case(a: &Path, b: &Path, ignore_case: bool) -> bool {
     match (a.to_str(), b.to_str()) {
-        (Some(a_str), Some(b_str)) => paths_equal(a_str, b_str),
+        (Some(a_str), Some(b_str)) => paths_equal_with_case(a_str, b_str, ignore_case),
         _ => false,
     }
 }
@@ -240,7 +280,113 @@ pub fn is_nfc(s: &str) -> bool {
 /// ```
 #[must_use]
 pub fn path_key(path: &Path) -> String {
-    normalize_path_str(&path.to_string_lossy())
+    path_key_with_case(path, false)
+}
+
+/// Like [`path_key`], but lowercased when `ignore_case` is set.
+#[must_use]
+pub fn path_key_with_case(path: &Path, ignore_case: bool) -> String {
+    comparison_key(&path.to_string_lossy(), ignore_case)
+}
+
+/// Check if `path` is `prefix` or lies beneath it.
+///
+/// Components are compared case-insensitively on Windows and when
+/// `ignore_case` is set.
+///
+/// # Example
+///
+/// ```
+/// use std::path::Path;
+/// use rustdupe::scanner::path_utils::path_starts_with;
+///
+/// assert!(path_starts_with(Path::new("/ref/sub/a.txt"), Path::new("/ref"), false));
+/// assert!(!path_starts_with(Path::new("/reference/a.txt"), Path::new("/ref"), false));
+/// assert!(path_starts_with(Path::new("/Ref/a.txt"), Path::new("/ref"), true));
+/// ```
+#[must_use]
+pub fn path_starts_with(path: &Path, prefix: &Path, ignore_case: bool) -> bool {
+    if cfg!(windows) || ignore_case {
+        // Convert to lowercase PathBuf for reliable component-based comparison
+        let p = PathBuf::from(path.to_string_lossy().to_lowercase());
+        let r = PathBuf::from(prefix.to_string_lossy().to_lowercase());
+        p.starts_with(r)
+    } else {
+        path.starts_with(prefix)
+    }
+}
+
+/// Normalize a filename for similar-name clustering.
+///
+/// Strips the markers that file managers and browsers append to copies so
+/// that `IMG_1234 (1).jpg`, `IMG_1234 copy.jpg` and `IMG_1234 copy 2.JPG` all
+/// map to `img_1234.jpg`. The result is NFC-normalized and lowercased.
+///
+/// Removed markers, repeatedly from the end of the stem:
+/// - a parenthesized counter: `name (2)`, `name(2)`
+/// - a copy marker, optionally followed by a counter: `name copy`,
+///   `name - Copy`, `name_copy`, `name copy 3`
+///
+/// A leading `Copy of ` prefix is removed as well. Digits that are part of
+/// the name itself (`IMG_1234`, `Track 01`) are kept.
+///
+/// # Example
+///
+/// ```
+/// use rustdupe::scanner::path_utils::normalize_filename;
+///
+/// assert_eq!(normalize_filename("IMG_1234 (1).jpg"), "img_1234.jpg");
+/// assert_eq!(normalize_filename("IMG_1234 copy 2.JPG"), "img_1234.jpg");
+/// assert_eq!(normalize_filename("Report - Copy.pdf"), "report.pdf");
+/// ```
+#[must_use]
+pub fn normalize_filename(name: &str) -> String {
+    let name = normalize_path_str(name.trim()).to_lowercase();
+    let (stem, ext) = match name.rfind('.') {
+        Some(i) if i > 0 => (&name[..i], Some(&name[i + 1..])),
+        _ => (name.as_str(), None),
+    };
+
+    let mut stem = stem.strip_prefix("copy of ").unwrap_or(stem).trim_end();
+    loop {
+        let stripped = strip_copy_suffix(stem).trim_end();
+        if stripped.len() == stem.len() || stripped.is_empty() {
+            break;
+        }
+        stem = stripped;
+    }
+
+    match ext {
+        Some(ext) => format!("{}.{}", stem, ext),
+        None => stem.to_string(),
+    }
+}
+
+/// Remove one trailing copy marker from a lowercased filename stem.
+fn strip_copy_suffix(stem: &str) -> &str {
+    // "name (2)" / "name(2)"
+    if let Some(inner) = stem.strip_suffix(')') {
+        if let Some(open) = inner.rfind('(') {
+            let counter = &inner[open + 1..];
+            if !counter.is_empty() && counter.bytes().all(|b| b.is_ascii_digit()) {
+                return &stem[..open];
+            }
+        }
+    }
+
+    // "name copy", "name - copy", "name copy 3"
+    let without_counter = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    let base = match without_counter.strip_suffix(' ') {
+        Some(base) if without_counter.len() < stem.len() => base,
+        _ => stem,
+    };
+    for marker in [" - copy", " copy", "_copy", "-copy"] {
+        if let Some(rest) = base.strip_suffix(marker) {
+            return rest;
+        }
+    }
+
+    stem
 }
 
 #[cfg(test)]
@@ -343,6 +489,45 @@ mod tests {
         assert_eq!(path_key(nfc_path), path_key(nfd_path));
     }
 
+    #[test]
+    fn test_path_starts_with() {
+        let reference = Path::new("/ref/path");
+        assert!(path_starts_with(
+            Path::new("/ref/path/file.txt"),
+            reference,
+            false
+        ));
+        assert!(path_starts_with(Path::new("/ref/path"), reference, false));
+        assert!(!path_starts_with(
+            Path::new("/ref/path_suffix/file.txt"),
+            reference,
+            false
+        ));
+        assert!(!path_starts_with(Path::new("/ref/pat"), reference, false));
+    }
+
+    #[test]
+    fn test_path_starts_with_ignore_case() {
+        let reference = Path::new("/Ref/Path");
+        assert!(path_starts_with(
+            Path::new("/ref/path/file.txt"),
+            reference,
+            true
+        ));
+        assert!(!path_starts_with(
+            Path::new("/ref/path_suffix/file.txt"),
+            reference,
+            true
+        ));
+        if !cfg!(windows) {
+            assert!(!path_starts_with(
+                Path::new("/ref/path/file.txt"),
+                reference,
+                false
+            ));
+        }
+    }
+
     #[test]
     fn test_normalize_path_str_cow_already_nfc() {
         let nfc = "café.txt";
@@ -377,4 +562,40 @@ mod tests {
         let nfd = "\u{1100}\u{1161}.txt";
         assert!(paths_equal(nfc, nfd));
     }
+
+    #[test]
+    fn test_normalize_filename_counters() {
+        assert_eq!(normalize_filename("IMG_1234 (1).jpg"), "img_1234.jpg");
+        assert_eq!(normalize_filename("IMG_1234(12).jpg"), "img_1234.jpg");
+        assert_eq!(normalize_filename("IMG_1234 (1) (2).jpg"), "img_1234.jpg");
+        assert_eq!(normalize_filename("IMG_1234.jpg"), "img_1234.jpg");
+    }
+
+    #[test]
+    fn test_normalize_filename_copy_markers() {
+        assert_eq!(normalize_filename("IMG_1234 copy.jpg"), "img_1234.jpg");
+        assert_eq!(normalize_filename("IMG_1234 copy 2.JPG"), "img_1234.jpg");
+        assert_eq!(normalize_filename("Report - Copy.pdf"), "report.pdf");
+        assert_eq!(normalize_filename("Report - Copy (3).pdf"), "report.pdf");
+        assert_eq!(normalize_filename("notes_copy.txt"), "notes.txt");
+        assert_eq!(normalize_filename("Copy of notes.txt"), "notes.txt");
+    }
+
+    #[test]
+    fn test_normalize_filename_keeps_name_digits() {
+        assert_eq!(normalize_filename("Track 01.mp3"), "track 01.mp3");
+        assert_eq!(normalize_filename("IMG_1234.jpg"), "img_1234.jpg");
+        assert_eq!(normalize_filename("copy.txt"), "copy.txt");
+        assert_eq!(normalize_filename("(1).txt"), "(1).txt");
+        assert_eq!(normalize_filename(".bashrc"), ".bashrc");
+        assert_eq!(normalize_filename("Makefile"), "makefile");
+    }
+
+    #[test]
+    fn test_normalize_filename_unicode() {
+        assert_eq!(
+            normalize_filename("cafe\u{0301} (1).txt"),
+            normalize_filename("café.txt")
+        );
+    }
 }
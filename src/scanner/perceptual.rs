@@ -86,9 +86,15 @@ impl PerceptualHasher {
     }
 
     /// Compute the perceptual hash for an image at the given path.
+    ///
+    /// The decoder is chosen from the file's content, so images with a
+    /// wrong or missing extension still load.
     pub fn compute_hash<P: AsRef<Path>>(&self, path: P) -> Result<ImageHash, PerceptualError> {
         let path = path.as_ref();
-        let img = image::open(path)
+        let img = image::ImageReader::open(path)
+            .and_then(|reader| reader.with_guessed_format())
+            .map_err(image::ImageError::IoError)
+            .and_then(|reader| reader.decode())
             .map_err(|e| PerceptualError::LoadError(path.display().to_string(), e))?;
 
         Ok(self.hasher.hash_image(&img))
@@ -14,7 +14,11 @@
 //! # Features
 //!
 //! - Parallel directory traversal using rayon thread pool
-//! - Configurable symlink following with cycle detection
+//! - Configurable symlink following with cycle detection (directories are
+//!   identified by device and inode on Unix; a link back to an ancestor is a
+//!   cycle, other directories reached twice are read once)
+//! - Symlinked files followed independently of directories, deduplicated on
+//!   their resolved target
 //! - Gitignore-style pattern matching via the `ignore` crate
 //! - Size filtering (min/max)
 //! - Hidden file filtering
@@ -58,21 +62,123 @@
 //! println!("Found {} files across all directories", files.len());
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
 use std::fs::Metadata;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::SystemTime;
 
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
-use jwalk::WalkDir;
+use jwalk::WalkDirGeneric;
 
 use crate::progress::ProgressCallback;
 
-use super::hardlink::HardlinkTracker;
+use super::hardlink::{self, HardlinkTracker};
+use super::kind::{detect_file_kind, has_extension, resolve_category, FileKind};
 use super::{FileEntry, ScanError, WalkerConfig};
 
+/// Identity of a directory used to detect symlink cycles.
+///
+/// Unix uses the device and inode numbers. Other platforms fall back to the
+/// canonical path, since std exposes no stable file id there.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DirIdentity {
+    /// Device and inode number.
+    #[cfg(unix)]
+    Inode { dev: u64, ino: u64 },
+    /// Fully resolved path.
+    #[cfg(not(unix))]
+    Canonical(PathBuf),
+}
+
+impl DirIdentity {
+    /// Identify the directory at `path`, following symlinks.
+    fn of(path: &Path) -> Option<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let metadata = std::fs::metadata(path).ok()?;
+            Some(Self::Inode {
+                dev: metadata.dev(),
+                ino: metadata.ino(),
+            })
+        }
+        #[cfg(not(unix))]
+        {
+            std::fs::canonicalize(path).ok().map(Self::Canonical)
+        }
+    }
+}
+
+/// Target of the symlink at `path` if that target does not exist.
+///
+/// Returns `None` for anything that is not a symlink and for links that
+/// resolve, or fail to resolve for another reason such as a loop.
+fn broken_symlink_target(path: &Path) -> Option<PathBuf> {
+    let target = std::fs::read_link(path).ok()?;
+    match std::fs::metadata(path) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Some(target),
+        _ => None,
+    }
+}
+
+/// Directories pruned by [`WalkerConfig::exclude_dirs`].
+#[derive(Debug, Clone, Default)]
+struct ExcludedDirs {
+    /// Bare directory names, matched against any path component.
+    names: Vec<OsString>,
+    /// Canonicalized directory paths, matched by prefix.
+    prefixes: Vec<PathBuf>,
+}
+
+impl ExcludedDirs {
+    fn new(dirs: &[PathBuf]) -> Self {
+        let mut excluded = Self::default();
+        for dir in dirs {
+            let mut components = dir.components();
+            match (components.next(), components.next()) {
+                (Some(Component::Normal(name)), None) => excluded.names.push(name.to_os_string()),
+                _ => excluded
+                    .prefixes
+                    .push(std::fs::canonicalize(dir).unwrap_or_else(|_| dir.clone())),
+            }
+        }
+        excluded
+    }
+
+    fn is_empty(&self) -> bool {
+        self.names.is_empty() && self.prefixes.is_empty()
+    }
+
+    /// Check whether the directory at `path` should be pruned.
+    ///
+    /// Name matches are free; prefix matches canonicalize the directory,
+    /// which costs one syscall per directory rather than per file.
+    fn matches(&self, path: &Path) -> bool {
+        if path
+            .file_name()
+            .is_some_and(|name| self.names.iter().any(|n| n == name))
+        {
+            return true;
+        }
+        if self.prefixes.is_empty() {
+            return false;
+        }
+        let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        self.prefixes.iter().any(|p| canonical.starts_with(p))
+    }
+
+    /// Check whether `path` lies inside an excluded directory below `root`.
+    fn contains(&self, root: &Path, path: &Path) -> bool {
+        path.ancestors()
+            .skip(1)
+            .take_while(|dir| *dir != root && dir.starts_with(root))
+            .any(|dir| self.matches(dir))
+    }
+}
+
 /// Directory walker for parallel file discovery.
 ///
 /// Uses jwalk for efficient parallel traversal of directory trees.
@@ -296,8 +402,16 @@ impl Walker {
     }
 
     /// Check if a file passes file type filters.
-    fn passes_file_type_filter(&self, path: &Path) -> bool {
-        if self.config.file_categories.is_empty() {
+    ///
+    /// Categories and explicit extensions form a union: the file passes if
+    /// it matches any configured category or any explicit extension.
+    /// Categories consult `kind` (the sniffed format) before the extension.
+    fn passes_file_type_filter(&self, path: &Path, kind: Option<FileKind>) -> bool {
+        if self.config.file_categories.is_empty() && self.config.extensions.is_empty() {
+            return true;
+        }
+
+        if resolve_category(path, kind).is_some_and(|c| self.config.file_categories.contains(&c)) {
             return true;
         }
 
@@ -307,13 +421,18 @@ impl Walker {
             .map(|s| s.to_lowercase())
             .unwrap_or_default();
 
-        for category in &self.config.file_categories {
-            if category.extensions().contains(&extension.as_str()) {
-                return true;
-            }
-        }
+        self.config.extensions.contains(&extension)
+    }
 
-        false
+    /// Sniff a file's format when category filters need it.
+    ///
+    /// Only files without an extension are sniffed unless
+    /// [`WalkerConfig::sniff_content`] is set.
+    fn sniff_for_filter(&self, path: &Path) -> Option<FileKind> {
+        if self.config.file_categories.is_empty() {
+            return None;
+        }
+        (self.config.sniff_content || !has_extension(path)).then(|| detect_file_kind(path))
     }
 
     /// Walk the directory tree, yielding file entries.
@@ -339,13 +458,31 @@ impl Walker {
     pub fn walk(&self) -> impl Iterator<Item = Result<FileEntry, ScanError>> + '_ {
         let gitignore = self.build_gitignore();
         let mut hardlink_tracker = HardlinkTracker::new();
+        let mut symlink_targets = HashSet::new();
         let mut count = 0;
 
+        // Directories already descended into, and links skipped because they
+        // lead back to an ancestor (link path -> resolved target). Only needed
+        // when following symlinks; memory grows with directories, not files.
+        // Each read carries its ancestor chain as jwalk read-dir state, so a
+        // directory reached twice through sibling links (a diamond) is read
+        // once without being reported as a cycle.
+        let follow_symlinks = self.config.follow_dir_symlinks;
+        let visited: Arc<Mutex<HashSet<DirIdentity>>> = Arc::new(Mutex::new(HashSet::new()));
+        let cycles: Arc<Mutex<HashMap<PathBuf, PathBuf>>> = Arc::new(Mutex::new(HashMap::new()));
+        if follow_symlinks {
+            if let Some(id) = DirIdentity::of(&self.root) {
+                visited.lock().unwrap().insert(id);
+            }
+        }
+        let cycles_found = Arc::clone(&cycles);
+        let excluded = ExcludedDirs::new(&self.config.exclude_dirs);
+
         // Configure jwalk
-        let walk_dir = WalkDir::new(&self.root)
-            .follow_links(self.config.follow_symlinks)
+        let walk_dir = WalkDirGeneric::<(Vec<DirIdentity>, ())>::new(&self.root)
+            .follow_links(follow_symlinks)
             .skip_hidden(self.config.skip_hidden)
-            .process_read_dir(move |_depth, _path, _read_dir_state, children| {
+            .process_read_dir(move |_depth, dir_path, ancestors, children| {
                 // Sort children for deterministic output
                 children.sort_by(|a, b| match (a, b) {
                     (Ok(a), Ok(b)) => a.file_name().cmp(b.file_name()),
@@ -353,6 +490,47 @@ impl Walker {
                     (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
                     (Err(_), Err(_)) => std::cmp::Ordering::Equal,
                 });
+
+                // Prune excluded directories before jwalk reads them
+                if !excluded.is_empty() {
+                    for child in children.iter_mut().flatten() {
+                        if child.read_children_path.is_some() && excluded.matches(&child.path()) {
+                            log::trace!("Excluding directory: {}", child.path().display());
+                            child.read_children_path = None;
+                        }
+                    }
+                }
+
+                if !follow_symlinks {
+                    return;
+                }
+
+                // Children inherit this chain, extended by the directory being read
+                if let Some(id) = DirIdentity::of(dir_path) {
+                    ancestors.push(id);
+                }
+
+                // Don't descend into a directory that was already visited
+                for child in children.iter_mut().flatten() {
+                    if child.read_children_path.is_none() {
+                        continue;
+                    }
+                    let path = child.path();
+                    let Some(id) = DirIdentity::of(&path) else {
+                        continue;
+                    };
+                    if ancestors.contains(&id) {
+                        child.read_children_path = None;
+                        let target = std::fs::canonicalize(&path).unwrap_or_default();
+                        cycles.lock().unwrap().insert(path, target);
+                    } else if !visited.lock().unwrap().insert(id) {
+                        log::debug!(
+                            "Directory already scanned via another path: {}",
+                            path.display()
+                        );
+                        child.read_children_path = None;
+                    }
+                }
             });
 
         walk_dir.into_iter().filter_map(move |entry_result| {
@@ -374,6 +552,18 @@ impl Walker {
                     // Get file type (jwalk returns FileType directly)
                     let file_type = entry.file_type();
 
+                    // Report directories skipped to break a symlink cycle
+                    if file_type.is_dir() {
+                        if let Some(target) = cycles_found.lock().unwrap().remove(&path) {
+                            log::warn!(
+                                "Skipping symlink cycle: {} -> {}",
+                                path.display(),
+                                target.display()
+                            );
+                            return Some(Err(ScanError::SymlinkCycle { path, target }));
+                        }
+                    }
+
                     // Skip directories (we only want files)
                     if file_type.is_dir() {
                         // But still check if we should ignore this directory
@@ -389,15 +579,25 @@ impl Walker {
                         return None;
                     }
 
-                    // Handle symlinks
-                    let is_symlink = file_type.is_symlink();
-                    if is_symlink && !self.config.follow_symlinks {
+                    // Handle symlinks (followed directories were already descended into)
+                    let is_symlink = entry.path_is_symlink();
+                    if is_symlink && self.config.report_broken_symlinks {
+                        if let Some(target) = broken_symlink_target(&path) {
+                            log::debug!(
+                                "Broken symlink: {} -> {}",
+                                path.display(),
+                                target.display()
+                            );
+                            return Some(Err(ScanError::BrokenSymlink { path, target }));
+                        }
+                    }
+                    if is_symlink && !self.config.follow_file_symlinks {
                         log::trace!("Skipping symlink: {}", path.display());
                         return None;
                     }
 
-                    // Get metadata (follow symlinks if configured)
-                    let metadata = if self.config.follow_symlinks {
+                    // Get metadata (of the target for followed symlinks)
+                    let metadata = if is_symlink {
                         std::fs::metadata(&path)
                     } else {
                         std::fs::symlink_metadata(&path)
@@ -415,11 +615,26 @@ impl Walker {
                         return None;
                     }
 
+                    let symlink_target = if is_symlink {
+                        let target = self.resolve_file_symlink(&path)?;
+                        if !symlink_targets.insert(target.clone()) {
+                            log::debug!(
+                                "Skipping symlink to an already scanned target: {} -> {}",
+                                path.display(),
+                                target.display()
+                            );
+                            return None;
+                        }
+                        Some(target)
+                    } else {
+                        None
+                    };
+
                     // Process the file entry
                     let result = self.process_file_entry(
                         path,
                         metadata,
-                        is_symlink,
+                        symlink_target,
                         &mut hardlink_tracker,
                         &gitignore,
                     );
@@ -439,25 +654,110 @@ impl Walker {
                     let path = e
                         .path()
                         .map_or_else(|| self.root.clone(), std::borrow::ToOwned::to_owned);
+                    // Following a dangling link fails inside jwalk
+                    if self.config.report_broken_symlinks {
+                        if let Some(target) = broken_symlink_target(&path) {
+                            return Some(Err(ScanError::BrokenSymlink { path, target }));
+                        }
+                    }
                     Some(self.handle_jwalk_error(path, e))
                 }
             }
         })
     }
 
+    /// Build a [`FileEntry`] for a single file beneath this walker's root.
+    ///
+    /// Applies the same ignore, hidden, symlink and metadata filters as
+    /// [`walk`](Self::walk) without traversing the whole tree. Returns `None`
+    /// if the path is filtered out or is not a regular file.
+    pub fn entry_for_file(&self, path: &Path) -> Option<Result<FileEntry, ScanError>> {
+        let gitignore = self.build_gitignore();
+        if self.should_ignore(path, false, &gitignore) {
+            return None;
+        }
+
+        if ExcludedDirs::new(&self.config.exclude_dirs).contains(&self.root, path) {
+            return None;
+        }
+
+        if self.config.skip_hidden {
+            let relative = path.strip_prefix(&self.root).unwrap_or(path);
+            let hidden = relative
+                .components()
+                .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+            if hidden {
+                return None;
+            }
+        }
+
+        let link_metadata = match std::fs::symlink_metadata(path) {
+            Ok(m) => m,
+            Err(e) => return Some(self.handle_io_error(path, e)),
+        };
+        let is_symlink = link_metadata.file_type().is_symlink();
+        if is_symlink && !self.config.follow_file_symlinks {
+            return None;
+        }
+
+        let metadata = if is_symlink {
+            match std::fs::metadata(path) {
+                Ok(m) => m,
+                Err(e) => return Some(self.handle_io_error(path, e)),
+            }
+        } else {
+            link_metadata
+        };
+
+        if !metadata.is_file() {
+            return None;
+        }
+
+        let symlink_target = if is_symlink {
+            Some(self.resolve_file_symlink(path)?)
+        } else {
+            None
+        };
+
+        let mut hardlink_tracker = HardlinkTracker::new();
+        self.process_file_entry(
+            path.to_path_buf(),
+            metadata,
+            symlink_target,
+            &mut hardlink_tracker,
+            &gitignore,
+        )
+    }
+
+    /// Resolve a followed file symlink to its canonical target.
+    ///
+    /// Returns `None` if the target cannot be resolved. Targets inside the
+    /// scan root are followed too: the metadata read through the link has
+    /// the target's inode, so the hardlink tracker keeps the file from being
+    /// counted twice where inodes are available.
+    fn resolve_file_symlink(&self, path: &Path) -> Option<PathBuf> {
+        match std::fs::canonicalize(path) {
+            Ok(target) => Some(target),
+            Err(e) => {
+                log::debug!("Could not resolve symlink {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
     /// Process a file entry and create a FileEntry if valid.
     fn process_file_entry(
         &self,
         path: PathBuf,
         metadata: Metadata,
-        is_symlink: bool,
+        symlink_target: Option<PathBuf>,
         hardlink_tracker: &mut HardlinkTracker,
         _gitignore: &Option<Gitignore>,
     ) -> Option<Result<FileEntry, ScanError>> {
         let size = metadata.len();
 
-        // Skip empty files with a warning (they all hash the same)
-        if size == 0 {
+        // Skip empty files unless requested (they all hash the same)
+        if size == 0 && !self.config.include_empty {
             log::debug!("Skipping empty file: {}", path.display());
             return None;
         }
@@ -481,6 +781,22 @@ impl Walker {
             return None;
         }
 
+        // Leave files that may still be being written alone
+        if self
+            .config
+            .settle_seconds
+            .is_some_and(|settle| super::is_unsettled(modified, settle))
+        {
+            log::debug!("Skipping recently modified file: {}", path.display());
+            return None;
+        }
+
+        // Apply owner filters
+        if !self.config.allows_owner(&metadata) {
+            log::trace!("Skipping file due to owner filter: {}", path.display());
+            return None;
+        }
+
         // Apply regex filters
         if !self.passes_regex_filter(&path) {
             log::trace!("Skipping file due to regex filter: {}", path.display());
@@ -488,13 +804,15 @@ impl Walker {
         }
 
         // Apply file type filters
-        if !self.passes_file_type_filter(&path) {
+        let file_kind = self.sniff_for_filter(&path);
+        if !self.passes_file_type_filter(&path, file_kind) {
             log::trace!("Skipping file due to file type filter: {}", path.display());
             return None;
         }
 
         // Check for hardlinks using the tracker
-        if hardlink_tracker.is_hardlink(&metadata) {
+        let is_hardlink = hardlink_tracker.is_hardlink(&metadata);
+        if is_hardlink && !self.config.include_hardlinks {
             log::debug!("Skipping hardlink: {}", path.display());
             return None;
         }
@@ -503,11 +821,18 @@ impl Walker {
             path,
             size,
             modified,
-            is_symlink,
-            is_hardlink: false,
+            is_symlink: symlink_target.is_some(),
+            is_hardlink,
             group_name: self.group_name.clone(),
             perceptual_hash: None,
             document_fingerprint: None,
+            file_kind,
+            symlink_target,
+            inode: self
+                .config
+                .include_hardlinks
+                .then(|| hardlink::inode_id(&metadata))
+                .flatten(),
         }))
     }
 
@@ -745,8 +1070,6 @@ impl MultiWalker {
     ///
     /// Invalid paths (non-existent, non-directory) are logged and skipped.
     fn normalize_and_dedupe_paths(paths: Vec<PathBuf>) -> Vec<PathBuf> {
-        use std::collections::HashSet;
-
         if paths.is_empty() {
             return Vec::new();
         }
@@ -836,9 +1159,7 @@ impl MultiWalker {
     /// ```
     pub fn walk(&self) -> impl Iterator<Item = Result<FileEntry, ScanError>> + '_ {
         use rayon::prelude::*;
-        use std::collections::HashSet;
         use std::sync::atomic::AtomicUsize;
-        use std::sync::Mutex;
 
         // Early return for empty roots
         if self.roots.is_empty() || self.is_shutdown_requested() {
@@ -949,6 +1270,14 @@ impl ProgressCallback for SharedProgressCallback {
         self.inner.on_item_completed(bytes);
     }
 
+    fn on_bytes_read(&self, bytes: u64) {
+        self.inner.on_bytes_read(bytes);
+    }
+
+    fn on_phase_bytes(&self, total_bytes: u64) {
+        self.inner.on_phase_bytes(total_bytes);
+    }
+
     fn on_phase_end(&self, phase: &str) {
         self.inner.on_phase_end(phase);
     }
@@ -958,6 +1287,31 @@ impl ProgressCallback for SharedProgressCallback {
     }
 }
 
+/// Count the files a scan of `paths` would find, without hashing anything.
+///
+/// Runs the same traversal and filters as [`MultiWalker::walk`], so the
+/// count matches the files the real walk reports. This costs a second
+/// metadata pass over the tree, which is why `--precount` is opt-in.
+///
+/// Returns `None` if `shutdown_flag` is set before the count completes.
+#[must_use]
+pub fn precount_files(
+    paths: Vec<PathBuf>,
+    config: WalkerConfig,
+    shutdown_flag: Option<Arc<AtomicBool>>,
+) -> Option<usize> {
+    let mut walker = MultiWalker::new(paths, config);
+    if let Some(ref flag) = shutdown_flag {
+        walker = walker.with_shutdown_flag(Arc::clone(flag));
+    }
+    let count = walker.walk().filter(Result::is_ok).count();
+
+    if shutdown_flag.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+        return None;
+    }
+    Some(count)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1081,6 +1435,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_walker_include_empty_files() {
+        let dir = create_test_dir();
+        File::create(dir.path().join("empty.txt")).unwrap();
+
+        let config = WalkerConfig::default().with_include_empty(true);
+        let walker = Walker::new(dir.path(), config);
+
+        let files: Vec<_> = walker.walk().filter_map(Result::ok).collect();
+        assert!(files
+            .iter()
+            .any(|f| f.size == 0 && f.path.ends_with("empty.txt")));
+    }
+
     #[test]
     fn test_walker_skip_hidden_files() {
         let dir = create_test_dir();
@@ -1239,6 +1607,47 @@ mod tests {
         assert_eq!(files[0].path.file_name().unwrap(), "mid.txt");
     }
 
+    #[test]
+    fn test_walker_settle_window() {
+        use std::time::Duration;
+        let dir = TempDir::new().unwrap();
+        let fresh = dir.path().join("fresh.txt");
+        std::fs::write(&fresh, "still being written").unwrap();
+
+        let config = WalkerConfig::default().with_settle_seconds(Some(60));
+        let walker = Walker::new(dir.path(), config.clone());
+        assert_eq!(walker.walk().filter_map(Result::ok).count(), 0);
+
+        // Once the mtime is outside the window the file is walked again
+        let settled = SystemTime::now() - Duration::from_secs(61);
+        filetime::set_file_mtime(&fresh, filetime::FileTime::from_system_time(settled)).unwrap();
+        let walker = Walker::new(dir.path(), config);
+        let files: Vec<_> = walker.walk().filter_map(Result::ok).collect();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path.file_name().unwrap(), "fresh.txt");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_walker_owner_filters() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("mine.txt");
+        fs::write(&path, "owned by the test process").unwrap();
+        let uid = super::super::owner::file_owner(&fs::metadata(&path).unwrap()).unwrap();
+
+        let count = |only: Option<Vec<u32>>, exclude: Vec<u32>| {
+            let config = WalkerConfig::default().with_owner_filter(only, exclude);
+            Walker::new(dir.path(), config)
+                .walk()
+                .filter_map(Result::ok)
+                .count()
+        };
+        assert_eq!(count(Some(vec![uid]), Vec::new()), 1);
+        assert_eq!(count(Some(vec![uid.wrapping_add(1)]), Vec::new()), 0);
+        assert_eq!(count(None, vec![uid]), 0);
+        assert_eq!(count(None, vec![uid.wrapping_add(1)]), 1);
+    }
+
     #[test]
     fn test_walker_multiple_regex_include() {
         use regex::Regex;
@@ -1342,6 +1751,122 @@ mod tests {
         assert_eq!(files.len(), 0);
     }
 
+    #[test]
+    fn test_walker_file_type_filters_sniff_content() {
+        use super::super::{FileCategory, FileKind};
+        let dir = TempDir::new().unwrap();
+
+        // A PNG without an extension and a PDF disguised as a JPEG
+        fs::write(dir.path().join("photo"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+        fs::write(dir.path().join("scan.jpg"), b"%PDF-1.4\nnot an image").unwrap();
+
+        let names = |config: WalkerConfig| {
+            let mut names: Vec<_> = Walker::new(dir.path(), config)
+                .walk()
+                .filter_map(Result::ok)
+                .map(|f| f.path.file_name().unwrap().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            names
+        };
+
+        // Extensionless files are always sniffed; extensions are trusted by default
+        let images = WalkerConfig::default().with_file_categories(vec![FileCategory::Images]);
+        assert_eq!(names(images.clone()), vec!["photo", "scan.jpg"]);
+
+        // With sniffing the disguised PDF moves to documents
+        assert_eq!(names(images.with_sniff_content(true)), vec!["photo"]);
+        let documents = WalkerConfig::default()
+            .with_file_categories(vec![FileCategory::Documents])
+            .with_sniff_content(true);
+        assert_eq!(names(documents.clone()), vec!["scan.jpg"]);
+
+        // The detected kind is cached on the entry
+        let files: Vec<_> = Walker::new(dir.path(), documents)
+            .walk()
+            .filter_map(Result::ok)
+            .collect();
+        assert_eq!(files[0].file_kind, Some(FileKind::Pdf));
+        assert!(files[0].is_document());
+        assert!(!files[0].is_image());
+    }
+
+    #[test]
+    fn test_walker_extension_filters() {
+        use super::super::FileCategory;
+        let dir = TempDir::new().unwrap();
+
+        for name in ["shot.RAW", "shot.cr2", "photo.jpg", "notes.txt"] {
+            let mut f = File::create(dir.path().join(name)).unwrap();
+            writeln!(f, "{} content", name).unwrap();
+        }
+
+        // Custom extensions outside any built-in category, matched case-insensitively
+        let config =
+            WalkerConfig::default().with_extensions(vec!["raw".to_string(), ".CR2".to_string()]);
+        let walker = Walker::new(dir.path(), config);
+        let mut names: Vec<_> = walker
+            .walk()
+            .filter_map(Result::ok)
+            .map(|f| f.path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["shot.RAW", "shot.cr2"]);
+
+        // Union with file categories
+        let config = WalkerConfig::default()
+            .with_file_categories(vec![FileCategory::Images])
+            .with_extensions(vec!["raw".to_string()]);
+        let walker = Walker::new(dir.path(), config);
+        let mut names: Vec<_> = walker
+            .walk()
+            .filter_map(Result::ok)
+            .map(|f| f.path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["photo.jpg", "shot.RAW"]);
+    }
+
+    #[test]
+    fn test_walker_prunes_excluded_dirs() {
+        let dir = create_test_dir();
+        let modules = dir.path().join("web").join("node_modules");
+        fs::create_dir_all(modules.join("pkg")).unwrap();
+        File::create(modules.join("pkg").join("index.js")).unwrap();
+        let build = dir.path().join("build");
+        fs::create_dir(&build).unwrap();
+        let mut f = File::create(build.join("out.bin")).unwrap();
+        writeln!(f, "build output").unwrap();
+
+        // A cycle inside the pruned subtree would be reported if it were read
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&modules, modules.join("pkg").join("loop")).unwrap();
+
+        let config = WalkerConfig::default()
+            .with_follow_symlinks(true)
+            .with_exclude_dirs(vec![PathBuf::from("node_modules"), build.clone()]);
+        let walker = Walker::new(dir.path(), config);
+        let results: Vec<_> = walker.walk().collect();
+
+        for result in &results {
+            let path = match result {
+                Ok(entry) => entry.path.clone(),
+                Err(ScanError::SymlinkCycle { path, .. }) => path.clone(),
+                Err(e) => panic!("unexpected error: {e}"),
+            };
+            assert!(!path.starts_with(&modules), "visited {}", path.display());
+            assert!(!path.starts_with(&build), "visited {}", path.display());
+        }
+        let files: Vec<_> = results.into_iter().filter_map(Result::ok).collect();
+        assert_eq!(files.len(), 3);
+
+        // Single-file lookups honor the same exclusions
+        assert!(walker.entry_for_file(&build.join("out.bin")).is_none());
+        assert!(walker
+            .entry_for_file(&dir.path().join("file1.txt"))
+            .is_some());
+    }
+
     #[test]
     fn test_walker_shutdown_flag() {
         let dir = create_test_dir();
@@ -1384,6 +1909,229 @@ mod tests {
         assert!(results.is_empty() || results.iter().all(|r| r.is_err()));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_walker_breaks_symlink_cycle() {
+        use std::os::unix::fs::symlink;
+
+        let dir = TempDir::new().unwrap();
+        let sub = dir.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+        File::create(sub.join("a.txt"))
+            .unwrap()
+            .write_all(b"content")
+            .unwrap();
+        // sub/loop -> root forms a cycle
+        symlink(dir.path(), sub.join("loop")).unwrap();
+
+        let config = WalkerConfig::default().with_follow_symlinks(true);
+        let results: Vec<_> = Walker::new(dir.path(), config).walk().collect();
+
+        let files: Vec<_> = results.iter().filter_map(|r| r.as_ref().ok()).collect();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("sub/a.txt"));
+
+        let cycles: Vec<_> = results
+            .iter()
+            .filter_map(|r| match r {
+                Err(ScanError::SymlinkCycle { path, target }) => Some((path, target)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(cycles.len(), 1);
+        assert!(cycles[0].0.ends_with("sub/loop"));
+        assert_eq!(*cycles[0].1, dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_walker_diamond_symlinks_are_not_cycles() {
+        use std::os::unix::fs::symlink;
+
+        // left/link and right/link both lead to shared/, which is no ancestor
+        let dir = TempDir::new().unwrap();
+        let shared = dir.path().join("shared");
+        fs::create_dir(&shared).unwrap();
+        fs::write(shared.join("a.txt"), b"content").unwrap();
+        for side in ["left", "right"] {
+            let side = dir.path().join(side);
+            fs::create_dir(&side).unwrap();
+            symlink(&shared, side.join("link")).unwrap();
+        }
+
+        let config = WalkerConfig::default().with_follow_symlinks(true);
+        let results: Vec<_> = Walker::new(dir.path(), config).walk().collect();
+
+        assert!(
+            !results
+                .iter()
+                .any(|r| matches!(r, Err(ScanError::SymlinkCycle { .. }))),
+            "diamond reported as a cycle: {:?}",
+            results
+        );
+        // The shared directory is read once
+        let files: Vec<_> = results.iter().filter_map(|r| r.as_ref().ok()).collect();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("a.txt"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_walker_reports_broken_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("real.txt"), b"content").unwrap();
+        symlink(dir.path().join("real.txt"), dir.path().join("good")).unwrap();
+        symlink(dir.path().join("gone.txt"), dir.path().join("dangling")).unwrap();
+
+        let broken = |config: WalkerConfig| -> Vec<(PathBuf, PathBuf)> {
+            Walker::new(dir.path(), config)
+                .walk()
+                .filter_map(|r| match r {
+                    Err(ScanError::BrokenSymlink { path, target }) => Some((path, target)),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        // Skipped silently by default
+        assert!(broken(WalkerConfig::default()).is_empty());
+
+        for follow in [false, true] {
+            let config = WalkerConfig::default()
+                .with_follow_symlinks(follow)
+                .with_report_broken_symlinks(true);
+            let found = broken(config.clone());
+            assert_eq!(found.len(), 1, "follow_symlinks = {}", follow);
+            assert!(found[0].0.ends_with("dangling"));
+            assert_eq!(found[0].1, dir.path().join("gone.txt"));
+
+            // The regular file is still yielded
+            let files: Vec<_> = Walker::new(dir.path(), config)
+                .walk()
+                .filter_map(Result::ok)
+                .collect();
+            assert!(files.iter().any(|f| f.path.ends_with("real.txt")));
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_walker_symlink_follow_combinations() {
+        use std::os::unix::fs::symlink;
+
+        let dir = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("data")).unwrap();
+        fs::write(dir.path().join("data").join("real.txt"), b"in scope").unwrap();
+        fs::write(outside.path().join("ext.txt"), b"out of scope").unwrap();
+        fs::create_dir(outside.path().join("sub")).unwrap();
+        fs::write(outside.path().join("sub").join("inner.txt"), b"inner").unwrap();
+
+        // File symlinks to an in-scope and an out-of-scope target (twice)
+        symlink(
+            dir.path().join("data").join("real.txt"),
+            dir.path().join("link.txt"),
+        )
+        .unwrap();
+        symlink(
+            outside.path().join("ext.txt"),
+            dir.path().join("ext_link.txt"),
+        )
+        .unwrap();
+        symlink(
+            outside.path().join("ext.txt"),
+            dir.path().join("ext_link2.txt"),
+        )
+        .unwrap();
+        // Directory symlink
+        symlink(outside.path().join("sub"), dir.path().join("linkdir")).unwrap();
+
+        let scan = |dirs: bool, files: bool| -> Vec<FileEntry> {
+            let config = WalkerConfig::default()
+                .with_follow_dir_symlinks(dirs)
+                .with_follow_file_symlinks(files);
+            Walker::new(dir.path(), config)
+                .walk()
+                .filter_map(Result::ok)
+                .collect()
+        };
+        let names = |files: &[FileEntry]| -> Vec<String> {
+            let mut names: Vec<String> = files
+                .iter()
+                .map(|f| {
+                    f.path
+                        .strip_prefix(dir.path())
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect();
+            names.sort();
+            names
+        };
+
+        // The in-scope target is scanned once: link.txt reaches the same
+        // inode after data/real.txt and is skipped as a hardlink
+        assert_eq!(names(&scan(false, false)), vec!["data/real.txt"]);
+        assert_eq!(
+            names(&scan(true, false)),
+            vec!["data/real.txt", "linkdir/inner.txt"]
+        );
+
+        let files_only = scan(false, true);
+        assert_eq!(names(&files_only), vec!["data/real.txt", "ext_link.txt"]);
+        let link = files_only
+            .iter()
+            .find(|f| f.path.ends_with("ext_link.txt"))
+            .unwrap();
+        assert!(link.is_symlink);
+        assert_eq!(
+            link.symlink_target,
+            Some(outside.path().join("ext.txt").canonicalize().unwrap())
+        );
+
+        let both = scan(true, true);
+        assert_eq!(
+            names(&both),
+            vec!["data/real.txt", "ext_link.txt", "linkdir/inner.txt"]
+        );
+        assert!(both
+            .iter()
+            .filter(|f| !f.path.ends_with("ext_link.txt"))
+            .all(|f| !f.is_symlink && f.symlink_target.is_none()));
+
+        // The alias sets both
+        let alias = WalkerConfig::default().with_follow_symlinks(true);
+        assert!(alias.follow_dir_symlinks && alias.follow_file_symlinks);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_walker_follows_file_symlink_inside_root() {
+        use std::os::unix::fs::symlink;
+
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("z")).unwrap();
+        let real = dir.path().join("z").join("real.txt");
+        fs::write(&real, b"in scope").unwrap();
+        // Sorts before its target, so the walk reaches the link first
+        symlink(&real, dir.path().join("a_link.txt")).unwrap();
+
+        let config = WalkerConfig::default().with_follow_file_symlinks(true);
+        let files: Vec<_> = Walker::new(dir.path(), config)
+            .walk()
+            .filter_map(Result::ok)
+            .collect();
+
+        // Reported once, through the link, with its target recorded
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("a_link.txt"));
+        assert!(files[0].is_symlink);
+        assert_eq!(files[0].symlink_target, Some(real.canonicalize().unwrap()));
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_walker_detects_hardlinks() {
@@ -1421,6 +2169,29 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_walker_include_hardlinks_flags_later_links() {
+        use std::fs::hard_link;
+
+        let dir = TempDir::new().unwrap();
+        let original = dir.path().join("original.txt");
+        fs::write(&original, b"Original content").unwrap();
+        hard_link(&original, dir.path().join("hardlink.txt")).unwrap();
+
+        let config = WalkerConfig::default().with_include_hardlinks(true);
+        let files: Vec<_> = Walker::new(dir.path(), config)
+            .walk()
+            .filter_map(Result::ok)
+            .collect();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files.iter().filter(|f| f.is_hardlink).count(), 1);
+        // Both links record the inode they share
+        assert!(files[0].inode.is_some());
+        assert_eq!(files[0].inode, files[1].inode);
+    }
+
     #[test]
     fn test_file_entry_fields() {
         let dir = create_test_dir();
@@ -1440,6 +2211,32 @@ mod tests {
     // MultiWalker Tests
     // ========================================================================
 
+    #[test]
+    fn test_precount_files_matches_walk() {
+        let dir = create_test_dir();
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(dir.path().join("nested/deep.txt"), "deep").unwrap();
+        let paths = vec![dir.path().to_path_buf()];
+
+        assert_eq!(
+            precount_files(paths.clone(), WalkerConfig::default(), None),
+            Some(4)
+        );
+
+        let walked = MultiWalker::new(paths.clone(), WalkerConfig::default())
+            .walk()
+            .filter_map(Result::ok)
+            .count();
+        assert_eq!(walked, 4);
+
+        // A requested shutdown abandons the count
+        let flag = Arc::new(AtomicBool::new(true));
+        assert_eq!(
+            precount_files(paths, WalkerConfig::default(), Some(flag)),
+            None
+        );
+    }
+
     #[test]
     fn test_multi_walker_single_directory() {
         let dir = create_test_dir();
@@ -2,10 +2,11 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet};
 use std::path::PathBuf;
 
-use crate::duplicates::{DuplicateGroup, ScanSummary};
+use crate::duplicates::{reclaimable_by_extension, DuplicateGroup, ScanSummary, SizeHistogram};
+use crate::tui::app::{SortColumn, SortDirection};
 
 /// Current version of the session file format.
 pub const SESSION_VERSION: u32 = 2;
@@ -25,6 +26,12 @@ pub struct Session {
     pub groups: Vec<SessionGroup>,
     /// Paths selected by the user for deletion.
     pub user_selections: BTreeSet<PathBuf>,
+    /// Paths designated as the file to keep in their group.
+    #[serde(default)]
+    pub keepers: BTreeSet<PathBuf>,
+    /// Hashes of groups pinned in the TUI.
+    #[serde(default)]
+    pub pinned_groups: BTreeSet<[u8; 32]>,
     /// Currently selected group index in TUI.
     pub group_index: usize,
     /// Currently selected file index in TUI.
@@ -51,6 +58,8 @@ impl Session {
             settings,
             groups,
             user_selections: BTreeSet::new(),
+            keepers: BTreeSet::new(),
+            pinned_groups: BTreeSet::new(),
             group_index: 0,
             file_index: 0,
         }
@@ -72,6 +81,8 @@ impl Session {
             duplicate_groups: groups.len(),
             duplicate_files: groups.iter().map(|g| g.duplicate_count()).sum(),
             reclaimable_space: groups.iter().map(|g| g.wasted_space()).sum(),
+            size_histogram: SizeHistogram::from_groups(&groups),
+            reclaimable_by_extension: reclaimable_by_extension(&groups),
             // Total files and size are not fully known from session alone,
             // so we provide estimates based on duplicate groups.
             total_files: groups.iter().map(|g| g.files.len()).sum(),
@@ -84,6 +95,33 @@ impl Session {
 
         (groups, summary)
     }
+
+    /// Remove groups that were already decided on in this session.
+    ///
+    /// A group is suppressed when this session holds a group with the same
+    /// hash and exactly the same member paths, and at least one of those
+    /// members is in `user_selections`. Groups that gained or lost members
+    /// resurface so they can be reviewed again.
+    ///
+    /// Returns the suppressed groups.
+    pub fn suppress_decided(&self, groups: &mut Vec<DuplicateGroup>) -> Vec<DuplicateGroup> {
+        let decided: HashSet<([u8; 32], BTreeSet<&PathBuf>)> = self
+            .groups
+            .iter()
+            .filter(|g| {
+                g.files
+                    .iter()
+                    .any(|f| self.user_selections.contains(&f.path))
+            })
+            .map(|g| (g.hash, g.files.iter().map(|f| &f.path).collect()))
+            .collect();
+
+        let (suppressed, remaining) = std::mem::take(groups)
+            .into_iter()
+            .partition(|g| decided.contains(&(g.hash, g.files.iter().map(|f| &f.path).collect())));
+        *groups = remaining;
+        suppressed
+    }
 }
 
 /// Settings used during the scan that produced the session.
@@ -91,6 +129,12 @@ impl Session {
 pub struct SessionSettings {
     /// Follow symbolic links during traversal.
     pub follow_symlinks: bool,
+    /// Descend into symlinked directories.
+    #[serde(default)]
+    pub follow_dir_symlinks: bool,
+    /// Scan the targets of symlinked files.
+    #[serde(default)]
+    pub follow_file_symlinks: bool,
     /// Skip hidden files and directories.
     pub skip_hidden: bool,
     /// Minimum file size to include (in bytes).
@@ -103,16 +147,25 @@ pub struct SessionSettings {
     pub older_than: Option<DateTime<Utc>>,
     /// Glob patterns to ignore.
     pub ignore_patterns: Vec<String>,
+    /// Directories pruned during traversal.
+    #[serde(default)]
+    pub exclude_dirs: Vec<PathBuf>,
     /// Regex patterns to include.
     pub regex_include: Vec<String>,
     /// Regex patterns to exclude.
     pub regex_exclude: Vec<String>,
     /// File categories to include.
     pub file_categories: Vec<crate::scanner::FileCategory>,
+    /// Explicit file extensions to include.
+    #[serde(default)]
+    pub extensions: Vec<String>,
     /// Number of I/O threads used for hashing.
     pub io_threads: usize,
     /// Whether byte-by-byte verification was enabled.
     pub paranoid: bool,
+    /// Whether zero-byte files were included.
+    #[serde(default)]
+    pub include_empty: bool,
     /// Whether memory-mapped hashing was enabled.
     #[serde(default)]
     pub mmap: bool,
@@ -125,6 +178,9 @@ pub struct SessionSettings {
     /// Whether similar document detection was enabled.
     #[serde(default)]
     pub similar_documents: bool,
+    /// Whether similar-filename clustering was enabled.
+    #[serde(default)]
+    pub name_cluster: bool,
     /// Minimum group size.
     #[serde(default = "default_min_group_size")]
     pub min_group_size: usize,
@@ -139,8 +195,26 @@ pub struct SessionSettings {
     /// Whether adaptive buffer sizing was enabled.
     #[serde(default = "default_true")]
     pub io_adaptive_buffer: bool,
+    /// Streaming read buffer size used for hashing.
+    #[serde(default)]
+    pub read_buffer_size: Option<usize>,
+    /// Size above which files were compared by sampled hashing.
+    #[serde(default)]
+    pub sample_above: Option<u64>,
+    /// Whether alternate streams were folded into content hashes.
+    #[serde(default)]
+    pub include_streams: bool,
+    /// How hardlinks to already-seen files were treated.
+    #[serde(default)]
+    pub hardlinks: crate::duplicates::HardlinkPolicy,
     /// Threshold for document similarity.
     pub doc_similarity_threshold: Option<u32>,
+    /// Column the TUI groups were sorted by when the session was saved.
+    #[serde(default)]
+    pub sort_column: SortColumn,
+    /// Direction the TUI groups were sorted in when the session was saved.
+    #[serde(default)]
+    pub sort_direction: SortDirection,
 }
 
 fn default_true() -> bool {
@@ -183,6 +257,18 @@ pub struct SessionGroup {
     /// Whether this is a similar image group.
     #[serde(default)]
     pub is_similar: bool,
+    /// Whether this is a group of zero-byte files.
+    #[serde(default)]
+    pub is_empty_group: bool,
+    /// Whether this is an advisory group of similarly named files.
+    #[serde(default)]
+    pub is_name_similar: bool,
+    /// Shared content fraction of a partial duplicate pair.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial_similarity: Option<f64>,
+    /// Whether the files look like deliberate copies.
+    #[serde(default)]
+    pub likely_intentional: bool,
 }
 
 impl SessionGroup {
@@ -201,6 +287,10 @@ impl SessionGroup {
             files: group.files.clone(),
             reference_paths: group.reference_paths.clone(),
             is_similar: group.is_similar,
+            is_empty_group: group.is_empty_group,
+            is_name_similar: group.is_name_similar,
+            partial_similarity: group.partial_similarity,
+            likely_intentional: group.likely_intentional,
         }
     }
 }
@@ -209,6 +299,10 @@ impl From<SessionGroup> for DuplicateGroup {
     fn from(sg: SessionGroup) -> Self {
         let mut group = DuplicateGroup::new(sg.hash, sg.size, sg.files, sg.reference_paths);
         group.is_similar = sg.is_similar;
+        group.is_empty_group = sg.is_empty_group;
+        group.is_name_similar = sg.is_name_similar;
+        group.partial_similarity = sg.partial_similarity;
+        group.likely_intentional = sg.likely_intentional;
         group
     }
 }
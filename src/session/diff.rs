@@ -0,0 +1,440 @@
+//! Comparison of two saved sessions (`rustdupe diff`).
+//!
+//! Groups are matched by content hash. A group only in the new session was
+//! added, one only in the old session was removed, and a matched group grew
+//! or shrank when its file count changed. Matched groups whose file count is
+//! unchanged but whose members differ are reported as changed. For every
+//! matched group the files that joined or left it are listed. Groups are
+//! labelled with their [group ID](crate::duplicates::group_ids).
+//!
+//! Reclaimable space follows [`DuplicateGroup::wasted_space`]: every file
+//! except the first in a group counts.
+//!
+//! # Example Output
+//!
+//! ```text
+//! Reclaimable:  3.0 KiB -> 4.0 KiB (+1.0 KiB)
+//!
+//! Added groups (1):
+//!   + [9c41d07e2b6a] 2.0 KiB  2 files  /photos/new.jpg
+//!
+//! Removed groups (1):
+//!   - [03fe7a815c92] 1.0 KiB  2 files  /docs/old.txt
+//!
+//! Grown groups (1):
+//!   ~ [5b0c2de94f17] 2.0 KiB -> 3.0 KiB  3 -> 4 files  /music/a.mp3
+//!       + /music/copy.mp3
+//! ```
+//!
+//! [`DuplicateGroup::wasted_space`]: crate::duplicates::DuplicateGroup::wasted_space
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use super::data::{Session, SessionGroup};
+use crate::duplicates::groups::unique_group_ids;
+use crate::output::size::SizeFormat;
+use crate::scanner::hash_to_hex;
+
+/// A group that exists in only one of the two sessions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GroupSnapshot {
+    /// Stable group ID
+    pub group_id: String,
+    /// BLAKE3 hash (hex)
+    pub hash: String,
+    /// Reclaimable space in bytes
+    pub reclaimable_space: u64,
+    /// Member paths
+    pub files: Vec<PathBuf>,
+}
+
+/// A group present in both sessions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GroupDelta {
+    /// Stable group ID
+    pub group_id: String,
+    /// BLAKE3 hash (hex)
+    pub hash: String,
+    /// First file of the group in the new session
+    pub first_path: PathBuf,
+    /// Number of files in the old session
+    pub old_files: usize,
+    /// Number of files in the new session
+    pub new_files: usize,
+    /// Reclaimable space in the old session
+    pub old_reclaimable: u64,
+    /// Reclaimable space in the new session
+    pub new_reclaimable: u64,
+    /// Files only in the new session's group
+    pub added_files: Vec<PathBuf>,
+    /// Files only in the old session's group
+    pub removed_files: Vec<PathBuf>,
+}
+
+/// Differences between an old and a new session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionDiff {
+    /// Groups only in the new session
+    pub added: Vec<GroupSnapshot>,
+    /// Groups only in the old session
+    pub removed: Vec<GroupSnapshot>,
+    /// Matched groups with more files than before
+    pub grown: Vec<GroupDelta>,
+    /// Matched groups with fewer files than before
+    pub shrunk: Vec<GroupDelta>,
+    /// Matched groups with the same file count but different members
+    pub changed: Vec<GroupDelta>,
+    /// Total reclaimable space in the old session
+    pub old_reclaimable: u64,
+    /// Total reclaimable space in the new session
+    pub new_reclaimable: u64,
+    /// `new_reclaimable - old_reclaimable`
+    pub net_change: i128,
+}
+
+/// Compare two sessions, matching groups by hash.
+///
+/// If a session holds several groups with the same hash (similar-image
+/// groups can), they are paired in order of appearance.
+#[must_use]
+pub fn diff_sessions(old: &Session, new: &Session) -> SessionDiff {
+    let old_ids = session_group_ids(old);
+    let new_ids = session_group_ids(new);
+
+    let mut old_by_hash: HashMap<[u8; 32], VecDeque<(usize, &SessionGroup)>> = HashMap::new();
+    for (index, group) in old.groups.iter().enumerate() {
+        old_by_hash
+            .entry(group.hash)
+            .or_default()
+            .push_back((index, group));
+    }
+
+    let mut added = Vec::new();
+    let mut grown = Vec::new();
+    let mut shrunk = Vec::new();
+    let mut changed = Vec::new();
+
+    for (group, id) in new.groups.iter().zip(&new_ids) {
+        let Some((_, previous)) = old_by_hash
+            .get_mut(&group.hash)
+            .and_then(VecDeque::pop_front)
+        else {
+            added.push(snapshot(group, id));
+            continue;
+        };
+
+        let delta = group_delta(previous, group, id);
+        if delta.new_files > delta.old_files {
+            grown.push(delta);
+        } else if delta.new_files < delta.old_files {
+            shrunk.push(delta);
+        } else if !delta.added_files.is_empty() || !delta.removed_files.is_empty() {
+            changed.push(delta);
+        }
+    }
+
+    let mut removed: Vec<(usize, &SessionGroup)> = old_by_hash.into_values().flatten().collect();
+    // Keep the old session's order for removed groups
+    removed.sort_by_key(|&(index, _)| index);
+
+    let old_reclaimable: u64 = old.groups.iter().map(reclaimable).sum();
+    let new_reclaimable: u64 = new.groups.iter().map(reclaimable).sum();
+
+    SessionDiff {
+        added,
+        removed: removed
+            .into_iter()
+            .map(|(index, group)| snapshot(group, &old_ids[index]))
+            .collect(),
+        grown,
+        shrunk,
+        changed,
+        old_reclaimable,
+        new_reclaimable,
+        net_change: i128::from(new_reclaimable) - i128::from(old_reclaimable),
+    }
+}
+
+/// Space freed by keeping only the first file of a group.
+fn reclaimable(group: &SessionGroup) -> u64 {
+    group.files.iter().skip(1).map(|f| f.size).sum()
+}
+
+/// [Group IDs](crate::duplicates::group_ids) of the session's groups.
+fn session_group_ids(session: &Session) -> Vec<String> {
+    unique_group_ids(
+        session
+            .groups
+            .iter()
+            .map(|g| (&g.hash, g.files.iter().map(|f| f.path.as_path()))),
+    )
+}
+
+fn snapshot(group: &SessionGroup, group_id: &str) -> GroupSnapshot {
+    GroupSnapshot {
+        group_id: group_id.to_string(),
+        hash: hash_to_hex(&group.hash),
+        reclaimable_space: reclaimable(group),
+        files: group.files.iter().map(|f| f.path.clone()).collect(),
+    }
+}
+
+fn group_delta(old: &SessionGroup, new: &SessionGroup, group_id: &str) -> GroupDelta {
+    let old_paths: BTreeSet<&PathBuf> = old.files.iter().map(|f| &f.path).collect();
+    let new_paths: BTreeSet<&PathBuf> = new.files.iter().map(|f| &f.path).collect();
+    GroupDelta {
+        group_id: group_id.to_string(),
+        hash: hash_to_hex(&new.hash),
+        first_path: new
+            .files
+            .first()
+            .map(|f| f.path.clone())
+            .unwrap_or_default(),
+        old_files: old.files.len(),
+        new_files: new.files.len(),
+        old_reclaimable: reclaimable(old),
+        new_reclaimable: reclaimable(new),
+        added_files: new_paths
+            .difference(&old_paths)
+            .map(|p| (*p).clone())
+            .collect(),
+        removed_files: old_paths
+            .difference(&new_paths)
+            .map(|p| (*p).clone())
+            .collect(),
+    }
+}
+
+/// Format a signed byte count as `+1.0 KiB` or `-1.0 KiB`.
+fn format_change(bytes: i128, size_format: SizeFormat) -> String {
+    let magnitude = u64::try_from(bytes.unsigned_abs()).unwrap_or(u64::MAX);
+    if bytes < 0 {
+        format!("-{}", size_format.format(magnitude))
+    } else {
+        format!("+{}", size_format.format(magnitude))
+    }
+}
+
+impl SessionDiff {
+    /// Whether the sessions hold the same groups with the same members.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.grown.is_empty()
+            && self.shrunk.is_empty()
+            && self.changed.is_empty()
+    }
+
+    /// Write the comparison as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing or serialization fails.
+    pub fn write_json<W: Write>(&self, writer: &mut W, pretty: bool) -> serde_json::Result<()> {
+        if pretty {
+            serde_json::to_writer_pretty(&mut *writer, self)?;
+        } else {
+            serde_json::to_writer(&mut *writer, self)?;
+        }
+        writeln!(writer).map_err(serde_json::Error::io)
+    }
+
+    /// Write the human-readable report, with sizes in `size_format` units.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_text<W: Write>(
+        &self,
+        writer: &mut W,
+        size_format: SizeFormat,
+    ) -> std::io::Result<()> {
+        writeln!(
+            writer,
+            "Reclaimable:  {} -> {} ({})",
+            size_format.format(self.old_reclaimable),
+            size_format.format(self.new_reclaimable),
+            format_change(self.net_change, size_format)
+        )?;
+        if self.is_empty() {
+            writeln!(writer, "\nNo changes.")?;
+            return Ok(());
+        }
+
+        for (title, marker, groups) in
+            [("Added", '+', &self.added), ("Removed", '-', &self.removed)]
+        {
+            if groups.is_empty() {
+                continue;
+            }
+            writeln!(writer, "\n{} groups ({}):", title, groups.len())?;
+            for g in groups {
+                writeln!(
+                    writer,
+                    "  {} [{}] {}  {} files  {}",
+                    marker,
+                    g.group_id,
+                    size_format.format(g.reclaimable_space),
+                    g.files.len(),
+                    g.files
+                        .first()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_default()
+                )?;
+            }
+        }
+
+        for (title, deltas) in [
+            ("Grown", &self.grown),
+            ("Shrunk", &self.shrunk),
+            ("Changed", &self.changed),
+        ] {
+            if deltas.is_empty() {
+                continue;
+            }
+            writeln!(writer, "\n{} groups ({}):", title, deltas.len())?;
+            for d in deltas {
+                writeln!(
+                    writer,
+                    "  ~ [{}] {} -> {}  {} -> {} files  {}",
+                    d.group_id,
+                    size_format.format(d.old_reclaimable),
+                    size_format.format(d.new_reclaimable),
+                    d.old_files,
+                    d.new_files,
+                    d.first_path.display()
+                )?;
+                for path in &d.added_files {
+                    writeln!(writer, "      + {}", path.display())?;
+                }
+                for path in &d.removed_files {
+                    writeln!(writer, "      - {}", path.display())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileEntry;
+    use crate::session::SessionSettings;
+    use std::time::SystemTime;
+
+    fn group(id: usize, hash: u8, size: u64, paths: &[&str]) -> SessionGroup {
+        SessionGroup {
+            id,
+            hash: [hash; 32],
+            size,
+            files: paths
+                .iter()
+                .map(|p| FileEntry::new(PathBuf::from(p), size, SystemTime::UNIX_EPOCH))
+                .collect(),
+            reference_paths: Vec::new(),
+            is_similar: false,
+            is_empty_group: false,
+            is_name_similar: false,
+            partial_similarity: None,
+            likely_intentional: false,
+        }
+    }
+
+    fn session(groups: Vec<SessionGroup>) -> Session {
+        Session::new(vec!["/data".into()], SessionSettings::default(), groups)
+    }
+
+    #[test]
+    fn test_diff_sessions_categories() {
+        let old = session(vec![
+            group(0, 1, 100, &["/a/1", "/a/2"]),
+            group(1, 2, 200, &["/b/1", "/b/2", "/b/3"]),
+            group(2, 3, 300, &["/c/1", "/c/2", "/c/3"]),
+            group(3, 4, 400, &["/d/1", "/d/2"]),
+            group(4, 5, 500, &["/e/1", "/e/2"]),
+        ]);
+        let new = session(vec![
+            // 1 removed, 2 grown, 3 shrunk, 4 changed, 5 unchanged, 6 added
+            group(0, 2, 200, &["/b/1", "/b/2", "/b/3", "/b/4"]),
+            group(1, 3, 300, &["/c/1", "/c/3"]),
+            group(2, 4, 400, &["/d/1", "/d/9"]),
+            group(3, 5, 500, &["/e/1", "/e/2"]),
+            group(4, 6, 600, &["/f/1", "/f/2"]),
+        ]);
+
+        let diff = diff_sessions(&old, &new);
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].hash, hash_to_hex(&[6; 32]));
+        assert_eq!(diff.added[0].reclaimable_space, 600);
+
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].hash, hash_to_hex(&[1; 32]));
+        assert_eq!(
+            diff.removed[0].files,
+            vec![PathBuf::from("/a/1"), PathBuf::from("/a/2")]
+        );
+
+        assert_eq!(diff.grown.len(), 1);
+        assert_eq!((diff.grown[0].old_files, diff.grown[0].new_files), (3, 4));
+        assert_eq!(diff.grown[0].added_files, vec![PathBuf::from("/b/4")]);
+        assert!(diff.grown[0].removed_files.is_empty());
+        assert_eq!(
+            (diff.grown[0].old_reclaimable, diff.grown[0].new_reclaimable),
+            (400, 600)
+        );
+
+        assert_eq!(diff.shrunk.len(), 1);
+        assert_eq!(diff.shrunk[0].removed_files, vec![PathBuf::from("/c/2")]);
+        assert!(diff.shrunk[0].added_files.is_empty());
+
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].added_files, vec![PathBuf::from("/d/9")]);
+        assert_eq!(diff.changed[0].removed_files, vec![PathBuf::from("/d/2")]);
+
+        // Old: 100 + 400 + 600 + 400 + 500; new: 600 + 300 + 400 + 500 + 600
+        assert_eq!(diff.old_reclaimable, 2000);
+        assert_eq!(diff.new_reclaimable, 2400);
+        assert_eq!(diff.net_change, 400);
+    }
+
+    #[test]
+    fn test_diff_sessions_identical_and_report() {
+        let groups = vec![group(0, 1, 1024, &["/a", "/b"])];
+        let diff = diff_sessions(&session(groups.clone()), &session(groups));
+        assert!(diff.is_empty());
+        assert_eq!(diff.net_change, 0);
+
+        let mut out = Vec::new();
+        diff.write_text(&mut out, SizeFormat::default()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Reclaimable:  1.0 KiB -> 1.0 KiB (+0 B)\n\nNo changes.\n"
+        );
+
+        let old = session(vec![group(0, 1, 2048, &["/a", "/b"])]);
+        let new = session(vec![group(0, 2, 1024, &["/c", "/d"])]);
+        let diff = diff_sessions(&old, &new);
+        assert_eq!(diff.net_change, -1024);
+
+        let mut out = Vec::new();
+        diff.write_text(&mut out, SizeFormat::default()).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("Reclaimable:  2.0 KiB -> 1.0 KiB (-1.0 KiB)\n"));
+        assert!(text.contains("Added groups (1):\n  + [020202020202] 1.0 KiB  2 files  /c\n"));
+        assert!(text.contains("Removed groups (1):\n  - [010101010101] 2.0 KiB  2 files  /a\n"));
+
+        let mut out = Vec::new();
+        diff.write_json(&mut out, false).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["net_change"], -1024);
+        assert_eq!(json["added"][0]["files"][0], "/c");
+        assert_eq!(json["added"][0]["group_id"], "020202020202");
+    }
+}
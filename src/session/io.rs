@@ -1,13 +1,111 @@
 //! I/O operations for scan sessions.
+//!
+//! Sessions are JSON documents wrapped in a checksummed envelope. Large
+//! sessions can be compressed with gzip or zstd: the envelope is built from
+//! the uncompressed JSON first and then compressed as a whole, and loading
+//! detects the compression from the file's magic bytes.
 
 use crate::session::data::Session;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fs::File;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::Path;
 
+/// Magic bytes at the start of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Magic bytes at the start of a zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// zstd compression level for session files.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Compression applied to a saved session file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionCompression {
+    /// Plain JSON.
+    #[default]
+    None,
+    /// gzip (`.json.gz`).
+    Gzip,
+    /// zstd (`.json.zst`).
+    Zstd,
+}
+
+impl SessionCompression {
+    /// Pick the compression implied by a file name ending in `.gz` or `.zst`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustdupe::session::SessionCompression;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(
+    ///     SessionCompression::from_path(Path::new("scan.json.zst")),
+    ///     SessionCompression::Zstd
+    /// );
+    /// assert_eq!(
+    ///     SessionCompression::from_path(Path::new("scan.json")),
+    ///     SessionCompression::None
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("gz") => Self::Gzip,
+            Some(ext) if ext.eq_ignore_ascii_case("zst") => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+
+    /// Compress `data`.
+    fn compress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(data.to_vec()),
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::Zstd => zstd::encode_all(data, ZSTD_LEVEL),
+        }
+    }
+
+    /// Detect the compression of `data` from its magic bytes.
+    fn detect(data: &[u8]) -> Self {
+        if data.starts_with(&GZIP_MAGIC) {
+            Self::Gzip
+        } else if data.starts_with(&ZSTD_MAGIC) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// Decompress session file contents to the JSON text they hold.
+fn decode_session_bytes(data: Vec<u8>) -> Result<String> {
+    let json = match SessionCompression::detect(&data) {
+        SessionCompression::None => data,
+        SessionCompression::Gzip => {
+            let mut json = Vec::new();
+            flate2::read::MultiGzDecoder::new(data.as_slice())
+                .read_to_end(&mut json)
+                .context("Failed to decompress gzip session")?;
+            json
+        }
+        SessionCompression::Zstd => {
+            zstd::decode_all(data.as_slice()).context("Failed to decompress zstd session")?
+        }
+    };
+    String::from_utf8(json).context("Session file is not valid UTF-8")
+}
+
 /// Envelope for session files to include integrity checks.
 #[derive(Debug, Serialize, Deserialize)]
 struct SessionEnvelope {
@@ -20,6 +118,9 @@ struct SessionEnvelope {
 impl Session {
     /// Saves the session to a file with an integrity checksum.
     ///
+    /// Paths ending in `.gz` or `.zst` are compressed accordingly (see
+    /// [`SessionCompression::from_path`]).
+    ///
     /// # Arguments
     ///
     /// * `path` - The destination file path.
@@ -28,10 +129,24 @@ impl Session {
     ///
     /// Returns an error if serialization fails or if the file cannot be written.
     pub fn save(&self, path: &Path) -> Result<()> {
+        self.save_with(path, SessionCompression::from_path(path))
+    }
+
+    /// Saves the session to a file with the given compression, whatever the
+    /// file's extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or compression fails, or if the
+    /// file cannot be written.
+    pub fn save_with(&self, path: &Path, compression: SessionCompression) -> Result<()> {
         let json = self.to_json()?;
+        let data = compression
+            .compress(json.as_bytes())
+            .with_context(|| format!("Failed to compress session for: {}", path.display()))?;
         let mut file = File::create(path)
             .with_context(|| format!("Failed to create session file: {}", path.display()))?;
-        file.write_all(json.as_bytes())
+        file.write_all(&data)
             .with_context(|| format!("Failed to write session to: {}", path.display()))?;
         Ok(())
     }
@@ -69,6 +184,9 @@ impl Session {
 
     /// Loads a session from a file and verifies its integrity.
     ///
+    /// gzip and zstd compressed files are detected by their magic bytes and
+    /// decompressed transparently.
+    ///
     /// # Arguments
     ///
     /// * `path` - The path to the session file.
@@ -81,7 +199,9 @@ impl Session {
     /// * The integrity checksum mismatch.
     /// * The session version is unsupported.
     pub fn load(path: &Path) -> Result<Self> {
-        let content = std::fs::read_to_string(path)
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read session file: {}", path.display()))?;
+        let content = decode_session_bytes(data)
             .with_context(|| format!("Failed to read session file: {}", path.display()))?;
 
         let envelope: SessionEnvelope = serde_json::from_str(&content).context(
@@ -149,6 +269,10 @@ mod tests {
             ],
             reference_paths: Vec::new(),
             is_similar: false,
+            is_empty_group: false,
+            is_name_similar: false,
+            partial_similarity: None,
+            likely_intentional: false,
         }];
         let session = Session::new(vec!["/tmp".into()], settings, groups);
 
@@ -175,6 +299,10 @@ mod tests {
             ],
             reference_paths: Vec::new(),
             is_similar: false,
+            is_empty_group: false,
+            is_name_similar: false,
+            partial_similarity: None,
+            likely_intentional: false,
         }];
         let mut session = Session::new(vec!["/tmp".into()], settings, groups);
         session.user_selections.insert("/tmp/c.txt".into());
@@ -204,9 +332,14 @@ mod tests {
             ],
             reference_paths: Vec::new(),
             is_similar: false,
+            is_empty_group: false,
+            is_name_similar: false,
+            partial_similarity: None,
+            likely_intentional: false,
         }];
         let mut session = Session::new(vec!["/tmp".into()], settings, groups);
         session.user_selections.insert("/tmp/c.txt".into());
+        session.keepers.insert("/tmp/d.txt".into());
 
         session.save(&path).unwrap();
 
@@ -218,6 +351,7 @@ mod tests {
         assert!(loaded
             .user_selections
             .contains(&PathBuf::from("/tmp/c.txt")));
+        assert_eq!(loaded.keepers, session.keepers);
     }
 
     #[test]
@@ -290,6 +424,10 @@ mod tests {
             ],
             reference_paths: Vec::new(),
             is_similar: false,
+            is_empty_group: false,
+            is_name_similar: false,
+            partial_similarity: None,
+            likely_intentional: false,
         }];
         let session = Session::new(vec!["/tmp".into()], SessionSettings::default(), groups);
 
@@ -322,6 +460,10 @@ mod tests {
                 ],
                 reference_paths: Vec::new(),
                 is_similar: false,
+                is_empty_group: false,
+                is_name_similar: false,
+                partial_similarity: None,
+                likely_intentional: false,
             });
         }
 
@@ -363,6 +505,10 @@ mod tests {
             )],
             reference_paths: Vec::new(),
             is_similar: false,
+            is_empty_group: false,
+            is_name_similar: false,
+            partial_similarity: None,
+            likely_intentional: false,
         }];
         let session = Session::new(vec!["/tmp".into()], SessionSettings::default(), groups);
         session.save(&path).unwrap();
@@ -394,4 +540,89 @@ mod tests {
         assert!(json.contains("\"file_categories\": ["));
         assert!(json.contains("\"Images\""));
     }
+
+    fn compression_fixture() -> Session {
+        let now = std::time::SystemTime::now();
+        let groups = (0..50)
+            .map(|i| SessionGroup {
+                id: i,
+                hash: [(i % 256) as u8; 32],
+                size: 100,
+                files: vec![
+                    crate::scanner::FileEntry::new(format!("/tmp/a_{i}.txt").into(), 100, now),
+                    crate::scanner::FileEntry::new(format!("/tmp/b_{i}.txt").into(), 100, now),
+                ],
+                reference_paths: Vec::new(),
+                is_similar: false,
+                is_empty_group: false,
+                is_name_similar: false,
+                partial_similarity: None,
+                likely_intentional: false,
+            })
+            .collect();
+        let mut session = Session::new(vec!["/tmp".into()], SessionSettings::default(), groups);
+        session.user_selections.insert("/tmp/b_7.txt".into());
+        session.keepers.insert("/tmp/a_3.txt".into());
+        session
+    }
+
+    #[test]
+    fn test_session_compression_round_trip() {
+        let dir = tempdir().unwrap();
+        let session = compression_fixture();
+
+        let mut loaded = Vec::new();
+        for (name, expected, magic) in [
+            ("plain.json", SessionCompression::None, &b"{"[..]),
+            ("gzip.json.gz", SessionCompression::Gzip, &GZIP_MAGIC[..]),
+            ("zstd.json.zst", SessionCompression::Zstd, &ZSTD_MAGIC[..]),
+        ] {
+            let path = dir.path().join(name);
+            assert_eq!(SessionCompression::from_path(&path), expected);
+            session.save(&path).unwrap();
+            assert!(std::fs::read(&path).unwrap().starts_with(magic), "{name}");
+
+            // Load verifies the checksum of the decompressed envelope
+            let session = Session::load(&path).unwrap();
+            loaded.push(serde_json::to_string(&session).unwrap());
+        }
+
+        assert_eq!(loaded[0], serde_json::to_string(&session).unwrap());
+        assert_eq!(loaded[0], loaded[1]);
+        assert_eq!(loaded[0], loaded[2]);
+    }
+
+    #[test]
+    fn test_session_forced_compression_ignores_extension() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        let session = compression_fixture();
+
+        session.save_with(&path, SessionCompression::Zstd).unwrap();
+        let data = std::fs::read(&path).unwrap();
+        assert!(data.starts_with(&ZSTD_MAGIC));
+        assert!(data.len() < session.to_json().unwrap().len());
+
+        let loaded = Session::load(&path).unwrap();
+        assert_eq!(loaded.user_selections, session.user_selections);
+        assert_eq!(loaded.groups.len(), 50);
+    }
+
+    #[test]
+    fn test_compressed_session_checksum_still_verified() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("session.json.gz");
+        let session = compression_fixture();
+        let tampered = session
+            .to_json()
+            .unwrap()
+            .replace("\"checksum\": \"", "\"checksum\": \"bad");
+        let data = SessionCompression::Gzip
+            .compress(tampered.as_bytes())
+            .unwrap();
+        std::fs::write(&path, data).unwrap();
+
+        let err = Session::load(&path).unwrap_err();
+        assert!(err.to_string().contains("integrity check failed"));
+    }
 }
@@ -10,13 +10,21 @@
 //! * **Integrity**: Each session file is wrapped in an envelope with a SHA256 checksum.
 //! * **Versioning**: Supports versioned data formats to handle future schema changes.
 //! * **Portability**: Files are stored in a human-readable JSON format.
+//! * **Compression**: Optional gzip or zstd compression, detected on load.
 //!
 //! # Architecture
 //!
 //! * [`data`]: Serializable models for sessions, groups, and settings.
 //! * [`io`]: Logic for saving, loading, and verifying session files.
+//! * [`diff`]: Comparison of two sessions by group hash.
+//! * [`verify`]: Re-checking a session's files against the disk.
 
 pub mod data;
+pub mod diff;
 pub mod io;
+pub mod verify;
 
 pub use data::{Session, SessionGroup, SessionSettings, SESSION_VERSION};
+pub use diff::{diff_sessions, SessionDiff};
+pub use io::SessionCompression;
+pub use verify::{verify_session, VerifiedFile, VerifyReport, VerifyStatus};
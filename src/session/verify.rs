@@ -0,0 +1,328 @@
+//! Re-checking a session's files against the disk (`rustdupe verify`).
+//!
+//! A session records every file's size and modification time along with its
+//! group's content hash. Before acting on an old session, [`verify_session`]
+//! confirms that each file still exists and still matches:
+//!
+//! - **Full mode** (the default) rehashes each file and compares the result
+//!   with its group hash. Files above the session's `sample_above` threshold
+//!   are compared by sampled hash, as in the scan that produced the session.
+//! - **Fast mode** compares size and modification time only.
+//!
+//! Similar-image, similar-document and name-cluster groups share no content
+//! hash, so their files are always checked by metadata. Sessions scanned
+//! with `--include-streams` are rehashed with alternate streams folded in.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::scanner::Hasher;
+//! use rustdupe::session::{verify_session, Session};
+//! use std::path::Path;
+//!
+//! let session = Session::load(Path::new("backup.json")).unwrap();
+//! let report = verify_session(&session, Some(&Hasher::new()));
+//! for file in report.mismatches() {
+//!     println!("{}: {}", file.path.display(), file.status);
+//! }
+//! ```
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use rayon::prelude::*;
+
+use super::data::{Session, SessionGroup};
+use crate::scanner::{FileEntry, Hasher};
+
+/// What verification found for one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyStatus {
+    /// The file still matches the session
+    Unchanged,
+    /// The file no longer exists
+    Missing,
+    /// The file size differs from the session
+    SizeChanged {
+        /// Size recorded in the session
+        expected: u64,
+        /// Size on disk
+        actual: u64,
+    },
+    /// The modification time differs from the session (fast mode)
+    ModifiedChanged,
+    /// The content no longer matches the group hash (full mode)
+    HashChanged,
+    /// The file could not be inspected or read
+    Unreadable(String),
+}
+
+impl std::fmt::Display for VerifyStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unchanged => write!(f, "unchanged"),
+            Self::Missing => write!(f, "file no longer exists"),
+            Self::SizeChanged { expected, actual } => {
+                write!(f, "size changed ({} -> {} bytes)", expected, actual)
+            }
+            Self::ModifiedChanged => write!(f, "modified since the session was saved"),
+            Self::HashChanged => write!(f, "content no longer matches the group hash"),
+            Self::Unreadable(e) => write!(f, "cannot read file: {}", e),
+        }
+    }
+}
+
+/// Verification result for one session file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedFile {
+    /// Path of the file
+    pub path: PathBuf,
+    /// What was found
+    pub status: VerifyStatus,
+}
+
+/// Verification results for every file of a session, in session order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// One entry per session file
+    pub files: Vec<VerifiedFile>,
+}
+
+impl VerifyReport {
+    /// Files that no longer match the session.
+    pub fn mismatches(&self) -> impl Iterator<Item = &VerifiedFile> {
+        self.files
+            .iter()
+            .filter(|f| f.status != VerifyStatus::Unchanged)
+    }
+
+    /// Whether every file still matches.
+    #[must_use]
+    pub fn all_match(&self) -> bool {
+        self.mismatches().next().is_none()
+    }
+
+    /// Number of files that still match.
+    #[must_use]
+    pub fn unchanged_count(&self) -> usize {
+        self.files.len() - self.mismatches().count()
+    }
+
+    /// Number of files that no longer exist.
+    #[must_use]
+    pub fn missing_count(&self) -> usize {
+        self.mismatches()
+            .filter(|f| f.status == VerifyStatus::Missing)
+            .count()
+    }
+
+    /// Write each mismatch followed by a one-line summary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_text<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for file in self.mismatches() {
+            let label = match file.status {
+                VerifyStatus::Missing => "Missing",
+                VerifyStatus::Unreadable(_) => "Unreadable",
+                _ => "Changed",
+            };
+            writeln!(
+                writer,
+                "{}: {} ({})",
+                label,
+                file.path.display(),
+                file.status
+            )?;
+        }
+        let missing = self.missing_count();
+        writeln!(
+            writer,
+            "Verified {} file(s): {} unchanged, {} changed, {} missing",
+            self.files.len(),
+            self.unchanged_count(),
+            self.files.len() - self.unchanged_count() - missing,
+            missing
+        )
+    }
+}
+
+/// Check every file of `session` against the disk.
+///
+/// With a `hasher`, files of exact-match groups are rehashed and compared
+/// with their group hash; without one, only size and modification time are
+/// compared.
+#[must_use]
+pub fn verify_session(session: &Session, hasher: Option<&Hasher>) -> VerifyReport {
+    let sample_above = session.settings.sample_above;
+    let hasher = hasher.map(|h| {
+        h.clone()
+            .with_include_streams(session.settings.include_streams)
+    });
+    let hasher = hasher.as_ref();
+    let files: Vec<(&SessionGroup, &FileEntry)> = session
+        .groups
+        .iter()
+        .flat_map(|g| g.files.iter().map(move |f| (g, f)))
+        .collect();
+
+    let files = files
+        .into_par_iter()
+        .map(|(group, file)| VerifiedFile {
+            path: file.path.clone(),
+            status: verify_file(group, file, hasher, sample_above),
+        })
+        .collect();
+    VerifyReport { files }
+}
+
+/// Compare one file on disk with its session entry.
+fn verify_file(
+    group: &SessionGroup,
+    file: &FileEntry,
+    hasher: Option<&Hasher>,
+    sample_above: Option<u64>,
+) -> VerifyStatus {
+    let metadata = match fs::metadata(&file.path) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        Ok(_) => return VerifyStatus::Missing,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return VerifyStatus::Missing,
+        Err(e) => return VerifyStatus::Unreadable(e.to_string()),
+    };
+    if metadata.len() != file.size {
+        return VerifyStatus::SizeChanged {
+            expected: file.size,
+            actual: metadata.len(),
+        };
+    }
+
+    let content_hashed = !(group.is_similar || group.is_name_similar || group.is_empty_group);
+    match hasher.filter(|_| content_hashed) {
+        Some(hasher) => {
+            let hash = if sample_above.is_some_and(|threshold| file.size > threshold) {
+                hasher.sample_hash(&file.path, file.size)
+            } else {
+                hasher.full_hash(&file.path)
+            }
+            .and_then(|hash| hasher.fold_streams(&file.path, hash));
+            match hash {
+                Ok(hash) if hash == group.hash => VerifyStatus::Unchanged,
+                Ok(_) => VerifyStatus::HashChanged,
+                Err(e) => VerifyStatus::Unreadable(e.to_string()),
+            }
+        }
+        None => match metadata.modified() {
+            Ok(modified) if modified == file.modified => VerifyStatus::Unchanged,
+            Ok(_) => VerifyStatus::ModifiedChanged,
+            Err(e) => VerifyStatus::Unreadable(e.to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::SessionSettings;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn entry(path: &Path) -> FileEntry {
+        let metadata = fs::metadata(path).unwrap();
+        FileEntry::new(
+            path.to_path_buf(),
+            metadata.len(),
+            metadata.modified().unwrap(),
+        )
+    }
+
+    /// A session with one exact group of three identical files.
+    fn fixture(dir: &TempDir) -> (Session, Vec<PathBuf>) {
+        let paths: Vec<PathBuf> = ["a.txt", "b.txt", "c.txt"]
+            .iter()
+            .map(|name| dir.path().join(name))
+            .collect();
+        for path in &paths {
+            fs::write(path, "duplicate content").unwrap();
+        }
+        let group = SessionGroup {
+            id: 0,
+            hash: Hasher::new().full_hash(&paths[0]).unwrap(),
+            size: 17,
+            files: paths.iter().map(|p| entry(p)).collect(),
+            reference_paths: Vec::new(),
+            is_similar: false,
+            is_empty_group: false,
+            is_name_similar: false,
+            partial_similarity: None,
+            likely_intentional: false,
+        };
+        let session = Session::new(
+            vec![dir.path().to_path_buf()],
+            SessionSettings::default(),
+            vec![group],
+        );
+        (session, paths)
+    }
+
+    #[test]
+    fn test_verify_session_unchanged() {
+        let dir = TempDir::new().unwrap();
+        let (session, _) = fixture(&dir);
+
+        let report = verify_session(&session, Some(&Hasher::new()));
+        assert!(report.all_match());
+        assert_eq!(report.unchanged_count(), 3);
+        assert!(verify_session(&session, None).all_match());
+    }
+
+    #[test]
+    fn test_verify_session_detects_changes() {
+        let dir = TempDir::new().unwrap();
+        let (session, paths) = fixture(&dir);
+
+        // Same size, different content: only full mode notices
+        fs::write(&paths[0], "DUPLICATE CONTENT").unwrap();
+        fs::write(&paths[1], "shorter").unwrap();
+        fs::remove_file(&paths[2]).unwrap();
+
+        let report = verify_session(&session, Some(&Hasher::new()));
+        assert!(!report.all_match());
+        assert_eq!(report.files[0].status, VerifyStatus::HashChanged);
+        assert_eq!(
+            report.files[1].status,
+            VerifyStatus::SizeChanged {
+                expected: 17,
+                actual: 7
+            }
+        );
+        assert_eq!(report.files[2].status, VerifyStatus::Missing);
+        assert_eq!(report.missing_count(), 1);
+
+        let mut out = Vec::new();
+        report.write_text(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains(&format!("Missing: {}", paths[2].display())));
+        assert!(text.ends_with("Verified 3 file(s): 0 unchanged, 2 changed, 1 missing\n"));
+    }
+
+    #[test]
+    fn test_verify_session_fast_mode_uses_metadata() {
+        let dir = TempDir::new().unwrap();
+        let (mut session, paths) = fixture(&dir);
+
+        // Pretend the session recorded an older modification time
+        session.groups[0].files[0].modified = std::time::SystemTime::UNIX_EPOCH;
+        fs::remove_file(&paths[1]).unwrap();
+
+        let report = verify_session(&session, None);
+        assert_eq!(report.files[0].status, VerifyStatus::ModifiedChanged);
+        assert_eq!(report.files[1].status, VerifyStatus::Missing);
+        assert_eq!(report.files[2].status, VerifyStatus::Unchanged);
+
+        // Full mode only cares about content
+        let report = verify_session(&session, Some(&Hasher::new()));
+        assert_eq!(report.files[0].status, VerifyStatus::Unchanged);
+    }
+}
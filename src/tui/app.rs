@@ -47,13 +47,33 @@
 //! assert!(app.is_file_selected(&PathBuf::from("/b.txt")));
 //! ```
 
-use std::collections::HashSet;
-use std::path::PathBuf;
-
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::actions::delete::{DeleteConfig, DeleteError, DeleteMode, TrashLocation};
+use crate::actions::preview::TextPager;
+use crate::actions::rename::KeeperRename;
 use crate::cli::ThemeArg;
-use crate::duplicates::DuplicateGroup;
+use crate::duplicates::{DuplicateGroup, ProtectPolicy};
+use crate::output::size::SizeFormat;
+use crate::scanner::FileEntry;
 use crate::tui::theme::Theme;
 
+/// Default number of files above which deletion requires typing [`TYPED_CONFIRMATION_WORD`].
+pub const DEFAULT_CONFIRM_THRESHOLD_COUNT: usize = 100;
+
+/// Default total size (10 GB) above which deletion requires typing [`TYPED_CONFIRMATION_WORD`].
+pub const DEFAULT_CONFIRM_THRESHOLD_BYTES: u64 = 10_000_000_000;
+
+/// Word that must be typed to confirm a deletion above the thresholds.
+pub const TYPED_CONFIRMATION_WORD: &str = "DELETE";
+
+/// Lines of a paged text preview loaded at a time (more than any screen shows).
+const PREVIEW_WINDOW_LINES: usize = 200;
+
 /// Application mode/state.
 ///
 /// Represents the current state of the TUI application. Modes control
@@ -67,10 +87,14 @@ pub enum AppMode {
     Reviewing,
     /// Previewing a file's content
     Previewing,
+    /// Showing a line diff between the current file and its group's reference file
+    Diffing,
     /// Confirming a deletion operation
     Confirming,
     /// Confirming a bulk selection operation
     ConfirmingBulkSelection,
+    /// Stepping through pending bulk selections, un-marking files before applying
+    ReviewingPending,
     /// Selecting a folder for batch selection
     SelectingFolder,
     /// Selecting a named group for batch selection
@@ -79,8 +103,12 @@ pub enum AppMode {
     InputtingExtension,
     /// Inputting a directory for bulk selection
     InputtingDirectory,
+    /// Inputting the destination file for exporting the selected paths
+    InputtingExportPath,
     /// Searching duplicate groups
     Searching,
+    /// Typing a group number to jump to
+    JumpingToGroup,
     /// Exporting results
     Exporting,
     /// Showing help overlay with keybinding reference
@@ -96,6 +124,7 @@ impl AppMode {
         matches!(
             self,
             Self::Reviewing
+                | Self::ReviewingPending
                 | Self::SelectingFolder
                 | Self::SelectingGroup
                 | Self::Searching
@@ -116,13 +145,17 @@ impl AppMode {
         matches!(
             self,
             Self::Previewing
+                | Self::Diffing
                 | Self::Confirming
                 | Self::ConfirmingBulkSelection
+                | Self::ReviewingPending
                 | Self::SelectingFolder
                 | Self::SelectingGroup
                 | Self::InputtingExtension
                 | Self::InputtingDirectory
+                | Self::InputtingExportPath
                 | Self::Searching
+                | Self::JumpingToGroup
                 | Self::Exporting
                 | Self::ShowingHelp
         )
@@ -139,6 +172,8 @@ pub enum GroupFilter {
     ExactOnly,
     /// Show only similar image groups
     SimilarOnly,
+    /// Hide groups tagged as likely-intentional copies
+    HideIntentional,
 }
 
 impl GroupFilter {
@@ -148,7 +183,8 @@ impl GroupFilter {
         match self {
             Self::All => Self::ExactOnly,
             Self::ExactOnly => Self::SimilarOnly,
-            Self::SimilarOnly => Self::All,
+            Self::SimilarOnly => Self::HideIntentional,
+            Self::HideIntentional => Self::All,
         }
     }
 
@@ -159,8 +195,170 @@ impl GroupFilter {
             Self::All => "All",
             Self::ExactOnly => "Exact Only",
             Self::SimilarOnly => "Similar Only",
+            Self::HideIntentional => "Hide Intentional",
+        }
+    }
+
+    /// Whether `group` passes the filter.
+    #[must_use]
+    pub fn matches(&self, group: &DuplicateGroup) -> bool {
+        match self {
+            Self::All => true,
+            Self::ExactOnly => !group.is_similar,
+            Self::SimilarOnly => group.is_similar,
+            Self::HideIntentional => !group.likely_intentional,
+        }
+    }
+}
+
+/// Rule deciding which file of each group a bulk selection keeps.
+///
+/// Every bulk action marks all other files of a group for deletion, skipping
+/// protected ones, so the strategy only has to pick the survivor.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum KeeperStrategy {
+    /// Keep the first file in the group
+    #[default]
+    First,
+    /// Keep the file with the earliest modification time
+    Oldest,
+    /// Keep the file with the latest modification time
+    Newest,
+    /// Keep the file with the shortest path
+    ShortestPath,
+    /// Keep the first file under this directory, or the first file if none is
+    InPreferredDir(PathBuf),
+}
+
+impl KeeperStrategy {
+    /// Index of the file to keep in `files`.
+    ///
+    /// Ties go to the earliest file. Returns 0 for an empty slice.
+    #[must_use]
+    pub fn choose_keeper(&self, files: &[FileEntry]) -> usize {
+        // min_by_key returns the first of equal minimums
+        let mut indexed = files.iter().enumerate();
+        let keeper = match self {
+            Self::First => None,
+            Self::Oldest => indexed.min_by_key(|(_, f)| f.modified),
+            Self::Newest => indexed.min_by_key(|(_, f)| std::cmp::Reverse(f.modified)),
+            Self::ShortestPath => indexed.min_by_key(|(_, f)| f.path.as_os_str().len()),
+            Self::InPreferredDir(dir) => indexed.find(|(_, f)| f.path.starts_with(dir)),
+        };
+        keeper.map_or(0, |(i, _)| i)
+    }
+
+    /// Get the next strategy in rotation.
+    ///
+    /// The preferred-directory strategy is offered only when `preferred_dir`
+    /// is given.
+    #[must_use]
+    pub fn next(&self, preferred_dir: Option<&Path>) -> Self {
+        match self {
+            Self::First => Self::Oldest,
+            Self::Oldest => Self::Newest,
+            Self::Newest => Self::ShortestPath,
+            Self::ShortestPath => {
+                preferred_dir.map_or(Self::First, |dir| Self::InPreferredDir(dir.to_path_buf()))
+            }
+            Self::InPreferredDir(_) => Self::First,
+        }
+    }
+
+    /// Get the display name of the strategy.
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        match self {
+            Self::First => "first".to_string(),
+            Self::Oldest => "oldest".to_string(),
+            Self::Newest => "newest".to_string(),
+            Self::ShortestPath => "shortest path".to_string(),
+            Self::InPreferredDir(dir) => format!("in {}", dir.display()),
+        }
+    }
+}
+
+/// How the search query is matched against file paths and group names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    /// Case-insensitive substring match
+    Substring,
+    /// Case-insensitive regex, falling back to substring matching
+    #[default]
+    Regex,
+    /// Case-insensitive glob such as `*.jpg`, where `*` also crosses `/`
+    Glob,
+}
+
+impl SearchMode {
+    /// Get the next search mode in rotation.
+    #[must_use]
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Substring => Self::Regex,
+            Self::Regex => Self::Glob,
+            Self::Glob => Self::Substring,
+        }
+    }
+
+    /// Get the display name of the search mode.
+    #[must_use]
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Substring => "Substring",
+            Self::Regex => "Regex",
+            Self::Glob => "Glob",
+        }
+    }
+}
+
+/// Search query compiled for the active [`SearchMode`].
+enum SearchMatcher {
+    Substring(String),
+    Regex(Option<regex::Regex>, String),
+    /// `None` if the glob failed to compile; it then matches nothing.
+    Glob(Option<globset::GlobMatcher>),
+}
+
+impl SearchMatcher {
+    fn new(query: &str, mode: SearchMode) -> Self {
+        match mode {
+            SearchMode::Substring => Self::Substring(query.to_lowercase()),
+            SearchMode::Regex => Self::Regex(
+                regex::RegexBuilder::new(query)
+                    .case_insensitive(true)
+                    .build()
+                    .ok(),
+                query.to_lowercase(),
+            ),
+            SearchMode::Glob => Self::Glob(
+                globset::GlobBuilder::new(query)
+                    .case_insensitive(true)
+                    .build()
+                    .ok()
+                    .map(|glob| glob.compile_matcher()),
+            ),
         }
     }
+
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            Self::Substring(query) => text.to_lowercase().contains(query),
+            Self::Regex(re, query) => {
+                re.as_ref().is_some_and(|r| r.is_match(text)) || text.to_lowercase().contains(query)
+            }
+            Self::Glob(glob) => glob.as_ref().is_some_and(|g| g.is_match(text)),
+        }
+    }
+
+    /// Check whether any file in `group` matches by path or group name.
+    fn matches_group(&self, group: &DuplicateGroup) -> bool {
+        group.files.iter().any(|file| {
+            let group_name = file.group_name.as_deref().unwrap_or("");
+            self.is_match(&file.path.to_string_lossy())
+                || (!group_name.is_empty() && self.is_match(group_name))
+        })
+    }
 }
 
 /// User action triggered by keyboard input.
@@ -191,10 +389,14 @@ pub enum Action {
     SelectOldest,
     /// Select newest file in each group (keep oldest)
     SelectNewest,
+    /// Pre-mark all but the newest file in each group, then review before applying
+    ReviewKeepNewest,
     /// Select smallest file in each group (actually selects all but first since they match)
     SelectSmallest,
     /// Select largest file in each group (actually selects all but first since they match)
     SelectLargest,
+    /// Select all but one file in each group, keeping the file a chosen strategy picks
+    SelectByStrategy,
     /// Select files by extension (global)
     SelectByExtension,
     /// Select files by directory (global)
@@ -205,16 +407,46 @@ pub enum Action {
     DeselectAll,
     /// Preview the selected file
     Preview,
+    /// Reveal the current file in the platform file manager
+    RevealInFileManager,
+    /// Copy the current file's absolute path to the clipboard
+    CopyPath,
+    /// Copy every path of the current group to the clipboard, one per line
+    CopyGroupPaths,
+    /// Diff the current file against its group's reference file
+    DiffWithReference,
     /// Enter folder selection mode
     SelectFolder,
     /// Enter named group selection mode
     SelectGroup,
     /// Enter search mode
     Search,
+    /// Jump to a group by its number
+    JumpToGroup,
+    /// Cycle which list columns are visible
+    ToggleColumns,
     /// Export selected files to a format
     Export,
+    /// Write the selected file paths to a plain file list
+    ExportSelection,
     /// Delete selected files (to trash)
     Delete,
+    /// Restore the most recent deletion from the trash
+    UndoDelete,
+    /// Pause hashing of an in-progress scan
+    PauseScan,
+    /// Resume a paused scan
+    ResumeScan,
+    /// Mark the current file as the one to keep in its group
+    MarkAsKeeper,
+    /// Toggle a keep-mark on the current file (several per group allowed)
+    MarkKeepInverse,
+    /// Select every unmarked file in groups with at least one keep-mark
+    SelectComplementForDeletion,
+    /// Pin the current group so searches and filters never hide it
+    TogglePin,
+    /// Toggle the current group between a flat file list and a directory rollup
+    ToggleDirectoryRollup,
     /// Toggle theme
     ToggleTheme,
     /// Toggle expand/collapse of current group
@@ -231,6 +463,8 @@ pub enum Action {
     ReverseSortDirection,
     /// Cycle group filter (all, exact only, similar only)
     CycleGroupFilter,
+    /// Cycle search mode (substring, regex, glob)
+    ToggleSearchMode,
     /// Show help overlay with keybinding reference
     ShowHelp,
     /// Confirm current action
@@ -266,19 +500,36 @@ impl Action {
             Self::SelectAllDuplicates => "select_all_duplicates",
             Self::SelectOldest => "select_oldest",
             Self::SelectNewest => "select_newest",
+            Self::ReviewKeepNewest => "review_keep_newest",
             Self::SelectSmallest => "select_smallest",
             Self::SelectLargest => "select_largest",
+            Self::SelectByStrategy => "select_by_strategy",
             Self::SelectByExtension => "select_by_extension",
             Self::SelectByDirectory => "select_by_directory",
             Self::UndoSelection => "undo_selection",
             Self::DeselectAll => "deselect_all",
             Self::Preview => "preview",
+            Self::RevealInFileManager => "reveal_in_file_manager",
+            Self::CopyPath => "copy_path",
+            Self::CopyGroupPaths => "copy_group_paths",
+            Self::DiffWithReference => "diff_with_reference",
             Self::SelectFolder => "select_folder",
             Self::SelectGroup => "select_group",
             Self::Search => "search",
+            Self::JumpToGroup => "jump_to_group",
             Self::Export => "export",
+            Self::ExportSelection => "export_selection",
             Self::Delete => "delete",
+            Self::UndoDelete => "undo_delete",
+            Self::PauseScan => "pause_scan",
+            Self::ResumeScan => "resume_scan",
+            Self::MarkAsKeeper => "mark_as_keeper",
+            Self::MarkKeepInverse => "mark_keep_inverse",
+            Self::SelectComplementForDeletion => "select_complement_for_deletion",
+            Self::TogglePin => "toggle_pin",
+            Self::ToggleDirectoryRollup => "toggle_directory_rollup",
             Self::ToggleTheme => "toggle_theme",
+            Self::ToggleColumns => "toggle_columns",
             Self::ToggleExpand => "toggle_expand",
             Self::ExpandAll => "expand_all",
             Self::CollapseAll => "collapse_all",
@@ -286,6 +537,7 @@ impl Action {
             Self::CycleSortColumn => "cycle_sort_column",
             Self::ReverseSortDirection => "reverse_sort_direction",
             Self::CycleGroupFilter => "cycle_group_filter",
+            Self::ToggleSearchMode => "toggle_search_mode",
             Self::ShowHelp => "show_help",
             Self::Confirm => "confirm",
             Self::Cancel => "cancel",
@@ -308,19 +560,36 @@ impl Action {
             "select_all_duplicates",
             "select_oldest",
             "select_newest",
+            "review_keep_newest",
             "select_smallest",
             "select_largest",
+            "select_by_strategy",
             "select_by_extension",
             "select_by_directory",
             "undo_selection",
             "deselect_all",
             "preview",
+            "reveal_in_file_manager",
+            "copy_path",
+            "copy_group_paths",
+            "diff_with_reference",
             "select_folder",
             "select_group",
             "search",
+            "jump_to_group",
             "export",
+            "export_selection",
             "delete",
+            "undo_delete",
+            "pause_scan",
+            "resume_scan",
+            "mark_as_keeper",
+            "mark_keep_inverse",
+            "select_complement_for_deletion",
+            "toggle_pin",
+            "toggle_directory_rollup",
             "toggle_theme",
+            "toggle_columns",
             "toggle_expand",
             "expand_all",
             "collapse_all",
@@ -328,6 +597,7 @@ impl Action {
             "cycle_sort_column",
             "reverse_sort_direction",
             "cycle_group_filter",
+            "toggle_search_mode",
             "show_help",
             "confirm",
             "cancel",
@@ -337,7 +607,7 @@ impl Action {
 
     /// Returns all action variants.
     #[must_use]
-    pub const fn all() -> [Action; 35] {
+    pub const fn all() -> [Action; 53] {
         [
             Self::NavigateUp,
             Self::NavigateDown,
@@ -350,19 +620,36 @@ impl Action {
             Self::SelectAllDuplicates,
             Self::SelectOldest,
             Self::SelectNewest,
+            Self::ReviewKeepNewest,
             Self::SelectSmallest,
             Self::SelectLargest,
+            Self::SelectByStrategy,
             Self::SelectByExtension,
             Self::SelectByDirectory,
             Self::UndoSelection,
             Self::DeselectAll,
             Self::Preview,
+            Self::RevealInFileManager,
+            Self::CopyPath,
+            Self::CopyGroupPaths,
+            Self::DiffWithReference,
             Self::SelectFolder,
             Self::SelectGroup,
             Self::Search,
+            Self::JumpToGroup,
             Self::Export,
+            Self::ExportSelection,
             Self::Delete,
+            Self::UndoDelete,
+            Self::PauseScan,
+            Self::ResumeScan,
+            Self::MarkAsKeeper,
+            Self::MarkKeepInverse,
+            Self::SelectComplementForDeletion,
+            Self::TogglePin,
+            Self::ToggleDirectoryRollup,
             Self::ToggleTheme,
+            Self::ToggleColumns,
             Self::ToggleExpand,
             Self::ExpandAll,
             Self::CollapseAll,
@@ -370,6 +657,7 @@ impl Action {
             Self::CycleSortColumn,
             Self::ReverseSortDirection,
             Self::CycleGroupFilter,
+            Self::ToggleSearchMode,
             Self::ShowHelp,
             Self::Confirm,
             Self::Cancel,
@@ -394,19 +682,40 @@ impl std::str::FromStr for Action {
             "select_all_duplicates" | "select_all" => Ok(Self::SelectAllDuplicates),
             "select_oldest" | "oldest" => Ok(Self::SelectOldest),
             "select_newest" | "newest" => Ok(Self::SelectNewest),
+            "review_keep_newest" | "keep_newest" => Ok(Self::ReviewKeepNewest),
             "select_smallest" | "smallest" => Ok(Self::SelectSmallest),
             "select_largest" | "largest" => Ok(Self::SelectLargest),
+            "select_by_strategy" | "strategy" => Ok(Self::SelectByStrategy),
             "select_by_extension" | "extension" => Ok(Self::SelectByExtension),
             "select_by_directory" | "directory" => Ok(Self::SelectByDirectory),
             "undo_selection" | "undo" => Ok(Self::UndoSelection),
             "deselect_all" | "deselect" => Ok(Self::DeselectAll),
             "preview" => Ok(Self::Preview),
+            "reveal_in_file_manager" | "reveal" => Ok(Self::RevealInFileManager),
+            "copy_path" | "copy" => Ok(Self::CopyPath),
+            "copy_group_paths" => Ok(Self::CopyGroupPaths),
+            "diff_with_reference" | "diff" => Ok(Self::DiffWithReference),
             "select_folder" | "folder" => Ok(Self::SelectFolder),
             "select_group" | "group" => Ok(Self::SelectGroup),
             "search" | "/" => Ok(Self::Search),
+            "jump_to_group" | "jump" | ":" => Ok(Self::JumpToGroup),
             "export" | "x" => Ok(Self::Export),
+            "export_selection" | "export_list" => Ok(Self::ExportSelection),
             "delete" => Ok(Self::Delete),
+            "undo_delete" | "undo_deletion" | "restore" => Ok(Self::UndoDelete),
+            "pause_scan" | "pause" => Ok(Self::PauseScan),
+            "resume_scan" | "resume" => Ok(Self::ResumeScan),
+            "mark_as_keeper" | "keeper" | "keep" => Ok(Self::MarkAsKeeper),
+            "mark_keep_inverse" | "keep_mark" => Ok(Self::MarkKeepInverse),
+            "select_complement_for_deletion" | "select_complement" | "complement" => {
+                Ok(Self::SelectComplementForDeletion)
+            }
+            "toggle_pin" | "pin" => Ok(Self::TogglePin),
+            "toggle_directory_rollup" | "directory_rollup" | "rollup" => {
+                Ok(Self::ToggleDirectoryRollup)
+            }
             "toggle_theme" | "theme" => Ok(Self::ToggleTheme),
+            "toggle_columns" | "columns" => Ok(Self::ToggleColumns),
             "toggle_expand" | "expand" | "collapse" => Ok(Self::ToggleExpand),
             "expand_all" => Ok(Self::ExpandAll),
             "collapse_all" => Ok(Self::CollapseAll),
@@ -416,6 +725,7 @@ impl std::str::FromStr for Action {
                 Ok(Self::ReverseSortDirection)
             }
             "cycle_group_filter" | "group_filter" | "v" => Ok(Self::CycleGroupFilter),
+            "toggle_search_mode" | "search_mode" => Ok(Self::ToggleSearchMode),
             "show_help" | "help" => Ok(Self::ShowHelp),
             "confirm" | "enter" => Ok(Self::Confirm),
             "cancel" | "escape" | "esc" => Ok(Self::Cancel),
@@ -431,10 +741,146 @@ impl std::fmt::Display for Action {
     }
 }
 
+/// One row of a group shown as a directory rollup.
+///
+/// See [`App::rollup_rows`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RollupRow {
+    /// Directory header with the number of group files directly inside it
+    Directory {
+        /// Parent directory shared by the following files
+        path: PathBuf,
+        /// Number of files in the directory
+        count: usize,
+    },
+    /// File, by index into the group's file list
+    File(usize),
+}
+
+/// Distinct parent directories of the files in `group`, sorted.
+fn group_folders(group: &DuplicateGroup) -> Vec<PathBuf> {
+    let mut folders: Vec<PathBuf> = group
+        .files
+        .iter()
+        .filter_map(|f| f.path.parent().map(|p| p.to_path_buf()))
+        .collect();
+    folders.sort();
+    folders.dedup();
+    folders
+}
+
+/// Files of `group` grouped by parent directory.
+///
+/// Returns each directory in sorted order with the indices of the files
+/// directly inside it, in group order.
+#[must_use]
+pub fn directory_rollup(group: &DuplicateGroup) -> Vec<(PathBuf, Vec<usize>)> {
+    group_folders(group)
+        .into_iter()
+        .map(|dir| {
+            let files = group
+                .files
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| f.path.parent() == Some(dir.as_path()))
+                .map(|(i, _)| i)
+                .collect();
+            (dir, files)
+        })
+        .collect()
+}
+
+/// Live metrics about the current selection, shown in the footer.
+///
+/// Derived from the selected files and the duplicate groups each time
+/// [`App::statistics`] is called, so it can never go stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReviewStats {
+    /// Number of selected files
+    pub selected_files: usize,
+    /// Total size of the selected files in bytes
+    pub selected_bytes: u64,
+    /// Number of groups with at least one selected file
+    pub groups_touched: usize,
+    /// Total number of groups
+    pub total_groups: usize,
+    /// Unit system used when displaying `selected_bytes`
+    pub size_format: SizeFormat,
+}
+
+impl std::fmt::Display for ReviewStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "selected: {} files / {} / {} of {} groups touched",
+            self.selected_files,
+            self.size_format.format(self.selected_bytes),
+            self.groups_touched,
+            self.total_groups
+        )
+    }
+}
+
+/// What deleting the current selection would do to one group.
+///
+/// Returned by [`App::selection_plan`] so callers can inspect the outcome
+/// before anything is removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupPlan {
+    /// Content hash of the group (not unique: groups split by
+    /// `--dedupe-within` share it)
+    pub hash: [u8; 32],
+    /// File that survives the deletion
+    pub keeper: PathBuf,
+    /// Files selected for deletion, in group order
+    pub delete: Vec<PathBuf>,
+}
+
+/// A file moved to the trash during this session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashedFile {
+    /// Path of the file in its duplicate group
+    pub path: PathBuf,
+    /// Where the file went: the system trash or a `--trash-dir` directory
+    pub location: TrashLocation,
+}
+
+/// Result of undoing a deletion with [`App::undo_deletion`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UndoOutcome {
+    /// Files restored and put back into their groups
+    pub restored: Vec<PathBuf>,
+    /// Files that stayed in the trash, with the reason
+    pub failures: Vec<(PathBuf, String)>,
+}
+
+/// One completed deletion that can still be undone.
+#[derive(Debug, Clone)]
+struct DeletionBatch {
+    /// Files moved to the trash
+    trashed: Vec<TrashedFile>,
+    /// Touched groups as they were before the deletion, with their positions
+    groups: Vec<(usize, DuplicateGroup)>,
+}
+
+impl DeletionBatch {
+    /// Drop files that are gone for good from the group snapshots.
+    ///
+    /// Files trashed by this batch itself are kept so they can be restored.
+    fn forget(&mut self, gone: &HashSet<&PathBuf>) {
+        let own: HashSet<&PathBuf> = self.trashed.iter().map(|t| &t.path).collect();
+        for (_, group) in &mut self.groups {
+            group
+                .files
+                .retain(|f| own.contains(&f.path) || !gone.contains(&f.path));
+        }
+    }
+}
+
 /// Scan summary for display in TUI.
 ///
 /// Contains statistics about the completed scan to display to the user.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct ScanProgress {
     /// Current phase name (e.g., "Walking", "Prehashing", "Full hashing")
     pub phase: String,
@@ -446,6 +892,27 @@ pub struct ScanProgress {
     pub total: usize,
     /// Human-readable status message
     pub message: String,
+    /// Bytes processed so far in the current phase
+    pub bytes_done: u64,
+    /// Total bytes to process in the current phase (0 if unknown)
+    pub bytes_total: u64,
+    /// When the current phase started
+    pub started_at: Instant,
+}
+
+impl Default for ScanProgress {
+    fn default() -> Self {
+        Self {
+            phase: String::new(),
+            current_path: String::new(),
+            current: 0,
+            total: 0,
+            message: String::new(),
+            bytes_done: 0,
+            bytes_total: 0,
+            started_at: Instant::now(),
+        }
+    }
 }
 
 impl ScanProgress {
@@ -471,6 +938,44 @@ impl ScanProgress {
             ((self.current as f64 / self.total as f64) * 100.0).min(100.0) as u16
         }
     }
+
+    /// Average throughput in bytes per second since the phase started.
+    #[must_use]
+    pub fn throughput(&self) -> f64 {
+        self.throughput_after(self.started_at.elapsed())
+    }
+
+    /// Estimated time remaining based on the current byte rate.
+    ///
+    /// Returns `None` if the byte total is unknown or no bytes have been
+    /// processed yet.
+    #[must_use]
+    pub fn eta(&self) -> Option<Duration> {
+        self.eta_after(self.started_at.elapsed())
+    }
+
+    /// Throughput in bytes per second after `elapsed` time.
+    fn throughput_after(&self, elapsed: Duration) -> f64 {
+        let secs = elapsed.as_secs_f64();
+        if secs <= 0.0 {
+            0.0
+        } else {
+            self.bytes_done as f64 / secs
+        }
+    }
+
+    /// Remaining time after `elapsed`, extrapolated from the average byte rate.
+    fn eta_after(&self, elapsed: Duration) -> Option<Duration> {
+        if self.bytes_total == 0 {
+            return None;
+        }
+        let rate = self.throughput_after(elapsed);
+        if rate <= 0.0 {
+            return None;
+        }
+        let remaining = self.bytes_total.saturating_sub(self.bytes_done);
+        Some(Duration::from_secs_f64(remaining as f64 / rate))
+    }
 }
 
 /// Types of bulk selection actions.
@@ -479,13 +984,16 @@ pub enum BulkSelectionType {
     AllDuplicates,
     Oldest,
     Newest,
+    ReviewKeepNewest,
     Smallest,
     Largest,
+    ByStrategy,
     ByExtension,
     ByDirectory,
     InGroup,
     InFolder,
     InNamedGroup,
+    KeepComplement,
 }
 
 /// Column used for sorting duplicate groups.
@@ -558,6 +1066,104 @@ impl SortDirection {
     }
 }
 
+/// Optional column of the group and file lists.
+///
+/// The path (or group label) is always shown and takes whatever width the
+/// visible columns leave over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// Group size and wasted space, or per-file size for name clusters
+    Size,
+    /// File modification time
+    Date,
+    /// Number of copies in a group
+    Count,
+    /// Abbreviated content hash of a group
+    Hash,
+}
+
+/// Which optional columns are visible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VisibleColumns {
+    /// Show sizes
+    pub size: bool,
+    /// Show modification times
+    pub date: bool,
+    /// Show copy counts
+    pub count: bool,
+    /// Show content hashes
+    pub hash: bool,
+}
+
+impl VisibleColumns {
+    /// Check if `column` is visible.
+    #[must_use]
+    pub fn contains(&self, column: Column) -> bool {
+        match column {
+            Column::Size => self.size,
+            Column::Date => self.date,
+            Column::Count => self.count,
+            Column::Hash => self.hash,
+        }
+    }
+}
+
+/// Column sets cycled by [`Action::ToggleColumns`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnPreset {
+    /// Size, date and copy count
+    #[default]
+    Standard,
+    /// Every column, including the content hash
+    Full,
+    /// Size and copy count, leaving more room for paths
+    Compact,
+    /// Paths only
+    Paths,
+}
+
+impl ColumnPreset {
+    /// Get the next preset in rotation.
+    #[must_use]
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Standard => Self::Full,
+            Self::Full => Self::Compact,
+            Self::Compact => Self::Paths,
+            Self::Paths => Self::Standard,
+        }
+    }
+
+    /// Get the display name of the preset.
+    #[must_use]
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Standard => "Standard",
+            Self::Full => "Full",
+            Self::Compact => "Compact",
+            Self::Paths => "Paths Only",
+        }
+    }
+
+    /// Columns shown by this preset.
+    #[must_use]
+    pub fn columns(&self) -> VisibleColumns {
+        let (size, date, count, hash) = match self {
+            Self::Standard => (true, true, true, false),
+            Self::Full => (true, true, true, true),
+            Self::Compact => (true, false, true, false),
+            Self::Paths => (false, false, false, false),
+        };
+        VisibleColumns {
+            size,
+            date,
+            count,
+            hash,
+        }
+    }
+}
+
 /// TUI application state.
 ///
 /// The central state container for the TUI application. Manages:
@@ -576,6 +1182,12 @@ pub struct App {
     mode: AppMode,
     /// Duplicate groups to display
     groups: Vec<DuplicateGroup>,
+    /// Position of the first group with each hash, rebuilt whenever they change
+    hash_index: HashMap<[u8; 32], usize>,
+    /// [Group ID](crate::duplicates::group_ids) of each group in `groups`
+    group_ids: Vec<String>,
+    /// Position of each group in `groups` by group ID
+    id_index: HashMap<String, usize>,
     /// Currently selected group index
     group_index: usize,
     /// Currently selected file index within the group
@@ -588,10 +1200,18 @@ pub struct App {
     selected_files: HashSet<PathBuf>,
     /// Scan progress (for Scanning mode)
     scan_progress: ScanProgress,
+    /// Progress shared with a running scan, copied into `scan_progress` each frame
+    shared_scan_progress: Option<Arc<Mutex<ScanProgress>>>,
     /// Error message to display (if any)
     error_message: Option<String>,
-    /// Preview content (for Previewing mode)
+    /// Preview content (for Previewing and Diffing modes)
     preview_content: Option<String>,
+    /// Pager over the previewed text file (None for other previews)
+    preview_pager: Option<TextPager>,
+    /// First visible line of a paged text preview
+    preview_offset: usize,
+    /// First visible line of the diff pane (for Diffing mode)
+    diff_scroll: usize,
     /// Folder list for selection mode
     folder_list: Vec<PathBuf>,
     /// Currently selected folder index
@@ -602,22 +1222,70 @@ pub struct App {
     group_name_index: usize,
     /// Search query string
     search_query: String,
+    /// How the search query is matched
+    search_mode: SearchMode,
     /// Input query for bulk selection
     input_query: String,
     /// Indices of groups matching the search query (None if no search active)
     filtered_indices: Option<Vec<usize>>,
     /// Protected reference paths
     reference_paths: Vec<PathBuf>,
+    /// Match reference paths case-insensitively (`--ignore-case-paths`)
+    ignore_case_paths: bool,
+    /// Scan roots bounding empty-directory cleanup (None if disabled)
+    empty_dir_roots: Option<Vec<PathBuf>>,
+    /// Directories whose files are permanently deleted instead of trashed
+    permanent_under: Vec<PathBuf>,
+    /// Refuse to delete files modified within this many seconds
+    settle_seconds: Option<u64>,
+    /// Owners whose files may be deleted (`--only-owner`)
+    owner_uids: Option<Vec<u32>>,
+    /// Owners whose files are never deleted (`--exclude-owner`)
+    exclude_owner_uids: Vec<u32>,
+    /// Refuse deletions totalling more than this many bytes
+    max_total_bytes: Option<u64>,
+    /// Directory replacing the system trash (`--trash-dir`)
+    trash_dir: Option<PathBuf>,
+    /// Keep extended attributes of files moved into the trash directory
+    preserve_xattrs: bool,
+    /// All-or-nothing deletion batches (`--transactional-delete`)
+    transactional_delete: bool,
+    /// Substitution applied to surviving keepers after deletion (None if disabled)
+    keeper_rename: Option<KeeperRename>,
+    /// Policy choosing one protected file per group
+    protect_policy: ProtectPolicy,
+    /// Files protected by the current policy (one per group)
+    protected_files: HashSet<PathBuf>,
+    /// Whether hardlinks are protected (`--hardlinks protect`)
+    protect_hardlinks: bool,
+    /// Hardlinked files that cannot be selected (empty unless protecting)
+    hardlink_files: HashSet<PathBuf>,
+    /// Files designated to be kept, at most one per group. Keyed by path
+    /// rather than hash, since groups can share a hash (e.g. split by
+    /// `--dedupe-within`) and a path belongs to exactly one group.
+    keepers: HashSet<PathBuf>,
+    /// Files marked to keep by the inverse-selection workflow
+    keep_marks: HashSet<PathBuf>,
+    /// Groups kept visible under any search or filter, by group hash
+    pinned_groups: HashSet<[u8; 32]>,
     /// History of selections for undo
     selection_history: Vec<HashSet<PathBuf>>,
+    /// Completed trash deletions that can be undone, oldest first
+    deletion_history: Vec<DeletionBatch>,
     /// Pending selections for preview
     pending_selections: HashSet<PathBuf>,
     /// Type of pending bulk selection
     pending_bulk_action: Option<BulkSelectionType>,
+    /// Keeper strategy of the last strategy-driven bulk selection
+    keeper_strategy: KeeperStrategy,
+    /// Pause flag shared with the hashing workers of an in-progress scan
+    pause_flag: Option<Arc<AtomicBool>>,
     /// Total reclaimable space in bytes
     reclaimable_space: u64,
     /// Number of visible rows in the UI (for scroll calculation)
     visible_rows: usize,
+    /// Number of text lines visible in the preview dialog (for paging)
+    preview_rows: usize,
     /// Dry-run mode active (no deletions allowed)
     dry_run: bool,
     /// TUI theme setting
@@ -628,16 +1296,35 @@ pub struct App {
     keybindings: Option<crate::tui::keybindings::KeyBindings>,
     /// Expanded group hashes
     expanded_groups: HashSet<[u8; 32]>,
+    /// Member paths of groups shown as a directory rollup (by path, since
+    /// groups can share a hash)
+    rolled_up_groups: HashSet<PathBuf>,
+    /// Whether the rollup cursor is on the current file's directory header
+    rollup_on_directory: bool,
     /// Column to sort groups by
     sort_column: SortColumn,
     /// Direction to sort groups
     sort_direction: SortDirection,
     /// Accessible mode for screen reader compatibility
     accessible: bool,
+    /// Unit system used to display byte sizes
+    size_format: SizeFormat,
+    /// Show full modification timestamps instead of relative ages
+    absolute_times: bool,
+    /// Optional columns shown in the group and file lists
+    column_preset: ColumnPreset,
     /// Whether to export only selected files
     export_selected: bool,
+    /// Separate exported selection paths with NUL instead of newline
+    export_nul_separated: bool,
     /// Filter for duplicate groups
     group_filter: GroupFilter,
+    /// Confirmations still required before the pending deletion runs
+    delete_confirmations: u8,
+    /// Deleting more files than this requires a typed confirmation
+    confirm_threshold_count: usize,
+    /// Deleting more bytes than this requires a typed confirmation
+    confirm_threshold_bytes: u64,
 }
 
 impl Default for App {
@@ -662,37 +1349,76 @@ impl App {
         Self {
             mode: AppMode::Scanning,
             groups: Vec::new(),
+            hash_index: HashMap::new(),
+            group_ids: Vec::new(),
+            id_index: HashMap::new(),
             group_index: 0,
             file_index: 0,
             group_scroll: 0,
             file_scroll: 0,
             selected_files: HashSet::new(),
             scan_progress: ScanProgress::new(),
+            shared_scan_progress: None,
             error_message: None,
             preview_content: None,
+            preview_pager: None,
+            preview_offset: 0,
+            diff_scroll: 0,
             folder_list: Vec::new(),
             folder_index: 0,
             group_name_list: Vec::new(),
             group_name_index: 0,
             search_query: String::new(),
+            search_mode: SearchMode::default(),
             input_query: String::new(),
             filtered_indices: None,
             reference_paths: Vec::new(),
+            ignore_case_paths: false,
+            empty_dir_roots: None,
+            permanent_under: Vec::new(),
+            settle_seconds: None,
+            owner_uids: None,
+            exclude_owner_uids: Vec::new(),
+            max_total_bytes: None,
+            trash_dir: None,
+            preserve_xattrs: false,
+            transactional_delete: false,
+            keeper_rename: None,
+            protect_policy: ProtectPolicy::None,
+            protected_files: HashSet::new(),
+            protect_hardlinks: false,
+            hardlink_files: HashSet::new(),
+            keepers: HashSet::new(),
+            keep_marks: HashSet::new(),
+            pinned_groups: HashSet::new(),
             selection_history: Vec::new(),
+            deletion_history: Vec::new(),
             pending_selections: HashSet::new(),
             pending_bulk_action: None,
+            keeper_strategy: KeeperStrategy::default(),
+            pause_flag: None,
             reclaimable_space: 0,
             visible_rows: 20, // Default, will be updated by UI
+            preview_rows: 20,
             dry_run: false,
             theme_arg: ThemeArg::Auto,
             theme: Theme::dark(),
             keybindings: None,
             expanded_groups: HashSet::new(),
+            rolled_up_groups: HashSet::new(),
+            rollup_on_directory: false,
             sort_column: SortColumn::default(),
             sort_direction: SortDirection::default(),
             accessible: false,
+            size_format: SizeFormat::default(),
+            absolute_times: false,
+            column_preset: ColumnPreset::default(),
             export_selected: false,
+            export_nul_separated: false,
             group_filter: GroupFilter::default(),
+            delete_confirmations: 0,
+            confirm_threshold_count: DEFAULT_CONFIRM_THRESHOLD_COUNT,
+            confirm_threshold_bytes: DEFAULT_CONFIRM_THRESHOLD_BYTES,
         }
     }
 
@@ -776,6 +1502,59 @@ impl App {
         self.accessible
     }
 
+    /// Set the unit system used to display byte sizes (`--size-units`).
+    pub fn with_size_format(mut self, size_format: SizeFormat) -> Self {
+        self.size_format = size_format;
+        self
+    }
+
+    /// Unit system used to display byte sizes.
+    #[must_use]
+    pub fn size_format(&self) -> SizeFormat {
+        self.size_format
+    }
+
+    /// Show full modification timestamps instead of relative ages.
+    pub fn with_absolute_times(mut self, absolute_times: bool) -> Self {
+        self.absolute_times = absolute_times;
+        self
+    }
+
+    /// Set whether full modification timestamps are shown.
+    pub fn set_absolute_times(&mut self, absolute_times: bool) {
+        self.absolute_times = absolute_times;
+    }
+
+    /// Check if full modification timestamps are shown.
+    #[must_use]
+    pub fn absolute_times(&self) -> bool {
+        self.absolute_times
+    }
+
+    /// Start with the given column preset.
+    pub fn with_column_preset(mut self, preset: ColumnPreset) -> Self {
+        self.column_preset = preset;
+        self
+    }
+
+    /// Get the current column preset.
+    #[must_use]
+    pub fn column_preset(&self) -> ColumnPreset {
+        self.column_preset
+    }
+
+    /// Get the columns visible with the current preset.
+    #[must_use]
+    pub fn visible_columns(&self) -> VisibleColumns {
+        self.column_preset.columns()
+    }
+
+    /// Cycle to the next column preset.
+    pub fn toggle_columns(&mut self) {
+        self.column_preset = self.column_preset.next();
+        log::debug!("Column preset cycled to {:?}", self.column_preset);
+    }
+
     /// Check if export only selected is enabled.
     #[must_use]
     pub fn export_selected(&self) -> bool {
@@ -792,6 +1571,17 @@ impl App {
         self.export_selected = enabled;
     }
 
+    /// Check if the selection export uses NUL separators.
+    #[must_use]
+    pub fn export_nul_separated(&self) -> bool {
+        self.export_nul_separated
+    }
+
+    /// Toggle between newline and NUL separators for the selection export.
+    pub fn toggle_export_nul_separated(&mut self) {
+        self.export_nul_separated = !self.export_nul_separated;
+    }
+
     /// Set dry-run mode for the application.
     pub fn with_dry_run(mut self, dry_run: bool) -> Self {
         self.dry_run = dry_run;
@@ -804,6 +1594,38 @@ impl App {
         self.dry_run
     }
 
+    /// Share the scan's pause flag so the scan screen can pause hashing.
+    ///
+    /// See [`FinderConfig::with_pause_flag`](crate::duplicates::FinderConfig::with_pause_flag).
+    #[must_use]
+    pub fn with_pause_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.pause_flag = Some(flag);
+        self
+    }
+
+    /// Follow the progress of a scan running on another thread.
+    ///
+    /// See [`Progress::with_scan_progress`](crate::progress::Progress::with_scan_progress).
+    #[must_use]
+    pub fn with_scan_progress(mut self, shared: Arc<Mutex<ScanProgress>>) -> Self {
+        self.shared_scan_progress = Some(shared);
+        self
+    }
+
+    /// Whether the scan can be paused from the scan screen.
+    #[must_use]
+    pub fn can_pause_scan(&self) -> bool {
+        self.pause_flag.is_some()
+    }
+
+    /// Whether the in-progress scan is paused.
+    #[must_use]
+    pub fn is_scan_paused(&self) -> bool {
+        self.pause_flag
+            .as_ref()
+            .is_some_and(|f| f.load(Ordering::SeqCst))
+    }
+
     /// Set reference paths for the application.
     pub fn with_reference_paths(mut self, paths: Vec<PathBuf>) -> Self {
         self.reference_paths = paths;
@@ -815,50 +1637,493 @@ impl App {
         self.reference_paths = paths;
     }
 
-    /// Check if a path is in a protected reference directory.
-    pub fn is_in_reference_dir(&self, path: &std::path::Path) -> bool {
-        self.reference_paths.iter().any(|ref_path| {
-            if cfg!(windows) {
-                // Windows is case-insensitive. Convert to lowercase PathBuf for reliable
-                // component-based comparison.
-                let p = std::path::PathBuf::from(path.to_string_lossy().to_lowercase());
-                let r = std::path::PathBuf::from(ref_path.to_string_lossy().to_lowercase());
-                p.starts_with(r)
-            } else {
-                path.starts_with(ref_path)
-            }
-        })
+    /// Get the protected reference paths.
+    #[must_use]
+    pub fn reference_paths(&self) -> &[PathBuf] {
+        &self.reference_paths
     }
 
-    /// Check if a group is expanded.
-    #[must_use]
-    pub fn is_expanded(&self, group_hash: &[u8; 32]) -> bool {
-        self.expanded_groups.contains(group_hash)
+    /// Match reference paths case-insensitively, as on a Windows share.
+    pub fn with_ignore_case_paths(mut self, enabled: bool) -> Self {
+        self.ignore_case_paths = enabled;
+        self
     }
 
-    /// Check if the currently selected group is expanded.
-    #[must_use]
-    pub fn is_current_group_expanded(&self) -> bool {
-        self.current_group()
-            .map(|g| self.is_expanded(&g.hash))
-            .unwrap_or(false)
+    /// Remove directories left empty after deletion, stopping at `roots`.
+    pub fn with_empty_dir_cleanup(mut self, roots: Vec<PathBuf>) -> Self {
+        self.empty_dir_roots = Some(roots);
+        self
     }
 
-    /// Create an App with pre-loaded duplicate groups.
-    ///
-    /// The app starts in Reviewing mode if groups are provided.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use rustdupe::tui::app::App;
-    /// use rustdupe::duplicates::DuplicateGroup;
-    /// let app = App::with_groups(vec![]);
-    /// ```
+    /// Get the scan roots for empty-directory cleanup, if enabled.
     #[must_use]
-    pub fn with_groups(groups: Vec<DuplicateGroup>) -> Self {
-        let reclaimable = groups.iter().map(DuplicateGroup::wasted_space).sum();
-        let mode = if groups.is_empty() {
+    pub fn empty_dir_cleanup_roots(&self) -> Option<&[PathBuf]> {
+        self.empty_dir_roots.as_deref()
+    }
+
+    /// Permanently delete files under `dirs` instead of trashing them.
+    pub fn with_permanent_under(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.permanent_under = dirs;
+        self
+    }
+
+    /// Refuse to delete files modified within the last `seconds` seconds.
+    pub fn with_settle_seconds(mut self, seconds: Option<u64>) -> Self {
+        self.settle_seconds = seconds;
+        self
+    }
+
+    /// Refuse deletions totalling more than `bytes` (`--max-total-delete`).
+    pub fn with_max_total_bytes(mut self, bytes: Option<u64>) -> Self {
+        self.max_total_bytes = bytes;
+        self
+    }
+
+    /// Move deleted files into `dir` instead of the system trash (`--trash-dir`).
+    pub fn with_trash_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.trash_dir = dir;
+        self
+    }
+
+    /// Keep extended attributes of files moved into the trash directory
+    /// (`--preserve-xattrs`).
+    pub fn with_preserve_xattrs(mut self, preserve: bool) -> Self {
+        self.preserve_xattrs = preserve;
+        self
+    }
+
+    /// Restore already-trashed files when a deletion fails (`--transactional-delete`).
+    pub fn with_transactional_delete(mut self, transactional: bool) -> Self {
+        self.transactional_delete = transactional;
+        self
+    }
+
+    /// Refuse to delete files rejected by the owner filters.
+    pub fn with_owner_filter(mut self, only: Option<Vec<u32>>, exclude: Vec<u32>) -> Self {
+        self.owner_uids = only;
+        self.exclude_owner_uids = exclude;
+        self
+    }
+
+    /// Rename surviving keepers with `rule` after each deletion.
+    pub fn with_keeper_rename(mut self, rule: KeeperRename) -> Self {
+        self.keeper_rename = Some(rule);
+        self
+    }
+
+    /// Get the keeper rename rule, if enabled.
+    #[must_use]
+    pub fn keeper_rename(&self) -> Option<&KeeperRename> {
+        self.keeper_rename.as_ref()
+    }
+
+    /// Deletion configuration for the selected files.
+    ///
+    /// Files are trashed (into `--trash-dir` if set) unless a
+    /// `--permanent-under` rule matches; reference directories are always
+    /// trashed. Files modified within the
+    /// `--settle` window or rejected by the owner filters are refused, as
+    /// are batches larger than `--max-total-delete`. With
+    /// `--transactional-delete` a failed batch restores what it trashed.
+    #[must_use]
+    pub fn delete_config(&self) -> DeleteConfig {
+        DeleteConfig::trash()
+            .with_permanent_under(&self.permanent_under)
+            .with_protected_paths(self.reference_paths.clone())
+            .with_settle_seconds(self.settle_seconds)
+            .with_owner_filter(self.owner_uids.clone(), self.exclude_owner_uids.clone())
+            .with_max_total_bytes(self.max_total_bytes)
+            .with_trash_override(self.trash_dir.clone())
+            .with_preserve_xattrs(self.preserve_xattrs)
+            .with_transactional(self.transactional_delete)
+    }
+
+    /// Number of selected files that will be permanently deleted.
+    #[must_use]
+    pub fn selected_permanent_count(&self) -> usize {
+        let config = self.delete_config();
+        self.selected_files
+            .iter()
+            .filter(|p| config.mode_for(p) == DeleteMode::Permanent)
+            .count()
+    }
+
+    /// Set the protect policy for the application.
+    pub fn with_protect_policy(mut self, policy: ProtectPolicy) -> Self {
+        self.set_protect_policy(policy);
+        self
+    }
+
+    /// Set the protect policy and recompute protected files.
+    pub fn set_protect_policy(&mut self, policy: ProtectPolicy) {
+        self.protect_policy = policy;
+        self.refresh_protected_files();
+    }
+
+    /// Get the active protect policy.
+    #[must_use]
+    pub fn protect_policy(&self) -> ProtectPolicy {
+        self.protect_policy
+    }
+
+    /// Protect hardlinked files, since deleting one frees no space.
+    pub fn with_protect_hardlinks(mut self, protect: bool) -> Self {
+        self.protect_hardlinks = protect;
+        self.refresh_protected_files();
+        self
+    }
+
+    /// Check if a file was chosen by the protect policy.
+    #[must_use]
+    pub fn is_policy_protected(&self, path: &std::path::Path) -> bool {
+        self.protected_files.contains(path)
+    }
+
+    /// Check if a file is a hardlink protected by `--hardlinks protect`.
+    #[must_use]
+    pub fn is_hardlink_protected(&self, path: &std::path::Path) -> bool {
+        self.hardlink_files.contains(path)
+    }
+
+    /// Check if a file is the designated keeper of its group.
+    #[must_use]
+    pub fn is_keeper(&self, path: &std::path::Path) -> bool {
+        self.keepers.contains(path)
+    }
+
+    /// Check if a file carries a keep-mark (Action::MarkKeepInverse).
+    #[must_use]
+    pub fn is_keep_marked(&self, path: &std::path::Path) -> bool {
+        self.keep_marks.contains(path)
+    }
+
+    /// Check if a file can never be selected, either because it lives in a
+    /// reference directory, the protect policy chose it, it is a protected
+    /// hardlink, it is its group's designated keeper, or it is marked to keep.
+    #[must_use]
+    pub fn is_protected(&self, path: &std::path::Path) -> bool {
+        self.is_policy_protected(path)
+            || self.is_hardlink_protected(path)
+            || self.is_in_reference_dir(path)
+            || self.is_keeper(path)
+            || self.is_keep_marked(path)
+    }
+
+    /// Path of the file bulk selections keep in `group`: its designated
+    /// keeper, or the first file if none was marked.
+    fn kept_file(&self, group: &DuplicateGroup) -> Option<PathBuf> {
+        group
+            .files
+            .iter()
+            .find(|f| self.keepers.contains(&f.path))
+            .or_else(|| group.files.first())
+            .map(|f| f.path.clone())
+    }
+
+    /// Keeper and deletions for every group with a selected file.
+    ///
+    /// Each group keeps its designated keeper, or its first file if none was
+    /// marked. If that file is itself selected, the first unselected file is
+    /// kept instead. Groups are listed in their current sort order, including
+    /// those hidden by the current search or filter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeleteError::AllCopiesWouldBeDeleted`] if every file of a
+    /// group is selected.
+    pub fn selection_plan(&self) -> Result<Vec<GroupPlan>, DeleteError> {
+        let mut plans = Vec::new();
+        for group in &self.groups {
+            let delete: Vec<PathBuf> = group
+                .files
+                .iter()
+                .filter(|f| self.selected_files.contains(&f.path))
+                .map(|f| f.path.clone())
+                .collect();
+            if delete.is_empty() {
+                continue;
+            }
+            let keeper = self
+                .kept_file(group)
+                .filter(|path| !self.selected_files.contains(path))
+                .or_else(|| {
+                    group
+                        .files
+                        .iter()
+                        .find(|f| !self.selected_files.contains(&f.path))
+                        .map(|f| f.path.clone())
+                })
+                .ok_or(DeleteError::AllCopiesWouldBeDeleted)?;
+            plans.push(GroupPlan {
+                hash: group.hash,
+                keeper,
+                delete,
+            });
+        }
+        Ok(plans)
+    }
+
+    /// Toggle the highlighted file as its group's designated keeper.
+    ///
+    /// The keeper is deselected and excluded from every bulk selection in
+    /// place of the group's first file. Marking the current keeper again
+    /// clears the designation.
+    pub fn mark_as_keeper(&mut self) {
+        let Some(members) = self.current_group().map(|g| g.paths()) else {
+            return;
+        };
+        let Some(path) = self.current_file().cloned() else {
+            return;
+        };
+
+        if self.keepers.remove(&path) {
+            log::debug!("Cleared keeper: {}", path.display());
+            return;
+        }
+
+        // Replace any other keeper of the same group
+        for member in &members {
+            self.keepers.remove(member);
+        }
+        self.selected_files.remove(&path);
+        self.pending_selections.remove(&path);
+        log::debug!("Marked keeper: {}", path.display());
+        self.keepers.insert(path);
+    }
+
+    /// Toggle a keep-mark on the highlighted file.
+    ///
+    /// Unlike [`mark_as_keeper`](Self::mark_as_keeper), any number of files
+    /// per group can be marked. Marked files are deselected and skipped by
+    /// every selection; [`select_complement`](Self::select_complement) then
+    /// selects the rest of their groups.
+    pub fn toggle_keep_mark(&mut self) {
+        let Some(path) = self.current_file().cloned() else {
+            return;
+        };
+
+        if self.keep_marks.remove(&path) {
+            log::debug!("Cleared keep-mark: {}", path.display());
+            return;
+        }
+
+        self.selected_files.remove(&path);
+        self.pending_selections.remove(&path);
+        log::debug!("Marked to keep: {}", path.display());
+        self.keep_marks.insert(path);
+    }
+
+    /// Number of files carrying a keep-mark.
+    #[must_use]
+    pub fn keep_mark_count(&self) -> usize {
+        self.keep_marks.len()
+    }
+
+    /// Files [`select_complement`](Self::select_complement) would newly mark,
+    /// per group.
+    ///
+    /// Only groups with at least one keep-marked file take part. Their other
+    /// files are marked unless protected or already selected, and a group is
+    /// left alone if marking would leave none of its files unselected.
+    fn complement_selections(&self) -> Vec<([u8; 32], Vec<PathBuf>)> {
+        let mut selections = Vec::new();
+        for group in &self.groups {
+            if !group
+                .files
+                .iter()
+                .any(|f| self.keep_marks.contains(&f.path))
+            {
+                continue;
+            }
+            let marked: Vec<PathBuf> = group
+                .files
+                .iter()
+                .filter(|f| !self.is_protected(&f.path) && !self.selected_files.contains(&f.path))
+                .map(|f| f.path.clone())
+                .collect();
+            let keeps_one = group
+                .files
+                .iter()
+                .any(|f| !self.selected_files.contains(&f.path) && !marked.contains(&f.path));
+            if !marked.is_empty() && keeps_one {
+                selections.push((group.hash, marked));
+            }
+        }
+        selections
+    }
+
+    /// Stage every file not marked to keep for deletion, in each group with
+    /// at least one keep-mark.
+    ///
+    /// Groups without keep-marks are left untouched, so no group can end up
+    /// with zero kept files. Reference and otherwise protected files are
+    /// never marked.
+    pub fn select_complement(&mut self) {
+        if self.keep_marks.is_empty() {
+            self.set_error("No files marked to keep");
+            return;
+        }
+        let pending: HashSet<PathBuf> = self
+            .complement_selections()
+            .into_iter()
+            .flat_map(|(_, paths)| paths)
+            .collect();
+        if pending.is_empty() {
+            log::debug!("No new files to select outside the keep-marks");
+            return;
+        }
+
+        self.pending_selections = pending;
+        self.pending_bulk_action = Some(BulkSelectionType::KeepComplement);
+        self.set_mode(AppMode::ConfirmingBulkSelection);
+    }
+
+    /// Get designated keepers as a BTreeSet for deterministic serialization.
+    #[must_use]
+    pub fn keepers_btree(&self) -> std::collections::BTreeSet<PathBuf> {
+        self.keepers.iter().cloned().collect()
+    }
+
+    /// Restore designated keepers from a session.
+    ///
+    /// Paths that are no longer part of any group are ignored; if several
+    /// paths belong to the same group, the last one wins.
+    pub fn apply_keepers(&mut self, keepers: std::collections::BTreeSet<PathBuf>) {
+        self.keepers.clear();
+        for path in keepers {
+            let Some(group) = self
+                .groups
+                .iter()
+                .find(|g| g.files.iter().any(|f| f.path == path))
+            else {
+                continue;
+            };
+            for file in &group.files {
+                self.keepers.remove(&file.path);
+            }
+            self.selected_files.remove(&path);
+            self.keepers.insert(path);
+        }
+    }
+
+    /// Check if a group is pinned.
+    #[must_use]
+    pub fn is_pinned(&self, hash: &[u8; 32]) -> bool {
+        self.pinned_groups.contains(hash)
+    }
+
+    /// Pin or unpin the current group.
+    ///
+    /// Pinned groups stay visible under every search query and group filter.
+    /// Unpinning a group the active search excludes hides it again.
+    pub fn toggle_pin(&mut self) {
+        let Some(hash) = self.current_group().map(|g| g.hash) else {
+            return;
+        };
+        let pinned = self.pinned_groups.insert(hash);
+        if !pinned {
+            self.pinned_groups.remove(&hash);
+        }
+        let verb = if pinned { "Pinned" } else { "Unpinned" };
+        log::debug!(
+            "{} group {}",
+            verb,
+            self.current_group_id().unwrap_or_default()
+        );
+        self.refresh_filter();
+    }
+
+    /// Get pinned group hashes as a BTreeSet for deterministic serialization.
+    #[must_use]
+    pub fn pinned_groups_btree(&self) -> std::collections::BTreeSet<[u8; 32]> {
+        self.pinned_groups
+            .iter()
+            .filter(|hash| self.group_position(hash).is_some())
+            .copied()
+            .collect()
+    }
+
+    /// Restore pinned groups from a session.
+    ///
+    /// Hashes that no longer belong to any group are ignored.
+    pub fn apply_pinned_groups(&mut self, pinned: std::collections::BTreeSet<[u8; 32]>) {
+        self.pinned_groups = pinned
+            .into_iter()
+            .filter(|hash| self.group_position(hash).is_some())
+            .collect();
+        self.refresh_filter();
+    }
+
+    /// Recompute the policy-protected file of every group and the set of
+    /// protected hardlinks.
+    fn refresh_protected_files(&mut self) {
+        let policy = self.protect_policy;
+        self.protected_files = self
+            .groups
+            .iter()
+            .filter_map(|g| g.protected_file(policy))
+            .map(|f| f.path.clone())
+            .collect();
+        self.hardlink_files = if self.protect_hardlinks {
+            // Every link of a set is protected, including the first one seen
+            let mut links: HashMap<(u64, u64), usize> = HashMap::new();
+            for inode in self
+                .groups
+                .iter()
+                .flat_map(|g| &g.files)
+                .filter_map(|f| f.inode)
+            {
+                *links.entry(inode).or_default() += 1;
+            }
+            self.groups
+                .iter()
+                .flat_map(|g| &g.files)
+                .filter(|f| f.is_hardlink || f.inode.is_some_and(|inode| links[&inode] > 1))
+                .map(|f| f.path.clone())
+                .collect()
+        } else {
+            HashSet::new()
+        };
+    }
+
+    /// Check if a path is in a protected reference directory.
+    ///
+    /// Case is ignored on Windows and with `--ignore-case-paths`.
+    pub fn is_in_reference_dir(&self, path: &std::path::Path) -> bool {
+        self.reference_paths.iter().any(|ref_path| {
+            crate::scanner::path_starts_with(path, ref_path, self.ignore_case_paths)
+        })
+    }
+
+    /// Check if a group is expanded.
+    #[must_use]
+    pub fn is_expanded(&self, group_hash: &[u8; 32]) -> bool {
+        self.expanded_groups.contains(group_hash)
+    }
+
+    /// Check if the currently selected group is expanded.
+    #[must_use]
+    pub fn is_current_group_expanded(&self) -> bool {
+        self.current_group()
+            .map(|g| self.is_expanded(&g.hash))
+            .unwrap_or(false)
+    }
+
+    /// Create an App with pre-loaded duplicate groups.
+    ///
+    /// The app starts in Reviewing mode if groups are provided.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustdupe::tui::app::App;
+    /// use rustdupe::duplicates::DuplicateGroup;
+    /// let app = App::with_groups(vec![]);
+    /// ```
+    #[must_use]
+    pub fn with_groups(groups: Vec<DuplicateGroup>) -> Self {
+        let reclaimable = groups.iter().map(DuplicateGroup::wasted_space).sum();
+        let mode = if groups.is_empty() {
             AppMode::Scanning
         } else {
             AppMode::Reviewing
@@ -867,37 +2132,76 @@ impl App {
         let mut app = Self {
             mode,
             groups,
+            hash_index: HashMap::new(),
+            group_ids: Vec::new(),
+            id_index: HashMap::new(),
             group_index: 0,
             file_index: 0,
             group_scroll: 0,
             file_scroll: 0,
             selected_files: HashSet::new(),
             scan_progress: ScanProgress::new(),
+            shared_scan_progress: None,
             error_message: None,
             preview_content: None,
+            preview_pager: None,
+            preview_offset: 0,
+            diff_scroll: 0,
             folder_list: Vec::new(),
             folder_index: 0,
             group_name_list: Vec::new(),
             group_name_index: 0,
             search_query: String::new(),
+            search_mode: SearchMode::default(),
             input_query: String::new(),
             filtered_indices: None,
             reference_paths: Vec::new(),
+            ignore_case_paths: false,
+            empty_dir_roots: None,
+            permanent_under: Vec::new(),
+            settle_seconds: None,
+            owner_uids: None,
+            exclude_owner_uids: Vec::new(),
+            max_total_bytes: None,
+            trash_dir: None,
+            preserve_xattrs: false,
+            transactional_delete: false,
+            keeper_rename: None,
+            protect_policy: ProtectPolicy::None,
+            protected_files: HashSet::new(),
+            protect_hardlinks: false,
+            hardlink_files: HashSet::new(),
+            keepers: HashSet::new(),
+            keep_marks: HashSet::new(),
+            pinned_groups: HashSet::new(),
             selection_history: Vec::new(),
+            deletion_history: Vec::new(),
             pending_selections: HashSet::new(),
             pending_bulk_action: None,
+            keeper_strategy: KeeperStrategy::default(),
+            pause_flag: None,
             reclaimable_space: reclaimable,
             visible_rows: 20,
+            preview_rows: 20,
             dry_run: false,
             theme_arg: ThemeArg::Auto,
             theme: Theme::dark(),
             keybindings: None,
             expanded_groups: HashSet::new(),
+            rolled_up_groups: HashSet::new(),
+            rollup_on_directory: false,
             sort_column: SortColumn::default(),
             sort_direction: SortDirection::default(),
             accessible: false,
+            size_format: SizeFormat::default(),
+            absolute_times: false,
+            column_preset: ColumnPreset::default(),
             export_selected: false,
+            export_nul_separated: false,
             group_filter: GroupFilter::default(),
+            delete_confirmations: 0,
+            confirm_threshold_count: DEFAULT_CONFIRM_THRESHOLD_COUNT,
+            confirm_threshold_bytes: DEFAULT_CONFIRM_THRESHOLD_BYTES,
         };
 
         if app.has_groups() {
@@ -912,6 +2216,26 @@ impl App {
         app
     }
 
+    /// Set the group sort order, e.g. restored from a saved session.
+    ///
+    /// Re-sorts the groups and resets navigation to the top, so call this
+    /// before [`apply_session`](Self::apply_session) restores the position.
+    #[must_use]
+    pub fn with_sort(mut self, column: SortColumn, direction: SortDirection) -> Self {
+        if (column, direction) != (self.sort_column, self.sort_direction) {
+            self.sort_column = column;
+            self.sort_direction = direction;
+            if self.has_groups() {
+                self.sort_groups();
+                self.group_index = 0;
+                self.file_index = 0;
+                self.group_scroll = 0;
+                self.file_scroll = 0;
+            }
+        }
+        self
+    }
+
     /// Set selections and navigation state from a session.
     pub fn apply_session(
         &mut self,
@@ -983,7 +2307,9 @@ impl App {
     pub fn set_groups(&mut self, groups: Vec<DuplicateGroup>) {
         self.reclaimable_space = groups.iter().map(DuplicateGroup::wasted_space).sum();
         self.groups = groups;
+        self.rebuild_hash_index();
         self.selected_files.clear();
+        self.refresh_protected_files();
 
         if !self.groups.is_empty() {
             self.sort_groups();
@@ -1002,6 +2328,51 @@ impl App {
         );
     }
 
+    /// Look up a group by hash without scanning the list.
+    ///
+    /// Similar groups and split groups can share a hash; the first of them is
+    /// returned. Use [`group_by_id`](Self::group_by_id) to reach the others.
+    #[must_use]
+    pub fn group_by_hash(&self, hash: &[u8; 32]) -> Option<&DuplicateGroup> {
+        self.group_position(hash).map(|index| &self.groups[index])
+    }
+
+    /// Current position of the first group with `hash` in
+    /// [`groups`](Self::groups).
+    #[must_use]
+    pub fn group_position(&self, hash: &[u8; 32]) -> Option<usize> {
+        self.hash_index.get(hash).copied()
+    }
+
+    /// Look up a group by its [group ID](crate::duplicates::group_ids).
+    #[must_use]
+    pub fn group_by_id(&self, id: &str) -> Option<&DuplicateGroup> {
+        self.group_position_by_id(id)
+            .map(|index| &self.groups[index])
+    }
+
+    /// Current position of the group with `id` in [`groups`](Self::groups).
+    #[must_use]
+    pub fn group_position_by_id(&self, id: &str) -> Option<usize> {
+        self.id_index.get(id).copied()
+    }
+
+    /// Re-index the groups by hash and ID after they were replaced,
+    /// reordered or removed.
+    fn rebuild_hash_index(&mut self) {
+        self.hash_index.clear();
+        for (index, group) in self.groups.iter().enumerate() {
+            self.hash_index.entry(group.hash).or_insert(index);
+        }
+        self.group_ids = crate::duplicates::group_ids(&self.groups);
+        self.id_index = self
+            .group_ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (id.clone(), index))
+            .collect();
+    }
+
     /// Get the number of duplicate groups.
     #[must_use]
     pub fn group_count(&self) -> usize {
@@ -1058,11 +2429,21 @@ impl App {
         self.file_scroll
     }
 
+    /// Lines moved by one page of preview scrolling.
+    fn page_lines(&self) -> isize {
+        isize::try_from(self.preview_rows).unwrap_or(isize::MAX)
+    }
+
     /// Set the number of visible rows (for scroll calculation).
     pub fn set_visible_rows(&mut self, rows: usize) {
         self.visible_rows = rows.max(1);
     }
 
+    /// Set the number of text lines the preview dialog shows (for paging).
+    pub fn set_preview_rows(&mut self, rows: usize) {
+        self.preview_rows = rows.max(1);
+    }
+
     /// Get the currently selected group (if any).
     #[must_use]
     pub fn current_group(&self) -> Option<&DuplicateGroup> {
@@ -1093,10 +2474,18 @@ impl App {
         }
 
         match self.mode {
-            AppMode::Reviewing => {
+            AppMode::Reviewing | AppMode::ReviewingPending => {
                 if let Some(group) = self.current_group() {
                     let is_expanded = self.is_expanded(&group.hash);
-                    if is_expanded && self.file_index + 1 < group.files.len() {
+                    if is_expanded && self.is_rolled_up(group) {
+                        let row = self.rollup_index();
+                        if row + 1 < self.rollup_rows().len() {
+                            self.move_rollup_cursor(row + 1);
+                            log::trace!("Navigate next: rollup row = {}", row + 1);
+                        } else {
+                            self.next_group();
+                        }
+                    } else if is_expanded && self.file_index + 1 < group.files.len() {
                         self.file_index += 1;
                         self.update_file_scroll();
                         log::trace!("Navigate next: file_index = {}", self.file_index);
@@ -1132,10 +2521,14 @@ impl App {
         }
 
         match self.mode {
-            AppMode::Reviewing => {
+            AppMode::Reviewing | AppMode::ReviewingPending => {
                 if let Some(group) = self.current_group() {
                     let is_expanded = self.is_expanded(&group.hash);
-                    if is_expanded && self.file_index > 0 {
+                    let row = self.rollup_index();
+                    if is_expanded && self.is_rolled_up(group) && row > 0 {
+                        self.move_rollup_cursor(row - 1);
+                        log::trace!("Navigate previous: rollup row = {}", row - 1);
+                    } else if is_expanded && !self.is_rolled_up(group) && self.file_index > 0 {
                         self.file_index -= 1;
                         self.update_file_scroll();
                         log::trace!("Navigate previous: file_index = {}", self.file_index);
@@ -1147,7 +2540,10 @@ impl App {
                         // If we moved to a new group and it's expanded, go to its last file
                         if self.group_index != old_group_index {
                             if let Some(new_group) = self.current_group() {
-                                if self.is_expanded(&new_group.hash) {
+                                if self.is_rolled_up(new_group) {
+                                    let last_row = self.rollup_rows().len().saturating_sub(1);
+                                    self.move_rollup_cursor(last_row);
+                                } else if self.is_expanded(&new_group.hash) {
                                     self.file_index = new_group.files.len().saturating_sub(1);
                                     self.update_file_scroll();
                                 }
@@ -1188,7 +2584,11 @@ impl App {
             self.group_index += 1;
             self.file_index = 0;
             self.file_scroll = 0;
+            self.rollup_on_directory = false;
             self.update_group_scroll();
+            if self.is_current_group_rolled_up() {
+                self.move_rollup_cursor(0);
+            }
             log::trace!("Navigate next group: group_index = {}", self.group_index);
         }
     }
@@ -1203,7 +2603,11 @@ impl App {
             self.group_index -= 1;
             self.file_index = 0;
             self.file_scroll = 0;
+            self.rollup_on_directory = false;
             self.update_group_scroll();
+            if self.is_current_group_rolled_up() {
+                self.move_rollup_cursor(0);
+            }
             log::trace!(
                 "Navigate previous group: group_index = {}",
                 self.group_index
@@ -1211,15 +2615,47 @@ impl App {
         }
     }
 
+    /// Jump to the group number typed at the `:` prompt.
+    ///
+    /// Numbers count from 1 within the visible (filtered) groups and are
+    /// clamped to the last one. Returns to review mode either way.
+    pub fn jump_to_group(&mut self) {
+        let query = std::mem::take(&mut self.input_query);
+        self.set_mode(AppMode::Reviewing);
+        let count = self.visible_group_count();
+        match query.trim().parse::<usize>() {
+            Ok(number) if count > 0 => {
+                self.group_index = number.clamp(1, count) - 1;
+                self.file_index = 0;
+                self.file_scroll = 0;
+                self.rollup_on_directory = false;
+                self.update_group_scroll();
+                if self.is_current_group_rolled_up() {
+                    self.move_rollup_cursor(0);
+                }
+                log::trace!("Jump to group: group_index = {}", self.group_index);
+            }
+            Ok(_) => {}
+            Err(_) if query.trim().is_empty() => {}
+            Err(_) => self.set_error(&format!("Not a group number: {}", query.trim())),
+        }
+    }
+
     /// Update file scroll to keep current selection visible.
     fn update_file_scroll(&mut self) {
-        // Scroll down if selection is below visible area
-        if self.file_index >= self.file_scroll + self.visible_rows {
-            self.file_scroll = self.file_index - self.visible_rows + 1;
+        // A rolled-up group scrolls by row, directory headers included
+        let cursor = if self.is_current_group_rolled_up() {
+            self.rollup_index()
+        } else {
+            self.file_index
+        };
+        // Scroll down if selection is below visible area
+        if cursor >= self.file_scroll + self.visible_rows {
+            self.file_scroll = cursor - self.visible_rows + 1;
         }
         // Scroll up if selection is above visible area
-        if self.file_index < self.file_scroll {
-            self.file_scroll = self.file_index;
+        if cursor < self.file_scroll {
+            self.file_scroll = cursor;
         }
     }
 
@@ -1257,6 +2693,48 @@ impl App {
         files
     }
 
+    /// Write the selected file paths to `writer`, sorted by path.
+    ///
+    /// Each path is followed by a newline, or by a NUL byte when
+    /// `nul_separated` is set (for `xargs -0`). Paths are written as raw
+    /// OS bytes, so unusual filenames survive the round trip. This only
+    /// reads state, so it is allowed in dry-run mode.
+    ///
+    /// Returns the number of paths written.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `writer`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustdupe::tui::app::App;
+    /// use std::path::PathBuf;
+    ///
+    /// let mut app = App::new();
+    /// app.select(PathBuf::from("/b.txt"));
+    /// app.select(PathBuf::from("/a.txt"));
+    ///
+    /// let mut out = Vec::new();
+    /// assert_eq!(app.export_selection(&mut out, false).unwrap(), 2);
+    /// assert_eq!(out, b"/a.txt\n/b.txt\n");
+    /// ```
+    pub fn export_selection<W: std::io::Write>(
+        &self,
+        mut writer: W,
+        nul_separated: bool,
+    ) -> std::io::Result<usize> {
+        let separator = if nul_separated { b'\0' } else { b'\n' };
+        let files = self.selected_files_vec();
+        for path in &files {
+            writer.write_all(path.as_os_str().as_encoded_bytes())?;
+            writer.write_all(&[separator])?;
+        }
+        writer.flush()?;
+        Ok(files.len())
+    }
+
     /// Get the number of selected files.
     #[must_use]
     pub fn selected_count(&self) -> usize {
@@ -1269,6 +2747,109 @@ impl App {
         !self.selected_files.is_empty()
     }
 
+    /// Number of selected files matched only by filename, not by content.
+    ///
+    /// These files appear in a name-similar group but in no exact duplicate
+    /// group, so deleting them may lose unique content.
+    #[must_use]
+    pub fn selected_name_only_count(&self) -> usize {
+        self.selected_files
+            .iter()
+            .filter(|path| {
+                let in_name_group = self
+                    .groups
+                    .iter()
+                    .any(|g| g.is_name_similar && g.files.iter().any(|f| &f.path == *path));
+                let in_exact_group = self
+                    .groups
+                    .iter()
+                    .any(|g| !g.is_similar && g.files.iter().any(|f| &f.path == *path));
+                in_name_group && !in_exact_group
+            })
+            .count()
+    }
+
+    /// Whether the pending deletion still needs the extra name-similar confirmation.
+    #[must_use]
+    pub fn needs_name_similar_confirmation(&self) -> bool {
+        self.delete_confirmations > 1
+    }
+
+    /// Whether the pending deletion has received all required confirmations.
+    #[must_use]
+    pub fn is_delete_confirmed(&self) -> bool {
+        self.delete_confirmations == 0
+    }
+
+    /// Set the file count and byte thresholds above which deletion must be
+    /// confirmed by typing [`TYPED_CONFIRMATION_WORD`].
+    pub fn with_confirm_thresholds(mut self, count: usize, bytes: u64) -> Self {
+        self.confirm_threshold_count = count;
+        self.confirm_threshold_bytes = bytes;
+        self
+    }
+
+    /// Get the total size of the selected files in bytes.
+    #[must_use]
+    pub fn selected_size(&self) -> u64 {
+        self.selected_files
+            .iter()
+            .filter_map(|path| {
+                self.groups
+                    .iter()
+                    .find(|g| g.files.iter().any(|f| &f.path == path))
+                    .map(|g| g.size)
+            })
+            .sum()
+    }
+
+    /// Compute live review metrics for the current selection.
+    ///
+    /// Sizes come from each selected file's own entry, and a file listed in
+    /// several groups is only counted once. Selected paths that no longer
+    /// appear in any group (for example after a rescan) still count towards
+    /// the file total but contribute no bytes.
+    #[must_use]
+    pub fn statistics(&self) -> ReviewStats {
+        let mut counted: HashSet<&PathBuf> = HashSet::new();
+        let mut selected_bytes = 0u64;
+        let mut groups_touched = 0usize;
+
+        for group in &self.groups {
+            let mut touched = false;
+            for file in &group.files {
+                if self.selected_files.contains(&file.path) {
+                    touched = true;
+                    if counted.insert(&file.path) {
+                        selected_bytes += file.size;
+                    }
+                }
+            }
+            if touched {
+                groups_touched += 1;
+            }
+        }
+
+        ReviewStats {
+            selected_files: self.selected_files.len(),
+            selected_bytes,
+            groups_touched,
+            total_groups: self.groups.len(),
+            size_format: self.size_format,
+        }
+    }
+
+    /// Whether deleting the current selection requires typing
+    /// [`TYPED_CONFIRMATION_WORD`] instead of a single keypress.
+    ///
+    /// True when more files than the count threshold, or more bytes than the
+    /// byte threshold, are selected.
+    #[must_use]
+    pub fn requires_typed_confirmation(&self) -> bool {
+        self.selected_count() > self.confirm_threshold_count
+            || self.selected_size() > self.confirm_threshold_bytes
+    }
+
     /// Check if a specific file is selected.
     #[must_use]
     pub fn is_file_selected(&self, path: &PathBuf) -> bool {
@@ -1292,6 +2873,22 @@ impl App {
                 self.set_error("Cannot select file in protected reference directory");
                 return;
             }
+            if self.is_policy_protected(&path) {
+                self.set_error("Cannot select file protected by the --protect policy");
+                return;
+            }
+            if self.is_hardlink_protected(&path) {
+                self.set_error("Cannot select hardlink: deleting it frees no space");
+                return;
+            }
+            if self.is_keeper(&path) {
+                self.set_error("Cannot select the designated keeper");
+                return;
+            }
+            if self.is_keep_marked(&path) {
+                self.set_error("Cannot select a file marked to keep");
+                return;
+            }
 
             if self.selected_files.contains(&path) {
                 self.selected_files.remove(&path);
@@ -1305,8 +2902,15 @@ impl App {
 
     /// Select a specific file.
     ///
+    /// Files marked to keep are refused, as in
+    /// [`toggle_select`](Self::toggle_select).
+    ///
     /// Note: This bypasses the reference directory check.
     pub fn select(&mut self, path: PathBuf) {
+        if self.is_keep_marked(&path) {
+            self.set_error("Cannot select a file marked to keep");
+            return;
+        }
         self.selected_files.insert(path);
     }
 
@@ -1315,20 +2919,22 @@ impl App {
         self.selected_files.remove(path);
     }
 
-    /// Select all files in the current group except the first one.
+    /// Select all files in the current group except the kept one.
     ///
-    /// The first file is preserved as the "original" that should be kept.
-    /// Files in protected reference directories are skipped.
+    /// The designated keeper (or the first file if none was marked) is
+    /// preserved as the "original" that should be kept. Files in protected
+    /// reference directories are skipped.
     pub fn select_all_in_group(&mut self) {
         self.push_selection_history();
         // Clone files to avoid borrow conflict
         let files_to_select: Vec<PathBuf> = self
             .current_group()
             .map(|g| {
+                let kept = self.kept_file(g);
                 g.files
                     .iter()
-                    .skip(1)
-                    .filter(|f| !self.is_in_reference_dir(&f.path))
+                    .filter(|f| Some(&f.path) != kept.as_ref())
+                    .filter(|f| !self.is_protected(&f.path))
                     .map(|f| f.path.clone())
                     .collect()
             })
@@ -1341,133 +2947,217 @@ impl App {
 
         if count > 0 {
             log::debug!(
-                "Selected {} files in group (keeping first and skipping references)",
+                "Selected {} files in group (keeping keeper and skipping references)",
                 count
             );
         }
     }
 
-    /// Select all duplicates across ALL groups (keeping first in each).
-    pub fn select_all_duplicates(&mut self) {
-        let mut pending = HashSet::new();
+    /// Files a bulk selection under `strategy` would newly mark, per group.
+    ///
+    /// Every bulk action goes through here, so the same guards apply to all
+    /// of them: the strategy's keeper is never marked (under
+    /// [`KeeperStrategy::First`] a designated keeper takes the first file's
+    /// place), protected and already-selected files are skipped, and a group
+    /// is left alone if marking would leave none of its files unselected.
+    fn strategy_selections(&self, strategy: &KeeperStrategy) -> Vec<([u8; 32], Vec<PathBuf>)> {
+        let mut selections = Vec::new();
         for group in &self.groups {
-            for file in group.files.iter().skip(1) {
-                if !self.is_in_reference_dir(&file.path)
-                    && !self.selected_files.contains(&file.path)
-                {
-                    pending.insert(file.path.clone());
-                }
+            let kept = match strategy {
+                KeeperStrategy::First => self.kept_file(group),
+                _ => group
+                    .files
+                    .get(strategy.choose_keeper(&group.files))
+                    .map(|f| f.path.clone()),
+            };
+            let marked: Vec<PathBuf> = group
+                .files
+                .iter()
+                .filter(|f| Some(&f.path) != kept.as_ref())
+                .filter(|f| !self.is_protected(&f.path) && !self.selected_files.contains(&f.path))
+                .map(|f| f.path.clone())
+                .collect();
+            let keeps_one = group
+                .files
+                .iter()
+                .any(|f| !self.selected_files.contains(&f.path) && !marked.contains(&f.path));
+            if !marked.is_empty() && keeps_one {
+                selections.push((group.hash, marked));
             }
         }
+        selections
+    }
 
+    /// Stage a bulk selection under `strategy` for confirmation.
+    ///
+    /// Returns false (leaving the mode unchanged) if nothing would be marked.
+    fn prepare_strategy_selection(
+        &mut self,
+        strategy: &KeeperStrategy,
+        kind: BulkSelectionType,
+    ) -> bool {
+        let pending: HashSet<PathBuf> = self
+            .strategy_selections(strategy)
+            .into_iter()
+            .flat_map(|(_, paths)| paths)
+            .collect();
         if pending.is_empty() {
-            log::debug!("No new duplicates to select");
-            return;
+            log::debug!("No new files to select for {:?}", kind);
+            return false;
         }
 
         self.pending_selections = pending;
-        self.pending_bulk_action = Some(BulkSelectionType::AllDuplicates);
+        self.pending_bulk_action = Some(kind);
         self.set_mode(AppMode::ConfirmingBulkSelection);
+        true
+    }
+
+    /// Select all duplicates across ALL groups (keeping the keeper in each).
+    pub fn select_all_duplicates(&mut self) {
+        self.prepare_strategy_selection(&KeeperStrategy::First, BulkSelectionType::AllDuplicates);
     }
 
     /// Select the oldest file in each group (keeping the newest).
     pub fn select_oldest(&mut self) {
-        let mut pending = HashSet::new();
-        for group in &self.groups {
-            // Find the newest file to keep
-            if let Some(newest) = group.files.iter().max_by_key(|f| f.modified) {
-                for file in &group.files {
-                    if file.path != newest.path
-                        && !self.is_in_reference_dir(&file.path)
-                        && !self.selected_files.contains(&file.path)
-                    {
-                        pending.insert(file.path.clone());
-                    }
-                }
-            }
-        }
-
-        if pending.is_empty() {
-            log::debug!("No new oldest files to select");
-            return;
-        }
-
-        self.pending_selections = pending;
-        self.pending_bulk_action = Some(BulkSelectionType::Oldest);
-        self.set_mode(AppMode::ConfirmingBulkSelection);
+        self.prepare_strategy_selection(&KeeperStrategy::Newest, BulkSelectionType::Oldest);
     }
 
     /// Select the newest file in each group (keeping the oldest).
     pub fn select_newest(&mut self) {
-        let mut pending = HashSet::new();
-        for group in &self.groups {
-            // Find the oldest file to keep
-            if let Some(oldest) = group.files.iter().min_by_key(|f| f.modified) {
-                for file in &group.files {
-                    if file.path != oldest.path
-                        && !self.is_in_reference_dir(&file.path)
-                        && !self.selected_files.contains(&file.path)
-                    {
-                        pending.insert(file.path.clone());
-                    }
-                }
-            }
-        }
+        self.prepare_strategy_selection(&KeeperStrategy::Oldest, BulkSelectionType::Newest);
+    }
 
-        if pending.is_empty() {
-            log::debug!("No new newest files to select");
-            return;
+    /// Keeper strategy used by [`select_by_strategy`](Self::select_by_strategy).
+    #[must_use]
+    pub fn keeper_strategy(&self) -> &KeeperStrategy {
+        &self.keeper_strategy
+    }
+
+    /// Select all but one file in each group, choosing the keeper by strategy.
+    ///
+    /// Starts with the strategy used last time. While its confirmation is
+    /// open, calling this again switches to the next strategy and recomputes
+    /// the selection; the preferred-directory strategy uses the directory of
+    /// the file highlighted when the selection was started.
+    pub fn select_by_strategy(&mut self) {
+        if self.mode == AppMode::ConfirmingBulkSelection
+            && self.pending_bulk_action == Some(BulkSelectionType::ByStrategy)
+        {
+            self.keeper_strategy = self.keeper_strategy.next(self.strategy_dir().as_deref());
+        } else if let KeeperStrategy::InPreferredDir(_) = self.keeper_strategy {
+            // Follow the highlighted file rather than an old directory
+            self.keeper_strategy = self
+                .strategy_dir()
+                .map_or(KeeperStrategy::First, KeeperStrategy::InPreferredDir);
         }
 
-        self.pending_selections = pending;
-        self.pending_bulk_action = Some(BulkSelectionType::Newest);
-        self.set_mode(AppMode::ConfirmingBulkSelection);
+        let strategy = self.keeper_strategy.clone();
+        if !self.prepare_strategy_selection(&strategy, BulkSelectionType::ByStrategy)
+            && self.mode == AppMode::ConfirmingBulkSelection
+        {
+            // Keep the dialog open so the next strategy can still be tried
+            self.pending_selections.clear();
+        }
+        log::debug!("Keeper strategy: {}", strategy.display_name());
     }
 
-    /// Select all but the first file in each group (same size, so "smallest" is arbitrary).
-    pub fn select_smallest(&mut self) {
-        let mut pending = HashSet::new();
-        for group in &self.groups {
-            for file in group.files.iter().skip(1) {
-                if !self.is_in_reference_dir(&file.path)
-                    && !self.selected_files.contains(&file.path)
-                {
-                    pending.insert(file.path.clone());
-                }
-            }
-        }
+    /// Directory of the highlighted file, offered as the preferred directory.
+    fn strategy_dir(&self) -> Option<PathBuf> {
+        self.current_file()
+            .and_then(|p| p.parent())
+            .map(Path::to_path_buf)
+    }
 
-        if pending.is_empty() {
-            log::debug!("No new duplicates to select (smallest)");
+    /// Pre-mark all but the newest file in each group and enter pending review.
+    ///
+    /// Unlike [`select_oldest`](Self::select_oldest), marked files can be
+    /// stepped through and un-marked individually before the selection is
+    /// applied. Files in reference directories are never marked.
+    pub fn review_keep_newest(&mut self) {
+        let selections = self.strategy_selections(&KeeperStrategy::Newest);
+        if selections.is_empty() {
+            log::debug!("No files to review for keep-newest");
             return;
         }
 
+        // Expand affected groups so every marked file can be reached
+        let mut pending = HashSet::new();
+        for (hash, paths) in selections {
+            self.expanded_groups.insert(hash);
+            pending.extend(paths);
+        }
         self.pending_selections = pending;
-        self.pending_bulk_action = Some(BulkSelectionType::Smallest);
-        self.set_mode(AppMode::ConfirmingBulkSelection);
+        self.pending_bulk_action = Some(BulkSelectionType::ReviewKeepNewest);
+        self.file_index = 0;
+        self.file_scroll = 0;
+        self.set_mode(AppMode::ReviewingPending);
     }
 
-    /// Select all but the first file in each group (same size, so "largest" is arbitrary).
-    pub fn select_largest(&mut self) {
-        let mut pending = HashSet::new();
-        for group in &self.groups {
-            for file in group.files.iter().skip(1) {
-                if !self.is_in_reference_dir(&file.path)
-                    && !self.selected_files.contains(&file.path)
-                {
-                    pending.insert(file.path.clone());
-                }
-            }
+    /// Check if a file is marked in the pending bulk selection.
+    #[must_use]
+    pub fn is_pending(&self, path: &PathBuf) -> bool {
+        self.pending_selections.contains(path)
+    }
+
+    /// Toggle the pending mark on the currently highlighted file.
+    ///
+    /// Reference files cannot be marked, and at least one file per group
+    /// must remain unmarked.
+    pub fn toggle_pending(&mut self) {
+        let Some(path) = self.current_file().cloned() else {
+            return;
+        };
+
+        if self.pending_selections.remove(&path) {
+            log::debug!("Un-marked: {}", path.display());
+            return;
         }
 
-        if pending.is_empty() {
-            log::debug!("No new duplicates to select (largest)");
+        if self.is_in_reference_dir(&path) {
+            self.set_error("Cannot select file in protected reference directory");
+            return;
+        }
+        if self.is_policy_protected(&path) {
+            self.set_error("Cannot select file protected by the --protect policy");
+            return;
+        }
+        if self.is_hardlink_protected(&path) {
+            self.set_error("Cannot select hardlink: deleting it frees no space");
+            return;
+        }
+        if self.is_keeper(&path) {
+            self.set_error("Cannot select the designated keeper");
+            return;
+        }
+        if self.is_keep_marked(&path) {
+            self.set_error("Cannot select a file marked to keep");
             return;
         }
 
-        self.pending_selections = pending;
-        self.pending_bulk_action = Some(BulkSelectionType::Largest);
-        self.set_mode(AppMode::ConfirmingBulkSelection);
+        let keeps_another = self.current_group().is_some_and(|g| {
+            g.files.iter().any(|f| {
+                f.path != path
+                    && !self.pending_selections.contains(&f.path)
+                    && !self.selected_files.contains(&f.path)
+            })
+        });
+        if !keeps_another {
+            self.set_error("At least one file in each group must be kept");
+            return;
+        }
+
+        log::debug!("Marked: {}", path.display());
+        self.pending_selections.insert(path);
+    }
+
+    /// Select all but the kept file in each group (same size, so "smallest" is arbitrary).
+    pub fn select_smallest(&mut self) {
+        self.prepare_strategy_selection(&KeeperStrategy::First, BulkSelectionType::Smallest);
+    }
+
+    /// Select all but the kept file in each group (same size, so "largest" is arbitrary).
+    pub fn select_largest(&mut self) {
+        self.prepare_strategy_selection(&KeeperStrategy::First, BulkSelectionType::Largest);
     }
 
     /// Deselect all files.
@@ -1491,8 +3181,26 @@ impl App {
             group.files.retain(|f| !deleted_set.contains(&f.path));
         }
 
+        // Earlier deletions can no longer bring these files back
+        for batch in &mut self.deletion_history {
+            batch.forget(&deleted_set);
+        }
+
+        self.settle_groups();
+
+        log::info!(
+            "Removed {} deleted files, {} groups remaining",
+            deleted.len(),
+            self.groups.len()
+        );
+    }
+
+    /// Drop groups that are no longer duplicates and refresh derived state.
+    fn settle_groups(&mut self) {
         // Remove groups with less than 2 files (no longer duplicates)
         self.groups.retain(|g| g.files.len() >= 2);
+        self.rebuild_hash_index();
+        self.refresh_protected_files();
 
         // Recalculate reclaimable space
         self.reclaimable_space = self.groups.iter().map(DuplicateGroup::wasted_space).sum();
@@ -1508,12 +3216,145 @@ impl App {
         } else {
             self.file_index = 0;
         }
+    }
+
+    // ==================== Deletion Undo ====================
+
+    /// Record a deletion so it can be undone with [`Action::UndoDelete`].
+    ///
+    /// Call before [`remove_deleted_files`](Self::remove_deleted_files) so the
+    /// touched groups are captured intact. Only files moved to the trash
+    /// belong here: permanent deletions cannot be undone. An empty list
+    /// records nothing.
+    pub fn record_deletion(&mut self, trashed: Vec<TrashedFile>) {
+        if trashed.is_empty() {
+            return;
+        }
+        let paths: HashSet<&PathBuf> = trashed.iter().map(|t| &t.path).collect();
+        let groups = self
+            .groups
+            .iter()
+            .enumerate()
+            .filter(|(_, g)| g.files.iter().any(|f| paths.contains(&f.path)))
+            .map(|(index, g)| (index, g.clone()))
+            .collect();
+        self.deletion_history
+            .push(DeletionBatch { trashed, groups });
+    }
+
+    /// Check if there is a deletion that can be undone.
+    #[must_use]
+    pub fn can_undo_deletion(&self) -> bool {
+        !self.deletion_history.is_empty()
+    }
+
+    /// Undo the most recent deletion and put its files back into their groups.
+    ///
+    /// `restore` is called with each trashed file and must move it back out
+    /// of wherever it went (see [`DeleteConfig::restore`]). Files it fails to
+    /// restore stay deleted.
+    ///
+    /// Returns `None` if there is nothing to undo.
+    pub fn undo_deletion<F>(&mut self, mut restore: F) -> Option<UndoOutcome>
+    where
+        F: FnMut(&TrashedFile) -> Result<(), String>,
+    {
+        let batch = self.deletion_history.pop()?;
+
+        let mut outcome = UndoOutcome::default();
+        for file in &batch.trashed {
+            match restore(file) {
+                Ok(()) => outcome.restored.push(file.path.clone()),
+                Err(e) => {
+                    log::warn!("Could not restore {}: {}", file.path.display(), e);
+                    outcome.failures.push((file.path.clone(), e));
+                }
+            }
+        }
+
+        self.reinsert_restored_files(batch, &outcome.restored);
+
+        // Files left in the trash are gone for older deletions as well
+        let lost: HashSet<&PathBuf> = outcome.failures.iter().map(|(p, _)| p).collect();
+        for batch in &mut self.deletion_history {
+            batch.forget(&lost);
+        }
 
         log::info!(
-            "Removed {} deleted files, {} groups remaining",
-            deleted.len(),
-            self.groups.len()
+            "Undid deletion: {} restored, {} failed",
+            outcome.restored.len(),
+            outcome.failures.len()
         );
+        Some(outcome)
+    }
+
+    /// Put restored files back into the groups they were deleted from.
+    ///
+    /// Reverses [`remove_deleted_files`](Self::remove_deleted_files): each
+    /// touched group replaces what is left of it, or is re-inserted at its old
+    /// position if it had dropped below two files. Files still in the trash
+    /// are left out.
+    fn reinsert_restored_files(&mut self, batch: DeletionBatch, restored: &[PathBuf]) {
+        let restored: HashSet<&PathBuf> = restored.iter().collect();
+        let trashed: HashSet<&PathBuf> = batch.trashed.iter().map(|t| &t.path).collect();
+
+        for (index, mut group) in batch.groups {
+            group
+                .files
+                .retain(|f| !trashed.contains(&f.path) || restored.contains(&f.path));
+
+            let remainder = self.groups.iter().position(|g| {
+                g.hash == group.hash
+                    && g.size == group.size
+                    && g.files
+                        .iter()
+                        .any(|f| group.files.iter().any(|s| s.path == f.path))
+            });
+            match remainder {
+                Some(current) => self.groups[current] = group,
+                None => {
+                    let index = index.min(self.groups.len());
+                    self.groups.insert(index, group);
+                }
+            }
+        }
+
+        self.settle_groups();
+    }
+
+    /// Update file paths after keepers were renamed on disk.
+    ///
+    /// Each `(from, to)` pair replaces `from` in every group and in the
+    /// current selection.
+    pub fn apply_renames(&mut self, renamed: &[(PathBuf, PathBuf)]) {
+        for (from, to) in renamed {
+            for group in &mut self.groups {
+                for file in group.files.iter_mut().filter(|f| &f.path == from) {
+                    file.path = to.clone();
+                }
+            }
+            if self.selected_files.remove(from) {
+                self.selected_files.insert(to.clone());
+            }
+            if self.keepers.remove(from) {
+                self.keepers.insert(to.clone());
+            }
+            if self.keep_marks.remove(from) {
+                self.keep_marks.insert(to.clone());
+            }
+            if self.rolled_up_groups.remove(from) {
+                self.rolled_up_groups.insert(to.clone());
+            }
+            // Keep undo snapshots in step so an undo restores the new name
+            for batch in &mut self.deletion_history {
+                for (_, group) in &mut batch.groups {
+                    for file in group.files.iter_mut().filter(|f| &f.path == from) {
+                        file.path = to.clone();
+                    }
+                }
+            }
+        }
+        self.refresh_protected_files();
     }
 
     // ==================== Scan Progress ====================
@@ -1525,7 +3366,14 @@ impl App {
     }
 
     /// Update the scan progress.
+    ///
+    /// Entering a new phase resets the byte counters and the ETA clock.
     pub fn update_scan_progress(&mut self, phase: &str, current: usize, total: usize, path: &str) {
+        if self.scan_progress.phase != phase {
+            self.scan_progress.bytes_done = 0;
+            self.scan_progress.bytes_total = 0;
+            self.scan_progress.started_at = Instant::now();
+        }
         self.scan_progress.phase = phase.to_string();
         self.scan_progress.current = current;
         self.scan_progress.total = total;
@@ -1537,6 +3385,31 @@ impl App {
         self.scan_progress.message = message.to_string();
     }
 
+    /// Copy the latest progress from a running scan, if one is attached.
+    pub fn sync_scan_progress(&mut self) {
+        if let Some(ref shared) = self.shared_scan_progress {
+            if let Ok(progress) = shared.lock() {
+                self.scan_progress = progress.clone();
+            }
+        }
+    }
+
+    // ==================== Clipboard ====================
+
+    /// Paths copied by a clipboard action: the current file for
+    /// [`Action::CopyPath`], every file of the current group for
+    /// [`Action::CopyGroupPaths`].
+    #[must_use]
+    pub fn clipboard_paths(&self, action: Action) -> Option<Vec<PathBuf>> {
+        match action {
+            Action::CopyPath => self.current_file().map(|path| vec![path.clone()]),
+            Action::CopyGroupPaths => self
+                .current_group()
+                .map(|group| group.files.iter().map(|f| f.path.clone()).collect()),
+            _ => None,
+        }
+    }
+
     // ==================== Error Handling ====================
 
     /// Get the current error message (if any).
@@ -1599,13 +3472,17 @@ impl App {
     // ==================== Sorting ====================
 
     /// Sort the duplicate groups based on current sort settings.
+    ///
+    /// Exact groups always come first, then similar, partial, name-cluster
+    /// and empty-file groups, each sorted on its own. Within a section,
+    /// groups tagged as likely intentional follow the others.
     pub fn sort_groups(&mut self) {
         if self.groups.is_empty() {
             return;
         }
 
         // Store current selection if possible to restore position
-        let current_hash = self.current_group().map(|g| g.hash);
+        let current_id = self.current_group_id().map(str::to_string);
 
         match self.sort_column {
             SortColumn::Size => match self.sort_direction {
@@ -1657,43 +3534,18 @@ impl App {
                     .sort_by(|a, b| a.files.len().cmp(&b.files.len())),
             },
         }
+        // Special groups keep their own sections and likely-intentional
+        // copies go last in theirs, whatever the order
+        self.groups
+            .sort_by_key(|g| (g.match_kind().section(), g.likely_intentional));
+        self.rebuild_hash_index();
 
         // If search is active, we MUST re-apply it because the original indices have changed
-        if self.search_query.is_empty() {
-            self.filtered_indices = None;
-        } else {
-            // Re-apply search logic without resetting navigation (yet)
-            let query = self.search_query.to_lowercase();
-            let re = regex::RegexBuilder::new(&self.search_query)
-                .case_insensitive(true)
-                .build()
-                .ok();
-
-            let indices: Vec<usize> = self
-                .groups
-                .iter()
-                .enumerate()
-                .filter(|(_, group)| {
-                    group.files.iter().any(|file| {
-                        let path_str = file.path.to_string_lossy();
-                        let group_name = file.group_name.as_deref().unwrap_or("");
-                        if let Some(ref r) = re {
-                            if r.is_match(&path_str) || r.is_match(group_name) {
-                                return true;
-                            }
-                        }
-                        path_str.to_lowercase().contains(&query)
-                            || group_name.to_lowercase().contains(&query)
-                    })
-                })
-                .map(|(i, _)| i)
-                .collect();
-            self.filtered_indices = Some(indices);
-        }
+        self.filtered_indices = self.filter_indices();
 
         // Restore position or reset
-        if let Some(hash) = current_hash {
-            if let Some(new_idx) = self.groups.iter().position(|g| g.hash == hash) {
+        if let Some(id) = current_id {
+            if let Some(new_idx) = self.group_position_by_id(&id) {
                 if let Some(ref filtered) = self.filtered_indices {
                     if let Some(filtered_idx) =
                         filtered.iter().position(|&orig_idx| orig_idx == new_idx)
@@ -1795,7 +3647,7 @@ impl App {
                 if skip_one && i == 0 {
                     continue;
                 }
-                if !self.is_in_reference_dir(&file.path) {
+                if !self.is_protected(&file.path) {
                     pending.insert(file.path.clone());
                 }
             }
@@ -1842,7 +3694,7 @@ impl App {
                 if skip_one && i == 0 {
                     continue;
                 }
-                if !self.is_in_reference_dir(&file.path) {
+                if !self.is_protected(&file.path) {
                     pending.insert(file.path.clone());
                 }
             }
@@ -1902,6 +3754,115 @@ impl App {
     /// Clear the preview content.
     pub fn clear_preview(&mut self) {
         self.preview_content = None;
+        self.preview_pager = None;
+        self.preview_offset = 0;
+        self.diff_scroll = 0;
+    }
+
+    /// Show a text file through `pager`, starting at its first line.
+    pub fn set_text_preview(&mut self, pager: TextPager) {
+        self.preview_pager = Some(pager);
+        self.preview_offset = 0;
+        self.load_preview_window();
+    }
+
+    /// Get the first visible line of a paged text preview.
+    #[must_use]
+    pub fn preview_offset(&self) -> usize {
+        self.preview_offset
+    }
+
+    /// Whether the preview is a paged text file.
+    #[must_use]
+    pub fn is_preview_paged(&self) -> bool {
+        self.preview_pager.is_some()
+    }
+
+    /// Scroll a paged text preview by `delta` lines.
+    ///
+    /// Stops at the first line and at the last line of the file. Moving down
+    /// reads only as far into the file as needed to find that line.
+    pub fn scroll_preview(&mut self, delta: isize) {
+        let Some(pager) = self.preview_pager.as_mut() else {
+            return;
+        };
+        let mut target = self.preview_offset.saturating_add_signed(delta);
+        match pager.has_line(target) {
+            Ok(true) => {}
+            Ok(false) => {
+                // Past the end: the file is now fully indexed
+                target = pager.line_count().unwrap_or(0).saturating_sub(1);
+            }
+            Err(e) => {
+                self.preview_content = Some(format!("Preview error: {}", e));
+                return;
+            }
+        }
+        if target != self.preview_offset {
+            self.preview_offset = target;
+            self.load_preview_window();
+        }
+    }
+
+    /// Load the visible lines of a paged text preview into the preview content.
+    fn load_preview_window(&mut self) {
+        let Some(pager) = self.preview_pager.as_mut() else {
+            return;
+        };
+        self.preview_content = Some(
+            match pager.read_lines(self.preview_offset, PREVIEW_WINDOW_LINES) {
+                Ok(lines) if lines.is_empty() => "(empty file)".to_string(),
+                Ok(lines) => lines.join("\n"),
+                Err(e) => format!("Preview error: {}", e),
+            },
+        );
+    }
+
+    /// Get the file pair to diff: the group's reference file and the current file.
+    ///
+    /// The reference is the first file in a reference directory, falling back
+    /// to the policy-protected file and then the group's first file. When the
+    /// current file is itself the reference, it is compared with the next file
+    /// in the group instead. Returns `(reference, current)`.
+    #[must_use]
+    pub fn diff_target(&self) -> Option<(PathBuf, PathBuf)> {
+        let group = self.current_group()?;
+        let current = self.current_file()?;
+        let reference = group
+            .files
+            .iter()
+            .find(|f| self.is_in_reference_dir(&f.path))
+            .or_else(|| {
+                group
+                    .files
+                    .iter()
+                    .find(|f| self.is_policy_protected(&f.path))
+            })
+            .or_else(|| group.files.first())?;
+
+        if &reference.path != current {
+            return Some((reference.path.clone(), current.clone()));
+        }
+        group
+            .files
+            .iter()
+            .find(|f| &f.path != current)
+            .map(|other| (current.clone(), other.path.clone()))
+    }
+
+    /// Get the first visible line of the diff pane.
+    #[must_use]
+    pub fn diff_scroll(&self) -> usize {
+        self.diff_scroll
+    }
+
+    /// Scroll the diff pane by `delta` lines, clamped to the diff length.
+    pub fn scroll_diff(&mut self, delta: isize) {
+        let max = self
+            .preview_content
+            .as_deref()
+            .map_or(0, |c| c.lines().count().saturating_sub(1));
+        self.diff_scroll = self.diff_scroll.saturating_add_signed(delta).min(max);
     }
 
     // ==================== Search Management ====================
@@ -1918,6 +3879,24 @@ impl App {
         self.apply_search();
     }
 
+    /// Get the active search mode.
+    #[must_use]
+    pub fn search_mode(&self) -> SearchMode {
+        self.search_mode
+    }
+
+    /// Set the search mode and re-apply the current query.
+    pub fn set_search_mode(&mut self, mode: SearchMode) {
+        self.search_mode = mode;
+        self.apply_search();
+    }
+
+    /// Cycle to the next search mode and re-apply the current query.
+    pub fn cycle_search_mode(&mut self) {
+        self.set_search_mode(self.search_mode.next());
+        log::debug!("Search mode cycled to {:?}", self.search_mode);
+    }
+
     /// Check if a search is active.
     #[must_use]
     pub fn is_searching(&self) -> bool {
@@ -1934,71 +3913,54 @@ impl App {
         self.file_scroll = 0;
     }
 
-    /// Apply the current search query to the groups.
-    fn apply_search(&mut self) {
+    /// Indices of the groups the current search and group filter show, or
+    /// `None` if neither is active. Pinned groups are always included.
+    fn filter_indices(&self) -> Option<Vec<usize>> {
         let has_search = !self.search_query.is_empty();
-        let has_filter = self.group_filter != GroupFilter::All;
-
-        if !has_search && !has_filter {
-            self.filtered_indices = None;
-        } else {
-            let query = self.search_query.to_lowercase();
-
-            // Try to compile as regex if it looks like one, or just use substring
-            // We treat it as regex if it contains special chars or if it compiles successfully
-            let re = if has_search {
-                regex::RegexBuilder::new(&self.search_query)
-                    .case_insensitive(true)
-                    .build()
-                    .ok()
-            } else {
-                None
-            };
-
-            let indices: Vec<usize> = self
-                .groups
-                .iter()
-                .enumerate()
-                .filter(|(_, group)| {
-                    // Respect group filter
-                    match self.group_filter {
-                        GroupFilter::All => {}
-                        GroupFilter::ExactOnly => {
-                            if group.is_similar {
-                                return false;
-                            }
-                        }
-                        GroupFilter::SimilarOnly => {
-                            if !group.is_similar {
-                                return false;
-                            }
-                        }
-                    }
-
-                    if !has_search {
-                        return true;
-                    }
-
-                    // Match by filename, path, or group name
-                    group.files.iter().any(|file| {
-                        let path_str = file.path.to_string_lossy();
-                        let group_name = file.group_name.as_deref().unwrap_or("");
-
-                        if let Some(ref r) = re {
-                            if r.is_match(&path_str) || r.is_match(group_name) {
-                                return true;
-                            }
-                        }
-
-                        // Fallback to substring matching if regex doesn't match or wasn't used
-                        path_str.to_lowercase().contains(&query)
-                            || group_name.to_lowercase().contains(&query)
-                    })
-                })
-                .map(|(i, _)| i)
-                .collect();
-            self.filtered_indices = Some(indices);
+        if !has_search && self.group_filter == GroupFilter::All {
+            return None;
+        }
+        let matcher = SearchMatcher::new(&self.search_query, self.search_mode);
+        let indices = self
+            .groups
+            .iter()
+            .enumerate()
+            .filter(|(_, group)| {
+                // Respect group filter, then match by filename, path, or group name
+                self.pinned_groups.contains(&group.hash)
+                    || (self.group_filter.matches(group)
+                        && (!has_search || matcher.matches_group(group)))
+            })
+            .map(|(i, _)| i)
+            .collect();
+        Some(indices)
+    }
+
+    /// Recompute the filtered groups, keeping the current group selected if
+    /// it is still visible.
+    fn refresh_filter(&mut self) {
+        let current = self.current_group().map(|g| g.hash);
+        self.filtered_indices = self.filter_indices();
+        let position = current.and_then(|hash| {
+            (0..self.visible_group_count())
+                .position(|i| self.visible_group_at(i).is_some_and(|g| g.hash == hash))
+        });
+        match position {
+            Some(index) => self.group_index = index,
+            None => {
+                self.group_index = self
+                    .group_index
+                    .min(self.visible_group_count().saturating_sub(1));
+                self.file_index = 0;
+                self.file_scroll = 0;
+            }
         }
+        self.update_group_scroll();
+    }
+
+    /// Apply the current search query to the groups.
+    fn apply_search(&mut self) {
+        self.filtered_indices = self.filter_indices();
 
         // Reset navigation to the first match
         self.group_index = 0;
@@ -2033,8 +3995,11 @@ impl App {
         } else {
             match self.group_filter {
                 GroupFilter::All => self.groups.len(),
-                GroupFilter::ExactOnly => self.groups.iter().filter(|g| !g.is_similar).count(),
-                GroupFilter::SimilarOnly => self.groups.iter().filter(|g| g.is_similar).count(),
+                filter => self
+                    .groups
+                    .iter()
+                    .filter(|g| self.is_pinned(&g.hash) || filter.matches(g))
+                    .count(),
             }
         }
     }
@@ -2042,14 +4007,38 @@ impl App {
     /// Get a visible group by its relative index.
     #[must_use]
     pub fn visible_group_at(&self, index: usize) -> Option<&DuplicateGroup> {
+        self.groups.get(self.visible_group_position(index)?)
+    }
+
+    /// Get the [group ID](crate::duplicates::group_ids) of a visible group
+    /// by its relative index.
+    #[must_use]
+    pub fn visible_group_id_at(&self, index: usize) -> Option<&str> {
+        self.group_ids
+            .get(self.visible_group_position(index)?)
+            .map(String::as_str)
+    }
+
+    /// Get the group ID of the currently selected group.
+    #[must_use]
+    pub fn current_group_id(&self) -> Option<&str> {
+        self.visible_group_id_at(self.group_index)
+    }
+
+    /// Position in [`groups`](Self::groups) of the visible group at `index`.
+    fn visible_group_position(&self, index: usize) -> Option<usize> {
         if let Some(ref indices) = self.filtered_indices {
-            let actual_idx = *indices.get(index)?;
-            self.groups.get(actual_idx)
+            indices.get(index).copied()
         } else {
             match self.group_filter {
-                GroupFilter::All => self.groups.get(index),
-                GroupFilter::ExactOnly => self.groups.iter().filter(|g| !g.is_similar).nth(index),
-                GroupFilter::SimilarOnly => self.groups.iter().filter(|g| g.is_similar).nth(index),
+                GroupFilter::All => (index < self.groups.len()).then_some(index),
+                filter => self
+                    .groups
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, g)| self.is_pinned(&g.hash) || filter.matches(g))
+                    .nth(index)
+                    .map(|(position, _)| position),
             }
         }
     }
@@ -2071,14 +4060,7 @@ impl App {
     /// Enter folder selection mode for the current group.
     pub fn enter_folder_selection(&mut self) {
         if let Some(group) = self.current_group() {
-            let mut folders: Vec<PathBuf> = group
-                .files
-                .iter()
-                .filter_map(|f| f.path.parent().map(|p| p.to_path_buf()))
-                .collect();
-            folders.sort();
-            folders.dedup();
-            self.folder_list = folders;
+            self.folder_list = group_folders(group);
             self.folder_index = 0;
             self.set_mode(AppMode::SelectingFolder);
         }
@@ -2111,7 +4093,7 @@ impl App {
             group
                 .files
                 .iter()
-                .filter(|f| f.path.starts_with(&folder) && !self.is_in_reference_dir(&f.path))
+                .filter(|f| f.path.starts_with(&folder) && !self.is_protected(&f.path))
                 .map(|f| f.path.clone())
                 .collect()
         } else {
@@ -2133,6 +4115,165 @@ impl App {
         self.set_mode(AppMode::Reviewing);
     }
 
+    // ==================== Directory Rollup ====================
+
+    /// Check if a group is shown as a directory rollup.
+    #[must_use]
+    pub fn is_rolled_up(&self, group: &DuplicateGroup) -> bool {
+        group
+            .files
+            .iter()
+            .any(|f| self.rolled_up_groups.contains(&f.path))
+    }
+
+    /// Check if the currently selected group is shown as a directory rollup.
+    #[must_use]
+    pub fn is_current_group_rolled_up(&self) -> bool {
+        self.current_group().is_some_and(|g| self.is_rolled_up(g))
+    }
+
+    /// Toggle the current group between a flat file list and a directory
+    /// rollup. Rolling a group up also expands it.
+    pub fn toggle_directory_rollup(&mut self) {
+        let Some((hash, members)) = self.current_group().map(|g| (g.hash, g.paths())) else {
+            return;
+        };
+        self.rollup_on_directory = false;
+        if self.is_current_group_rolled_up() {
+            for member in &members {
+                self.rolled_up_groups.remove(member);
+            }
+            log::debug!("Directory rollup off for group {}", self.group_index);
+        } else {
+            self.rolled_up_groups.extend(members);
+            self.expanded_groups.insert(hash);
+            log::debug!("Directory rollup on for group {}", self.group_index);
+        }
+        self.update_file_scroll();
+    }
+
+    /// Rows of the current group's directory rollup: a header for each
+    /// directory followed by the files directly inside it.
+    ///
+    /// Empty unless the current group is rolled up.
+    #[must_use]
+    pub fn rollup_rows(&self) -> Vec<RollupRow> {
+        let Some(group) = self.current_group() else {
+            return Vec::new();
+        };
+        if !self.is_rolled_up(group) {
+            return Vec::new();
+        }
+        directory_rollup(group)
+            .into_iter()
+            .flat_map(|(path, files)| {
+                std::iter::once(RollupRow::Directory {
+                    path,
+                    count: files.len(),
+                })
+                .chain(files.into_iter().map(RollupRow::File))
+            })
+            .collect()
+    }
+
+    /// Index of the highlighted row in [`rollup_rows`](Self::rollup_rows).
+    #[must_use]
+    pub fn rollup_index(&self) -> usize {
+        let directory = self.current_rollup_directory();
+        self.rollup_rows()
+            .iter()
+            .position(|row| match row {
+                RollupRow::Directory { path, .. } => directory.as_ref() == Some(path),
+                RollupRow::File(i) => directory.is_none() && *i == self.file_index,
+            })
+            .unwrap_or(0)
+    }
+
+    /// Directory whose rollup header is highlighted, if any.
+    #[must_use]
+    pub fn current_rollup_directory(&self) -> Option<PathBuf> {
+        if !self.rollup_on_directory || !self.is_current_group_rolled_up() {
+            return None;
+        }
+        self.current_file()
+            .and_then(|p| p.parent())
+            .map(Path::to_path_buf)
+    }
+
+    /// Move the rollup cursor to `row`, keeping `file_index` on the row's
+    /// file (or the first file of a directory header).
+    fn move_rollup_cursor(&mut self, row: usize) {
+        let rows = self.rollup_rows();
+        match rows.get(row) {
+            Some(RollupRow::Directory { .. }) => {
+                // Every directory header is followed by at least one file
+                if let Some(RollupRow::File(first)) = rows.get(row + 1) {
+                    self.file_index = *first;
+                    self.rollup_on_directory = true;
+                }
+            }
+            Some(RollupRow::File(i)) => {
+                self.file_index = *i;
+                self.rollup_on_directory = false;
+            }
+            None => return,
+        }
+        self.update_file_scroll();
+    }
+
+    /// Select or deselect every file directly inside the highlighted
+    /// rollup directory.
+    ///
+    /// The directory's selectable files are selected unless all of them
+    /// already are, in which case they are deselected. Protected files are
+    /// skipped, and a selection that would leave no file in the group
+    /// unselected is refused.
+    pub fn toggle_directory_selection(&mut self) {
+        let Some(dir) = self.current_rollup_directory() else {
+            return;
+        };
+        let Some((files, keeps_another)) = self.current_group().map(|group| {
+            let files: Vec<PathBuf> = group
+                .files
+                .iter()
+                .filter(|f| f.path.parent() == Some(dir.as_path()) && !self.is_protected(&f.path))
+                .map(|f| f.path.clone())
+                .collect();
+            let keeps_another = group
+                .files
+                .iter()
+                .any(|f| !files.contains(&f.path) && !self.selected_files.contains(&f.path));
+            (files, keeps_another)
+        }) else {
+            return;
+        };
+
+        if files.is_empty() {
+            self.set_error("No selectable files in this directory");
+            return;
+        }
+        let deselect = files.iter().all(|p| self.selected_files.contains(p));
+        if !deselect && !keeps_another {
+            self.set_error("Cannot select all files in group - at least one must be preserved");
+            return;
+        }
+
+        self.push_selection_history();
+        for path in &files {
+            if deselect {
+                self.selected_files.remove(path);
+            } else {
+                self.selected_files.insert(path.clone());
+            }
+        }
+        log::debug!(
+            "{} {} files in directory {}",
+            if deselect { "Deselected" } else { "Selected" },
+            files.len(),
+            dir.display()
+        );
+    }
+
     // ==================== Named Group Selection ====================
 
     /// Get the list of unique group names across all files.
@@ -2201,7 +4342,7 @@ impl App {
                 .files
                 .iter()
                 .filter(|f| {
-                    f.group_name.as_ref() == Some(&group_name) && !self.is_in_reference_dir(&f.path)
+                    f.group_name.as_ref() == Some(&group_name) && !self.is_protected(&f.path)
                 })
                 .map(|f| f.path.clone())
                 .collect()
@@ -2247,7 +4388,7 @@ impl App {
                     if skip_first && i == 0 {
                         continue; // Skip first to preserve at least one
                     }
-                    if !self.is_in_reference_dir(&file.path)
+                    if !self.is_protected(&file.path)
                         && self.selected_files.insert(file.path.clone())
                     {
                         count += 1;
@@ -2272,9 +4413,13 @@ impl App {
         }
 
         match self.mode {
-            AppMode::Reviewing => {
+            AppMode::Reviewing | AppMode::ReviewingPending => {
                 self.file_index = 0;
                 self.file_scroll = 0;
+                self.rollup_on_directory = false;
+                if self.is_current_group_rolled_up() {
+                    self.move_rollup_cursor(0);
+                }
                 log::trace!("Navigate to top: file_index = 0");
             }
             AppMode::SelectingFolder => {
@@ -2296,10 +4441,15 @@ impl App {
         }
 
         match self.mode {
-            AppMode::Reviewing => {
+            AppMode::Reviewing | AppMode::ReviewingPending => {
                 if let Some(group) = self.current_group() {
                     let last_index = group.files.len().saturating_sub(1);
-                    self.file_index = last_index;
+                    if self.is_rolled_up(group) {
+                        let last_row = self.rollup_rows().len().saturating_sub(1);
+                        self.move_rollup_cursor(last_row);
+                    } else {
+                        self.file_index = last_index;
+                    }
                     self.update_file_scroll();
                     log::trace!("Navigate to bottom: file_index = {}", self.file_index);
                 }
@@ -2341,33 +4491,69 @@ impl App {
 
         match action {
             Action::NavigateUp => {
-                self.previous();
+                if self.mode == AppMode::Diffing {
+                    self.scroll_diff(-1);
+                } else if self.mode == AppMode::Previewing {
+                    self.scroll_preview(-1);
+                } else {
+                    self.previous();
+                }
                 true
             }
             Action::NavigateDown => {
-                self.next();
+                if self.mode == AppMode::Diffing {
+                    self.scroll_diff(1);
+                } else if self.mode == AppMode::Previewing {
+                    self.scroll_preview(1);
+                } else {
+                    self.next();
+                }
                 true
             }
             Action::NextGroup => {
-                self.next_group();
+                // Page keys page through a text preview
+                if self.mode == AppMode::Previewing {
+                    self.scroll_preview(self.page_lines());
+                } else {
+                    self.next_group();
+                }
                 true
             }
             Action::PreviousGroup => {
-                self.previous_group();
+                if self.mode == AppMode::Previewing {
+                    self.scroll_preview(-self.page_lines());
+                } else {
+                    self.previous_group();
+                }
                 true
             }
             Action::GoToTop => {
-                self.go_to_top();
+                if self.mode == AppMode::Diffing {
+                    self.diff_scroll = 0;
+                } else if self.mode == AppMode::Previewing {
+                    self.scroll_preview(isize::MIN);
+                } else {
+                    self.go_to_top();
+                }
                 true
             }
             Action::GoToBottom => {
-                self.go_to_bottom();
+                if self.mode == AppMode::Diffing {
+                    self.scroll_diff(isize::MAX);
+                } else if self.mode == AppMode::Previewing {
+                    self.scroll_preview(isize::MAX);
+                } else {
+                    self.go_to_bottom();
+                }
                 true
             }
             Action::ToggleSelect => {
                 if self.mode == AppMode::Exporting {
                     self.toggle_export_selected();
                     true
+                } else if self.mode == AppMode::ReviewingPending {
+                    self.toggle_pending();
+                    true
                 } else if self.mode.is_navigable() {
                     if let Some(group) = self.current_group() {
                         let hash = group.hash;
@@ -2376,6 +4562,13 @@ impl App {
                         if !is_expanded {
                             // Expand collapsed group
                             self.expanded_groups.insert(hash);
+                        } else if self.is_rolled_up(group) {
+                            // Directory headers toggle the whole directory
+                            if self.current_rollup_directory().is_some() {
+                                self.toggle_directory_selection();
+                            } else {
+                                self.toggle_select();
+                            }
                         } else if self.file_index == 0 {
                             // Collapse expanded group if on first file
                             self.expanded_groups.remove(&hash);
@@ -2405,6 +4598,14 @@ impl App {
                 self.select_newest();
                 true
             }
+            Action::ReviewKeepNewest => {
+                if self.mode == AppMode::Reviewing {
+                    self.review_keep_newest();
+                    true
+                } else {
+                    false
+                }
+            }
             Action::SelectSmallest => {
                 self.select_smallest();
                 true
@@ -2413,6 +4614,17 @@ impl App {
                 self.select_largest();
                 true
             }
+            Action::SelectByStrategy => {
+                if self.mode == AppMode::Reviewing
+                    || (self.mode == AppMode::ConfirmingBulkSelection
+                        && self.pending_bulk_action == Some(BulkSelectionType::ByStrategy))
+                {
+                    self.select_by_strategy();
+                    true
+                } else {
+                    false
+                }
+            }
             Action::SelectByExtension => {
                 if self.mode == AppMode::Reviewing {
                     self.input_query.clear();
@@ -2440,6 +4652,15 @@ impl App {
                 self.deselect_all();
                 true
             }
+            Action::PauseScan | Action::ResumeScan => match self.pause_flag {
+                Some(ref flag) if self.mode == AppMode::Scanning => {
+                    let paused = action == Action::PauseScan;
+                    flag.store(paused, Ordering::SeqCst);
+                    log::info!("Scan {}", if paused { "paused" } else { "resumed" });
+                    true
+                }
+                _ => false,
+            },
             Action::Preview => {
                 if self.mode == AppMode::Reviewing && self.current_file().is_some() {
                     self.set_mode(AppMode::Previewing);
@@ -2448,6 +4669,24 @@ impl App {
                     false
                 }
             }
+            Action::RevealInFileManager => {
+                // Read-only, so allowed in dry-run mode; launched by the TUI main loop
+                self.mode == AppMode::Reviewing && self.current_file().is_some()
+            }
+            Action::CopyPath | Action::CopyGroupPaths => {
+                // The clipboard is written by the TUI main loop
+                self.mode == AppMode::Reviewing && self.clipboard_paths(action).is_some()
+            }
+            Action::DiffWithReference => {
+                // The diff itself is computed by the TUI main loop
+                if self.mode == AppMode::Reviewing && self.diff_target().is_some() {
+                    self.clear_preview();
+                    self.set_mode(AppMode::Diffing);
+                    true
+                } else {
+                    false
+                }
+            }
             Action::SelectFolder => {
                 if self.mode == AppMode::Reviewing && self.current_group().is_some() {
                     self.enter_folder_selection();
@@ -2472,6 +4711,15 @@ impl App {
                     false
                 }
             }
+            Action::JumpToGroup => {
+                if self.mode == AppMode::Reviewing && self.visible_group_count() > 0 {
+                    self.input_query.clear();
+                    self.set_mode(AppMode::JumpingToGroup);
+                    true
+                } else {
+                    false
+                }
+            }
             Action::Export => {
                 if self.mode == AppMode::Reviewing {
                     self.set_mode(AppMode::Exporting);
@@ -2480,22 +4728,95 @@ impl App {
                     false
                 }
             }
+            Action::ExportSelection => {
+                // Read-only, so allowed in dry-run mode; the file is written by the TUI main loop
+                if self.mode != AppMode::Reviewing {
+                    return false;
+                }
+                if self.has_selections() {
+                    self.input_query.clear();
+                    self.set_mode(AppMode::InputtingExportPath);
+                } else {
+                    self.set_error("No files selected to export");
+                }
+                true
+            }
             Action::Delete => {
                 if self.dry_run {
                     self.set_error("Cannot delete files in dry-run mode");
                     return true; // Action handled (but blocked)
                 }
                 if self.mode == AppMode::Reviewing && self.has_selections() {
+                    // Files matched only by name need a second confirmation
+                    self.delete_confirmations = if self.selected_name_only_count() > 0 {
+                        2
+                    } else {
+                        1
+                    };
+                    self.input_query.clear();
                     self.set_mode(AppMode::Confirming);
                     true
                 } else {
                     false
                 }
             }
+            Action::UndoDelete => {
+                // Files are restored from the trash by the TUI main loop
+                if self.mode != AppMode::Reviewing {
+                    return false;
+                }
+                if !self.delete_config().can_restore() {
+                    self.set_error(
+                        "Undo is unavailable: this platform's trash cannot restore files",
+                    );
+                } else if !self.can_undo_deletion() {
+                    self.set_error("No deletion to undo");
+                }
+                true
+            }
+            Action::MarkAsKeeper => {
+                if self.mode != AppMode::Reviewing {
+                    return false;
+                }
+                self.mark_as_keeper();
+                true
+            }
+            Action::MarkKeepInverse => {
+                if self.mode != AppMode::Reviewing {
+                    return false;
+                }
+                self.toggle_keep_mark();
+                true
+            }
+            Action::SelectComplementForDeletion => {
+                if self.mode != AppMode::Reviewing {
+                    return false;
+                }
+                self.select_complement();
+                true
+            }
+            Action::TogglePin => {
+                if self.mode != AppMode::Reviewing || self.current_group().is_none() {
+                    return false;
+                }
+                self.toggle_pin();
+                true
+            }
+            Action::ToggleDirectoryRollup => {
+                if self.mode != AppMode::Reviewing || self.current_group().is_none() {
+                    return false;
+                }
+                self.toggle_directory_rollup();
+                true
+            }
             Action::ToggleTheme => {
                 self.toggle_theme();
                 true
             }
+            Action::ToggleColumns => {
+                self.toggle_columns();
+                true
+            }
             Action::ToggleExpand => {
                 if let Some(group) = self.current_group() {
                     let hash = group.hash;
@@ -2539,6 +4860,10 @@ impl App {
                 self.cycle_group_filter();
                 true
             }
+            Action::ToggleSearchMode => {
+                self.cycle_search_mode();
+                true
+            }
             Action::ShowHelp => {
                 if self.mode == AppMode::ShowingHelp {
                     // Toggle off - return to reviewing
@@ -2563,12 +4888,31 @@ impl App {
                 } else if self.mode == AppMode::InputtingDirectory {
                     self.prepare_select_by_directory();
                     true
-                } else if self.mode == AppMode::ConfirmingBulkSelection {
-                    self.apply_bulk_selection();
+                } else if self.mode == AppMode::JumpingToGroup {
+                    self.jump_to_group();
+                    true
+                } else if self.mode == AppMode::ConfirmingBulkSelection
+                    || self.mode == AppMode::ReviewingPending
+                {
+                    self.apply_bulk_selection();
                     true
                 } else if self.mode == AppMode::Searching {
                     self.set_mode(AppMode::Reviewing);
                     true
+                } else if self.mode == AppMode::Confirming {
+                    // Large deletions need the confirmation word typed out
+                    if self.requires_typed_confirmation()
+                        && self.input_query != TYPED_CONFIRMATION_WORD
+                    {
+                        self.set_error(&format!(
+                            "Type {} to confirm this deletion",
+                            TYPED_CONFIRMATION_WORD
+                        ));
+                        return true;
+                    }
+                    // Deletion itself is performed by the TUI main loop
+                    self.delete_confirmations = self.delete_confirmations.saturating_sub(1);
+                    true
                 } else if self.mode == AppMode::Exporting {
                     // Confirmation handling is done by the TUI main loop
                     true
@@ -2589,14 +4933,15 @@ impl App {
             }
             Action::Cancel => {
                 match self.mode {
-                    AppMode::Previewing => {
+                    AppMode::Previewing | AppMode::Diffing => {
                         self.clear_preview();
                         self.set_mode(AppMode::Reviewing);
                     }
                     AppMode::Confirming => {
+                        self.input_query.clear();
                         self.set_mode(AppMode::Reviewing);
                     }
-                    AppMode::ConfirmingBulkSelection => {
+                    AppMode::ConfirmingBulkSelection | AppMode::ReviewingPending => {
                         self.cancel_bulk_selection();
                     }
                     AppMode::SelectingFolder => {
@@ -2605,7 +4950,10 @@ impl App {
                     AppMode::SelectingGroup => {
                         self.set_mode(AppMode::Reviewing);
                     }
-                    AppMode::InputtingExtension | AppMode::InputtingDirectory => {
+                    AppMode::InputtingExtension
+                    | AppMode::InputtingDirectory
+                    | AppMode::InputtingExportPath
+                    | AppMode::JumpingToGroup => {
                         self.clear_input_query();
                         self.set_mode(AppMode::Reviewing);
                     }
@@ -2820,6 +5168,128 @@ mod tests {
         assert_eq!(app.selected_count(), 0);
     }
 
+    #[test]
+    fn test_directory_rollup_groups_files_by_parent() {
+        let groups = vec![make_group(
+            100,
+            vec![
+                "/photos/b.jpg",
+                "/backup/a.jpg",
+                "/photos/a.jpg",
+                "/old/a.jpg",
+                "/backup/b.jpg",
+            ],
+        )];
+        let mut app = App::with_groups(groups);
+        assert!(app.rollup_rows().is_empty());
+
+        assert!(app.handle_action(Action::ToggleDirectoryRollup));
+        assert!(app.is_current_group_expanded());
+        assert_eq!(
+            app.rollup_rows(),
+            vec![
+                RollupRow::Directory {
+                    path: PathBuf::from("/backup"),
+                    count: 2
+                },
+                RollupRow::File(1),
+                RollupRow::File(4),
+                RollupRow::Directory {
+                    path: PathBuf::from("/old"),
+                    count: 1
+                },
+                RollupRow::File(3),
+                RollupRow::Directory {
+                    path: PathBuf::from("/photos"),
+                    count: 2
+                },
+                RollupRow::File(0),
+                RollupRow::File(2),
+            ]
+        );
+
+        // The cursor stays on the first file, then walks the rows in order
+        assert_eq!(app.rollup_index(), 6);
+        app.go_to_top();
+        assert_eq!(app.rollup_index(), 0);
+        assert_eq!(
+            app.current_rollup_directory(),
+            Some(PathBuf::from("/backup"))
+        );
+        app.next();
+        assert_eq!(app.current_file(), Some(&PathBuf::from("/backup/a.jpg")));
+        assert_eq!(app.current_rollup_directory(), None);
+        app.next();
+        app.next();
+        assert_eq!(app.current_rollup_directory(), Some(PathBuf::from("/old")));
+
+        app.handle_action(Action::ToggleDirectoryRollup);
+        assert!(app.rollup_rows().is_empty());
+        assert_eq!(app.current_file(), Some(&PathBuf::from("/old/a.jpg")));
+    }
+
+    #[test]
+    fn test_directory_rollup_is_per_group_when_hashes_match() {
+        let first = make_group(100, vec!["/x/a.txt", "/x/b.txt"]);
+        let second = make_group(100, vec!["/y/c.txt", "/y/d.txt"]);
+        assert_eq!(first.hash, second.hash);
+        let mut app = App::with_groups(vec![first.clone(), second.clone()]);
+
+        assert!(app.handle_action(Action::ToggleDirectoryRollup));
+        assert!(app.is_rolled_up(&first));
+        assert!(!app.is_rolled_up(&second));
+
+        assert!(app.handle_action(Action::ToggleDirectoryRollup));
+        assert!(!app.is_rolled_up(&first));
+    }
+
+    #[test]
+    fn test_directory_rollup_selects_whole_directories() {
+        let groups = vec![make_group(
+            100,
+            vec![
+                "/photos/a.jpg",
+                "/backup/a.jpg",
+                "/backup/b.jpg",
+                "/old/a.jpg",
+            ],
+        )];
+        let mut app = App::with_groups(groups);
+        app.handle_action(Action::ToggleDirectoryRollup);
+
+        // Rows: /backup, a, b, /old, a, /photos, a
+        app.go_to_top();
+        app.handle_action(Action::ToggleSelect);
+        assert!(app.is_file_selected(&PathBuf::from("/backup/a.jpg")));
+        assert!(app.is_file_selected(&PathBuf::from("/backup/b.jpg")));
+        assert_eq!(app.selected_count(), 2);
+
+        app.go_to_bottom();
+        app.previous();
+        assert_eq!(
+            app.current_rollup_directory(),
+            Some(PathBuf::from("/photos"))
+        );
+        app.previous();
+        app.previous();
+        assert_eq!(app.current_rollup_directory(), Some(PathBuf::from("/old")));
+        app.handle_action(Action::ToggleSelect);
+        assert_eq!(app.selected_count(), 3);
+
+        // Selecting the last unselected directory would leave nothing to keep
+        app.go_to_bottom();
+        app.previous();
+        app.handle_action(Action::ToggleSelect);
+        assert!(app.error_message().is_some());
+        assert!(!app.is_file_selected(&PathBuf::from("/photos/a.jpg")));
+
+        // Toggling a fully selected directory deselects it
+        app.go_to_top();
+        app.handle_action(Action::ToggleSelect);
+        assert_eq!(app.selected_count(), 1);
+        assert!(app.is_file_selected(&PathBuf::from("/old/a.jpg")));
+    }
+
     fn make_group_with_names(
         size: u64,
         paths_and_names: Vec<(&str, Option<&str>)>,
@@ -2967,6 +5437,96 @@ mod tests {
         assert_eq!(app.selected_count(), 2);
     }
 
+    #[test]
+    fn test_statistics_tracks_selection_changes() {
+        let groups = vec![
+            make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"]),
+            make_group(1000, vec!["/d.txt", "/e.txt"]),
+            make_group(10, vec!["/f.txt", "/g.txt"]),
+        ];
+        let mut app = App::with_groups(groups);
+        assert_eq!(
+            app.statistics(),
+            ReviewStats {
+                total_groups: 3,
+                ..ReviewStats::default()
+            }
+        );
+
+        app.select_all_in_group();
+        let stats = app.statistics();
+        assert_eq!(stats.selected_files, 2);
+        assert_eq!(stats.selected_bytes, 200);
+        assert_eq!(stats.groups_touched, 1);
+
+        app.select(PathBuf::from("/e.txt"));
+        let stats = app.statistics();
+        assert_eq!(stats.selected_files, 3);
+        assert_eq!(stats.selected_bytes, 1200);
+        assert_eq!(stats.groups_touched, 2);
+
+        app.deselect(&PathBuf::from("/b.txt"));
+        app.deselect(&PathBuf::from("/c.txt"));
+        let stats = app.statistics();
+        assert_eq!(stats.selected_files, 1);
+        assert_eq!(stats.selected_bytes, 1000);
+        assert_eq!(stats.groups_touched, 1);
+
+        app.select_all_duplicates();
+        app.apply_bulk_selection();
+        let stats = app.statistics();
+        assert_eq!(stats.selected_files, 4);
+        assert_eq!(stats.selected_bytes, 1210);
+        assert_eq!(stats.groups_touched, 3);
+
+        app.deselect_all();
+        assert_eq!(app.statistics().selected_files, 0);
+        assert_eq!(app.statistics().groups_touched, 0);
+    }
+
+    #[test]
+    fn test_statistics_counts_shared_files_once() {
+        let groups = vec![
+            make_group(100, vec!["/a.txt", "/shared.txt"]),
+            make_group(100, vec!["/b.txt", "/shared.txt"]),
+        ];
+        let mut app = App::with_groups(groups);
+        app.select(PathBuf::from("/shared.txt"));
+
+        let stats = app.statistics();
+        assert_eq!(stats.selected_files, 1);
+        assert_eq!(stats.selected_bytes, 100);
+        assert_eq!(stats.groups_touched, 2);
+        assert_eq!(
+            stats.to_string(),
+            "selected: 1 files / 100 B / 2 of 2 groups touched"
+        );
+    }
+
+    #[test]
+    fn test_apply_renames_updates_groups_and_selection() {
+        let groups = vec![make_group(100, vec!["/a (1).txt", "/b.txt", "/c.txt"])];
+        let mut app = App::with_groups(groups);
+        app.select(PathBuf::from("/b.txt"));
+
+        app.apply_renames(&[
+            (PathBuf::from("/a (1).txt"), PathBuf::from("/a.txt")),
+            (PathBuf::from("/b.txt"), PathBuf::from("/b2.txt")),
+        ]);
+
+        let paths = app.groups()[0].paths();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/a.txt"),
+                PathBuf::from("/b2.txt"),
+                PathBuf::from("/c.txt")
+            ]
+        );
+        assert!(app.is_file_selected(&PathBuf::from("/b2.txt")));
+        assert!(!app.is_file_selected(&PathBuf::from("/b.txt")));
+    }
+
     #[test]
     fn test_deselect_all() {
         let groups = vec![make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"])];
@@ -3022,6 +5582,142 @@ mod tests {
         assert!(!app.is_file_selected(&PathBuf::from("/e.txt")));
     }
 
+    /// Record `paths` as trashed (token = path), then remove them from the app.
+    fn trash_files(app: &mut App, paths: &[&str]) {
+        let deleted: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+        app.record_deletion(
+            deleted
+                .iter()
+                .map(|p| TrashedFile {
+                    path: p.clone(),
+                    location: TrashLocation::System(p.clone()),
+                })
+                .collect(),
+        );
+        app.remove_deleted_files(&deleted);
+    }
+
+    #[test]
+    fn test_undo_deletion_reinserts_files_and_groups() {
+        let groups = vec![
+            make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"]),
+            make_group(200, vec!["/d.txt", "/e.txt"]),
+            make_group(300, vec!["/f.txt", "/g.txt"]),
+        ];
+        let mut app = App::with_groups(groups.clone());
+        let reclaimable = app.reclaimable_space();
+
+        trash_files(&mut app, &["/b.txt", "/e.txt"]);
+        assert_eq!(app.group_count(), 2);
+        assert!(app.can_undo_deletion());
+
+        let mut restored_tokens = Vec::new();
+        let outcome = app
+            .undo_deletion(|file| {
+                restored_tokens.push(file.location.clone());
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(
+            restored_tokens,
+            vec![
+                TrashLocation::System(PathBuf::from("/b.txt")),
+                TrashLocation::System(PathBuf::from("/e.txt"))
+            ]
+        );
+        assert_eq!(outcome.restored.len(), 2);
+        assert!(outcome.failures.is_empty());
+        // Files are back in place and the dropped group is at its old position
+        let paths: Vec<Vec<PathBuf>> = app.groups().iter().map(DuplicateGroup::paths).collect();
+        let expected: Vec<Vec<PathBuf>> = groups.iter().map(DuplicateGroup::paths).collect();
+        assert_eq!(paths, expected);
+        assert_eq!(app.reclaimable_space(), reclaimable);
+        assert!(!app.can_undo_deletion());
+        assert!(app.undo_deletion(|_| Ok(())).is_none());
+    }
+
+    #[test]
+    fn test_undo_deletion_is_last_in_first_out() {
+        let groups = vec![make_group(
+            100,
+            vec!["/a.txt", "/b.txt", "/c.txt", "/d.txt"],
+        )];
+        let mut app = App::with_groups(groups);
+
+        trash_files(&mut app, &["/b.txt"]);
+        trash_files(&mut app, &["/c.txt", "/d.txt"]);
+        assert_eq!(app.group_count(), 0);
+
+        app.undo_deletion(|_| Ok(())).unwrap();
+        assert_eq!(
+            app.groups()[0].paths(),
+            vec![
+                PathBuf::from("/a.txt"),
+                PathBuf::from("/c.txt"),
+                PathBuf::from("/d.txt")
+            ]
+        );
+
+        app.undo_deletion(|_| Ok(())).unwrap();
+        assert_eq!(app.groups()[0].files.len(), 4);
+    }
+
+    #[test]
+    fn test_undo_deletion_skips_failed_restores() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"])];
+        let mut app = App::with_groups(groups);
+
+        trash_files(&mut app, &["/b.txt", "/c.txt"]);
+        let outcome = app
+            .undo_deletion(|file| {
+                if file.path == Path::new("/c.txt") {
+                    Err("no matching trash entry".to_string())
+                } else {
+                    Ok(())
+                }
+            })
+            .unwrap();
+
+        assert_eq!(outcome.restored, vec![PathBuf::from("/b.txt")]);
+        assert_eq!(
+            outcome.failures,
+            vec![(
+                PathBuf::from("/c.txt"),
+                "no matching trash entry".to_string()
+            )]
+        );
+        assert_eq!(
+            app.groups()[0].paths(),
+            vec![PathBuf::from("/a.txt"), PathBuf::from("/b.txt")]
+        );
+    }
+
+    #[test]
+    fn test_undo_deletion_excludes_permanent_deletions() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"])];
+        // A trash directory can always restore, whatever the platform trash does
+        let mut app = App::with_groups(groups).with_trash_dir(Some(PathBuf::from("/trash")));
+        app.set_mode(AppMode::Reviewing);
+
+        // Nothing trashed (e.g. only permanent deletions): nothing to undo
+        app.record_deletion(Vec::new());
+        app.remove_deleted_files(&[PathBuf::from("/c.txt")]);
+        assert!(!app.can_undo_deletion());
+        assert!(app.handle_action(Action::UndoDelete));
+        assert_eq!(app.error_message(), Some("No deletion to undo"));
+
+        // A later permanent deletion is not resurrected by undoing an earlier one
+        let mut app = App::with_groups(vec![make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"])]);
+        trash_files(&mut app, &["/b.txt"]);
+        app.remove_deleted_files(&[PathBuf::from("/c.txt")]);
+        app.undo_deletion(|_| Ok(())).unwrap();
+        assert_eq!(
+            app.groups()[0].paths(),
+            vec![PathBuf::from("/a.txt"), PathBuf::from("/b.txt")]
+        );
+    }
+
     #[test]
     fn test_current_file() {
         let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
@@ -3127,84 +5823,969 @@ mod tests {
         assert!(!app.pending_selections.contains(&PathBuf::from("/a.jpg")));
     }
 
+    fn make_dated_group(files: &[(&str, u64)]) -> DuplicateGroup {
+        let base = std::time::SystemTime::UNIX_EPOCH;
+        DuplicateGroup::new(
+            [7u8; 32],
+            100,
+            files
+                .iter()
+                .map(|(p, secs)| {
+                    crate::scanner::FileEntry::new(
+                        PathBuf::from(p),
+                        100,
+                        base + Duration::from_secs(*secs),
+                    )
+                })
+                .collect(),
+            Vec::new(),
+        )
+    }
+
     #[test]
-    fn test_mode_transitions() {
-        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
+    fn test_review_keep_newest_marks_all_but_newest() {
+        let groups = vec![make_dated_group(&[
+            ("/a.txt", 10),
+            ("/ref/b.txt", 20),
+            ("/c.txt", 30),
+            ("/d.txt", 5),
+        ])];
+        let mut app = App::with_groups(groups).with_reference_paths(vec![PathBuf::from("/ref")]);
+
+        assert!(app.handle_action(Action::ReviewKeepNewest));
+        assert_eq!(app.mode(), AppMode::ReviewingPending);
+        assert_eq!(app.pending_selection_count(), 2);
+        assert!(app.is_pending(&PathBuf::from("/a.txt")));
+        assert!(app.is_pending(&PathBuf::from("/d.txt")));
+        // Newest file is kept and reference files are never marked
+        assert!(!app.is_pending(&PathBuf::from("/c.txt")));
+        assert!(!app.is_pending(&PathBuf::from("/ref/b.txt")));
+        // Nothing is selected until the review is applied
+        assert_eq!(app.selected_count(), 0);
+    }
+
+    #[test]
+    fn test_review_keep_newest_unmark_and_apply() {
+        let groups = vec![make_dated_group(&[
+            ("/a.txt", 10),
+            ("/b.txt", 20),
+            ("/c.txt", 30),
+        ])];
         let mut app = App::with_groups(groups);
+        app.handle_action(Action::ReviewKeepNewest);
+        assert_eq!(app.pending_selection_count(), 2);
+
+        // Un-mark the first file
+        assert_eq!(app.current_file(), Some(&PathBuf::from("/a.txt")));
+        app.handle_action(Action::ToggleSelect);
+        assert!(!app.is_pending(&PathBuf::from("/a.txt")));
+        assert_eq!(app.pending_selection_count(), 1);
+
+        // Re-marking it is allowed because /c.txt is still kept
+        app.handle_action(Action::ToggleSelect);
+        assert!(app.is_pending(&PathBuf::from("/a.txt")));
+
+        // Marking the last kept file is refused
+        app.handle_action(Action::GoToBottom);
+        assert_eq!(app.current_file(), Some(&PathBuf::from("/c.txt")));
+        app.handle_action(Action::ToggleSelect);
+        assert!(!app.is_pending(&PathBuf::from("/c.txt")));
+        assert!(app.error_message().is_some());
 
+        // Un-mark /b.txt, then apply
+        app.handle_action(Action::NavigateUp);
+        app.handle_action(Action::ToggleSelect);
+        assert!(app.handle_action(Action::Confirm));
         assert_eq!(app.mode(), AppMode::Reviewing);
+        assert_eq!(app.selected_count(), 1);
+        assert!(app.is_file_selected(&PathBuf::from("/a.txt")));
+        assert_eq!(app.pending_selection_count(), 0);
+    }
 
-        app.set_mode(AppMode::Previewing);
-        assert_eq!(app.mode(), AppMode::Previewing);
+    #[test]
+    fn test_review_keep_newest_cancel() {
+        let groups = vec![make_dated_group(&[("/a.txt", 10), ("/b.txt", 20)])];
+        let mut app = App::with_groups(groups);
+        app.handle_action(Action::ReviewKeepNewest);
+        assert_eq!(app.mode(), AppMode::ReviewingPending);
 
-        app.set_mode(AppMode::Confirming);
-        assert_eq!(app.mode(), AppMode::Confirming);
+        app.handle_action(Action::Cancel);
+        assert_eq!(app.mode(), AppMode::Reviewing);
+        assert_eq!(app.pending_selection_count(), 0);
+        assert_eq!(app.selected_count(), 0);
+    }
 
-        app.set_mode(AppMode::Quitting);
-        assert!(app.should_quit());
+    #[test]
+    fn test_keeper_strategy_choose_keeper() {
+        let group = make_dated_group(&[
+            ("/data/archive/a.txt", 20),
+            ("/data/oldest.txt", 5),
+            ("/data/newest.txt", 40),
+            ("/x.txt", 20),
+            ("/keep/c.txt", 30),
+        ]);
+        let files = &group.files;
+
+        assert_eq!(KeeperStrategy::First.choose_keeper(files), 0);
+        assert_eq!(KeeperStrategy::Oldest.choose_keeper(files), 1);
+        assert_eq!(KeeperStrategy::Newest.choose_keeper(files), 2);
+        assert_eq!(KeeperStrategy::ShortestPath.choose_keeper(files), 3);
+        assert_eq!(
+            KeeperStrategy::InPreferredDir(PathBuf::from("/keep")).choose_keeper(files),
+            4
+        );
+        // No file in the preferred directory falls back to the first file
+        assert_eq!(
+            KeeperStrategy::InPreferredDir(PathBuf::from("/missing")).choose_keeper(files),
+            0
+        );
+
+        // Ties go to the earliest file
+        let tied = make_dated_group(&[("/b.txt", 10), ("/a.txt", 10)]);
+        assert_eq!(KeeperStrategy::Oldest.choose_keeper(&tied.files), 0);
+        assert_eq!(KeeperStrategy::Newest.choose_keeper(&tied.files), 0);
+        assert_eq!(KeeperStrategy::ShortestPath.choose_keeper(&tied.files), 0);
+        assert_eq!(KeeperStrategy::Newest.choose_keeper(&[]), 0);
     }
 
     #[test]
-    fn test_handle_action_navigate() {
-        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"])];
+    fn test_select_by_strategy_cycles_in_confirmation() {
+        let groups = vec![make_dated_group(&[
+            ("/data/long/a.txt", 20),
+            ("/data/b.txt", 5),
+            ("/c.txt", 40),
+        ])];
         let mut app = App::with_groups(groups);
-        app.handle_action(Action::ToggleExpandAll);
 
-        assert!(app.handle_action(Action::NavigateDown));
-        assert_eq!(app.file_index(), 1);
+        let pending = |app: &App| -> Vec<bool> {
+            ["/data/long/a.txt", "/data/b.txt", "/c.txt"]
+                .iter()
+                .map(|p| app.is_pending(&PathBuf::from(p)))
+                .collect()
+        };
+
+        assert!(app.handle_action(Action::SelectByStrategy));
+        assert_eq!(app.mode(), AppMode::ConfirmingBulkSelection);
+        assert_eq!(app.keeper_strategy(), &KeeperStrategy::First);
+        assert_eq!(pending(&app), [false, true, true]);
+
+        app.handle_action(Action::SelectByStrategy);
+        assert_eq!(app.keeper_strategy(), &KeeperStrategy::Oldest);
+        assert_eq!(pending(&app), [true, false, true]);
+
+        app.handle_action(Action::SelectByStrategy);
+        assert_eq!(app.keeper_strategy(), &KeeperStrategy::Newest);
+        assert_eq!(pending(&app), [true, true, false]);
+
+        app.handle_action(Action::SelectByStrategy);
+        assert_eq!(app.keeper_strategy(), &KeeperStrategy::ShortestPath);
+        assert_eq!(pending(&app), [true, true, false]);
+
+        // The preferred directory is the highlighted file's
+        app.handle_action(Action::SelectByStrategy);
+        assert_eq!(
+            app.keeper_strategy(),
+            &KeeperStrategy::InPreferredDir(PathBuf::from("/data/long"))
+        );
+        assert_eq!(pending(&app), [false, true, true]);
+
+        app.handle_action(Action::SelectByStrategy);
+        assert_eq!(app.keeper_strategy(), &KeeperStrategy::First);
+
+        app.handle_action(Action::Confirm);
+        assert_eq!(app.mode(), AppMode::Reviewing);
+        assert_eq!(app.selected_count(), 2);
+        assert!(!app.is_file_selected(&PathBuf::from("/data/long/a.txt")));
+    }
+
+    #[test]
+    fn test_strategy_selection_never_selects_whole_group() {
+        let groups = vec![make_dated_group(&[("/a.txt", 10), ("/b.txt", 20)])];
+        let mut app = App::with_groups(groups);
+        // The newest file is already selected, so keeping it would leave nothing
+        app.selected_files.insert(PathBuf::from("/b.txt"));
+
+        app.select_oldest();
+        assert_eq!(app.mode(), AppMode::Reviewing);
+        assert_eq!(app.pending_selection_count(), 0);
+    }
+
+    #[test]
+    fn test_protect_policy_excluded_from_select_all_duplicates() {
+        // Protected file is deliberately never first, which select_all keeps anyway
+        let cases = [
+            (ProtectPolicy::Oldest, "/b/oldest.txt"),
+            (ProtectPolicy::Newest, "/c/newest.txt"),
+            (ProtectPolicy::ShallowestPath, "/shallow.txt"),
+        ];
+
+        for (policy, protected) in cases {
+            let groups = vec![make_dated_group(&[
+                ("/a/x/first.txt", 20),
+                ("/b/oldest.txt", 5),
+                ("/c/newest.txt", 50),
+                ("/shallow.txt", 30),
+            ])];
+            let mut app = App::with_groups(groups).with_protect_policy(policy);
+            assert!(app.is_protected(&PathBuf::from(protected)));
+
+            app.select_all_duplicates();
+            app.apply_bulk_selection();
+
+            assert!(
+                !app.is_file_selected(&PathBuf::from(protected)),
+                "{policy} should protect {protected}"
+            );
+            assert_eq!(app.selected_count(), 2, "policy {policy}");
+        }
+    }
+
+    #[test]
+    fn test_protect_policy_none_protects_nothing() {
+        let groups = vec![make_dated_group(&[("/a.txt", 10), ("/b.txt", 20)])];
+        let mut app = App::with_groups(groups).with_protect_policy(ProtectPolicy::None);
+        assert!(!app.is_protected(&PathBuf::from("/a.txt")));
+        assert!(!app.is_protected(&PathBuf::from("/b.txt")));
+
+        app.select_all_duplicates();
+        app.apply_bulk_selection();
+        assert_eq!(app.selected_count(), 1);
+    }
+
+    #[test]
+    fn test_bulk_selection_honors_non_first_keeper() {
+        let groups = vec![
+            make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"]),
+            make_group(200, vec!["/d.txt", "/e.txt"]),
+        ];
+        let mut app = App::with_groups(groups);
+        app.handle_action(Action::ExpandAll);
+
+        // Designate the second file of the first group as its keeper
+        app.handle_action(Action::NavigateDown);
+        assert!(app.handle_action(Action::MarkAsKeeper));
+        assert!(app.is_keeper(&PathBuf::from("/b.txt")));
+
+        app.select_all_in_group();
+        assert!(app.is_file_selected(&PathBuf::from("/a.txt")));
+        assert!(!app.is_file_selected(&PathBuf::from("/b.txt")));
+        assert!(app.is_file_selected(&PathBuf::from("/c.txt")));
+
+        app.deselect_all();
+        app.select_all_duplicates();
+        app.apply_bulk_selection();
+        assert!(app.is_file_selected(&PathBuf::from("/a.txt")));
+        assert!(!app.is_file_selected(&PathBuf::from("/b.txt")));
+        assert!(app.is_file_selected(&PathBuf::from("/c.txt")));
+        // Groups without a keeper still keep their first file
+        assert!(!app.is_file_selected(&PathBuf::from("/d.txt")));
+        assert!(app.is_file_selected(&PathBuf::from("/e.txt")));
+        assert_eq!(app.selected_count(), 3);
+    }
+
+    #[test]
+    fn test_keeper_cannot_be_selected_and_toggles_off() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
+        let mut app = App::with_groups(groups);
+        app.handle_action(Action::ExpandAll);
+        app.handle_action(Action::NavigateDown);
+        app.toggle_select();
+        assert!(app.is_file_selected(&PathBuf::from("/b.txt")));
+
+        // Marking deselects the file and locks it
+        app.mark_as_keeper();
+        assert!(!app.is_file_selected(&PathBuf::from("/b.txt")));
+        app.toggle_select();
+        assert!(!app.is_file_selected(&PathBuf::from("/b.txt")));
+
+        // Marking again clears the designation
+        app.mark_as_keeper();
+        assert!(!app.is_keeper(&PathBuf::from("/b.txt")));
+        app.toggle_select();
+        assert!(app.is_file_selected(&PathBuf::from("/b.txt")));
+    }
+
+    #[test]
+    fn test_keepers_are_per_group_when_hashes_match() {
+        // Same content split into two groups, as --dedupe-within does
+        let first = make_group(100, vec!["/x/a.txt", "/x/b.txt"]);
+        let second = make_group(100, vec!["/y/c.txt", "/y/d.txt"]);
+        assert_eq!(first.hash, second.hash);
+        let mut app = App::with_groups(vec![first, second]);
+        app.handle_action(Action::ExpandAll);
+
+        // Mark the second file of each group as its keeper
+        app.handle_action(Action::NavigateDown);
+        app.mark_as_keeper();
+        app.handle_action(Action::NavigateDown);
+        app.handle_action(Action::NavigateDown);
+        app.mark_as_keeper();
+        assert!(app.is_keeper(&PathBuf::from("/x/b.txt")));
+        assert!(app.is_keeper(&PathBuf::from("/y/d.txt")));
+
+        app.select_all_duplicates();
+        app.apply_bulk_selection();
+        assert!(app.is_file_selected(&PathBuf::from("/x/a.txt")));
+        assert!(app.is_file_selected(&PathBuf::from("/y/c.txt")));
+        assert_eq!(app.selected_count(), 2);
+    }
+
+    #[test]
+    fn test_select_complement_of_keep_marks() {
+        let groups = vec![
+            make_group(200, vec!["/a.txt", "/b.txt", "/c.txt", "/ref/d.txt"]),
+            make_group(100, vec!["/e.txt", "/f.txt"]),
+        ];
+        let mut app = App::with_groups(groups).with_reference_paths(vec![PathBuf::from("/ref")]);
+        app.handle_action(Action::ExpandAll);
+
+        // Keep /a.txt and /c.txt; /b.txt was selected before and is released
+        app.handle_action(Action::NavigateDown);
+        app.toggle_select();
+        app.handle_action(Action::NavigateUp);
+        assert!(app.handle_action(Action::MarkKeepInverse));
+        app.handle_action(Action::NavigateDown);
+        app.handle_action(Action::NavigateDown);
+        assert!(app.handle_action(Action::MarkKeepInverse));
+        assert!(app.is_keep_marked(&PathBuf::from("/a.txt")));
+        assert!(app.is_keep_marked(&PathBuf::from("/c.txt")));
+        assert_eq!(app.keep_mark_count(), 2);
+
+        app.deselect_all();
+        assert!(app.handle_action(Action::SelectComplementForDeletion));
+        assert_eq!(app.mode(), AppMode::ConfirmingBulkSelection);
+        assert_eq!(
+            app.pending_bulk_action(),
+            Some(BulkSelectionType::KeepComplement)
+        );
+        app.apply_bulk_selection();
+
+        // The reference copy and the group without keep-marks are untouched
+        assert!(app.is_file_selected(&PathBuf::from("/b.txt")));
+        assert!(!app.is_file_selected(&PathBuf::from("/a.txt")));
+        assert!(!app.is_file_selected(&PathBuf::from("/c.txt")));
+        assert!(!app.is_file_selected(&PathBuf::from("/ref/d.txt")));
+        assert!(!app.is_file_selected(&PathBuf::from("/e.txt")));
+        assert!(!app.is_file_selected(&PathBuf::from("/f.txt")));
+        assert_eq!(app.selected_count(), 1);
+
+        // Keep-marked files cannot be selected by hand or in bulk
+        app.handle_action(Action::NavigateUp);
+        app.handle_action(Action::NavigateUp);
+        app.toggle_select();
+        assert!(!app.is_file_selected(&PathBuf::from("/a.txt")));
+        app.select_all_duplicates();
+        app.apply_bulk_selection();
+        assert!(!app.is_file_selected(&PathBuf::from("/a.txt")));
+        assert!(!app.is_file_selected(&PathBuf::from("/c.txt")));
+    }
+
+    #[test]
+    fn test_select_complement_keeps_at_least_one() {
+        let groups = vec![
+            make_group(200, vec!["/a.txt", "/b.txt"]),
+            make_group(100, vec!["/c.txt", "/d.txt"]),
+        ];
+        let mut app = App::with_groups(groups);
+        app.handle_action(Action::ExpandAll);
+
+        // Nothing marked: refuse instead of selecting whole groups
+        app.handle_action(Action::SelectComplementForDeletion);
+        assert_eq!(app.mode(), AppMode::Reviewing);
+        assert_eq!(app.error_message(), Some("No files marked to keep"));
+
+        // Toggling the only mark again leaves the group unmarked
+        app.toggle_keep_mark();
+        app.toggle_keep_mark();
+        assert_eq!(app.keep_mark_count(), 0);
+
+        // Every file marked: nothing left to select
+        app.toggle_keep_mark();
+        app.handle_action(Action::NavigateDown);
+        app.toggle_keep_mark();
+        app.select_complement();
+        assert_eq!(app.mode(), AppMode::Reviewing);
+        assert!(!app.has_selections());
+
+        // A marked file cannot be selected directly either
+        app.toggle_keep_mark();
+        app.select(PathBuf::from("/a.txt"));
+        assert!(!app.is_file_selected(&PathBuf::from("/a.txt")));
+        assert_eq!(
+            app.error_message(),
+            Some("Cannot select a file marked to keep")
+        );
+
+        // Only the unmarked file of the marked group is staged
+        app.select_complement();
+        assert_eq!(app.mode(), AppMode::ConfirmingBulkSelection);
+        assert_eq!(app.pending_selection_count(), 1);
+    }
+
+    #[test]
+    fn test_selection_plan() {
+        let groups = vec![
+            make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"]),
+            make_group(200, vec!["/d.txt", "/e.txt"]),
+            make_group(300, vec!["/f.txt", "/g.txt"]),
+        ];
+        let mut app = App::with_groups(groups);
+        assert_eq!(app.selection_plan().unwrap(), Vec::new());
+
+        // Explicit keeper in the first group
+        app.handle_action(Action::ExpandAll);
+        app.handle_action(Action::NavigateDown);
+        app.mark_as_keeper();
+        app.select(PathBuf::from("/a.txt"));
+        app.select(PathBuf::from("/c.txt"));
+        // No keeper: the first file stays; the third group is untouched
+        app.select(PathBuf::from("/e.txt"));
+
+        let plan = app.selection_plan().unwrap();
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].keeper, PathBuf::from("/b.txt"));
+        assert_eq!(
+            plan[0].delete,
+            vec![PathBuf::from("/a.txt"), PathBuf::from("/c.txt")]
+        );
+        assert_eq!(plan[1].hash, app.groups()[1].hash);
+        assert_eq!(plan[1].keeper, PathBuf::from("/d.txt"));
+        assert_eq!(plan[1].delete, vec![PathBuf::from("/e.txt")]);
+
+        // A selected first file hands the keep over to the next one
+        app.deselect(&PathBuf::from("/e.txt"));
+        app.select(PathBuf::from("/d.txt"));
+        let plan = app.selection_plan().unwrap();
+        assert_eq!(plan[1].keeper, PathBuf::from("/e.txt"));
+        assert_eq!(plan[1].delete, vec![PathBuf::from("/d.txt")]);
+    }
+
+    #[test]
+    fn test_selection_plan_keepers_of_groups_sharing_a_hash() {
+        let groups = vec![
+            make_group(100, vec!["/x/a.txt", "/x/b.txt", "/x/c.txt"]),
+            make_group(100, vec!["/y/d.txt", "/y/e.txt", "/y/f.txt"]),
+        ];
+        let mut app = App::with_groups(groups);
+        app.handle_action(Action::ExpandAll);
+
+        // Keep the last file of each group
+        app.handle_action(Action::NavigateDown);
+        app.handle_action(Action::NavigateDown);
+        app.mark_as_keeper();
+        for _ in 0..3 {
+            app.handle_action(Action::NavigateDown);
+        }
+        app.mark_as_keeper();
+        app.select(PathBuf::from("/x/a.txt"));
+        app.select(PathBuf::from("/y/d.txt"));
+
+        let plan = app.selection_plan().unwrap();
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].keeper, PathBuf::from("/x/c.txt"));
+        assert_eq!(plan[1].keeper, PathBuf::from("/y/f.txt"));
+    }
+
+    #[test]
+    fn test_selection_plan_rejects_fully_selected_group() {
+        let groups = vec![
+            make_group(100, vec!["/a.txt", "/b.txt"]),
+            make_group(200, vec!["/c.txt", "/d.txt"]),
+        ];
+        let mut app = App::with_groups(groups);
+        app.select(PathBuf::from("/b.txt"));
+        app.select(PathBuf::from("/c.txt"));
+        app.select(PathBuf::from("/d.txt"));
+
+        assert!(matches!(
+            app.selection_plan(),
+            Err(DeleteError::AllCopiesWouldBeDeleted)
+        ));
+    }
+
+    #[test]
+    fn test_keepers_round_trip_through_session() {
+        let groups = vec![
+            make_group(100, vec!["/a.txt", "/b.txt"]),
+            make_group(200, vec!["/c.txt", "/d.txt"]),
+        ];
+        let mut app = App::with_groups(groups.clone());
+        app.handle_action(Action::ExpandAll);
+        app.handle_action(Action::NavigateDown);
+        app.mark_as_keeper();
+        let keepers = app.keepers_btree();
+        assert_eq!(
+            keepers.iter().collect::<Vec<_>>(),
+            vec![&PathBuf::from("/b.txt")]
+        );
+
+        let mut restored = App::with_groups(groups);
+        let mut with_stale = keepers.clone();
+        with_stale.insert(PathBuf::from("/gone.txt"));
+        restored.apply_keepers(with_stale);
+        assert_eq!(restored.keepers_btree(), keepers);
+
+        restored.select_all_duplicates();
+        restored.apply_bulk_selection();
+        assert!(restored.is_file_selected(&PathBuf::from("/a.txt")));
+        assert!(!restored.is_file_selected(&PathBuf::from("/b.txt")));
+    }
+
+    #[test]
+    fn test_toggle_select_policy_protected_file() {
+        let groups = vec![make_dated_group(&[("/a.txt", 10), ("/b.txt", 20)])];
+        let mut app = App::with_groups(groups).with_protect_policy(ProtectPolicy::Newest);
+        app.handle_action(Action::ToggleExpandAll);
+
+        app.handle_action(Action::NavigateDown);
+        assert_eq!(app.current_file(), Some(&PathBuf::from("/b.txt")));
+        app.toggle_select();
+        assert!(!app.is_file_selected(&PathBuf::from("/b.txt")));
+        assert!(app.error_message().is_some());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_protected_hardlinks_cannot_be_selected() {
+        use crate::scanner::{FileEntry, HardlinkTracker};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let original = dir.path().join("original.txt");
+        let link = dir.path().join("link.txt");
+        let copy = dir.path().join("copy.txt");
+        std::fs::write(&original, b"content").unwrap();
+        std::fs::hard_link(&original, &link).unwrap();
+        std::fs::write(&copy, b"content").unwrap();
+
+        let mut tracker = HardlinkTracker::new();
+        let files: Vec<FileEntry> = [original.clone(), link.clone(), copy.clone()]
+            .into_iter()
+            .map(|path| {
+                let meta = std::fs::metadata(&path).unwrap();
+                let mut entry = FileEntry::new(path, meta.len(), meta.modified().unwrap());
+                entry.is_hardlink = tracker.is_hardlink(&meta);
+                entry.inode = crate::scanner::hardlink::inode_id(&meta);
+                entry
+            })
+            .collect();
+        let groups = vec![DuplicateGroup::new([1u8; 32], 7, files, Vec::new())];
+
+        // Without protection the hardlink is an ordinary duplicate
+        let app = App::with_groups(groups.clone());
+        assert!(!app.is_protected(&link));
+
+        let mut app = App::with_groups(groups).with_protect_hardlinks(true);
+        assert!(app.is_hardlink_protected(&link));
+        // The first link is protected too: deleting it frees no space either
+        assert!(app.is_hardlink_protected(&original));
+        assert!(!app.is_protected(&copy));
+
+        app.select_all_duplicates();
+        app.apply_bulk_selection();
+        assert!(!app.is_file_selected(&original));
+        assert!(!app.is_file_selected(&link));
+        assert!(app.is_file_selected(&copy));
+
+        app.handle_action(Action::ToggleExpandAll);
+        app.handle_action(Action::NavigateDown);
+        assert_eq!(app.current_file(), Some(&link));
+        app.toggle_select();
+        assert!(!app.is_file_selected(&link));
+        assert!(app.error_message().is_some());
+    }
+
+    #[test]
+    fn test_mode_transitions() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
+        let mut app = App::with_groups(groups);
+
+        assert_eq!(app.mode(), AppMode::Reviewing);
+
+        app.set_mode(AppMode::Previewing);
+        assert_eq!(app.mode(), AppMode::Previewing);
+
+        app.set_mode(AppMode::Confirming);
+        assert_eq!(app.mode(), AppMode::Confirming);
+
+        app.set_mode(AppMode::Quitting);
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn test_handle_action_navigate() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"])];
+        let mut app = App::with_groups(groups);
+        app.handle_action(Action::ToggleExpandAll);
+
+        assert!(app.handle_action(Action::NavigateDown));
+        assert_eq!(app.file_index(), 1);
+
+        assert!(app.handle_action(Action::NavigateUp));
+        assert_eq!(app.file_index(), 0);
+    }
+
+    #[test]
+    fn test_handle_action_toggle_select() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
+        let mut app = App::with_groups(groups);
+
+        // Initially collapsed
+        assert!(!app.is_current_group_expanded());
+
+        assert!(app.handle_action(Action::ToggleSelect));
+        // First press should expand
+        assert!(app.is_current_group_expanded());
+        assert!(!app.is_current_selected());
+
+        // Pressing space on first file (index 0) should collapse
+        assert!(app.handle_action(Action::ToggleSelect));
+        assert!(!app.is_current_group_expanded());
+
+        // Expand again and move to second file
+        app.handle_action(Action::ToggleExpandAll);
+        app.next();
+        assert_eq!(app.file_index(), 1);
+
+        assert!(app.handle_action(Action::ToggleSelect));
+        assert!(app.is_current_selected());
+    }
+
+    #[test]
+    fn test_handle_action_preview() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
+        let mut app = App::with_groups(groups);
+
+        assert!(app.handle_action(Action::Preview));
+        assert_eq!(app.mode(), AppMode::Previewing);
+    }
+
+    #[test]
+    fn test_handle_action_copy_paths() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"])];
+        let mut app = App::with_groups(groups).with_dry_run(true);
+        app.handle_action(Action::NavigateDown);
+
+        assert!(app.handle_action(Action::CopyPath));
+        assert_eq!(
+            app.clipboard_paths(Action::CopyPath),
+            Some(vec![PathBuf::from("/b.txt")])
+        );
+        assert!(app.handle_action(Action::CopyGroupPaths));
+        let paths = app.clipboard_paths(Action::CopyGroupPaths).unwrap();
+        assert_eq!(
+            crate::tui::clipboard::clipboard_text(&paths),
+            "/a.txt\n/b.txt\n/c.txt"
+        );
+        assert_eq!(app.mode(), AppMode::Reviewing);
+
+        assert_eq!(app.clipboard_paths(Action::Preview), None);
+        app.set_mode(AppMode::Searching);
+        assert!(!app.handle_action(Action::CopyPath));
+        assert!(!App::new().handle_action(Action::CopyGroupPaths));
+    }
+
+    #[test]
+    fn test_handle_action_reveal() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
+        let mut app = App::with_groups(groups).with_dry_run(true);
+
+        assert!(app.handle_action(Action::RevealInFileManager));
+        assert_eq!(app.mode(), AppMode::Reviewing);
+
+        app.set_mode(AppMode::Searching);
+        assert!(!app.handle_action(Action::RevealInFileManager));
+        assert!(!App::new().handle_action(Action::RevealInFileManager));
+    }
+
+    #[test]
+    fn test_handle_action_export_selection() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
+        let mut app = App::with_groups(groups).with_dry_run(true);
+
+        // Nothing selected: stay in review with a message
+        assert!(app.handle_action(Action::ExportSelection));
+        assert_eq!(app.mode(), AppMode::Reviewing);
+        assert!(app.error_message().is_some());
+        app.clear_error();
+
+        // Works in dry-run since nothing is modified
+        app.select(PathBuf::from("/b.txt"));
+        app.set_input_query("stale".to_string());
+        assert!(app.handle_action(Action::ExportSelection));
+        assert_eq!(app.mode(), AppMode::InputtingExportPath);
+        assert_eq!(app.input_query(), "");
+
+        app.handle_action(Action::Cancel);
+        assert_eq!(app.mode(), AppMode::Reviewing);
+    }
+
+    #[test]
+    fn test_export_selection_sorted_newline() {
+        let groups = vec![
+            make_group(100, vec!["/z/keep.txt", "/z/copy.txt"]),
+            make_group(200, vec!["/m/keep.bin", "/a/copy.bin", "/b/copy.bin"]),
+        ];
+        let selected = ["/z/copy.txt", "/b/copy.bin", "/a/copy.bin"];
+        let mut app = App::with_groups(groups.clone());
+        for path in selected {
+            app.select(PathBuf::from(path));
+        }
+
+        let mut first = Vec::new();
+        assert_eq!(app.export_selection(&mut first, false).unwrap(), 3);
+        assert_eq!(
+            String::from_utf8(first.clone()).unwrap(),
+            "/a/copy.bin\n/b/copy.bin\n/z/copy.txt\n"
+        );
+
+        // Selection order does not affect the output
+        let mut reversed = App::with_groups(groups);
+        for path in selected.iter().rev() {
+            reversed.select(PathBuf::from(path));
+        }
+        let mut second = Vec::new();
+        reversed.export_selection(&mut second, false).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_export_selection_nul_separated() {
+        let groups = vec![make_group(
+            100,
+            vec!["/keep.txt", "/with space.txt", "/b.txt"],
+        )];
+        let mut app = App::with_groups(groups);
+        app.select(PathBuf::from("/with space.txt"));
+        app.select(PathBuf::from("/b.txt"));
+
+        let mut out = Vec::new();
+        assert_eq!(app.export_selection(&mut out, true).unwrap(), 2);
+        assert_eq!(out, b"/b.txt\0/with space.txt\0");
+        assert!(!out.contains(&b'\n'));
+
+        let mut empty = Vec::new();
+        assert_eq!(App::new().export_selection(&mut empty, true).unwrap(), 0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_export_nul_separated() {
+        let mut app = App::new();
+        assert!(!app.export_nul_separated());
+        app.toggle_export_nul_separated();
+        assert!(app.export_nul_separated());
+    }
+
+    #[test]
+    fn test_handle_action_diff() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt", "/c.txt"])];
+        let mut app = App::with_groups(groups);
+
+        // The first file is the reference, so it is compared with the next one
+        assert_eq!(
+            app.diff_target(),
+            Some((PathBuf::from("/a.txt"), PathBuf::from("/b.txt")))
+        );
+        assert!(app.handle_action(Action::DiffWithReference));
+        assert_eq!(app.mode(), AppMode::Diffing);
+
+        app.set_preview("--- a\n+++ b\n@@ -1 +1 @@\n-x\n+y\n".to_string());
+        app.handle_action(Action::NavigateDown);
+        app.handle_action(Action::NavigateDown);
+        assert_eq!(app.diff_scroll(), 2);
+        app.handle_action(Action::GoToBottom);
+        assert_eq!(app.diff_scroll(), 4);
+        app.handle_action(Action::NavigateDown);
+        assert_eq!(app.diff_scroll(), 4);
+        app.handle_action(Action::GoToTop);
+        assert_eq!(app.diff_scroll(), 0);
+
+        assert!(app.handle_action(Action::Cancel));
+        assert_eq!(app.mode(), AppMode::Reviewing);
+        assert!(app.preview_content().is_none());
+
+        // A reference directory file is preferred as the base
+        let groups = vec![make_group(100, vec!["/a.txt", "/ref/b.txt"])];
+        let app = App::with_groups(groups).with_reference_paths(vec![PathBuf::from("/ref")]);
+        assert_eq!(
+            app.diff_target(),
+            Some((PathBuf::from("/ref/b.txt"), PathBuf::from("/a.txt")))
+        );
+    }
+
+    #[test]
+    fn test_delete_config_transactional() {
+        let app = App::with_groups(vec![make_group(100, vec!["/a.txt", "/b.txt"])]);
+        assert!(!app.delete_config().transactional);
+
+        let app = app.with_transactional_delete(true);
+        assert!(app.delete_config().transactional);
+    }
+
+    #[test]
+    fn test_delete_config_permanent_under() {
+        let groups = vec![make_group(
+            100,
+            vec!["/ref/a.txt", "/scratch/b.txt", "/docs/c.txt"],
+        )];
+        let mut app = App::with_groups(groups)
+            .with_reference_paths(vec![PathBuf::from("/ref")])
+            .with_permanent_under(vec![PathBuf::from("/scratch"), PathBuf::from("/ref")]);
+
+        let config = app.delete_config();
+        assert_eq!(
+            config.mode_for(std::path::Path::new("/scratch/b.txt")),
+            DeleteMode::Permanent
+        );
+        assert_eq!(
+            config.mode_for(std::path::Path::new("/docs/c.txt")),
+            DeleteMode::Trash
+        );
+        // Reference files are never deleted permanently
+        assert_eq!(
+            config.mode_for(std::path::Path::new("/ref/a.txt")),
+            DeleteMode::Trash
+        );
+
+        app.select_all_duplicates();
+        app.apply_bulk_selection();
+        assert_eq!(app.selected_count(), 2);
+        assert_eq!(app.selected_permanent_count(), 1);
+    }
+
+    #[test]
+    fn test_handle_action_delete_requires_selection() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
+        let mut app = App::with_groups(groups);
+
+        // Without selection, delete should not work
+        assert!(!app.handle_action(Action::Delete));
+        assert_eq!(app.mode(), AppMode::Reviewing);
+
+        // With selection, delete should transition to Confirming
+        app.toggle_select();
+        assert!(app.handle_action(Action::Delete));
+        assert_eq!(app.mode(), AppMode::Confirming);
+    }
+
+    #[test]
+    fn test_delete_name_similar_requires_second_confirmation() {
+        let now = std::time::SystemTime::now();
+        let group = DuplicateGroup::new_name_similar(
+            "img.jpg",
+            vec![
+                crate::scanner::FileEntry::new(PathBuf::from("/img.jpg"), 10, now),
+                crate::scanner::FileEntry::new(PathBuf::from("/img (1).jpg"), 12, now),
+            ],
+            Vec::new(),
+        );
+        let mut app = App::with_groups(vec![group]);
+
+        app.toggle_select();
+        assert_eq!(app.selected_name_only_count(), 1);
+        assert!(app.handle_action(Action::Delete));
+        assert!(app.needs_name_similar_confirmation());
+
+        app.handle_action(Action::Confirm);
+        assert_eq!(app.mode(), AppMode::Confirming);
+        assert!(!app.needs_name_similar_confirmation());
+        assert!(!app.is_delete_confirmed());
+
+        app.handle_action(Action::Confirm);
+        assert!(app.is_delete_confirmed());
+    }
+
+    #[test]
+    fn test_delete_exact_group_single_confirmation() {
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
+        let mut app = App::with_groups(groups);
+
+        app.toggle_select();
+        assert_eq!(app.selected_name_only_count(), 0);
+        assert!(app.handle_action(Action::Delete));
+        app.handle_action(Action::Confirm);
+        assert!(app.is_delete_confirmed());
+    }
+
+    fn make_threshold_app() -> App {
+        let groups = vec![
+            make_group(100, vec!["/a1", "/a2"]),
+            make_group(200, vec!["/b1", "/b2"]),
+            make_group(300, vec!["/c1", "/c2"]),
+        ];
+        App::with_groups(groups)
+    }
 
-        assert!(app.handle_action(Action::NavigateUp));
-        assert_eq!(app.file_index(), 0);
+    fn select_paths(app: &mut App, paths: &[&str]) {
+        for path in paths {
+            app.selected_files.insert(PathBuf::from(path));
+        }
     }
 
     #[test]
-    fn test_handle_action_toggle_select() {
-        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
-        let mut app = App::with_groups(groups);
+    fn test_typed_confirmation_count_threshold() {
+        let mut app = make_threshold_app().with_confirm_thresholds(2, u64::MAX);
 
-        // Initially collapsed
-        assert!(!app.is_current_group_expanded());
+        select_paths(&mut app, &["/a2", "/b2"]);
+        assert_eq!(app.selected_size(), 300);
+        // Exactly at the threshold: a keypress is enough
+        assert!(!app.requires_typed_confirmation());
 
-        assert!(app.handle_action(Action::ToggleSelect));
-        // First press should expand
-        assert!(app.is_current_group_expanded());
-        assert!(!app.is_current_selected());
+        select_paths(&mut app, &["/c2"]);
+        assert!(app.requires_typed_confirmation());
+    }
 
-        // Pressing space on first file (index 0) should collapse
-        assert!(app.handle_action(Action::ToggleSelect));
-        assert!(!app.is_current_group_expanded());
+    #[test]
+    fn test_typed_confirmation_bytes_threshold() {
+        let mut app = make_threshold_app().with_confirm_thresholds(usize::MAX, 300);
 
-        // Expand again and move to second file
-        app.handle_action(Action::ToggleExpandAll);
-        app.next();
-        assert_eq!(app.file_index(), 1);
+        select_paths(&mut app, &["/a2", "/b2"]);
+        assert!(!app.requires_typed_confirmation());
 
-        assert!(app.handle_action(Action::ToggleSelect));
-        assert!(app.is_current_selected());
+        select_paths(&mut app, &["/a1"]);
+        assert_eq!(app.selected_size(), 400);
+        assert!(app.requires_typed_confirmation());
     }
 
     #[test]
-    fn test_handle_action_preview() {
-        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
-        let mut app = App::with_groups(groups);
+    fn test_typed_confirmation_defaults() {
+        let mut app = make_threshold_app();
+        select_paths(&mut app, &["/a2", "/b2", "/c2"]);
+        assert!(!app.requires_typed_confirmation());
 
-        assert!(app.handle_action(Action::Preview));
-        assert_eq!(app.mode(), AppMode::Previewing);
+        let big = make_group(DEFAULT_CONFIRM_THRESHOLD_BYTES + 1, vec!["/big1", "/big2"]);
+        let mut app = App::with_groups(vec![big]);
+        select_paths(&mut app, &["/big2"]);
+        assert!(app.requires_typed_confirmation());
     }
 
     #[test]
-    fn test_handle_action_delete_requires_selection() {
-        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
-        let mut app = App::with_groups(groups);
-
-        // Without selection, delete should not work
-        assert!(!app.handle_action(Action::Delete));
-        assert_eq!(app.mode(), AppMode::Reviewing);
+    fn test_typed_confirmation_gates_delete() {
+        let mut app = make_threshold_app().with_confirm_thresholds(1, u64::MAX);
+        select_paths(&mut app, &["/a2", "/b2"]);
 
-        // With selection, delete should transition to Confirming
-        app.toggle_select();
         assert!(app.handle_action(Action::Delete));
         assert_eq!(app.mode(), AppMode::Confirming);
+
+        // A single keypress is rejected
+        app.handle_action(Action::Confirm);
+        assert!(!app.is_delete_confirmed());
+        assert!(app
+            .error_message()
+            .unwrap()
+            .contains(TYPED_CONFIRMATION_WORD));
+
+        app.set_input_query("delete".to_string());
+        app.handle_action(Action::Confirm);
+        assert!(!app.is_delete_confirmed());
+
+        app.set_input_query(TYPED_CONFIRMATION_WORD.to_string());
+        app.handle_action(Action::Confirm);
+        assert!(app.is_delete_confirmed());
+
+        // Cancelling clears the typed word for the next attempt
+        app.handle_action(Action::Cancel);
+        assert!(app.input_query().is_empty());
     }
 
     #[test]
@@ -3246,6 +6827,34 @@ mod tests {
         assert!(app.error_message().unwrap().contains("dry-run"));
     }
 
+    #[test]
+    fn test_pause_and_resume_scan() {
+        let pause = Arc::new(AtomicBool::new(false));
+        let mut app = App::new().with_pause_flag(pause.clone());
+        assert!(app.can_pause_scan());
+
+        assert!(app.handle_action(Action::PauseScan));
+        assert!(pause.load(Ordering::SeqCst));
+        assert!(app.is_scan_paused());
+        assert!(app.handle_action(Action::ResumeScan));
+        assert!(!pause.load(Ordering::SeqCst));
+
+        // The preview key no longer doubles as a pause toggle
+        assert!(!app.handle_action(Action::Preview));
+        assert!(!app.is_scan_paused());
+
+        // Only the scan screen pauses, and only with a shared flag
+        app.set_mode(AppMode::Reviewing);
+        assert!(!app.handle_action(Action::PauseScan));
+        assert!(!pause.load(Ordering::SeqCst));
+        let mut app = App::new();
+        assert!(!app.can_pause_scan());
+        assert!(!app.handle_action(Action::PauseScan));
+        assert!(!app.is_scan_paused());
+        assert_eq!("pause".parse::<Action>(), Ok(Action::PauseScan));
+        assert_eq!(Action::ResumeScan.name(), "resume_scan");
+    }
+
     #[test]
     fn test_scan_progress() {
         let mut app = App::new();
@@ -3278,6 +6887,73 @@ mod tests {
         assert_eq!(progress.percentage(), 100);
     }
 
+    #[test]
+    fn test_scan_progress_eta() {
+        let mut progress = ScanProgress::new();
+        progress.bytes_done = 250 * 1024 * 1024;
+        progress.bytes_total = 1000 * 1024 * 1024;
+
+        // 250 MiB in 10s = 25 MiB/s; 750 MiB remaining -> 30s
+        let elapsed = Duration::from_secs(10);
+        assert!((progress.throughput_after(elapsed) - 25.0 * 1024.0 * 1024.0).abs() < 1.0);
+        assert_eq!(progress.eta_after(elapsed), Some(Duration::from_secs(30)));
+
+        // Finished: nothing left
+        progress.bytes_done = progress.bytes_total;
+        assert_eq!(progress.eta_after(elapsed), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_scan_progress_eta_unknown() {
+        let mut progress = ScanProgress::new();
+        let elapsed = Duration::from_secs(5);
+
+        // Nothing processed yet
+        progress.bytes_total = 1000;
+        assert_eq!(progress.eta_after(elapsed), None);
+
+        // Unknown total still reports throughput
+        progress.bytes_total = 0;
+        progress.bytes_done = 500;
+        assert_eq!(progress.eta_after(elapsed), None);
+        assert!((progress.throughput_after(elapsed) - 100.0).abs() < f64::EPSILON);
+
+        // Zero elapsed time never divides by zero
+        assert_eq!(progress.throughput_after(Duration::ZERO), 0.0);
+    }
+
+    #[test]
+    fn test_update_scan_progress_resets_bytes_on_phase_change() {
+        let mut app = App::new();
+        app.update_scan_progress("Prehashing", 1, 10, "/a");
+        app.scan_progress.bytes_total = 100;
+        app.scan_progress.bytes_done = 40;
+        app.update_scan_progress("Prehashing", 2, 10, "/b");
+        assert_eq!(app.scan_progress().bytes_done, 40);
+
+        app.update_scan_progress("Full hashing", 1, 5, "/c");
+        assert_eq!(app.scan_progress().bytes_done, 0);
+        assert_eq!(app.scan_progress().bytes_total, 0);
+    }
+
+    #[test]
+    fn test_sync_scan_progress_from_shared() {
+        let shared = Arc::new(Mutex::new(ScanProgress::new()));
+        let mut app = App::new().with_scan_progress(shared.clone());
+        {
+            let mut progress = shared.lock().unwrap();
+            progress.phase = "Full hashing".to_string();
+            progress.bytes_done = 40;
+            progress.bytes_total = 100;
+        }
+        assert_eq!(app.scan_progress().bytes_done, 0);
+
+        app.sync_scan_progress();
+        assert_eq!(app.scan_progress().phase, "Full hashing");
+        assert_eq!(app.scan_progress().bytes_done, 40);
+        assert_eq!(app.scan_progress().bytes_total, 100);
+    }
+
     #[test]
     fn test_error_handling() {
         let mut app = App::new();
@@ -3304,6 +6980,58 @@ mod tests {
         assert!(app.preview_content().is_none());
     }
 
+    #[test]
+    fn test_preview_scroll_bounds() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("long.txt");
+        let text: String = (0..50).map(|i| format!("line {}\n", i)).collect();
+        std::fs::write(&path, text).unwrap();
+
+        let groups = vec![make_group(100, vec!["/a.txt", "/b.txt"])];
+        let mut app = App::with_groups(groups);
+        assert!(app.handle_action(Action::Preview));
+        app.set_text_preview(TextPager::new(&path));
+        assert!(app.is_preview_paged());
+        assert!(app.preview_content().unwrap().starts_with("line 0\n"));
+
+        // Cannot scroll above the first line
+        app.handle_action(Action::NavigateUp);
+        assert_eq!(app.preview_offset(), 0);
+
+        app.handle_action(Action::NavigateDown);
+        app.handle_action(Action::NavigateDown);
+        assert_eq!(app.preview_offset(), 2);
+        assert!(app.preview_content().unwrap().starts_with("line 2\n"));
+
+        // Page keys jump a screen; the file selection does not move
+        app.handle_action(Action::NextGroup);
+        assert_eq!(app.preview_offset(), 22);
+        assert_eq!(app.group_index(), 0);
+        app.handle_action(Action::PreviousGroup);
+        app.handle_action(Action::PreviousGroup);
+        assert_eq!(app.preview_offset(), 0);
+
+        // Cannot scroll past the last line
+        app.handle_action(Action::GoToBottom);
+        assert_eq!(app.preview_offset(), 49);
+        assert_eq!(app.preview_content(), Some("line 49"));
+        app.handle_action(Action::NavigateDown);
+        app.handle_action(Action::NextGroup);
+        assert_eq!(app.preview_offset(), 49);
+
+        app.handle_action(Action::GoToTop);
+        assert_eq!(app.preview_offset(), 0);
+
+        // A page is the height of the preview dialog
+        app.set_preview_rows(10);
+        app.handle_action(Action::NextGroup);
+        assert_eq!(app.preview_offset(), 10);
+
+        assert!(app.handle_action(Action::Cancel));
+        assert!(!app.is_preview_paged());
+        assert_eq!(app.preview_offset(), 0);
+    }
+
     #[test]
     fn test_app_mode_is_navigable() {
         assert!(!AppMode::Scanning.is_navigable());
@@ -3469,6 +7197,107 @@ mod tests {
         assert_eq!(app.visible_group_at(0).unwrap().size, 300);
     }
 
+    fn search_mode_app() -> App {
+        App::with_groups(vec![
+            make_group(100, vec!["/photos/cat.jpg", "/backup/cat.jpg"]),
+            make_group(200, vec!["/docs/jpg-notes.txt", "/old/jpg-notes.txt"]),
+            make_group(300, vec!["/photos/dog.png", "/temp/dog.png"]),
+            make_group(400, vec!["/music/a+b.mp3", "/old/a+b.mp3"]),
+        ])
+    }
+
+    fn visible_sizes(app: &App) -> Vec<u64> {
+        let mut sizes: Vec<u64> = (0..app.visible_group_count())
+            .map(|i| app.visible_group_at(i).unwrap().size)
+            .collect();
+        sizes.sort_unstable();
+        sizes
+    }
+
+    #[test]
+    fn test_search_mode_default_and_cycle() {
+        let mut app = search_mode_app();
+        assert_eq!(app.search_mode(), SearchMode::Regex);
+
+        app.handle_action(Action::ToggleSearchMode);
+        assert_eq!(app.search_mode(), SearchMode::Glob);
+        app.handle_action(Action::ToggleSearchMode);
+        assert_eq!(app.search_mode(), SearchMode::Substring);
+        app.handle_action(Action::ToggleSearchMode);
+        assert_eq!(app.search_mode(), SearchMode::Regex);
+    }
+
+    #[test]
+    fn test_search_mode_substring() {
+        let mut app = search_mode_app();
+        app.set_search_mode(SearchMode::Substring);
+
+        app.set_search_query("JPG".to_string());
+        assert_eq!(visible_sizes(&app), vec![100, 200]);
+
+        // Glob and regex syntax is taken literally
+        app.set_search_query("*.jpg".to_string());
+        assert!(visible_sizes(&app).is_empty());
+        app.set_search_query("a+b".to_string());
+        assert_eq!(visible_sizes(&app), vec![400]);
+    }
+
+    #[test]
+    fn test_search_mode_regex() {
+        let mut app = search_mode_app();
+
+        app.set_search_query(r"\.(jpg|png)$".to_string());
+        assert_eq!(visible_sizes(&app), vec![100, 300]);
+
+        // `a+b` is a regex that does not match the literal "+"
+        app.set_search_query("a+b".to_string());
+        assert!(visible_sizes(&app).is_empty());
+
+        // Invalid regex falls back to substring matching
+        app.set_search_query("cat.jpg(".to_string());
+        assert!(visible_sizes(&app).is_empty());
+        app.set_search_query("dog.png".to_string());
+        assert_eq!(visible_sizes(&app), vec![300]);
+    }
+
+    #[test]
+    fn test_search_mode_glob() {
+        let mut app = search_mode_app();
+        app.set_search_mode(SearchMode::Glob);
+
+        // Only files ending in .jpg, not paths merely containing "jpg"
+        app.set_search_query("*.jpg".to_string());
+        assert_eq!(visible_sizes(&app), vec![100]);
+
+        app.set_search_query("*.JPG".to_string());
+        assert_eq!(visible_sizes(&app), vec![100]);
+
+        app.set_search_query("/photos/*".to_string());
+        assert_eq!(visible_sizes(&app), vec![100, 300]);
+
+        app.set_search_query("*.{png,mp3}".to_string());
+        assert_eq!(visible_sizes(&app), vec![300, 400]);
+
+        // A bare word must match the whole path
+        app.set_search_query("cat".to_string());
+        assert!(visible_sizes(&app).is_empty());
+
+        // Invalid globs match nothing
+        app.set_search_query("[".to_string());
+        assert!(visible_sizes(&app).is_empty());
+    }
+
+    #[test]
+    fn test_search_mode_change_reapplies_query() {
+        let mut app = search_mode_app();
+        app.set_search_query("*.jpg".to_string());
+        // As a regex, `*.jpg` is invalid and falls back to a substring with no match
+        assert!(visible_sizes(&app).is_empty());
+
+        app.set_search_mode(SearchMode::Glob);
+        assert_eq!(visible_sizes(&app), vec![100]);
+    }
+
     #[test]
     fn test_navigation_with_search() {
         let groups = vec![
@@ -3504,6 +7333,159 @@ mod tests {
         assert_eq!(app.current_group().unwrap().size, 300);
     }
 
+    #[test]
+    fn test_toggle_columns_cycles_presets() {
+        let mut app = App::with_groups(vec![make_group(100, vec!["/a.txt", "/b.txt"])]);
+        assert_eq!(app.column_preset(), ColumnPreset::Standard);
+        assert!(!app.visible_columns().hash);
+
+        assert!(app.handle_action(Action::ToggleColumns));
+        assert_eq!(app.column_preset(), ColumnPreset::Full);
+        assert!(app.visible_columns().contains(Column::Hash));
+
+        app.handle_action(Action::ToggleColumns);
+        assert_eq!(app.column_preset(), ColumnPreset::Compact);
+        assert!(!app.visible_columns().date);
+
+        app.handle_action(Action::ToggleColumns);
+        assert_eq!(app.visible_columns(), ColumnPreset::Paths.columns());
+        assert!(!app.visible_columns().size);
+
+        app.handle_action(Action::ToggleColumns);
+        assert_eq!(app.column_preset(), ColumnPreset::Standard);
+
+        let app = App::new().with_column_preset(ColumnPreset::Compact);
+        assert_eq!(app.column_preset(), ColumnPreset::Compact);
+        assert_eq!("columns".parse::<Action>().unwrap(), Action::ToggleColumns);
+    }
+
+    #[test]
+    fn test_jump_to_group() {
+        let groups = (1..=50)
+            .map(|i| make_group(i * 100, vec!["/a.bin", "/b.bin"]))
+            .collect();
+        let mut app = App::with_groups(groups);
+
+        assert!(app.handle_action(Action::JumpToGroup));
+        assert_eq!(app.mode(), AppMode::JumpingToGroup);
+        app.set_input_query("42".to_string());
+        assert!(app.handle_action(Action::Confirm));
+        assert_eq!(app.mode(), AppMode::Reviewing);
+        assert_eq!(app.group_index(), 41);
+        assert!(app.input_query().is_empty());
+
+        // Past the end lands on the last group, zero on the first
+        app.handle_action(Action::JumpToGroup);
+        app.set_input_query("999".to_string());
+        app.handle_action(Action::Confirm);
+        assert_eq!(app.group_index(), 49);
+        app.handle_action(Action::JumpToGroup);
+        app.set_input_query("0".to_string());
+        app.handle_action(Action::Confirm);
+        assert_eq!(app.group_index(), 0);
+
+        // Escape leaves the position alone
+        app.handle_action(Action::JumpToGroup);
+        app.set_input_query("7".to_string());
+        app.handle_action(Action::Cancel);
+        assert_eq!(app.mode(), AppMode::Reviewing);
+        assert_eq!(app.group_index(), 0);
+        assert!(app.input_query().is_empty());
+    }
+
+    /// Every group's indexed position matches its place in the list.
+    fn assert_hash_index_consistent(app: &App) {
+        let ids = crate::duplicates::group_ids(app.groups());
+        for (index, group) in app.groups().iter().enumerate() {
+            assert_eq!(app.group_position(&group.hash), Some(index));
+            assert_eq!(app.group_by_hash(&group.hash).unwrap().size, group.size);
+            assert_eq!(app.group_position_by_id(&ids[index]), Some(index));
+        }
+    }
+
+    #[test]
+    fn test_group_by_id_reaches_groups_sharing_a_hash() {
+        // Same size, so make_group gives both the same hash
+        let first = make_group(100, vec!["/a1", "/a2"]);
+        let second = make_group(100, vec!["/b1", "/b2", "/b3"]);
+        assert_eq!(first.hash, second.hash);
+        let mut app = App::with_groups(vec![first, second])
+            .with_sort(SortColumn::Count, SortDirection::Descending);
+
+        let prefix = crate::duplicates::group_id(&app.groups()[0].hash);
+        let second_id = format!("{}-2", prefix);
+        assert_eq!(app.group_position_by_id(&second_id), Some(0));
+        assert_eq!(app.group_position_by_id(&prefix), Some(1));
+        assert_eq!(app.group_by_id(&prefix).unwrap().files.len(), 2);
+        assert_eq!(app.current_group_id(), Some(second_id.as_str()));
+
+        // Sorting keeps each ID on its group, and the selection follows it
+        app.reverse_sort_direction();
+        assert_eq!(app.group_position_by_id(&second_id), Some(1));
+        assert_eq!(app.current_group_id(), Some(second_id.as_str()));
+        assert_eq!(app.current_group().unwrap().files.len(), 3);
+    }
+
+    #[test]
+    fn test_group_by_hash_across_sort_and_deletion() {
+        let small = make_group(100, vec!["/s1", "/s2"]);
+        let medium = make_group(200, vec!["/m1", "/m2", "/m3"]);
+        let large = make_group(300, vec!["/l1", "/l2"]);
+        let (small_hash, medium_hash, large_hash) = (small.hash, medium.hash, large.hash);
+        let mut app = App::with_groups(vec![small, medium, large]);
+
+        // Size descending by default
+        assert_eq!(app.group_position(&large_hash), Some(0));
+        assert_eq!(app.group_position(&small_hash), Some(2));
+        assert_hash_index_consistent(&app);
+
+        app.reverse_sort_direction();
+        assert_eq!(app.group_position(&small_hash), Some(0));
+        assert_eq!(app.group_position(&large_hash), Some(2));
+        assert_hash_index_consistent(&app);
+
+        app.cycle_sort_column();
+        assert_hash_index_consistent(&app);
+
+        // Dropping a group below two files removes it from the index
+        app.remove_deleted_files(&[PathBuf::from("/s1")]);
+        assert!(app.group_by_hash(&small_hash).is_none());
+        assert_eq!(app.group_by_hash(&medium_hash).unwrap().files.len(), 3);
+        assert_hash_index_consistent(&app);
+
+        app.remove_deleted_files(&[PathBuf::from("/m3")]);
+        assert_eq!(app.group_by_hash(&medium_hash).unwrap().files.len(), 2);
+        assert_hash_index_consistent(&app);
+
+        app.set_groups(Vec::new());
+        assert!(app.group_by_hash(&large_hash).is_none());
+    }
+
+    #[test]
+    fn test_jump_to_group_with_search() {
+        let groups = vec![
+            make_group(100, vec!["/a/cat.jpg", "/b/cat.jpg"]),
+            make_group(200, vec!["/a/dog.png", "/b/dog.png"]),
+            make_group(300, vec!["/a/bird.jpg", "/b/bird.jpg"]),
+        ];
+        let mut app = App::with_groups(groups);
+        app.set_search_query("jpg".to_string());
+        assert_eq!(app.visible_group_count(), 2);
+
+        // Numbers count within the filtered view
+        app.handle_action(Action::JumpToGroup);
+        app.set_input_query("5".to_string());
+        app.handle_action(Action::Confirm);
+        assert_eq!(app.group_index(), 1);
+        assert!(app.is_searching());
+        assert!(app
+            .current_group()
+            .unwrap()
+            .files
+            .iter()
+            .all(|f| f.path.to_string_lossy().ends_with(".jpg")));
+    }
+
     #[test]
     fn test_sorting_groups() {
         let groups = vec![
@@ -3548,6 +7530,195 @@ mod tests {
         assert_eq!(app.groups()[1].files.len(), 2);
     }
 
+    #[test]
+    fn test_special_groups_sort_after_exact_groups() {
+        let files = |paths: &[&str], size: u64| -> Vec<crate::scanner::FileEntry> {
+            paths
+                .iter()
+                .map(|p| {
+                    crate::scanner::FileEntry::new(
+                        PathBuf::from(p),
+                        size,
+                        std::time::SystemTime::now(),
+                    )
+                })
+                .collect()
+        };
+        let named = DuplicateGroup::new_name_similar(
+            "photo.jpg",
+            files(&["/photo.jpg", "/photo (1).jpg"], 900),
+            Vec::new(),
+        );
+        let groups = vec![
+            named,
+            make_group(100, vec!["/z.txt", "/z2.txt"]),
+            make_group(300, vec!["/a.txt", "/a2.txt"]),
+        ];
+        let mut app = App::with_groups(groups);
+
+        // The largest group is a name cluster, listed after the exact groups
+        let sizes: Vec<u64> = app.groups().iter().map(|g| g.size).collect();
+        assert_eq!(sizes, vec![300, 100, 900]);
+        app.handle_action(Action::ReverseSortDirection);
+        let sizes: Vec<u64> = app.groups().iter().map(|g| g.size).collect();
+        assert_eq!(sizes, vec![100, 300, 900]);
+    }
+
+    #[test]
+    fn test_intentional_groups_sort_last_and_filter() {
+        let mut backup = make_group(500, vec!["/etc/app.conf", "/etc/app.conf.bak"]);
+        backup.likely_intentional = true;
+        let groups = vec![
+            backup,
+            make_group(100, vec!["/z.txt", "/z2.txt"]),
+            make_group(300, vec!["/a.txt", "/a2.txt"]),
+        ];
+        let mut app = App::with_groups(groups);
+
+        // Largest group, but listed last in either direction
+        assert_eq!(app.groups()[0].size, 300);
+        assert_eq!(app.groups()[2].size, 500);
+        app.handle_action(Action::ReverseSortDirection);
+        assert_eq!(app.groups()[0].size, 100);
+        assert_eq!(app.groups()[2].size, 500);
+
+        // Exact Only -> Similar Only -> Hide Intentional
+        for _ in 0..3 {
+            app.handle_action(Action::CycleGroupFilter);
+        }
+        assert_eq!(app.group_filter(), GroupFilter::HideIntentional);
+        assert_eq!(app.visible_group_count(), 2);
+        assert!(app
+            .visible_group_at(1)
+            .is_some_and(|g| !g.likely_intentional));
+        assert!(app.visible_group_at(2).is_none());
+
+        app.handle_action(Action::CycleGroupFilter);
+        assert_eq!(app.group_filter(), GroupFilter::All);
+        assert_eq!(app.visible_group_count(), 3);
+    }
+
+    #[test]
+    fn test_pinned_group_stays_visible_under_search() {
+        let groups = vec![
+            make_group(300, vec!["/photos/cat.jpg", "/backup/cat.jpg"]),
+            make_group(200, vec!["/music/song.mp3", "/old/song.mp3"]),
+            make_group(100, vec!["/photos/dog.jpg", "/backup/dog.jpg"]),
+        ];
+        let mut app = App::with_groups(groups);
+
+        // Pin the song group
+        app.handle_action(Action::NextGroup);
+        assert!(app.handle_action(Action::TogglePin));
+        let pinned = app.current_group().unwrap().hash;
+        assert!(app.is_pinned(&pinned));
+
+        // The query excludes it, but it stays listed in sort order
+        app.set_search_query("jpg".to_string());
+        assert!(app.is_searching());
+        assert_eq!(app.visible_group_count(), 3);
+        assert_eq!(app.visible_group_at(1).unwrap().hash, pinned);
+
+        app.set_search_query("cat".to_string());
+        assert_eq!(app.visible_group_count(), 2);
+        assert_eq!(app.visible_group_at(0).unwrap().size, 300);
+        assert_eq!(app.visible_group_at(1).unwrap().hash, pinned);
+        assert!(app.visible_group_at(2).is_none());
+
+        // Also immune to the group filter
+        app.clear_search();
+        app.handle_action(Action::CycleGroupFilter);
+        app.handle_action(Action::CycleGroupFilter);
+        assert_eq!(app.group_filter(), GroupFilter::SimilarOnly);
+        assert_eq!(app.visible_group_count(), 1);
+        assert_eq!(app.visible_group_at(0).unwrap().hash, pinned);
+
+        // Unpinning hides it again
+        assert!(app.handle_action(Action::TogglePin));
+        assert!(!app.is_pinned(&pinned));
+        assert_eq!(app.visible_group_count(), 0);
+        assert!(!app.handle_action(Action::TogglePin));
+    }
+
+    #[test]
+    fn test_pinned_groups_round_trip_through_session() {
+        let groups = vec![
+            make_group(100, vec!["/a.txt", "/b.txt"]),
+            make_group(200, vec!["/c.txt", "/d.txt"]),
+        ];
+        let mut app = App::with_groups(groups.clone());
+        app.toggle_pin();
+        let pinned = app.pinned_groups_btree();
+        assert_eq!(pinned.len(), 1);
+        assert!(pinned.contains(&app.groups()[0].hash));
+
+        let mut restored = App::with_groups(groups);
+        let mut with_stale = pinned.clone();
+        with_stale.insert([9; 32]);
+        restored.apply_pinned_groups(with_stale);
+        assert_eq!(restored.pinned_groups_btree(), pinned);
+
+        restored.set_search_query("a.txt".to_string());
+        assert_eq!(restored.visible_group_count(), 2);
+    }
+
+    #[test]
+    fn test_sort_state_session_round_trip() {
+        use crate::session::{Session, SessionGroup, SessionSettings};
+
+        let groups = vec![
+            make_group(300, vec!["/a.txt", "/a2.txt"]),
+            make_group(100, vec!["/z.txt", "/z2.txt", "/z3.txt", "/z4.txt"]),
+            make_group(200, vec!["/m.txt", "/m2.txt", "/m3.txt"]),
+        ];
+        let mut app = App::with_groups(groups.clone());
+        while app.sort_column() != SortColumn::Count {
+            app.handle_action(Action::CycleSortColumn);
+        }
+        app.handle_action(Action::ReverseSortDirection);
+        assert_eq!(app.sort_direction(), SortDirection::Ascending);
+        app.handle_action(Action::NavigateDown);
+
+        let settings = SessionSettings {
+            sort_column: app.sort_column(),
+            sort_direction: app.sort_direction(),
+            ..Default::default()
+        };
+        let session_groups = app
+            .groups()
+            .iter()
+            .enumerate()
+            .map(|(id, g)| SessionGroup::from_duplicate_group(g, id))
+            .collect();
+        let mut session = Session::new(vec!["/".into()], settings, session_groups);
+        session.group_index = app.group_index();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        session.save(&path).unwrap();
+        let loaded = Session::load(&path).unwrap();
+
+        let (restored_groups, _) = loaded.to_results();
+        let mut restored = App::with_groups(restored_groups)
+            .with_sort(loaded.settings.sort_column, loaded.settings.sort_direction);
+        restored.apply_session(
+            loaded.user_selections,
+            loaded.group_index,
+            loaded.file_index,
+        );
+
+        assert_eq!(restored.sort_column(), SortColumn::Count);
+        assert_eq!(restored.sort_direction(), SortDirection::Ascending);
+        let sizes: Vec<u64> = restored.groups().iter().map(|g| g.size).collect();
+        assert_eq!(sizes, vec![300, 200, 100]);
+        assert_eq!(restored.current_group().unwrap().size, 200);
+
+        // Sessions written before sort state was saved fall back to defaults
+        let old: SessionSettings = serde_json::from_str(r#"{"follow_symlinks": false, "skip_hidden": false, "min_size": null, "max_size": null, "newer_than": null, "older_than": null, "ignore_patterns": [], "regex_include": [], "regex_exclude": [], "file_categories": [], "io_threads": 4, "paranoid": false, "io_buffer_size": null, "doc_similarity_threshold": null}"#).unwrap();
+        assert_eq!(old.sort_column, SortColumn::Size);
+        assert_eq!(old.sort_direction, SortDirection::Descending);
+    }
+
     #[test]
     fn test_sorting_maintains_selection() {
         let groups = vec![
@@ -3638,7 +7809,7 @@ mod tests {
     #[test]
     fn test_action_all_names() {
         let names = Action::all_names();
-        assert_eq!(names.len(), 35);
+        assert_eq!(names.len(), 53);
         assert!(names.contains(&"navigate_down"));
         assert!(names.contains(&"show_help"));
         assert!(names.contains(&"select_group"));
@@ -3651,7 +7822,7 @@ mod tests {
     #[test]
     fn test_action_all() {
         let actions = Action::all();
-        assert_eq!(actions.len(), 35);
+        assert_eq!(actions.len(), 53);
         assert!(actions.contains(&Action::NavigateDown));
         assert!(actions.contains(&Action::ShowHelp));
         assert!(actions.contains(&Action::SelectGroup));
@@ -0,0 +1,104 @@
+//! Copying file paths to the system clipboard.
+//!
+//! The TUI copies the current file's path (or every path of its group, one
+//! per line) so it can be pasted into another terminal. Paths are made
+//! absolute first. Clipboard access goes through `arboard`; headless
+//! sessions without a display server report [`ClipboardError::Unavailable`].
+//!
+//! On Linux the process that copied owns the clipboard contents, so the
+//! clipboard handle is kept for the life of the TUI thread instead of being
+//! dropped after each copy.
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::tui::clipboard::clipboard_text;
+//! use std::path::PathBuf;
+//!
+//! let paths = [PathBuf::from("/a.txt"), PathBuf::from("/b.txt")];
+//! assert_eq!(clipboard_text(&paths), "/a.txt\n/b.txt");
+//! ```
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors that can occur when copying to the clipboard.
+#[derive(Debug, Error)]
+pub enum ClipboardError {
+    /// No clipboard could be opened (e.g. a headless session).
+    #[error("clipboard unavailable: {0}")]
+    Unavailable(String),
+
+    /// The clipboard rejected the text.
+    #[error("failed to set clipboard text: {0}")]
+    SetFailed(String),
+}
+
+thread_local! {
+    static CLIPBOARD: RefCell<Option<arboard::Clipboard>> = const { RefCell::new(None) };
+}
+
+/// Make `path` absolute against the current directory, if it is not already.
+#[must_use]
+pub fn absolute_path(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Join the absolute forms of `paths` with newlines, without a trailing one.
+#[must_use]
+pub fn clipboard_text<P: AsRef<Path>>(paths: &[P]) -> String {
+    paths
+        .iter()
+        .map(|p| absolute_path(p.as_ref()).display().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Put `text` on the system clipboard.
+///
+/// # Errors
+///
+/// Returns [`ClipboardError::Unavailable`] if no clipboard can be opened and
+/// [`ClipboardError::SetFailed`] if it rejects the text.
+pub fn copy_to_clipboard(text: &str) -> Result<(), ClipboardError> {
+    CLIPBOARD.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        let clipboard = match slot.take() {
+            Some(clipboard) => clipboard,
+            None => {
+                arboard::Clipboard::new().map_err(|e| ClipboardError::Unavailable(e.to_string()))?
+            }
+        };
+        slot.insert(clipboard)
+            .set_text(text)
+            .map_err(|e| ClipboardError::SetFailed(e.to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_text_joins_absolute_paths() {
+        let paths = [
+            PathBuf::from("/photos/a.jpg"),
+            PathBuf::from("/backup/photos/a.jpg"),
+            PathBuf::from("/path with spaces/a.jpg"),
+        ];
+        assert_eq!(
+            clipboard_text(&paths),
+            "/photos/a.jpg\n/backup/photos/a.jpg\n/path with spaces/a.jpg"
+        );
+        assert_eq!(clipboard_text(&paths[..1]), "/photos/a.jpg");
+        assert_eq!(clipboard_text::<PathBuf>(&[]), "");
+    }
+
+    #[test]
+    fn test_clipboard_text_resolves_relative_paths() {
+        let text = clipboard_text(&[Path::new("docs/readme.txt")]);
+        let expected = std::env::current_dir().unwrap().join("docs/readme.txt");
+        assert_eq!(text, expected.display().to_string());
+    }
+}
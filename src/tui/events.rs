@@ -27,6 +27,10 @@
 //! | `Escape` | Cancel current action |
 //! | `p` | Preview current file |
 //! | `d` | Delete selected files (to trash) |
+//! | `Z` | Restore the last deletion from the trash |
+//! | `M` | Mark current file as its group's keeper |
+//! | `P` | Select all but one file per group by keeper strategy |
+//! | `T` | Toggle directory rollup of the current group |
 //! | `q` | Quit application |
 //!
 //! # Example
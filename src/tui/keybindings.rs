@@ -655,6 +655,11 @@ impl KeyBindings {
             ],
         );
 
+        bindings.insert(
+            Action::ReviewKeepNewest,
+            vec![Self::key(KeyCode::Char('R'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::SelectSmallest,
             vec![
@@ -671,6 +676,11 @@ impl KeyBindings {
             ],
         );
 
+        bindings.insert(
+            Action::SelectByStrategy,
+            vec![Self::key(KeyCode::Char('P'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::SelectByExtension,
             vec![Self::key(KeyCode::Char('E'), KeyModifiers::SHIFT)],
@@ -708,6 +718,26 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('p'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::RevealInFileManager,
+            vec![Self::key(KeyCode::Char('w'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::CopyPath,
+            vec![Self::key(KeyCode::Char('y'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::CopyGroupPaths,
+            vec![Self::key(KeyCode::Char('y'), KeyModifiers::ALT)],
+        );
+
+        bindings.insert(
+            Action::DiffWithReference,
+            vec![Self::key(KeyCode::Char('i'), KeyModifiers::NONE)],
+        );
+
         bindings.insert(
             Action::SelectFolder,
             vec![
@@ -729,6 +759,15 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('/'), KeyModifiers::NONE)],
         );
 
+        // ':' is shifted on most layouts; accept both reports as for '?'
+        bindings.insert(
+            Action::JumpToGroup,
+            vec![
+                Self::key(KeyCode::Char(':'), KeyModifiers::NONE),
+                Self::key(KeyCode::Char(':'), KeyModifiers::SHIFT),
+            ],
+        );
+
         bindings.insert(
             Action::Export,
             vec![
@@ -737,6 +776,41 @@ impl KeyBindings {
             ],
         );
 
+        bindings.insert(
+            Action::ExportSelection,
+            vec![Self::key(KeyCode::Char('Y'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::UndoDelete,
+            vec![Self::key(KeyCode::Char('Z'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::MarkAsKeeper,
+            vec![Self::key(KeyCode::Char('M'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::MarkKeepInverse,
+            vec![Self::key(KeyCode::Char('H'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::SelectComplementForDeletion,
+            vec![Self::key(KeyCode::Char('c'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::TogglePin,
+            vec![Self::key(KeyCode::Char('m'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::ToggleDirectoryRollup,
+            vec![Self::key(KeyCode::Char('T'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::Delete,
             vec![Self::key(KeyCode::Char('d'), KeyModifiers::NONE)],
@@ -747,6 +821,11 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('t'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::ToggleColumns,
+            vec![Self::key(KeyCode::Char('C'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::ToggleExpandAll,
             vec![Self::key(KeyCode::Char('e'), KeyModifiers::NONE)],
@@ -774,6 +853,16 @@ impl KeyBindings {
             ],
         );
 
+        // Scan screen
+        bindings.insert(
+            Action::PauseScan,
+            vec![Self::key(KeyCode::Char('z'), KeyModifiers::NONE)],
+        );
+        bindings.insert(
+            Action::ResumeScan,
+            vec![Self::key(KeyCode::Char('r'), KeyModifiers::NONE)],
+        );
+
         // Quit
         bindings.insert(
             Action::Quit,
@@ -867,6 +956,11 @@ impl KeyBindings {
             ],
         );
 
+        bindings.insert(
+            Action::ReviewKeepNewest,
+            vec![Self::key(KeyCode::Char('R'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::SelectSmallest,
             vec![
@@ -883,6 +977,11 @@ impl KeyBindings {
             ],
         );
 
+        bindings.insert(
+            Action::SelectByStrategy,
+            vec![Self::key(KeyCode::Char('P'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::SelectByExtension,
             vec![Self::key(KeyCode::Char('E'), KeyModifiers::SHIFT)],
@@ -920,6 +1019,26 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('p'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::RevealInFileManager,
+            vec![Self::key(KeyCode::Char('w'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::CopyPath,
+            vec![Self::key(KeyCode::Char('y'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::CopyGroupPaths,
+            vec![Self::key(KeyCode::Char('y'), KeyModifiers::ALT)],
+        );
+
+        bindings.insert(
+            Action::DiffWithReference,
+            vec![Self::key(KeyCode::Char('i'), KeyModifiers::NONE)],
+        );
+
         bindings.insert(
             Action::SelectFolder,
             vec![
@@ -941,6 +1060,15 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('/'), KeyModifiers::NONE)],
         );
 
+        // ':' is shifted on most layouts; accept both reports as for '?'
+        bindings.insert(
+            Action::JumpToGroup,
+            vec![
+                Self::key(KeyCode::Char(':'), KeyModifiers::NONE),
+                Self::key(KeyCode::Char(':'), KeyModifiers::SHIFT),
+            ],
+        );
+
         bindings.insert(
             Action::Export,
             vec![
@@ -949,6 +1077,41 @@ impl KeyBindings {
             ],
         );
 
+        bindings.insert(
+            Action::ExportSelection,
+            vec![Self::key(KeyCode::Char('Y'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::UndoDelete,
+            vec![Self::key(KeyCode::Char('Z'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::MarkAsKeeper,
+            vec![Self::key(KeyCode::Char('M'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::MarkKeepInverse,
+            vec![Self::key(KeyCode::Char('H'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::SelectComplementForDeletion,
+            vec![Self::key(KeyCode::Char('c'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::TogglePin,
+            vec![Self::key(KeyCode::Char('m'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::ToggleDirectoryRollup,
+            vec![Self::key(KeyCode::Char('T'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::Delete,
             vec![Self::key(KeyCode::Char('d'), KeyModifiers::NONE)],
@@ -959,6 +1122,11 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('t'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::ToggleColumns,
+            vec![Self::key(KeyCode::Char('C'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::ToggleExpandAll,
             vec![Self::key(KeyCode::Char('e'), KeyModifiers::NONE)],
@@ -984,6 +1152,16 @@ impl KeyBindings {
             ],
         );
 
+        // Scan screen
+        bindings.insert(
+            Action::PauseScan,
+            vec![Self::key(KeyCode::Char('z'), KeyModifiers::NONE)],
+        );
+        bindings.insert(
+            Action::ResumeScan,
+            vec![Self::key(KeyCode::Char('r'), KeyModifiers::NONE)],
+        );
+
         // Quit
         bindings.insert(
             Action::Quit,
@@ -1065,6 +1243,11 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('n'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::ReviewKeepNewest,
+            vec![Self::key(KeyCode::Char('R'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::SelectSmallest,
             vec![Self::key(KeyCode::Char('s'), KeyModifiers::NONE)],
@@ -1075,6 +1258,11 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('l'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::SelectByStrategy,
+            vec![Self::key(KeyCode::Char('P'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::SelectByExtension,
             vec![Self::key(KeyCode::Char('E'), KeyModifiers::SHIFT)],
@@ -1115,6 +1303,26 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('p'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::RevealInFileManager,
+            vec![Self::key(KeyCode::Char('w'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::CopyPath,
+            vec![Self::key(KeyCode::Char('y'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::CopyGroupPaths,
+            vec![Self::key(KeyCode::Char('y'), KeyModifiers::ALT)],
+        );
+
+        bindings.insert(
+            Action::DiffWithReference,
+            vec![Self::key(KeyCode::Char('i'), KeyModifiers::NONE)],
+        );
+
         bindings.insert(
             Action::SelectFolder,
             vec![Self::key(KeyCode::Char('f'), KeyModifiers::NONE)],
@@ -1130,6 +1338,15 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('/'), KeyModifiers::NONE)],
         );
 
+        // ':' is shifted on most layouts; accept both reports as for '?'
+        bindings.insert(
+            Action::JumpToGroup,
+            vec![
+                Self::key(KeyCode::Char(':'), KeyModifiers::NONE),
+                Self::key(KeyCode::Char(':'), KeyModifiers::SHIFT),
+            ],
+        );
+
         bindings.insert(
             Action::Export,
             vec![
@@ -1138,6 +1355,41 @@ impl KeyBindings {
             ],
         );
 
+        bindings.insert(
+            Action::ExportSelection,
+            vec![Self::key(KeyCode::Char('Y'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::UndoDelete,
+            vec![Self::key(KeyCode::Char('Z'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::MarkAsKeeper,
+            vec![Self::key(KeyCode::Char('M'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::MarkKeepInverse,
+            vec![Self::key(KeyCode::Char('H'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::SelectComplementForDeletion,
+            vec![Self::key(KeyCode::Char('c'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::TogglePin,
+            vec![Self::key(KeyCode::Char('m'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::ToggleDirectoryRollup,
+            vec![Self::key(KeyCode::Char('T'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::Delete,
             vec![Self::key(KeyCode::Delete, KeyModifiers::NONE)],
@@ -1148,6 +1400,11 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('t'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::ToggleColumns,
+            vec![Self::key(KeyCode::Char('C'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::ToggleExpandAll,
             vec![Self::key(KeyCode::Char('e'), KeyModifiers::NONE)],
@@ -1173,6 +1430,16 @@ impl KeyBindings {
             ],
         );
 
+        // Scan screen
+        bindings.insert(
+            Action::PauseScan,
+            vec![Self::key(KeyCode::Char('z'), KeyModifiers::NONE)],
+        );
+        bindings.insert(
+            Action::ResumeScan,
+            vec![Self::key(KeyCode::Char('r'), KeyModifiers::NONE)],
+        );
+
         // Quit
         bindings.insert(
             Action::Quit,
@@ -1267,6 +1534,11 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('n'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::ReviewKeepNewest,
+            vec![Self::key(KeyCode::Char('R'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::SelectSmallest,
             vec![Self::key(KeyCode::Char('s'), KeyModifiers::NONE)],
@@ -1277,6 +1549,11 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('l'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::SelectByStrategy,
+            vec![Self::key(KeyCode::Char('P'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::SelectByExtension,
             vec![Self::key(KeyCode::Char('E'), KeyModifiers::SHIFT)],
@@ -1320,6 +1597,26 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('p'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::RevealInFileManager,
+            vec![Self::key(KeyCode::Char('w'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::CopyPath,
+            vec![Self::key(KeyCode::Char('y'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::CopyGroupPaths,
+            vec![Self::key(KeyCode::Char('y'), KeyModifiers::ALT)],
+        );
+
+        bindings.insert(
+            Action::DiffWithReference,
+            vec![Self::key(KeyCode::Char('i'), KeyModifiers::NONE)],
+        );
+
         bindings.insert(
             Action::SelectFolder,
             vec![Self::key(KeyCode::Char('f'), KeyModifiers::NONE)],
@@ -1335,6 +1632,15 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('/'), KeyModifiers::NONE)],
         );
 
+        // ':' is shifted on most layouts; accept both reports as for '?'
+        bindings.insert(
+            Action::JumpToGroup,
+            vec![
+                Self::key(KeyCode::Char(':'), KeyModifiers::NONE),
+                Self::key(KeyCode::Char(':'), KeyModifiers::SHIFT),
+            ],
+        );
+
         bindings.insert(
             Action::Export,
             vec![
@@ -1343,6 +1649,41 @@ impl KeyBindings {
             ],
         );
 
+        bindings.insert(
+            Action::ExportSelection,
+            vec![Self::key(KeyCode::Char('Y'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::UndoDelete,
+            vec![Self::key(KeyCode::Char('Z'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::MarkAsKeeper,
+            vec![Self::key(KeyCode::Char('M'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::MarkKeepInverse,
+            vec![Self::key(KeyCode::Char('H'), KeyModifiers::SHIFT)],
+        );
+
+        bindings.insert(
+            Action::SelectComplementForDeletion,
+            vec![Self::key(KeyCode::Char('c'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::TogglePin,
+            vec![Self::key(KeyCode::Char('m'), KeyModifiers::NONE)],
+        );
+
+        bindings.insert(
+            Action::ToggleDirectoryRollup,
+            vec![Self::key(KeyCode::Char('T'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::Delete,
             vec![Self::key(KeyCode::Char('d'), KeyModifiers::NONE)],
@@ -1353,6 +1694,11 @@ impl KeyBindings {
             vec![Self::key(KeyCode::Char('t'), KeyModifiers::NONE)],
         );
 
+        bindings.insert(
+            Action::ToggleColumns,
+            vec![Self::key(KeyCode::Char('C'), KeyModifiers::SHIFT)],
+        );
+
         bindings.insert(
             Action::ToggleExpand,
             vec![
@@ -1386,6 +1732,16 @@ impl KeyBindings {
             ],
         );
 
+        // Scan screen
+        bindings.insert(
+            Action::PauseScan,
+            vec![Self::key(KeyCode::Char('z'), KeyModifiers::NONE)],
+        );
+        bindings.insert(
+            Action::ResumeScan,
+            vec![Self::key(KeyCode::Char('r'), KeyModifiers::NONE)],
+        );
+
         // Quit
         bindings.insert(
             Action::Quit,
@@ -1576,6 +1932,24 @@ mod tests {
         assert_eq!(bindings.resolve(&key), Some(Action::NextGroup));
     }
 
+    #[test]
+    fn test_pause_and_resume_scan_keys_in_every_profile() {
+        for profile in KeybindingProfile::all() {
+            let bindings = KeyBindings::from_profile(*profile);
+            for (action, c) in [(Action::PauseScan, 'z'), (Action::ResumeScan, 'r')] {
+                let key = key_press(KeyCode::Char(c), KeyModifiers::NONE);
+                assert_eq!(bindings.resolve(&key), Some(action), "{}", profile);
+                // No other action shares the key
+                let sharing = bindings
+                    .all_bindings()
+                    .values()
+                    .filter(|keys| keys.iter().any(|k| KeyBindings::key_matches(k, &key)))
+                    .count();
+                assert_eq!(sharing, 1, "{}", profile);
+            }
+        }
+    }
+
     #[test]
     fn test_universal_previous_group_vim() {
         let bindings = KeyBindings::from_profile(KeybindingProfile::Universal);
@@ -1653,6 +2027,78 @@ mod tests {
         assert_eq!(bindings.resolve(&key), Some(Action::Preview));
     }
 
+    #[test]
+    fn test_diff_bound_in_all_profiles() {
+        for profile in KeybindingProfile::all() {
+            let bindings = KeyBindings::from_profile(*profile);
+            let key = key_press(KeyCode::Char('i'), KeyModifiers::NONE);
+            assert_eq!(bindings.resolve(&key), Some(Action::DiffWithReference));
+        }
+    }
+
+    #[test]
+    fn test_export_selection_bound_in_all_profiles() {
+        for profile in KeybindingProfile::all() {
+            let bindings = KeyBindings::from_profile(*profile);
+            let key = key_press(KeyCode::Char('Y'), KeyModifiers::SHIFT);
+            assert_eq!(bindings.resolve(&key), Some(Action::ExportSelection));
+        }
+    }
+
+    #[test]
+    fn test_jump_to_group_bound_in_all_profiles() {
+        for profile in KeybindingProfile::all() {
+            let bindings = KeyBindings::from_profile(*profile);
+            let key = key_press(KeyCode::Char(':'), KeyModifiers::SHIFT);
+            assert_eq!(bindings.resolve(&key), Some(Action::JumpToGroup));
+        }
+    }
+
+    #[test]
+    fn test_toggle_columns_bound_in_all_profiles() {
+        for profile in KeybindingProfile::all() {
+            let bindings = KeyBindings::from_profile(*profile);
+            let key = key_press(KeyCode::Char('C'), KeyModifiers::SHIFT);
+            assert_eq!(bindings.resolve(&key), Some(Action::ToggleColumns));
+        }
+    }
+
+    #[test]
+    fn test_undo_delete_bound_in_all_profiles() {
+        for profile in KeybindingProfile::all() {
+            let bindings = KeyBindings::from_profile(*profile);
+            let key = key_press(KeyCode::Char('Z'), KeyModifiers::SHIFT);
+            assert_eq!(bindings.resolve(&key), Some(Action::UndoDelete));
+        }
+    }
+
+    #[test]
+    fn test_select_by_strategy_bound_in_all_profiles() {
+        for profile in KeybindingProfile::all() {
+            let bindings = KeyBindings::from_profile(*profile);
+            let key = key_press(KeyCode::Char('P'), KeyModifiers::SHIFT);
+            assert_eq!(bindings.resolve(&key), Some(Action::SelectByStrategy));
+        }
+    }
+
+    #[test]
+    fn test_mark_as_keeper_bound_in_all_profiles() {
+        for profile in KeybindingProfile::all() {
+            let bindings = KeyBindings::from_profile(*profile);
+            let key = key_press(KeyCode::Char('M'), KeyModifiers::SHIFT);
+            assert_eq!(bindings.resolve(&key), Some(Action::MarkAsKeeper));
+        }
+    }
+
+    #[test]
+    fn test_directory_rollup_bound_in_all_profiles() {
+        for profile in KeybindingProfile::all() {
+            let bindings = KeyBindings::from_profile(*profile);
+            let key = key_press(KeyCode::Char('T'), KeyModifiers::SHIFT);
+            assert_eq!(bindings.resolve(&key), Some(Action::ToggleDirectoryRollup));
+        }
+    }
+
     #[test]
     fn test_universal_delete() {
         let bindings = KeyBindings::from_profile(KeybindingProfile::Universal);
@@ -8,6 +8,7 @@
 //! The TUI module consists of:
 //! - [`app`]: Application state management (modes, navigation, selection)
 //! - [`events`]: Keyboard event handling
+//! - [`clipboard`]: Copying file paths to the system clipboard
 //! - [`ui`]: Ratatui rendering
 //! - [`run_tui`]: Main loop that coordinates everything
 //!
@@ -45,6 +46,7 @@
 //! ```
 
 pub mod app;
+pub mod clipboard;
 pub mod events;
 pub mod keybindings;
 mod run;
@@ -52,8 +54,12 @@ pub mod theme;
 pub mod ui;
 
 // Re-export commonly used types
-pub use app::{Action, App, AppMode, ScanProgress};
+pub use app::{
+    Action, App, AppMode, Column, ColumnPreset, GroupPlan, KeeperStrategy, ReviewStats, RollupRow,
+    ScanProgress, SearchMode, TrashedFile, UndoOutcome, VisibleColumns,
+};
+pub use clipboard::ClipboardError;
 pub use events::{EventError, EventHandler};
 pub use keybindings::{KeyBindings, KeybindingError, KeybindingProfile};
-pub use run::{run_tui, run_tui_with_bindings, TuiError};
+pub use run::{run_scan_tui, run_tui, run_tui_with_bindings, TuiError};
 pub use ui::{format_size, render, truncate_path, truncate_string};
@@ -59,12 +59,19 @@ use crossterm::{
 use ratatui::prelude::*;
 use thiserror::Error;
 
-use super::app::{Action, App, AppMode};
+use super::app::{Action, App, AppMode, TrashedFile};
+use super::clipboard::{clipboard_text, copy_to_clipboard};
 use super::events::EventHandler;
 use super::keybindings::{KeyBindings, KeybindingProfile};
-use super::ui::render;
-use crate::actions::delete::{delete_batch, validate_preserves_copy, DeleteConfig};
-use crate::actions::preview::preview_file_simple;
+use super::ui::{preview_rows, render};
+use crate::actions::cleanup::{candidate_dirs, remove_empty_dirs};
+use crate::actions::delete::{
+    delete_batch, trash_restore_supported, trash_restore_token, validate_preserves_copy,
+    TrashLocation,
+};
+use crate::actions::preview::{diff_files, preview_file, preview_file_simple};
+use crate::actions::rename::plan_keeper_renames;
+use crate::actions::reveal::reveal_in_file_manager;
 use crate::duplicates::ScanSummary;
 use crate::output::HtmlOutput;
 
@@ -204,6 +211,82 @@ pub fn run_tui_with_bindings(
     result
 }
 
+/// Show the scan screen while a scan runs on another thread.
+///
+/// Renders the progress attached with [`App::with_scan_progress`] until
+/// `scan_done` returns `true`. Quitting sets `shutdown_flag` so the scan
+/// stops early; the caller still joins the scan thread.
+///
+/// # Errors
+///
+/// Returns `TuiError::Io` for terminal I/O errors.
+/// Returns `TuiError::Event` for event handling errors.
+pub fn run_scan_tui(
+    app: &mut App,
+    scan_done: impl Fn() -> bool,
+    shutdown_flag: Arc<AtomicBool>,
+    bindings: Option<KeyBindings>,
+) -> TuiResult<()> {
+    let original_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        let _ = restore_terminal();
+        original_hook(panic_info);
+    }));
+
+    let result = run_scan_tui_inner(app, scan_done, &shutdown_flag, bindings);
+
+    let _ = panic::take_hook();
+
+    result
+}
+
+/// Inner loop of [`run_scan_tui`].
+fn run_scan_tui_inner(
+    app: &mut App,
+    scan_done: impl Fn() -> bool,
+    shutdown_flag: &AtomicBool,
+    bindings: Option<KeyBindings>,
+) -> TuiResult<()> {
+    let mut terminal = setup_terminal()?;
+
+    let event_handler = match bindings {
+        Some(b) => EventHandler::with_bindings(b),
+        None => EventHandler::new(),
+    };
+    app.set_keybindings(event_handler.bindings().clone());
+
+    let mut last_render = Instant::now();
+
+    while !scan_done() && !shutdown_flag.load(Ordering::SeqCst) {
+        if app.should_quit() {
+            log::info!("Scan cancelled from the scan screen");
+            shutdown_flag.store(true, Ordering::SeqCst);
+            break;
+        }
+
+        app.sync_scan_progress();
+        terminal.draw(|frame| render(frame, app))?;
+
+        if let Some(crossterm::event::Event::Key(key)) = event_handler.poll_event(POLL_TIMEOUT)? {
+            // Only quitting and pausing apply until results exist
+            if let Some(action @ (Action::Quit | Action::PauseScan | Action::ResumeScan)) =
+                event_handler.translate_key(key)
+            {
+                app.handle_action(action);
+            }
+        }
+
+        let elapsed = last_render.elapsed();
+        if elapsed < FRAME_DURATION {
+            std::thread::sleep(FRAME_DURATION - elapsed);
+        }
+        last_render = Instant::now();
+    }
+
+    restore_terminal()?;
+    Ok(())
+}
+
 /// Inner function that runs the TUI loop.
 ///
 /// This is separated from `run_tui` to ensure cleanup happens correctly.
@@ -243,8 +326,9 @@ fn run_tui_inner(
             break;
         }
 
-        // Render the current state
-        terminal.draw(|frame| render(frame, app))?;
+        // Render the current state; text previews page by the dialog height
+        let area = terminal.draw(|frame| render(frame, app))?.area;
+        app.set_preview_rows(preview_rows(area));
 
         // Poll for events with timeout
         if let Some(crossterm::event::Event::Key(key)) = event_handler.poll_event(POLL_TIMEOUT)? {
@@ -252,8 +336,14 @@ fn run_tui_inner(
                 handle_search_key(app, key);
             } else if app.mode() == AppMode::InputtingExtension
                 || app.mode() == AppMode::InputtingDirectory
+                || app.mode() == AppMode::InputtingExportPath
+                || app.mode() == AppMode::JumpingToGroup
             {
                 handle_input_key(app, key);
+            } else if app.mode() == AppMode::Confirming && app.requires_typed_confirmation() {
+                if let Some(action) = handle_typed_confirmation_key(app, key) {
+                    handle_action(app, action, &shutdown_flag)?;
+                }
             } else if let Some(action) = event_handler.translate_key(key) {
                 handle_action(app, action, &shutdown_flag)?;
             }
@@ -289,7 +379,7 @@ fn handle_action(
     // Handle special actions that require additional processing
     match action {
         Action::Confirm => {
-            if app.mode() == AppMode::Confirming {
+            if app.mode() == AppMode::Confirming && app.is_delete_confirmed() {
                 // Perform the actual deletion
                 let result = perform_deletion(app);
                 match result {
@@ -318,11 +408,78 @@ fn handle_action(
                 }
             }
         }
+        Action::RevealInFileManager => {
+            if was_handled {
+                if let Some(path) = app.current_file().cloned() {
+                    match reveal_in_file_manager(&path) {
+                        Ok(()) => log::info!("Revealed {}", path.display()),
+                        Err(e) => app.set_error(&format!("Reveal failed: {}", e)),
+                    }
+                }
+            }
+        }
+        Action::CopyPath | Action::CopyGroupPaths => {
+            if was_handled {
+                if let Some(paths) = app.clipboard_paths(action) {
+                    match copy_to_clipboard(&clipboard_text(&paths)) {
+                        Ok(()) if paths.len() == 1 => app
+                            .set_error(&format!("Copied {} to the clipboard", paths[0].display())),
+                        Ok(()) => {
+                            app.set_error(&format!("Copied {} paths to the clipboard", paths.len()))
+                        }
+                        Err(e) => app.set_error(&format!("Copy failed: {}", e)),
+                    }
+                }
+            }
+        }
+        Action::UndoDelete => {
+            if was_handled && app.can_undo_deletion() {
+                let config = app.delete_config();
+                let outcome = app.undo_deletion(|file| {
+                    config
+                        .restore(&file.path, &file.location)
+                        .map_err(|e| e.to_string())
+                });
+                if let Some(outcome) = outcome {
+                    match outcome.failures.first() {
+                        None => app.set_error(&format!(
+                            "Restored {} file(s) from the trash",
+                            outcome.restored.len()
+                        )),
+                        Some((path, error)) => app.set_error(&format!(
+                            "Restored {} file(s); {} could not be restored ({}: {})",
+                            outcome.restored.len(),
+                            outcome.failures.len(),
+                            path.display(),
+                            error
+                        )),
+                    }
+                }
+            }
+        }
         Action::Preview => {
             if app.mode() == AppMode::Previewing {
                 // Load preview content for the current file
-                if let Some(path) = app.current_file() {
-                    let content = preview_file_simple(path);
+                // Text files get a pager; other previews are shown as is
+                if let Some(path) = app.current_file().cloned() {
+                    match preview_file(&path, app.size_format()) {
+                        Ok(content) => match content.pager {
+                            Some(pager) => app.set_text_preview(pager),
+                            None => app.set_preview(content.content),
+                        },
+                        Err(_) => app.set_preview(preview_file_simple(&path, app.size_format())),
+                    }
+                }
+            }
+        }
+        Action::DiffWithReference => {
+            if app.mode() == AppMode::Diffing {
+                // Reference file is the old side, the current file the new side
+                if let Some((reference, current)) = app.diff_target() {
+                    let content = match diff_files(&reference, &current, app.size_format()) {
+                        Ok(diff) => diff.content,
+                        Err(e) => format!("Diff error: {}", e),
+                    };
                     app.set_preview(content);
                 }
             }
@@ -344,7 +501,7 @@ fn handle_action(
     Ok(())
 }
 
-/// Handle keyboard input when in input mode (extension or directory).
+/// Handle keyboard input when in input mode (extension, directory, export path or group number).
 fn handle_input_key(app: &mut App, key: crossterm::event::KeyEvent) {
     use crossterm::event::KeyCode;
 
@@ -353,6 +510,8 @@ fn handle_input_key(app: &mut App, key: crossterm::event::KeyEvent) {
     }
 
     match key.code {
+        // Group numbers are digits only
+        KeyCode::Char(c) if app.mode() == AppMode::JumpingToGroup && !c.is_ascii_digit() => {}
         KeyCode::Char(c) => {
             let mut query = app.input_query().to_string();
             query.push(c);
@@ -364,12 +523,28 @@ fn handle_input_key(app: &mut App, key: crossterm::event::KeyEvent) {
             app.set_input_query(query);
         }
         KeyCode::Enter => {
-            if app.mode() == AppMode::InputtingExtension {
+            if app.mode() == AppMode::JumpingToGroup {
+                app.jump_to_group();
+            } else if app.mode() == AppMode::InputtingExtension {
                 app.prepare_select_by_extension();
             } else if app.mode() == AppMode::InputtingDirectory {
                 app.prepare_select_by_directory();
+            } else if app.mode() == AppMode::InputtingExportPath {
+                match perform_export_selection(app) {
+                    Ok((path, count)) => {
+                        log::info!("Exported {} selected path(s) to {}", count, path);
+                        app.clear_input_query();
+                        app.set_mode(AppMode::Reviewing);
+                        app.set_error(&format!("Exported {} path(s) to {}", count, path));
+                    }
+                    // Stay in the prompt so the path can be corrected
+                    Err(e) => app.set_error(&format!("Export failed: {}", e)),
+                }
             }
         }
+        KeyCode::Tab if app.mode() == AppMode::InputtingExportPath => {
+            app.toggle_export_nul_separated();
+        }
         KeyCode::Esc => {
             app.clear_input_query();
             app.set_mode(AppMode::Reviewing);
@@ -378,6 +553,36 @@ fn handle_input_key(app: &mut App, key: crossterm::event::KeyEvent) {
     }
 }
 
+/// Handle keyboard input while a large deletion awaits its typed confirmation.
+///
+/// Characters are collected into the input query; Enter and Esc are returned
+/// as actions so the deletion runs through the normal confirm path.
+fn handle_typed_confirmation_key(app: &mut App, key: crossterm::event::KeyEvent) -> Option<Action> {
+    use crossterm::event::KeyCode;
+
+    if key.kind != crossterm::event::KeyEventKind::Press {
+        return None;
+    }
+
+    match key.code {
+        KeyCode::Char(c) => {
+            let mut query = app.input_query().to_string();
+            query.push(c);
+            app.set_input_query(query);
+            None
+        }
+        KeyCode::Backspace => {
+            let mut query = app.input_query().to_string();
+            query.pop();
+            app.set_input_query(query);
+            None
+        }
+        KeyCode::Enter => Some(Action::Confirm),
+        KeyCode::Esc => Some(Action::Cancel),
+        _ => None,
+    }
+}
+
 /// Handle keyboard input when in search mode.
 fn handle_search_key(app: &mut App, key: crossterm::event::KeyEvent) {
     use crossterm::event::KeyCode;
@@ -400,6 +605,9 @@ fn handle_search_key(app: &mut App, key: crossterm::event::KeyEvent) {
         KeyCode::Enter => {
             app.set_mode(AppMode::Reviewing);
         }
+        KeyCode::Tab => {
+            app.handle_action(Action::ToggleSearchMode);
+        }
         KeyCode::Esc => {
             app.clear_search();
             app.set_mode(AppMode::Reviewing);
@@ -439,16 +647,86 @@ fn perform_deletion(app: &mut App) -> Result<usize, TuiError> {
         }
     }
 
-    // Use trash deletion by default
-    let config = DeleteConfig::trash();
+    // Trash by default; --permanent-under rules may delete some files permanently
+    let config = app.delete_config();
+
+    // Resolve restore tokens while the files still exist
+    let restore_tokens: std::collections::HashMap<&std::path::PathBuf, std::path::PathBuf> =
+        selected_files
+            .iter()
+            .map(|p| (p, trash_restore_token(p)))
+            .collect();
 
     // Perform deletion
     let result = delete_batch(&selected_files, &config, None::<&NoOpProgress>);
 
     // Update app state with deleted files
     let deleted_paths: Vec<_> = result.successes.iter().map(|r| r.path.clone()).collect();
+
+    // Remember trashed files for undo, through the backend each one went to;
+    // permanent deletions cannot be reversed
+    let permanent_count = result.successes.iter().filter(|r| r.permanent).count();
+    let system_restore = trash_restore_supported();
+    let trashed = result
+        .successes
+        .iter()
+        .filter(|r| !r.permanent)
+        .filter_map(|r| {
+            let location = match &r.trashed_to {
+                Some(moved) => TrashLocation::Directory(moved.clone()),
+                None if system_restore => TrashLocation::System(
+                    restore_tokens
+                        .get(&r.path)
+                        .cloned()
+                        .unwrap_or_else(|| r.path.clone()),
+                ),
+                None => return None,
+            };
+            Some(TrashedFile {
+                path: r.path.clone(),
+                location,
+            })
+        })
+        .collect();
+    app.record_deletion(trashed);
+
+    // Pick keepers to rename while the groups still list the deleted files (opt-in)
+    let deleted_set: std::collections::HashSet<&std::path::Path> = deleted_paths
+        .iter()
+        .map(std::path::PathBuf::as_path)
+        .collect();
+    let planned_renames = app
+        .keeper_rename()
+        .map(|rule| plan_keeper_renames(app.groups(), |p| deleted_set.contains(p), rule))
+        .unwrap_or_default();
+
     app.remove_deleted_files(&deleted_paths);
 
+    // Rename keepers; collisions and other failures leave the file untouched
+    if let Some(rule) = app.keeper_rename().cloned() {
+        let mut renamed = Vec::new();
+        for (from, _) in &planned_renames {
+            match rule.apply(from) {
+                Ok(Some(to)) => renamed.push((from.clone(), to)),
+                Ok(None) => {}
+                Err(e) => log::warn!("{}", e),
+            }
+        }
+        if !renamed.is_empty() {
+            log::info!("Renamed {} keepers", renamed.len());
+            app.apply_renames(&renamed);
+        }
+    }
+
+    // Remove directories the deletion left empty (opt-in)
+    if let Some(roots) = app.empty_dir_cleanup_roots() {
+        let candidates = candidate_dirs(&deleted_paths, app.reference_paths());
+        let removed = remove_empty_dirs(&candidates, roots);
+        if !removed.is_empty() {
+            log::info!("Removed {} empty directories", removed.len());
+        }
+    }
+
     // Report any failures
     if !result.failures.is_empty() {
         let (failed_path, error_msg) = &result.failures[0];
@@ -465,6 +743,13 @@ fn perform_deletion(app: &mut App) -> Result<usize, TuiError> {
         }
     }
 
+    if permanent_count > 0 {
+        app.set_error(&format!(
+            "{} file(s) deleted permanently; these cannot be undone",
+            permanent_count
+        ));
+    }
+
     Ok(result.success_count())
 }
 
@@ -533,6 +818,8 @@ fn perform_export(app: &App) -> Result<String, String> {
         duplicate_groups: app.group_count(),
         duplicate_files: app.duplicate_file_count(),
         reclaimable_space: app.reclaimable_space(),
+        size_histogram: crate::duplicates::SizeHistogram::from_groups(&groups),
+        reclaimable_by_extension: crate::duplicates::reclaimable_by_extension(&groups),
         ..Default::default()
     };
 
@@ -551,8 +838,11 @@ fn perform_export(app: &App) -> Result<String, String> {
         return Err("No results to export".to_string());
     }
 
-    // Use default config for report settings
-    let config = crate::config::Config::default();
+    // Use default config for report settings, with the TUI's size units
+    let config = crate::config::Config {
+        size_units: app.size_format(),
+        ..crate::config::Config::default()
+    };
     let html_output = HtmlOutput::new(&groups, &summary, &config);
 
     let path = "rustdupe_export.html";
@@ -562,6 +852,23 @@ fn perform_export(app: &App) -> Result<String, String> {
     Ok(path.to_string())
 }
 
+/// Write the selected paths to the file named in the input prompt.
+///
+/// Returns the destination and the number of paths written.
+fn perform_export_selection(app: &App) -> Result<(String, usize), String> {
+    let path = app.input_query().trim();
+    if path.is_empty() {
+        return Err("No destination file given".to_string());
+    }
+
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    let count = app
+        .export_selection(io::BufWriter::new(file), app.export_nul_separated())
+        .map_err(|e| e.to_string())?;
+
+    Ok((path.to_string(), count))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -579,7 +886,10 @@ mod tests {
 
     #[test]
     fn test_preview_file_simple_nonexistent() {
-        let content = preview_file_simple(std::path::Path::new("/nonexistent/file.txt"));
+        let content = preview_file_simple(
+            std::path::Path::new("/nonexistent/file.txt"),
+            crate::output::SizeFormat::default(),
+        );
         assert!(content.to_lowercase().contains("error") || content.contains("not found"));
     }
 
@@ -598,7 +908,7 @@ mod tests {
             writeln!(file, "Line 3").unwrap();
         }
 
-        let content = preview_file_simple(&temp_path);
+        let content = preview_file_simple(&temp_path, crate::output::SizeFormat::default());
         assert!(content.contains("Line 1"));
         assert!(content.contains("Line 2"));
         assert!(content.contains("Line 3"));
@@ -617,7 +927,7 @@ mod tests {
         // Create empty file
         File::create(&temp_path).unwrap();
 
-        let content = preview_file_simple(&temp_path);
+        let content = preview_file_simple(&temp_path, crate::output::SizeFormat::default());
         assert!(content.contains("empty"));
 
         // Cleanup
@@ -650,11 +960,7 @@ mod tests {
         progress.on_delete_failure(std::path::Path::new("/test"), "error");
 
         // Create a mock result for on_complete
-        let result = crate::actions::delete::BatchDeleteResult {
-            successes: vec![],
-            failures: vec![],
-            bytes_freed: 0,
-        };
+        let result = crate::actions::delete::BatchDeleteResult::default();
         progress.on_complete(&result);
     }
 
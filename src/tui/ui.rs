@@ -21,7 +21,8 @@
 //! }
 //! ```
 
-use bytesize::ByteSize;
+use std::time::{Duration, SystemTime};
+
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -34,7 +35,11 @@ use ratatui::{
     Frame,
 };
 
-use super::app::{App, AppMode};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::app::{App, AppMode, Column, RollupRow, VisibleColumns, TYPED_CONFIRMATION_WORD};
+use crate::duplicates::DuplicateGroup;
+use crate::output::size::SizeFormat;
 
 // ==================== Accessible Mode Helpers ====================
 
@@ -114,6 +119,7 @@ pub fn render(frame: &mut Frame, app: &App) {
     // Render modal dialogs based on mode
     match app.mode() {
         AppMode::Previewing => render_preview_dialog(frame, app, area),
+        AppMode::Diffing => render_diff_dialog(frame, app, area),
         AppMode::Confirming => render_confirm_dialog(frame, app, area),
         AppMode::ConfirmingBulkSelection => render_bulk_selection_confirm_dialog(frame, app, area),
         AppMode::SelectingFolder => render_folder_selection_dialog(frame, app, area),
@@ -132,6 +138,14 @@ pub fn render(frame: &mut Frame, app: &App) {
             "Select by Directory",
             "Enter directory path:",
         ),
+        AppMode::InputtingExportPath => render_export_selection_dialog(frame, app, area),
+        AppMode::JumpingToGroup => render_input_dialog(
+            frame,
+            app,
+            area,
+            "Jump to Group",
+            &format!("Enter group number (1-{}):", app.visible_group_count()),
+        ),
         AppMode::Exporting => render_export_dialog(frame, app, area),
         AppMode::ShowingHelp => render_help_dialog(frame, app, area),
         _ => {}
@@ -143,14 +157,20 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     let dry_run_suffix = if app.is_dry_run() { " [DRY RUN]" } else { "" };
     let title = match app.mode() {
         AppMode::Scanning => format!(
-            "rustdupe - Smart Duplicate Finder{} [Scanning...]",
-            dry_run_suffix
+            "rustdupe - Smart Duplicate Finder{} [{}]",
+            dry_run_suffix,
+            if app.is_scan_paused() {
+                "PAUSED"
+            } else {
+                "Scanning..."
+            }
         ),
         AppMode::Reviewing => format!("rustdupe - Smart Duplicate Finder{}", dry_run_suffix),
         AppMode::Previewing => format!(
             "rustdupe - Smart Duplicate Finder{} [Preview]",
             dry_run_suffix
         ),
+        AppMode::Diffing => format!("rustdupe - Smart Duplicate Finder{} [Diff]", dry_run_suffix),
         AppMode::Confirming => format!(
             "rustdupe - Smart Duplicate Finder{} [Confirm Delete]",
             dry_run_suffix
@@ -173,15 +193,31 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
             dry_run_suffix,
             app.input_query()
         ),
+        AppMode::InputtingExportPath => format!(
+            "rustdupe - Smart Duplicate Finder{} [Export Selection: {}]",
+            dry_run_suffix,
+            app.input_query()
+        ),
         AppMode::ConfirmingBulkSelection => format!(
             "rustdupe - Smart Duplicate Finder{} [Confirm Bulk Selection]",
             dry_run_suffix
         ),
+        AppMode::ReviewingPending => format!(
+            "rustdupe - Smart Duplicate Finder{} [Review Pending: {} marked]",
+            dry_run_suffix,
+            app.pending_selection_count()
+        ),
         AppMode::Searching => format!(
-            "rustdupe - Smart Duplicate Finder{} [Searching: {}]",
+            "rustdupe - Smart Duplicate Finder{} [Searching ({}): {}]",
             dry_run_suffix,
+            app.search_mode().display_name(),
             app.search_query()
         ),
+        AppMode::JumpingToGroup => format!(
+            "rustdupe - Smart Duplicate Finder{} [Jump to Group: {}]",
+            dry_run_suffix,
+            app.input_query()
+        ),
         AppMode::Exporting => {
             format!(
                 "rustdupe - Smart Duplicate Finder{} [Export Results]",
@@ -197,10 +233,14 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     let stats = if app.has_groups() {
         let groups = app.group_count();
         let files = app.duplicate_file_count();
-        let reclaimable = format_size(app.reclaimable_space());
+        let reclaimable = app.size_format().format(app.reclaimable_space());
+        let current = app
+            .current_group_id()
+            .map(|id| format!(" | group {}", id))
+            .unwrap_or_default();
         format!(
-            " | {} groups, {} files, {} reclaimable",
-            groups, files, reclaimable
+            " | {} groups, {} files, {} reclaimable{}",
+            groups, files, reclaimable, current
         )
     } else if app.mode() == AppMode::Scanning {
         let progress = app.scan_progress();
@@ -240,13 +280,17 @@ fn render_content(frame: &mut Frame, app: &App, area: Rect) {
         AppMode::Scanning => render_scanning_content(frame, app, area),
         AppMode::Reviewing
         | AppMode::Previewing
+        | AppMode::Diffing
         | AppMode::Confirming
         | AppMode::ConfirmingBulkSelection
+        | AppMode::ReviewingPending
         | AppMode::SelectingFolder
         | AppMode::SelectingGroup
         | AppMode::InputtingExtension
         | AppMode::InputtingDirectory
+        | AppMode::InputtingExportPath
         | AppMode::Searching
+        | AppMode::JumpingToGroup
         | AppMode::Exporting
         | AppMode::ShowingHelp => render_reviewing_content(frame, app, area),
         AppMode::Quitting => render_quitting_content(frame, app, area),
@@ -267,8 +311,27 @@ fn render_quitting_content(frame: &mut Frame, app: &App, area: Rect) {
 /// The footer hints adapt based on:
 /// - Active keybinding profile (if available from App)
 /// - Platform (Windows shows arrow keys, Linux/macOS shows vim keys)
+///
+/// Once results are available, the footer border also shows the live
+/// selection statistics from [`App::statistics`].
 fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
-    let commands = get_footer_commands(app);
+    let pause_key;
+    let mut commands: Vec<(&str, &str)> = get_footer_commands(app);
+    if app.mode() == AppMode::Scanning && app.can_pause_scan() {
+        // The pause keys can be rebound, so show whichever key is bound
+        let (action, desc) = if app.is_scan_paused() {
+            (crate::tui::Action::ResumeScan, "Resume")
+        } else {
+            (crate::tui::Action::PauseScan, "Pause")
+        };
+        pause_key = app.keybindings().map_or_else(
+            || crate::tui::keybindings::KeyBindings::default().key_hint(&action),
+            |bindings| bindings.key_hint(&action),
+        );
+        if !pause_key.is_empty() {
+            commands.insert(0, (pause_key.as_str(), desc));
+        }
+    }
 
     let spans: Vec<Span> = commands
         .iter()
@@ -295,11 +358,21 @@ fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
         })
         .collect();
 
+    let mut block =
+        create_block(app.is_accessible()).border_style(Style::default().fg(app.theme().dim));
+    if app.has_groups() {
+        block = block.title(
+            Line::from(Span::styled(
+                format!(" {} ", app.statistics()),
+                Style::default().fg(app.theme().normal),
+            ))
+            .alignment(Alignment::Right),
+        );
+    }
+
     let footer = Paragraph::new(Line::from(spans))
         .alignment(Alignment::Center)
-        .block(
-            create_block(app.is_accessible()).border_style(Style::default().fg(app.theme().dim)),
-        );
+        .block(block);
 
     frame.render_widget(footer, area);
 }
@@ -314,15 +387,23 @@ fn render_scanning_content(frame: &mut Frame, app: &App, area: Rect) {
         .constraints([
             Constraint::Length(1), // Phase label
             Constraint::Length(3), // Progress bar
+            Constraint::Length(1), // ETA and throughput
             Constraint::Length(1), // Current path
             Constraint::Min(0),    // Message
         ])
         .split(area);
 
-    // Phase label
-    let phase_text = format!("Phase: {}", progress.phase);
+    // Phase label, flagged while hashing is paused
+    let (phase_text, phase_color) = if app.is_scan_paused() {
+        (
+            format!("Phase: {} - PAUSED", progress.phase),
+            app.theme().secondary,
+        )
+    } else {
+        (format!("Phase: {}", progress.phase), app.theme().normal)
+    };
     let phase = Paragraph::new(phase_text)
-        .style(Style::default().fg(app.theme().normal))
+        .style(Style::default().fg(phase_color))
         .alignment(Alignment::Center);
     frame.render_widget(phase, chunks[0]);
 
@@ -335,6 +416,21 @@ fn render_scanning_content(frame: &mut Frame, app: &App, area: Rect) {
         .label(format!("{}%", percentage));
     frame.render_widget(gauge, chunks[1]);
 
+    // ETA and throughput (throughput only when the byte total is unknown)
+    let throughput = progress.throughput();
+    if throughput > 0.0 {
+        let rate_text = format_scan_rate(
+            progress.eta(),
+            throughput,
+            app.size_format(),
+            app.is_accessible(),
+        );
+        let rate = Paragraph::new(rate_text)
+            .style(Style::default().fg(app.theme().normal))
+            .alignment(Alignment::Center);
+        frame.render_widget(rate, chunks[2]);
+    }
+
     // Current path (truncated)
     let path_text = truncate_path(
         &progress.current_path,
@@ -343,14 +439,41 @@ fn render_scanning_content(frame: &mut Frame, app: &App, area: Rect) {
     let path = Paragraph::new(path_text)
         .style(Style::default().fg(app.theme().dim))
         .alignment(Alignment::Center);
-    frame.render_widget(path, chunks[2]);
+    frame.render_widget(path, chunks[3]);
 
     // Message
     if !progress.message.is_empty() {
         let message = Paragraph::new(progress.message.clone())
             .style(Style::default().fg(app.theme().normal))
             .alignment(Alignment::Center);
-        frame.render_widget(message, chunks[3]);
+        frame.render_widget(message, chunks[4]);
+    }
+}
+
+/// Format the scan ETA and throughput line (e.g. "ETA 00:03:12 · 245.0 MiB/s").
+///
+/// Accessible mode uses an ASCII separator.
+fn format_scan_rate(
+    eta: Option<Duration>,
+    bytes_per_sec: f64,
+    size_format: SizeFormat,
+    accessible: bool,
+) -> String {
+    let rate = format!("{}/s", size_format.format(bytes_per_sec as u64));
+    match eta {
+        Some(eta) => {
+            let secs = eta.as_secs();
+            let separator = if accessible { "-" } else { "·" };
+            format!(
+                "ETA {:02}:{:02}:{:02} {} {}",
+                secs / 3600,
+                (secs % 3600) / 60,
+                secs % 60,
+                separator,
+                rate
+            )
+        }
+        None => rate,
     }
 }
 
@@ -394,13 +517,16 @@ fn render_reviewing_content(frame: &mut Frame, app: &App, area: Rect) {
 fn render_groups_list(frame: &mut Frame, app: &App, area: Rect) {
     let visible_count = app.visible_group_count();
     let selected_group = app.group_index();
+    let columns = app.visible_columns();
+    // Borders and scrollbar
+    let row_width = area.width.saturating_sub(3) as usize;
 
     let items: Vec<ListItem> = (0..visible_count)
         .filter_map(|i| {
             let group = app.visible_group_at(i)?;
-            let size = format_size(group.size);
+            let size = app.size_format().format(group.size);
             let copies = group.files.len();
-            let wasted = format_size(group.wasted_space());
+            let wasted = app.size_format().format(group.wasted_space());
 
             // First file name as label (truncated)
             let label = group
@@ -414,11 +540,40 @@ fn render_groups_list(frame: &mut Frame, app: &App, area: Rect) {
                 })
                 .unwrap_or_else(|| "Unknown".to_string());
 
-            let label = truncate_string(&label, 20);
-
             let is_expanded = app.is_expanded(&group.hash);
             let expand_indicator = if is_expanded { "[-] " } else { "[+] " };
-            let sim_indicator = if group.is_similar {
+            let prefix = format!("{}[{}] ", expand_indicator, i + 1);
+
+            let count_label = if columns.count {
+                format!(" ({} copies)", copies)
+            } else {
+                String::new()
+            };
+            let size_label = if columns.size {
+                format!(" {} - {}", size, wasted)
+            } else {
+                String::new()
+            };
+            let hash_label = if columns.hash {
+                format!(" {}", app.visible_group_id_at(i).unwrap_or_default())
+            } else {
+                String::new()
+            };
+
+            // Hidden columns leave their space to the label
+            let label_width = row_width
+                .saturating_sub(
+                    prefix.width() + count_label.width() + size_label.width() + hash_label.width(),
+                )
+                .max(MIN_GROUP_LABEL_WIDTH);
+            let label = truncate_string(&label, label_width);
+            let partial_label;
+            let sim_indicator = if let Some(similarity) = group.partial_similarity {
+                partial_label = format!(" [PARTIAL {:.0}%]", similarity * 100.0);
+                partial_label.as_str()
+            } else if group.is_name_similar {
+                " [NAME]"
+            } else if group.is_similar {
                 if group.files.first().is_some_and(|f| f.is_image()) {
                     " [SIM-IMG]"
                 } else if group.files.first().is_some_and(|f| f.is_document()) {
@@ -426,19 +581,32 @@ fn render_groups_list(frame: &mut Frame, app: &App, area: Rect) {
                 } else {
                     " [SIM]"
                 }
+            } else if group.is_empty_group {
+                " [EMPTY]"
+            } else {
+                ""
+            };
+            let intentional_indicator = if group.likely_intentional {
+                " [INTENTIONAL]"
+            } else {
+                ""
+            };
+            let pin_indicator = if app.is_pinned(&group.hash) {
+                " [PINNED]"
             } else {
                 ""
             };
 
             let text = format!(
-                "{}[{}] {} ({} copies) {} - {}{}",
-                expand_indicator,
-                i + 1,
+                "{}{}{}{}{}{}{}{}",
+                prefix,
                 label,
-                copies,
-                size,
-                wasted,
-                sim_indicator
+                count_label,
+                size_label,
+                hash_label,
+                sim_indicator,
+                intentional_indicator,
+                pin_indicator
             );
 
             let style = if i == selected_group {
@@ -446,6 +614,14 @@ fn render_groups_list(frame: &mut Frame, app: &App, area: Rect) {
                     .fg(app.theme().inverted_fg)
                     .bg(app.theme().primary)
                     .add_modifier(Modifier::BOLD)
+            } else if group.is_name_similar {
+                // Advisory groups: contents may differ
+                Style::default()
+                    .fg(app.theme().secondary)
+                    .add_modifier(Modifier::ITALIC)
+            } else if group.likely_intentional {
+                // Probably deliberate copies: downranked
+                Style::default().fg(app.theme().dim)
             } else {
                 Style::default().fg(app.theme().normal)
             };
@@ -545,7 +721,10 @@ fn render_files_list(frame: &mut Frame, app: &App, area: Rect) {
             ]),
             Line::from(""),
             Line::from(format!("This group contains {} files", group.files.len())),
-            Line::from(format!("Total size: {}", format_size(group.size))),
+            Line::from(format!(
+                "Total size: {}",
+                app.size_format().format(group.size)
+            )),
         ])
         .alignment(Alignment::Center)
         .block(
@@ -556,17 +735,22 @@ fn render_files_list(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
+    if app.is_rolled_up(group) {
+        render_rollup_list(frame, app, group, area);
+        return;
+    }
+
     let selected_file = app.file_index();
     let max_path_len = area.width.saturating_sub(12) as usize;
+    let columns = app.visible_columns();
 
     let items: Vec<ListItem> = group
         .files
         .iter()
         .enumerate()
         .map(|(i, entry)| {
-            let is_selected = app.is_file_selected(&entry.path);
-            let is_ref = app.is_in_reference_dir(&entry.path);
             let is_first = i == 0;
+            let (prefix, style) = file_marker(app, &entry.path, is_first, i == selected_file);
 
             // Build group label if present
             let group_label = entry
@@ -575,13 +759,18 @@ fn render_files_list(frame: &mut Frame, app: &App, area: Rect) {
                 .map(|g| format!("[{}] ", g))
                 .unwrap_or_default();
 
-            // Adjust max path length to account for prefix and group label
-            let prefix_len = 4; // "[X] " or similar
-            let group_label_len = group_label.len();
-            let available_path_len = max_path_len.saturating_sub(prefix_len + group_label_len);
+            let time_label = if app.absolute_times() {
+                format!("  {}", format_absolute_time(entry.modified))
+            } else {
+                format!("  {}", format_relative_time(entry.modified))
+            };
 
-            let path_str = entry.path.to_string_lossy();
-            let path_display = truncate_path(&path_str, available_path_len);
+            // Name clusters hold different contents, so each file shows its size
+            let size_label = if group.is_name_similar {
+                format!(" [{}]", app.size_format().format(entry.size))
+            } else {
+                String::new()
+            };
 
             let distance_label = if group.is_similar && !is_first {
                 if let (Some(ref h1), Some(ref h2)) =
@@ -600,48 +789,37 @@ fn render_files_list(frame: &mut Frame, app: &App, area: Rect) {
                 String::new()
             };
 
-            let prefix = if is_selected {
-                "[X]"
-            } else if is_ref {
-                "[R]" // Reference marker
-            } else if is_first {
-                "[*]" // Original/keep marker
+            // Adjust max path length to account for prefix, group label and
+            // whichever columns fit
+            let prefix_len = prefix.width() + 1; // "[X] " or similar
+            let layout = compute_column_layout(
+                columns,
+                &[
+                    (Column::Date, time_label.width()),
+                    (Column::Size, size_label.width()),
+                ],
+                max_path_len
+                    .saturating_sub(prefix_len + group_label.width() + distance_label.width()),
+            );
+            let size_label = if layout.shows(Column::Size) {
+                size_label.as_str()
             } else {
-                "[ ]"
+                ""
+            };
+            let time_label = if layout.shows(Column::Date) {
+                time_label.as_str()
+            } else {
+                ""
             };
 
+            let path_str = entry.path.to_string_lossy();
+            let path_display = truncate_path(&path_str, layout.path_width);
+
             let text = format!(
-                "{} {}{}{}",
-                prefix, group_label, path_display, distance_label
+                "{} {}{}{}{}{}",
+                prefix, group_label, path_display, size_label, distance_label, time_label
             );
 
-            let style = if i == selected_file {
-                if is_selected {
-                    Style::default()
-                        .fg(app.theme().inverted_fg)
-                        .bg(app.theme().danger)
-                        .add_modifier(Modifier::BOLD)
-                } else if is_ref {
-                    Style::default()
-                        .fg(app.theme().inverted_fg)
-                        .bg(app.theme().reference)
-                        .add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default()
-                        .fg(app.theme().inverted_fg)
-                        .bg(app.theme().secondary)
-                        .add_modifier(Modifier::BOLD)
-                }
-            } else if is_selected {
-                Style::default().fg(app.theme().danger)
-            } else if is_ref {
-                Style::default().fg(app.theme().reference)
-            } else if is_first {
-                Style::default().fg(app.theme().success) // Original is green
-            } else {
-                Style::default().fg(app.theme().normal)
-            };
-
             ListItem::new(text).style(style)
         })
         .collect();
@@ -659,14 +837,14 @@ fn render_files_list(frame: &mut Frame, app: &App, area: Rect) {
             selected_file + 1,
             group.files.len(),
             selected_count,
-            format_size(app.reclaimable_space())
+            app.size_format().format(app.reclaimable_space())
         )
     } else {
         format!(
             "Files ({}/{}) - {} each",
             selected_file + 1,
             group.files.len(),
-            format_size(group.size)
+            app.size_format().format(group.size)
         )
     };
 
@@ -706,9 +884,274 @@ fn render_files_list(frame: &mut Frame, app: &App, area: Rect) {
     }
 }
 
+/// Narrowest path a file row keeps before optional columns are dropped.
+pub const MIN_PATH_WIDTH: usize = 16;
+
+/// Group labels are never truncated below this width.
+const MIN_GROUP_LABEL_WIDTH: usize = 20;
+
+/// Space given to the path and to each optional column of a list row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnLayout {
+    /// Cells left for the path
+    pub path_width: usize,
+    /// Columns that fit, with their widths, in the order requested
+    pub columns: Vec<(Column, usize)>,
+}
+
+impl ColumnLayout {
+    /// Check if `column` made it into the row.
+    #[must_use]
+    pub fn shows(&self, column: Column) -> bool {
+        self.columns.iter().any(|(c, _)| *c == column)
+    }
+}
+
+/// Lay out a list row `width` cells wide.
+///
+/// `candidates` are the optional columns the row can show, most important
+/// first, with the cells each one needs. Hidden columns are skipped, and a
+/// column is dropped when it would leave the path fewer than
+/// [`MIN_PATH_WIDTH`] cells. Whatever remains goes to the path, so hiding
+/// columns or widening the terminal shows more of it.
+///
+/// # Examples
+///
+/// ```
+/// use rustdupe::tui::app::{Column, ColumnPreset};
+/// use rustdupe::tui::ui::compute_column_layout;
+///
+/// let row = [(Column::Date, 12)];
+/// let layout = compute_column_layout(ColumnPreset::Standard.columns(), &row, 60);
+/// assert_eq!(layout.path_width, 48);
+///
+/// let layout = compute_column_layout(ColumnPreset::Paths.columns(), &row, 60);
+/// assert_eq!(layout.path_width, 60);
+/// ```
+#[must_use]
+pub fn compute_column_layout(
+    visible: VisibleColumns,
+    candidates: &[(Column, usize)],
+    width: usize,
+) -> ColumnLayout {
+    let mut path_width = width;
+    let mut columns = Vec::new();
+    for &(column, cells) in candidates {
+        if visible.contains(column) && path_width >= MIN_PATH_WIDTH + cells {
+            path_width -= cells;
+            columns.push((column, cells));
+        }
+    }
+    ColumnLayout {
+        path_width,
+        columns,
+    }
+}
+
+/// Selection marker and style of a file row.
+///
+/// `is_first` marks the group's first file (kept by default) and
+/// `highlighted` the row under the cursor. Markers are padded to the
+/// display width of the widest one so paths stay aligned.
+fn file_marker(
+    app: &App,
+    path: &std::path::Path,
+    is_first: bool,
+    highlighted: bool,
+) -> (String, Style) {
+    let is_selected = app.is_file_selected(path)
+        || (app.mode() == AppMode::ReviewingPending && app.is_pending(path));
+    let is_ref = app.is_in_reference_dir(path);
+    let is_locked = app.is_policy_protected(path) || app.is_hardlink_protected(path);
+    let is_keeper = app.is_keeper(path);
+    let is_keep_marked = app.is_keep_marked(path);
+    let lock_marker = if app.is_accessible() { "[L]" } else { "[🔒]" };
+
+    let prefix = if is_selected {
+        "[X]"
+    } else if is_ref {
+        "[R]" // Reference marker
+    } else if is_keeper {
+        // Designated keeper (Action::MarkAsKeeper)
+        if app.is_accessible() {
+            "[K]"
+        } else {
+            "[★]"
+        }
+    } else if is_keep_marked {
+        // Marked to keep (Action::MarkKeepInverse)
+        if app.is_accessible() {
+            "[+]"
+        } else {
+            "[✓]"
+        }
+    } else if is_locked {
+        // Protected by --protect policy
+        lock_marker
+    } else if is_first {
+        "[*]" // Original/keep marker
+    } else {
+        "[ ]"
+    };
+
+    let style = if highlighted {
+        if is_selected {
+            Style::default()
+                .fg(app.theme().inverted_fg)
+                .bg(app.theme().danger)
+                .add_modifier(Modifier::BOLD)
+        } else if is_ref || is_locked {
+            Style::default()
+                .fg(app.theme().inverted_fg)
+                .bg(app.theme().reference)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(app.theme().inverted_fg)
+                .bg(app.theme().secondary)
+                .add_modifier(Modifier::BOLD)
+        }
+    } else if is_selected {
+        Style::default().fg(app.theme().danger)
+    } else if is_ref || is_locked {
+        Style::default().fg(app.theme().reference)
+    } else if is_keeper {
+        Style::default()
+            .fg(app.theme().success)
+            .add_modifier(Modifier::BOLD)
+    } else if is_keep_marked {
+        Style::default()
+            .fg(app.theme().success)
+            .add_modifier(Modifier::UNDERLINED)
+    } else if is_first {
+        Style::default().fg(app.theme().success) // Original is green
+    } else {
+        Style::default().fg(app.theme().normal)
+    };
+
+    (pad_to_width(prefix, lock_marker.width()), style)
+}
+
+/// Pad `text` with spaces to `width` terminal columns.
+fn pad_to_width(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(text.width())))
+}
+
+/// Render the current group as a directory rollup: a header per directory
+/// with its file and selection counts, followed by the files inside it.
+fn render_rollup_list(frame: &mut Frame, app: &App, group: &DuplicateGroup, area: Rect) {
+    let rows = app.rollup_rows();
+    let cursor = app.rollup_index();
+    let max_path_len = area.width.saturating_sub(12) as usize;
+    let directories = rows
+        .iter()
+        .filter(|row| matches!(row, RollupRow::Directory { .. }))
+        .count();
+
+    let items: Vec<ListItem> = rows
+        .iter()
+        .enumerate()
+        .map(|(row, item)| match item {
+            RollupRow::Directory { path, count } => {
+                let selected = group
+                    .files
+                    .iter()
+                    .filter(|f| f.path.parent() == Some(path.as_path()))
+                    .filter(|f| app.is_file_selected(&f.path))
+                    .count();
+                let counts = format!(" ({} files, {} selected)", count, selected);
+                let marker = if app.is_accessible() { "+" } else { "▾" };
+                let path_str = path.to_string_lossy();
+                let text = format!(
+                    "{} {}{}",
+                    marker,
+                    truncate_path(&path_str, max_path_len.saturating_sub(counts.len() + 2)),
+                    counts
+                );
+                let style = if row == cursor {
+                    Style::default()
+                        .fg(app.theme().inverted_fg)
+                        .bg(app.theme().secondary)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                        .fg(app.theme().primary)
+                        .add_modifier(Modifier::BOLD)
+                };
+                ListItem::new(text).style(style)
+            }
+            RollupRow::File(i) => {
+                let entry = &group.files[*i];
+                let (prefix, style) = file_marker(app, &entry.path, *i == 0, row == cursor);
+                let name = entry
+                    .path
+                    .file_name()
+                    .map_or_else(|| entry.path.to_string_lossy(), |n| n.to_string_lossy());
+                let text = format!(
+                    "  {} {}",
+                    prefix,
+                    truncate_path(&name, max_path_len.saturating_sub(6))
+                );
+                ListItem::new(text).style(style)
+            }
+        })
+        .collect();
+
+    let visible_height = area.height.saturating_sub(2) as usize;
+    let mut scrollbar_state =
+        ScrollbarState::new(rows.len().saturating_sub(visible_height)).position(app.file_scroll());
+
+    let title = format!(
+        "Files by directory ({} dirs, {} files) - {} each",
+        directories,
+        group.files.len(),
+        app.size_format().format(group.size)
+    );
+    let list = List::new(items).block(
+        create_block_with_title(app.is_accessible(), title)
+            .border_style(Style::default().fg(app.theme().secondary)),
+    );
+
+    let inner_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    frame.render_widget(list, inner_chunks[0]);
+
+    if rows.len() > visible_height {
+        let (begin_sym, end_sym) = if app.is_accessible() {
+            ("^", "v")
+        } else {
+            ("▲", "▼")
+        };
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some(begin_sym))
+                .end_symbol(Some(end_sym)),
+            inner_chunks[1],
+            &mut scrollbar_state,
+        );
+    }
+}
+
 /// Render preview dialog.
+/// Area of the preview dialog on a screen of size `area`.
+fn preview_dialog_area(area: Rect) -> Rect {
+    centered_rect(80, 80, area)
+}
+
+/// Number of text lines the preview dialog shows on a screen of size `area`.
+///
+/// Used as the page size when paging through a text preview.
+#[must_use]
+pub fn preview_rows(area: Rect) -> usize {
+    // The block border takes a row above and below the text
+    usize::from(preview_dialog_area(area).height.saturating_sub(2))
+}
+
 fn render_preview_dialog(frame: &mut Frame, app: &App, area: Rect) {
-    let dialog_area = centered_rect(80, 80, area);
+    let dialog_area = preview_dialog_area(area);
     frame.render_widget(Clear, dialog_area);
 
     let path = app
@@ -721,20 +1164,73 @@ fn render_preview_dialog(frame: &mut Frame, app: &App, area: Rect) {
         .unwrap_or("Loading preview...")
         .to_string();
 
+    let title = if app.is_preview_paged() {
+        format!(
+            "Preview: {} (line {})",
+            truncate_path(&path, 50),
+            app.preview_offset() + 1
+        )
+    } else {
+        format!("Preview: {}", truncate_path(&path, 50))
+    };
+
     let preview = Paragraph::new(content)
         .style(Style::default().fg(app.theme().normal))
         .wrap(Wrap { trim: false })
         .block(
-            create_block_with_title(
-                app.is_accessible(),
-                format!("Preview: {}", truncate_path(&path, 50)),
-            )
-            .border_style(Style::default().fg(app.theme().secondary)),
+            create_block_with_title(app.is_accessible(), title)
+                .border_style(Style::default().fg(app.theme().secondary)),
         );
 
     frame.render_widget(preview, dialog_area);
 }
 
+/// Render the diff pane comparing the current file with its reference file.
+///
+/// Added lines use the theme's success color, removed lines its danger
+/// color and hunk headers its primary color.
+fn render_diff_dialog(frame: &mut Frame, app: &App, area: Rect) {
+    let dialog_area = centered_rect(90, 85, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let theme = app.theme();
+    let title = match app.diff_target() {
+        Some((reference, current)) => format!(
+            "Diff: {} -> {}",
+            truncate_path(&reference.to_string_lossy(), 35),
+            truncate_path(&current.to_string_lossy(), 35)
+        ),
+        None => "Diff".to_string(),
+    };
+
+    let content = app.preview_content().unwrap_or("Computing diff...");
+    let lines: Vec<Line> = content
+        .lines()
+        .map(|line| {
+            let color = if line.starts_with("+++") || line.starts_with("---") {
+                theme.dim
+            } else if line.starts_with("@@") {
+                theme.primary
+            } else if line.starts_with('+') {
+                theme.success
+            } else if line.starts_with('-') {
+                theme.danger
+            } else {
+                theme.normal
+            };
+            Line::from(Span::styled(line.to_string(), Style::default().fg(color)))
+        })
+        .collect();
+
+    let scroll = u16::try_from(app.diff_scroll()).unwrap_or(u16::MAX);
+    let diff = Paragraph::new(lines).scroll((scroll, 0)).block(
+        create_block_with_title(app.is_accessible(), title)
+            .border_style(Style::default().fg(theme.secondary)),
+    );
+
+    frame.render_widget(diff, dialog_area);
+}
+
 /// Render confirmation dialog.
 fn render_confirm_dialog(frame: &mut Frame, app: &App, area: Rect) {
     let dialog_area = centered_rect(60, 40, area);
@@ -742,18 +1238,7 @@ fn render_confirm_dialog(frame: &mut Frame, app: &App, area: Rect) {
 
     let selected_count = app.selected_count();
     let files = app.selected_files_vec();
-    let total_size: u64 = files
-        .iter()
-        .filter_map(|p| {
-            app.groups().iter().find_map(|g| {
-                if g.files.iter().any(|f| &f.path == p) {
-                    Some(g.size)
-                } else {
-                    None
-                }
-            })
-        })
-        .sum();
+    let total_size = app.selected_size();
 
     let text = vec![
         Line::from(Span::styled(
@@ -766,7 +1251,7 @@ fn render_confirm_dialog(frame: &mut Frame, app: &App, area: Rect) {
         Line::from(format!(
             "Delete {} file(s) ({}) to trash?",
             selected_count,
-            format_size(total_size)
+            app.size_format().format(total_size)
         )),
         Line::from(""),
         Line::from(Span::styled(
@@ -779,6 +1264,38 @@ fn render_confirm_dialog(frame: &mut Frame, app: &App, area: Rect) {
 
     let mut lines: Vec<Line> = text;
 
+    let permanent = app.selected_permanent_count();
+    if permanent > 0 {
+        lines.insert(
+            4,
+            Line::from(Span::styled(
+                format!(
+                    "Warning: {} file(s) under --permanent-under will be deleted permanently.",
+                    permanent
+                ),
+                Style::default()
+                    .fg(app.theme().danger)
+                    .add_modifier(Modifier::BOLD),
+            )),
+        );
+    }
+
+    let name_only = app.selected_name_only_count();
+    if name_only > 0 {
+        lines.insert(
+            4,
+            Line::from(Span::styled(
+                format!(
+                    "Warning: {} file(s) matched by name only; contents may differ.",
+                    name_only
+                ),
+                Style::default()
+                    .fg(app.theme().danger)
+                    .add_modifier(Modifier::BOLD),
+            )),
+        );
+    }
+
     // Show first few files
     for (i, file) in files.iter().take(5).enumerate() {
         let path = file.to_string_lossy();
@@ -791,8 +1308,27 @@ fn render_confirm_dialog(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     lines.push(Line::from(""));
+    if app.requires_typed_confirmation() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "Large deletion: type {} to confirm: {}_",
+                TYPED_CONFIRMATION_WORD,
+                app.input_query()
+            ),
+            Style::default()
+                .fg(app.theme().danger)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    let hint = if app.needs_name_similar_confirmation() {
+        "[Enter] Continue (confirm again to delete)    [Esc] Cancel"
+    } else if name_only > 0 {
+        "[Enter] Delete files with different content    [Esc] Cancel"
+    } else {
+        "[Enter] Confirm    [Esc] Cancel"
+    };
     lines.push(Line::from(Span::styled(
-        "[Enter] Confirm    [Esc] Cancel",
+        hint,
         Style::default().fg(app.theme().primary),
     )));
 
@@ -812,21 +1348,29 @@ fn render_bulk_selection_confirm_dialog(frame: &mut Frame, app: &App, area: Rect
     frame.render_widget(Clear, dialog_area);
 
     let count = app.pending_selection_count();
-    let action_type = app
-        .pending_bulk_action()
-        .map(|t| match t {
-            crate::tui::app::BulkSelectionType::AllDuplicates => "all duplicates",
-            crate::tui::app::BulkSelectionType::Oldest => "oldest files",
-            crate::tui::app::BulkSelectionType::Newest => "newest files",
-            crate::tui::app::BulkSelectionType::Smallest => "smallest files",
-            crate::tui::app::BulkSelectionType::Largest => "largest files",
-            crate::tui::app::BulkSelectionType::ByExtension => "files by extension",
-            crate::tui::app::BulkSelectionType::ByDirectory => "files by directory",
-            crate::tui::app::BulkSelectionType::InGroup => "files in group",
-            crate::tui::app::BulkSelectionType::InFolder => "files in folder",
-            crate::tui::app::BulkSelectionType::InNamedGroup => "files in named group",
-        })
-        .unwrap_or("files");
+    let action_type = match app.pending_bulk_action() {
+        Some(crate::tui::app::BulkSelectionType::ByStrategy) => {
+            format!("files (keep {})", app.keeper_strategy().display_name())
+        }
+        other => other
+            .map(|t| match t {
+                crate::tui::app::BulkSelectionType::AllDuplicates => "all duplicates",
+                crate::tui::app::BulkSelectionType::Oldest => "oldest files",
+                crate::tui::app::BulkSelectionType::Newest => "newest files",
+                crate::tui::app::BulkSelectionType::ReviewKeepNewest => "files (keep newest)",
+                crate::tui::app::BulkSelectionType::Smallest => "smallest files",
+                crate::tui::app::BulkSelectionType::Largest => "largest files",
+                crate::tui::app::BulkSelectionType::ByStrategy => "files",
+                crate::tui::app::BulkSelectionType::ByExtension => "files by extension",
+                crate::tui::app::BulkSelectionType::ByDirectory => "files by directory",
+                crate::tui::app::BulkSelectionType::InGroup => "files in group",
+                crate::tui::app::BulkSelectionType::InFolder => "files in folder",
+                crate::tui::app::BulkSelectionType::InNamedGroup => "files in named group",
+                crate::tui::app::BulkSelectionType::KeepComplement => "files not marked to keep",
+            })
+            .unwrap_or("files")
+            .to_string(),
+    };
 
     let text = vec![
         Line::from(Span::styled(
@@ -895,6 +1439,48 @@ fn render_input_dialog(frame: &mut Frame, app: &App, area: Rect, title: &str, pr
     frame.render_widget(input_widget, dialog_area);
 }
 
+/// Render the destination prompt for exporting the selected paths.
+fn render_export_selection_dialog(frame: &mut Frame, app: &App, area: Rect) {
+    let dialog_area = centered_rect(60, 25, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let separator = if app.export_nul_separated() {
+        "NUL (for xargs -0)"
+    } else {
+        "newline"
+    };
+
+    let text = vec![
+        Line::from(format!(
+            "Write {} selected path(s) to file:",
+            app.selected_count()
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(" > {}_", app.input_query()),
+            Style::default()
+                .fg(app.theme().secondary)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(format!("Separator: {}", separator)),
+        Line::from(""),
+        Line::from(Span::styled(
+            "[Tab] Toggle separator    [Enter] Export    [Esc] Cancel",
+            Style::default().fg(app.theme().dim),
+        )),
+    ];
+
+    let input_widget = Paragraph::new(Text::from(text))
+        .alignment(Alignment::Center)
+        .block(
+            create_block_with_title(app.is_accessible(), "Export Selection")
+                .border_style(Style::default().fg(app.theme().primary)),
+        );
+
+    frame.render_widget(input_widget, dialog_area);
+}
+
 /// Render folder selection dialog.
 fn render_folder_selection_dialog(frame: &mut Frame, app: &App, area: Rect) {
     let dialog_area = centered_rect(70, 60, area);
@@ -1005,7 +1591,8 @@ fn render_error_dialog(frame: &mut Frame, app: &App, area: Rect) {
 
 /// Format bytes as human-readable size.
 ///
-/// Uses IEC binary units (KiB, MiB, GiB) via the bytesize crate.
+/// Uses the default IEC binary units (KiB, MiB, GiB); the TUI itself
+/// renders with [`App::size_format`]. See [`crate::output::size`].
 ///
 /// # Examples
 ///
@@ -1017,10 +1604,67 @@ fn render_error_dialog(frame: &mut Frame, app: &App, area: Rect) {
 /// ```
 #[must_use]
 pub fn format_size(bytes: u64) -> String {
-    ByteSize::b(bytes).to_string()
+    SizeFormat::default().format(bytes)
+}
+
+/// Format a modification time as an age relative to now, such as "3 days ago".
+///
+/// Times in the future (e.g. from clock skew) are shown as "just now".
+///
+/// # Examples
+///
+/// ```
+/// use rustdupe::tui::ui::format_relative_time;
+/// use std::time::{Duration, SystemTime};
+///
+/// let t = SystemTime::now() - Duration::from_secs(3 * 24 * 60 * 60 + 30);
+/// assert_eq!(format_relative_time(t), "3 days ago");
+/// assert_eq!(format_relative_time(SystemTime::now()), "just now");
+/// ```
+#[must_use]
+pub fn format_relative_time(t: SystemTime) -> String {
+    format_relative_time_at(t, SystemTime::now())
 }
 
-/// Truncate a string with ellipsis if it exceeds max length.
+/// Format `t` as an age relative to `now`.
+fn format_relative_time_at(t: SystemTime, now: SystemTime) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const WEEK: u64 = 7 * DAY;
+    const MONTH: u64 = 30 * DAY;
+    const YEAR: u64 = 365 * DAY;
+
+    let secs = match now.duration_since(t) {
+        Ok(age) => age.as_secs(),
+        Err(_) => return "just now".to_string(),
+    };
+
+    let (count, unit) = match secs {
+        0 => return "just now".to_string(),
+        s if s < MINUTE => (s, "second"),
+        s if s < HOUR => (s / MINUTE, "minute"),
+        s if s < DAY => (s / HOUR, "hour"),
+        s if s < WEEK => (s / DAY, "day"),
+        s if s < MONTH => (s / WEEK, "week"),
+        s if s < YEAR => (s / MONTH, "month"),
+        s => (s / YEAR, "year"),
+    };
+
+    if count == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", count, unit)
+    }
+}
+
+/// Format a modification time as a full local timestamp.
+fn format_absolute_time(t: SystemTime) -> String {
+    let datetime: chrono::DateTime<chrono::Local> = t.into();
+    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Truncate a string with ellipsis if it exceeds `max_len` display columns.
 ///
 /// # Examples
 ///
@@ -1032,15 +1676,27 @@ pub fn format_size(bytes: u64) -> String {
 /// ```
 #[must_use]
 pub fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+    if s.width() <= max_len {
         s.to_string()
     } else if max_len <= 3 {
         ".".repeat(max_len)
     } else {
-        format!("{}...", &s[..max_len - 3])
+        format!("{}...", take_width(s, max_len - 3))
     }
 }
 
+/// The longest prefix of `s` that fits in `width` display columns.
+fn take_width(s: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, c) in s.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            return &s[..i];
+        }
+    }
+    s
+}
+
 /// Truncate a path with ellipsis, preserving the filename.
 ///
 /// For long paths, keeps the filename visible and truncates the middle.
@@ -1060,7 +1716,7 @@ pub fn truncate_string(s: &str, max_len: usize) -> String {
 /// ```
 #[must_use]
 pub fn truncate_path(path: &str, max_len: usize) -> String {
-    if path.len() <= max_len {
+    if path.width() <= max_len {
         return path.to_string();
     }
 
@@ -1071,11 +1727,11 @@ pub fn truncate_path(path: &str, max_len: usize) -> String {
     // Try to preserve the filename
     let parts: Vec<&str> = path.split(['/', '\\']).collect();
     if let Some(filename) = parts.last() {
-        if filename.len() + 4 <= max_len {
+        if filename.width() + 4 <= max_len {
             // Can fit ".../" + filename
-            let remaining = max_len - filename.len() - 4;
+            let remaining = max_len - filename.width() - 4;
             if remaining > 0 {
-                return format!("{}.../{}", &path[..remaining], filename);
+                return format!("{}.../{}", take_width(path, remaining), filename);
             }
             return format!(".../{}", filename);
         }
@@ -1130,15 +1786,48 @@ fn get_footer_commands(app: &App) -> Vec<(&'static str, &'static str)> {
     match app.mode() {
         AppMode::Scanning => vec![("q", "Quit"), ("", "Press Ctrl+C to cancel scan")],
         AppMode::Reviewing => get_reviewing_commands(app, profile),
-        AppMode::Previewing => vec![("Esc", "Close"), ("q", "Quit")],
+        AppMode::Previewing => vec![
+            (get_nav_hint(profile), "Scroll"),
+            ("PgUp/PgDn", "Page"),
+            ("Esc", "Close"),
+            ("q", "Quit"),
+        ],
+        AppMode::Diffing => vec![
+            (get_nav_hint(profile), "Scroll"),
+            ("Esc", "Close"),
+            ("q", "Quit"),
+        ],
         AppMode::Confirming => vec![("Enter", "Confirm"), ("Esc", "Cancel")],
+        AppMode::ConfirmingBulkSelection
+            if app.pending_bulk_action()
+                == Some(crate::tui::app::BulkSelectionType::ByStrategy) =>
+        {
+            vec![
+                ("Enter", "Apply"),
+                ("P", "Next strategy"),
+                ("Esc", "Cancel"),
+            ]
+        }
         AppMode::ConfirmingBulkSelection => vec![("Enter", "Apply"), ("Esc", "Cancel")],
+        AppMode::ReviewingPending => vec![
+            (get_nav_hint(profile), "Nav"),
+            (get_group_nav_hint(profile), "Grp"),
+            ("Space", "Mark/Keep"),
+            ("Enter", "Apply"),
+            ("Esc", "Cancel"),
+        ],
         AppMode::SelectingFolder => get_folder_selection_commands(profile),
         AppMode::SelectingGroup => get_group_selection_commands(profile),
         AppMode::InputtingExtension | AppMode::InputtingDirectory => {
             vec![("Enter", "Apply"), ("Esc", "Cancel")]
         }
-        AppMode::Searching => vec![("Enter", "Confirm"), ("Esc", "Cancel")],
+        AppMode::JumpingToGroup => vec![("Enter", "Jump"), ("Esc", "Cancel")],
+        AppMode::InputtingExportPath => vec![
+            ("Tab", "Toggle NUL"),
+            ("Enter", "Export"),
+            ("Esc", "Cancel"),
+        ],
+        AppMode::Searching => vec![("Enter", "Confirm"), ("Tab", "Mode"), ("Esc", "Cancel")],
         AppMode::Exporting => vec![
             ("Space", "Toggle Sel-Only"),
             ("Enter", "Export"),
@@ -1344,6 +2033,11 @@ fn get_help_lines_from_bindings<'a>(
         ),
         "Go to top/bottom",
     ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::JumpToGroup),
+        "Jump to group number",
+    ));
 
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
@@ -1373,6 +2067,16 @@ fn get_help_lines_from_bindings<'a>(
         bindings.key_hint(&Action::SelectNewest),
         "Select old/new",
     ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::ReviewKeepNewest),
+        "Keep newest, review the rest",
+    ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::SelectByStrategy),
+        "Select by keeper strategy",
+    ));
     lines.push(format_help_line(
         app,
         bindings.key_hint(&Action::SelectSmallest),
@@ -1412,21 +2116,85 @@ fn get_help_lines_from_bindings<'a>(
         &bindings.key_hint(&Action::Preview),
         "Preview file",
     ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::RevealInFileManager),
+        "Reveal in file manager",
+    ));
+    lines.push(format_help_line(
+        app,
+        bindings.key_hint(&Action::CopyPath),
+        bindings.key_hint(&Action::CopyGroupPaths),
+        "Copy path / group paths",
+    ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::DiffWithReference),
+        "Diff with reference file",
+    ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::ExportSelection),
+        "Export selected paths",
+    ));
     lines.push(format_help_line_single(
         app,
         &bindings.key_hint(&Action::Delete),
         "Delete selected",
     ));
+    // Undo is hidden where trashed files cannot be restored (macOS system trash)
+    if app.delete_config().can_restore() {
+        lines.push(format_help_line_single(
+            app,
+            &bindings.key_hint(&Action::UndoDelete),
+            "Undo last deletion",
+        ));
+    }
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::MarkAsKeeper),
+        "Mark file to keep in group",
+    ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::MarkKeepInverse),
+        "Toggle keep-mark (several per group)",
+    ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::SelectComplementForDeletion),
+        "Select all but keep-marked files",
+    ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::TogglePin),
+        "Pin group (visible in all searches)",
+    ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::ToggleDirectoryRollup),
+        "Group files by directory",
+    ));
     lines.push(format_help_line_single(
         app,
         &bindings.key_hint(&Action::ToggleTheme),
         "Toggle theme",
     ));
+    lines.push(format_help_line_single(
+        app,
+        &bindings.key_hint(&Action::ToggleColumns),
+        "Cycle visible columns",
+    ));
     lines.push(format_help_line_single(
         app,
         &bindings.key_hint(&Action::Search),
         "Filter groups",
     ));
+    lines.push(format_help_line_single(
+        app,
+        "Tab (search)",
+        "Cycle substring / regex / glob",
+    ));
     lines.push(format_help_line_single(
         app,
         &bindings.key_hint(&Action::ShowHelp),
@@ -1467,8 +2235,10 @@ fn get_default_help_lines(app: &App) -> Vec<Line<'static>> {
         format_help_line_static(app, "Tab, S-Tab", "Cycle sort / Reverse"),
         format_help_line_static(app, "v", "Cycle group filter"),
         format_help_line_static(app, "p", "Preview file"),
+        format_help_line_static(app, "Y", "Export selected paths"),
         format_help_line_static(app, "d", "Delete selected"),
         format_help_line_static(app, "t", "Toggle theme"),
+        format_help_line_static(app, "C", "Cycle visible columns"),
         format_help_line_static(app, "/", "Filter groups"),
         format_help_line_static(app, "?/F1", "Show help"),
         format_help_line_static(app, "q", "Quit"),
@@ -1618,7 +2388,7 @@ mod tests {
     fn test_format_size() {
         assert_eq!(format_size(0), "0 B");
         assert_eq!(format_size(512), "512 B");
-        // bytesize uses 1024-based units but may use "KiB" (IEC) or "KB" format
+        // Default units are 1024-based, labeled "KiB" (IEC) or "KB"
         // Test that output contains reasonable size indicators
         let kb = format_size(1024);
         println!("1024 bytes = '{}'", kb);
@@ -1637,6 +2407,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_scan_rate() {
+        let rate = 245.0 * 1024.0 * 1024.0;
+        let eta = Some(Duration::from_secs(3 * 60 + 12));
+        assert_eq!(
+            format_scan_rate(eta, rate, SizeFormat::default(), false),
+            format!("ETA 00:03:12 · {}/s", format_size(rate as u64))
+        );
+        assert!(
+            format_scan_rate(eta, rate, SizeFormat::default(), true).starts_with("ETA 00:03:12 - ")
+        );
+
+        // Unknown total: throughput only
+        assert_eq!(
+            format_scan_rate(None, rate, SizeFormat::default(), false),
+            format!("{}/s", format_size(rate as u64))
+        );
+        assert_eq!(
+            format_scan_rate(None, 1000.0, SizeFormat::Si, false),
+            "1.0 kB/s"
+        );
+    }
+
     #[test]
     fn test_truncate_string() {
         assert_eq!(truncate_string("hello", 10), "hello");
@@ -1682,6 +2475,67 @@ mod tests {
         assert!(truncated.contains(".."));
     }
 
+    #[test]
+    fn test_truncate_uses_display_width() {
+        // Wide and multi-byte characters are measured in columns, not bytes
+        let name = "写真のコピー.jpg";
+        assert_eq!(truncate_string(name, name.width()), name);
+        let truncated = truncate_string(name, 9);
+        assert!(truncated.width() <= 9);
+        assert!(truncated.ends_with("..."));
+
+        let path = "/données/archivé/写真のコピー.jpg";
+        let truncated = truncate_path(path, 24);
+        assert!(truncated.width() <= 24);
+        assert!(truncated.ends_with("写真のコピー.jpg"));
+    }
+
+    #[test]
+    fn test_format_relative_time_boundaries() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let ago = |secs: u64| format_relative_time_at(now - Duration::from_secs(secs), now);
+
+        assert_eq!(ago(0), "just now");
+        assert_eq!(ago(1), "1 second ago");
+        assert_eq!(ago(59), "59 seconds ago");
+        assert_eq!(ago(60), "1 minute ago");
+        assert_eq!(ago(119), "1 minute ago");
+        assert_eq!(ago(59 * 60 + 59), "59 minutes ago");
+        assert_eq!(ago(60 * 60), "1 hour ago");
+        assert_eq!(ago(24 * 60 * 60 - 1), "23 hours ago");
+        assert_eq!(ago(24 * 60 * 60), "1 day ago");
+        assert_eq!(ago(6 * 24 * 60 * 60), "6 days ago");
+        assert_eq!(ago(7 * 24 * 60 * 60 - 1), "6 days ago");
+        assert_eq!(ago(7 * 24 * 60 * 60), "1 week ago");
+        assert_eq!(ago(29 * 24 * 60 * 60), "4 weeks ago");
+        assert_eq!(ago(30 * 24 * 60 * 60), "1 month ago");
+        assert_eq!(ago(364 * 24 * 60 * 60), "12 months ago");
+        assert_eq!(ago(365 * 24 * 60 * 60), "1 year ago");
+        assert_eq!(ago(3 * 365 * 24 * 60 * 60), "3 years ago");
+    }
+
+    #[test]
+    fn test_format_relative_time_future_is_just_now() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let future = now + Duration::from_secs(3600);
+        assert_eq!(format_relative_time_at(future, now), "just now");
+        assert_eq!(
+            format_relative_time(SystemTime::now() + Duration::from_secs(86_400)),
+            "just now"
+        );
+    }
+
+    #[test]
+    fn test_pad_to_width_aligns_wide_markers() {
+        let width = "[🔒]".width();
+        assert_eq!(width, 4);
+        assert_eq!(pad_to_width("[X]", width), "[X] ");
+        assert_eq!(pad_to_width("[🔒]", width), "[🔒]");
+        assert_eq!(pad_to_width("[★]", width).width(), width);
+        // Never truncates
+        assert_eq!(pad_to_width("[L]", 2), "[L]");
+    }
+
     #[test]
     fn test_centered_rect() {
         let area = Rect::new(0, 0, 100, 50);
@@ -1694,6 +2548,56 @@ mod tests {
         assert!(centered.height < area.height);
     }
 
+    #[test]
+    fn test_compute_column_layout_presets() {
+        use crate::tui::app::ColumnPreset;
+
+        let row = [(Column::Date, 15), (Column::Size, 12)];
+
+        // Standard: both columns fit on a wide row
+        let layout = compute_column_layout(ColumnPreset::Standard.columns(), &row, 80);
+        assert_eq!(layout.columns, vec![(Column::Date, 15), (Column::Size, 12)]);
+        assert_eq!(layout.path_width, 53);
+
+        // Compact hides the date, and the path takes its space
+        let layout = compute_column_layout(ColumnPreset::Compact.columns(), &row, 80);
+        assert_eq!(layout.columns, vec![(Column::Size, 12)]);
+        assert_eq!(layout.path_width, 68);
+
+        // Paths only: the whole row goes to the path
+        let layout = compute_column_layout(ColumnPreset::Paths.columns(), &row, 80);
+        assert!(layout.columns.is_empty());
+        assert_eq!(layout.path_width, 80);
+    }
+
+    #[test]
+    fn test_compute_column_layout_narrow_widths() {
+        use crate::tui::app::ColumnPreset;
+
+        let row = [(Column::Date, 15), (Column::Size, 12)];
+        let columns = ColumnPreset::Full.columns();
+
+        // Room for the date but not the size
+        let layout = compute_column_layout(columns, &row, 40);
+        assert!(layout.shows(Column::Date));
+        assert!(!layout.shows(Column::Size));
+        assert_eq!(layout.path_width, 25);
+
+        // Exactly enough for both
+        let layout = compute_column_layout(columns, &row, MIN_PATH_WIDTH + 27);
+        assert_eq!(layout.columns.len(), 2);
+        assert_eq!(layout.path_width, MIN_PATH_WIDTH);
+
+        // Too narrow for any column; a later, smaller column may still fit
+        let layout = compute_column_layout(columns, &row, 30);
+        assert_eq!(layout.columns, vec![(Column::Size, 12)]);
+        assert_eq!(layout.path_width, 18);
+
+        let layout = compute_column_layout(columns, &row, 10);
+        assert!(layout.columns.is_empty());
+        assert_eq!(layout.path_width, 10);
+    }
+
     // Test that render functions don't panic with various app states
     mod render_tests {
         use super::*;
@@ -1733,7 +2637,7 @@ mod tests {
 
         #[test]
         fn test_format_size_integration() {
-            // Verify bytesize integration works
+            // Verify formatting works across magnitudes
             let sizes = [0, 100, 1024, 1_000_000, 1_000_000_000];
             for size in sizes {
                 let formatted = format_size(size);
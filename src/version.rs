@@ -0,0 +1,161 @@
+//! Version and build metadata (`rustdupe version`).
+//!
+//! The crate version comes from Cargo; the git commit, build time, target,
+//! profile and enabled features are recorded by the build script. Values the
+//! build could not determine (e.g. the commit of a build outside a git
+//! checkout) are reported as `null` in JSON.
+//!
+//! # JSON Format
+//!
+//! ```json
+//! {
+//!   "name": "rustdupe",
+//!   "version": "0.3.0",
+//!   "git_commit": "3ac0b70d2f...",
+//!   "build_date": "2026-01-01T00:00:00Z",
+//!   "target": "x86_64-unknown-linux-gnu",
+//!   "profile": "release",
+//!   "features": []
+//! }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use rustdupe::version::BuildInfo;
+//!
+//! let info = BuildInfo::current();
+//! assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+//! println!("{}", info.to_json(false).unwrap());
+//! ```
+
+use std::io::{self, Write};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Version and build metadata of the running binary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildInfo {
+    /// Package name
+    pub name: &'static str,
+    /// Crate version
+    pub version: &'static str,
+    /// Full git commit hash the binary was built from
+    pub git_commit: Option<&'static str>,
+    /// Build time (RFC 3339, UTC)
+    pub build_date: Option<String>,
+    /// Target triple
+    pub target: Option<&'static str>,
+    /// Cargo profile (`debug` or `release`)
+    pub profile: Option<&'static str>,
+    /// Enabled Cargo features
+    pub features: Vec<&'static str>,
+}
+
+/// Treat an unset or empty build variable as unknown.
+fn non_empty(value: Option<&'static str>) -> Option<&'static str> {
+    value.filter(|v| !v.is_empty())
+}
+
+impl BuildInfo {
+    /// Metadata of the running binary.
+    #[must_use]
+    pub fn current() -> Self {
+        let build_date = non_empty(option_env!("RUSTDUPE_BUILD_TIMESTAMP"))
+            .and_then(|s| s.parse::<i64>().ok())
+            .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0))
+            .map(|date| date.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+        Self {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+            git_commit: non_empty(option_env!("RUSTDUPE_GIT_COMMIT")),
+            build_date,
+            target: non_empty(option_env!("RUSTDUPE_BUILD_TARGET")),
+            profile: non_empty(option_env!("RUSTDUPE_BUILD_PROFILE")),
+            features: non_empty(option_env!("RUSTDUPE_FEATURES"))
+                .map(|f| f.split(',').collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Human-readable version line, with the short commit when known.
+    #[must_use]
+    pub fn render(&self) -> String {
+        match self.git_commit {
+            Some(commit) => format!(
+                "{} {} ({})",
+                self.name,
+                self.version,
+                &commit[..commit.len().min(12)]
+            ),
+            None => format!("{} {}", self.name, self.version),
+        }
+    }
+
+    /// Serialize the metadata as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self, pretty: bool) -> serde_json::Result<String> {
+        if pretty {
+            serde_json::to_string_pretty(self)
+        } else {
+            serde_json::to_string(self)
+        }
+    }
+
+    /// Write the metadata as pretty JSON (`json`) or as the version line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W, json: bool) -> io::Result<()> {
+        if json {
+            writeln!(writer, "{}", self.to_json(true)?)
+        } else {
+            writeln!(writer, "{}", self.render())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_json_fields() {
+        let json = BuildInfo::current().to_json(false).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["name"], "rustdupe");
+        assert_eq!(value["version"], env!("CARGO_PKG_VERSION"));
+        for field in ["git_commit", "build_date", "target", "profile", "features"] {
+            assert!(value.get(field).is_some(), "missing field {}", field);
+        }
+        assert!(value["features"].is_array());
+        // The build script always records the build time
+        let date = value["build_date"].as_str().unwrap();
+        assert!(DateTime::parse_from_rfc3339(date).is_ok());
+    }
+
+    #[test]
+    fn test_build_info_render() {
+        let mut info = BuildInfo::current();
+        info.git_commit = Some("0123456789abcdef0123");
+        assert_eq!(
+            info.render(),
+            format!("rustdupe {} (0123456789ab)", env!("CARGO_PKG_VERSION"))
+        );
+        info.git_commit = None;
+        assert_eq!(
+            info.render(),
+            format!("rustdupe {}", env!("CARGO_PKG_VERSION"))
+        );
+
+        let mut out = Vec::new();
+        info.write_to(&mut out, true).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert!(value["git_commit"].is_null());
+    }
+}
@@ -0,0 +1,402 @@
+//! Watch mode: keep a live duplicate report as files change.
+//!
+//! [`DuplicateWatcher`] performs an initial scan of one or more directories,
+//! then subscribes to filesystem notifications via the `notify` crate. Events
+//! are debounced into batches; once a batch settles, only the affected paths
+//! are re-examined and the duplicate groups are recomputed. With a hash cache
+//! configured, unchanged files are served from the cache so only new or
+//! modified files are actually re-hashed.
+//!
+//! Batches that contain only deletions skip hashing entirely and simply drop
+//! the removed files from the existing groups (see [`drop_deleted_files`]).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rustdupe::duplicates::FinderConfig;
+//! use rustdupe::watch::DuplicateWatcher;
+//! use std::path::PathBuf;
+//! use std::time::Duration;
+//!
+//! let mut watcher = DuplicateWatcher::new(
+//!     vec![PathBuf::from("/home/user/Downloads")],
+//!     FinderConfig::default(),
+//!     Duration::from_millis(500),
+//! );
+//! watcher
+//!     .run(|groups, _summary| {
+//!         println!("{} duplicate group(s)", groups.len());
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use notify::{RecursiveMode, Watcher};
+
+use crate::duplicates::{
+    reclaimable_by_extension, DuplicateFinder, DuplicateGroup, FinderConfig, FinderError,
+    ScanSummary, SizeHistogram,
+};
+use crate::scanner::{FileEntry, MultiWalker, Walker, WalkerConfig};
+
+/// Maximum time to block waiting for events before re-checking the shutdown flag.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Errors that can occur in watch mode.
+#[derive(thiserror::Error, Debug)]
+pub enum WatchError {
+    /// The filesystem watcher could not be created or attached.
+    #[error("Filesystem watcher error: {0}")]
+    Notify(#[from] notify::Error),
+
+    /// The duplicate finder failed.
+    #[error(transparent)]
+    Finder(#[from] FinderError),
+
+    /// Writing a batch report failed.
+    #[error("I/O error while reporting results: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Live duplicate detector driven by filesystem notifications.
+pub struct DuplicateWatcher {
+    /// Canonical root directories being watched
+    roots: Vec<PathBuf>,
+    /// Walker configuration used to filter discovered files
+    walker_config: WalkerConfig,
+    /// Finder used to (re)compute duplicate groups
+    finder: DuplicateFinder,
+    /// Quiet period before a batch of events is processed
+    debounce: Duration,
+    /// Shutdown flag shared with the signal handler
+    shutdown_flag: Option<Arc<AtomicBool>>,
+    /// Index of every file currently known to the watcher
+    files: HashMap<PathBuf, FileEntry>,
+    /// Current duplicate groups
+    groups: Vec<DuplicateGroup>,
+}
+
+impl DuplicateWatcher {
+    /// Create a new watcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `roots` - Canonical directories to watch
+    /// * `config` - Finder configuration (walker filters, cache, shutdown flag)
+    /// * `debounce` - Quiet period before a batch of events is processed
+    #[must_use]
+    pub fn new(roots: Vec<PathBuf>, config: FinderConfig, debounce: Duration) -> Self {
+        let walker_config = config
+            .walker_config
+            .clone()
+            .with_include_empty(config.include_empty);
+        let shutdown_flag = config.shutdown_flag.clone();
+        Self {
+            roots,
+            walker_config,
+            finder: DuplicateFinder::new(config),
+            debounce,
+            shutdown_flag,
+            files: HashMap::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    /// Get the current duplicate groups.
+    #[must_use]
+    pub fn groups(&self) -> &[DuplicateGroup] {
+        &self.groups
+    }
+
+    /// Run the initial scan and then watch for changes until shutdown.
+    ///
+    /// `on_batch` is invoked once with the initial results and again after
+    /// every settled batch of filesystem events that changed the results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the watcher cannot be attached, the finder fails,
+    /// or `on_batch` returns an error.
+    pub fn run<F>(&mut self, mut on_batch: F) -> Result<(), WatchError>
+    where
+        F: FnMut(&[DuplicateGroup], &ScanSummary) -> std::io::Result<()>,
+    {
+        // Attach the watcher before the initial scan so no change slips through
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        for root in &self.roots {
+            watcher.watch(root, RecursiveMode::Recursive)?;
+            log::info!("Watching {}", root.display());
+        }
+
+        let Some(summary) = self.initial_scan()? else {
+            return Ok(());
+        };
+        on_batch(&self.groups, &summary)?;
+
+        let poll = POLL_INTERVAL.min(self.debounce);
+        let mut pending: BTreeSet<PathBuf> = BTreeSet::new();
+        let mut last_event: Option<Instant> = None;
+
+        while !self.is_shutdown_requested() {
+            match rx.recv_timeout(poll) {
+                Ok(Ok(event)) => {
+                    log::trace!("Watch event: {:?}", event);
+                    pending.extend(event.paths);
+                    last_event = Some(Instant::now());
+                }
+                Ok(Err(e)) => log::warn!("Watch error: {}", e),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            let settled = last_event.is_some_and(|t| t.elapsed() >= self.debounce);
+            if settled && !pending.is_empty() {
+                let changed: Vec<PathBuf> = std::mem::take(&mut pending).into_iter().collect();
+                last_event = None;
+                log::debug!("Processing batch of {} changed path(s)", changed.len());
+                if let Some(summary) = self.apply_changes(&changed)? {
+                    on_batch(&self.groups, &summary)?;
+                }
+            }
+        }
+
+        log::info!("Watch mode stopped");
+        Ok(())
+    }
+
+    /// Walk all roots, populate the file index and compute the initial groups.
+    ///
+    /// Returns `None` if the scan was interrupted.
+    fn initial_scan(&mut self) -> Result<Option<ScanSummary>, WatchError> {
+        let mut walker = MultiWalker::new(self.roots.clone(), self.walker_config.clone());
+        if let Some(ref flag) = self.shutdown_flag {
+            walker = walker.with_shutdown_flag(flag.clone());
+        }
+
+        for result in walker.walk() {
+            match result {
+                Ok(file) => {
+                    self.files.insert(file.path.clone(), file);
+                }
+                Err(e) => log::warn!("Watch scan error: {}", e),
+            }
+        }
+
+        self.regroup()
+    }
+
+    /// Apply a settled batch of changed paths.
+    ///
+    /// Returns the new summary if the duplicate groups may have changed.
+    fn apply_changes(&mut self, paths: &[PathBuf]) -> Result<Option<ScanSummary>, WatchError> {
+        let mut deleted = Vec::new();
+        let mut updated = false;
+
+        for path in paths {
+            match std::fs::symlink_metadata(path) {
+                Err(_) => {
+                    // Path is gone: drop it and anything that lived beneath it
+                    let removed: Vec<PathBuf> = self
+                        .files
+                        .keys()
+                        .filter(|p| p.starts_with(path))
+                        .cloned()
+                        .collect();
+                    for p in removed {
+                        self.files.remove(&p);
+                        deleted.push(p);
+                    }
+                }
+                Ok(metadata) if metadata.is_dir() => {
+                    // A directory appeared (e.g. moved in); index its contents
+                    let walker = Walker::new(path, self.walker_config.clone());
+                    for file in walker.walk().filter_map(Result::ok) {
+                        updated |= self.upsert(file);
+                    }
+                }
+                Ok(_) => match self.walker_for(path).and_then(|w| w.entry_for_file(path)) {
+                    Some(Ok(file)) => updated |= self.upsert(file),
+                    Some(Err(e)) => log::warn!("Watch error for {}: {}", path.display(), e),
+                    None => {
+                        // Now filtered out (e.g. truncated to zero bytes)
+                        if self.files.remove(path).is_some() {
+                            deleted.push(path.clone());
+                        }
+                    }
+                },
+            }
+        }
+
+        if updated {
+            self.regroup()
+        } else if !deleted.is_empty() {
+            drop_deleted_files(&mut self.groups, &deleted);
+            Ok(Some(self.summary_from_groups()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Insert or refresh a file in the index.
+    ///
+    /// Returns `true` if the file is new or its size/mtime changed.
+    fn upsert(&mut self, file: FileEntry) -> bool {
+        let changed = self
+            .files
+            .get(&file.path)
+            .is_none_or(|old| old.size != file.size || old.modified != file.modified);
+        self.files.insert(file.path.clone(), file);
+        changed
+    }
+
+    /// Build a walker rooted at the watched directory that contains `path`.
+    fn walker_for(&self, path: &std::path::Path) -> Option<Walker> {
+        self.roots
+            .iter()
+            .find(|root| path.starts_with(root))
+            .map(|root| Walker::new(root, self.walker_config.clone()))
+    }
+
+    /// Recompute duplicate groups from the file index.
+    ///
+    /// Returns `None` if the finder was interrupted by shutdown.
+    fn regroup(&mut self) -> Result<Option<ScanSummary>, WatchError> {
+        let files: Vec<FileEntry> = self.files.values().cloned().collect();
+        match self.finder.find_duplicates_from_files(files) {
+            Ok((groups, summary)) => {
+                self.groups = groups;
+                Ok(Some(summary))
+            }
+            Err(FinderError::Interrupted) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Build a summary for the current groups without re-hashing.
+    fn summary_from_groups(&self) -> ScanSummary {
+        ScanSummary {
+            total_files: self.files.len(),
+            total_size: self.files.values().map(|f| f.size).sum(),
+            duplicate_groups: self.groups.len(),
+            duplicate_files: self
+                .groups
+                .iter()
+                .map(DuplicateGroup::duplicate_count)
+                .sum(),
+            total_duplicate_size: self.groups.iter().map(DuplicateGroup::total_size).sum(),
+            reclaimable_space: self.groups.iter().map(DuplicateGroup::wasted_space).sum(),
+            size_histogram: SizeHistogram::from_groups(&self.groups),
+            reclaimable_by_extension: reclaimable_by_extension(&self.groups),
+            ..ScanSummary::default()
+        }
+    }
+
+    /// Check if shutdown has been requested.
+    fn is_shutdown_requested(&self) -> bool {
+        self.shutdown_flag
+            .as_ref()
+            .is_some_and(|f| f.load(Ordering::SeqCst))
+    }
+}
+
+/// Remove deleted files from duplicate groups.
+///
+/// Groups left with fewer than two files are no longer duplicates and are
+/// dropped, mirroring [`App::remove_deleted_files`](crate::tui::App::remove_deleted_files).
+pub fn drop_deleted_files(groups: &mut Vec<DuplicateGroup>, deleted: &[PathBuf]) {
+    let deleted_set: HashSet<&PathBuf> = deleted.iter().collect();
+
+    for group in groups.iter_mut() {
+        group.files.retain(|f| !deleted_set.contains(&f.path));
+    }
+    groups.retain(|g| g.files.len() >= 2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    fn make_file(path: &str, size: u64) -> FileEntry {
+        FileEntry::new(PathBuf::from(path), size, SystemTime::now())
+    }
+
+    #[test]
+    fn test_drop_deleted_files() {
+        let mut groups = vec![
+            DuplicateGroup::new(
+                [0u8; 32],
+                100,
+                vec![make_file("/a.txt", 100), make_file("/b.txt", 100)],
+                Vec::new(),
+            ),
+            DuplicateGroup::new(
+                [1u8; 32],
+                200,
+                vec![
+                    make_file("/c.txt", 200),
+                    make_file("/d.txt", 200),
+                    make_file("/e.txt", 200),
+                ],
+                Vec::new(),
+            ),
+        ];
+
+        drop_deleted_files(
+            &mut groups,
+            &[PathBuf::from("/a.txt"), PathBuf::from("/c.txt")],
+        );
+
+        // First group falls below two files and is dropped
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].files.len(), 2);
+        assert_eq!(groups[0].files[0].path, PathBuf::from("/d.txt"));
+    }
+
+    #[test]
+    fn test_watch_reports_new_duplicate() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("original.txt"), b"duplicate content").unwrap();
+        let root = dir.path().canonicalize().unwrap();
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+
+        let flag = shutdown.clone();
+        let handle = std::thread::spawn(move || {
+            let config = FinderConfig::default().with_shutdown_flag(flag);
+            let mut watcher = DuplicateWatcher::new(vec![root], config, Duration::from_millis(100));
+            watcher.run(|groups, _| {
+                let _ = tx.send(groups.len());
+                Ok(())
+            })
+        });
+
+        // Initial scan: a single file, no duplicates
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), 0);
+
+        fs::write(dir.path().join("copy.txt"), b"duplicate content").unwrap();
+
+        let mut reported = false;
+        while let Ok(count) = rx.recv_timeout(Duration::from_secs(10)) {
+            if count == 1 {
+                reported = true;
+                break;
+            }
+        }
+
+        shutdown.store(true, Ordering::SeqCst);
+        handle.join().unwrap().unwrap();
+        assert!(reported, "new duplicate group was not reported");
+    }
+}
@@ -149,7 +149,7 @@ io_threads = 8
     // 3. CLI flags override environment variables
     let cli = Cli::try_parse_from(["rustdupe", "--theme", "light", "scan", "."]).unwrap();
     config.merge_cli(&cli);
-    if let Commands::Scan(args) = &cli.command {
+    if let Some(Commands::Scan(args)) = &cli.command {
         config.merge_scan_args(args);
     }
     assert_eq!(config.theme, ThemeArg::Light);
@@ -158,7 +158,7 @@ io_threads = 8
     // 4. CLI flag for io_threads overrides config file
     let cli = Cli::try_parse_from(["rustdupe", "scan", ".", "--io-threads", "16"]).unwrap();
     config.merge_cli(&cli);
-    if let Commands::Scan(args) = &cli.command {
+    if let Some(Commands::Scan(args)) = &cli.command {
         config.merge_scan_args(args);
     }
     assert_eq!(config.io_threads, 16);
@@ -181,7 +181,7 @@ fn test_boolean_overrides() {
 
     // CLI: --no-follow-symlinks should override config
     let cli = Cli::try_parse_from(["rustdupe", "scan", ".", "--no-follow-symlinks"]).unwrap();
-    if let Commands::Scan(args) = &cli.command {
+    if let Some(Commands::Scan(args)) = &cli.command {
         config.merge_scan_args(args);
     }
     assert!(!config.follow_symlinks);
@@ -190,12 +190,61 @@ fn test_boolean_overrides() {
     let mut config2 = Config::default();
     assert!(!config2.follow_symlinks);
     let cli = Cli::try_parse_from(["rustdupe", "scan", ".", "--follow-symlinks"]).unwrap();
-    if let Commands::Scan(args) = &cli.command {
+    if let Some(Commands::Scan(args)) = &cli.command {
         config2.merge_scan_args(args);
     }
     assert!(config2.follow_symlinks);
 }
 
+#[test]
+fn test_io_buffer_size_flag_overrides_configured_read_buffer() {
+    let _lock = ENV_MUTEX.lock().unwrap();
+    clear_env();
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    fs::write(&config_path, "read_buffer_size = 1048576").unwrap();
+    let mut config = Config::load_from_path(config_path, None);
+    assert_eq!(config.read_buffer_size, Some(1_048_576));
+
+    let cli = Cli::try_parse_from(["rustdupe", "scan", ".", "--io-buffer-size", "65536"]).unwrap();
+    if let Some(Commands::Scan(args)) = &cli.command {
+        config.merge_scan_args(args);
+    }
+    assert_eq!(config.io_buffer_size, Some(65_536));
+    assert_eq!(config.read_buffer_size, None);
+}
+
+#[test]
+fn test_relative_modified_window_resolves_to_absolute_times() {
+    let _lock = ENV_MUTEX.lock().unwrap();
+    clear_env();
+
+    let before = chrono::Utc::now();
+    let mut config = Config::default();
+    let cli = Cli::try_parse_from([
+        "rustdupe",
+        "scan",
+        ".",
+        "--modified-within",
+        "7d",
+        "--modified-before",
+        "12h",
+    ])
+    .unwrap();
+    if let Some(Commands::Scan(args)) = &cli.command {
+        config.merge_scan_args(args);
+    }
+    let after = chrono::Utc::now();
+
+    let newer = config.newer_than.unwrap();
+    assert!(newer >= before - chrono::Duration::days(7));
+    assert!(newer <= after - chrono::Duration::days(7));
+    let older = config.older_than.unwrap();
+    assert!(older >= before - chrono::Duration::hours(12));
+    assert!(older <= after - chrono::Duration::hours(12));
+}
+
 #[test]
 fn test_output_override() {
     let _lock = ENV_MUTEX.lock().unwrap();
@@ -210,7 +259,7 @@ fn test_output_override() {
 
     // CLI: --output csv should override
     let cli = Cli::try_parse_from(["rustdupe", "scan", ".", "--output", "csv"]).unwrap();
-    if let Commands::Scan(args) = &cli.command {
+    if let Some(Commands::Scan(args)) = &cli.command {
         config.merge_scan_args(args);
     }
     assert_eq!(config.output, OutputFormat::Csv);
@@ -177,3 +177,50 @@ fn test_scan_same_path_twice() {
 
     assert_eq!(summary.total_files, 1);
 }
+
+#[test]
+fn test_paths_from_stdin_keeps_first_listed_path_as_reference() {
+    use std::process::{Command, Stdio};
+
+    let root = tempdir().unwrap();
+    let reference = root.path().join("b_reference");
+    let other = root.path().join("a_other");
+    fs::create_dir(&reference).unwrap();
+    fs::create_dir(&other).unwrap();
+    fs::write(reference.join("original.txt"), b"same content").unwrap();
+    fs::write(other.join("copy.txt"), b"same content").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rustdupe"))
+        .args([
+            "scan",
+            "--paths-from",
+            "-",
+            "--output",
+            "keepers",
+            "--no-cache",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let list = format!("{}\n{}\n", reference.display(), other.display());
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(list.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let report = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        report.contains("# 1 group(s), 1 kept, 1 deleted"),
+        "{report}"
+    );
+    assert!(report
+        .lines()
+        .any(|l| l.starts_with("  KEEP:") && l.ends_with("original.txt")));
+    assert!(report
+        .lines()
+        .any(|l| l.starts_with("  DELETE:") && l.ends_with("copy.txt")));
+}
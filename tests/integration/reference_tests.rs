@@ -240,3 +240,79 @@ fn test_first_path_is_reference_in_multi_path_mode() {
     assert!(group.is_in_reference_dir(&entry1.path));
     assert!(!group.is_in_reference_dir(&entry2.path));
 }
+
+#[test]
+fn test_reference_from_list_protects_listed_dirs() {
+    use std::process::Command;
+
+    let root = tempdir().unwrap();
+    let data = root.path().join("data");
+    let refs_a = root.path().join("refs_a");
+    let refs_b = root.path().join("refs_b");
+    for dir in [&data, &refs_a, &refs_b] {
+        fs::create_dir(dir).unwrap();
+    }
+    fs::write(refs_a.join("alpha.txt"), b"alpha content").unwrap();
+    fs::write(data.join("alpha_copy.txt"), b"alpha content").unwrap();
+    fs::write(refs_b.join("beta.txt"), b"beta content").unwrap();
+    fs::write(data.join("beta_copy.txt"), b"beta content").unwrap();
+
+    let list = root.path().join("references.txt");
+    fs::write(
+        &list,
+        format!("{}\n\n{}\n", refs_a.display(), refs_b.display()),
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustdupe"))
+        .arg("scan")
+        .arg(root.path())
+        .arg("--reference-from")
+        .arg(&list)
+        // Also given inline: the combined set is deduplicated
+        .arg("--reference")
+        .arg(&refs_a)
+        .args(["--output", "keepers", "--no-cache"])
+        .output()
+        .unwrap();
+    let report = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{report}");
+    assert!(
+        report.contains("# 2 group(s), 2 kept, 2 deleted"),
+        "{report}"
+    );
+    for kept in ["alpha.txt", "beta.txt"] {
+        assert!(
+            report
+                .lines()
+                .any(|l| l.starts_with("  KEEP:") && l.ends_with(kept)),
+            "{report}"
+        );
+    }
+
+    // A missing entry fails like a missing --reference
+    let missing = root.path().join("gone");
+    fs::write(
+        &list,
+        format!("{}\n{}\n", refs_a.display(), missing.display()),
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rustdupe"))
+        .arg("scan")
+        .arg(root.path())
+        .arg("--reference-from")
+        .arg(&list)
+        .args(["--output", "keepers", "--no-cache"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!(
+            "Reference path does not exist: {}",
+            missing.display()
+        )),
+        "{stderr}"
+    );
+}
@@ -108,6 +108,10 @@ fn test_load_referencing_deleted_files_integration() {
         files: vec![rustdupe::scanner::FileEntry::new(file_path.clone(), 7, now)],
         reference_paths: Vec::new(),
         is_similar: false,
+        is_empty_group: false,
+        is_name_similar: false,
+        partial_similarity: None,
+        likely_intentional: false,
     };
 
     let session = Session::new(
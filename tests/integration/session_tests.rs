@@ -90,6 +90,10 @@ fn test_session_load_with_missing_files() {
         )],
         reference_paths: Vec::new(),
         is_similar: false,
+        is_empty_group: false,
+        is_name_similar: false,
+        partial_similarity: None,
+        likely_intentional: false,
     };
 
     let session = Session::new(
@@ -152,3 +156,123 @@ fn test_session_app_integration() {
     assert_eq!(app.group_index(), 0);
     assert_eq!(app.file_index(), 1);
 }
+
+#[test]
+fn test_since_session_suppresses_only_decided_unchanged_groups() {
+    let dir = tempdir().unwrap();
+    let scan_path = dir.path().to_path_buf();
+    for (name, content) in [
+        ("a1.txt", "alpha"),
+        ("a2.txt", "alpha"),
+        ("b1.txt", "beta!"),
+        ("b2.txt", "beta!"),
+        ("c1.txt", "gamma"),
+        ("c2.txt", "gamma"),
+    ] {
+        fs::write(scan_path.join(name), content).unwrap();
+    }
+
+    // Prior run: decisions recorded for the alpha and beta groups only
+    let finder = DuplicateFinder::with_defaults();
+    let (groups, _) = finder.find_duplicates(&scan_path).unwrap();
+    assert_eq!(groups.len(), 3);
+    let hash_of = |name: &str| {
+        groups
+            .iter()
+            .find(|g| g.files.iter().any(|f| f.path.ends_with(name)))
+            .unwrap()
+            .hash
+    };
+    let (alpha, beta, gamma) = (hash_of("a1.txt"), hash_of("b1.txt"), hash_of("c1.txt"));
+
+    let session_groups = groups
+        .iter()
+        .enumerate()
+        .map(|(i, g)| SessionGroup::from_duplicate_group(g, i))
+        .collect();
+    let mut session = Session::new(
+        vec![scan_path.clone()],
+        SessionSettings::default(),
+        session_groups,
+    );
+    for name in ["a2.txt", "b2.txt"] {
+        let path = groups
+            .iter()
+            .flat_map(|g| &g.files)
+            .find(|f| f.path.ends_with(name))
+            .unwrap()
+            .path
+            .clone();
+        session.user_selections.insert(path);
+    }
+    // Saved outside the scanned tree so the rescan does not pick it up
+    let session_dir = tempdir().unwrap();
+    let session_path = session_dir.path().join("prior.json");
+    session.save(&session_path).unwrap();
+    let prior = Session::load(&session_path).unwrap();
+
+    // Current run: beta gains a member and a brand new group appears
+    fs::write(scan_path.join("b3.txt"), "beta!").unwrap();
+    fs::write(scan_path.join("d1.txt"), "delta").unwrap();
+    fs::write(scan_path.join("d2.txt"), "delta").unwrap();
+    let (mut current, _) = finder.find_duplicates(&scan_path).unwrap();
+    assert_eq!(current.len(), 4);
+
+    let suppressed = prior.suppress_decided(&mut current);
+
+    assert_eq!(suppressed.len(), 1);
+    assert_eq!(suppressed[0].hash, alpha);
+    let remaining: Vec<_> = current.iter().map(|g| g.hash).collect();
+    assert_eq!(remaining.len(), 3);
+    assert!(remaining.contains(&beta));
+    assert!(remaining.contains(&gamma));
+    assert!(!remaining.contains(&alpha));
+}
+
+#[test]
+fn test_stats_command_reads_saved_session() {
+    use std::process::Command;
+
+    let dir = tempdir().unwrap();
+    let scan_path = dir.path().join("scan");
+    fs::create_dir(&scan_path).unwrap();
+    fs::write(scan_path.join("photo.jpg"), vec![7u8; 4096]).unwrap();
+    fs::write(scan_path.join("photo copy.jpg"), vec![7u8; 4096]).unwrap();
+    fs::write(scan_path.join("notes.txt"), "same notes").unwrap();
+    fs::write(scan_path.join("notes.bak"), "same notes").unwrap();
+    fs::write(scan_path.join("notes.old"), "same notes").unwrap();
+
+    let finder = DuplicateFinder::with_defaults();
+    let (groups, _) = finder.find_duplicates(&scan_path).unwrap();
+    assert_eq!(groups.len(), 2);
+    let session_groups = groups
+        .iter()
+        .enumerate()
+        .map(|(i, g)| SessionGroup::from_duplicate_group(g, i))
+        .collect();
+    let session = Session::new(
+        vec![scan_path.clone()],
+        SessionSettings::default(),
+        session_groups,
+    );
+    let session_path = dir.path().join("session.json");
+    session.save(&session_path).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustdupe"))
+        .arg("stats")
+        .arg(&session_path)
+        .args(["--output", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["groups"], 2);
+    assert_eq!(stats["files"], 5);
+    assert_eq!(stats["reclaimable_space"], 4096 + 2 * 10);
+    // The image group frees the most space, so it leads both breakdowns
+    assert_eq!(stats["largest_groups"][0]["reclaimable_space"], 4096);
+    assert_eq!(stats["largest_groups"][1]["files"], 3);
+    assert_eq!(stats["by_category"][0]["category"], "images");
+    assert_eq!(stats["by_category"][0]["reclaimable_space"], 4096);
+}
@@ -249,3 +249,51 @@ fn test_batch_selection_respects_references() {
     assert!(app.is_file_selected(&file3.path));
     assert!(!app.is_file_selected(&file2.path));
 }
+
+#[test]
+fn test_ignore_case_paths_matches_references_across_case() {
+    use rustdupe::scanner::{path_key_with_case, paths_equal_normalized_with_case};
+
+    assert!(paths_equal_normalized_with_case(
+        Path::new("/Share/Foo.txt"),
+        Path::new("/share/foo.txt"),
+        true
+    ));
+    assert!(paths_equal_normalized_with_case(
+        Path::new("/Share/CAFE\u{0301}.txt"),
+        Path::new("/share/café.txt"),
+        true
+    ));
+    assert!(!paths_equal_normalized_with_case(
+        Path::new("/share/foo.txt"),
+        Path::new("/share/bar.txt"),
+        true
+    ));
+    assert!(!paths_equal_normalized_with_case(
+        Path::new("/Share/Foo.txt"),
+        Path::new("/share/foo.txt"),
+        false
+    ));
+    assert_eq!(
+        path_key_with_case(Path::new("/Share/Foo.txt"), true),
+        path_key_with_case(Path::new("/share/foo.txt"), true)
+    );
+
+    let mut groups = vec![make_group_with_refs(
+        1000,
+        vec!["/data/file1.txt", "/Ref/File1_copy.txt"],
+        vec!["/ref"],
+    )];
+    if !cfg!(windows) {
+        assert!(!groups[0].is_in_reference_dir(Path::new("/Ref/File1_copy.txt")));
+    }
+    groups[0].ignore_case_paths = true;
+    assert!(groups[0].is_in_reference_dir(Path::new("/Ref/File1_copy.txt")));
+
+    let mut app = App::with_groups(groups)
+        .with_reference_paths(vec![PathBuf::from("/ref")])
+        .with_ignore_case_paths(true);
+    app.select_all_in_group();
+    assert!(app.is_protected(Path::new("/Ref/File1_copy.txt")));
+    assert!(!app.is_file_selected(&PathBuf::from("/Ref/File1_copy.txt")));
+}